@@ -0,0 +1,28 @@
+//! Scan throughput on a synthetic React/Next.js repo.
+//!
+//! `cargo bench` runs a few repo sizes; `baseline bench --files N` runs one
+//! size and also breaks the cost down per rule.
+
+use code_baseline::bench::{self, BenchOptions};
+
+fn main() {
+    for files in [100, 500, 2000] {
+        let options = BenchOptions {
+            files,
+            iterations: 5,
+        };
+        match bench::run(&options) {
+            Ok(report) => println!(
+                "scan/{:<5} best {:>8.1}ms  mean {:>8.1}ms  {:>8.0} files/sec",
+                files,
+                report.best.as_secs_f64() * 1000.0,
+                report.mean.as_secs_f64() * 1000.0,
+                report.files_per_sec()
+            ),
+            Err(e) => {
+                eprintln!("scan/{}: {}", files, e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
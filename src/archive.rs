@@ -0,0 +1,233 @@
+use crate::scan::ScanResult;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use tempfile::TempDir;
+
+/// Archive formats `scan --archive` can read, by the tool that unpacks them.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArchiveKind {
+    /// `.zip` (and `.jar`, `.vsix`, `.xpi`, which are zips), read with `unzip`.
+    Zip,
+    /// `.tar`, `.tgz`, `.tar.gz`, `.tar.bz2`, `.tar.xz`, read with `tar`.
+    Tar,
+}
+
+impl ArchiveKind {
+    /// The kind of `path`, from its extension.
+    pub fn detect(path: &Path) -> Option<Self> {
+        let name = path.file_name()?.to_str()?.to_ascii_lowercase();
+        if [".zip", ".jar", ".vsix", ".xpi"].iter().any(|ext| name.ends_with(ext)) {
+            Some(ArchiveKind::Zip)
+        } else if [".tar", ".tgz", ".tar.gz", ".tar.bz2", ".tbz2", ".tar.xz", ".txz"]
+            .iter()
+            .any(|ext| name.ends_with(ext))
+        {
+            Some(ArchiveKind::Tar)
+        } else {
+            None
+        }
+    }
+
+    fn tool(self) -> &'static str {
+        match self {
+            ArchiveKind::Zip => "unzip",
+            ArchiveKind::Tar => "tar",
+        }
+    }
+
+    /// Whether the tool that unpacks this kind can be run.
+    fn tool_available(self) -> bool {
+        let version_flag = match self {
+            ArchiveKind::Zip => "-v",
+            ArchiveKind::Tar => "--version",
+        };
+        Command::new(self.tool())
+            .arg(version_flag)
+            .stdin(Stdio::null())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status()
+            .is_ok()
+    }
+}
+
+#[derive(Debug)]
+pub enum ArchiveError {
+    UnknownFormat(PathBuf),
+    NotFound(PathBuf),
+    ToolNotFound(&'static str),
+    ExtractFailed(String),
+}
+
+impl fmt::Display for ArchiveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ArchiveError::UnknownFormat(path) => write!(
+                f,
+                "unsupported archive '{}' (expected .zip, .tar, .tgz, .tar.gz, .tar.bz2 or .tar.xz)",
+                path.display()
+            ),
+            ArchiveError::NotFound(path) => write!(f, "archive '{}' not found", path.display()),
+            ArchiveError::ToolNotFound(tool) => write!(
+                f,
+                "--archive unpacks archives with `{}`, which is not installed or not in PATH; \
+                 install it, or unpack the archive yourself and scan the directory",
+                tool
+            ),
+            ArchiveError::ExtractFailed(msg) => write!(f, "failed to extract archive: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ArchiveError {}
+
+/// An archive unpacked into a temporary directory, which is removed on drop.
+///
+/// Archives are unpacked with the system's `unzip` or `tar` rather than read
+/// in place, so the scan walks them like any other directory.
+pub struct ExtractedArchive {
+    archive: PathBuf,
+    root: PathBuf,
+    /// Removes `root` on drop.
+    _dir: TempDir,
+}
+
+impl ExtractedArchive {
+    /// Unpack `archive` with `unzip` or `tar`. Both refuse entries that would
+    /// land outside the directory (absolute paths, `..`).
+    pub fn extract(archive: &Path) -> Result<Self, ArchiveError> {
+        let kind = ArchiveKind::detect(archive).ok_or_else(|| ArchiveError::UnknownFormat(archive.to_path_buf()))?;
+        if !archive.is_file() {
+            return Err(ArchiveError::NotFound(archive.to_path_buf()));
+        }
+        if !kind.tool_available() {
+            return Err(ArchiveError::ToolNotFound(kind.tool()));
+        }
+        // A fresh, randomly named directory only this user can read; an
+        // existing path is never reused
+        let mut builder = tempfile::Builder::new();
+        builder.prefix("baseline-archive-");
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            builder.permissions(std::fs::Permissions::from_mode(0o700));
+        }
+        let dir = builder
+            .tempdir()
+            .map_err(|e| ArchiveError::ExtractFailed(e.to_string()))?;
+        // Owns the directory from here, so a failed extraction cleans up too
+        let extracted = Self {
+            archive: archive.to_path_buf(),
+            root: dir.path().to_path_buf(),
+            _dir: dir,
+        };
+
+        let mut command = Command::new(kind.tool());
+        match kind {
+            ArchiveKind::Zip => command.arg("-qq").arg(archive).arg("-d").arg(&extracted.root),
+            ArchiveKind::Tar => command.arg("-xf").arg(archive).arg("-C").arg(&extracted.root),
+        };
+        let output = command
+            .stdin(Stdio::null())
+            .output()
+            .map_err(|_| ArchiveError::ToolNotFound(kind.tool()))?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(ArchiveError::ExtractFailed(format!("{} {}", kind.tool(), stderr.trim())));
+        }
+        Ok(extracted)
+    }
+
+    /// Directory holding the archive's contents; scan this path.
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// How a file in the archive is shown: `dist.zip!/assets/app.js`. `path`
+    /// is under [`root`](Self::root), or relative to it as file-presence
+    /// rules report; other paths are returned unchanged.
+    pub fn label(&self, path: &Path) -> PathBuf {
+        let inner = match path.strip_prefix(&self.root) {
+            Ok(inner) => inner,
+            Err(_) if path.is_relative() && self.root.join(path).exists() => path,
+            Err(_) => return path.to_path_buf(),
+        };
+        PathBuf::from(format!("{}!/{}", self.archive.display(), inner.display()))
+    }
+
+    /// Replace temporary paths in `result` with [`label`](Self::label)s, so
+    /// reports name files inside the archive.
+    pub fn relabel(&self, result: &mut ScanResult) {
+        for v in &mut result.violations {
+            v.file = self.label(&v.file);
+        }
+        for path in &mut result.scanned_files {
+            *path = self.label(path);
+        }
+        result.file_hashes = result
+            .file_hashes
+            .drain()
+            .map(|(path, hash)| (self.label(&path), hash))
+            .collect();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn detects_kind_from_extension() {
+        assert_eq!(ArchiveKind::detect(Path::new("dist.zip")), Some(ArchiveKind::Zip));
+        assert_eq!(ArchiveKind::detect(Path::new("ext.VSIX")), Some(ArchiveKind::Zip));
+        assert_eq!(ArchiveKind::detect(Path::new("pkg-1.0.0.tgz")), Some(ArchiveKind::Tar));
+        assert_eq!(ArchiveKind::detect(Path::new("out/release.tar.gz")), Some(ArchiveKind::Tar));
+        assert_eq!(ArchiveKind::detect(Path::new("dist")), None);
+        assert!(matches!(
+            ExtractedArchive::extract(Path::new("dist.rar")),
+            Err(ArchiveError::UnknownFormat(_))
+        ));
+    }
+
+    #[test]
+    fn extracts_tar_and_labels_paths_inside_it() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("package/dist")).unwrap();
+        fs::write(dir.path().join("package/dist/index.js"), "console.log(1)").unwrap();
+        let archive = dir.path().join("pkg.tgz");
+        let status = Command::new("tar")
+            .arg("-czf")
+            .arg(&archive)
+            .arg("-C")
+            .arg(dir.path())
+            .arg("package")
+            .status()
+            .unwrap();
+        assert!(status.success());
+
+        let extracted = ExtractedArchive::extract(&archive).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = fs::metadata(extracted.root()).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+        let file = extracted.root().join("package/dist/index.js");
+        assert_eq!(fs::read_to_string(&file).unwrap(), "console.log(1)");
+        assert_eq!(
+            extracted.label(&file),
+            PathBuf::from(format!("{}!/package/dist/index.js", archive.display()))
+        );
+        assert_eq!(
+            extracted.label(Path::new("package/dist")),
+            PathBuf::from(format!("{}!/package/dist", archive.display()))
+        );
+        assert_eq!(extracted.label(Path::new("README.md")), PathBuf::from("README.md"));
+
+        let root = extracted.root().to_path_buf();
+        drop(extracted);
+        assert!(!root.exists());
+    }
+}
@@ -0,0 +1,289 @@
+use crate::rules::{factory, ScanContext};
+use crate::scan::{self, ScanError};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// Presets the benchmark config extends: the heaviest commonly used set.
+pub const BENCH_PRESETS: &[&str] = &["react", "nextjs-best-practices"];
+
+#[derive(Debug)]
+pub enum BenchError {
+    Io(std::io::Error),
+    Scan(ScanError),
+}
+
+impl fmt::Display for BenchError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BenchError::Io(e) => write!(f, "failed to write benchmark repo: {}", e),
+            BenchError::Scan(e) => write!(f, "benchmark scan failed: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for BenchError {}
+
+#[derive(Debug, Clone)]
+pub struct BenchOptions {
+    /// Number of source files in the synthetic repo.
+    pub files: usize,
+    /// Full scans to run; the fastest one is reported.
+    pub iterations: usize,
+}
+
+impl Default for BenchOptions {
+    fn default() -> Self {
+        Self {
+            files: 500,
+            iterations: 3,
+        }
+    }
+}
+
+/// Time spent in one rule across the whole synthetic repo.
+#[derive(Debug, Clone)]
+pub struct RuleCost {
+    pub rule_id: String,
+    pub rule_type: String,
+    pub time: Duration,
+    /// Files the rule ran on after glob and conditioning filters.
+    pub files: usize,
+    pub violations: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct BenchReport {
+    pub files: usize,
+    pub rules: usize,
+    pub iterations: usize,
+    /// Fastest full scan (parallel, as `baseline scan` runs it).
+    pub best: Duration,
+    /// Mean of all full scans.
+    pub mean: Duration,
+    /// Per-rule cost from a single-threaded pass, most expensive first.
+    pub rule_costs: Vec<RuleCost>,
+}
+
+impl BenchReport {
+    pub fn files_per_sec(&self) -> f64 {
+        self.files as f64 / self.best.as_secs_f64().max(f64::EPSILON)
+    }
+}
+
+/// Generate a synthetic repo in a fresh temp directory and benchmark it.
+pub fn run(options: &BenchOptions) -> Result<BenchReport, BenchError> {
+    let dir = std::env::temp_dir().join(format!("baseline-bench-{}", std::process::id()));
+    let report = generate_repo(&dir, options.files)
+        .map_err(BenchError::Io)
+        .and_then(|config| run_in(&config, &dir.join("src"), options));
+    let _ = fs::remove_dir_all(&dir);
+    report
+}
+
+/// Benchmark `config` against the files under `root`.
+pub fn run_in(config: &Path, root: &Path, options: &BenchOptions) -> Result<BenchReport, BenchError> {
+    let targets = [root.to_path_buf()];
+    let mut times = Vec::with_capacity(options.iterations.max(1));
+    let mut last = None;
+    for _ in 0..options.iterations.max(1) {
+        let started = Instant::now();
+        let result = scan::run_scan(config, &targets).map_err(BenchError::Scan)?;
+        times.push(started.elapsed());
+        last = Some(result);
+    }
+    let result = last.expect("at least one iteration runs");
+
+    Ok(BenchReport {
+        files: result.files_scanned,
+        rules: result.rules_loaded,
+        iterations: times.len(),
+        best: times.iter().copied().min().unwrap_or_default(),
+        mean: times.iter().sum::<Duration>() / times.len() as u32,
+        rule_costs: rule_costs(config, &result.scanned_files)?,
+    })
+}
+
+/// Run each content rule on its own over `files` and time it.
+fn rule_costs(config: &Path, files: &[PathBuf]) -> Result<Vec<RuleCost>, BenchError> {
+    let loaded = scan::load_config(config).map_err(BenchError::Scan)?;
+    let contents: Vec<(&PathBuf, String)> = files
+        .iter()
+        .filter_map(|f| fs::read_to_string(f).ok().map(|c| (f, c)))
+        .collect();
+
+    let mut costs = Vec::new();
+    for toml_rule in &loaded.rules {
+        if toml_rule.rule_type == "file-presence" || toml_rule.rule_type == "expo-router-structure" {
+            continue;
+        }
+        let config = toml_rule.to_rule_config();
+        let rule = factory::build_rule(&toml_rule.rule_type, &config)
+            .map_err(|e| BenchError::Scan(ScanError::RuleFactory(e)))?;
+        let include = match rule.file_glob() {
+            Some(pattern) => Some(scan::build_glob_set_from_pattern(pattern).map_err(BenchError::Scan)?),
+            None => None,
+        };
+        let exclude = scan::build_glob_set(&config.exclude_glob).map_err(BenchError::Scan)?;
+
+        let mut cost = RuleCost {
+            rule_id: config.id.clone(),
+            rule_type: toml_rule.rule_type.clone(),
+            time: Duration::ZERO,
+            files: 0,
+            violations: 0,
+        };
+        for (path, content) in &contents {
+            let name = path.file_name().unwrap_or_default();
+            if include
+                .as_ref()
+                .is_some_and(|gs| !gs.is_match(path) && !gs.is_match(name))
+                || exclude.is_match(path)
+                || exclude.is_match(name)
+                || config.file_contains.as_ref().is_some_and(|n| !content.contains(n.as_str()))
+                || config.file_not_contains.as_ref().is_some_and(|n| content.contains(n.as_str()))
+                || (!config.sibling_files.is_empty()
+                    && !config
+                        .sibling_files
+                        .iter()
+                        .any(|s| path.with_file_name(s).is_file()))
+            {
+                continue;
+            }
+            let ctx = ScanContext::new(path, content);
+            let started = Instant::now();
+            cost.violations += rule.check_file(&ctx).len();
+            cost.time += started.elapsed();
+            cost.files += 1;
+        }
+        costs.push(cost);
+    }
+
+    costs.sort_by(|a, b| b.time.cmp(&a.time).then_with(|| a.rule_id.cmp(&b.rule_id)));
+    Ok(costs)
+}
+
+/// Write a synthetic React/Next.js repo with `files` source files under
+/// `dir/src`, and a config extending [`BENCH_PRESETS`]. Returns the config
+/// path. Content is deterministic so runs are comparable across versions.
+pub fn generate_repo(dir: &Path, files: usize) -> std::io::Result<PathBuf> {
+    for sub in ["src/components", "src/lib", "src/app"] {
+        fs::create_dir_all(dir.join(sub))?;
+    }
+    for i in 0..files {
+        let (path, content) = match i % 3 {
+            0 => (format!("src/components/Widget{}.tsx", i), component(i)),
+            1 => (format!("src/lib/load{}.ts", i), module(i)),
+            _ => {
+                fs::create_dir_all(dir.join(format!("src/app/route{}", i)))?;
+                (format!("src/app/route{}/page.tsx", i), page(i))
+            }
+        };
+        fs::write(dir.join(path), content)?;
+    }
+
+    let presets: Vec<String> = BENCH_PRESETS.iter().map(|p| format!("\"{}\"", p)).collect();
+    let config = dir.join("baseline.toml");
+    fs::write(
+        &config,
+        format!("[baseline]\nname = \"bench\"\nextends = [{}]\n", presets.join(", ")),
+    )?;
+    fs::write(dir.join("package.json"), "{ \"name\": \"bench\" }\n")?;
+    Ok(config)
+}
+
+fn component(i: usize) -> String {
+    format!(
+        r#""use client";
+import {{ useState, useEffect }} from "react";
+
+export function Widget{i}({{ items }}: {{ items: string[] }}) {{
+  const [count, setCount] = useState({i});
+  const [doubled, setDoubled] = useState(0);
+  const [open, setOpen] = useState(false);
+  const [query, setQuery] = useState("");
+
+  useEffect(() => {{
+    setDoubled(count * 2);
+  }}, [count]);
+
+  const visible = items.filter((item) => new RegExp(query).test(item));
+
+  return (
+    <div className="flex bg-white p-4 text-gray-900" onClick={{() => setCount(count + 1)}}>
+      {{visible.map((item) => <span key={{item}}>{{item}}</span>)}}
+      <img src="/logo-{i}.png" />
+      <button style={{{{ outline: "none" }}}} onClick={{() => setOpen(!open)}}>toggle</button>
+    </div>
+  );
+}}
+"#
+    )
+}
+
+fn module(i: usize) -> String {
+    format!(
+        r#"// TODO: handle errors in load{i}
+export async function load{i}(id: string): Promise<any> {{
+  const res = await fetch(`/api/items/${{id}}?v={i}`);
+  console.log("loaded", id);
+  return res.json();
+}}
+
+export const limit{i} = {i} as any;
+"#
+    )
+}
+
+fn page(i: usize) -> String {
+    format!(
+        r#"import {{ load{i} }} from "../../lib/load{i}";
+
+export default async function Page{i}() {{
+  const data = await load{i}("{i}");
+  return (
+    <main className="container mx-auto">
+      <h1>Route {i}</h1>
+      <a href="/route{i}">self</a>
+      <pre>{{JSON.stringify(data)}}</pre>
+    </main>
+  );
+}}
+"#
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_repo_benchmarks_every_preset_rule() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = generate_repo(dir.path(), 6).unwrap();
+        let options = BenchOptions {
+            files: 6,
+            iterations: 2,
+        };
+
+        let report = run_in(&config, &dir.path().join("src"), &options).unwrap();
+        assert_eq!(report.files, 6);
+        assert_eq!(report.iterations, 2);
+        assert!(report.rules > 0);
+        assert!(report.best <= report.mean);
+        assert!(report.files_per_sec() > 0.0);
+        assert!(report.rule_costs.iter().any(|c| c.violations > 0));
+        assert!(report
+            .rule_costs
+            .windows(2)
+            .all(|w| w[0].time >= w[1].time));
+    }
+
+    #[test]
+    fn generated_content_is_deterministic() {
+        assert_eq!(component(4), component(4));
+        assert_ne!(module(1), module(2));
+        assert!(page(2).contains("Page2"));
+    }
+}
@@ -0,0 +1,263 @@
+use crate::scan::BaselineResult;
+use std::collections::HashSet;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// What `clean` does with an artifact.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ArtifactKind {
+    /// Regenerated on demand; removed outright.
+    Cache,
+    /// Append-only JSONL; trimmed to the newest `--keep-history` entries.
+    History,
+    /// `baseline baseline` output; entries for rules that are no longer
+    /// ratchets in the config are pruned.
+    Snapshot,
+    /// Rendered reports; removed outright.
+    Report,
+}
+
+/// A file or directory the tool generates, relative to the config's
+/// directory. Subsystems that write artifacts register them here so `clean`
+/// can find them.
+#[derive(Debug)]
+pub struct Artifact {
+    pub name: &'static str,
+    pub path: &'static str,
+    pub kind: ArtifactKind,
+}
+
+pub const ARTIFACTS: &[Artifact] = &[
+    Artifact {
+        name: "cache",
+        path: ".baseline/cache",
+        kind: ArtifactKind::Cache,
+    },
+    Artifact {
+        name: "history",
+        path: ".baseline/history.jsonl",
+        kind: ArtifactKind::History,
+    },
+    Artifact {
+        name: "snapshot",
+        path: ".baseline-snapshot.json",
+        kind: ArtifactKind::Snapshot,
+    },
+    Artifact {
+        name: "reports",
+        path: ".baseline/reports",
+        kind: ArtifactKind::Report,
+    },
+];
+
+/// The artifact registered under `name`.
+pub fn artifact(name: &str) -> Option<&'static Artifact> {
+    ARTIFACTS.iter().find(|a| a.name == name)
+}
+
+#[derive(Debug)]
+pub enum CleanError {
+    Io(PathBuf, io::Error),
+    Snapshot(PathBuf, serde_json::Error),
+}
+
+impl fmt::Display for CleanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CleanError::Io(path, e) => write!(f, "{}: {}", path.display(), e),
+            CleanError::Snapshot(path, e) => write!(f, "{}: not a baseline snapshot: {}", path.display(), e),
+        }
+    }
+}
+
+impl std::error::Error for CleanError {}
+
+/// What happened, or would happen with `--dry-run`, to one artifact.
+#[derive(Debug, PartialEq, Eq)]
+pub enum CleanAction {
+    /// Removed, with the number of files it held.
+    Removed { files: usize },
+    /// Kept, with `removed` entries (lines or snapshot entries) dropped.
+    Pruned { removed: usize, kept: usize },
+}
+
+#[derive(Debug)]
+pub struct CleanOutcome {
+    pub artifact: &'static Artifact,
+    pub path: PathBuf,
+    pub action: CleanAction,
+}
+
+pub struct CleanOptions<'a> {
+    /// Report what would change without touching the disk.
+    pub dry_run: bool,
+    /// History entries to keep; all of them are removed when zero.
+    pub keep_history: usize,
+    /// Ids of the config's ratchet rules; snapshot entries for other ids are stale.
+    pub ratchets: &'a HashSet<String>,
+    /// Artifacts to clean, by name; every registered artifact when empty.
+    pub only: &'a [String],
+}
+
+/// Remove or prune the registered artifacts under `root`. Artifacts that
+/// don't exist, or that need no change, are left out of the result.
+pub fn clean(root: &Path, options: &CleanOptions) -> Result<Vec<CleanOutcome>, CleanError> {
+    let mut outcomes = Vec::new();
+    for artifact in ARTIFACTS {
+        if !options.only.is_empty() && !options.only.iter().any(|n| n == artifact.name) {
+            continue;
+        }
+        let path = root.join(artifact.path);
+        if !path.exists() {
+            continue;
+        }
+        let action = match artifact.kind {
+            ArtifactKind::Cache | ArtifactKind::Report => remove(&path, options.dry_run)?,
+            ArtifactKind::History => prune_history(&path, options.keep_history, options.dry_run)?,
+            ArtifactKind::Snapshot => prune_snapshot(&path, options.ratchets, options.dry_run)?,
+        };
+        if let Some(action) = action {
+            outcomes.push(CleanOutcome { artifact, path, action });
+        }
+    }
+    Ok(outcomes)
+}
+
+fn remove(path: &Path, dry_run: bool) -> Result<Option<CleanAction>, CleanError> {
+    let io_err = |e| CleanError::Io(path.to_path_buf(), e);
+    let files = count_files(path).map_err(io_err)?;
+    if !dry_run {
+        if path.is_dir() {
+            fs::remove_dir_all(path).map_err(io_err)?;
+        } else {
+            fs::remove_file(path).map_err(io_err)?;
+        }
+    }
+    Ok(Some(CleanAction::Removed { files }))
+}
+
+fn count_files(path: &Path) -> io::Result<usize> {
+    if !path.is_dir() {
+        return Ok(1);
+    }
+    let mut files = 0;
+    for entry in fs::read_dir(path)? {
+        files += count_files(&entry?.path())?;
+    }
+    Ok(files)
+}
+
+/// Keep the newest `keep` lines of a JSONL history file.
+fn prune_history(path: &Path, keep: usize, dry_run: bool) -> Result<Option<CleanAction>, CleanError> {
+    if keep == 0 {
+        return remove(path, dry_run);
+    }
+    let io_err = |e| CleanError::Io(path.to_path_buf(), e);
+    let content = fs::read_to_string(path).map_err(io_err)?;
+    let lines: Vec<&str> = content.lines().filter(|l| !l.trim().is_empty()).collect();
+    if lines.len() <= keep {
+        return Ok(None);
+    }
+    let removed = lines.len() - keep;
+    if !dry_run {
+        let mut kept = lines[removed..].join("\n");
+        kept.push('\n');
+        fs::write(path, kept).map_err(io_err)?;
+    }
+    Ok(Some(CleanAction::Pruned { removed, kept: keep }))
+}
+
+/// Drop snapshot entries whose rule is no longer a ratchet, and the whole
+/// snapshot once no entry is left.
+fn prune_snapshot(
+    path: &Path,
+    ratchets: &HashSet<String>,
+    dry_run: bool,
+) -> Result<Option<CleanAction>, CleanError> {
+    let io_err = |e| CleanError::Io(path.to_path_buf(), e);
+    let content = fs::read_to_string(path).map_err(io_err)?;
+    let mut snapshot: BaselineResult =
+        serde_json::from_str(&content).map_err(|e| CleanError::Snapshot(path.to_path_buf(), e))?;
+    let before = snapshot.entries.len();
+    snapshot.entries.retain(|e| ratchets.contains(&e.rule_id));
+    let removed = before - snapshot.entries.len();
+    if snapshot.entries.is_empty() {
+        return remove(path, dry_run);
+    }
+    if removed == 0 {
+        return Ok(None);
+    }
+    if !dry_run {
+        let json = serde_json::to_string_pretty(&snapshot).unwrap();
+        fs::write(path, json).map_err(io_err)?;
+    }
+    Ok(Some(CleanAction::Pruned {
+        removed,
+        kept: snapshot.entries.len(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options<'a>(ratchets: &'a HashSet<String>, dry_run: bool) -> CleanOptions<'a> {
+        CleanOptions {
+            dry_run,
+            keep_history: 2,
+            ratchets,
+            only: &[],
+        }
+    }
+
+    #[test]
+    fn removes_caches_and_prunes_history_and_stale_snapshot_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join(".baseline/cache/v1")).unwrap();
+        fs::write(root.join(".baseline/cache/v1/a"), "x").unwrap();
+        fs::write(root.join(".baseline/cache/b"), "x").unwrap();
+        fs::write(root.join(".baseline/history.jsonl"), "{\"n\":1}\n{\"n\":2}\n{\"n\":3}\n").unwrap();
+        fs::write(
+            root.join(".baseline-snapshot.json"),
+            r#"{ "entries": [
+                { "rule_id": "todo", "pattern": "TODO", "count": 3 },
+                { "rule_id": "gone", "pattern": "legacy", "count": 1 }
+            ], "files_scanned": 4 }"#,
+        )
+        .unwrap();
+        let ratchets: HashSet<String> = ["todo".to_string()].into();
+
+        let planned = clean(root, &options(&ratchets, true)).unwrap();
+        let actions: Vec<(&str, &CleanAction)> = planned.iter().map(|o| (o.artifact.name, &o.action)).collect();
+        assert_eq!(
+            actions,
+            vec![
+                ("cache", &CleanAction::Removed { files: 2 }),
+                ("history", &CleanAction::Pruned { removed: 1, kept: 2 }),
+                ("snapshot", &CleanAction::Pruned { removed: 1, kept: 1 }),
+            ]
+        );
+        assert!(root.join(".baseline/cache/b").exists(), "dry run leaves files alone");
+
+        let done = clean(root, &options(&ratchets, false)).unwrap();
+        assert_eq!(done.len(), 3);
+        assert!(!root.join(".baseline/cache").exists());
+        assert_eq!(
+            fs::read_to_string(root.join(".baseline/history.jsonl")).unwrap(),
+            "{\"n\":2}\n{\"n\":3}\n"
+        );
+        let snapshot: BaselineResult =
+            serde_json::from_str(&fs::read_to_string(root.join(".baseline-snapshot.json")).unwrap()).unwrap();
+        assert_eq!(snapshot.entries.len(), 1);
+
+        assert!(clean(root, &options(&ratchets, false)).unwrap().is_empty(), "nothing left to do");
+        let none = HashSet::new();
+        let only = ["snapshot".to_string()];
+        let outcomes = clean(root, &CleanOptions { only: &only, ..options(&none, false) }).unwrap();
+        assert_eq!(outcomes[0].action, CleanAction::Removed { files: 1 });
+        assert!(root.join(".baseline/history.jsonl").exists());
+    }
+}
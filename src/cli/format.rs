@@ -1,11 +1,38 @@
-use crate::config::Severity;
+use crate::bench::BenchReport;
+use crate::clean::{CleanAction, CleanOutcome};
+use crate::cli::ReportFormat;
+use crate::components::Component;
+use crate::cli::toml_config::{RuleOrigin, TomlRule};
+use crate::config::{ColumnUnit, Severity};
+use crate::deadline::RatchetDeadline;
+use crate::fix::{self, FixReport, TextEdit};
+use crate::git_diff::ChangedLines;
+use crate::locale::Catalog;
+use crate::presets::{PresetSnapshot, RuleChange};
+use crate::projects::ProjectResult;
 use crate::rules::Violation;
-use crate::scan::ScanResult;
+use crate::scan::{self, ConfigCheck, RuleCount, ScanResult, SeveritySimulation};
+use crate::snippet::SnippetResult;
+use crate::verify::VerifyReport;
 use serde_json::json;
 use std::collections::BTreeMap;
 use std::collections::HashMap;
 use std::io::Write;
-use std::path::Path;
+
+/// Display limits for the human-readable formats (pretty, markdown). They
+/// only change what is printed; summary counts and machine-readable formats
+/// always cover every violation.
+#[derive(Debug, Clone, Default)]
+pub struct DisplayOptions {
+    /// Show at most this many violations per rule, rolling up the rest.
+    pub max_per_rule: Option<usize>,
+    /// Show which preset, plugin, or config each violation's rule came from.
+    pub verbose: bool,
+    /// Translated CLI strings for pretty output (`--locale`).
+    pub catalog: Catalog,
+    /// Roll up violation counts per directory this many levels deep (`--by-directory`).
+    pub by_directory: Option<usize>,
+}
 
 /// Print violations grouped by file with ANSI colors.
 pub fn print_pretty(result: &ScanResult) {
@@ -13,20 +40,67 @@ pub fn print_pretty(result: &ScanResult) {
     write_pretty(result, &mut out);
 }
 
+pub fn print_pretty_with_options(result: &ScanResult, options: &DisplayOptions) {
+    let mut out = std::io::stdout();
+    write_pretty_with_options(result, options, &mut out);
+}
+
 fn write_pretty(result: &ScanResult, out: &mut dyn Write) {
+    write_pretty_with_options(result, &DisplayOptions::default(), out);
+}
+
+/// Keep the first `max_per_rule` violations of each rule, in scan order.
+/// Returns the kept violations and, per rule, how many were left out.
+fn cap_per_rule(
+    violations: &[Violation],
+    max_per_rule: Option<usize>,
+) -> (Vec<&Violation>, BTreeMap<&str, usize>) {
+    let Some(max) = max_per_rule else {
+        return (violations.iter().collect(), BTreeMap::new());
+    };
+    let mut shown: HashMap<&str, usize> = HashMap::new();
+    let mut hidden: BTreeMap<&str, usize> = BTreeMap::new();
+    let mut kept = Vec::new();
+    for v in violations {
+        let count = shown.entry(v.rule_id.as_str()).or_default();
+        if *count < max {
+            *count += 1;
+            kept.push(v);
+        } else {
+            *hidden.entry(v.rule_id.as_str()).or_default() += 1;
+        }
+    }
+    (kept, hidden)
+}
+
+fn write_pretty_with_options(result: &ScanResult, options: &DisplayOptions, out: &mut dyn Write) {
+    let catalog = &options.catalog;
+    let counts = catalog.text(
+        "scan-counts",
+        &scan_counts(result),
+        &[("files", &result.files_scanned), ("rules", &result.rules_loaded)],
+    );
     if result.violations.is_empty() {
         let _ = writeln!(
             out,
-            "\x1b[32m✓\x1b[0m No violations found ({} files scanned, {} rules loaded)",
-            result.files_scanned, result.rules_loaded
+            "\x1b[32m✓\x1b[0m {} ({})",
+            catalog.text("no-violations", "No violations found", &[]),
+            counts
         );
-        write_ratchet_summary_pretty(&result.ratchet_counts, out);
+        write_ratchet_summary_pretty(
+            &result.ratchet_counts,
+            &result.ratchet_deadlines,
+            &result.unevaluated_ratchets(),
+            out,
+        );
+        write_fix_summary_pretty(result.fixes.as_ref(), out);
         return;
     }
 
     // Group violations by file
+    let (shown, hidden) = cap_per_rule(&result.violations, options.max_per_rule);
     let mut by_file: BTreeMap<String, Vec<&Violation>> = BTreeMap::new();
-    for v in &result.violations {
+    for v in shown {
         by_file
             .entry(v.file.display().to_string())
             .or_default()
@@ -39,30 +113,76 @@ fn write_pretty(result: &ScanResult, out: &mut dyn Write) {
             let severity_str = match v.severity {
                 Severity::Error => "\x1b[31merror\x1b[0m",
                 Severity::Warning => "\x1b[33mwarn \x1b[0m",
+                Severity::Info => "\x1b[36minfo \x1b[0m",
+                Severity::Hint => "\x1b[90mhint \x1b[0m",
             };
 
-            let location = match (v.line, v.column) {
+            let location = match (v.line, v.column_in(result.output.column_unit())) {
                 (Some(l), Some(c)) => format!("{}:{}", l, c),
                 (Some(l), None) => format!("{}:1", l),
                 _ => "1:1".to_string(),
             };
 
+            let enclosing = match v.enclosing {
+                Some(ref name) => format!(" {}", catalog.text("in-function", "in {name}", &[("name", name)])),
+                None => String::new(),
+            };
             let _ = writeln!(
                 out,
-                "  \x1b[90m{:<8}\x1b[0m {} \x1b[90m{:<25}\x1b[0m {}",
-                location, severity_str, v.rule_id, v.message
+                "  \x1b[90m{:<8}\x1b[0m {} \x1b[90m{:<25}\x1b[0m {}\x1b[90m{}{}\x1b[0m",
+                location,
+                severity_str,
+                v.rule_id,
+                v.message,
+                enclosing,
+                origin_suffix(v)
             );
 
             if let Some(ref source) = v.source_line {
                 let _ = writeln!(out, "           \x1b[90m│\x1b[0m {}", source.trim());
             }
 
-            if let Some(ref suggest) = v.suggest {
-                let _ = writeln!(out, "           \x1b[90m└─\x1b[0m \x1b[36m{}\x1b[0m", suggest);
+            match v.suggest.as_slice() {
+                [] => {}
+                [only] => {
+                    let _ = writeln!(out, "           \x1b[90m└─\x1b[0m \x1b[36m{}\x1b[0m", only.title);
+                }
+                options => {
+                    for (i, s) in options.iter().enumerate() {
+                        let branch = if i == 0 { "└─" } else { "  " };
+                        let _ = writeln!(
+                            out,
+                            "           \x1b[90m{}\x1b[0m \x1b[36m{}. {}\x1b[0m",
+                            branch,
+                            i + 1,
+                            s.title
+                        );
+                    }
+                }
+            }
+
+            if let Some(meta) = options.verbose.then(|| scan::rule_for(result, v)).flatten() {
+                let _ = writeln!(out, "           \x1b[90m· from {}\x1b[0m", meta.origin);
             }
         }
     }
 
+    if !hidden.is_empty() {
+        let _ = writeln!(out);
+        for (rule_id, count) in &hidden {
+            let _ = writeln!(
+                out,
+                "  \x1b[90m{:<25}\x1b[0m {}",
+                rule_id,
+                catalog.text("and-more", "…and {count} more", &[("count", count)])
+            );
+        }
+    }
+
+    if let Some(depth) = options.by_directory {
+        write_directory_rollup(&result.violations, depth, catalog, out);
+    }
+
     let errors = result
         .violations
         .iter()
@@ -77,68 +197,372 @@ fn write_pretty(result: &ScanResult, out: &mut dyn Write) {
     let _ = writeln!(out);
     let _ = write!(out, "\x1b[1m");
     if errors > 0 {
-        let _ = write!(out, "\x1b[31m{} error{}\x1b[0m\x1b[1m", errors, if errors == 1 { "" } else { "s" });
+        let (key, default) = if errors == 1 { ("error", "{count} error") } else { ("errors", "{count} errors") };
+        let _ = write!(out, "\x1b[31m{}\x1b[0m\x1b[1m", catalog.text(key, default, &[("count", &errors)]));
     }
     if errors > 0 && warnings > 0 {
         let _ = write!(out, ", ");
     }
     if warnings > 0 {
-        let _ = write!(out, "\x1b[33m{} warning{}\x1b[0m\x1b[1m", warnings, if warnings == 1 { "" } else { "s" });
+        let (key, default) = if warnings == 1 { ("warning", "{count} warning") } else { ("warnings", "{count} warnings") };
+        let _ = write!(out, "\x1b[33m{}\x1b[0m\x1b[1m", catalog.text(key, default, &[("count", &warnings)]));
+    }
+    for (i, part) in informational_parts(result).iter().enumerate() {
+        if i > 0 || errors > 0 || warnings > 0 {
+            let _ = write!(out, ", ");
+        }
+        let _ = write!(out, "\x1b[36m{}\x1b[0m\x1b[1m", part);
     }
     let _ = writeln!(
         out,
-        " ({} files scanned, {} rules loaded)\x1b[0m",
+        " ({})\x1b[0m",
+        counts
+    );
+
+    write_ratchet_summary_pretty(
+        &result.ratchet_counts,
+        &result.ratchet_deadlines,
+        &result.unevaluated_ratchets(),
+        out,
+    );
+    write_fix_summary_pretty(result.fixes.as_ref(), out);
+}
+
+/// Violation counts (errors, warnings, info, hints) per directory, `depth`
+/// levels deep, most errors first. A file less than `depth` directories down
+/// counts toward its own directory, and a file at the top toward `.`.
+fn directory_rollup(violations: &[Violation], depth: usize) -> Vec<(String, [usize; 4])> {
+    let cwd = std::env::current_dir().unwrap_or_default();
+    let mut by_dir: BTreeMap<String, [usize; 4]> = BTreeMap::new();
+    for v in violations {
+        let file = v.file.strip_prefix(&cwd).unwrap_or(&v.file);
+        let mut dirs: Vec<String> = file
+            .components()
+            .filter_map(|c| match c {
+                std::path::Component::Normal(part) => Some(part.to_string_lossy().into_owned()),
+                _ => None,
+            })
+            .collect();
+        dirs.pop();
+        dirs.truncate(depth.max(1));
+        let dir = if dirs.is_empty() { ".".to_string() } else { dirs.join("/") };
+        let slot = match v.severity {
+            Severity::Error => 0,
+            Severity::Warning => 1,
+            Severity::Info => 2,
+            Severity::Hint => 3,
+        };
+        by_dir.entry(dir).or_default()[slot] += 1;
+    }
+    let mut rollup: Vec<(String, [usize; 4])> = by_dir.into_iter().collect();
+    rollup.sort_by(|(a_dir, a), (b_dir, b)| {
+        (b[0], b[1], b.iter().sum::<usize>())
+            .cmp(&(a[0], a[1], a.iter().sum::<usize>()))
+            .then_with(|| a_dir.cmp(b_dir))
+    });
+    rollup
+}
+
+fn write_directory_rollup(violations: &[Violation], depth: usize, catalog: &Catalog, out: &mut dyn Write) {
+    let rollup = directory_rollup(violations, depth);
+    let width = rollup.iter().map(|(dir, _)| dir.chars().count()).max().unwrap_or(0).max(30);
+    let _ = writeln!(out, "\n\x1b[1m{}\x1b[0m", catalog.text("by-directory", "By directory:", &[]));
+    for (dir, [errors, warnings, info, hints]) in &rollup {
+        let mut parts = Vec::new();
+        if *errors > 0 {
+            let (key, default) = if *errors == 1 { ("error", "{count} error") } else { ("errors", "{count} errors") };
+            parts.push(format!("\x1b[31m{}\x1b[0m", catalog.text(key, default, &[("count", errors)])));
+        }
+        if *warnings > 0 {
+            let (key, default) = if *warnings == 1 { ("warning", "{count} warning") } else { ("warnings", "{count} warnings") };
+            parts.push(format!("\x1b[33m{}\x1b[0m", catalog.text(key, default, &[("count", warnings)])));
+        }
+        if *info > 0 {
+            parts.push(format!("\x1b[36m{} info\x1b[0m", info));
+        }
+        if *hints > 0 {
+            parts.push(format!("\x1b[36m{} hint{}\x1b[0m", hints, if *hints == 1 { "" } else { "s" }));
+        }
+        let _ = writeln!(out, "  {:<width$} {}", dir, parts.join(", "), width = width);
+    }
+}
+
+/// Summary fragments for violations that never fail a scan, e.g.
+/// `["2 info", "1 hint"]`.
+fn informational_parts(result: &ScanResult) -> Vec<String> {
+    let count = |s: Severity| result.violations.iter().filter(|v| v.severity == s).count();
+    let (info, hints) = (count(Severity::Info), count(Severity::Hint));
+
+    let mut parts = Vec::new();
+    if info > 0 {
+        parts.push(format!("{} info", info));
+    }
+    if hints > 0 {
+        parts.push(format!("{} hint{}", hints, if hints == 1 { "" } else { "s" }));
+    }
+    parts
+}
+
+/// `"5 files scanned, 2 rules loaded"`, plus the `--group`s the rules came
+/// from and how many generated files were skipped, when there were any.
+fn scan_counts(result: &ScanResult) -> String {
+    let mut counts = format!(
+        "{} files scanned, {} rules loaded",
         result.files_scanned, result.rules_loaded
     );
+    if !result.groups.is_empty() {
+        counts.push_str(&format!(
+            " from group{} {}",
+            if result.groups.len() == 1 { "" } else { "s" },
+            result.groups.join(" + ")
+        ));
+    }
+    if result.generated_skipped > 0 {
+        counts.push_str(&format!(
+            ", {} generated file{} skipped",
+            result.generated_skipped,
+            if result.generated_skipped == 1 { "" } else { "s" }
+        ));
+    }
+    counts
+}
 
-    write_ratchet_summary_pretty(&result.ratchet_counts, out);
+fn fix_counts(report: &FixReport) -> String {
+    format!(
+        "{} {}, {} skipped, {} conflicted",
+        report.applied(),
+        if report.dry_run { "would be applied" } else { "applied" },
+        report.skipped(),
+        report.conflicted()
+    )
 }
 
+fn write_fix_summary_pretty(report: Option<&FixReport>, out: &mut dyn Write) {
+    let Some(report) = report else { return };
+
+    let prefix = if report.dry_run { "\x1b[36m(dry run)\x1b[0m " } else { "" };
+    let _ = writeln!(out, "\n\x1b[1mFixes:\x1b[0m {}{}", prefix, fix_counts(report));
+    for o in report.unapplied() {
+        let _ = writeln!(
+            out,
+            "  \x1b[33m{}\x1b[0m {}:{} \x1b[90m{}\x1b[0m — {}",
+            o.status.as_str(),
+            o.file.display(),
+            o.line.unwrap_or(1),
+            o.rule_id,
+            o.status.describe()
+        );
+    }
+}
+
+fn write_fix_summary_stderr(report: Option<&FixReport>, err: &mut dyn Write) {
+    let Some(report) = report else { return };
+
+    let _ = writeln!(
+        err,
+        "fixes: {}{}",
+        fix_counts(report),
+        if report.dry_run { " (dry run)" } else { "" }
+    );
+    for o in report.unapplied() {
+        let _ = writeln!(
+            err,
+            "fix: {}:{} {} {} ({})",
+            o.file.display(),
+            o.line.unwrap_or(1),
+            o.rule_id,
+            o.status.as_str(),
+            o.status.describe()
+        );
+    }
+}
+
+/// Ratchet status lines. `unevaluated` lists ratchets a `--fail-fast` scan
+/// stopped before counting.
 fn write_ratchet_summary_pretty(
     ratchet_counts: &HashMap<String, (usize, usize)>,
+    deadlines: &HashMap<String, RatchetDeadline>,
+    unevaluated: &[&str],
     out: &mut dyn Write,
 ) {
-    if ratchet_counts.is_empty() {
+    if ratchet_counts.is_empty() && unevaluated.is_empty() {
         return;
     }
 
     let _ = writeln!(out, "\n\x1b[1mRatchet rules:\x1b[0m");
+    for rule_id in unevaluated {
+        let _ = writeln!(out, "  {:<30} \x1b[90mnot evaluated (stopped early)\x1b[0m", rule_id);
+    }
     let mut sorted: Vec<_> = ratchet_counts.iter().collect();
     sorted.sort_by_key(|(id, _)| (*id).clone());
 
     for (rule_id, &(found, max)) in &sorted {
+        let counts = format!("({}/{}{})", found, max, deadline_suffix(deadlines, rule_id, found));
         let status = if found <= max {
-            format!("\x1b[32m✓ pass\x1b[0m ({}/{})", found, max)
+            format!("\x1b[32m✓ pass\x1b[0m {}", counts)
         } else {
-            format!("\x1b[31m✗ OVER\x1b[0m ({}/{})", found, max)
+            format!("\x1b[31m✗ OVER\x1b[0m {}", counts)
         };
         let _ = writeln!(out, "  {:<30} {}", rule_id, status);
     }
 }
 
+/// `", 28 days left, 3/week"` for a ratchet count whose rule has a deadline.
+fn deadline_suffix(deadlines: &HashMap<String, RatchetDeadline>, key: &str, found: usize) -> String {
+    let (rule_id, _) = scan::split_ratchet_key(key);
+    deadlines
+        .get(rule_id)
+        .map(|d| format!(", {}", d.describe(found)))
+        .unwrap_or_default()
+}
+
+/// Print per-rule counts, ratchet status, and totals without listing
+/// individual violations (for quick health checks and cron jobs).
+pub fn print_summary(result: &ScanResult) {
+    let mut out = std::io::stdout();
+    write_summary(result, &mut out);
+}
+
+fn write_summary(result: &ScanResult, out: &mut dyn Write) {
+    let counts = scan::rule_counts(result);
+    if !counts.is_empty() {
+        let _ = writeln!(out, "\x1b[1m{:<30} {:<8} {:>10} {:>6}\x1b[0m", "Rule", "Severity", "Violations", "Files");
+        for c in &counts {
+            let _ = writeln!(
+                out,
+                "{:<30} {:<8} {:>10} {:>6}",
+                c.rule_id,
+                c.severity.as_str(),
+                c.violations,
+                c.files
+            );
+        }
+    }
+    write_ratchet_summary_pretty(
+        &result.ratchet_counts,
+        &result.ratchet_deadlines,
+        &result.unevaluated_ratchets(),
+        out,
+    );
+
+    let count = |severity: Severity| {
+        result
+            .violations
+            .iter()
+            .filter(|v| v.severity == severity)
+            .count()
+    };
+    let (errors, warnings) = (count(Severity::Error), count(Severity::Warning));
+    let mut parts = vec![
+        format!("{} error{}", errors, if errors == 1 { "" } else { "s" }),
+        format!("{} warning{}", warnings, if warnings == 1 { "" } else { "s" }),
+    ];
+    parts.extend(informational_parts(result));
+    let _ = writeln!(
+        out,
+        "\n{} ({})",
+        parts.join(", "),
+        scan_counts(result)
+    );
+}
+
 /// Print violations as structured JSON.
 pub fn print_json(result: &ScanResult) {
     let mut out = std::io::stdout();
     write_json(result, &mut out);
 }
 
-fn write_json(result: &ScanResult, out: &mut dyn Write) {
+pub(crate) fn write_json(result: &ScanResult, out: &mut dyn Write) {
+    let _ = writeln!(out, "{}", json_string(result));
+}
+
+/// The `--format json` report as a string (for `[hooks]`).
+pub fn json_string(result: &ScanResult) -> String {
+    serde_json::to_string_pretty(&json_report(result)).unwrap()
+}
+
+/// Print JSON output with an extra `projects` array mapping each project to
+/// its files, counts, and exit code (for `--by-project`).
+pub fn print_json_by_project(result: &ScanResult, projects: &[ProjectResult]) {
+    let mut out = std::io::stdout();
+    write_json_by_project(result, projects, &mut out);
+}
+
+fn write_json_by_project(result: &ScanResult, projects: &[ProjectResult], out: &mut dyn Write) {
+    let mut output = json_report(result);
+    output["projects"] = projects
+        .iter()
+        .map(|p| {
+            let mut files: Vec<String> = p
+                .result
+                .violations
+                .iter()
+                .map(|v| v.file.display().to_string())
+                .collect();
+            files.sort();
+            files.dedup();
+            json!({
+                "name": p.project.name,
+                "root": p.project.root.display().to_string(),
+                "errors": p.errors(),
+                "warnings": p.warnings(),
+                "files_scanned": p.result.files_scanned,
+                "files_with_violations": files,
+                "exit_code": p.exit_code(),
+            })
+        })
+        .collect();
+    let _ = writeln!(out, "{}", serde_json::to_string_pretty(&output).unwrap());
+}
+
+fn origin_json(origin: &RuleOrigin) -> serde_json::Value {
+    match origin {
+        RuleOrigin::User => json!({ "kind": "user" }),
+        RuleOrigin::Preset(name) => json!({ "kind": "preset", "preset": name }),
+        RuleOrigin::ScopedPreset { preset, path } => {
+            json!({ "kind": "scoped-preset", "preset": preset, "path": path })
+        }
+        RuleOrigin::Plugin(path) => json!({ "kind": "plugin", "path": path.display().to_string() }),
+    }
+}
+
+/// Every suggestion on a violation, with its fix when it has one.
+pub(crate) fn suggestions_json(v: &Violation) -> serde_json::Value {
+    v.suggest
+        .iter()
+        .map(|s| {
+            json!({
+                "title": s.title,
+                "fix": s.fix.as_ref().map(|f| json!({ "old": f.old, "new": f.new })),
+            })
+        })
+        .collect()
+}
+
+/// Version of the `--format json` structure, reported as `"version"`. Bumped
+/// when an existing field changes; new fields don't bump it. `baseline schema
+/// --output-format` prints the matching JSON Schema.
+pub const JSON_OUTPUT_VERSION: u32 = 1;
+
+fn json_report(result: &ScanResult) -> serde_json::Value {
     let violations: Vec<_> = result
         .violations
         .iter()
         .map(|v| {
             json!({
+                "fingerprint": v.fingerprint(),
                 "rule_id": v.rule_id,
-                "severity": match v.severity {
-                    Severity::Error => "error",
-                    Severity::Warning => "warning",
-                },
+                "config": v.origin.as_ref().map(|c| c.display().to_string()),
+                "origin": scan::rule_for(result, v).map(|meta| origin_json(&meta.origin)),
+                "severity": v.severity.as_str(),
                 "file": v.file.display().to_string(),
                 "line": v.line,
-                "column": v.column,
+                "column": v.column_in(result.output.column_unit()),
                 "message": v.message,
-                "suggest": v.suggest,
+                "suggest": v.suggest_text(),
+                "suggestions": suggestions_json(v),
                 "source_line": v.source_line,
+                "enclosing": v.enclosing,
                 "fix": v.fix.as_ref().map(|f| json!({
                     "old": f.old,
                     "new": f.new,
@@ -151,30 +575,125 @@ fn write_json(result: &ScanResult, out: &mut dyn Write) {
         .ratchet_counts
         .iter()
         .map(|(id, &(found, max))| {
-            (
-                id.clone(),
-                json!({ "found": found, "max": max, "pass": found <= max }),
-            )
+            let (rule_id, scope) = crate::scan::split_ratchet_key(id);
+            let mut entry = json!({
+                "rule_id": rule_id,
+                "scope": scope,
+                "found": found,
+                "max": max,
+                "pass": found <= max,
+            });
+            if let Some(d) = result.ratchet_deadlines.get(rule_id) {
+                entry["deadline"] = json!({
+                    "date": d.date,
+                    "target": d.target,
+                    "days_remaining": d.days_remaining,
+                    "weekly_rate": d.weekly_rate(found),
+                });
+            }
+            (id.clone(), entry)
         })
         .collect();
 
-    let output = json!({
+    let rules: Vec<_> = scan::rule_metrics(result)
+        .into_iter()
+        .map(|(meta, fired)| {
+            json!({
+                "rule_id": meta.rule_id,
+                "type": meta.rule_type,
+                "severity": meta.severity.as_str(),
+                "fired": fired,
+                "glob_restricted": meta.glob_restricted,
+                "conditioned": meta.conditioned,
+                "origin": origin_json(&meta.origin),
+                "owner": meta.owner,
+                "requires_review": meta.requires_review,
+            })
+        })
+        .collect();
+
+    let mut report = json!({
+        "version": JSON_OUTPUT_VERSION,
         "violations": violations,
         "summary": {
             "total": result.violations.len(),
             "errors": result.violations.iter().filter(|v| v.severity == Severity::Error).count(),
             "warnings": result.violations.iter().filter(|v| v.severity == Severity::Warning).count(),
+            "info": result.violations.iter().filter(|v| v.severity == Severity::Info).count(),
+            "hints": result.violations.iter().filter(|v| v.severity == Severity::Hint).count(),
             "files_scanned": result.files_scanned,
+            "generated_skipped": result.generated_skipped,
             "rules_loaded": result.rules_loaded,
+            "groups": result.groups,
+            "column_unit": result.output.column_unit(),
+            "stopped_early": result.stopped_early,
         },
         "ratchet": ratchet,
+        "rules": rules,
     });
 
-    let _ = writeln!(out, "{}", serde_json::to_string_pretty(&output).unwrap());
+    if let Some(ref fixes) = result.fixes {
+        report["fixes"] = json!({
+            "dry_run": fixes.dry_run,
+            "applied": fixes.applied(),
+            "skipped": fixes.skipped(),
+            "conflicted": fixes.conflicted(),
+            "outcomes": fixes.outcomes.iter().map(|o| json!({
+                "file": o.file.display().to_string(),
+                "line": o.line,
+                "rule_id": o.rule_id,
+                "status": o.status.as_str(),
+            })).collect::<Vec<_>>(),
+        });
+    }
+    report
+}
+
+/// ` (config.toml)` naming the config a violation came from, when the scan
+/// evaluated several configs.
+fn origin_suffix(v: &Violation) -> String {
+    v.origin
+        .as_ref()
+        .map(|c| format!(" ({})", c.display()))
+        .unwrap_or_default()
+}
+
+/// Print the files a scan read (`scan --list-files`), sorted: one path per
+/// line, or a JSON array of paths.
+pub fn print_file_list(result: &ScanResult, json: bool) {
+    let mut files: Vec<String> = result
+        .scanned_files
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect();
+    files.sort();
+    if json {
+        println!("{}", serde_json::to_string_pretty(&files).unwrap());
+    } else {
+        for file in files {
+            println!("{}", file);
+        }
+    }
 }
 
 /// Print violations in compact one-line-per-violation format.
 /// Violations go to stdout; summary goes to stderr.
+/// Print a `--stdin --fix` run: `output` (the fixed content or a diff) on
+/// stdout, where an editor reads it back, and the remaining violations as
+/// compact lines on stderr.
+pub fn print_stdin_fix(result: &ScanResult, output: &str) {
+    let mut stdout = std::io::stdout();
+    let mut stderr = std::io::stderr();
+    write_stdin_fix(result, output, &mut stdout, &mut stderr);
+}
+
+fn write_stdin_fix(result: &ScanResult, output: &str, out: &mut dyn Write, err: &mut dyn Write) {
+    let _ = out.write_all(output.as_bytes());
+    let mut summary = Vec::new();
+    write_compact(result, err, &mut summary);
+    let _ = err.write_all(&summary);
+}
+
 pub fn print_compact(result: &ScanResult) {
     let mut stdout = std::io::stdout();
     let mut stderr = std::io::stderr();
@@ -183,68 +702,294 @@ pub fn print_compact(result: &ScanResult) {
 
 fn write_compact(result: &ScanResult, out: &mut dyn Write, err: &mut dyn Write) {
     for v in &result.violations {
-        let severity = match v.severity {
-            Severity::Error => "error",
-            Severity::Warning => "warning",
-        };
+        let severity = v.severity.as_str();
         let line = v.line.unwrap_or(1);
-        let col = v.column.unwrap_or(1);
+        let col = v.column_in(result.output.column_unit()).unwrap_or(1);
 
         let _ = writeln!(
             out,
-            "{}:{}:{}: {}[{}] {}",
+            "{}:{}:{}: {}[{}] {}{}",
             v.file.display(),
             line,
             col,
             severity,
             v.rule_id,
-            v.message
+            v.message,
+            origin_suffix(v)
         );
     }
 
     write_summary_stderr(result, err);
-    write_ratchet_stderr(&result.ratchet_counts, err);
+    write_ratchet_stderr(
+        &result.ratchet_counts,
+        &result.ratchet_deadlines,
+        &result.unevaluated_ratchets(),
+        err,
+    );
+    write_fix_summary_stderr(result.fixes.as_ref(), err);
+}
+
+/// A GitHub Actions problem matcher for `--format compact` lines
+/// (`file:line:col: severity[rule] message`). Errors and warnings keep their
+/// severity; `info` and `hint` become notices.
+pub fn problem_matcher() -> serde_json::Value {
+    let pattern = |severity: &str| {
+        json!({
+            "regexp": format!(r"^(.+?):(\d+):(\d+): ({})\[([^\]]+)\] (.*)$", severity),
+            "file": 1,
+            "line": 2,
+            "column": 3,
+            "severity": 4,
+            "code": 5,
+            "message": 6
+        })
+    };
+    json!({
+        "problemMatcher": [
+            {
+                "owner": "baseline",
+                "pattern": [pattern("error|warning")]
+            },
+            {
+                "owner": "baseline-notice",
+                "severity": "notice",
+                "pattern": [pattern("info|hint")]
+            }
+        ]
+    })
+}
+
+/// GitHub keeps this many annotations of each type (error, warning,
+/// notice) per step and silently drops the rest.
+pub const GITHUB_ANNOTATION_LIMIT: usize = 10;
+
+/// Lines per `::group::` block when overflow is printed to the log.
+const GITHUB_GROUP_SIZE: usize = 50;
+
+/// How `--format github` fits its output into GitHub's annotation limits.
+#[derive(Debug)]
+pub struct GithubOptions {
+    /// Annotations of each type to emit, including the rollup; 0 emits all.
+    pub annotation_limit: usize,
+    /// Also print the annotations past the limit as plain log lines, in
+    /// collapsible groups.
+    pub group_overflow: bool,
+    /// Lines changed by the pull request. Violations on them are annotated
+    /// before the rest.
+    pub changed: Option<ChangedLines>,
+}
+
+impl Default for GithubOptions {
+    fn default() -> Self {
+        Self {
+            annotation_limit: GITHUB_ANNOTATION_LIMIT,
+            group_overflow: false,
+            changed: None,
+        }
+    }
+}
+
+/// One workflow-command annotation, before limits are applied.
+struct Annotation {
+    level: &'static str,
+    /// Lower is annotated first within a level.
+    rank: u8,
+    title: String,
+    /// `file=...,line=...` properties, if the annotation has a location.
+    location: Option<String>,
+    /// `file:line` for the plain log line (empty without a location).
+    place: String,
+    message: String,
 }
 
 /// Print violations as GitHub Actions workflow commands.
 /// Violations go to stdout; summary goes to stderr.
 pub fn print_github(result: &ScanResult) {
+    print_github_with_options(result, &GithubOptions::default());
+}
+
+pub fn print_github_with_options(result: &ScanResult, options: &GithubOptions) {
     let mut stdout = std::io::stdout();
     let mut stderr = std::io::stderr();
-    write_github(result, &mut stdout, &mut stderr);
+    write_github_with_options(result, options, &mut stdout, &mut stderr);
 }
 
+#[cfg(test)]
 fn write_github(result: &ScanResult, out: &mut dyn Write, err: &mut dyn Write) {
+    write_github_with_options(result, &GithubOptions::default(), out, err);
+}
+
+fn write_github_with_options(result: &ScanResult, options: &GithubOptions, out: &mut dyn Write, err: &mut dyn Write) {
+    let mut annotations = Vec::new();
+
+    // Ratchet failures first: one per rule, and each fails the build
+    let mut sorted: Vec<_> = result.ratchet_counts.iter().collect();
+    sorted.sort_by_key(|(id, _)| (*id).clone());
+    for (rule_id, &(found, max)) in &sorted {
+        if found > max {
+            annotations.push(Annotation {
+                level: "error",
+                rank: 0,
+                title: format!("ratchet-{}", rule_id),
+                location: None,
+                place: String::new(),
+                message: format!(
+                    "Ratchet rule '{}' exceeded budget: {} found, max {}{}",
+                    rule_id,
+                    found,
+                    max,
+                    deadline_suffix(&result.ratchet_deadlines, rule_id, found)
+                ),
+            });
+        }
+    }
+
     for v in &result.violations {
         let level = match v.severity {
             Severity::Error => "error",
             Severity::Warning => "warning",
+            Severity::Info | Severity::Hint => "notice",
         };
+        let in_diff = options.changed.as_ref().is_some_and(|c| c.contains(&v.file, v.line));
 
         let line = v.line.unwrap_or(1);
         let mut props = format!("file={},line={}", v.file.display(), line);
-        if let Some(col) = v.column {
+        if let Some(col) = v.column_in(result.output.column_unit()) {
             props.push_str(&format!(",col={}", col));
         }
-        props.push_str(&format!(",title={}", v.rule_id));
+        annotations.push(Annotation {
+            level,
+            rank: if in_diff { 1 } else { 2 },
+            title: format!("{}{}", v.rule_id, origin_suffix(v)),
+            location: Some(props),
+            place: format!("{}:{}", v.file.display(), line),
+            message: v.message.clone(),
+        });
+    }
 
-        let _ = writeln!(out, "::{} {}::{}", level, props, v.message);
+    // Fixes that were not applied as annotations
+    if let Some(ref fixes) = result.fixes {
+        for o in fixes.unapplied() {
+            let line = o.line.unwrap_or(1);
+            annotations.push(Annotation {
+                level: "warning",
+                rank: 3,
+                title: format!("fix-{}", o.status.as_str()),
+                location: Some(format!("file={},line={}", o.file.display(), line)),
+                place: format!("{}:{}", o.file.display(), line),
+                message: format!("Fix for {} not applied: {}", o.rule_id, o.status.describe()),
+            });
+        }
     }
 
-    // Ratchet failures as annotations
-    let mut sorted: Vec<_> = result.ratchet_counts.iter().collect();
-    sorted.sort_by_key(|(id, _)| (*id).clone());
-    for (rule_id, &(found, max)) in &sorted {
-        if found > max {
+    // Errors, then warnings, then notices; each level past the limit ends
+    // with a rollup annotation standing in for the rest
+    let mut overflow: Vec<(&str, Vec<&Annotation>)> = Vec::new();
+    for level in ["error", "warning", "notice"] {
+        let mut of_level: Vec<&Annotation> = annotations.iter().filter(|a| a.level == level).collect();
+        of_level.sort_by_key(|a| a.rank);
+
+        let limit = options.annotation_limit;
+        let shown = if limit == 0 || of_level.len() <= limit {
+            of_level.len()
+        } else {
+            limit - 1
+        };
+        let hidden = of_level.split_off(shown);
+        for a in of_level {
+            write_annotation(out, a);
+        }
+        if !hidden.is_empty() {
             let _ = writeln!(
                 out,
-                "::error title=ratchet-{}::Ratchet rule '{}' exceeded budget: {} found, max {}",
-                rule_id, rule_id, found, max
+                "::{} title=baseline-rollup::{}",
+                level,
+                rollup_message(level, limit, &hidden, options.group_overflow)
             );
+            overflow.push((level, hidden));
+        }
+    }
+
+    if options.group_overflow {
+        for (level, hidden) in &overflow {
+            let total = hidden.len();
+            for (i, chunk) in hidden.chunks(GITHUB_GROUP_SIZE).enumerate() {
+                let first = i * GITHUB_GROUP_SIZE + 1;
+                let _ = writeln!(
+                    out,
+                    "::group::baseline: {} {}-{} of {} not annotated",
+                    plural_level(level, total),
+                    first,
+                    first + chunk.len() - 1,
+                    total
+                );
+                for a in chunk {
+                    if !a.place.is_empty() {
+                        let _ = write!(out, "{}  ", a.place);
+                    }
+                    let _ = writeln!(out, "{}  {}", a.title, a.message);
+                }
+                let _ = writeln!(out, "::endgroup::");
+            }
         }
     }
 
     write_summary_stderr(result, err);
+    write_fix_summary_stderr(result.fixes.as_ref(), err);
+}
+
+fn write_annotation(out: &mut dyn Write, a: &Annotation) {
+    match a.location {
+        Some(ref props) => {
+            let _ = writeln!(out, "::{} {},title={}::{}", a.level, props, a.title, a.message);
+        }
+        None => {
+            let _ = writeln!(out, "::{} title={}::{}", a.level, a.title, a.message);
+        }
+    }
+}
+
+fn plural_level(level: &str, n: usize) -> String {
+    if n == 1 {
+        level.to_string()
+    } else {
+        format!("{}s", level)
+    }
+}
+
+/// The annotation standing in for `hidden`: how many, and which rules they
+/// came from, most frequent first.
+fn rollup_message(level: &str, limit: usize, hidden: &[&Annotation], grouped: bool) -> String {
+    let mut by_rule: BTreeMap<&str, usize> = BTreeMap::new();
+    for a in hidden {
+        *by_rule.entry(a.title.as_str()).or_insert(0) += 1;
+    }
+    let mut rules: Vec<_> = by_rule.into_iter().collect();
+    rules.sort_by_key(|&(_, n)| std::cmp::Reverse(n));
+    let listed: Vec<String> = rules
+        .iter()
+        .take(5)
+        .map(|(rule, n)| format!("{} ({})", rule, n))
+        .collect();
+    let more = if rules.len() > 5 {
+        format!(", and {} more rules", rules.len() - 5)
+    } else {
+        String::new()
+    };
+    let see = if grouped {
+        "They are listed in the job log"
+    } else {
+        "Use --annotation-groups to list them in the job log, or --format sarif for code scanning"
+    };
+    format!(
+        "{} more {} not annotated (GitHub shows {} per type per step): {}{}. {}.",
+        hidden.len(),
+        plural_level(level, hidden.len()),
+        limit,
+        listed.join(", "),
+        more,
+        see
+    )
 }
 
 fn write_summary_stderr(result: &ScanResult, err: &mut dyn Write) {
@@ -259,7 +1004,8 @@ fn write_summary_stderr(result: &ScanResult, err: &mut dyn Write) {
         .filter(|v| v.severity == Severity::Warning)
         .count();
 
-    if errors > 0 || warnings > 0 {
+    let informational = informational_parts(result);
+    if errors > 0 || warnings > 0 || !informational.is_empty() {
         let mut parts = Vec::new();
         if errors > 0 {
             parts.push(format!(
@@ -275,27 +1021,588 @@ fn write_summary_stderr(result: &ScanResult, err: &mut dyn Write) {
                 if warnings == 1 { "" } else { "s" }
             ));
         }
+        parts.extend(informational);
         let _ = writeln!(
             err,
-            "{} ({} files scanned, {} rules loaded)",
+            "{} ({})",
             parts.join(", "),
-            result.files_scanned,
-            result.rules_loaded
+            scan_counts(result)
         );
     } else {
         let _ = writeln!(
             err,
-            "No violations found ({} files scanned, {} rules loaded)",
-            result.files_scanned,
-            result.rules_loaded
+            "No violations found ({})",
+            scan_counts(result)
+        );
+    }
+}
+
+/// Print a per-project pass/fail summary to stderr (for `--by-project`).
+pub fn print_project_summary(projects: &[ProjectResult]) {
+    let mut err = std::io::stderr().lock();
+    write_project_summary(projects, &mut err);
+}
+
+fn write_project_summary(projects: &[ProjectResult], err: &mut dyn Write) {
+    if projects.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(err, "\n\x1b[1mProjects:\x1b[0m");
+    for p in projects {
+        let (errors, warnings) = (p.errors(), p.warnings());
+        let status = if errors > 0 {
+            format!(
+                "\x1b[31m✗ {} error{}\x1b[0m, {} warning{}",
+                errors,
+                if errors == 1 { "" } else { "s" },
+                warnings,
+                if warnings == 1 { "" } else { "s" }
+            )
+        } else if warnings > 0 {
+            format!(
+                "\x1b[33m✓ {} warning{}\x1b[0m",
+                warnings,
+                if warnings == 1 { "" } else { "s" }
+            )
+        } else {
+            "\x1b[32m✓ pass\x1b[0m".to_string()
+        };
+        let _ = writeln!(
+            err,
+            "  {:<30} {} ({} files, exit {})",
+            p.project.name,
+            status,
+            p.result.files_scanned,
+            p.exit_code()
+        );
+    }
+}
+
+/// Print a severity-simulation report to stderr (for `--simulate-severity`).
+pub fn print_simulation(result: &ScanResult, sim: &SeveritySimulation) {
+    let mut err = std::io::stderr().lock();
+    write_simulation(result, sim, &mut err);
+}
+
+fn write_simulation(result: &ScanResult, sim: &SeveritySimulation, err: &mut dyn Write) {
+    let current_exit = if result.violations.iter().any(|v| v.severity == Severity::Error) {
+        1
+    } else {
+        0
+    };
+
+    let _ = writeln!(
+        err,
+        "\n\x1b[36m(simulation)\x1b[0m with warnings as errors: {} error{}, {} warning{} → exit {} (currently exit {})",
+        sim.errors,
+        if sim.errors == 1 { "" } else { "s" },
+        sim.warnings,
+        if sim.warnings == 1 { "" } else { "s" },
+        sim.exit_code,
+        current_exit
+    );
+    if sim.promoted > 0 {
+        let _ = writeln!(
+            err,
+            "  {} warning{} promoted from: {}",
+            sim.promoted,
+            if sim.promoted == 1 { "" } else { "s" },
+            sim.promoted_rules.join(", ")
+        );
+    }
+}
+
+/// Print every non-ratchet rule's total to stderr, in the layout of the
+/// ratchet summary (for `--count-all`).
+pub fn print_rule_totals(totals: &[RuleCount]) {
+    let mut err = std::io::stderr().lock();
+    write_rule_totals(totals, &mut err);
+}
+
+fn write_rule_totals(totals: &[RuleCount], err: &mut dyn Write) {
+    if totals.is_empty() {
+        return;
+    }
+
+    let _ = writeln!(err, "\n\x1b[1mRule totals:\x1b[0m");
+    for c in totals {
+        let files = match c.files {
+            0 => String::new(),
+            1 => " in 1 file".to_string(),
+            n => format!(" in {} files", n),
+        };
+        let _ = writeln!(err, "  {:<30} {} ({}{})", c.rule_id, c.severity.as_str(), c.violations, files);
+    }
+}
+
+/// Print phase timings to stderr (for `--timing`).
+pub fn print_timing(result: &ScanResult) {
+    let mut err = std::io::stderr().lock();
+    write_timing(result, &mut err);
+}
+
+fn write_timing(result: &ScanResult, err: &mut dyn Write) {
+    let t = &result.timings;
+    let _ = writeln!(
+        err,
+        "timing: config {:.1}ms, rule build {:.1}ms ({} cached), scan {:.1}ms",
+        t.config_load.as_secs_f64() * 1000.0,
+        t.rule_build.as_secs_f64() * 1000.0,
+        t.cached_rules,
+        t.scan.as_secs_f64() * 1000.0
+    );
+    for g in &t.rule_groups {
+        let mut scope = match g.glob {
+            Some(ref glob) => format!("'{}'", glob),
+            None => "all files".to_string(),
+        };
+        if !g.exclude.is_empty() {
+            scope.push_str(&format!(" excluding '{}'", g.exclude.join("', '")));
+        }
+        let files = match g.file_contains {
+            Some(ref needle) => {
+                scope.push_str(&format!(" containing {:?}", needle));
+                format!("{} of {} matched files", g.files_checked, g.files_matched)
+            }
+            None => format!("{} file{}", g.files_checked, if g.files_checked == 1 { "" } else { "s" }),
+        };
+        let _ = writeln!(
+            err,
+            "timing: group {}: {} rule{} on {} ({})",
+            scope,
+            g.rule_ids.len(),
+            if g.rule_ids.len() == 1 { "" } else { "s" },
+            files,
+            g.rule_ids.join(", ")
+        );
+    }
+}
+
+/// Print a benchmark report (for `baseline bench`): throughput, then the
+/// `top` most expensive rules.
+pub fn print_bench(report: &BenchReport, top: usize) {
+    let mut out = std::io::stdout().lock();
+    write_bench(report, top, &mut out);
+}
+
+fn write_bench(report: &BenchReport, top: usize, out: &mut dyn Write) {
+    let _ = writeln!(
+        out,
+        "{} files, {} rules, {} iteration{}",
+        report.files,
+        report.rules,
+        report.iterations,
+        if report.iterations == 1 { "" } else { "s" }
+    );
+    let _ = writeln!(
+        out,
+        "scan: best {:.1}ms, mean {:.1}ms ({:.0} files/sec)",
+        report.best.as_secs_f64() * 1000.0,
+        report.mean.as_secs_f64() * 1000.0,
+        report.files_per_sec()
+    );
+
+    let total: f64 = report.rule_costs.iter().map(|c| c.time.as_secs_f64()).sum();
+    let _ = writeln!(
+        out,
+        "\n  {:<36} {:<24} {:>10} {:>6} {:>7} {:>10}",
+        "Rule", "Type", "Time", "Share", "Files", "Violations"
+    );
+    for cost in report.rule_costs.iter().take(top) {
+        let secs = cost.time.as_secs_f64();
+        let _ = writeln!(
+            out,
+            "  {:<36} {:<24} {:>8.2}ms {:>5.1}% {:>7} {:>10}",
+            cost.rule_id,
+            cost.rule_type,
+            secs * 1000.0,
+            if total > 0.0 { secs / total * 100.0 } else { 0.0 },
+            cost.files,
+            cost.violations
+        );
+    }
+    if report.rule_costs.len() > top {
+        let _ = writeln!(out, "  \x1b[90m…and {} more\x1b[0m", report.rule_costs.len() - top);
+    }
+}
+
+/// Print the resolved rule list (for `baseline rules`).
+pub fn print_rules(rules: &[TomlRule]) {
+    let mut out = std::io::stdout().lock();
+    write_rules(rules, &mut out);
+}
+
+fn write_rules(rules: &[TomlRule], out: &mut dyn Write) {
+    for rule in rules {
+        if rule.enabled {
+            let _ = writeln!(out, "  {:<30} {:<24} {}", rule.id, rule.rule_type, rule.severity);
+        } else {
+            let _ = writeln!(
+                out,
+                "  \x1b[90m{:<30} {:<24} disabled\x1b[0m",
+                rule.id, rule.rule_type
+            );
+        }
+    }
+
+    let disabled = rules.iter().filter(|r| !r.enabled).count();
+    let _ = writeln!(
+        out,
+        "\n{} rule{} enabled, {} disabled",
+        rules.len() - disabled,
+        if rules.len() - disabled == 1 { "" } else { "s" },
+        disabled
+    );
+}
+
+/// Print the outcome of `baseline check-config`.
+pub fn print_config_check(check: &ConfigCheck) {
+    let mut out = std::io::stdout().lock();
+    write_config_check(check, &mut out);
+}
+
+fn write_config_check(check: &ConfigCheck, out: &mut dyn Write) {
+    for unmatched in &check.unmatched_globs {
+        let _ = writeln!(out, "  \x1b[33m!\x1b[0m {}", unmatched);
+    }
+    if check.unmatched_globs.is_empty() {
+        let _ = writeln!(
+            out,
+            "\x1b[32m✓\x1b[0m {} rule{} built; every glob matches at least one of {} file{}",
+            check.rules_loaded,
+            if check.rules_loaded == 1 { "" } else { "s" },
+            check.files,
+            if check.files == 1 { "" } else { "s" }
+        );
+    } else {
+        let _ = writeln!(
+            out,
+            "\n{} of {} rule{} matched none of {} file{}; check each glob against the project layout",
+            check.unmatched_globs.len(),
+            check.rules_loaded,
+            if check.rules_loaded == 1 { "" } else { "s" },
+            check.files,
+            if check.files == 1 { "" } else { "s" }
+        );
+    }
+}
+
+/// Print what `baseline clean` removed or pruned, or would with `--dry-run`.
+pub fn print_clean(outcomes: &[CleanOutcome], dry_run: bool) {
+    let mut out = std::io::stdout().lock();
+    write_clean(outcomes, dry_run, &mut out);
+}
+
+fn write_clean(outcomes: &[CleanOutcome], dry_run: bool, out: &mut dyn Write) {
+    if outcomes.is_empty() {
+        let _ = writeln!(out, "\x1b[32m✓\x1b[0m Nothing to clean");
+        return;
+    }
+    for outcome in outcomes {
+        let (verb, detail) = match outcome.action {
+            CleanAction::Removed { files } => (
+                if dry_run { "would remove" } else { "removed" },
+                format!("{} file{}", files, if files == 1 { "" } else { "s" }),
+            ),
+            CleanAction::Pruned { removed, kept } => (
+                if dry_run { "would prune" } else { "pruned" },
+                format!("{} stale entr{}, {} kept", removed, if removed == 1 { "y" } else { "ies" }, kept),
+            ),
+        };
+        let _ = writeln!(
+            out,
+            "  {:<13} {:<10} {} \x1b[90m({})\x1b[0m",
+            verb,
+            outcome.artifact.name,
+            outcome.path.display(),
+            detail
+        );
+    }
+    if dry_run {
+        let _ = writeln!(out, "\nDry run; nothing was changed");
+    }
+}
+
+/// Print the component inventory (for `baseline components`). `total` is
+/// the count before `--limit`.
+pub fn print_components(components: &[Component], total: usize, format: ReportFormat) {
+    let mut out = std::io::stdout().lock();
+    write_components(components, total, format, &mut out);
+}
+
+fn write_components(components: &[Component], total: usize, format: ReportFormat, out: &mut dyn Write) {
+    let location = |c: &Component| format!("{}:{}", c.file.display(), c.line);
+    let hooks = |c: &Component| {
+        c.hooks
+            .iter()
+            .map(|(name, n)| if *n == 1 { name.clone() } else { format!("{}×{}", name, n) })
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    match format {
+        ReportFormat::Json => {
+            let components: Vec<_> = components
+                .iter()
+                .map(|c| {
+                    json!({
+                        "name": c.name,
+                        "file": c.file,
+                        "line": c.line,
+                        "lines": c.lines,
+                        "hook_count": c.hook_count(),
+                        "hooks": c.hooks,
+                        "props": c.props,
+                    })
+                })
+                .collect();
+            let report = json!({ "total": total, "components": components });
+            let _ = writeln!(out, "{}", serde_json::to_string_pretty(&report).unwrap());
+        }
+        ReportFormat::Markdown => {
+            let _ = writeln!(out, "| Component | Location | Lines | Hooks | Props |");
+            let _ = writeln!(out, "|---|---|---:|---:|---:|");
+            for c in components {
+                let _ = writeln!(
+                    out,
+                    "| `{}` | `{}` | {} | {} | {} |",
+                    c.name,
+                    location(c),
+                    c.lines,
+                    c.hook_count(),
+                    c.props
+                );
+            }
+        }
+        ReportFormat::Pretty => {
+            let _ = writeln!(out, "  {:<30} {:>6} {:>6} {:>6}  Location", "Component", "Lines", "Hooks", "Props");
+            for c in components {
+                let _ = writeln!(
+                    out,
+                    "  {:<30} {:>6} {:>6} {:>6}  \x1b[4m{}\x1b[0m",
+                    c.name,
+                    c.lines,
+                    c.hook_count(),
+                    c.props,
+                    location(c)
+                );
+                if !c.hooks.is_empty() {
+                    let _ = writeln!(out, "  \x1b[90m{}\x1b[0m", hooks(c));
+                }
+            }
+            let _ = writeln!(out);
+            if components.len() < total {
+                let _ = writeln!(out, "{} of {} components", components.len(), total);
+            } else {
+                let _ = writeln!(out, "{} component{}", total, if total == 1 { "" } else { "s" });
+            }
+        }
+    }
+}
+
+/// Print where a `check-snippet` rule matched, with a caret under each match.
+pub fn print_snippet(result: &SnippetResult, filename: &str) {
+    let mut out = std::io::stdout().lock();
+    write_snippet(result, filename, &mut out);
+    if let Some(ref glob) = result.skipped_by_glob {
+        tracing::warn!(
+            "the rule's glob '{}' doesn't match {}, so a scan would skip this file",
+            glob, filename
+        );
+    }
+}
+
+fn write_snippet(result: &SnippetResult, filename: &str, out: &mut dyn Write) {
+    for v in &result.violations {
+        let line = v.line.unwrap_or(1);
+        let col = v.column_in(ColumnUnit::Chars).unwrap_or(1);
+        let _ = write!(out, "\x1b[4m{}:{}:{}\x1b[0m  \x1b[1m{}\x1b[0m", filename, line, col, v.rule_id);
+        if v.message.is_empty() {
+            let _ = writeln!(out);
+        } else {
+            let _ = writeln!(out, " {}", v.message);
+        }
+        if let Some(ref source) = v.source_line {
+            let _ = writeln!(out, "  {:>4} │ {}", line, source);
+            let _ = writeln!(out, "       │ {}\x1b[31m^\x1b[0m", " ".repeat(col.saturating_sub(1)));
+        }
+    }
+    match result.violations.len() {
+        0 => {
+            let _ = writeln!(out, "No matches");
+        }
+        n => {
+            let _ = writeln!(out, "\n{} match{}", n, if n == 1 { "" } else { "es" });
+        }
+    }
+}
+
+/// Print the rule-level differences between two preset snapshots, each
+/// given with the spec it was loaded from.
+pub fn print_preset_diff(
+    old: (&str, &PresetSnapshot),
+    new: (&str, &PresetSnapshot),
+    changes: &[RuleChange],
+) {
+    let mut out = std::io::stdout().lock();
+    write_preset_diff(old, new, changes, &mut out);
+}
+
+fn write_preset_diff(
+    old: (&str, &PresetSnapshot),
+    new: (&str, &PresetSnapshot),
+    changes: &[RuleChange],
+    out: &mut dyn Write,
+) {
+    let label = |(spec, snapshot): (&str, &PresetSnapshot)| {
+        if snapshot.version.is_empty() {
+            spec.to_string()
+        } else {
+            format!("{} ({})", spec, snapshot.version)
+        }
+    };
+    let _ = writeln!(out, "\x1b[1m{}\x1b[0m → \x1b[1m{}\x1b[0m\n", label(old), label(new));
+
+    let value = |v: &Option<serde_json::Value>| match v {
+        Some(v) => v.to_string(),
+        None => "(unset)".to_string(),
+    };
+    let (mut added, mut removed, mut changed) = (0, 0, 0);
+    for change in changes {
+        match change {
+            RuleChange::Added(rule) => {
+                added += 1;
+                let _ = writeln!(out, "\x1b[32m+ {:<30}\x1b[0m {:<24} {}", rule.id, rule.rule_type, rule.severity);
+            }
+            RuleChange::Removed(rule) => {
+                removed += 1;
+                let _ = writeln!(out, "\x1b[31m- {:<30}\x1b[0m {:<24} {}", rule.id, rule.rule_type, rule.severity);
+            }
+            RuleChange::Changed { id, fields } => {
+                changed += 1;
+                let _ = writeln!(out, "\x1b[33m~ {}\x1b[0m", id);
+                for (field, before, after) in fields {
+                    let _ = writeln!(out, "    {}: {} → {}", field, value(before), value(after));
+                }
+            }
+        }
+    }
+
+    if changes.is_empty() {
+        let _ = writeln!(out, "No rule changes");
+    } else {
+        let _ = writeln!(out, "\n{} added, {} removed, {} changed", added, removed, changed);
+    }
+}
+
+/// Print every available fix as an LSP `TextEdit` with its document URI
+/// (for `--format lsp-edits`).
+pub fn print_lsp_edits(edits: &[TextEdit]) {
+    let mut out = std::io::stdout();
+    write_lsp_edits(edits, &mut out);
+}
+
+fn write_lsp_edits(edits: &[TextEdit], out: &mut dyn Write) {
+    let position = |p: fix::Position| json!({ "line": p.line, "character": p.character });
+    let edits: Vec<_> = edits
+        .iter()
+        .map(|e| {
+            json!({
+                "uri": fix::file_uri(&e.file),
+                "rule_id": e.rule_id,
+                "message": e.message,
+                "title": e.title,
+                "range": { "start": position(e.start), "end": position(e.end) },
+                "newText": e.new_text,
+            })
+        })
+        .collect();
+    let output = json!({ "edits": edits });
+    let _ = writeln!(out, "{}", serde_json::to_string_pretty(&output).unwrap());
+}
+
+/// Print the outcome of `baseline fix`: diffs for a dry run, then applied
+/// counts per rule and anything left unapplied.
+pub fn print_fix(report: &FixReport) {
+    let mut out = std::io::stdout().lock();
+    write_fix(report, &mut out);
+}
+
+fn write_fix(report: &FixReport, out: &mut dyn Write) {
+    for change in &report.changes {
+        for line in change.unified_diff().lines() {
+            let color = match line.as_bytes().first() {
+                _ if line.starts_with("---") || line.starts_with("+++") => "\x1b[1m",
+                Some(b'-') => "\x1b[31m",
+                Some(b'+') => "\x1b[32m",
+                Some(b'@') => "\x1b[36m",
+                _ => "",
+            };
+            let reset = if color.is_empty() { "" } else { "\x1b[0m" };
+            let _ = writeln!(out, "{}{}{}", color, line, reset);
+        }
+        let _ = writeln!(out);
+    }
+
+    if report.outcomes.is_empty() {
+        let _ = writeln!(out, "\x1b[32m✓\x1b[0m Nothing to fix");
+        return;
+    }
+    for (rule_id, count) in report.applied_by_rule() {
+        let _ = writeln!(out, "  {:<32} {}", rule_id, count);
+    }
+    write_fix_summary_pretty(Some(report), out);
+}
+
+/// Print the outcome of `baseline verify`.
+pub fn print_verify(report: &VerifyReport) {
+    let mut out = std::io::stdout().lock();
+    write_verify(report, &mut out);
+}
+
+fn write_verify(report: &VerifyReport, out: &mut dyn Write) {
+    for (label, violations) in [("missing", &report.missing), ("unexpected", &report.unexpected)] {
+        for v in violations {
+            let _ = writeln!(
+                out,
+                "  {:<10} {}  {}:{}  {}  [{}]",
+                label,
+                v.fingerprint(),
+                v.file.display(),
+                v.line.map(|l| l.to_string()).unwrap_or_default(),
+                v.message,
+                v.rule_id
+            );
+        }
+    }
+
+    if report.is_match() {
+        let _ = writeln!(
+            out,
+            "\x1b[32m✓\x1b[0m Scan matches the recorded result ({} violation{})",
+            report.matched,
+            if report.matched == 1 { "" } else { "s" }
+        );
+    } else {
+        let _ = writeln!(
+            out,
+            "\x1b[31m✗\x1b[0m Scan differs from the recorded result: {} missing, {} unexpected, {} matched",
+            report.missing.len(),
+            report.unexpected.len(),
+            report.matched
         );
     }
 }
 
 fn write_ratchet_stderr(
     ratchet_counts: &HashMap<String, (usize, usize)>,
+    deadlines: &HashMap<String, RatchetDeadline>,
+    unevaluated: &[&str],
     err: &mut dyn Write,
 ) {
+    for rule_id in unevaluated {
+        let _ = writeln!(err, "ratchet: {} not evaluated (stopped early)", rule_id);
+    }
     if ratchet_counts.is_empty() {
         return;
     }
@@ -305,7 +1612,15 @@ fn write_ratchet_stderr(
 
     for (rule_id, &(found, max)) in &sorted {
         let status = if found <= max { "pass" } else { "OVER" };
-        let _ = writeln!(err, "ratchet: {} {} ({}/{})", rule_id, status, found, max);
+        let _ = writeln!(
+            err,
+            "ratchet: {} {} ({}/{}{})",
+            rule_id,
+            status,
+            found,
+            max,
+            deadline_suffix(deadlines, rule_id, found)
+        );
     }
 }
 
@@ -316,6 +1631,11 @@ pub fn print_sarif(result: &ScanResult) {
 }
 
 fn write_sarif(result: &ScanResult, out: &mut dyn Write) {
+    // SARIF has no byte column kind, so byte columns go out as code points
+    let (unit, column_kind) = match result.output.column_unit() {
+        ColumnUnit::Utf16 => (ColumnUnit::Utf16, "utf16CodeUnits"),
+        ColumnUnit::Bytes | ColumnUnit::Chars => (ColumnUnit::Chars, "unicodeCodePoints"),
+    };
     // Collect unique rules
     let mut rule_ids: Vec<String> = result
         .violations
@@ -349,6 +1669,7 @@ fn write_sarif(result: &ScanResult, out: &mut dyn Write) {
             let level = match v.severity {
                 Severity::Error => "error",
                 Severity::Warning => "warning",
+                Severity::Info | Severity::Hint => "note",
             };
 
             let location = json!({
@@ -358,7 +1679,7 @@ fn write_sarif(result: &ScanResult, out: &mut dyn Write) {
                     },
                     "region": {
                         "startLine": v.line.unwrap_or(1),
-                        "startColumn": v.column.unwrap_or(1),
+                        "startColumn": v.column_in(unit).unwrap_or(1),
                     }
                 }
             });
@@ -371,30 +1692,36 @@ fn write_sarif(result: &ScanResult, out: &mut dyn Write) {
                 "locations": [location],
             });
 
-            // Add fix if available
-            if let Some(ref fix) = v.fix {
-                result_obj["fixes"] = json!([{
-                    "description": { "text": v.suggest.as_deref().unwrap_or("Apply fix") },
-                    "artifactChanges": [{
-                        "artifactLocation": {
-                            "uri": v.file.display().to_string(),
-                        },
-                        "replacements": [{
-                            "deletedRegion": {
-                                "startLine": v.line.unwrap_or(1),
-                                "startColumn": v.column.unwrap_or(1),
-                            },
-                            "insertedContent": { "text": &fix.new }
-                        }]
-                    }]
-                }]);
+            // Add fixes if available, one per alternative
+            let fixes = v.fixes();
+            if !fixes.is_empty() {
+                result_obj["fixes"] = fixes
+                    .into_iter()
+                    .map(|(title, fix)| {
+                        json!({
+                            "description": { "text": title.unwrap_or("Apply fix") },
+                            "artifactChanges": [{
+                                "artifactLocation": {
+                                    "uri": v.file.display().to_string(),
+                                },
+                                "replacements": [{
+                                    "deletedRegion": {
+                                        "startLine": v.line.unwrap_or(1),
+                                        "startColumn": v.column_in(unit).unwrap_or(1),
+                                    },
+                                    "insertedContent": { "text": &fix.new }
+                                }]
+                            }]
+                        })
+                    })
+                    .collect();
             }
 
             result_obj
         })
         .collect();
 
-    let sarif = json!({
+    let mut sarif = json!({
         "$schema": "https://raw.githubusercontent.com/oasis-tcs/sarif-spec/main/sarif-2.1/schema/sarif-schema-2.1.0.json",
         "version": "2.1.0",
         "runs": [{
@@ -406,21 +1733,118 @@ fn write_sarif(result: &ScanResult, out: &mut dyn Write) {
                     "rules": rules,
                 }
             },
+            "columnKind": column_kind,
             "results": results,
         }]
     });
 
+    // Fix outcomes as tool execution notifications
+    if let Some(ref fixes) = result.fixes {
+        let notifications: Vec<serde_json::Value> = fixes
+            .outcomes
+            .iter()
+            .map(|o| {
+                json!({
+                    "level": if o.status == crate::fix::FixStatus::Applied { "note" } else { "warning" },
+                    "message": { "text": format!("Fix for {}: {}", o.rule_id, o.status.describe()) },
+                    "locations": [{
+                        "physicalLocation": {
+                            "artifactLocation": { "uri": o.file.display().to_string() },
+                            "region": { "startLine": o.line.unwrap_or(1) },
+                        }
+                    }],
+                    "properties": { "status": o.status.as_str() },
+                })
+            })
+            .collect();
+        sarif["runs"][0]["invocations"] = json!([{
+            "executionSuccessful": true,
+            "toolExecutionNotifications": notifications,
+        }]);
+    }
+
     let _ = writeln!(out, "{}", serde_json::to_string_pretty(&sarif).unwrap());
 }
 
-/// Print violations as a Markdown report (for GitHub PR summaries).
-pub fn print_markdown(result: &ScanResult) {
+/// Print violations as a Markdown report (for GitHub PR summaries).
+pub fn print_markdown(result: &ScanResult) {
+    let mut out = std::io::stdout();
+    write_markdown(result, &mut out);
+}
+
+pub fn print_markdown_with_options(result: &ScanResult, options: &DisplayOptions) {
+    let mut out = std::io::stdout();
+    write_markdown_with_options(result, options, &mut out);
+}
+
+fn write_markdown(result: &ScanResult, out: &mut dyn Write) {
+    write_markdown_with_options(result, &DisplayOptions::default(), out);
+}
+
+fn write_markdown_with_options(result: &ScanResult, options: &DisplayOptions, out: &mut dyn Write) {
+    write_markdown_titled(result, "Baseline Report", options, out);
+    write_markdown_fixes(result.fixes.as_ref(), out);
+}
+
+fn write_markdown_fixes(report: Option<&FixReport>, out: &mut dyn Write) {
+    let Some(report) = report else { return };
+
+    let _ = writeln!(out, "### Fixes\n");
+    let _ = writeln!(
+        out,
+        "{}{}\n",
+        if report.dry_run { "_(dry run)_ " } else { "" },
+        fix_counts(report)
+    );
+    if report.unapplied().next().is_none() {
+        return;
+    }
+    let _ = writeln!(out, "| File | Line | Rule | Status |");
+    let _ = writeln!(out, "|------|------|------|--------|");
+    for o in report.unapplied() {
+        let line = o.line.map(|l| l.to_string()).unwrap_or_else(|| "-".to_string());
+        let _ = writeln!(
+            out,
+            "| `{}` | {} | `{}` | {} |",
+            o.file.display(),
+            line,
+            o.rule_id,
+            o.status.describe()
+        );
+    }
+    let _ = writeln!(out);
+}
+
+/// Print the aggregate Markdown report followed by one section per project.
+pub fn print_markdown_by_project(
+    result: &ScanResult,
+    projects: &[ProjectResult],
+    options: &DisplayOptions,
+) {
     let mut out = std::io::stdout();
-    write_markdown(result, &mut out);
+    write_markdown_by_project(result, projects, options, &mut out);
 }
 
-fn write_markdown(result: &ScanResult, out: &mut dyn Write) {
-    let _ = writeln!(out, "## Baseline Report\n");
+fn write_markdown_by_project(
+    result: &ScanResult,
+    projects: &[ProjectResult],
+    options: &DisplayOptions,
+    out: &mut dyn Write,
+) {
+    write_markdown_with_options(result, options, out);
+    for p in projects {
+        let title = format!("{} (`{}`)", p.project.name, p.project.root.display());
+        write_markdown_titled(&p.result, &title, options, out);
+    }
+}
+
+fn write_markdown_titled(
+    result: &ScanResult,
+    title: &str,
+    options: &DisplayOptions,
+    out: &mut dyn Write,
+) {
+    let _ = writeln!(out, "## {}\n", title);
 
     let errors = result
         .violations
@@ -434,8 +1858,9 @@ fn write_markdown(result: &ScanResult, out: &mut dyn Write) {
         .count();
 
     // Summary line
-    if errors == 0 && warnings == 0 {
-        let _ = writeln!(out, "\\:white_check_mark: **No violations found** ({} files scanned, {} rules loaded)\n", result.files_scanned, result.rules_loaded);
+    let informational = informational_parts(result);
+    if errors == 0 && warnings == 0 && informational.is_empty() {
+        let _ = writeln!(out, "\\:white_check_mark: **No violations found** ({})\n", scan_counts(result));
     } else {
         let mut parts = Vec::new();
         if errors > 0 {
@@ -452,6 +1877,7 @@ fn write_markdown(result: &ScanResult, out: &mut dyn Write) {
                 if warnings == 1 { "" } else { "s" }
             ));
         }
+        parts.extend(informational);
         let _ = writeln!(
             out,
             "**{}** in {} files ({} rules loaded)\n",
@@ -477,14 +1903,15 @@ fn write_markdown(result: &ScanResult, out: &mut dyn Write) {
     }
 
     // Group by severity then by file
-    let error_violations: Vec<&Violation> = result
-        .violations
+    let (shown, hidden) = cap_per_rule(&result.violations, options.max_per_rule);
+    let error_violations: Vec<&Violation> = shown
         .iter()
+        .copied()
         .filter(|v| v.severity == Severity::Error)
         .collect();
-    let warning_violations: Vec<&Violation> = result
-        .violations
+    let warning_violations: Vec<&Violation> = shown
         .iter()
+        .copied()
         .filter(|v| v.severity == Severity::Warning)
         .collect();
 
@@ -494,6 +1921,19 @@ fn write_markdown(result: &ScanResult, out: &mut dyn Write) {
     if !warning_violations.is_empty() {
         write_markdown_severity_section(out, "Warnings", &warning_violations);
     }
+    for (severity, title) in [(Severity::Info, "Info"), (Severity::Hint, "Hints")] {
+        let section: Vec<&Violation> = shown
+            .iter()
+            .copied()
+            .filter(|v| v.severity == severity)
+            .collect();
+        if !section.is_empty() {
+            write_markdown_severity_section(out, title, &section);
+        }
+    }
+    for (rule_id, count) in &hidden {
+        let _ = writeln!(out, "> `{}`: …and {} more\n", rule_id, count);
+    }
 
     // Ratchet section
     if !result.ratchet_counts.is_empty() {
@@ -535,88 +1975,26 @@ fn write_markdown_severity_section(out: &mut dyn Write, title: &str, violations:
 
         for v in file_violations {
             let line = v.line.map(|l| l.to_string()).unwrap_or_else(|| "-".to_string());
-            let suggest = v.suggest.as_deref().unwrap_or("");
+            let suggest = v
+                .suggest
+                .iter()
+                .map(|s| s.title.as_str())
+                .collect::<Vec<_>>()
+                .join("<br>");
+            let message = match v.enclosing {
+                Some(ref name) => format!("{} (in `{}`)", v.message, name),
+                None => v.message.clone(),
+            };
             let _ = writeln!(
                 out,
                 "| {} | `{}` | {} | {} |",
-                line, v.rule_id, v.message, suggest
+                line, v.rule_id, message, suggest
             );
         }
         let _ = writeln!(out);
     }
 }
 
-/// Apply fixes from violations to source files. Returns the number of fixes applied.
-/// Fixes are targeted to the specific line where the violation occurred to avoid
-/// accidentally replacing a different occurrence of the same pattern.
-pub fn apply_fixes(result: &ScanResult, dry_run: bool) -> usize {
-    // Group fixable violations by file, keeping line info for targeted replacement
-    let mut fixes_by_file: BTreeMap<String, Vec<(Option<usize>, &str, &str)>> = BTreeMap::new();
-
-    for v in &result.violations {
-        if let Some(ref fix) = v.fix {
-            fixes_by_file
-                .entry(v.file.display().to_string())
-                .or_default()
-                .push((v.line, &fix.old, &fix.new));
-        }
-    }
-
-    let mut total_applied = 0;
-
-    for (file_path, fixes) in &fixes_by_file {
-        let path = Path::new(file_path);
-        let content = match std::fs::read_to_string(path) {
-            Ok(c) => c,
-            Err(_) => continue,
-        };
-
-        let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
-        // Preserve trailing newline if present
-        let trailing_newline = content.ends_with('\n');
-        let mut applied = 0;
-
-        for (line_num, old, new) in fixes {
-            if let Some(ln) = line_num {
-                // Line-targeted: only replace within the specific line (1-indexed)
-                if *ln > 0 && *ln <= lines.len() {
-                    let line = &lines[*ln - 1];
-                    if line.contains(*old) {
-                        lines[*ln - 1] = line.replacen(*old, *new, 1);
-                        applied += 1;
-                    }
-                }
-            } else {
-                // No line info — fall back to first-occurrence replacement
-                let joined = lines.join("\n");
-                if joined.contains(*old) {
-                    let modified = joined.replacen(*old, *new, 1);
-                    lines = modified.lines().map(|l| l.to_string()).collect();
-                    applied += 1;
-                }
-            }
-        }
-
-        if applied > 0 && !dry_run {
-            let mut modified = lines.join("\n");
-            if trailing_newline {
-                modified.push('\n');
-            }
-            if let Err(e) = std::fs::write(path, &modified) {
-                eprintln!(
-                    "\x1b[31merror\x1b[0m: failed to write {}: {}",
-                    file_path, e
-                );
-                continue;
-            }
-        }
-
-        total_applied += applied;
-    }
-
-    total_applied
-}
-
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -627,10 +2005,22 @@ mod tests {
         ScanResult {
             violations,
             files_scanned: 5,
+            generated_skipped: 0,
             rules_loaded: 2,
+            unmatched_globs: Vec::new(),
+            groups: Vec::new(),
+            rules: Vec::new(),
             ratchet_counts: HashMap::new(),
+            ratchet_deadlines: HashMap::new(),
             changed_files_count: None,
             base_ref: None,
+            timings: Default::default(),
+            scanned_files: vec![],
+            file_hashes: HashMap::new(),
+            fixes: None,
+            output: Default::default(),
+            hooks: Default::default(),
+            stopped_early: false,
         }
     }
 
@@ -649,12 +2039,172 @@ mod tests {
             line: Some(line),
             column: Some(col),
             message: message.to_string(),
-            suggest: None,
+            suggest: Vec::new(),
             source_line: None,
             fix: None,
+            origin: None,
+            enclosing: None,
         }
     }
 
+    fn make_projects() -> Vec<ProjectResult> {
+        use crate::projects::{partition, Project};
+        let mut result = make_result(vec![
+            make_violation("packages/web/a.tsx", 1, 1, Severity::Error, "r1", "err msg"),
+            make_violation("packages/api/b.ts", 2, 1, Severity::Warning, "r2", "warn msg"),
+        ]);
+        result.scanned_files = vec![
+            PathBuf::from("packages/web/a.tsx"),
+            PathBuf::from("packages/api/b.ts"),
+        ];
+        let projects = vec![
+            Project { name: "api".into(), root: PathBuf::from("packages/api") },
+            Project { name: "web".into(), root: PathBuf::from("packages/web") },
+        ];
+        partition(&result, &projects)
+    }
+
+    #[test]
+    fn project_summary_lists_each_project() {
+        let projects = make_projects();
+        let mut err = Vec::new();
+        write_project_summary(&projects, &mut err);
+
+        let stderr = String::from_utf8(err).unwrap();
+        assert!(stderr.contains("api"));
+        assert!(stderr.contains("1 warning"));
+        assert!(stderr.contains("(1 files, exit 0)"));
+        assert!(stderr.contains("1 error"));
+        assert!(stderr.contains("exit 1"));
+    }
+
+    #[test]
+    fn rules_list_marks_disabled_rules() {
+        let rules = vec![
+            TomlRule {
+                id: "no-console".into(),
+                rule_type: "banned-pattern".into(),
+                severity: "error".into(),
+                ..Default::default()
+            },
+            TomlRule {
+                id: "use-theme-tokens".into(),
+                rule_type: "tailwind-theme-tokens".into(),
+                enabled: false,
+                ..Default::default()
+            },
+        ];
+        let mut out = Vec::new();
+        write_rules(&rules, &mut out);
+
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+        assert!(lines[0].contains("no-console") && lines[0].ends_with("error"));
+        assert!(lines[1].contains("use-theme-tokens") && lines[1].contains("disabled"));
+        assert!(text.ends_with("1 rule enabled, 1 disabled\n"));
+    }
+
+    #[test]
+    fn json_by_project_includes_mapping() {
+        let projects = make_projects();
+        let result = make_result(vec![]);
+        let mut out = Vec::new();
+        write_json_by_project(&result, &projects, &mut out);
+
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let projects = json["projects"].as_array().unwrap();
+        assert_eq!(projects.len(), 2);
+        assert_eq!(projects[1]["name"], "web");
+        assert_eq!(projects[1]["root"], "packages/web");
+        assert_eq!(projects[1]["exit_code"], 1);
+        assert_eq!(projects[1]["files_with_violations"][0], "packages/web/a.tsx");
+        assert!(json["summary"].is_object());
+    }
+
+    #[test]
+    fn markdown_by_project_has_section_per_project() {
+        let projects = make_projects();
+        let result = make_result(vec![]);
+        let mut out = Vec::new();
+        write_markdown_by_project(&result, &projects, &DisplayOptions::default(), &mut out);
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("## Baseline Report"));
+        assert!(output.contains("## api (`packages/api`)"));
+        assert!(output.contains("## web (`packages/web`)"));
+    }
+
+    #[test]
+    fn simulation_reports_promoted_warnings() {
+        let result = make_result(vec![
+            make_violation("a.ts", 1, 1, Severity::Warning, "no-console", "warn msg"),
+            make_violation("b.ts", 2, 1, Severity::Warning, "no-any", "warn msg"),
+        ]);
+        let sim = crate::scan::simulate_error_severity(&result, &[]);
+        let mut err = Vec::new();
+        write_simulation(&result, &sim, &mut err);
+
+        let stderr = String::from_utf8(err).unwrap();
+        assert!(stderr.contains("2 errors, 0 warnings → exit 1 (currently exit 0)"));
+        assert!(stderr.contains("2 warnings promoted from: no-any, no-console"));
+    }
+
+    #[test]
+    fn json_ratchet_entries_include_scope() {
+        let mut result = make_result(vec![]);
+        result.ratchet_counts.insert("legacy:apps/web".into(), (3, 2));
+        result.ratchet_counts.insert("legacy".into(), (1, 5));
+        let mut out = Vec::new();
+        write_json(&result, &mut out);
+
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let scoped = &json["ratchet"]["legacy:apps/web"];
+        assert_eq!(scoped["rule_id"], "legacy");
+        assert_eq!(scoped["scope"], "apps/web");
+        assert_eq!(scoped["pass"], false);
+        assert!(json["ratchet"]["legacy"]["scope"].is_null());
+    }
+
+    #[test]
+    fn timing_reports_phases() {
+        let mut result = make_result(vec![]);
+        result.timings = crate::scan::ScanTimings {
+            config_load: std::time::Duration::from_millis(2),
+            rule_build: std::time::Duration::from_micros(1500),
+            scan: std::time::Duration::from_millis(10),
+            cached_rules: 3,
+            rule_groups: vec![
+                crate::scan::RuleGroupSummary {
+                    glob: Some("**/*.tsx".into()),
+                    exclude: Vec::new(),
+                    file_contains: Some("'use client'".into()),
+                    rule_ids: vec!["no-async-client".into(), "no-private-env".into()],
+                    files_matched: 12,
+                    files_checked: 4,
+                },
+                crate::scan::RuleGroupSummary {
+                    glob: None,
+                    exclude: vec!["**/test/**".into()],
+                    file_contains: None,
+                    rule_ids: vec!["no-todo".into()],
+                    files_matched: 1,
+                    files_checked: 1,
+                },
+            ],
+        };
+        let mut err = Vec::new();
+        write_timing(&result, &mut err);
+
+        let stderr = String::from_utf8(err).unwrap();
+        assert_eq!(
+            stderr,
+            "timing: config 2.0ms, rule build 1.5ms (3 cached), scan 10.0ms\n\
+             timing: group '**/*.tsx' containing \"'use client'\": 2 rules on 4 of 12 matched files \
+             (no-async-client, no-private-env)\n\
+             timing: group all files excluding '**/test/**': 1 rule on 1 file (no-todo)\n"
+        );
+    }
+
     #[test]
     fn compact_single_error() {
         let result = make_result(vec![make_violation(
@@ -694,6 +2244,32 @@ mod tests {
         assert!(stderr.contains("1 error, 1 warning"));
     }
 
+    #[test]
+    fn problem_matcher_parses_compact_lines() {
+        let result = make_result(vec![
+            make_violation("C:/src/a.ts", 3, 7, Severity::Error, "no-console", "no console: use logger"),
+            make_violation("b.ts", 5, 10, Severity::Hint, "r2", "hint msg"),
+        ]);
+        let mut out = Vec::new();
+        write_compact(&result, &mut out, &mut Vec::new());
+        let stdout = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = stdout.lines().collect();
+
+        let matcher = problem_matcher();
+        let matchers = matcher["problemMatcher"].as_array().unwrap();
+        let regex = |i: usize| regex::Regex::new(matchers[i]["pattern"][0]["regexp"].as_str().unwrap()).unwrap();
+
+        let caps = regex(0).captures(lines[0]).unwrap();
+        assert_eq!(&caps[1], "C:/src/a.ts");
+        assert_eq!((&caps[2], &caps[3], &caps[4]), ("3", "7", "error"));
+        assert_eq!((&caps[5], &caps[6]), ("no-console", "no console: use logger"));
+        assert!(regex(0).captures(lines[1]).is_none());
+
+        let caps = regex(1).captures(lines[1]).unwrap();
+        assert_eq!((&caps[1], &caps[4], &caps[5]), ("b.ts", "hint", "r2"));
+        assert_eq!(matchers[1]["severity"], "notice");
+    }
+
     #[test]
     fn compact_no_violations() {
         let result = make_result(vec![]);
@@ -752,9 +2328,11 @@ mod tests {
             line: Some(3),
             column: None,
             message: "msg".to_string(),
-            suggest: None,
+            suggest: Vec::new(),
             source_line: None,
             fix: None,
+            origin: None,
+            enclosing: None,
         };
         let result = make_result(vec![v]);
         let mut out = Vec::new();
@@ -795,6 +2373,64 @@ mod tests {
         assert!(stdout.is_empty());
     }
 
+    #[test]
+    fn stdin_fix_keeps_stdout_for_the_content() {
+        let result = make_result(vec![make_violation("a.tsx", 2, 1, Severity::Error, "no-eval", "No eval")]);
+        let mut out = Vec::new();
+        let mut err = Vec::new();
+        write_stdin_fix(&result, "const a = 1;\neval(a);\n", &mut out, &mut err);
+
+        assert_eq!(String::from_utf8(out).unwrap(), "const a = 1;\neval(a);\n");
+        let stderr = String::from_utf8(err).unwrap();
+        assert!(stderr.starts_with("a.tsx:2:1: error[no-eval] No eval\n"));
+    }
+
+    #[test]
+    fn github_rolls_up_annotations_past_the_limit() {
+        use crate::git_diff::DiffInfo;
+
+        let mut violations: Vec<Violation> = (1..=12)
+            .map(|i| make_violation("src/old.ts", i, 1, Severity::Warning, "no-any", "Avoid any"))
+            .collect();
+        violations.push(make_violation("src/new.ts", 7, 1, Severity::Warning, "no-console", "No console"));
+        violations.push(make_violation("src/a.ts", 1, 1, Severity::Error, "no-eval", "No eval"));
+        let result = make_result(violations);
+
+        let mut changed_lines = HashMap::new();
+        changed_lines.insert(PathBuf::from("src/new.ts"), vec![5..=9]);
+        let options = GithubOptions {
+            annotation_limit: 5,
+            group_overflow: true,
+            changed: Some(ChangedLines::new(PathBuf::from("/repo"), DiffInfo { changed_lines, ..Default::default() })),
+        };
+        let mut out = Vec::new();
+        let mut err = Vec::new();
+        write_github_with_options(&result, &options, &mut out, &mut err);
+        let stdout = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = stdout.lines().collect();
+
+        // Errors come first; the changed line leads the warnings
+        assert!(lines[0].starts_with("::error file=src/a.ts,line=1"));
+        assert!(lines[1].starts_with("::warning file=src/new.ts,line=7"));
+        assert_eq!(lines.iter().filter(|l| l.starts_with("::warning file=")).count(), 4);
+        assert_eq!(
+            lines[5],
+            "::warning title=baseline-rollup::9 more warnings not annotated (GitHub shows 5 per type per step): \
+             no-any (9). They are listed in the job log."
+        );
+        assert_eq!(lines[6], "::group::baseline: warnings 1-9 of 9 not annotated");
+        assert_eq!(lines[7], "src/old.ts:4  no-any  Avoid any");
+        assert_eq!(lines.last(), Some(&"::endgroup::"));
+
+        let options = GithubOptions {
+            annotation_limit: 0,
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        write_github_with_options(&result, &options, &mut out, &mut err);
+        assert_eq!(String::from_utf8(out).unwrap().lines().count(), 14);
+    }
+
     // ── write_markdown tests ──
 
     #[test]
@@ -879,7 +2515,7 @@ mod tests {
     #[test]
     fn markdown_violation_with_suggestion() {
         let mut v = make_violation("src/a.tsx", 5, 1, Severity::Warning, "theme-tokens", "raw color");
-        v.suggest = Some("Use bg-background instead".into());
+        v.suggest = vec![crate::rules::Suggestion::new("Use bg-background instead")];
         let result = make_result(vec![v]);
         let mut out = Vec::new();
         write_markdown(&result, &mut out);
@@ -888,6 +2524,24 @@ mod tests {
         assert!(output.contains("Use bg-background instead"));
     }
 
+    #[test]
+    fn enclosing_function_shown_in_every_format() {
+        let mut v = make_violation("src/a.tsx", 5, 1, Severity::Warning, "theme-tokens", "raw color");
+        v.enclosing = Some("Cart.render".into());
+        let result = make_result(vec![v]);
+
+        let mut out = Vec::new();
+        write_markdown(&result, &mut out);
+        assert!(String::from_utf8(out).unwrap().contains("| raw color (in `Cart.render`) |"));
+
+        let mut out = Vec::new();
+        write_pretty(&result, &mut out);
+        assert!(String::from_utf8(out).unwrap().contains("raw color\x1b[90m in Cart.render"));
+
+        let json = json_report(&result);
+        assert_eq!(json["violations"][0]["enclosing"], "Cart.render");
+    }
+
     #[test]
     fn markdown_violation_no_line_number() {
         let v = Violation {
@@ -897,9 +2551,11 @@ mod tests {
             line: None,
             column: None,
             message: "README.md missing".into(),
-            suggest: None,
+            suggest: Vec::new(),
             source_line: None,
             fix: None,
+            origin: None,
+            enclosing: None,
         };
         let result = make_result(vec![v]);
         let mut out = Vec::new();
@@ -972,7 +2628,7 @@ mod tests {
     fn ratchet_stderr_empty() {
         let counts = HashMap::new();
         let mut err = Vec::new();
-        write_ratchet_stderr(&counts, &mut err);
+        write_ratchet_stderr(&counts, &HashMap::new(), &[], &mut err);
 
         let stderr = String::from_utf8(err).unwrap();
         assert!(stderr.is_empty());
@@ -984,7 +2640,7 @@ mod tests {
         counts.insert("a-rule".to_string(), (2usize, 5usize));
         counts.insert("b-rule".to_string(), (10, 3));
         let mut err = Vec::new();
-        write_ratchet_stderr(&counts, &mut err);
+        write_ratchet_stderr(&counts, &HashMap::new(), &[], &mut err);
 
         let stderr = String::from_utf8(err).unwrap();
         assert!(stderr.contains("ratchet: a-rule pass (2/5)"));
@@ -1002,9 +2658,11 @@ mod tests {
             line: None,
             column: None,
             message: "msg".to_string(),
-            suggest: None,
+            suggest: Vec::new(),
             source_line: None,
             fix: None,
+            origin: None,
+            enclosing: None,
         };
         let result = make_result(vec![v]);
         let mut out = Vec::new();
@@ -1026,9 +2684,11 @@ mod tests {
             line: None,
             column: None,
             message: "msg".to_string(),
-            suggest: None,
+            suggest: Vec::new(),
             source_line: None,
             fix: None,
+            origin: None,
+            enclosing: None,
         };
         let result = make_result(vec![v]);
         let mut out = Vec::new();
@@ -1040,220 +2700,373 @@ mod tests {
         assert!(!stdout.contains("col="));
     }
 
-    // ── apply_fixes tests ──
+    // ── write_json tests ──
 
     #[test]
-    fn apply_fixes_line_targeted() {
-        let dir = tempfile::tempdir().unwrap();
-        let file = dir.path().join("test.tsx");
-        std::fs::write(&file, "let a = bg-white;\nlet b = bg-white;\n").unwrap();
+    fn json_with_violations_and_ratchet() {
+        let mut v = make_violation("src/a.tsx", 10, 5, Severity::Error, "dark-mode", "missing dark");
+        v.suggest = vec![crate::rules::Suggestion::new("add dark variant")];
+        v.source_line = Some("  <div className=\"bg-white\">".into());
+        v.fix = Some(crate::rules::Fix {
+            old: "bg-white".into(),
+            new: "bg-background".into(),
+        });
 
-        let result = ScanResult {
-            violations: vec![Violation {
-                rule_id: "theme".into(),
-                severity: Severity::Warning,
-                file: file.clone(),
-                line: Some(1),
-                column: Some(9),
-                message: "raw color".into(),
-                suggest: Some("Use bg-background".into()),
-                source_line: None,
-                fix: Some(crate::rules::Fix {
-                    old: "bg-white".into(),
-                    new: "bg-background".into(),
-                }),
+        let mut result = make_result(vec![v]);
+        result.ratchet_counts.insert("legacy".into(), (2, 5));
+
+        let mut out = Vec::new();
+        write_json(&result, &mut out);
+
+        let output = String::from_utf8(out).unwrap();
+        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(parsed["summary"]["total"], 1);
+        assert_eq!(parsed["summary"]["errors"], 1);
+        assert_eq!(parsed["summary"]["warnings"], 0);
+        assert_eq!(parsed["summary"]["files_scanned"], 5);
+        assert_eq!(parsed["summary"]["rules_loaded"], 2);
+        assert_eq!(parsed["violations"][0]["rule_id"], "dark-mode");
+        assert_eq!(parsed["violations"][0]["severity"], "error");
+        assert_eq!(parsed["violations"][0]["suggest"], "add dark variant");
+        assert_eq!(parsed["violations"][0]["fix"]["old"], "bg-white");
+        assert_eq!(parsed["violations"][0]["fix"]["new"], "bg-background");
+        assert!(parsed["ratchet"]["legacy"]["pass"].as_bool().unwrap());
+        assert_eq!(parsed["ratchet"]["legacy"]["found"], 2);
+        assert_eq!(parsed["ratchet"]["legacy"]["max"], 5);
+    }
+
+    #[test]
+    fn snippet_output_marks_each_match() {
+        let mut v = make_violation("snippet.ts", 2, 7, Severity::Error, "snippet", "");
+        v.source_line = Some("const legacyFetch = 1;".into());
+        let result = SnippetResult {
+            violations: vec![v],
+            skipped_by_glob: None,
+        };
+        let mut out = Vec::new();
+        write_snippet(&result, "snippet.ts", &mut out);
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("snippet.ts:2:7"));
+        assert!(output.contains("     2 │ const legacyFetch = 1;\n       │       \x1b[31m^"));
+        assert!(output.ends_with("\n1 match\n"));
+
+        let mut out = Vec::new();
+        write_snippet(&SnippetResult { violations: vec![], skipped_by_glob: None }, "a.ts", &mut out);
+        assert_eq!(String::from_utf8(out).unwrap(), "No matches\n");
+    }
+
+    #[test]
+    fn clean_reports_dry_run_actions() {
+        let outcomes = vec![
+            CleanOutcome {
+                artifact: crate::clean::artifact("cache").unwrap(),
+                path: PathBuf::from(".baseline/cache"),
+                action: CleanAction::Removed { files: 3 },
+            },
+            CleanOutcome {
+                artifact: crate::clean::artifact("snapshot").unwrap(),
+                path: PathBuf::from(".baseline-snapshot.json"),
+                action: CleanAction::Pruned { removed: 1, kept: 2 },
+            },
+        ];
+        let mut out = Vec::new();
+        write_clean(&outcomes, true, &mut out);
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("would remove  cache      .baseline/cache \x1b[90m(3 files)"));
+        assert!(output.contains("would prune   snapshot   .baseline-snapshot.json \x1b[90m(1 stale entry, 2 kept)"));
+        assert!(output.contains("Dry run"));
+
+        let mut out = Vec::new();
+        write_clean(&[], false, &mut out);
+        assert!(String::from_utf8(out).unwrap().contains("Nothing to clean"));
+    }
+
+    #[test]
+    fn config_check_lists_unmatched_globs() {
+        let mut check = ConfigCheck {
+            rules_loaded: 3,
+            files: 12,
+            unmatched_globs: vec![scan::UnmatchedGlob {
+                rule_id: "app-todo".into(),
+                glob: "app/**".into(),
             }],
-            files_scanned: 1,
-            rules_loaded: 1,
-            ratchet_counts: HashMap::new(),
-            changed_files_count: None,
-            base_ref: None,
         };
+        let mut out = Vec::new();
+        write_config_check(&check, &mut out);
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.starts_with("  \x1b[33m!\x1b[0m rule 'app-todo' matched no files (glob 'app/**')\n"));
+        assert!(output.ends_with("1 of 3 rules matched none of 12 files; check each glob against the project layout\n"));
 
-        let count = apply_fixes(&result, false);
-        assert_eq!(count, 1);
+        check.unmatched_globs.clear();
+        let mut out = Vec::new();
+        write_config_check(&check, &mut out);
+        assert!(String::from_utf8(out).unwrap().contains("3 rules built; every glob matches at least one of 12 files"));
+    }
 
-        let content = std::fs::read_to_string(&file).unwrap();
-        // Only line 1 should be fixed
-        assert!(content.starts_with("let a = bg-background;"));
-        assert!(content.contains("let b = bg-white;"));
+    #[test]
+    fn component_report_formats() {
+        let component = Component {
+            name: "Checkout".into(),
+            file: "src/Checkout.tsx".into(),
+            line: 12,
+            lines: 240,
+            hooks: BTreeMap::from([("useEffect".to_string(), 3), ("useState".to_string(), 1)]),
+            props: 7,
+        };
+        let render = |format| {
+            let mut out = Vec::new();
+            write_components(std::slice::from_ref(&component), 4, format, &mut out);
+            String::from_utf8(out).unwrap()
+        };
+        let pretty = render(ReportFormat::Pretty);
+        assert!(pretty.contains("  Checkout                          240      4      7  \x1b[4msrc/Checkout.tsx:12"));
+        assert!(pretty.contains("useEffect×3, useState"));
+        assert!(pretty.ends_with("1 of 4 components\n"));
+        assert!(render(ReportFormat::Markdown).ends_with("| `Checkout` | `src/Checkout.tsx:12` | 240 | 4 | 7 |\n"));
+        let json: serde_json::Value = serde_json::from_str(&render(ReportFormat::Json)).unwrap();
+        assert_eq!(json["total"], 4);
+        assert_eq!(json["components"][0]["hooks"]["useEffect"], 3);
     }
 
     #[test]
-    fn apply_fixes_no_line_fallback() {
-        let dir = tempfile::tempdir().unwrap();
-        let file = dir.path().join("test.tsx");
-        std::fs::write(&file, "bg-white is used here\n").unwrap();
+    fn json_output_matches_output_schema() {
+        let keys = |value: &serde_json::Value| {
+            let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+            keys.sort();
+            keys
+        };
+        let mut result = make_result(vec![make_violation("a.ts", 1, 1, Severity::Error, "no-todo", "todo")]);
+        result.rules = vec![scan::RuleMeta {
+            rule_id: "no-todo".into(),
+            rule_type: "banned-pattern".into(),
+            severity: Severity::Error,
+            glob_restricted: false,
+            conditioned: false,
+            origin: RuleOrigin::User,
+            config: None,
+            fix_gated: false,
+            owner: None,
+            requires_review: false,
+        }];
+        result.ratchet_counts.insert("legacy".into(), (1, 2));
+        let report = json_report(&result);
+        let schema = crate::schema::output_schema();
+
+        assert_eq!(report["version"], JSON_OUTPUT_VERSION);
+        let mut required: Vec<String> = serde_json::from_value(schema["required"].clone()).unwrap();
+        required.sort();
+        assert_eq!(keys(&report), required);
+        let items = |field: &str| &schema["properties"][field]["items"]["properties"];
+        assert_eq!(keys(&report["violations"][0]), keys(items("violations")));
+        assert_eq!(keys(&report["rules"][0]), keys(items("rules")));
+        assert_eq!(keys(&report["summary"]), keys(&schema["properties"]["summary"]["properties"]));
+        let mut ratchet = keys(&schema["properties"]["ratchet"]["additionalProperties"]["properties"]);
+        ratchet.retain(|k| k != "deadline");
+        assert_eq!(keys(&report["ratchet"]["legacy"]), ratchet);
+    }
 
-        let result = ScanResult {
-            violations: vec![Violation {
-                rule_id: "theme".into(),
-                severity: Severity::Warning,
-                file: file.clone(),
-                line: None,
-                column: None,
-                message: "raw color".into(),
-                suggest: None,
-                source_line: None,
-                fix: Some(crate::rules::Fix {
-                    old: "bg-white".into(),
-                    new: "bg-background".into(),
-                }),
-            }],
-            files_scanned: 1,
-            rules_loaded: 1,
-            ratchet_counts: HashMap::new(),
-            changed_files_count: None,
-            base_ref: None,
+    #[test]
+    fn json_rules_include_zero_fired_rules() {
+        let meta = |id: &str, rule_type: &str, glob: bool, conditioned: bool| scan::RuleMeta {
+            rule_id: id.into(),
+            rule_type: rule_type.into(),
+            severity: Severity::Warning,
+            glob_restricted: glob,
+            conditioned,
+            origin: RuleOrigin::User,
+            config: None,
+            fix_gated: false,
+            owner: None,
+            requires_review: false,
         };
+        let mut result = make_result(vec![
+            make_violation("a.ts", 1, 1, Severity::Warning, "no-todo", "todo"),
+            make_violation("b.ts", 2, 1, Severity::Warning, "no-todo", "todo"),
+        ]);
+        result.rules = vec![
+            meta("no-todo", "banned-pattern", false, false),
+            meta("dead-rule", "banned-pattern", true, true),
+            meta("legacy", "ratchet", false, false),
+        ];
+        result.ratchet_counts.insert("legacy".into(), (3, 5));
+        result.ratchet_counts.insert("legacy:src/old".into(), (1, 1));
+
+        let mut out = Vec::new();
+        write_json(&result, &mut out);
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+
+        let rules = parsed["rules"].as_array().unwrap();
+        assert_eq!(rules.len(), 3);
+        assert_eq!(rules[0]["rule_id"], "no-todo");
+        assert_eq!(rules[0]["fired"], 2);
+        assert_eq!(rules[0]["glob_restricted"], false);
+        assert_eq!(rules[1]["rule_id"], "dead-rule");
+        assert_eq!(rules[1]["fired"], 0);
+        assert_eq!(rules[1]["glob_restricted"], true);
+        assert_eq!(rules[1]["conditioned"], true);
+        assert_eq!(rules[2]["type"], "ratchet");
+        assert_eq!(rules[2]["fired"], 4, "ratchet matches within budget still count");
+        assert_eq!(rules[0]["origin"]["kind"], "user");
+    }
+
+    #[test]
+    fn rule_origin_in_json_and_verbose_pretty() {
+        let mut result = make_result(vec![
+            make_violation("a.ts", 1, 1, Severity::Error, "no-img", "use next/image"),
+            make_violation("b.ts", 2, 1, Severity::Warning, "no-todo", "todo"),
+        ]);
+        let meta = |id: &str, origin: RuleOrigin| scan::RuleMeta {
+            rule_id: id.into(),
+            rule_type: "banned-pattern".into(),
+            severity: Severity::Warning,
+            glob_restricted: false,
+            conditioned: false,
+            origin,
+            config: None,
+            fix_gated: false,
+            owner: None,
+            requires_review: false,
+        };
+        result.rules = vec![
+            meta(
+                "no-img",
+                RuleOrigin::ScopedPreset {
+                    preset: "nextjs".into(),
+                    path: "apps/web".into(),
+                },
+            ),
+            meta("no-todo", RuleOrigin::Plugin(PathBuf::from("rules/team.toml"))),
+        ];
+
+        let mut out = Vec::new();
+        write_json(&result, &mut out);
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let origin = &parsed["violations"][0]["origin"];
+        assert_eq!(origin["kind"], "scoped-preset");
+        assert_eq!(origin["preset"], "nextjs");
+        assert_eq!(origin["path"], "apps/web");
+        assert_eq!(parsed["violations"][1]["origin"]["kind"], "plugin");
+        assert_eq!(parsed["rules"][1]["origin"]["path"], "rules/team.toml");
+
+        let mut out = Vec::new();
+        write_pretty(&result, &mut out);
+        assert!(!String::from_utf8(out).unwrap().contains("from "));
+
+        let options = DisplayOptions {
+            verbose: true,
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        write_pretty_with_options(&result, &options, &mut out);
+        let pretty = String::from_utf8(out).unwrap();
+        assert!(pretty.contains("from preset nextjs scoped to apps/web"));
+        assert!(pretty.contains("from plugin rules/team.toml"));
+    }
 
-        let count = apply_fixes(&result, false);
-        assert_eq!(count, 1);
+    #[test]
+    fn origin_is_shown_for_multi_config_scans() {
+        let mut v = make_violation("a.ts", 1, 1, Severity::Error, "r1", "msg");
+        v.origin = Some(PathBuf::from("platform.toml"));
+        let result = make_result(vec![v]);
+
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        write_compact(&result, &mut out, &mut err);
+        assert!(String::from_utf8(out).unwrap().contains("error[r1] msg (platform.toml)"));
 
-        let content = std::fs::read_to_string(&file).unwrap();
-        assert!(content.contains("bg-background"));
+        let mut out = Vec::new();
+        write_json(&result, &mut out);
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(parsed["violations"][0]["config"], "platform.toml");
+
+        let mut out = Vec::new();
+        write_json(&make_result(vec![make_violation("a.ts", 1, 1, Severity::Error, "r1", "m")]), &mut out);
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert!(parsed["violations"][0]["config"].is_null());
     }
 
     #[test]
-    fn apply_fixes_dry_run_no_write() {
-        let dir = tempfile::tempdir().unwrap();
-        let file = dir.path().join("test.tsx");
-        std::fs::write(&file, "bg-white\n").unwrap();
+    fn json_violations_carry_fingerprints() {
+        let v = make_violation("a.ts", 1, 1, Severity::Error, "r1", "msg");
+        let fingerprint = v.fingerprint();
+        let mut out = Vec::new();
+        write_json(&make_result(vec![v]), &mut out);
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(parsed["violations"][0]["fingerprint"], fingerprint);
+    }
 
-        let result = ScanResult {
-            violations: vec![Violation {
-                rule_id: "theme".into(),
-                severity: Severity::Warning,
-                file: file.clone(),
-                line: Some(1),
-                column: Some(1),
-                message: "raw color".into(),
-                suggest: None,
-                source_line: None,
-                fix: Some(crate::rules::Fix {
-                    old: "bg-white".into(),
-                    new: "bg-background".into(),
-                }),
-            }],
-            files_scanned: 1,
-            rules_loaded: 1,
-            ratchet_counts: HashMap::new(),
-            changed_files_count: None,
-            base_ref: None,
+    #[test]
+    fn lsp_edits_are_text_edit_shaped() {
+        let edit = TextEdit {
+            file: PathBuf::from("/repo/src/a.tsx"),
+            rule_id: "theme".into(),
+            message: "use tokens".into(),
+            title: Some("Use bg-background".into()),
+            start: fix::Position { line: 2, character: 4 },
+            end: fix::Position { line: 2, character: 12 },
+            new_text: "bg-background".into(),
         };
+        let mut out = Vec::new();
+        write_lsp_edits(&[edit], &mut out);
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
 
-        let count = apply_fixes(&result, true);
-        assert_eq!(count, 1);
-
-        // File should not be modified
-        let content = std::fs::read_to_string(&file).unwrap();
-        assert!(content.contains("bg-white"));
+        let e = &parsed["edits"][0];
+        assert_eq!(e["uri"], "file:///repo/src/a.tsx");
+        assert_eq!(e["rule_id"], "theme");
+        assert_eq!(e["range"]["start"], json!({ "line": 2, "character": 4 }));
+        assert_eq!(e["range"]["end"], json!({ "line": 2, "character": 12 }));
+        assert_eq!(e["newText"], "bg-background");
+        assert_eq!(e["title"], "Use bg-background");
     }
 
     #[test]
-    fn apply_fixes_no_fixable_violations() {
-        let result = make_result(vec![
-            make_violation("a.ts", 1, 1, Severity::Error, "r1", "msg"),
-        ]);
-        let count = apply_fixes(&result, false);
-        assert_eq!(count, 0);
-    }
-
-    #[test]
-    fn apply_fixes_preserves_trailing_newline() {
-        let dir = tempfile::tempdir().unwrap();
-        let file = dir.path().join("test.tsx");
-        std::fs::write(&file, "bg-white\n").unwrap();
-
-        let result = ScanResult {
-            violations: vec![Violation {
-                rule_id: "theme".into(),
-                severity: Severity::Warning,
-                file: file.clone(),
-                line: Some(1),
-                column: Some(1),
-                message: "raw color".into(),
-                suggest: None,
-                source_line: None,
-                fix: Some(crate::rules::Fix {
-                    old: "bg-white".into(),
-                    new: "bg-background".into(),
-                }),
-            }],
-            files_scanned: 1,
-            rules_loaded: 1,
-            ratchet_counts: HashMap::new(),
-            changed_files_count: None,
-            base_ref: None,
+    fn bench_report_lists_costliest_rules() {
+        use crate::bench::RuleCost;
+        use std::time::Duration;
+        let cost = |id: &str, ms: u64| RuleCost {
+            rule_id: id.into(),
+            rule_type: "banned-pattern".into(),
+            time: Duration::from_millis(ms),
+            files: 10,
+            violations: 2,
         };
-
-        apply_fixes(&result, false);
-        let content = std::fs::read_to_string(&file).unwrap();
-        assert!(content.ends_with('\n'));
-    }
-
-    #[test]
-    fn apply_fixes_nonexistent_file_skipped() {
-        let result = ScanResult {
-            violations: vec![Violation {
-                rule_id: "theme".into(),
-                severity: Severity::Warning,
-                file: PathBuf::from("/nonexistent/file.tsx"),
-                line: Some(1),
-                column: Some(1),
-                message: "msg".into(),
-                suggest: None,
-                source_line: None,
-                fix: Some(crate::rules::Fix {
-                    old: "old".into(),
-                    new: "new".into(),
-                }),
-            }],
-            files_scanned: 1,
-            rules_loaded: 1,
-            ratchet_counts: HashMap::new(),
-            changed_files_count: None,
-            base_ref: None,
+        let report = BenchReport {
+            files: 100,
+            rules: 3,
+            iterations: 2,
+            best: Duration::from_millis(50),
+            mean: Duration::from_millis(60),
+            rule_costs: vec![cost("slow", 30), cost("medium", 10), cost("fast", 0)],
         };
+        let mut out = Vec::new();
+        write_bench(&report, 2, &mut out);
+        let text = String::from_utf8(out).unwrap();
 
-        let count = apply_fixes(&result, false);
-        assert_eq!(count, 0);
+        assert!(text.contains("100 files, 3 rules, 2 iterations"));
+        assert!(text.contains("best 50.0ms, mean 60.0ms (2000 files/sec)"));
+        assert!(text.contains("slow"));
+        assert!(text.contains("75.0%"));
+        assert!(!text.contains("fast"));
+        assert!(text.contains("and 1 more"));
     }
 
-    // ── write_json tests ──
-
     #[test]
-    fn json_with_violations_and_ratchet() {
-        let mut v = make_violation("src/a.tsx", 10, 5, Severity::Error, "dark-mode", "missing dark");
-        v.suggest = Some("add dark variant".into());
-        v.source_line = Some("  <div className=\"bg-white\">".into());
-        v.fix = Some(crate::rules::Fix {
-            old: "bg-white".into(),
-            new: "bg-background".into(),
-        });
-
-        let mut result = make_result(vec![v]);
-        result.ratchet_counts.insert("legacy".into(), (2, 5));
-
+    fn verify_lists_differences() {
+        let report = VerifyReport {
+            missing: vec![make_violation("a.ts", 3, 1, Severity::Error, "r1", "gone")],
+            unexpected: vec![],
+            matched: 2,
+        };
         let mut out = Vec::new();
-        write_json(&result, &mut out);
-
-        let output = String::from_utf8(out).unwrap();
-        let parsed: serde_json::Value = serde_json::from_str(&output).unwrap();
+        write_verify(&report, &mut out);
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("missing"));
+        assert!(text.contains("a.ts:3  gone  [r1]"));
+        assert!(text.contains("1 missing, 0 unexpected, 2 matched"));
 
-        assert_eq!(parsed["summary"]["total"], 1);
-        assert_eq!(parsed["summary"]["errors"], 1);
-        assert_eq!(parsed["summary"]["warnings"], 0);
-        assert_eq!(parsed["summary"]["files_scanned"], 5);
-        assert_eq!(parsed["summary"]["rules_loaded"], 2);
-        assert_eq!(parsed["violations"][0]["rule_id"], "dark-mode");
-        assert_eq!(parsed["violations"][0]["severity"], "error");
-        assert_eq!(parsed["violations"][0]["suggest"], "add dark variant");
-        assert_eq!(parsed["violations"][0]["fix"]["old"], "bg-white");
-        assert_eq!(parsed["violations"][0]["fix"]["new"], "bg-background");
-        assert!(parsed["ratchet"]["legacy"]["pass"].as_bool().unwrap());
-        assert_eq!(parsed["ratchet"]["legacy"]["found"], 2);
-        assert_eq!(parsed["ratchet"]["legacy"]["max"], 5);
+        let mut out = Vec::new();
+        write_verify(&VerifyReport { matched: 1, ..Default::default() }, &mut out);
+        assert!(String::from_utf8(out).unwrap().contains("matches the recorded result (1 violation)"));
     }
 
     #[test]
@@ -1284,6 +3097,30 @@ mod tests {
         assert_eq!(parsed["summary"]["warnings"], 1);
     }
 
+    #[test]
+    fn json_lists_every_suggestion() {
+        let mut v = make_violation("a.tsx", 1, 1, Severity::Error, "r1", "msg");
+        v.suggest = vec![
+            crate::rules::Suggestion::with_fix(
+                "Use bg-background",
+                crate::rules::Fix {
+                    old: "bg-white".into(),
+                    new: "bg-background".into(),
+                },
+            ),
+            crate::rules::Suggestion::new("Add a dark: variant"),
+        ];
+        let mut out = Vec::new();
+        write_json(&make_result(vec![v]), &mut out);
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+
+        let violation = &parsed["violations"][0];
+        assert_eq!(violation["suggest"], "Use bg-background");
+        assert_eq!(violation["suggestions"][0]["fix"]["new"], "bg-background");
+        assert_eq!(violation["suggestions"][1]["title"], "Add a dark: variant");
+        assert!(violation["suggestions"][1]["fix"].is_null());
+    }
+
     #[test]
     fn json_violation_without_fix() {
         let result = make_result(vec![
@@ -1307,7 +3144,7 @@ mod tests {
             old: "bg-white".into(),
             new: "bg-background".into(),
         });
-        v.suggest = Some("Use bg-background".into());
+        v.suggest = vec![crate::rules::Suggestion::new("Use bg-background")];
 
         let result = make_result(vec![
             v,
@@ -1344,6 +3181,36 @@ mod tests {
         assert!(results[1].get("fixes").is_none());
     }
 
+    #[test]
+    fn column_unit_applies_to_compact_and_sarif() {
+        // "é" is two bytes, one char, one UTF-16 unit; "😀" is four, one, two
+        let mut v = make_violation("src/a.tsx", 1, 20, Severity::Error, "r", "msg");
+        v.source_line = Some("const é = '😀'; bg-white".into());
+        let mut result = make_result(vec![v]);
+
+        let compact = |result: &ScanResult| {
+            let (mut out, mut err) = (Vec::new(), Vec::new());
+            write_compact(result, &mut out, &mut err);
+            String::from_utf8(out).unwrap()
+        };
+        let sarif = |result: &ScanResult| {
+            let mut out = Vec::new();
+            write_sarif(result, &mut out);
+            serde_json::from_slice::<serde_json::Value>(&out).unwrap()["runs"][0].clone()
+        };
+
+        assert!(compact(&result).starts_with("src/a.tsx:1:20:"));
+        let run = sarif(&result);
+        assert_eq!(run["columnKind"], "unicodeCodePoints");
+        assert_eq!(run["results"][0]["locations"][0]["physicalLocation"]["region"]["startColumn"], 16);
+
+        result.output.column_unit = Some(ColumnUnit::Utf16);
+        assert!(compact(&result).starts_with("src/a.tsx:1:17:"));
+        let run = sarif(&result);
+        assert_eq!(run["columnKind"], "utf16CodeUnits");
+        assert_eq!(run["results"][0]["locations"][0]["physicalLocation"]["region"]["startColumn"], 17);
+    }
+
     #[test]
     fn sarif_empty_violations() {
         let result = make_result(vec![]);
@@ -1364,7 +3231,7 @@ mod tests {
             old: "old".into(),
             new: "new".into(),
         });
-        // v.suggest is None
+        // v.suggest is empty
 
         let result = make_result(vec![v]);
         let mut out = Vec::new();
@@ -1379,6 +3246,28 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sarif_offers_one_fix_per_suggestion() {
+        let fix = |new: &str| crate::rules::Fix {
+            old: "bg-white".into(),
+            new: new.into(),
+        };
+        let mut v = make_violation("a.tsx", 1, 1, Severity::Error, "r1", "msg");
+        v.fix = Some(fix("bg-background"));
+        v.suggest = vec![
+            crate::rules::Suggestion::with_fix("Use bg-background", fix("bg-background")),
+            crate::rules::Suggestion::with_fix("Use bg-card", fix("bg-card")),
+        ];
+
+        let mut out = Vec::new();
+        write_sarif(&make_result(vec![v]), &mut out);
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+
+        let fixes = parsed["runs"][0]["results"][0]["fixes"].as_array().unwrap();
+        let described: Vec<_> = fixes.iter().map(|f| f["description"]["text"].clone()).collect();
+        assert_eq!(described, [json!("Use bg-background"), json!("Use bg-card")]);
+    }
+
     #[test]
     fn sarif_missing_line_col_defaults_to_1() {
         let v = Violation {
@@ -1388,9 +3277,11 @@ mod tests {
             line: None,
             column: None,
             message: "msg".into(),
-            suggest: None,
+            suggest: Vec::new(),
             source_line: None,
             fix: None,
+            origin: None,
+            enclosing: None,
         };
         let result = make_result(vec![v]);
         let mut out = Vec::new();
@@ -1474,6 +3365,99 @@ mod tests {
         assert!(output.contains("### Warnings"));
     }
 
+    // ── summary ──
+
+    #[test]
+    fn summary_lists_rule_counts_without_violations() {
+        let mut result = make_result(vec![
+            make_violation("a.ts", 1, 1, Severity::Warning, "noisy", "noisy message"),
+            make_violation("b.ts", 2, 1, Severity::Warning, "noisy", "noisy message"),
+            make_violation("b.ts", 3, 1, Severity::Error, "quiet", "quiet message"),
+        ]);
+        result.ratchet_counts.insert("legacy".into(), (3, 5));
+        let mut out = Vec::new();
+        write_summary(&result, &mut out);
+
+        let output = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+        assert!(lines[1].starts_with("noisy") && lines[1].ends_with("2      2"));
+        assert!(lines[2].starts_with("quiet") && lines[2].contains("error"));
+        assert!(output.contains("legacy"));
+        assert!(output.contains("1 error, 2 warnings (5 files scanned, 2 rules loaded)"));
+
+        result.generated_skipped = 3;
+        let mut out = Vec::new();
+        write_summary(&result, &mut out);
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("(5 files scanned, 2 rules loaded, 3 generated files skipped)"));
+        assert!(!output.contains("message"));
+
+        result.groups = vec!["security".into(), "perf".into()];
+        let mut out = Vec::new();
+        write_summary(&result, &mut out);
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("(5 files scanned, 2 rules loaded from groups security + perf, 3 generated"));
+        assert!(!output.contains("a.ts"));
+    }
+
+    #[test]
+    fn summary_without_violations_reports_zero_totals() {
+        let mut out = Vec::new();
+        write_summary(&make_result(vec![]), &mut out);
+        assert_eq!(
+            String::from_utf8(out).unwrap(),
+            "\n0 errors, 0 warnings (5 files scanned, 2 rules loaded)\n"
+        );
+    }
+
+    // ── max per rule ──
+
+    fn noisy_result() -> ScanResult {
+        let mut violations: Vec<Violation> = (1..=5)
+            .map(|i| make_violation("a.ts", i, 1, Severity::Warning, "noisy", "n"))
+            .collect();
+        violations.push(make_violation("b.ts", 1, 1, Severity::Error, "quiet", "q"));
+        make_result(violations)
+    }
+
+    #[test]
+    fn max_per_rule_caps_pretty_and_markdown() {
+        let result = noisy_result();
+        let options = DisplayOptions {
+            max_per_rule: Some(2),
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        write_pretty_with_options(&result, &options, &mut out);
+        let pretty = String::from_utf8(out).unwrap();
+        assert_eq!(pretty.matches("noisy").count(), 3);
+        assert!(pretty.contains("…and 3 more"));
+        assert!(pretty.contains("quiet"));
+        assert!(pretty.contains("5 warnings"));
+
+        let mut out = Vec::new();
+        write_markdown_with_options(&result, &options, &mut out);
+        let markdown = String::from_utf8(out).unwrap();
+        assert_eq!(markdown.matches("| `noisy` |").count(), 2);
+        assert!(markdown.contains("> `noisy`: …and 3 more"));
+        assert!(!markdown.contains("`quiet`: …"));
+        assert!(markdown.contains("5 warnings"));
+    }
+
+    #[test]
+    fn max_per_rule_leaves_json_complete() {
+        let result = noisy_result();
+        let mut out = Vec::new();
+        write_pretty(&result, &mut out);
+        assert!(!String::from_utf8(out).unwrap().contains("more"));
+
+        let mut out = Vec::new();
+        write_json(&result, &mut out);
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json["violations"].as_array().unwrap().len(), 6);
+    }
+
     // ── markdown ratchet only (no violations) ──
 
     #[test]
@@ -1507,58 +3491,42 @@ mod tests {
         assert!(!stderr.contains("warnings"));
     }
 
-    // ── apply_fixes with multiple fixes in same file ──
-
-    #[test]
-    fn apply_fixes_multiple_in_same_file() {
-        let dir = tempfile::tempdir().unwrap();
-        let file = dir.path().join("test.tsx");
-        std::fs::write(&file, "bg-white text-gray-900\nbg-white text-gray-500\n").unwrap();
-
-        let result = ScanResult {
-            violations: vec![
-                Violation {
-                    rule_id: "theme".into(),
-                    severity: Severity::Warning,
-                    file: file.clone(),
-                    line: Some(1),
-                    column: Some(1),
-                    message: "raw color".into(),
-                    suggest: None,
-                    source_line: None,
-                    fix: Some(crate::rules::Fix {
-                        old: "bg-white".into(),
-                        new: "bg-background".into(),
-                    }),
-                },
-                Violation {
-                    rule_id: "theme".into(),
-                    severity: Severity::Warning,
-                    file: file.clone(),
-                    line: Some(2),
-                    column: Some(1),
-                    message: "raw color".into(),
-                    suggest: None,
-                    source_line: None,
-                    fix: Some(crate::rules::Fix {
-                        old: "bg-white".into(),
-                        new: "bg-background".into(),
-                    }),
-                },
-            ],
-            files_scanned: 1,
-            rules_loaded: 1,
-            ratchet_counts: HashMap::new(),
-            changed_files_count: None,
-            base_ref: None,
-        };
+    #[test]
+    fn pretty_rolls_up_violations_by_directory() {
+        let result = make_result(vec![
+            make_violation("./packages/ui/button.tsx", 1, 1, Severity::Warning, "r2", "w"),
+            make_violation("apps/web/app/page.tsx", 1, 1, Severity::Error, "r1", "e"),
+            make_violation("apps/web/lib/db.ts", 4, 1, Severity::Error, "r1", "e"),
+            make_violation("apps/docs/index.md", 2, 1, Severity::Info, "r3", "i"),
+            make_violation("vite.config.ts", 1, 1, Severity::Error, "r1", "e"),
+        ]);
+        let rollup = directory_rollup(&result.violations, 2);
+        let summary: Vec<(&str, [usize; 4])> = rollup.iter().map(|(d, c)| (d.as_str(), *c)).collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("apps/web", [2, 0, 0, 0]),
+                (".", [1, 0, 0, 0]),
+                ("packages/ui", [0, 1, 0, 0]),
+                ("apps/docs", [0, 0, 1, 0]),
+            ]
+        );
+        assert_eq!(directory_rollup(&result.violations, 1)[0], ("apps".to_string(), [2, 0, 1, 0]));
 
-        let count = apply_fixes(&result, false);
-        assert_eq!(count, 2);
+        let mut out = Vec::new();
+        write_pretty(&result, &mut out);
+        assert!(!String::from_utf8(out).unwrap().contains("By directory:"));
 
-        let content = std::fs::read_to_string(&file).unwrap();
-        assert!(!content.contains("bg-white"));
-        assert_eq!(content.matches("bg-background").count(), 2);
+        let options = DisplayOptions {
+            by_directory: Some(1),
+            ..Default::default()
+        };
+        let mut out = Vec::new();
+        write_pretty_with_options(&result, &options, &mut out);
+        let output = String::from_utf8(out).unwrap();
+        let rollup = output.split("By directory:").nth(1).unwrap();
+        assert!(rollup.contains("apps                           \x1b[31m2 errors\x1b[0m, \x1b[36m1 info\x1b[0m"));
+        assert!(rollup.contains("packages                       \x1b[33m1 warning\x1b[0m"));
     }
 
     // ── write_pretty tests ──
@@ -1575,6 +3543,39 @@ mod tests {
         assert!(output.contains("2 rules loaded"));
     }
 
+    #[test]
+    fn pretty_uses_catalog_strings() {
+        let catalog: Catalog = toml::from_str(
+            r#"
+[messages]
+no-violations = "違反は見つかりませんでした"
+errors = "{count} 件のエラー"
+scan-counts = "{files} ファイル, {rules} ルール"
+"#,
+        )
+        .unwrap();
+        let options = DisplayOptions {
+            catalog,
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        write_pretty_with_options(&make_result(vec![]), &options, &mut out);
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("違反は見つかりませんでした (5 ファイル, 2 ルール)"));
+
+        let result = make_result(vec![
+            make_violation("a.ts", 1, 1, Severity::Error, "r1", "e1"),
+            make_violation("a.ts", 2, 1, Severity::Error, "r1", "e2"),
+            make_violation("a.ts", 3, 1, Severity::Warning, "r2", "w1"),
+        ]);
+        let mut out = Vec::new();
+        write_pretty_with_options(&result, &options, &mut out);
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("2 件のエラー"));
+        assert!(output.contains("1 warning"), "untranslated strings fall back to English");
+    }
+
     #[test]
     fn pretty_with_error_and_warning() {
         let result = make_result(vec![
@@ -1633,10 +3634,26 @@ mod tests {
         assert!(output.contains("<div className=\"bg-white\">"));
     }
 
+    #[test]
+    fn pretty_numbers_alternative_suggestions() {
+        let mut v = make_violation("a.tsx", 5, 1, Severity::Error, "r1", "msg");
+        v.suggest = vec![
+            crate::rules::Suggestion::new("Add alt text"),
+            crate::rules::Suggestion::new("Mark it decorative"),
+        ];
+        let result = make_result(vec![v]);
+        let mut out = Vec::new();
+        write_pretty(&result, &mut out);
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("└─\x1b[0m \x1b[36m1. Add alt text"));
+        assert!(output.contains("  \x1b[0m \x1b[36m2. Mark it decorative"));
+    }
+
     #[test]
     fn pretty_with_suggestion() {
         let mut v = make_violation("a.tsx", 5, 1, Severity::Error, "r1", "msg");
-        v.suggest = Some("Use bg-background instead".into());
+        v.suggest = vec![crate::rules::Suggestion::new("Use bg-background instead")];
         let result = make_result(vec![v]);
         let mut out = Vec::new();
         write_pretty(&result, &mut out);
@@ -1654,9 +3671,11 @@ mod tests {
             line: Some(7),
             column: None,
             message: "msg".into(),
-            suggest: None,
+            suggest: Vec::new(),
             source_line: None,
             fix: None,
+            origin: None,
+            enclosing: None,
         };
         let result = make_result(vec![v]);
         let mut out = Vec::new();
@@ -1675,9 +3694,11 @@ mod tests {
             line: None,
             column: None,
             message: "msg".into(),
-            suggest: None,
+            suggest: Vec::new(),
             source_line: None,
             fix: None,
+            origin: None,
+            enclosing: None,
         };
         let result = make_result(vec![v]);
         let mut out = Vec::new();
@@ -1718,13 +3739,48 @@ mod tests {
         assert!(output.contains("pass"));
     }
 
+    #[test]
+    fn ratchet_deadline_in_pretty_and_json() {
+        let mut result = make_result(vec![]);
+        result.ratchet_counts.insert("legacy:apps/web".into(), (10, 12));
+        let deadline = RatchetDeadline::new("2025-09-01", 0, crate::deadline::parse_date("2025-08-04").unwrap());
+        result.ratchet_deadlines.insert("legacy".into(), deadline.unwrap());
+
+        let mut out = Vec::new();
+        write_pretty(&result, &mut out);
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("(10/12, 28 days left, 3/week)"), "{}", output);
+
+        let mut out = Vec::new();
+        write_json(&result, &mut out);
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let entry = &json["ratchet"]["legacy:apps/web"]["deadline"];
+        assert_eq!(entry["date"], "2025-09-01");
+        assert_eq!(entry["days_remaining"], 28);
+        assert_eq!(entry["weekly_rate"], 3);
+    }
+
     // ── write_ratchet_summary_pretty tests ──
 
+    #[test]
+    fn ratchet_summary_marks_unevaluated_ratchets() {
+        let mut out = Vec::new();
+        write_ratchet_summary_pretty(&HashMap::new(), &HashMap::new(), &["legacy-api"], &mut out);
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("legacy-api"), "{}", output);
+        assert!(output.contains("not evaluated (stopped early)"), "{}", output);
+        assert!(!output.contains("pass"), "{}", output);
+
+        let mut err = Vec::new();
+        write_ratchet_stderr(&HashMap::new(), &HashMap::new(), &["legacy-api"], &mut err);
+        assert_eq!(String::from_utf8(err).unwrap(), "ratchet: legacy-api not evaluated (stopped early)\n");
+    }
+
     #[test]
     fn ratchet_summary_pretty_empty() {
         let counts = HashMap::new();
         let mut out = Vec::new();
-        write_ratchet_summary_pretty(&counts, &mut out);
+        write_ratchet_summary_pretty(&counts, &HashMap::new(), &[], &mut out);
 
         let output = String::from_utf8(out).unwrap();
         assert!(output.is_empty());
@@ -1736,7 +3792,7 @@ mod tests {
         counts.insert("a-rule".to_string(), (2usize, 5usize));
         counts.insert("b-rule".to_string(), (10, 3));
         let mut out = Vec::new();
-        write_ratchet_summary_pretty(&counts, &mut out);
+        write_ratchet_summary_pretty(&counts, &HashMap::new(), &[], &mut out);
 
         let output = String::from_utf8(out).unwrap();
         assert!(output.contains("Ratchet rules:"));
@@ -1748,6 +3804,28 @@ mod tests {
         assert!(output.contains("(10/3)"));
     }
 
+    #[test]
+    fn rule_totals_use_the_ratchet_layout() {
+        let count = |rule_id: &str, violations, files| RuleCount {
+            rule_id: rule_id.into(),
+            severity: Severity::Warning,
+            violations,
+            files,
+        };
+        let mut err = Vec::new();
+        write_rule_totals(&[count("no-any", 12, 4), count("no-todo", 1, 1), count("quiet", 0, 0)], &mut err);
+
+        let output = String::from_utf8(err).unwrap();
+        assert!(output.contains("Rule totals:"));
+        assert!(output.contains(&format!("  {:<30} warning (12 in 4 files)", "no-any")));
+        assert!(output.contains(&format!("  {:<30} warning (1 in 1 file)", "no-todo")));
+        assert!(output.contains(&format!("  {:<30} warning (0)", "quiet")));
+
+        let mut err = Vec::new();
+        write_rule_totals(&[], &mut err);
+        assert!(err.is_empty());
+    }
+
     #[test]
     fn pretty_no_violations_with_ratchet() {
         let mut result = make_result(vec![]);
@@ -1788,4 +3866,152 @@ mod tests {
         assert!(output.contains("1 error"));
         assert!(output.contains("1 warning"));
     }
+
+    // ── fix report tests ──
+
+    fn with_fix_report(dry_run: bool) -> ScanResult {
+        use crate::fix::{FixOutcome, FixStatus};
+        let mut result = make_result(vec![]);
+        let outcome = |line, status| FixOutcome {
+            file: PathBuf::from("src/a.tsx"),
+            line: Some(line),
+            rule_id: "theme".into(),
+            status,
+        };
+        result.fixes = Some(FixReport {
+            outcomes: vec![
+                outcome(1, FixStatus::Applied),
+                outcome(2, FixStatus::Stale),
+                outcome(3, FixStatus::Conflict),
+            ],
+            dry_run,
+            ..Default::default()
+        });
+        result
+    }
+
+    #[test]
+    fn fix_command_prints_diffs_and_per_rule_counts() {
+        let mut report = with_fix_report(true).fixes.unwrap();
+        report.changes.push(crate::fix::FileChange {
+            path: PathBuf::from("src/a.tsx"),
+            before: "bg-white\n".into(),
+            after: "bg-background\n".into(),
+        });
+        let mut out = Vec::new();
+        write_fix(&report, &mut out);
+        let text = String::from_utf8(out).unwrap();
+        assert!(text.contains("\x1b[31m-bg-white\x1b[0m"));
+        assert!(text.contains("\x1b[32m+bg-background\x1b[0m"));
+        assert!(text.contains("  theme"));
+        assert!(text.contains("1 would be applied, 1 skipped, 1 conflicted"));
+
+        let mut out = Vec::new();
+        write_fix(&FixReport::default(), &mut out);
+        assert!(String::from_utf8(out).unwrap().contains("Nothing to fix"));
+    }
+
+    #[test]
+    fn fix_report_in_json_and_sarif() {
+        let result = with_fix_report(false);
+
+        let mut out = Vec::new();
+        write_json(&result, &mut out);
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(parsed["fixes"]["applied"], 1);
+        assert_eq!(parsed["fixes"]["skipped"], 1);
+        assert_eq!(parsed["fixes"]["conflicted"], 1);
+        assert_eq!(parsed["fixes"]["outcomes"][2]["status"], "conflict");
+
+        let mut out = Vec::new();
+        write_sarif(&result, &mut out);
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        let notes = &parsed["runs"][0]["invocations"][0]["toolExecutionNotifications"];
+        assert_eq!(notes.as_array().unwrap().len(), 3);
+        assert_eq!(notes[1]["properties"]["status"], "stale");
+
+        // No --fix, no fixes key
+        let mut out = Vec::new();
+        write_json(&make_result(vec![]), &mut out);
+        let parsed: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert!(parsed.get("fixes").is_none());
+    }
+
+    #[test]
+    fn fix_report_in_text_formats() {
+        let result = with_fix_report(true);
+
+        let mut out = Vec::new();
+        write_pretty(&result, &mut out);
+        let pretty = String::from_utf8(out).unwrap();
+        assert!(pretty.contains("(dry run)"));
+        assert!(pretty.contains("1 would be applied, 1 skipped, 1 conflicted"));
+        assert!(pretty.contains("src/a.tsx:3"));
+        assert!(!pretty.contains("src/a.tsx:1"));
+
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        write_compact(&result, &mut out, &mut err);
+        let err = String::from_utf8(err).unwrap();
+        assert!(err.contains("fixes: 1 would be applied, 1 skipped, 1 conflicted (dry run)"));
+        assert!(err.contains("fix: src/a.tsx:2 theme stale"));
+
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        write_github(&result, &mut out, &mut err);
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains("::warning file=src/a.tsx,line=3,title=fix-conflict::"));
+
+        let mut out = Vec::new();
+        write_markdown(&result, &mut out);
+        let md = String::from_utf8(out).unwrap();
+        assert!(md.contains("### Fixes"));
+        assert!(md.contains("| `src/a.tsx` | 2 | `theme` | file changed since the scan |"));
+    }
+
+    // ── info / hint severity tests ──
+
+    #[test]
+    fn informational_severities_render_distinctly() {
+        let result = make_result(vec![
+            make_violation("src/a.tsx", 1, 1, Severity::Info, "new-rule", "consider this"),
+            make_violation("src/a.tsx", 2, 1, Severity::Hint, "style", "maybe this"),
+        ]);
+
+        let mut out = Vec::new();
+        write_pretty(&result, &mut out);
+        let pretty = String::from_utf8(out).unwrap();
+        assert!(pretty.contains("\x1b[36minfo \x1b[0m"));
+        assert!(pretty.contains("\x1b[90mhint \x1b[0m"));
+        assert!(pretty.contains("1 info"));
+        assert!(pretty.contains("1 hint"));
+
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        write_compact(&result, &mut out, &mut err);
+        assert!(String::from_utf8(out).unwrap().contains("src/a.tsx:2:1: hint[style]"));
+        assert!(String::from_utf8(err).unwrap().starts_with("1 info, 1 hint ("));
+
+        let (mut out, mut err) = (Vec::new(), Vec::new());
+        write_github(&result, &mut out, &mut err);
+        assert!(String::from_utf8(out).unwrap().starts_with("::notice file=src/a.tsx,line=1"));
+
+        let mut out = Vec::new();
+        write_sarif(&result, &mut out);
+        let sarif: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(sarif["runs"][0]["results"][0]["level"], "note");
+        assert_eq!(sarif["runs"][0]["results"][1]["level"], "note");
+
+        let mut out = Vec::new();
+        write_json(&result, &mut out);
+        let json: serde_json::Value = serde_json::from_slice(&out).unwrap();
+        assert_eq!(json["violations"][0]["severity"], "info");
+        assert_eq!(json["summary"]["errors"], 0);
+        assert_eq!(json["summary"]["info"], 1);
+        assert_eq!(json["summary"]["hints"], 1);
+
+        let mut out = Vec::new();
+        write_markdown(&result, &mut out);
+        let md = String::from_utf8(out).unwrap();
+        assert!(md.contains("**1 info, 1 hint**"));
+        assert!(md.contains("### Info"));
+        assert!(md.contains("### Hints"));
+    }
 }
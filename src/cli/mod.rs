@@ -1,27 +1,45 @@
 pub mod format;
 pub mod toml_config;
 
+use crate::logging::{LogFormat, LogLevel};
 use clap::{Parser, Subcommand, ValueEnum};
 use std::path::PathBuf;
 
 #[derive(Parser)]
-#[command(name = "baseline", about = "Enforce architectural decisions AI coding tools keep ignoring")]
+#[command(name = "baseline", version, about = "Enforce architectural decisions AI coding tools keep ignoring")]
 pub struct Cli {
     #[command(subcommand)]
     pub command: Commands,
+
+    /// Log verbosity for status lines, hints and diagnostics (logs go to stderr)
+    #[arg(long, global = true, value_enum, default_value_t = LogLevel::Info)]
+    pub log_level: LogLevel,
+
+    /// Diagnostic log encoding
+    #[arg(long, global = true, value_enum, default_value_t = LogFormat::Text)]
+    pub log_format: LogFormat,
 }
 
 #[derive(Subcommand)]
 pub enum Commands {
     /// Scan files for rule violations
+    #[command(group(clap::ArgGroup::new("fix_mode").args(["fix", "fix_suppress"])))]
     Scan {
-        /// Paths to scan (files or directories)
-        #[arg(required_unless_present = "stdin")]
+        /// Paths to scan: files, directories, or quoted globs like 'src/**/*.tsx'
+        #[arg(required_unless_present_any = ["stdin", "archive"])]
         paths: Vec<PathBuf>,
 
-        /// Path to baseline.toml config file
+        /// Scan the files inside a .zip or tar archive instead of PATHS (needs unzip or tar)
+        #[arg(long, value_name = "PATH", conflicts_with_all = ["paths", "stdin", "changed_only", "fix", "fix_suppress", "by_project"])]
+        archive: Option<PathBuf>,
+
+        /// Path to baseline.toml config file; repeat to evaluate several configs in one pass
         #[arg(short, long, default_value = "baseline.toml")]
-        config: PathBuf,
+        config: Vec<PathBuf>,
+
+        /// Run only the rules in this `[groups.<NAME>]` of the config (repeatable)
+        #[arg(long = "group", value_name = "NAME")]
+        groups: Vec<String>,
 
         /// Output format
         #[arg(short, long, value_enum, default_value_t = OutputFormat::Pretty)]
@@ -43,18 +61,157 @@ pub enum Commands {
         #[arg(long, requires = "changed_only")]
         base: Option<String>,
 
+        /// With --changed-only, treat renamed files as new instead of diffing them against their old path
+        #[arg(long, requires = "changed_only")]
+        no_renames: bool,
+
         /// Apply fixes automatically
         #[arg(long)]
         fix: bool,
 
-        /// Preview fixes without applying (requires --fix)
+        /// With --fix, also apply fixes from rules not marked safe_fix when `safe_fixes_only` is set
         #[arg(long, requires = "fix")]
+        fix_unsafe: bool,
+
+        /// With --stdin --fix, what to print to stdout (violations go to stderr)
+        #[arg(long, value_enum, value_name = "KIND", default_value = "content", requires = "stdin")]
+        fix_output: StdinFixOutput,
+
+        /// Preview fixes without applying (requires --fix or --fix-suppress)
+        #[arg(long, requires = "fix_mode")]
         dry_run: bool,
+
+        /// Insert `baseline:allow-next-line` comments above existing violations instead of fixing them
+        #[arg(long, conflicts_with_all = ["fix", "stdin"])]
+        fix_suppress: bool,
+
+        /// Limit --fix-suppress to these rule ids (repeatable)
+        #[arg(long = "suppress-rule", value_name = "ID", requires = "fix_suppress")]
+        suppress_rules: Vec<String>,
+
+        /// Ticket reference written into suppression comments as TODO(<TICKET>)
+        #[arg(long, value_name = "TICKET", requires = "fix_suppress")]
+        suppress_ticket: Option<String>,
+
+        /// Scan files even if they look minified (long lines)
+        #[arg(long)]
+        include_minified: bool,
+
+        /// Scan files even if their header marks them as generated
+        #[arg(long)]
+        include_generated: bool,
+
+        /// Print the files the scan would read, after excludes, .gitignore and the minified and generated checks, without running rules (one per line, or a JSON array with --format json)
+        #[arg(long, conflicts_with_all = ["stdin", "fix", "fix_suppress", "by_project"])]
+        list_files: bool,
+
+        /// Write a GitHub Actions problem matcher for compact output here; registered automatically under GitHub Actions
+        #[arg(long, value_name = "PATH")]
+        emit_problem_matcher: Option<PathBuf>,
+
+        /// Count columns in UTF-8 bytes, Unicode scalar values, or UTF-16 code units; overrides `[output] column_unit`
+        #[arg(long, value_enum, value_name = "UNIT")]
+        column_unit: Option<crate::config::ColumnUnit>,
+
+        /// Append violations, ratchet counts and scan metadata to a database (sqlite:<PATH>; needs the sqlite3 CLI)
+        #[arg(long, value_name = "TARGET")]
+        export: Option<crate::export::ExportTarget>,
+
+        /// Stop at the first error-severity violation and report only that one
+        #[arg(long, conflicts_with_all = ["fix", "fix_suppress"])]
+        fail_fast: bool,
+
+        /// Descend at most N directories below each scanned path (1 = only its direct children)
+        #[arg(long, value_name = "N")]
+        max_depth: Option<usize>,
+
+        /// Print config-load, rule-build, and scan timings, and how rules were grouped, to stderr
+        #[arg(long)]
+        timing: bool,
+
+        /// Also print every non-ratchet rule's violation total to stderr, for picking ratchet budgets
+        #[arg(long)]
+        count_all: bool,
+
+        /// Partition results per workspace package (directories with package.json)
+        #[arg(long, conflicts_with = "stdin")]
+        by_project: bool,
+
+        /// Report what the counts and exit code would be at this severity (does not change enforcement)
+        #[arg(long, value_enum)]
+        simulate_severity: Option<SimulatedSeverity>,
+
+        /// Limit --simulate-severity to these rule ids (repeatable)
+        #[arg(long = "simulate-rule", value_name = "ID", requires = "simulate_severity")]
+        simulate_rules: Vec<String>,
+
+        /// Show at most N violations per rule in pretty/markdown output (JSON and SARIF stay complete)
+        #[arg(long, value_name = "N")]
+        max_per_rule: Option<usize>,
+
+        /// In pretty output, add violation counts per directory, DEPTH levels deep (default: 1, the top-level directories)
+        #[arg(long, value_name = "DEPTH", num_args = 0..=1, require_equals = true, default_missing_value = "1")]
+        by_directory: Option<usize>,
+
+        /// Show where each violation's rule was defined (preset, scoped preset, plugin, or user config)
+        #[arg(short, long)]
+        verbose: bool,
+
+        /// Translate rule messages and pretty output with locales/<LOCALE>.toml next to the config (or a .toml path)
+        #[arg(long, value_name = "LOCALE")]
+        locale: Option<String>,
+
+        /// Print a hint for each regex rule that an AST rule type could replace
+        #[arg(long)]
+        suggest_upgrades: bool,
+
+        /// With --format github, annotations of each type to emit before rolling up the rest (0 = all)
+        #[arg(long, value_name = "N", default_value_t = crate::cli::format::GITHUB_ANNOTATION_LIMIT)]
+        annotation_limit: usize,
+
+        /// With --format github, also list violations past the annotation limit in collapsible log groups
+        #[arg(long)]
+        annotation_groups: bool,
+
+        /// Fail on warnings, unreadable files, rules whose glob matches no files, and unused suppressions
+        #[arg(long)]
+        strict: bool,
+    },
+
+    /// Scan, then apply the fixes of the selected rules and report how many were applied per rule
+    Fix {
+        /// Paths to fix: files, directories, or quoted globs like 'src/**/*.tsx'
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Path to baseline.toml config file; repeat to evaluate several configs in one pass
+        #[arg(short, long, default_value = "baseline.toml")]
+        config: Vec<PathBuf>,
+
+        /// Only apply fixes from these rule ids (repeatable; default: every fixable rule)
+        #[arg(long = "rule", value_name = "ID")]
+        rules: Vec<String>,
+
+        /// Print the diff each file would get without writing it
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Also apply fixes from rules not marked safe_fix when `safe_fixes_only` is set
+        #[arg(long = "unsafe")]
+        allow_unsafe: bool,
+
+        /// Fix files even if they look minified (long lines)
+        #[arg(long)]
+        include_minified: bool,
+
+        /// Fix files even if their header marks them as generated
+        #[arg(long)]
+        include_generated: bool,
     },
 
     /// Count current occurrences of ratchet patterns and write a baseline JSON file
     Baseline {
-        /// Paths to scan (files or directories)
+        /// Paths to scan: files, directories, or quoted globs like 'src/**/*.tsx'
         #[arg(required = true)]
         paths: Vec<PathBuf>,
 
@@ -67,11 +224,205 @@ pub enum Commands {
         output: PathBuf,
     },
 
+    /// Remove or prune generated artifacts: scan cache, history, stale snapshot entries, reports
+    Clean {
+        /// Path to baseline.toml config file; artifacts live next to it
+        #[arg(short, long, default_value = "baseline.toml")]
+        config: PathBuf,
+
+        /// Show what would be removed without touching anything
+        #[arg(long)]
+        dry_run: bool,
+
+        /// Keep the newest N history entries (0 removes the history)
+        #[arg(long, value_name = "N", default_value_t = 0)]
+        keep_history: usize,
+
+        /// Only clean these artifacts (repeatable)
+        #[arg(long, value_name = "ARTIFACT", value_parser = ["cache", "history", "snapshot", "reports"])]
+        only: Vec<String>,
+    },
+
+    /// List the resolved rules (presets, plugins, and user rules), including disabled ones
+    Rules {
+        /// Path to baseline.toml config file
+        #[arg(short, long, default_value = "baseline.toml")]
+        config: PathBuf,
+    },
+
+    /// Build every rule and report rules whose glob matches no files
+    CheckConfig {
+        /// Paths to walk, as for `scan`
+        #[arg(default_value = ".")]
+        paths: Vec<PathBuf>,
+
+        /// Path to baseline.toml config file
+        #[arg(short, long, default_value = "baseline.toml")]
+        config: PathBuf,
+    },
+
+    /// Generate a markdown document describing the enabled rules
+    Docs {
+        /// Path to baseline.toml config file
+        #[arg(short, long, default_value = "baseline.toml")]
+        config: PathBuf,
+
+        /// Write the document here instead of stdout
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Fail if --output is missing or out of date instead of writing it
+        #[arg(long, requires = "output")]
+        check: bool,
+    },
+
+    /// Scan, then post a summary to a Slack-compatible webhook; exits with the scan's code
+    Notify {
+        /// Paths to scan: files, directories, or quoted globs like 'src/**/*.tsx'
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Path to baseline.toml config file
+        #[arg(short, long, default_value = "baseline.toml")]
+        config: PathBuf,
+
+        /// Webhook URL; overrides `[notify] webhook` and `webhook_env`
+        #[arg(long, value_name = "URL")]
+        webhook: Option<String>,
+
+        /// Only post when the scan fails
+        #[arg(long)]
+        only_on_failure: bool,
+
+        /// Link shown under the summary (default: the GitHub Actions run, when there is one)
+        #[arg(long, value_name = "URL")]
+        link: Option<String>,
+
+        /// Print the payload instead of posting it
+        #[arg(long)]
+        dry_run: bool,
+    },
+
+    /// Re-run the scan and fail if its violations differ from a recorded `--format json` result
+    Verify {
+        /// Paths to scan: files, directories, or quoted globs like 'src/**/*.tsx'
+        #[arg(required = true)]
+        paths: Vec<PathBuf>,
+
+        /// Path to baseline.toml config file
+        #[arg(short, long, default_value = "baseline.toml")]
+        config: PathBuf,
+
+        /// Result to compare against (output of `baseline scan --format json`)
+        #[arg(long, value_name = "JSON")]
+        against: PathBuf,
+
+        /// Scan files even if they look minified (long lines)
+        #[arg(long)]
+        include_minified: bool,
+
+        /// Scan files even if their header marks them as generated
+        #[arg(long)]
+        include_generated: bool,
+    },
+
+    /// Benchmark a synthetic React/Next.js repo (for measuring perf regressions)
+    #[command(hide = true)]
+    Bench {
+        /// Number of files in the synthetic repo
+        #[arg(long, default_value_t = 500)]
+        files: usize,
+
+        /// Full scans to run; the fastest is reported
+        #[arg(long, default_value_t = 3)]
+        iterations: usize,
+
+        /// Number of rules to list, most expensive first
+        #[arg(long, default_value_t = 15)]
+        top: usize,
+    },
+
+    /// Run one rule over a code snippet and show where it matches; exits 1 if nothing matches
+    CheckSnippet {
+        /// Id of a rule in the config (presets and plugins included)
+        #[arg(long, required_unless_present_any = ["define", "pattern"], conflicts_with_all = ["define", "pattern"])]
+        rule: Option<String>,
+
+        /// Inline rule definition as TOML, e.g. 'type = "banned-pattern"\npattern = "foo"'
+        #[arg(long, value_name = "TOML", conflicts_with = "pattern")]
+        define: Option<String>,
+
+        /// Try this pattern as a banned-pattern rule
+        #[arg(long)]
+        pattern: Option<String>,
+
+        /// Treat --pattern as a regex
+        #[arg(long, requires = "pattern")]
+        regex: bool,
+
+        /// Snippet to check; read from stdin when omitted
+        #[arg(long)]
+        code: Option<String>,
+
+        /// Filename the snippet is checked as (picks the language for AST rules)
+        #[arg(long, default_value = "snippet.tsx")]
+        filename: String,
+
+        /// Path to baseline.toml config file (for --rule)
+        #[arg(short, long, default_value = "baseline.toml")]
+        config: PathBuf,
+    },
+
+    /// List React components with their size, hook and prop counts, biggest first
+    Components {
+        /// Paths to search: files, directories, or quoted globs like 'src/**/*.tsx'
+        #[arg(default_value = ".")]
+        paths: Vec<PathBuf>,
+
+        /// Path to baseline.toml config file (for its excludes; optional)
+        #[arg(short, long, default_value = "baseline.toml")]
+        config: PathBuf,
+
+        /// Column to sort by
+        #[arg(long, value_enum, default_value_t = crate::components::ComponentSort::Lines)]
+        sort: crate::components::ComponentSort,
+
+        /// Output format
+        #[arg(short, long, value_enum, default_value_t = ReportFormat::Pretty)]
+        format: ReportFormat,
+
+        /// Show only the first N components
+        #[arg(long, value_name = "N")]
+        limit: Option<usize>,
+    },
+
+    /// Print a JSON Schema for baseline.toml (for editor validation and completion)
+    Schema {
+        /// Print the schema of `scan --format json` output instead
+        #[arg(long)]
+        output_format: bool,
+    },
+
+    /// Replace this binary with the release built for this platform
+    SelfUpdate {
+        /// Install this version instead of the latest release (e.g. 1.6.0)
+        #[arg(long)]
+        version: Option<String>,
+    },
+
     /// Run as an MCP (Model Context Protocol) server over stdio
     Mcp {
         /// Path to baseline.toml config file
         #[arg(short, long, default_value = "baseline.toml")]
         config: PathBuf,
+
+        /// Watch the workspace and push notifications when violations change
+        #[arg(long)]
+        watch: bool,
+
+        /// Polling interval for --watch, in milliseconds
+        #[arg(long, default_value_t = 500, requires = "watch")]
+        watch_interval: u64,
     },
 
     /// Generate a starter baseline.toml for your project
@@ -83,6 +434,10 @@ pub enum Commands {
         /// Overwrite existing config file
         #[arg(long)]
         force: bool,
+
+        /// Also write a CI pipeline that scans changed files and reports SARIF and a markdown summary
+        #[arg(long, value_enum, value_name = "PROVIDER")]
+        ci: Option<crate::init::CiProvider>,
     },
 
     /// Manage ratchet rules (add, tighten, import from baseline)
@@ -90,6 +445,30 @@ pub enum Commands {
         #[command(subcommand)]
         command: RatchetCommands,
     },
+
+    /// Export bundled presets and compare them across versions
+    Preset {
+        #[command(subcommand)]
+        command: PresetCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum PresetCommands {
+    /// Print a bundled preset's rules as a TOML snapshot
+    Export {
+        /// Preset name
+        name: String,
+    },
+
+    /// Show rule-level differences between two presets or snapshots; exits 1 if they differ
+    Diff {
+        /// NAME or NAME@current for a bundled preset, NAME@PATH or PATH for a snapshot
+        old: String,
+
+        /// Same forms as OLD
+        new: String,
+    },
 }
 
 #[derive(Subcommand)]
@@ -119,9 +498,17 @@ pub enum RatchetCommands {
         #[arg(short, long, default_value = "baseline.toml")]
         config: PathBuf,
 
-        /// Paths to scan (files or directories)
+        /// Paths to scan: files, directories, or quoted globs like 'src/**/*.tsx'
         #[arg(default_value = ".")]
         paths: Vec<PathBuf>,
+
+        /// Show the first N matches (file:line and source) before writing the rule
+        #[arg(long, value_name = "N", num_args = 0..=1, default_missing_value = "10")]
+        preview: Option<usize>,
+
+        /// Ask for confirmation before writing the rule (shows a preview)
+        #[arg(long)]
+        interactive: bool,
     },
 
     /// Re-count and lower max_count for an existing ratchet rule
@@ -133,7 +520,7 @@ pub enum RatchetCommands {
         #[arg(short, long, default_value = "baseline.toml")]
         config: PathBuf,
 
-        /// Paths to scan (files or directories)
+        /// Paths to scan: files, directories, or quoted globs like 'src/**/*.tsx'
         #[arg(default_value = ".")]
         paths: Vec<PathBuf>,
     },
@@ -149,6 +536,29 @@ pub enum RatchetCommands {
     },
 }
 
+/// Severity that `--simulate-severity` re-grades warnings to.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum SimulatedSeverity {
+    Error,
+}
+
+/// Output formats for reports other than scan results.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ReportFormat {
+    Pretty,
+    Json,
+    Markdown,
+}
+
+/// What `scan --stdin --fix` writes to stdout.
+#[derive(Clone, Copy, ValueEnum)]
+pub enum StdinFixOutput {
+    /// The whole fixed content, to replace the editor buffer with
+    Content,
+    /// A unified diff from the input to the fixed content
+    Diff,
+}
+
 #[derive(Clone, ValueEnum)]
 pub enum OutputFormat {
     Pretty,
@@ -157,4 +567,7 @@ pub enum OutputFormat {
     Github,
     Sarif,
     Markdown,
+    Summary,
+    /// Every available fix as an LSP `TextEdit` (uri, range, newText)
+    LspEdits,
 }
@@ -1,22 +1,175 @@
-use crate::config::{RuleConfig, Severity};
-use serde::Deserialize;
+use crate::config::{ColumnUnit, RuleConfig, Severity};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::path::PathBuf;
 
 /// Top-level TOML config file structure.
 #[derive(Debug, Deserialize)]
 pub struct TomlConfig {
+    /// Extra config files (globs relative to this file) merged into this one
+    #[serde(default)]
+    pub include: Vec<String>,
     pub baseline: BaselineSection,
     #[serde(default)]
+    pub output: OutputSection,
+    #[serde(default)]
+    pub hooks: HooksSection,
+    #[serde(default)]
+    pub notify: NotifySection,
+    /// Named subsets of rules, run with `scan --group <name>`
+    #[serde(default)]
+    pub groups: BTreeMap<String, GroupSection>,
+    #[serde(default)]
     pub rule: Vec<TomlRule>,
 }
 
+/// A `[groups.<name>]` entry: rule ids a CI job or hook can run on their own.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GroupSection {
+    /// Ids of the rules in the group
+    pub rules: Vec<String>,
+}
+
+/// The `[output]` section: the lowest severity each output format reports,
+/// and under `[output.exit]`, the lowest severity that fails the scan.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutputSection {
+    /// Severities that fail the scan (default: error)
+    pub exit: Option<OutputGate>,
+    pub pretty: Option<OutputGate>,
+    pub compact: Option<OutputGate>,
+    pub json: Option<OutputGate>,
+    pub github: Option<OutputGate>,
+    pub sarif: Option<OutputGate>,
+    pub markdown: Option<OutputGate>,
+    pub summary: Option<OutputGate>,
+    /// How reported columns are counted (default: bytes)
+    pub column_unit: Option<ColumnUnit>,
+}
+
+/// An `[output.<format>]` table.
+#[derive(Debug, Clone, Copy, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutputGate {
+    /// Lowest severity included
+    pub min_severity: Severity,
+}
+
+impl OutputSection {
+    /// Unit every output format reports columns in.
+    pub fn column_unit(&self) -> ColumnUnit {
+        self.column_unit.unwrap_or_default()
+    }
+
+    /// Lowest severity that fails the scan.
+    pub fn fail_on(&self) -> Severity {
+        self.exit.map_or(Severity::Error, |g| g.min_severity)
+    }
+
+    /// Lowest severity reported by `format` (`"github"`, `"sarif"`, ...).
+    /// Formats without a table report everything.
+    pub fn min_severity(&self, format: &str) -> Severity {
+        let gate = match format {
+            "pretty" => self.pretty,
+            "compact" => self.compact,
+            "json" => self.json,
+            "github" => self.github,
+            "sarif" => self.sarif,
+            "markdown" => self.markdown,
+            "summary" => self.summary,
+            _ => None,
+        };
+        gate.map_or(Severity::Hint, |g| g.min_severity)
+    }
+}
+
+/// The `[hooks]` section: external commands run with a scan's result, so
+/// integrations (chat notifications, dashboards) live outside the tool.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct HooksSection {
+    /// Commands run after `baseline scan`, with its JSON result on stdin
+    #[serde(default)]
+    pub after_scan: Vec<Hook>,
+}
+
+/// A `[[hooks.after_scan]]` entry.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct Hook {
+    /// Program and its arguments, run from the config's directory
+    pub command: Vec<String>,
+    /// Seconds before the hook is killed (default: 30)
+    #[serde(default = "default_hook_timeout")]
+    pub timeout_secs: u64,
+    /// What a failing or timed-out hook does (default: warn)
+    #[serde(default)]
+    pub on_failure: HookFailure,
+}
+
+fn default_hook_timeout() -> u64 {
+    30
+}
+
+/// What happens when a hook exits non-zero, can't start, or times out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HookFailure {
+    /// Carry on silently.
+    Ignore,
+    /// Print a warning; the exit code is unchanged.
+    #[default]
+    Warn,
+    /// Print an error and exit 2.
+    Fail,
+}
+
+/// The `[notify]` section: defaults for `baseline notify`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct NotifySection {
+    /// Webhook URL to post to
+    pub webhook: Option<String>,
+    /// Environment variable holding the webhook URL; takes precedence over `webhook`
+    pub webhook_env: Option<String>,
+    /// Only post when the scan fails (default: false)
+    #[serde(default)]
+    pub only_on_failure: bool,
+    /// Rules listed in the message, most violations first (default: 5)
+    pub top_rules: Option<usize>,
+}
+
 /// A `[[baseline.scoped]]` entry that applies a preset to a specific directory.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct ScopedPreset {
     #[serde(deserialize_with = "string_or_vec")]
     pub preset: Vec<String>,
     pub path: String,
     #[serde(default)]
     pub exclude_rules: Vec<String>,
+    /// Paths or globs under `path` the presets should not apply to.
+    #[serde(default)]
+    pub exclude_paths: Vec<String>,
+}
+
+/// One pattern or a list of patterns.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(transparent)]
+pub struct PatternList(#[serde(deserialize_with = "string_or_vec")] pub Vec<String>);
+
+impl From<&str> for PatternList {
+    fn from(s: &str) -> Self {
+        Self(vec![s.to_string()])
+    }
+}
+
+impl From<String> for PatternList {
+    fn from(s: String) -> Self {
+        Self(vec![s])
+    }
 }
 
 /// Deserialize a TOML value that is either a single string or an array of strings.
@@ -52,14 +205,21 @@ where
 }
 
 /// The `[baseline]` section.
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct BaselineSection {
-    #[allow(dead_code)]
     pub name: Option<String>,
+    /// Versions of baseline this config works with, e.g. `">=0.6"`
+    pub required_version: Option<String>,
     #[serde(default)]
     pub include: Vec<String>,
+    /// Globs to skip; replaces the default excludes when set
+    pub exclude: Option<Vec<String>>,
+    /// Globs to skip in addition to `exclude` (or the defaults)
+    #[serde(default)]
+    pub exclude_extend: Vec<String>,
+    /// Don't apply the default excludes when `exclude` is unset (default: false)
     #[serde(default)]
-    pub exclude: Vec<String>,
+    pub no_default_excludes: bool,
     #[serde(default)]
     pub extends: Vec<String>,
     /// Paths to plugin TOML files containing additional rules
@@ -68,20 +228,101 @@ pub struct BaselineSection {
     /// Scoped presets: apply a preset only to files under a specific path
     #[serde(default)]
     pub scoped: Vec<ScopedPreset>,
+    /// Lines longer than this mark a file as minified and skip it (default: 500, 0 disables)
+    pub max_line_length: Option<usize>,
+    /// Globs for files that are always scanned even if they look minified
+    #[serde(default)]
+    pub minified_globs: Vec<String>,
+    /// Leading lines searched for generated-file markers (default: 5, 0 disables)
+    pub generated_header_lines: Option<usize>,
+    /// Follow symlinked files and directories while walking (default: false)
+    #[serde(default)]
+    pub follow_symlinks: bool,
+    /// How git submodules are treated while walking
+    #[serde(default)]
+    pub submodules: SubmodulePolicy,
+    /// Whether conventional vendored directories are scanned
+    #[serde(default)]
+    pub vendored: VendoredPolicy,
+    /// Directory names treated as vendored code (default: `vendor`, `third_party`)
+    #[serde(default = "default_vendored_dirs")]
+    pub vendored_dirs: Vec<String>,
+    /// Lines an `allow-next-line` directive may sit above a violation (default: 1)
+    pub suppression_lookback: Option<usize>,
+    /// Apply only fixes from rules marked `safe_fix` unless `--fix-unsafe` is passed
+    #[serde(default)]
+    pub safe_fixes_only: bool,
+}
+
+/// How the file walker treats git submodules (directories with a `.git` file).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum SubmodulePolicy {
+    /// Scan submodule files with the root config.
+    #[default]
+    Scan,
+    /// Skip submodules entirely.
+    Skip,
+    /// Scan submodules with their own nested config when one exists.
+    Nested,
+}
+
+/// Whether conventional vendored directories (`vendor/`, `third_party/`) are scanned.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum VendoredPolicy {
+    #[default]
+    Skip,
+    Scan,
+}
+
+/// Excludes applied when a config sets neither `exclude` nor
+/// `no_default_excludes`, so a repo without a `.gitignore` doesn't walk
+/// dependencies and build output.
+pub const DEFAULT_EXCLUDES: &[&str] = &[
+    "**/node_modules/**",
+    "**/.next/**",
+    "**/dist/**",
+    "**/build/**",
+    "**/coverage/**",
+    "**/*.min.js",
+];
+
+impl BaselineSection {
+    /// The exclude globs in effect: `exclude` if set, otherwise the defaults
+    /// (unless disabled), followed by `exclude_extend`.
+    pub fn excludes(&self) -> Vec<String> {
+        let mut excludes = match &self.exclude {
+            Some(exclude) => exclude.clone(),
+            None if self.no_default_excludes => Vec::new(),
+            None => DEFAULT_EXCLUDES.iter().map(|s| s.to_string()).collect(),
+        };
+        excludes.extend(self.exclude_extend.iter().cloned());
+        excludes
+    }
+}
+
+fn default_vendored_dirs() -> Vec<String> {
+    vec!["vendor".into(), "third_party".into()]
 }
 
 /// A single `[[rule]]` entry.
-#[derive(Debug, Clone, Deserialize)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct TomlRule {
     pub id: String,
-    #[serde(rename = "type")]
+    /// Rule type. May be omitted when the entry only disables a preset rule.
+    #[serde(rename = "type", default)]
     pub rule_type: String,
+    /// `enabled = false` drops the rule after presets are resolved.
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
     #[serde(default = "default_severity")]
     pub severity: String,
     pub glob: Option<String>,
     #[serde(default)]
     pub message: String,
-    pub suggest: Option<String>,
+    /// One suggestion, or a list of alternatives.
+    pub suggest: Option<PatternList>,
     #[serde(default)]
     pub allowed_classes: Vec<String>,
     #[serde(default)]
@@ -91,6 +332,11 @@ pub struct TomlRule {
     #[serde(default)]
     pub packages: Vec<String>,
     #[serde(default)]
+    pub allow_type_imports: bool,
+    pub max_relative_depth: Option<usize>,
+    #[serde(default)]
+    pub ban_cross_package_imports: bool,
+    #[serde(default)]
     pub regex: bool,
     pub manifest: Option<String>,
     #[serde(default)]
@@ -98,23 +344,119 @@ pub struct TomlRule {
     pub file_contains: Option<String>,
     pub file_not_contains: Option<String>,
     #[serde(default)]
+    pub sibling_files: Vec<String>,
+    #[serde(default)]
     pub required_files: Vec<String>,
     #[serde(default)]
     pub forbidden_files: Vec<String>,
+    /// Content a required file must have (`[rule.must_contain]`), keyed by
+    /// path; each value is one pattern or a list of patterns.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub must_contain: BTreeMap<String, PatternList>,
     pub condition_pattern: Option<String>,
     #[serde(default)]
     pub skip_strings: bool,
+    #[serde(default)]
+    pub scope_to_function: bool,
+    #[serde(default)]
+    pub components: Vec<String>,
+    pub app_dir: Option<String>,
+    pub feature_dirs: Option<String>,
+    #[serde(default)]
+    pub env_vars: Vec<String>,
+    pub env_file: Option<String>,
+    #[serde(default)]
+    pub checks: Vec<String>,
+    /// Scripts the manifest must define (used by package-scripts).
+    #[serde(default)]
+    pub scripts: Vec<String>,
+    /// Directories mirroring the source tree with tests (used by test-colocation).
+    #[serde(default)]
+    pub test_dirs: Vec<String>,
+    /// Components and the module they must be imported from
+    /// (`[rule.canonical_imports]`), keyed by module; each value is one
+    /// component name or a list (used by canonical-import).
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub canonical_imports: BTreeMap<String, PatternList>,
+    /// Id of another rule whose violations this ratchet budgets, instead of
+    /// counting its own `pattern`.
+    pub track: Option<String>,
+    /// Whether this rule's fixes are safe to apply under `safe_fixes_only`;
+    /// unset uses the rule type's default (see `fix::is_safe_fix`).
+    pub safe_fix: Option<bool>,
+    /// Date (`YYYY-MM-DD`) from which this ratchet's budget becomes `target`.
+    pub deadline: Option<String>,
+    /// Budget once `deadline` is reached (default 0).
+    pub target: Option<usize>,
+    /// Per-directory ratchet budgets (`[rule.budgets]`), keyed by path
+    /// relative to the scan root. `max_count` then covers everything else.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub budgets: BTreeMap<String, usize>,
+    /// How a ratchet is enforced under `--changed-only` (default: budget).
+    pub ratchet_mode: Option<RatchetMode>,
+    /// Team or person responsible for the rule, e.g. `@platform-team`.
+    pub owner: Option<String>,
+    /// Suppressing the rule or changing its config in a `--changed-only`
+    /// scan reports a `review-required` notice naming the `owner`.
+    #[serde(default)]
+    pub requires_review: bool,
+    /// Where the rule was defined; set while presets and plugins are merged.
+    #[serde(skip)]
+    pub origin: RuleOrigin,
+}
+
+/// How a ratchet rule is enforced under `--changed-only`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum RatchetMode {
+    /// Compare the repo-wide count with `max_count`.
+    #[default]
+    Budget,
+    /// Allow no matches on changed lines. Full scans still use `max_count`.
+    NoNew,
+}
+
+/// Where a resolved rule came from.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Hash)]
+pub enum RuleOrigin {
+    /// A `[[rule]]` in the config file (or a file it includes).
+    #[default]
+    User,
+    /// A rule from a preset named in `extends`.
+    Preset(String),
+    /// A rule from a `[[baseline.scoped]]` preset applied under `path`.
+    ScopedPreset { preset: String, path: String },
+    /// A rule loaded from a file listed in `plugins`.
+    Plugin(PathBuf),
+}
+
+impl fmt::Display for RuleOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RuleOrigin::User => write!(f, "user config"),
+            RuleOrigin::Preset(name) => write!(f, "preset {}", name),
+            RuleOrigin::ScopedPreset { preset, path } => {
+                write!(f, "preset {} scoped to {}", preset, path)
+            }
+            RuleOrigin::Plugin(path) => write!(f, "plugin {}", path.display()),
+        }
+    }
 }
 
 fn default_severity() -> String {
     "warning".into()
 }
 
+fn default_enabled() -> bool {
+    true
+}
+
 impl Default for TomlRule {
     fn default() -> Self {
         Self {
             id: String::new(),
             rule_type: String::new(),
+            enabled: true,
             severity: default_severity(),
             glob: None,
             message: String::new(),
@@ -124,15 +466,39 @@ impl Default for TomlRule {
             pattern: None,
             max_count: None,
             packages: Vec::new(),
+            allow_type_imports: false,
+            max_relative_depth: None,
+            ban_cross_package_imports: false,
             regex: false,
             manifest: None,
             exclude_glob: Vec::new(),
             file_contains: None,
             file_not_contains: None,
+            sibling_files: Vec::new(),
             required_files: Vec::new(),
             forbidden_files: Vec::new(),
+            must_contain: BTreeMap::new(),
             condition_pattern: None,
             skip_strings: false,
+            scope_to_function: false,
+            components: Vec::new(),
+            app_dir: None,
+            feature_dirs: None,
+            env_vars: Vec::new(),
+            env_file: None,
+            checks: Vec::new(),
+            scripts: Vec::new(),
+            test_dirs: Vec::new(),
+            canonical_imports: BTreeMap::new(),
+            track: None,
+            safe_fix: None,
+            deadline: None,
+            target: None,
+            budgets: BTreeMap::new(),
+            ratchet_mode: None,
+            owner: None,
+            requires_review: false,
+            origin: RuleOrigin::User,
         }
     }
 }
@@ -142,6 +508,8 @@ impl TomlRule {
     pub fn to_rule_config(&self) -> RuleConfig {
         let severity = match self.severity.to_lowercase().as_str() {
             "error" => Severity::Error,
+            "info" => Severity::Info,
+            "hint" => Severity::Hint,
             _ => Severity::Warning,
         };
 
@@ -149,22 +517,45 @@ impl TomlRule {
             id: self.id.clone(),
             severity,
             message: self.message.clone(),
-            suggest: self.suggest.clone(),
+            suggest: self.suggest.clone().map(|s| s.0).unwrap_or_default(),
             glob: self.glob.clone(),
             allowed_classes: self.allowed_classes.clone(),
             token_map: self.token_map.clone(),
             pattern: self.pattern.clone(),
             max_count: self.max_count,
             packages: self.packages.clone(),
+            allow_type_imports: self.allow_type_imports,
+            max_relative_depth: self.max_relative_depth,
+            ban_cross_package_imports: self.ban_cross_package_imports,
             regex: self.regex,
             manifest: self.manifest.clone(),
             exclude_glob: self.exclude_glob.clone(),
             file_contains: self.file_contains.clone(),
             file_not_contains: self.file_not_contains.clone(),
+            sibling_files: self.sibling_files.clone(),
             required_files: self.required_files.clone(),
             forbidden_files: self.forbidden_files.clone(),
+            must_contain: self
+                .must_contain
+                .iter()
+                .map(|(file, patterns)| (file.clone(), patterns.0.clone()))
+                .collect(),
             condition_pattern: self.condition_pattern.clone(),
             skip_strings: self.skip_strings,
+            scope_to_function: self.scope_to_function,
+            components: self.components.clone(),
+            app_dir: self.app_dir.clone(),
+            feature_dirs: self.feature_dirs.clone(),
+            env_vars: self.env_vars.clone(),
+            env_file: self.env_file.clone(),
+            checks: self.checks.clone(),
+            scripts: self.scripts.clone(),
+            test_dirs: self.test_dirs.clone(),
+            canonical_imports: self
+                .canonical_imports
+                .iter()
+                .map(|(module, components)| (module.clone(), components.0.clone()))
+                .collect(),
         }
     }
 }
@@ -0,0 +1,279 @@
+use crate::cli::toml_config::DEFAULT_EXCLUDES;
+use crate::rules::ast::{is_component_node, parse_file};
+use crate::scan::{self, ScanError, WalkPolicy};
+use clap::ValueEnum;
+use rayon::prelude::*;
+use std::cmp::Reverse;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+
+/// Extensions whose files can define components. Plain `.ts` can't hold JSX.
+const COMPONENT_EXTENSIONS: &[&str] = &["tsx", "jsx", "js"];
+
+/// One React component found by `baseline components`.
+#[derive(Debug, Clone)]
+pub struct Component {
+    pub name: String,
+    pub file: PathBuf,
+    pub line: usize,
+    /// Lines the declaration spans, as `max-component-size` counts them.
+    pub lines: usize,
+    /// Calls per hook (`useState`, `useEffect`, custom `use*` hooks, `use`),
+    /// not counting nested components.
+    pub hooks: BTreeMap<String, usize>,
+    /// Props the component reads: destructured names, or distinct
+    /// `props.x` / `this.props.x` accesses.
+    pub props: usize,
+}
+
+impl Component {
+    pub fn hook_count(&self) -> usize {
+        self.hooks.values().sum()
+    }
+}
+
+/// Column `baseline components` sorts by, largest first (`name` sorts A-Z).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ComponentSort {
+    Lines,
+    Hooks,
+    Props,
+    Name,
+}
+
+/// Every component in the files under `paths`. The config's excludes apply
+/// when `config_path` exists; otherwise the default excludes do.
+pub fn inventory(config_path: &Path, paths: &[PathBuf]) -> Result<Vec<Component>, ScanError> {
+    let (excludes, policy) = if config_path.exists() {
+        let loaded = scan::load_config(config_path)?;
+        let policy = WalkPolicy::from_section(&loaded.toml.baseline, config_path);
+        (loaded.toml.baseline.excludes(), policy)
+    } else {
+        let excludes = DEFAULT_EXCLUDES.iter().map(|s| s.to_string()).collect();
+        (excludes, WalkPolicy::default())
+    };
+    let exclude_set = scan::build_glob_set(&excludes)?;
+
+    let files: Vec<PathBuf> = scan::collect_files(paths, &exclude_set, &policy)
+        .into_iter()
+        .filter(|f| {
+            f.extension()
+                .and_then(|e| e.to_str())
+                .is_some_and(|e| COMPONENT_EXTENSIONS.contains(&e))
+        })
+        .collect();
+    let mut components: Vec<Component> = files
+        .par_iter()
+        .flat_map_iter(|file| {
+            let content = std::fs::read_to_string(file).unwrap_or_default();
+            components_in(file, &content)
+        })
+        .collect();
+    components.sort_by(|a, b| a.file.cmp(&b.file).then(a.line.cmp(&b.line)));
+    Ok(components)
+}
+
+/// Components declared in one file, in source order.
+pub fn components_in(path: &Path, content: &str) -> Vec<Component> {
+    let Some(tree) = parse_file(path, content) else {
+        return Vec::new();
+    };
+    let mut components = Vec::new();
+    visit(tree.root_node(), content.as_bytes(), path, &mut components);
+    components
+}
+
+/// Sort `components` by `sort`, biggest first; ties keep file order.
+pub fn sort_components(components: &mut [Component], sort: ComponentSort) {
+    match sort {
+        ComponentSort::Lines => components.sort_by_key(|c| Reverse(c.lines)),
+        ComponentSort::Hooks => components.sort_by_key(|c| Reverse(c.hook_count())),
+        ComponentSort::Props => components.sort_by_key(|c| Reverse(c.props)),
+        ComponentSort::Name => components.sort_by(|a, b| a.name.cmp(&b.name)),
+    }
+}
+
+fn visit(node: tree_sitter::Node, source: &[u8], path: &Path, out: &mut Vec<Component>) {
+    if is_component_node(&node, source) {
+        let mut hooks = BTreeMap::new();
+        let mut renders = false;
+        scan_body(node, source, &mut hooks, &mut renders);
+        // PascalCase helpers and classes that neither render JSX nor call
+        // hooks aren't components
+        if renders || !hooks.is_empty() {
+            out.push(Component {
+                name: component_name(&node, source).unwrap_or_default(),
+                file: path.to_path_buf(),
+                line: node.start_position().row + 1,
+                lines: node.end_position().row - node.start_position().row + 1,
+                hooks,
+                props: count_props(&node, source),
+            });
+        }
+    }
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            visit(child, source, path, out);
+        }
+    }
+}
+
+fn component_name(node: &tree_sitter::Node, source: &[u8]) -> Option<String> {
+    let name = match node.kind() {
+        "arrow_function" => node.parent()?.child_by_field_name("name")?,
+        _ => node.child_by_field_name("name")?,
+    };
+    name.utf8_text(source).ok().map(str::to_string)
+}
+
+/// Count hook calls and look for JSX under `node`, skipping nested
+/// components.
+fn scan_body(node: tree_sitter::Node, source: &[u8], hooks: &mut BTreeMap<String, usize>, renders: &mut bool) {
+    for i in 0..node.child_count() {
+        let Some(child) = node.child(i) else { continue };
+        if is_component_node(&child, source) {
+            continue;
+        }
+        if child.kind().starts_with("jsx_") {
+            *renders = true;
+        }
+        if child.kind() == "call_expression" {
+            if let Some(hook) = hook_name(&child, source) {
+                *hooks.entry(hook.to_string()).or_insert(0) += 1;
+            }
+        }
+        scan_body(child, source, hooks, renders);
+    }
+}
+
+/// `useState` for `useState(...)` and `React.useState(...)`.
+fn hook_name<'a>(call: &tree_sitter::Node, source: &'a [u8]) -> Option<&'a str> {
+    let function = call.child_by_field_name("function")?;
+    let name = match function.kind() {
+        "identifier" => function.utf8_text(source).ok()?,
+        "member_expression" => function.child_by_field_name("property")?.utf8_text(source).ok()?,
+        _ => return None,
+    };
+    let is_hook = name == "use"
+        || name
+            .strip_prefix("use")
+            .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_uppercase()));
+    is_hook.then_some(name)
+}
+
+fn count_props(node: &tree_sitter::Node, source: &[u8]) -> usize {
+    if node.kind() == "class_declaration" {
+        let mut names = BTreeSet::new();
+        member_reads(*node, source, "this.props", &mut names);
+        return names.len();
+    }
+    let param = node
+        .child_by_field_name("parameters")
+        .and_then(|params| params.named_child(0))
+        .or_else(|| node.child_by_field_name("parameter"));
+    let Some(mut param) = param else {
+        return 0;
+    };
+    // TypeScript wraps each parameter (`props: Props`)
+    if matches!(param.kind(), "required_parameter" | "optional_parameter") {
+        match param.child_by_field_name("pattern") {
+            Some(pattern) => param = pattern,
+            None => return 0,
+        }
+    }
+    match param.kind() {
+        "object_pattern" => (0..param.named_child_count())
+            .filter_map(|i| param.named_child(i))
+            .filter(|p| p.kind() != "comment")
+            .count(),
+        "identifier" => {
+            let Some(name) = param.utf8_text(source).ok() else {
+                return 0;
+            };
+            let mut names = BTreeSet::new();
+            if let Some(body) = node.child_by_field_name("body") {
+                member_reads(body, source, name, &mut names);
+            }
+            names.len()
+        }
+        _ => 0,
+    }
+}
+
+/// Properties read off `object` (e.g. `props` or `this.props`) under `node`.
+fn member_reads<'a>(node: tree_sitter::Node, source: &'a [u8], object: &str, names: &mut BTreeSet<&'a str>) {
+    for i in 0..node.child_count() {
+        let Some(child) = node.child(i) else { continue };
+        if child.kind() == "member_expression"
+            && child
+                .child_by_field_name("object")
+                .and_then(|o| o.utf8_text(source).ok())
+                == Some(object)
+        {
+            if let Some(property) = child.child_by_field_name("property").and_then(|p| p.utf8_text(source).ok()) {
+                names.insert(property);
+            }
+        }
+        member_reads(child, source, object, names);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn inventories_components_with_hooks_and_props() {
+        let content = r#"
+type Props = { title: string; items: string[] };
+
+export function List({ title, items, ...rest }: Props) {
+  const [open, setOpen] = useState(false);
+  const [query, setQuery] = React.useState("");
+  useEffect(() => {}, [open]);
+  const Row = ({ item }) => <li>{item}</li>;
+  return <ul {...rest}>{items.map((i) => <Row item={i} />)}</ul>;
+}
+
+const Header = (props) => {
+  const theme = useTheme();
+  return <h1 className={theme.h1}>{props.title} {props.subtitle} {props.title}</h1>;
+};
+
+class Legacy extends React.Component {
+  render() {
+    return <div>{this.props.label}</div>;
+  }
+}
+
+function ApiClient() {
+  return fetch("/api");
+}
+"#;
+        let components = components_in(Path::new("List.tsx"), content);
+        let summary: Vec<_> = components
+            .iter()
+            .map(|c| (c.name.as_str(), c.line, c.lines, c.hook_count(), c.props))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("List", 4, 7, 3, 3),
+                ("Row", 8, 1, 0, 1),
+                ("Header", 12, 4, 1, 2),
+                ("Legacy", 17, 5, 0, 1),
+            ]
+        );
+        assert_eq!(components[0].hooks.get("useState"), Some(&2));
+    }
+
+    #[test]
+    fn sorts_biggest_first() {
+        let content = "function A() { return <a />; }\nfunction B() {\n  useX();\n  return <b />;\n}\n";
+        let mut components = components_in(Path::new("a.jsx"), content);
+        sort_components(&mut components, ComponentSort::Lines);
+        assert_eq!(components[0].name, "B");
+        sort_components(&mut components, ComponentSort::Name);
+        assert_eq!(components[0].name, "A");
+    }
+}
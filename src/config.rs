@@ -1,17 +1,85 @@
-/// Severity level for a rule violation.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// Severity level for a rule violation. By default only `Error` fails a scan
+/// (see `[output.exit]`); `Info` and `Hint` are for socializing a rule before
+/// it starts warning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
 pub enum Severity {
     Error,
     Warning,
+    Info,
+    Hint,
+}
+
+impl Severity {
+    /// Lowercase name, as written in config and machine-readable output.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+            Severity::Info => "info",
+            Severity::Hint => "hint",
+        }
+    }
+
+    /// Whether this severity is `min` or more severe.
+    pub fn at_least(self, min: Severity) -> bool {
+        self.rank() >= min.rank()
+    }
+
+    fn rank(self) -> u8 {
+        match self {
+            Severity::Hint => 0,
+            Severity::Info => 1,
+            Severity::Warning => 2,
+            Severity::Error => 3,
+        }
+    }
+}
+
+/// How columns are counted within a line (`[output] column_unit`).
+///
+/// Rules report byte columns; output converts them, so editors and LSP
+/// clients that count in UTF-16 code units put squiggles under the right
+/// characters on non-ASCII lines.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize, clap::ValueEnum)]
+pub enum ColumnUnit {
+    /// UTF-8 bytes.
+    #[default]
+    #[serde(rename = "bytes")]
+    #[value(name = "bytes")]
+    Bytes,
+    /// Unicode scalar values (Rust `char`s).
+    #[serde(rename = "chars")]
+    #[value(name = "chars")]
+    Chars,
+    /// UTF-16 code units, as the Language Server Protocol counts them.
+    #[serde(rename = "utf-16")]
+    #[value(name = "utf-16")]
+    Utf16,
+}
+
+impl ColumnUnit {
+    /// Length of `text` in this unit.
+    pub fn measure(self, text: &str) -> usize {
+        match self {
+            ColumnUnit::Bytes => text.len(),
+            ColumnUnit::Chars => text.chars().count(),
+            ColumnUnit::Utf16 => text.encode_utf16().count(),
+        }
+    }
 }
 
 /// Parsed rule configuration from `baseline.toml`.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct RuleConfig {
     pub id: String,
     pub severity: Severity,
     pub message: String,
-    pub suggest: Option<String>,
+    /// Remediation text; several entries are offered as alternatives.
+    pub suggest: Vec<String>,
     pub glob: Option<String>,
     /// Classes exempt from enforcement.
     pub allowed_classes: Vec<String>,
@@ -22,7 +90,14 @@ pub struct RuleConfig {
     /// Maximum allowed occurrences (used by ratchet rules).
     pub max_count: Option<usize>,
     /// Banned package names (used by banned-import and banned-dependency rules).
+    /// banned-import also accepts `*` wildcards, e.g. `lodash/*` or `@internal/legacy-*`.
     pub packages: Vec<String>,
+    /// Let `import type` / `export type` through (used by banned-import).
+    pub allow_type_imports: bool,
+    /// Most `../` segments a relative import may climb (used by banned-import).
+    pub max_relative_depth: Option<usize>,
+    /// Ban relative imports that land in another workspace package (used by banned-import).
+    pub ban_cross_package_imports: bool,
     /// Whether `pattern` should be interpreted as a regex (default: false).
     pub regex: bool,
     /// Manifest filename to check (used by banned-dependency, defaults to `package.json`).
@@ -33,14 +108,46 @@ pub struct RuleConfig {
     pub file_contains: Option<String>,
     /// Only run rule if file does NOT contain this string.
     pub file_not_contains: Option<String>,
+    /// Only run rule if one of these files exists next to the scanned file.
+    pub sibling_files: Vec<String>,
     /// Required files that must exist (used by file-presence rule).
     pub required_files: Vec<String>,
     /// Forbidden files that must NOT exist (used by file-presence rule).
     pub forbidden_files: Vec<String>,
+    /// Patterns a required file must contain, keyed by file (used by file-presence rule).
+    pub must_contain: BTreeMap<String, Vec<String>>,
     /// Condition pattern: only enforce required-pattern if this pattern is present.
     pub condition_pattern: Option<String>,
     /// When true, skip matches that fall inside string literals (requires `ast` feature).
     pub skip_strings: bool,
+    /// When true, window-pattern searches the enclosing function instead of a
+    /// fixed line window (JS/TS files only).
+    pub scope_to_function: bool,
+    /// Extra component names checked like native form controls (used by
+    /// require-input-label), e.g. shadcn's `Input`.
+    pub components: Vec<String>,
+    /// Expo Router app directory (used by expo-router-structure; defaults to
+    /// `app` or `src/app`).
+    pub app_dir: Option<String>,
+    /// Glob for feature root directories (used by feature-boundaries).
+    pub feature_dirs: Option<String>,
+    /// Environment variables code may read (used by env-allowlist).
+    pub env_vars: Vec<String>,
+    /// Env template listing further allowed variables, found from each
+    /// scanned file's directory upward (used by env-allowlist).
+    pub env_file: Option<String>,
+    /// Which of a rule's checks run, when it has several (used by
+    /// fetch-safety, package-scripts and pinned-references); empty runs them all.
+    /// For route-handler-validation, the HTTP methods to check.
+    pub checks: Vec<String>,
+    /// Scripts a manifest must define (used by package-scripts).
+    pub scripts: Vec<String>,
+    /// Directories that mirror the source tree with test files, relative to
+    /// the directory holding `src` (used by test-colocation).
+    pub test_dirs: Vec<String>,
+    /// Component names keyed by the module they must be imported from (used
+    /// by canonical-import).
+    pub canonical_imports: BTreeMap<String, Vec<String>>,
 }
 
 impl Default for RuleConfig {
@@ -49,22 +156,37 @@ impl Default for RuleConfig {
             id: String::new(),
             severity: Severity::Warning,
             message: String::new(),
-            suggest: None,
+            suggest: Vec::new(),
             glob: None,
             allowed_classes: Vec::new(),
             token_map: Vec::new(),
             pattern: None,
             max_count: None,
             packages: Vec::new(),
+            allow_type_imports: false,
+            max_relative_depth: None,
+            ban_cross_package_imports: false,
             regex: false,
             manifest: None,
             exclude_glob: Vec::new(),
             file_contains: None,
             file_not_contains: None,
+            sibling_files: Vec::new(),
             required_files: Vec::new(),
             forbidden_files: Vec::new(),
+            must_contain: BTreeMap::new(),
             condition_pattern: None,
             skip_strings: false,
+            scope_to_function: false,
+            components: Vec::new(),
+            app_dir: None,
+            feature_dirs: None,
+            env_vars: Vec::new(),
+            env_file: None,
+            checks: Vec::new(),
+            scripts: Vec::new(),
+            test_dirs: Vec::new(),
+            canonical_imports: BTreeMap::new(),
         }
     }
 }
@@ -0,0 +1,179 @@
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use toml_edit::{ArrayOfTables, DocumentMut, Item, Table, Value};
+
+#[derive(Debug)]
+pub enum ConfigEditError {
+    Read(std::io::Error),
+    Parse(toml_edit::TomlError),
+    Write(std::io::Error),
+}
+
+impl fmt::Display for ConfigEditError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConfigEditError::Read(e) => write!(f, "failed to read config: {}", e),
+            ConfigEditError::Parse(e) => write!(f, "failed to parse config: {}", e),
+            ConfigEditError::Write(e) => write!(f, "failed to write config: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for ConfigEditError {}
+
+/// A config file opened for editing. All commands that modify a config go
+/// through this type, so comments, key order, and formatting the user wrote
+/// survive; only the values being changed are rewritten.
+pub struct ConfigDocument {
+    path: PathBuf,
+    doc: DocumentMut,
+}
+
+impl ConfigDocument {
+    pub fn open(path: &Path) -> Result<Self, ConfigEditError> {
+        let text = fs::read_to_string(path).map_err(ConfigEditError::Read)?;
+        Self::parse(path, &text)
+    }
+
+    /// Parse config text that will be saved to `path`.
+    pub fn parse(path: &Path, text: &str) -> Result<Self, ConfigEditError> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            doc: text.parse().map_err(ConfigEditError::Parse)?,
+        })
+    }
+
+    pub fn save(&self) -> Result<(), ConfigEditError> {
+        fs::write(&self.path, self.doc.to_string()).map_err(ConfigEditError::Write)
+    }
+
+    fn rules(&self) -> Option<&ArrayOfTables> {
+        self.doc.get("rule").and_then(Item::as_array_of_tables)
+    }
+
+    pub fn has_rule(&self, id: &str) -> bool {
+        self.rules()
+            .is_some_and(|rules| rules.iter().any(|t| rule_id(t) == Some(id)))
+    }
+
+    /// The `[[rule]]` table with the given id.
+    pub fn rule_mut(&mut self, id: &str) -> Option<&mut Table> {
+        self.doc
+            .get_mut("rule")
+            .and_then(Item::as_array_of_tables_mut)?
+            .iter_mut()
+            .find(|t| rule_id(t) == Some(id))
+    }
+
+    /// Append a `[[rule]]` table after the existing ones, separated by a
+    /// blank line.
+    pub fn push_rule(&mut self, mut rule: Table) {
+        rule.decor_mut().set_prefix("\n");
+        let rules = self
+            .doc
+            .entry("rule")
+            .or_insert_with(|| Item::ArrayOfTables(ArrayOfTables::new()));
+        if let Some(rules) = rules.as_array_of_tables_mut() {
+            rules.push(rule);
+        }
+    }
+}
+
+impl fmt::Display for ConfigDocument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.doc)
+    }
+}
+
+fn rule_id(table: &Table) -> Option<&str> {
+    table.get("id").and_then(Item::as_str)
+}
+
+/// Set `key` in `table`, keeping the spacing and any trailing comment of the
+/// value it replaces.
+pub fn set_value(table: &mut Table, key: &str, value: impl Into<Value>) {
+    let mut value = value.into();
+    if let Some(old) = table.get(key).and_then(Item::as_value) {
+        *value.decor_mut() = old.decor().clone();
+    }
+    table[key] = Item::Value(value);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONFIG: &str = r#"# Team guardrails
+[baseline]
+exclude = ["dist/**"]  # build output
+
+# Legacy API migration
+[[rule]]
+id   = "legacy-api"
+type = "ratchet"
+max_count = 42 # from 2024 audit
+message = "42 remaining"
+"#;
+
+    fn doc() -> ConfigDocument {
+        ConfigDocument::parse(Path::new("baseline.toml"), CONFIG).unwrap()
+    }
+
+    #[test]
+    fn set_value_keeps_comments_and_alignment() {
+        let mut doc = doc();
+        let rule = doc.rule_mut("legacy-api").unwrap();
+        set_value(rule, "max_count", 10);
+
+        assert_eq!(
+            doc.to_string(),
+            CONFIG.replace("max_count = 42 #", "max_count = 10 #")
+        );
+    }
+
+    #[test]
+    fn push_rule_appends_after_existing_rules() {
+        let mut doc = doc();
+        let mut rule = Table::new();
+        rule["id"] = toml_edit::value("new-rule");
+        doc.push_rule(rule);
+
+        assert!(doc.has_rule("new-rule"));
+        assert_eq!(
+            doc.to_string(),
+            format!("{}\n[[rule]]\nid = \"new-rule\"\n", CONFIG)
+        );
+    }
+
+    #[test]
+    fn push_rule_into_config_without_rules() {
+        let mut doc = ConfigDocument::parse(Path::new("b.toml"), "[baseline]\n").unwrap();
+        assert!(!doc.has_rule("x"));
+        let mut rule = Table::new();
+        rule["id"] = toml_edit::value("x");
+        doc.push_rule(rule);
+        assert_eq!(doc.to_string(), "[baseline]\n\n[[rule]]\nid = \"x\"\n");
+    }
+
+    #[test]
+    fn save_writes_to_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("baseline.toml");
+        fs::write(&path, CONFIG).unwrap();
+
+        let mut doc = ConfigDocument::open(&path).unwrap();
+        set_value(doc.rule_mut("legacy-api").unwrap(), "message", "1 remaining");
+        doc.save().unwrap();
+
+        let text = fs::read_to_string(&path).unwrap();
+        assert!(text.contains("message = \"1 remaining\"\n"));
+        assert!(text.starts_with("# Team guardrails\n"));
+    }
+
+    #[test]
+    fn parse_error_is_reported() {
+        let err = ConfigDocument::parse(Path::new("b.toml"), "[baseline\n").err().unwrap();
+        assert!(err.to_string().contains("failed to parse config"));
+    }
+}
@@ -0,0 +1,137 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A ratchet's migration deadline: from `date` on, its budget drops to
+/// `target`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RatchetDeadline {
+    /// The deadline as written in the config (`YYYY-MM-DD`).
+    pub date: String,
+    /// Budget that applies once the deadline is reached.
+    pub target: usize,
+    /// Days from today until the deadline; zero or less once it is reached.
+    pub days_remaining: i64,
+}
+
+impl RatchetDeadline {
+    /// Deadline `date` as seen on `today` (days since 1970-01-01). Returns
+    /// `None` if `date` isn't a valid `YYYY-MM-DD` date.
+    pub fn new(date: &str, target: usize, today: i64) -> Option<Self> {
+        Some(Self {
+            date: date.to_string(),
+            target,
+            days_remaining: parse_date(date)? - today,
+        })
+    }
+
+    /// Whether the deadline has been reached.
+    pub fn passed(&self) -> bool {
+        self.days_remaining <= 0
+    }
+
+    /// The budget in force: `max_count` until the deadline, `target` after.
+    pub fn max_count(&self, max_count: usize) -> usize {
+        if self.passed() {
+            self.target
+        } else {
+            max_count
+        }
+    }
+
+    /// Matches to remove per week, rounded up, to reach `target` from
+    /// `found` by the deadline.
+    pub fn weekly_rate(&self, found: usize) -> usize {
+        let remaining = found.saturating_sub(self.target);
+        if self.passed() {
+            return remaining;
+        }
+        let days = self.days_remaining as usize;
+        (remaining * 7).div_ceil(days).min(remaining)
+    }
+
+    /// `"45 days left, 3/week"`, or `"deadline 2025-09-01 passed"`.
+    pub fn describe(&self, found: usize) -> String {
+        if self.passed() {
+            format!("deadline {} passed", self.date)
+        } else {
+            let days = if self.days_remaining == 1 { "day" } else { "days" };
+            format!(
+                "{} {} left, {}/week",
+                self.days_remaining,
+                days,
+                self.weekly_rate(found)
+            )
+        }
+    }
+}
+
+/// Today's date in UTC, as days since 1970-01-01.
+pub fn today() -> i64 {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+    (secs / 86_400) as i64
+}
+
+/// Parse a `YYYY-MM-DD` date into days since 1970-01-01.
+pub fn parse_date(date: &str) -> Option<i64> {
+    let mut parts = date.split('-');
+    let (year, month, day) = (parts.next()?, parts.next()?, parts.next()?);
+    if parts.next().is_some() || year.len() != 4 || month.len() != 2 || day.len() != 2 {
+        return None;
+    }
+    let year: i64 = year.parse().ok()?;
+    let month: i64 = month.parse().ok()?;
+    let day: i64 = day.parse().ok()?;
+    let leap = (year % 4 == 0 && year % 100 != 0) || year % 400 == 0;
+    let month_days = match month {
+        2 if leap => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        1..=12 => 31,
+        _ => return None,
+    };
+    if !(1..=month_days).contains(&day) {
+        return None;
+    }
+
+    // Days from civil date (proleptic Gregorian), with March as month 0
+    let y = if month <= 2 { year - 1 } else { year };
+    let era = y.div_euclid(400);
+    let yoe = y - era * 400;
+    let mp = (month + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    Some(era * 146_097 + doe - 719_468)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_dates() {
+        assert_eq!(parse_date("1970-01-01"), Some(0));
+        assert_eq!(parse_date("2000-03-01"), Some(11_017));
+        assert_eq!(parse_date("2024-02-29"), Some(19_782));
+        assert_eq!(parse_date("2025-02-29"), None);
+        assert_eq!(parse_date("2025-13-01"), None);
+        assert_eq!(parse_date("2025-9-1"), None);
+        assert_eq!(parse_date("next week"), None);
+    }
+
+    #[test]
+    fn budget_drops_to_target_at_the_deadline() {
+        let deadline = parse_date("2025-09-01").unwrap();
+        let before = RatchetDeadline::new("2025-09-01", 0, deadline - 28).unwrap();
+        assert_eq!(before.max_count(40), 40);
+        assert_eq!(before.weekly_rate(10), 3);
+        assert_eq!(before.describe(10), "28 days left, 3/week");
+
+        let on = RatchetDeadline::new("2025-09-01", 2, deadline).unwrap();
+        assert!(on.passed());
+        assert_eq!(on.max_count(40), 2);
+        assert_eq!(on.weekly_rate(10), 8);
+        assert_eq!(on.describe(10), "deadline 2025-09-01 passed");
+    }
+}
@@ -0,0 +1,208 @@
+use crate::cli::toml_config::{RuleOrigin, TomlRule};
+use std::fmt::Write;
+
+/// Render the enabled rules as a markdown document for `baseline docs`.
+///
+/// Rules are grouped by where they come from (the project's own config,
+/// each preset, each plugin) in the order the groups first appear, and
+/// keep their resolved order within a group. Everything shown is read off
+/// the resolved rules, so regenerating the document is enough to keep it
+/// in sync with the config.
+pub fn render(rules: &[TomlRule], config_name: &str) -> String {
+    let rules: Vec<&TomlRule> = rules.iter().filter(|r| r.enabled).collect();
+
+    let mut out = String::new();
+    let _ = writeln!(
+        out,
+        "<!-- Generated by `baseline docs` from {}. DO NOT EDIT; regenerate instead. -->\n",
+        config_name
+    );
+    let _ = writeln!(out, "# Guardrails\n");
+    if rules.is_empty() {
+        let _ = writeln!(out, "No rules are enabled.");
+        return out;
+    }
+
+    let errors = rules.iter().filter(|r| r.severity == "error").count();
+    let _ = writeln!(
+        out,
+        "{} rule{} enforced by `baseline`, {} at error severity.\n",
+        rules.len(),
+        if rules.len() == 1 { "" } else { "s" },
+        errors
+    );
+
+    let mut groups: Vec<(&RuleOrigin, Vec<&TomlRule>)> = Vec::new();
+    for rule in rules {
+        match groups.iter_mut().find(|(origin, _)| **origin == rule.origin) {
+            Some((_, members)) => members.push(rule),
+            None => groups.push((&rule.origin, vec![rule])),
+        }
+    }
+
+    for (origin, members) in groups {
+        let _ = writeln!(out, "## {}\n", group_title(origin));
+        for rule in members {
+            write_rule(rule, &mut out);
+        }
+    }
+    out.truncate(out.trim_end().len());
+    out.push('\n');
+    out
+}
+
+fn group_title(origin: &RuleOrigin) -> String {
+    match origin {
+        RuleOrigin::User => "Project rules".into(),
+        RuleOrigin::Preset(name) => format!("Preset: {}", name),
+        RuleOrigin::ScopedPreset { preset, path } => format!("Preset: {} (in {})", preset, path),
+        RuleOrigin::Plugin(path) => format!("Plugin: {}", path.display()),
+    }
+}
+
+fn write_rule(rule: &TomlRule, out: &mut String) {
+    let _ = writeln!(out, "### `{}` · {}\n", rule.id, rule.severity);
+    if !rule.message.is_empty() {
+        let _ = writeln!(out, "{}\n", rule.message);
+    }
+
+    let _ = writeln!(out, "- **Type:** `{}`", rule.rule_type);
+    let mut scope = code(rule.glob.as_deref().unwrap_or("**/*"));
+    if !rule.exclude_glob.is_empty() {
+        scope.push_str(&format!(" (except {})", code_list(&rule.exclude_glob)));
+    }
+    let _ = writeln!(out, "- **Applies to:** {}", scope);
+    if let Some(ref contains) = rule.file_contains {
+        let _ = writeln!(out, "- **Only in files containing:** {}", code(contains));
+    }
+    if let Some(ref not_contains) = rule.file_not_contains {
+        let _ = writeln!(out, "- **Only in files without:** {}", code(not_contains));
+    }
+    for (label, detail) in checks(rule) {
+        let _ = writeln!(out, "- **{}:** {}", label, detail);
+    }
+
+    let suggestions: Vec<&String> = rule.suggest.iter().flat_map(|s| &s.0).collect();
+    match suggestions.as_slice() {
+        [] => {}
+        [one] => {
+            let _ = writeln!(out, "- **Instead:** {}", one);
+        }
+        many => {
+            let _ = writeln!(out, "- **Instead, either:**");
+            for s in many {
+                let _ = writeln!(out, "  - {}", s);
+            }
+        }
+    }
+    let _ = writeln!(out);
+}
+
+/// What the rule looks for, as labelled details.
+fn checks(rule: &TomlRule) -> Vec<(&'static str, String)> {
+    let mut checks = Vec::new();
+    if let Some(ref pattern) = rule.pattern {
+        let kind = if rule.regex { "regex" } else { "text" };
+        checks.push(("Matches", format!("{} ({})", code(pattern), kind)));
+    }
+    if let Some(ref condition) = rule.condition_pattern {
+        checks.push(("Near", code(condition)));
+    }
+    if !rule.packages.is_empty() {
+        checks.push(("Packages", code_list(&rule.packages)));
+    }
+    if !rule.components.is_empty() {
+        checks.push(("Components", code_list(&rule.components)));
+    }
+    if !rule.required_files.is_empty() {
+        checks.push(("Required files", code_list(&rule.required_files)));
+    }
+    if !rule.forbidden_files.is_empty() {
+        checks.push(("Forbidden files", code_list(&rule.forbidden_files)));
+    }
+    if let Some(max) = rule.max_count {
+        checks.push(("Allowed", format!("at most {}", max)));
+    }
+    for (path, max) in &rule.budgets {
+        checks.push(("Allowed", format!("at most {} in {}", max, code(path))));
+    }
+    checks
+}
+
+/// `text` as an inline code span, widening the fence when it contains
+/// backticks.
+fn code(text: &str) -> String {
+    if text.contains('`') {
+        format!("`` {} ``", text)
+    } else {
+        format!("`{}`", text)
+    }
+}
+
+fn code_list(items: &[String]) -> String {
+    items.iter().map(|i| code(i)).collect::<Vec<_>>().join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(id: &str, origin: RuleOrigin) -> TomlRule {
+        TomlRule {
+            id: id.into(),
+            rule_type: "banned-pattern".into(),
+            message: format!("{} message", id),
+            origin,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn groups_rules_by_origin_and_skips_disabled() {
+        let rules = vec![
+            rule("a", RuleOrigin::Preset("nextjs".into())),
+            rule("b", RuleOrigin::User),
+            rule("c", RuleOrigin::Preset("nextjs".into())),
+            TomlRule {
+                enabled: false,
+                ..rule("off", RuleOrigin::User)
+            },
+        ];
+        let doc = render(&rules, "baseline.toml");
+
+        assert!(doc.starts_with("<!-- Generated by `baseline docs` from baseline.toml."));
+        assert!(doc.contains("3 rules enforced by `baseline`, 0 at error severity."));
+        let preset = doc.find("## Preset: nextjs").unwrap();
+        let user = doc.find("## Project rules").unwrap();
+        assert!(preset < doc.find("### `a`").unwrap());
+        assert!(doc.find("### `c`").unwrap() < user);
+        assert!(user < doc.find("### `b`").unwrap());
+        assert!(!doc.contains("`off`"));
+    }
+
+    #[test]
+    fn describes_scope_checks_and_suggestions() {
+        let rules = vec![TomlRule {
+            severity: "error".into(),
+            glob: Some("src/**/*.ts".into()),
+            exclude_glob: vec!["**/*.test.ts".into()],
+            pattern: Some("console.log(`".into()),
+            suggest: Some(crate::cli::toml_config::PatternList(vec![
+                "Use the logger".into(),
+                "Delete it".into(),
+            ])),
+            ..rule("no-console", RuleOrigin::User)
+        }];
+        let doc = render(&rules, "baseline.toml");
+
+        assert!(doc.contains("### `no-console` · error\n\nno-console message\n"));
+        assert!(doc.contains("- **Applies to:** `src/**/*.ts` (except `**/*.test.ts`)\n"));
+        assert!(doc.contains("- **Matches:** `` console.log(` `` (text)\n"));
+        assert!(doc.contains("- **Instead, either:**\n  - Use the logger\n  - Delete it\n"));
+    }
+
+    #[test]
+    fn empty_config_says_so() {
+        assert!(render(&[], "baseline.toml").contains("No rules are enabled."));
+    }
+}
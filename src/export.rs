@@ -0,0 +1,288 @@
+use crate::config::Severity;
+use crate::scan::{self, ScanResult};
+use std::fmt;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Where `scan --export` appends results.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ExportTarget {
+    /// `sqlite:<path>`, a SQLite database created on first use.
+    Sqlite(PathBuf),
+}
+
+impl FromStr for ExportTarget {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.split_once(':') {
+            Some(("sqlite", path)) if !path.is_empty() => Ok(ExportTarget::Sqlite(PathBuf::from(path))),
+            _ => Err(format!("unknown export target '{}' (expected sqlite:<path>)", s)),
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    SqliteNotFound,
+    CommandFailed(String),
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExportError::SqliteNotFound => write!(
+                f,
+                "sqlite3 is not installed or not in PATH; sqlite exports need the SQLite command-line shell"
+            ),
+            ExportError::CommandFailed(msg) => write!(f, "sqlite3 failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for ExportError {}
+
+/// Schema version, stored in SQLite's `user_version`. Bump it when a table
+/// changes so dashboards can tell old rows apart.
+const SCHEMA_VERSION: u32 = 1;
+
+/// Tables are only ever added to; each scan appends one `scans` row plus its
+/// violations and ratchet counts.
+const SCHEMA: &str = "\
+CREATE TABLE IF NOT EXISTS scans (
+    id INTEGER PRIMARY KEY,
+    scanned_at INTEGER NOT NULL,
+    files_scanned INTEGER NOT NULL,
+    rules_loaded INTEGER NOT NULL,
+    errors INTEGER NOT NULL,
+    warnings INTEGER NOT NULL,
+    base_ref TEXT
+);
+CREATE TABLE IF NOT EXISTS violations (
+    scan_id INTEGER NOT NULL REFERENCES scans(id),
+    rule_id TEXT NOT NULL,
+    severity TEXT NOT NULL,
+    file TEXT NOT NULL,
+    line INTEGER,
+    col INTEGER,
+    message TEXT NOT NULL
+);
+CREATE TABLE IF NOT EXISTS ratchets (
+    scan_id INTEGER NOT NULL REFERENCES scans(id),
+    rule_id TEXT NOT NULL,
+    scope TEXT,
+    found INTEGER NOT NULL,
+    max_count INTEGER NOT NULL
+);
+";
+
+/// Append `result` to `target`.
+pub fn export(result: &ScanResult, target: &ExportTarget) -> Result<(), ExportError> {
+    match target {
+        ExportTarget::Sqlite(path) => {
+            let scanned_at = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or(0);
+            run_sqlite(path, &sqlite_script(result, scanned_at))
+        }
+    }
+}
+
+/// Check that the tools `target` needs are installed, so a missing one is
+/// reported before scanning rather than after.
+pub fn check_available(target: &ExportTarget) -> Result<(), ExportError> {
+    match target {
+        ExportTarget::Sqlite(_) => Command::new("sqlite3")
+            .arg("-version")
+            .stdin(Stdio::null())
+            .output()
+            .map(|_| ())
+            .map_err(|_| ExportError::SqliteNotFound),
+    }
+}
+
+/// Feed `script` to the `sqlite3` shell for the database at `path`.
+fn run_sqlite(path: &Path, script: &str) -> Result<(), ExportError> {
+    let mut child = Command::new("sqlite3")
+        .arg("-bail")
+        .arg(path)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|_| ExportError::SqliteNotFound)?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(script.as_bytes())
+            .map_err(|e| ExportError::CommandFailed(e.to_string()))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| ExportError::CommandFailed(e.to_string()))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(ExportError::CommandFailed(stderr.trim().to_string()));
+    }
+    Ok(())
+}
+
+/// SQL that creates the schema if needed, sets its version and appends one
+/// scan, in a single transaction so a failure leaves the database as it was.
+fn sqlite_script(result: &ScanResult, scanned_at: u64) -> String {
+    let count = |severity: Severity| result.violations.iter().filter(|v| v.severity == severity).count();
+    let mut sql = String::from("BEGIN;\n");
+    sql.push_str(SCHEMA);
+    sql.push_str(&format!("PRAGMA user_version = {};\n", SCHEMA_VERSION));
+    sql.push_str(&format!(
+        "INSERT INTO scans (scanned_at, files_scanned, rules_loaded, errors, warnings, base_ref) VALUES ({}, {}, {}, {}, {}, {});\n",
+        scanned_at,
+        result.files_scanned,
+        result.rules_loaded,
+        count(Severity::Error),
+        count(Severity::Warning),
+        result.base_ref.as_deref().map_or("NULL".to_string(), quote),
+    ));
+
+    // The scan row just inserted, which later inserts don't change
+    let scan_id = "(SELECT max(id) FROM scans)";
+    for v in &result.violations {
+        sql.push_str(&format!(
+            "INSERT INTO violations VALUES ({}, {}, {}, {}, {}, {}, {});\n",
+            scan_id,
+            quote(&v.rule_id),
+            quote(v.severity.as_str()),
+            quote(&v.file.to_string_lossy()),
+            v.line.map_or("NULL".to_string(), |l| l.to_string()),
+            v.column.map_or("NULL".to_string(), |c| c.to_string()),
+            quote(&v.message),
+        ));
+    }
+
+    let mut ratchets: Vec<_> = result.ratchet_counts.iter().collect();
+    ratchets.sort_by(|a, b| a.0.cmp(b.0));
+    for (key, &(found, max)) in ratchets {
+        let (rule_id, scope) = scan::split_ratchet_key(key);
+        sql.push_str(&format!(
+            "INSERT INTO ratchets VALUES ({}, {}, {}, {}, {});\n",
+            scan_id,
+            quote(rule_id),
+            scope.map_or("NULL".to_string(), quote),
+            found,
+            max,
+        ));
+    }
+    sql.push_str("COMMIT;\n");
+    sql
+}
+
+/// A SQL string literal.
+fn quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Violation;
+    use std::collections::HashMap;
+
+    fn result() -> ScanResult {
+        let mut result = empty_result();
+        result.violations.push(Violation {
+            rule_id: "no-console".into(),
+            severity: Severity::Error,
+            file: "src/it's.ts".into(),
+            line: Some(3),
+            column: None,
+            message: "no console".into(),
+            suggest: Vec::new(),
+            source_line: None,
+            fix: None,
+            origin: None,
+            enclosing: None,
+        });
+        result.ratchet_counts.insert("legacy:apps/web".into(), (4, 5));
+        result
+    }
+
+    fn empty_result() -> ScanResult {
+        ScanResult {
+            violations: Vec::new(),
+            files_scanned: 2,
+            generated_skipped: 0,
+            rules_loaded: 1,
+            unmatched_globs: Vec::new(),
+            groups: Vec::new(),
+            rules: Vec::new(),
+            ratchet_counts: HashMap::new(),
+            ratchet_deadlines: HashMap::new(),
+            changed_files_count: None,
+            base_ref: None,
+            timings: Default::default(),
+            scanned_files: Vec::new(),
+            file_hashes: HashMap::new(),
+            fixes: None,
+            output: Default::default(),
+            hooks: Default::default(),
+            stopped_early: false,
+        }
+    }
+
+    #[test]
+    fn parses_targets() {
+        assert_eq!(
+            "sqlite:out/guardrails.db".parse::<ExportTarget>(),
+            Ok(ExportTarget::Sqlite(PathBuf::from("out/guardrails.db")))
+        );
+        assert!("sqlite:".parse::<ExportTarget>().is_err());
+        assert!("postgres://db".parse::<ExportTarget>().is_err());
+    }
+
+    #[test]
+    fn script_quotes_values_and_scopes_ratchets() {
+        let sql = sqlite_script(&result(), 1_700_000_000);
+        assert!(sql.contains("VALUES (1700000000, 2, 1, 1, 0, NULL);"));
+        assert!(sql.contains("'no-console', 'error', 'src/it''s.ts', 3, NULL, 'no console');"));
+        assert!(sql.contains("'legacy', 'apps/web', 4, 5);"));
+        assert!(sql.starts_with("BEGIN;\n"));
+        assert!(sql.ends_with("COMMIT;\n"));
+    }
+
+    #[test]
+    fn failed_export_leaves_the_version_unchanged() {
+        if Command::new("sqlite3").arg("-version").output().is_err() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let db = dir.path().join("guardrails.db");
+        // An existing, incompatible `scans` table makes the first INSERT fail
+        run_sqlite(&db, "CREATE TABLE scans (id INTEGER PRIMARY KEY);").unwrap();
+        assert!(export(&result(), &ExportTarget::Sqlite(db.clone())).is_err());
+
+        let output = Command::new("sqlite3").arg(&db).arg("PRAGMA user_version;").output().unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "0");
+    }
+
+    #[test]
+    fn appends_to_a_sqlite_database() {
+        if Command::new("sqlite3").arg("-version").output().is_err() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let db = dir.path().join("guardrails.db");
+        let target = ExportTarget::Sqlite(db.clone());
+        export(&result(), &target).unwrap();
+        export(&result(), &target).unwrap();
+
+        let output = Command::new("sqlite3")
+            .arg(&db)
+            .arg("SELECT count(*), count(DISTINCT scan_id) FROM violations;")
+            .output()
+            .unwrap();
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "2|2");
+    }
+}
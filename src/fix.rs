@@ -0,0 +1,941 @@
+use crate::cli::toml_config::TomlRule;
+use crate::rules::Violation;
+use crate::scan::{self, FixVerifier, ScanResult};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// Unchanged lines shown around each change in a dry-run diff.
+const DIFF_CONTEXT: usize = 3;
+
+/// What happened to a single fix during `scan --fix`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FixStatus {
+    /// Written to disk (or would be, with `--dry-run`).
+    Applied,
+    /// The file changed (or disappeared) after the scan.
+    Stale,
+    /// Overlaps a fix already applied to the same region.
+    Conflict,
+    /// The rule still reported the violation afterwards, so the fix was reverted.
+    Unverified,
+    /// The text to replace is no longer where the violation was reported.
+    NotFound,
+    /// The fixed file could not be written.
+    WriteFailed,
+    /// The config sets `safe_fixes_only` and the rule isn't marked `safe_fix`.
+    Unsafe,
+}
+
+impl FixStatus {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            FixStatus::Applied => "applied",
+            FixStatus::Stale => "stale",
+            FixStatus::Conflict => "conflict",
+            FixStatus::Unverified => "unverified",
+            FixStatus::NotFound => "not-found",
+            FixStatus::WriteFailed => "write-failed",
+            FixStatus::Unsafe => "unsafe",
+        }
+    }
+
+    /// Human-readable reason, for reports.
+    pub fn describe(self) -> &'static str {
+        match self {
+            FixStatus::Applied => "applied",
+            FixStatus::Stale => "file changed since the scan",
+            FixStatus::Conflict => "overlaps another fix",
+            FixStatus::Unverified => "rule still reports the violation after fixing",
+            FixStatus::NotFound => "text to replace not found",
+            FixStatus::WriteFailed => "file could not be written",
+            FixStatus::Unsafe => "rule not marked safe_fix (pass --fix-unsafe to apply)",
+        }
+    }
+}
+
+/// Outcome of one fix, identified by the violation it came from.
+#[derive(Debug, Clone)]
+pub struct FixOutcome {
+    pub file: PathBuf,
+    pub line: Option<usize>,
+    pub rule_id: String,
+    pub status: FixStatus,
+}
+
+/// A file's content before and after its fixes, kept for dry-run diffs.
+#[derive(Debug, Clone)]
+pub struct FileChange {
+    pub path: PathBuf,
+    pub before: String,
+    pub after: String,
+}
+
+impl FileChange {
+    /// The change as a unified diff. Fixes usually rewrite lines in place, so
+    /// equal-length files are compared line by line; otherwise the region
+    /// between the common prefix and suffix is shown as one change.
+    pub fn unified_diff(&self) -> String {
+        let old: Vec<&str> = self.before.lines().collect();
+        let new: Vec<&str> = self.after.lines().collect();
+        let blocks = changed_blocks(&old, &new);
+
+        let mut out = format!("--- {0}\n+++ {0}\n", self.path.display());
+        let mut i = 0;
+        while i < blocks.len() {
+            let mut j = i;
+            while j + 1 < blocks.len() && blocks[j + 1].0.start <= blocks[j].0.end + 2 * DIFF_CONTEXT {
+                j += 1;
+            }
+            let start = blocks[i].0.start.saturating_sub(DIFF_CONTEXT);
+            let old_end = (blocks[j].0.end + DIFF_CONTEXT).min(old.len());
+            let new_end = old_end + blocks[j].1.end - blocks[j].0.end;
+            out.push_str(&format!(
+                "@@ -{},{} +{},{} @@\n",
+                start + 1,
+                old_end - start,
+                start + 1,
+                new_end - start
+            ));
+
+            let mut cursor = start;
+            for (old_range, new_range) in &blocks[i..=j] {
+                for line in &old[cursor..old_range.start] {
+                    out.push_str(&format!(" {}\n", line));
+                }
+                for line in &old[old_range.clone()] {
+                    out.push_str(&format!("-{}\n", line));
+                }
+                for line in &new[new_range.clone()] {
+                    out.push_str(&format!("+{}\n", line));
+                }
+                cursor = old_range.end;
+            }
+            for line in &old[cursor..old_end] {
+                out.push_str(&format!(" {}\n", line));
+            }
+            i = j + 1;
+        }
+        out
+    }
+}
+
+/// Line ranges that differ between `old` and `new`, paired old/new.
+fn changed_blocks(old: &[&str], new: &[&str]) -> Vec<(Range<usize>, Range<usize>)> {
+    if old.len() == new.len() {
+        let mut blocks = Vec::new();
+        let mut i = 0;
+        while i < old.len() {
+            if old[i] == new[i] {
+                i += 1;
+                continue;
+            }
+            let start = i;
+            while i < old.len() && old[i] != new[i] {
+                i += 1;
+            }
+            blocks.push((start..i, start..i));
+        }
+        return blocks;
+    }
+
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    vec![(prefix..old.len() - suffix, prefix..new.len() - suffix)]
+}
+
+/// Every fix considered by `apply_fixes`, with its outcome.
+#[derive(Debug, Clone, Default)]
+pub struct FixReport {
+    pub outcomes: Vec<FixOutcome>,
+    pub dry_run: bool,
+    /// Files that would change, recorded only for dry runs.
+    pub changes: Vec<FileChange>,
+}
+
+impl FixReport {
+    pub fn applied(&self) -> usize {
+        self.count(|s| s == FixStatus::Applied)
+    }
+
+    /// Applied fixes per rule id, sorted by id.
+    pub fn applied_by_rule(&self) -> BTreeMap<&str, usize> {
+        let mut counts = BTreeMap::new();
+        for o in self.outcomes.iter().filter(|o| o.status == FixStatus::Applied) {
+            *counts.entry(o.rule_id.as_str()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Fixes left out for any reason other than a conflict.
+    pub fn skipped(&self) -> usize {
+        self.count(|s| s != FixStatus::Applied && s != FixStatus::Conflict)
+    }
+
+    pub fn conflicted(&self) -> usize {
+        self.count(|s| s == FixStatus::Conflict)
+    }
+
+    /// Outcomes for fixes that were not applied.
+    pub fn unapplied(&self) -> impl Iterator<Item = &FixOutcome> {
+        self.outcomes
+            .iter()
+            .filter(|o| o.status != FixStatus::Applied)
+    }
+
+    fn count(&self, pred: impl Fn(FixStatus) -> bool) -> usize {
+        self.outcomes.iter().filter(|o| pred(o.status)).count()
+    }
+}
+
+/// Whether `rule`'s fixes count as safe under `safe_fixes_only`: its
+/// `safe_fix` setting, or else whether it is a built-in fix. The default
+/// `tailwind-theme-tokens` mapping is built in; a custom `token_map` is not.
+pub fn is_safe_fix(rule: &TomlRule) -> bool {
+    rule.safe_fix
+        .unwrap_or_else(|| rule.rule_type == "tailwind-theme-tokens" && rule.token_map.is_empty())
+}
+
+/// Narrow `result` to violations that carry a fix, and to `rules` unless
+/// that is empty. Used by `baseline fix`.
+pub fn retain_fixable(result: &mut ScanResult, rules: &[String]) {
+    result
+        .violations
+        .retain(|v| v.fix.is_some() && (rules.is_empty() || rules.contains(&v.rule_id)));
+}
+
+/// Apply fixes from violations to source files.
+///
+/// Fixes are targeted to the line where the violation occurred to avoid
+/// replacing a different occurrence of the same pattern. A file whose content
+/// no longer matches the hash recorded at scan time is left alone. Fixes whose
+/// regions overlap on the same line are applied first-come, the rest reported
+/// as conflicts. With a `verifier`, each fix is kept only if re-running the
+/// rules shows one fewer violation of that rule on that line. Unless
+/// `allow_unsafe`, fixes from rules gated by `safe_fixes_only` are left out.
+pub fn apply_fixes(
+    result: &ScanResult,
+    verifier: Option<&FixVerifier>,
+    dry_run: bool,
+    allow_unsafe: bool,
+) -> FixReport {
+    let mut report = FixReport {
+        dry_run,
+        ..Default::default()
+    };
+
+    let mut by_file: BTreeMap<&Path, Vec<&Violation>> = BTreeMap::new();
+    for v in result.violations.iter().filter(|v| v.fix.is_some()) {
+        if !allow_unsafe && scan::rule_for(result, v).is_some_and(|meta| meta.fix_gated) {
+            report.outcomes.push(FixOutcome {
+                file: v.file.clone(),
+                line: v.line,
+                rule_id: v.rule_id.clone(),
+                status: FixStatus::Unsafe,
+            });
+            continue;
+        }
+        by_file.entry(v.file.as_path()).or_default().push(v);
+    }
+
+    for (path, fixes) in by_file {
+        let current = fs::read_to_string(path).ok().filter(|content| {
+            result
+                .file_hashes
+                .get(path)
+                .is_none_or(|&hash| scan::content_hash(content) == hash)
+        });
+
+        let statuses = match current {
+            Some(ref content) => {
+                let (modified, mut statuses) = fix_content(path, content, &fixes, verifier);
+                if dry_run && statuses.contains(&FixStatus::Applied) {
+                    report.changes.push(FileChange {
+                        path: path.to_path_buf(),
+                        before: content.clone(),
+                        after: modified.clone(),
+                    });
+                }
+                if !dry_run && statuses.contains(&FixStatus::Applied) {
+                    if let Err(e) = fs::write(path, &modified) {
+                        tracing::error!("failed to write {}: {}", path.display(), e);
+                        for s in statuses.iter_mut().filter(|s| **s == FixStatus::Applied) {
+                            *s = FixStatus::WriteFailed;
+                        }
+                    }
+                }
+                statuses
+            }
+            None => {
+                tracing::warn!("{} changed since the scan; skipping its fixes", path.display());
+                vec![FixStatus::Stale; fixes.len()]
+            }
+        };
+
+        for (v, status) in fixes.iter().zip(statuses) {
+            report.outcomes.push(FixOutcome {
+                file: v.file.clone(),
+                line: v.line,
+                rule_id: v.rule_id.clone(),
+                status,
+            });
+        }
+    }
+
+    report
+}
+
+/// Apply the fixes in a `--stdin` scan's `result` to `content`, the text it
+/// read, and return the fixed text. Nothing is written to disk: the report is
+/// attached to `result` and fixed violations are dropped from it, so what
+/// remains is what still needs attention.
+pub fn fix_stdin(result: &mut ScanResult, content: &str, verifier: Option<&FixVerifier>, allow_unsafe: bool) -> String {
+    let mut report = FixReport::default();
+    let mut fixes = Vec::new();
+    for (i, v) in result.violations.iter().enumerate().filter(|(_, v)| v.fix.is_some()) {
+        if !allow_unsafe && scan::rule_for(result, v).is_some_and(|meta| meta.fix_gated) {
+            report.outcomes.push(FixOutcome {
+                file: v.file.clone(),
+                line: v.line,
+                rule_id: v.rule_id.clone(),
+                status: FixStatus::Unsafe,
+            });
+            continue;
+        }
+        fixes.push(i);
+    }
+    if fixes.is_empty() {
+        result.fixes = Some(report);
+        return content.to_string();
+    }
+
+    let violations: Vec<&Violation> = fixes.iter().map(|&i| &result.violations[i]).collect();
+    let (fixed, statuses) = fix_content(&violations[0].file, content, &violations, verifier);
+    let mut applied = HashSet::new();
+    for ((&i, v), status) in fixes.iter().zip(&violations).zip(statuses) {
+        if status == FixStatus::Applied {
+            applied.insert(i);
+        }
+        report.outcomes.push(FixOutcome {
+            file: v.file.clone(),
+            line: v.line,
+            rule_id: v.rule_id.clone(),
+            status,
+        });
+    }
+
+    let mut i = 0;
+    result.violations.retain(|_| {
+        i += 1;
+        !applied.contains(&(i - 1))
+    });
+    result.fixes = Some(report);
+    fixed
+}
+
+/// Apply `fixes` to `content`, returning the new content and one status per
+/// fix (in input order).
+fn fix_content(
+    path: &Path,
+    content: &str,
+    fixes: &[&Violation],
+    verifier: Option<&FixVerifier>,
+) -> (String, Vec<FixStatus>) {
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let mut statuses = vec![FixStatus::NotFound; fixes.len()];
+
+    // Earlier columns win when regions overlap
+    let mut order: Vec<usize> = (0..fixes.len()).collect();
+    order.sort_by_key(|&i| (fixes[i].line, fixes[i].column));
+
+    let mut claimed: HashMap<usize, Vec<(usize, usize)>> = HashMap::new();
+    let mut before = verifier.map(|ver| ver.check(path, &lines.join(newline)));
+
+    for i in order {
+        let v = fixes[i];
+        let fix = v.fix.as_ref().expect("only fixable violations are grouped");
+
+        if let Some(line) = v.line {
+            let region = fix_region(v.column, &fix.old);
+            let taken = claimed.entry(line).or_default();
+            if taken.iter().any(|&(s, e)| region.0 < e && s < region.1) {
+                statuses[i] = FixStatus::Conflict;
+                continue;
+            }
+            taken.push(region);
+        }
+
+        let mut candidate = lines.clone();
+        if !replace_in(&mut candidate, v.line, &fix.old, &fix.new) {
+            continue;
+        }
+
+        if let (Some(ver), Some(prev)) = (verifier, before.as_ref()) {
+            let after = ver.check(path, &candidate.join(newline));
+            if count_matching(&after, v) >= count_matching(prev, v) {
+                statuses[i] = FixStatus::Unverified;
+                continue;
+            }
+            before = Some(after);
+        }
+
+        lines = candidate;
+        statuses[i] = FixStatus::Applied;
+    }
+
+    let mut output = lines.join(newline);
+    if content.ends_with('\n') {
+        output.push_str(newline);
+    }
+    (output, statuses)
+}
+
+/// Byte range a fix replaces on its line. Without a column the whole line is
+/// claimed, so any other fix on that line conflicts.
+fn fix_region(column: Option<usize>, old: &str) -> (usize, usize) {
+    match column {
+        Some(c) => {
+            let start = c.saturating_sub(1);
+            (start, start + old.len().max(1))
+        }
+        None => (0, usize::MAX),
+    }
+}
+
+/// Replace the first occurrence of `old` on `line` (1-indexed), or anywhere
+/// in the file when the violation has no line.
+fn replace_in(lines: &mut Vec<String>, line: Option<usize>, old: &str, new: &str) -> bool {
+    match line {
+        Some(ln) if ln > 0 && ln <= lines.len() => {
+            let target = &lines[ln - 1];
+            if !target.contains(old) {
+                return false;
+            }
+            lines[ln - 1] = target.replacen(old, new, 1);
+            true
+        }
+        Some(_) => false,
+        None => {
+            let joined = lines.join("\n");
+            if !joined.contains(old) {
+                return false;
+            }
+            *lines = joined
+                .replacen(old, new, 1)
+                .lines()
+                .map(|l| l.to_string())
+                .collect();
+            true
+        }
+    }
+}
+
+/// A position in the LSP sense: zero-based line, and a character offset in
+/// UTF-16 code units.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Position {
+    pub line: usize,
+    pub character: usize,
+}
+
+/// A fix expressed as an LSP `TextEdit` (`range` + `newText`), so editors can
+/// apply it without redoing the old/new string matching. A violation with
+/// several fixable suggestions yields one edit per suggestion.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextEdit {
+    pub file: PathBuf,
+    pub rule_id: String,
+    pub message: String,
+    /// Title of the suggestion this edit applies, for the code action label.
+    pub title: Option<String>,
+    pub start: Position,
+    pub end: Position,
+    pub new_text: String,
+}
+
+/// Resolve every fix in `result` to a `TextEdit`. `read` supplies file
+/// content (from disk, or stdin for `--stdin`). Fixes in files that changed
+/// since the scan, or whose text can no longer be found, are left out.
+pub fn text_edits(result: &ScanResult, read: impl Fn(&Path) -> Option<String>) -> Vec<TextEdit> {
+    let mut contents: HashMap<&Path, Option<String>> = HashMap::new();
+    let mut edits = Vec::new();
+
+    for v in &result.violations {
+        let fixes = v.fixes();
+        if fixes.is_empty() {
+            continue;
+        }
+        let content = contents.entry(v.file.as_path()).or_insert_with(|| {
+            read(&v.file).filter(|content| {
+                result
+                    .file_hashes
+                    .get(v.file.as_path())
+                    .is_none_or(|&hash| scan::content_hash(content) == hash)
+            })
+        });
+        let Some(content) = content.as_deref() else { continue };
+
+        for (title, fix) in fixes {
+            let Some(start) = locate_fix(content, v.line, v.column, &fix.old) else {
+                continue;
+            };
+            edits.push(TextEdit {
+                file: v.file.clone(),
+                rule_id: v.rule_id.clone(),
+                message: v.message.clone(),
+                title: title.map(String::from),
+                start: position_at(content, start),
+                end: position_at(content, start + fix.old.len()),
+                new_text: fix.new.clone(),
+            });
+        }
+    }
+
+    edits
+}
+
+/// Byte offset of `old` in `content`: at the reported column when the text is
+/// there, else its first occurrence on the reported line (matching what
+/// `--fix` replaces), or in the file when the violation has no line.
+fn locate_fix(content: &str, line: Option<usize>, column: Option<usize>, old: &str) -> Option<usize> {
+    let Some(line) = line else {
+        return content.find(old);
+    };
+    let line_start = if line == 1 {
+        0
+    } else {
+        content.match_indices('\n').nth(line.checked_sub(2)?)?.0 + 1
+    };
+    let rest = &content[line_start..];
+    let text = rest[..rest.find('\n').unwrap_or(rest.len())].trim_end_matches('\r');
+
+    let at_column = column
+        .map(|c| c.saturating_sub(1))
+        .filter(|&c| text.get(c..).is_some_and(|t| t.starts_with(old)));
+    at_column.or_else(|| text.find(old)).map(|c| line_start + c)
+}
+
+/// LSP position of a byte offset in `content`.
+fn position_at(content: &str, offset: usize) -> Position {
+    let before = &content[..offset];
+    let line_start = before.rfind('\n').map_or(0, |i| i + 1);
+    Position {
+        line: before.matches('\n').count(),
+        character: before[line_start..].encode_utf16().count(),
+    }
+}
+
+/// `file://` URI for a path, made absolute against the working directory.
+pub fn file_uri(path: &Path) -> String {
+    let absolute = std::path::absolute(path).unwrap_or_else(|_| path.to_path_buf());
+    let mut text = absolute.to_string_lossy().replace('\\', "/");
+    if !text.starts_with('/') {
+        text.insert(0, '/');
+    }
+
+    let mut uri = String::from("file://");
+    for byte in text.bytes() {
+        if byte.is_ascii_alphanumeric() || b"-._~/".contains(&byte) {
+            uri.push(byte as char);
+        } else {
+            uri.push_str(&format!("%{:02X}", byte));
+        }
+    }
+    uri
+}
+
+/// Violations of the same rule on the same line as `target`.
+fn count_matching(violations: &[Violation], target: &Violation) -> usize {
+    violations
+        .iter()
+        .filter(|v| v.rule_id == target.rule_id && (target.line.is_none() || v.line == target.line))
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Severity;
+    use crate::rules::{Fix, Suggestion};
+
+    fn fixable(file: &Path, line: Option<usize>, column: Option<usize>, old: &str, new: &str) -> Violation {
+        Violation {
+            rule_id: "theme".into(),
+            severity: Severity::Warning,
+            file: file.to_path_buf(),
+            line,
+            column,
+            message: "raw color".into(),
+            suggest: Vec::new(),
+            source_line: None,
+            fix: Some(Fix {
+                old: old.into(),
+                new: new.into(),
+            }),
+            origin: None,
+            enclosing: None,
+        }
+    }
+
+    fn result(violations: Vec<Violation>) -> ScanResult {
+        ScanResult {
+            violations,
+            files_scanned: 1,
+            generated_skipped: 0,
+            rules_loaded: 1,
+            unmatched_globs: Vec::new(),
+            groups: Vec::new(),
+            rules: Vec::new(),
+            ratchet_counts: HashMap::new(),
+            ratchet_deadlines: HashMap::new(),
+            changed_files_count: None,
+            base_ref: None,
+            timings: Default::default(),
+            scanned_files: vec![],
+            file_hashes: HashMap::new(),
+            fixes: None,
+            output: Default::default(),
+            hooks: Default::default(),
+            stopped_early: false,
+        }
+    }
+
+    #[test]
+    fn apply_fixes_line_targeted() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("test.tsx");
+        fs::write(&file, "let a = bg-white;\nlet b = bg-white;\n").unwrap();
+
+        let scan = result(vec![fixable(&file, Some(1), Some(9), "bg-white", "bg-background")]);
+        let report = apply_fixes(&scan, None, false, true);
+        assert_eq!(report.applied(), 1);
+
+        // Only line 1 should be fixed
+        let content = fs::read_to_string(&file).unwrap();
+        assert_eq!(content, "let a = bg-background;\nlet b = bg-white;\n");
+    }
+
+    #[test]
+    fn fix_stdin_returns_fixed_content_and_keeps_unfixed_violations() {
+        let file = Path::new("stdin.tsx");
+        let mut unfixable = fixable(file, Some(2), Some(1), "x", "y");
+        unfixable.fix = None;
+        let mut scan = result(vec![
+            fixable(file, Some(1), Some(9), "bg-white", "bg-background"),
+            unfixable,
+            fixable(file, Some(2), Some(9), "bg-black", "bg-foreground"),
+        ]);
+        let content = "let a = bg-white;\nlet b = bg-slate;\n";
+
+        let fixed = fix_stdin(&mut scan, content, None, true);
+        assert_eq!(fixed, "let a = bg-background;\nlet b = bg-slate;\n");
+        let report = scan.fixes.as_ref().unwrap();
+        assert_eq!((report.applied(), report.skipped()), (1, 1));
+        let left: Vec<_> = scan.violations.iter().map(|v| (v.line, v.fix.is_some())).collect();
+        assert_eq!(left, vec![(Some(2), false), (Some(2), true)]);
+        assert!(!file.exists());
+    }
+
+    #[test]
+    fn apply_fixes_no_line_fallback() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("test.tsx");
+        fs::write(&file, "bg-white is used here\n").unwrap();
+
+        let scan = result(vec![fixable(&file, None, None, "bg-white", "bg-background")]);
+        assert_eq!(apply_fixes(&scan, None, false, true).applied(), 1);
+        assert!(fs::read_to_string(&file).unwrap().contains("bg-background"));
+    }
+
+    #[test]
+    fn apply_fixes_dry_run_no_write() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("test.tsx");
+        fs::write(&file, "bg-white\n").unwrap();
+
+        let scan = result(vec![fixable(&file, Some(1), Some(1), "bg-white", "bg-background")]);
+        let report = apply_fixes(&scan, None, true, true);
+        assert_eq!(report.applied(), 1);
+        assert!(report.dry_run);
+        assert_eq!(fs::read_to_string(&file).unwrap(), "bg-white\n");
+        assert_eq!(report.changes.len(), 1);
+        assert_eq!(report.changes[0].after, "bg-background\n");
+    }
+
+    #[test]
+    fn retain_fixable_filters_by_rule() {
+        let violations = || {
+            let file = Path::new("a.tsx");
+            let mut other = fixable(file, Some(2), None, "x", "y");
+            other.rule_id = "other".into();
+            let mut unfixable = fixable(file, Some(3), None, "x", "y");
+            unfixable.fix = None;
+            vec![fixable(file, Some(1), None, "x", "y"), other, unfixable]
+        };
+
+        let mut all = result(violations());
+        retain_fixable(&mut all, &[]);
+        assert_eq!(all.violations.len(), 2);
+
+        let mut selected = result(violations());
+        retain_fixable(&mut selected, &["other".into()]);
+        assert_eq!(selected.violations.len(), 1);
+        assert_eq!(selected.violations[0].rule_id, "other");
+    }
+
+    #[test]
+    fn applied_counts_are_grouped_by_rule() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("test.tsx");
+        fs::write(&file, "bg-white\nbg-white\ntext-black\n").unwrap();
+
+        let mut text = fixable(&file, Some(3), Some(1), "text-black", "text-foreground");
+        text.rule_id = "text".into();
+        let scan = result(vec![
+            fixable(&file, Some(1), Some(1), "bg-white", "bg-background"),
+            fixable(&file, Some(2), Some(1), "bg-white", "bg-background"),
+            text,
+        ]);
+        let report = apply_fixes(&scan, None, false, true);
+        let counts: Vec<_> = report.applied_by_rule().into_iter().collect();
+        assert_eq!(counts, [("text", 1), ("theme", 2)]);
+        assert!(report.changes.is_empty());
+    }
+
+    #[test]
+    fn unified_diff_shows_changed_lines_with_context() {
+        let before: String = (1..=12).map(|i| format!("line {}\n", i)).collect();
+        let change = FileChange {
+            path: PathBuf::from("a.ts"),
+            after: before.replace("line 2\n", "line two\n").replace("line 11\n", "line eleven\n"),
+            before,
+        };
+        assert_eq!(
+            change.unified_diff(),
+            "--- a.ts\n+++ a.ts\n\
+             @@ -1,5 +1,5 @@\n line 1\n-line 2\n+line two\n line 3\n line 4\n line 5\n\
+             @@ -8,5 +8,5 @@\n line 8\n line 9\n line 10\n-line 11\n+line eleven\n line 12\n"
+        );
+
+        let grown = FileChange {
+            path: PathBuf::from("b.ts"),
+            before: "a\nb\nc\n".into(),
+            after: "a\nb1\nb2\nc\n".into(),
+        };
+        assert_eq!(
+            grown.unified_diff(),
+            "--- b.ts\n+++ b.ts\n@@ -1,3 +1,4 @@\n a\n-b\n+b1\n+b2\n c\n"
+        );
+    }
+
+    #[test]
+    fn apply_fixes_multiple_in_same_file_keeps_newlines() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("test.tsx");
+        fs::write(&file, "bg-white text-gray-900\r\nbg-white text-gray-500\r\n").unwrap();
+
+        let scan = result(vec![
+            fixable(&file, Some(1), Some(1), "bg-white", "bg-background"),
+            fixable(&file, Some(2), Some(1), "bg-white", "bg-background"),
+        ]);
+        assert_eq!(apply_fixes(&scan, None, false, true).applied(), 2);
+        assert_eq!(
+            fs::read_to_string(&file).unwrap(),
+            "bg-background text-gray-900\r\nbg-background text-gray-500\r\n"
+        );
+    }
+
+    #[test]
+    fn apply_fixes_skips_missing_and_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("test.tsx");
+        fs::write(&file, "bg-white\n").unwrap();
+
+        let mut scan = result(vec![
+            fixable(&file, Some(1), Some(1), "bg-white", "bg-background"),
+            fixable(Path::new("/nonexistent/file.tsx"), Some(1), Some(1), "old", "new"),
+        ]);
+        scan.file_hashes
+            .insert(file.clone(), scan::content_hash("bg-white\nmore\n"));
+
+        let report = apply_fixes(&scan, None, false, true);
+        assert_eq!(report.applied(), 0);
+        assert_eq!(report.skipped(), 2);
+        assert!(report.outcomes.iter().all(|o| o.status == FixStatus::Stale));
+        assert_eq!(fs::read_to_string(&file).unwrap(), "bg-white\n");
+    }
+
+    #[test]
+    fn apply_fixes_reports_overlapping_fixes() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("test.tsx");
+        fs::write(&file, "bg-white-ish\n").unwrap();
+
+        let scan = result(vec![
+            fixable(&file, Some(1), Some(1), "bg-white-ish", "bg-a"),
+            fixable(&file, Some(1), Some(1), "bg-white", "bg-b"),
+        ]);
+        let report = apply_fixes(&scan, None, false, true);
+        assert_eq!(report.applied(), 1);
+        assert_eq!(report.conflicted(), 1);
+        assert_eq!(fs::read_to_string(&file).unwrap(), "bg-a\n");
+    }
+
+    #[test]
+    fn apply_fixes_reverts_fix_that_does_not_resolve_violation() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "theme"
+type = "tailwind-theme-tokens"
+severity = "warning"
+glob = "**/*.tsx"
+token_map = ["bg-white=bg-slate-50"]
+message = "use tokens"
+"#,
+        )
+        .unwrap();
+        let file = dir.path().join("a.tsx");
+        let source = "const A = () => <div className=\"bg-white text-gray-900\" />;\n";
+        fs::write(&file, source).unwrap();
+
+        let scan = scan::run_scan(&config, std::slice::from_ref(&file)).unwrap();
+        assert_eq!(scan.violations.len(), 2);
+
+        let verifier = FixVerifier::new(&config).unwrap();
+        let report = apply_fixes(&scan, Some(&verifier), false, true);
+        let status = |old: &str| {
+            let v = scan
+                .violations
+                .iter()
+                .position(|v| v.fix.as_ref().unwrap().old == old)
+                .unwrap();
+            report.outcomes[v].status
+        };
+        // bg-slate-50 is itself a raw color, so that fix is rejected
+        assert_eq!(status("bg-white"), FixStatus::Unverified);
+        assert_eq!(status("text-gray-900"), FixStatus::Applied);
+        assert_eq!(
+            fs::read_to_string(&file).unwrap(),
+            "const A = () => <div className=\"bg-white text-foreground\" />;\n"
+        );
+    }
+
+    #[test]
+    fn safe_fixes_only_gates_custom_fixes() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        let rules = r#"
+[[rule]]
+id = "builtin"
+type = "tailwind-theme-tokens"
+glob = "a.tsx"
+message = "use tokens"
+
+[[rule]]
+id = "custom"
+type = "tailwind-theme-tokens"
+glob = "b.tsx"
+token_map = ["bg-white=bg-card"]
+message = "use tokens"
+"#;
+        fs::write(&config, format!("[baseline]\nsafe_fixes_only = true\n{}", rules)).unwrap();
+        let source = "const A = () => <div className=\"bg-white\" />;\n";
+        let (a, b) = (dir.path().join("a.tsx"), dir.path().join("b.tsx"));
+        fs::write(&a, source).unwrap();
+        fs::write(&b, source).unwrap();
+
+        let scan = scan::run_scan(&config, &[a.clone(), b.clone()]).unwrap();
+        let report = apply_fixes(&scan, None, true, false);
+        let status = |id: &str| report.outcomes.iter().find(|o| o.rule_id == id).unwrap().status;
+        assert_eq!(status("builtin"), FixStatus::Applied);
+        assert_eq!(status("custom"), FixStatus::Unsafe);
+
+        let report = apply_fixes(&scan, None, true, true);
+        assert_eq!(report.applied(), 2);
+
+        // Marking the rule safe lets it through without the override
+        let rules = rules.replace("token_map", "safe_fix = true\ntoken_map");
+        fs::write(&config, format!("[baseline]\nsafe_fixes_only = true\n{}", rules)).unwrap();
+        let scan = scan::run_scan(&config, &[a, b]).unwrap();
+        assert_eq!(apply_fixes(&scan, None, true, false).applied(), 2);
+    }
+
+    #[test]
+    fn text_edits_use_column_and_utf16_offsets() {
+        let content = "let a = \"bg-white\";\r\nconst é = \"bg-white bg-white\";\n";
+        let file = Path::new("a.tsx");
+        let scan = result(vec![
+            fixable(file, Some(2), Some(22), "bg-white", "bg-background"),
+            fixable(file, Some(1), Some(99), "bg-white", "bg-background"),
+            fixable(file, None, None, "const", "let"),
+            fixable(file, Some(1), Some(1), "missing", "x"),
+        ]);
+
+        let edits = text_edits(&scan, |_| Some(content.to_string()));
+        assert_eq!(edits.len(), 3, "text that can't be found is left out");
+        let ranges: Vec<_> = edits
+            .iter()
+            .map(|e| (e.start.line, e.start.character, e.end.line, e.end.character))
+            .collect();
+        // "é" is two bytes but one UTF-16 unit; the column points at the second match
+        assert_eq!(ranges, [(1, 20, 1, 28), (0, 9, 0, 17), (1, 0, 1, 5)]);
+        assert_eq!(edits[0].new_text, "bg-background");
+        assert_eq!(edits[0].rule_id, "theme");
+    }
+
+    #[test]
+    fn text_edits_offer_each_suggested_fix() {
+        let file = Path::new("a.tsx");
+        let mut v = fixable(file, Some(1), Some(1), "bg-white", "bg-background");
+        let fix = |new: &str| Fix {
+            old: "bg-white".into(),
+            new: new.into(),
+        };
+        v.suggest = vec![
+            Suggestion::with_fix("Use bg-background", fix("bg-background")),
+            Suggestion::with_fix("Use bg-card", fix("bg-card")),
+            Suggestion::new("Add a dark: variant"),
+        ];
+
+        let edits = text_edits(&result(vec![v]), |_| Some("bg-white".into()));
+        let offered: Vec<_> = edits
+            .iter()
+            .map(|e| (e.title.as_deref(), e.new_text.as_str()))
+            .collect();
+        assert_eq!(
+            offered,
+            [(Some("Use bg-background"), "bg-background"), (Some("Use bg-card"), "bg-card")]
+        );
+    }
+
+    #[test]
+    fn text_edits_skip_files_changed_since_scan() {
+        let file = Path::new("a.tsx");
+        let mut scan = result(vec![fixable(file, Some(1), Some(1), "old", "new")]);
+        scan.file_hashes.insert(file.to_path_buf(), scan::content_hash("old"));
+
+        assert_eq!(text_edits(&scan, |_| Some("old".into())).len(), 1);
+        assert!(text_edits(&scan, |_| Some("old!".into())).is_empty());
+        assert!(text_edits(&scan, |_| None).is_empty());
+    }
+
+    #[test]
+    fn file_uri_is_absolute_and_escaped() {
+        let uri = file_uri(Path::new("/tmp/my app/a#1.ts"));
+        assert_eq!(uri, "file:///tmp/my%20app/a%231.ts");
+        assert!(file_uri(Path::new("rel.ts")).starts_with("file:///"));
+    }
+}
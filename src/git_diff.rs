@@ -1,7 +1,7 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
 use std::ops::RangeInclusive;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
 #[derive(Debug)]
@@ -27,11 +27,25 @@ impl fmt::Display for GitDiffError {
 
 impl std::error::Error for GitDiffError {}
 
+/// How a file in the diff changed relative to the base ref.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    /// The file did not exist at the base ref.
+    Added,
+    /// The file existed at the base ref, or was renamed or copied from one that did.
+    Modified,
+}
+
 /// Changed files and line ranges from a git diff.
-#[derive(Debug)]
+#[derive(Debug, Default)]
 pub struct DiffInfo {
     /// Map of relative file path to list of changed line ranges.
     pub changed_lines: HashMap<PathBuf, Vec<RangeInclusive<usize>>>,
+    /// Relative paths of the files the diff adds (`new file mode`).
+    pub added: HashSet<PathBuf>,
+    /// Renamed files, new path to old path. A rename without edits has no
+    /// changed lines, so nothing in it is reported.
+    pub renamed: HashMap<PathBuf, PathBuf>,
 }
 
 impl DiffInfo {
@@ -39,6 +53,17 @@ impl DiffInfo {
         self.changed_lines.contains_key(path)
     }
 
+    /// Whether `path` was added or modified, or `None` when the diff doesn't touch it.
+    pub fn status(&self, path: &PathBuf) -> Option<FileStatus> {
+        if self.added.contains(path) {
+            Some(FileStatus::Added)
+        } else if self.has_file(path) || self.renamed.contains_key(path) {
+            Some(FileStatus::Modified)
+        } else {
+            None
+        }
+    }
+
     /// Check if a specific line in a file is within a changed range.
     pub fn has_line(&self, path: &PathBuf, line: usize) -> bool {
         match self.changed_lines.get(path) {
@@ -48,6 +73,49 @@ impl DiffInfo {
     }
 }
 
+/// The lines a diff touched, looked up by the paths violations report
+/// (relative to the working directory or absolute).
+#[derive(Debug)]
+pub struct ChangedLines {
+    root: PathBuf,
+    diff: DiffInfo,
+}
+
+impl ChangedLines {
+    pub fn new(root: PathBuf, diff: DiffInfo) -> Self {
+        Self { root, diff }
+    }
+
+    /// Diff `HEAD` against `base_ref` in the current repository.
+    pub fn load(base_ref: &str, renames: bool) -> Result<Self, GitDiffError> {
+        let diff = diff_info(base_ref, renames)?;
+        Ok(Self::new(repo_root()?, diff))
+    }
+
+    /// Whether `line` of `file` was added or changed. A file-level location
+    /// (no line) counts when the file changed at all.
+    pub fn contains(&self, file: &Path, line: Option<usize>) -> bool {
+        let rel_path = self.relative(file);
+        match line {
+            Some(line) => self.diff.has_line(&rel_path, line),
+            None => self.diff.has_file(&rel_path),
+        }
+    }
+
+    /// Whether `file` is new or modified in the diff, or `None` when unchanged.
+    pub fn status(&self, file: &Path) -> Option<FileStatus> {
+        self.diff.status(&self.relative(file))
+    }
+
+    fn relative(&self, file: &Path) -> PathBuf {
+        if file.is_absolute() {
+            file.strip_prefix(&self.root).unwrap_or(file).to_path_buf()
+        } else {
+            file.to_path_buf()
+        }
+    }
+}
+
 /// Detect the base ref from CI environment variables, falling back to "main".
 pub fn detect_base_ref() -> String {
     // GitHub Actions
@@ -90,7 +158,9 @@ pub fn repo_root() -> Result<PathBuf, GitDiffError> {
 ///
 /// Uses triple-dot diff (`base...HEAD`) for correct merge-base comparison.
 /// Only includes Added, Copied, Modified, Renamed files (`--diff-filter=ACMR`).
-pub fn diff_info(base_ref: &str) -> Result<DiffInfo, GitDiffError> {
+/// With `renames`, a moved file only contributes the lines edited after the
+/// move (`-M`, regardless of `diff.renames`); without, it is a new file.
+pub fn diff_info(base_ref: &str, renames: bool) -> Result<DiffInfo, GitDiffError> {
     // Ensure we're in a git repo
     repo_root()?;
 
@@ -101,6 +171,7 @@ pub fn diff_info(base_ref: &str) -> Result<DiffInfo, GitDiffError> {
         .args([
             "diff",
             "-U0",
+            if renames { "-M" } else { "--no-renames" },
             "--diff-filter=ACMR",
             &format!("{}...HEAD", effective_base),
         ])
@@ -116,6 +187,25 @@ pub fn diff_info(base_ref: &str) -> Result<DiffInfo, GitDiffError> {
     Ok(parse_diff(&diff_text))
 }
 
+/// `path`'s contents at the merge base of `base_ref` and `HEAD`, the
+/// revision [`diff_info`] compares against. `None` if it didn't exist there.
+pub fn base_contents(base_ref: &str, path: &Path) -> Option<String> {
+    let root = repo_root().ok()?.canonicalize().ok()?;
+    let rel = path.canonicalize().ok()?.strip_prefix(&root).ok()?.to_path_buf();
+    let base = resolve_base_ref(base_ref).ok()?;
+    let merge_base = Command::new("git").args(["merge-base", &base, "HEAD"]).output().ok()?;
+    if !merge_base.status.success() {
+        return None;
+    }
+    let revision = String::from_utf8_lossy(&merge_base.stdout).trim().to_string();
+    let spec = format!("{}:{}", revision, rel.to_string_lossy().replace('\\', "/"));
+    let output = Command::new("git").current_dir(&root).args(["show", &spec]).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
 /// Resolve a base ref, trying the ref directly then with origin/ prefix.
 /// For shallow clones, attempts a fetch first.
 fn resolve_base_ref(base_ref: &str) -> Result<String, GitDiffError> {
@@ -157,15 +247,43 @@ fn ref_exists(r: &str) -> bool {
 /// Parse unified diff output into a DiffInfo.
 fn parse_diff(diff_text: &str) -> DiffInfo {
     let mut changed_lines: HashMap<PathBuf, Vec<RangeInclusive<usize>>> = HashMap::new();
+    let mut added = HashSet::new();
+    let mut renamed = HashMap::new();
     let mut current_file: Option<PathBuf> = None;
+    let mut new_file = false;
+    let mut rename_from: Option<PathBuf> = None;
 
     for line in diff_text.lines() {
+        // Each file's header starts with `diff --git`; `new file mode` marks an addition
+        if line.starts_with("diff --git ") {
+            new_file = false;
+            rename_from = None;
+            continue;
+        }
+        if line.starts_with("new file mode") {
+            new_file = true;
+            continue;
+        }
+        if let Some(path) = line.strip_prefix("rename from ") {
+            rename_from = Some(PathBuf::from(path));
+            continue;
+        }
+        if let Some(path) = line.strip_prefix("rename to ") {
+            if let Some(from) = rename_from.take() {
+                renamed.insert(PathBuf::from(path), from);
+            }
+            continue;
+        }
+
         // Detect file path from +++ line
         if let Some(path) = line.strip_prefix("+++ b/") {
             current_file = Some(PathBuf::from(path));
             changed_lines
                 .entry(PathBuf::from(path))
                 .or_insert_with(Vec::new);
+            if new_file {
+                added.insert(PathBuf::from(path));
+            }
             continue;
         }
 
@@ -179,7 +297,11 @@ fn parse_diff(diff_text: &str) -> DiffInfo {
         }
     }
 
-    DiffInfo { changed_lines }
+    DiffInfo {
+        changed_lines,
+        added,
+        renamed,
+    }
 }
 
 /// Parse a hunk header like `@@ -10,3 +15,4 @@` and return the new-side line range.
@@ -269,6 +391,37 @@ new file mode 100644
         let bar_ranges = &info.changed_lines[&PathBuf::from("src/bar.rs")];
         assert_eq!(bar_ranges.len(), 1);
         assert_eq!(bar_ranges[0], 1..=10);
+
+        assert_eq!(info.status(&PathBuf::from("src/foo.rs")), Some(FileStatus::Modified));
+        assert_eq!(info.status(&PathBuf::from("src/bar.rs")), Some(FileStatus::Added));
+        assert_eq!(info.status(&PathBuf::from("src/baz.rs")), None);
+    }
+
+    #[test]
+    fn parse_diff_renames() {
+        let diff = "\
+diff --git a/src/old.ts b/src/moved.ts
+similarity index 100%
+rename from src/old.ts
+rename to src/moved.ts
+diff --git a/lib/util.ts b/src/util.ts
+similarity index 92%
+rename from lib/util.ts
+rename to src/util.ts
+--- a/lib/util.ts
++++ b/src/util.ts
+@@ -4 +4 @@ export function util() {
+-  return 1;
++  return 2;
+";
+        let info = parse_diff(diff);
+        // A pure move has no changed lines; an edited one only its edits
+        assert!(!info.has_file(&PathBuf::from("src/moved.ts")));
+        assert_eq!(info.changed_lines[&PathBuf::from("src/util.ts")], vec![4..=4]);
+        assert_eq!(info.renamed[&PathBuf::from("src/moved.ts")], PathBuf::from("src/old.ts"));
+        assert_eq!(info.status(&PathBuf::from("src/moved.ts")), Some(FileStatus::Modified));
+        assert_eq!(info.status(&PathBuf::from("src/util.ts")), Some(FileStatus::Modified));
+        assert!(info.added.is_empty());
     }
 
     #[test]
@@ -278,7 +431,10 @@ new file mode 100644
             PathBuf::from("src/main.rs"),
             vec![5..=10, 20..=25],
         );
-        let info = DiffInfo { changed_lines };
+        let info = DiffInfo {
+            changed_lines,
+            ..Default::default()
+        };
 
         assert!(info.has_file(&PathBuf::from("src/main.rs")));
         assert!(!info.has_file(&PathBuf::from("src/other.rs")));
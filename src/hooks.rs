@@ -0,0 +1,127 @@
+use crate::cli::toml_config::Hook;
+use std::fmt;
+use std::io::{self, Write};
+use std::path::Path;
+use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// How often a running hook is polled for exit.
+const POLL_INTERVAL: Duration = Duration::from_millis(20);
+
+#[derive(Debug)]
+pub enum HookError {
+    EmptyCommand,
+    Spawn(io::Error),
+    Exit(ExitStatus),
+    Timeout(u64),
+}
+
+impl fmt::Display for HookError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HookError::EmptyCommand => write!(f, "command is empty"),
+            HookError::Spawn(e) => write!(f, "failed to start: {}", e),
+            HookError::Exit(status) => write!(f, "exited with {}", status),
+            HookError::Timeout(secs) => write!(f, "killed after {}s", secs),
+        }
+    }
+}
+
+impl std::error::Error for HookError {}
+
+/// Run each hook in order from `dir`, with `input` on stdin and
+/// `BASELINE_EXIT_CODE` set to the scan's exit code. A hook's stdout goes to
+/// stderr, so it never mixes with the report. Returns the hooks that failed.
+pub fn run_after_scan<'h>(
+    hooks: &'h [Hook],
+    input: &str,
+    dir: &Path,
+    exit_code: i32,
+) -> Vec<(&'h Hook, HookError)> {
+    hooks
+        .iter()
+        .filter_map(|hook| run_hook(hook, input, dir, exit_code).err().map(|e| (hook, e)))
+        .collect()
+}
+
+fn run_hook(hook: &Hook, input: &str, dir: &Path, exit_code: i32) -> Result<(), HookError> {
+    let (program, args) = hook.command.split_first().ok_or(HookError::EmptyCommand)?;
+    tracing::debug!("running hook {}", hook.command.join(" "));
+    let mut child = Command::new(program)
+        .args(args)
+        .current_dir(dir)
+        .env("BASELINE_EXIT_CODE", exit_code.to_string())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::from(io::stderr()))
+        .spawn()
+        .map_err(HookError::Spawn)?;
+
+    // Written from a thread: a hook that doesn't read all of a large result
+    // would otherwise block us on a full pipe, and the timeout never fires
+    let writer = child.stdin.take().map(|mut stdin| {
+        let input = input.as_bytes().to_vec();
+        thread::spawn(move || {
+            // A hook may exit without reading stdin; that isn't a failure
+            let _ = stdin.write_all(&input);
+        })
+    });
+
+    let deadline = Instant::now() + Duration::from_secs(hook.timeout_secs);
+    let status = loop {
+        if let Some(status) = child.try_wait().map_err(HookError::Spawn)? {
+            break status;
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(HookError::Timeout(hook.timeout_secs));
+        }
+        thread::sleep(POLL_INTERVAL);
+    };
+    if let Some(writer) = writer {
+        let _ = writer.join();
+    }
+    if status.success() {
+        Ok(())
+    } else {
+        Err(HookError::Exit(status))
+    }
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::cli::toml_config::HookFailure;
+
+    fn hook(script: &str, timeout_secs: u64) -> Hook {
+        Hook {
+            command: vec!["sh".into(), "-c".into(), script.into()],
+            timeout_secs,
+            on_failure: HookFailure::Warn,
+        }
+    }
+
+    #[test]
+    fn hooks_get_the_result_and_failures_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let hooks = vec![
+            hook("cat > received.json && echo \"$BASELINE_EXIT_CODE\" > exit_code", 5),
+            hook("exit 3", 5),
+            hook("sleep 5", 0),
+            Hook { command: Vec::new(), ..hook("", 5) },
+        ];
+        let failed = run_after_scan(&hooks, "{\"violations\":[]}", dir.path(), 1);
+
+        assert_eq!(
+            std::fs::read_to_string(dir.path().join("received.json")).unwrap(),
+            "{\"violations\":[]}"
+        );
+        assert_eq!(std::fs::read_to_string(dir.path().join("exit_code")).unwrap(), "1\n");
+        let errors: Vec<String> = failed.iter().map(|(_, e)| e.to_string()).collect();
+        assert_eq!(errors.len(), 3);
+        assert!(errors[0].starts_with("exited with"), "{}", errors[0]);
+        assert_eq!(errors[1], "killed after 0s");
+        assert_eq!(errors[2], "command is empty");
+    }
+}
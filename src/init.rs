@@ -1,4 +1,5 @@
-use std::path::Path;
+use clap::ValueEnum;
+use std::path::{Path, PathBuf};
 
 /// Detected project type based on config files present.
 #[derive(Debug, PartialEq)]
@@ -185,6 +186,56 @@ exclude = ["**/node_modules/**", "**/dist/**", "**/build/**"]
     .to_string()
 }
 
+/// CI system `baseline init --ci` writes a pipeline for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CiProvider {
+    Github,
+    Gitlab,
+    Circle,
+}
+
+impl CiProvider {
+    /// Where the generated pipeline is written, relative to the project root.
+    pub fn pipeline_path(self) -> PathBuf {
+        match self {
+            CiProvider::Github => PathBuf::from(".github/workflows/baseline.yml"),
+            CiProvider::Gitlab => PathBuf::from(".gitlab/ci/baseline.yml"),
+            CiProvider::Circle => PathBuf::from(".circleci/baseline.yml"),
+        }
+    }
+
+    fn template(self) -> &'static str {
+        match self {
+            CiProvider::Github => include_str!("../templates/ci/github.yml"),
+            CiProvider::Gitlab => include_str!("../templates/ci/gitlab.yml"),
+            CiProvider::Circle => include_str!("../templates/ci/circle.yml"),
+        }
+    }
+
+    /// What the user still has to do for the pipeline to run, if anything.
+    pub fn next_step(self) -> Option<String> {
+        match self {
+            CiProvider::Github => None,
+            CiProvider::Gitlab => Some(format!(
+                "include it from .gitlab-ci.yml with `include: [{{ local: {} }}]`",
+                self.pipeline_path().display()
+            )),
+            CiProvider::Circle => Some("merge its job and workflow into .circleci/config.yml".to_string()),
+        }
+    }
+}
+
+/// A pipeline for `provider` that scans with the config at `config_path`:
+/// changed files only on pull/merge requests, a SARIF report, and a markdown
+/// summary. It installs this exact version, so the flags it uses exist.
+pub fn generate_pipeline(provider: CiProvider, config_path: &Path) -> String {
+    provider
+        .template()
+        .replace("@VERSION@", env!("CARGO_PKG_VERSION"))
+        .replace("@CONFIG@", &config_path.display().to_string())
+        .replace("@PATH@", &provider.pipeline_path().display().to_string())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -261,4 +312,38 @@ mod tests {
         assert!(config.contains("banned-import"));
         assert!(config.contains("banned-dependency"));
     }
+
+    #[test]
+    fn pipelines_pin_this_version_and_config() {
+        for provider in [CiProvider::Github, CiProvider::Gitlab, CiProvider::Circle] {
+            let pipeline = generate_pipeline(provider, Path::new("ci/baseline.toml"));
+            assert!(pipeline.contains(&format!("cargo install code-baseline --version {} --locked", env!("CARGO_PKG_VERSION"))));
+            assert!(pipeline.contains("--config ci/baseline.toml --format sarif"));
+            assert!(pipeline.contains("--format markdown"));
+            assert!(pipeline.contains("--changed-only"));
+            assert!(!pipeline.contains("@VERSION@") && !pipeline.contains("@CONFIG@") && !pipeline.contains("@PATH@"));
+        }
+        let gitlab = generate_pipeline(CiProvider::Gitlab, Path::new("baseline.toml"));
+        assert!(gitlab.contains("- local: .gitlab/ci/baseline.yml"));
+    }
+
+    #[test]
+    fn pipeline_scan_commands_parse() {
+        use clap::Parser;
+        for provider in [CiProvider::Github, CiProvider::Gitlab, CiProvider::Circle] {
+            let pipeline = generate_pipeline(provider, Path::new("baseline.toml"));
+            let commands: Vec<&str> = pipeline
+                .lines()
+                .filter_map(|line| line.find("baseline scan ").map(|i| &line[i..]))
+                .map(|command| command.split(['>', '|']).next().unwrap())
+                .collect();
+            assert_eq!(commands.len(), 3 - usize::from(provider == CiProvider::Github));
+            for command in commands {
+                let args = command
+                    .split_whitespace()
+                    .map(|arg| if arg == "$CHANGED" { "--changed-only" } else { arg });
+                assert!(crate::cli::Cli::try_parse_from(args).is_ok(), "{:?}: {}", provider, command);
+            }
+        }
+    }
 }
@@ -1,9 +1,31 @@
+pub mod archive;
+pub mod bench;
+pub mod clean;
 pub mod cli;
+pub mod components;
 pub mod config;
+pub mod config_edit;
+pub mod deadline;
+pub mod docs;
+pub mod export;
+pub mod fix;
 pub mod git_diff;
+pub mod hooks;
 pub mod init;
+pub mod locale;
+pub mod logging;
 pub mod mcp;
+pub mod notify;
 pub mod presets;
+pub mod projects;
 pub mod ratchet;
 pub mod rules;
 pub mod scan;
+pub mod schema;
+pub mod self_update;
+pub mod snippet;
+pub mod suppress;
+pub mod upgrades;
+pub mod verify;
+pub mod version;
+pub mod watch;
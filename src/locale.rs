@@ -0,0 +1,183 @@
+use crate::cli::toml_config::{PatternList, TomlRule};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+/// Localized text for one rule.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct RuleText {
+    pub message: Option<String>,
+    pub suggest: Option<PatternList>,
+}
+
+/// A message catalog selected with `--locale`.
+///
+/// ```toml
+/// [messages]
+/// no-violations = "違反は見つかりませんでした"
+/// errors = "{count} 件のエラー"
+///
+/// [rules.require-img-alt]
+/// message = "img 要素には alt 属性が必要です"
+/// suggest = ["画像を説明する alt=\"...\" を追加する", "装飾画像なら alt=\"\" にする"]
+/// ```
+///
+/// `messages` holds CLI strings keyed by message id; `{name}` placeholders
+/// are filled in when the string is printed. `rules` overrides the message
+/// and suggestions of rules by rule id, so preset rules can be translated
+/// without redefining them. Anything the catalog leaves out falls back to
+/// the built-in English text.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct Catalog {
+    #[serde(default)]
+    pub messages: HashMap<String, String>,
+    #[serde(default)]
+    pub rules: HashMap<String, RuleText>,
+}
+
+#[derive(Debug)]
+pub enum LocaleError {
+    NotFound(PathBuf),
+    Read(PathBuf, std::io::Error),
+    Parse(PathBuf, toml::de::Error),
+}
+
+impl fmt::Display for LocaleError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LocaleError::NotFound(path) => write!(f, "locale file '{}' not found", path.display()),
+            LocaleError::Read(path, e) => write!(f, "failed to read '{}': {}", path.display(), e),
+            LocaleError::Parse(path, e) => write!(f, "failed to parse '{}': {}", path.display(), e),
+        }
+    }
+}
+
+impl std::error::Error for LocaleError {}
+
+/// The catalog file for `locale`: a path when it ends in `.toml`, otherwise
+/// `locales/<locale>.toml` next to the config.
+pub fn locale_path(locale: &str, config_path: &Path) -> PathBuf {
+    if locale.ends_with(".toml") {
+        return PathBuf::from(locale);
+    }
+    config_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("locales")
+        .join(format!("{}.toml", locale))
+}
+
+impl Catalog {
+    /// Load the catalog for `locale` (see [`locale_path`]).
+    pub fn load(locale: &str, config_path: &Path) -> Result<Self, LocaleError> {
+        let path = locale_path(locale, config_path);
+        let text = std::fs::read_to_string(&path).map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => LocaleError::NotFound(path.clone()),
+            _ => LocaleError::Read(path.clone(), e),
+        })?;
+        toml::from_str(&text).map_err(|e| LocaleError::Parse(path, e))
+    }
+
+    /// The catalog's text for `key`, or `default`, with each `{name}`
+    /// placeholder replaced by its value from `args`.
+    pub fn text(&self, key: &str, default: &str, args: &[(&str, &dyn fmt::Display)]) -> String {
+        let mut text = self.messages.get(key).map_or(default, String::as_str).to_string();
+        for (name, value) in args {
+            text = text.replace(&format!("{{{}}}", name), &value.to_string());
+        }
+        text
+    }
+
+    /// Replace the message and suggestions of every rule the catalog
+    /// translates.
+    pub fn localize_rules(&self, rules: &mut [TomlRule]) {
+        for rule in rules {
+            let Some(text) = self.rules.get(&rule.id) else { continue };
+            if let Some(ref message) = text.message {
+                rule.message = message.clone();
+            }
+            if let Some(ref suggest) = text.suggest {
+                rule.suggest = Some(suggest.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const JA: &str = r#"
+[messages]
+errors = "{count} 件のエラー"
+
+[rules.require-img-alt]
+message = "img 要素には alt 属性が必要です"
+suggest = ["alt を追加する", "装飾画像なら alt=\"\" にする"]
+
+[rules.no-console]
+suggest = "logger を使う"
+"#;
+
+    #[test]
+    fn text_falls_back_and_fills_placeholders() {
+        let catalog: Catalog = toml::from_str(JA).unwrap();
+        assert_eq!(catalog.text("errors", "{count} errors", &[("count", &3)]), "3 件のエラー");
+        assert_eq!(catalog.text("warnings", "{count} warnings", &[("count", &2)]), "2 warnings");
+        assert_eq!(Catalog::default().text("errors", "{count} errors", &[("count", &1)]), "1 errors");
+    }
+
+    #[test]
+    fn rules_are_localized_by_id() {
+        let catalog: Catalog = toml::from_str(JA).unwrap();
+        let mut rules = vec![
+            TomlRule {
+                id: "require-img-alt".into(),
+                message: "img element must have an alt attribute".into(),
+                suggest: Some("Add alt".into()),
+                ..Default::default()
+            },
+            TomlRule {
+                id: "no-console".into(),
+                message: "no console".into(),
+                ..Default::default()
+            },
+            TomlRule {
+                id: "untranslated".into(),
+                message: "left alone".into(),
+                ..Default::default()
+            },
+        ];
+        catalog.localize_rules(&mut rules);
+
+        assert_eq!(rules[0].message, "img 要素には alt 属性が必要です");
+        assert_eq!(rules[0].suggest.as_ref().unwrap().0.len(), 2);
+        assert_eq!(rules[1].message, "no console");
+        assert_eq!(rules[1].suggest, Some("logger を使う".into()));
+        assert_eq!(rules[2].message, "left alone");
+    }
+
+    #[test]
+    fn locale_names_resolve_next_to_the_config() {
+        let config = Path::new("repo/baseline.toml");
+        assert_eq!(locale_path("ja", config), Path::new("repo/locales/ja.toml"));
+        assert_eq!(locale_path("i18n/ja.toml", config), Path::new("i18n/ja.toml"));
+    }
+
+    #[test]
+    fn load_reports_missing_and_invalid_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        assert!(matches!(Catalog::load("ja", &config), Err(LocaleError::NotFound(_))));
+
+        std::fs::create_dir(dir.path().join("locales")).unwrap();
+        std::fs::write(dir.path().join("locales/ja.toml"), "[rulez]\n").unwrap();
+        assert!(matches!(Catalog::load("ja", &config), Err(LocaleError::Parse(..))));
+
+        std::fs::write(dir.path().join("locales/ja.toml"), JA).unwrap();
+        assert!(Catalog::load("ja", &config).unwrap().rules.contains_key("no-console"));
+    }
+}
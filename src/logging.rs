@@ -0,0 +1,203 @@
+//! Diagnostic logging to stderr through `tracing`.
+//!
+//! Records from dependencies that use the `log` facade are forwarded too.
+
+use clap::ValueEnum;
+use serde_json::json;
+use std::fmt;
+use tracing::field::{Field, Visit};
+use tracing::{Event, Level, Subscriber};
+use tracing_subscriber::filter::LevelFilter;
+use tracing_subscriber::fmt::format::Writer;
+use tracing_subscriber::fmt::{FmtContext, FormatEvent, FormatFields};
+use tracing_subscriber::registry::LookupSpan;
+
+/// Verbosity for diagnostic logs written to stderr.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogLevel {
+    Off,
+    Error,
+    Warn,
+    Info,
+    Debug,
+    Trace,
+}
+
+impl From<LogLevel> for LevelFilter {
+    fn from(level: LogLevel) -> Self {
+        match level {
+            LogLevel::Off => LevelFilter::OFF,
+            LogLevel::Error => LevelFilter::ERROR,
+            LogLevel::Warn => LevelFilter::WARN,
+            LogLevel::Info => LevelFilter::INFO,
+            LogLevel::Debug => LevelFilter::DEBUG,
+            LogLevel::Trace => LevelFilter::TRACE,
+        }
+    }
+}
+
+/// Encoding for log lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum LogFormat {
+    /// Colored human-readable lines
+    Text,
+    /// One JSON object per line
+    Json,
+}
+
+/// Writes each event as one line in `format`, keeping stdout free for scan
+/// output and protocol traffic (MCP).
+struct StderrFormat {
+    format: LogFormat,
+}
+
+impl<S, N> FormatEvent<S, N> for StderrFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(&self, _ctx: &FmtContext<'_, S, N>, mut writer: Writer<'_>, event: &Event<'_>) -> fmt::Result {
+        let mut message = Message::default();
+        event.record(&mut message);
+        let metadata = event.metadata();
+        let target = message.log_target.as_deref().unwrap_or(metadata.target());
+        writeln!(
+            writer,
+            "{}",
+            format_line(self.format, *metadata.level(), target, &message.text)
+        )
+    }
+}
+
+/// An event's `message` field, followed by any other fields as `key=value`.
+/// Records forwarded from the `log` facade carry their origin in `log.*`
+/// fields; only the target is kept.
+#[derive(Default)]
+struct Message {
+    text: String,
+    log_target: Option<String>,
+}
+
+impl Visit for Message {
+    fn record_str(&mut self, field: &Field, value: &str) {
+        if field.name() == "log.target" {
+            self.log_target = Some(value.to_string());
+        } else {
+            self.record_debug(field, &value);
+        }
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        use std::fmt::Write;
+        if field.name().starts_with("log.") {
+            return;
+        }
+        if !self.text.is_empty() {
+            self.text.push(' ');
+        }
+        if field.name() == "message" {
+            let _ = write!(self.text, "{:?}", value);
+        } else {
+            let _ = write!(self.text, "{}={:?}", field.name(), value);
+        }
+    }
+}
+
+fn format_line(format: LogFormat, level: Level, target: &str, message: &str) -> String {
+    match format {
+        LogFormat::Json => json!({
+            "level": level.as_str().to_lowercase(),
+            "target": target,
+            "message": message,
+        })
+        .to_string(),
+        LogFormat::Text => {
+            // Info lines are status and hints meant to be read as-is; errors
+            // and warnings get a label, and only debugging shows the module
+            let label = match level {
+                Level::INFO => return message.to_string(),
+                Level::ERROR => return format!("\x1b[31merror\x1b[0m: {}", message),
+                Level::WARN => return format!("\x1b[33mwarning\x1b[0m: {}", message),
+                Level::DEBUG => "\x1b[90mdebug\x1b[0m",
+                Level::TRACE => "\x1b[90mtrace\x1b[0m",
+            };
+            format!("{} [{}]: {}", label, target, message)
+        }
+    }
+}
+
+/// Install the stderr subscriber. Only the first call takes effect.
+pub fn init(level: LogLevel, format: LogFormat) {
+    let _ = tracing_subscriber::fmt()
+        .with_writer(std::io::stderr)
+        .with_max_level(LevelFilter::from(level))
+        .event_format(StderrFormat { format })
+        .try_init();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn text_format_includes_level_and_target_when_debugging() {
+        let line = format_line(LogFormat::Text, Level::DEBUG, "code_baseline::scan", "loaded 3 rules");
+        assert_eq!(line, "\x1b[90mdebug\x1b[0m [code_baseline::scan]: loaded 3 rules");
+    }
+
+    #[test]
+    fn text_format_omits_target_for_user_facing_levels() {
+        let line = format_line(LogFormat::Text, Level::ERROR, "baseline", "config file 'x' not found");
+        assert_eq!(line, "\x1b[31merror\x1b[0m: config file 'x' not found");
+        let line = format_line(LogFormat::Text, Level::INFO, "baseline", "✓ Created baseline.toml");
+        assert_eq!(line, "✓ Created baseline.toml");
+    }
+
+    #[test]
+    fn json_format_is_one_object() {
+        let line = format_line(LogFormat::Json, Level::WARN, "code_baseline::scan", "skipped \"a.ts\"");
+        let v: serde_json::Value = serde_json::from_str(&line).unwrap();
+        assert_eq!(v["level"], "warn");
+        assert_eq!(v["target"], "code_baseline::scan");
+        assert_eq!(v["message"], "skipped \"a.ts\"");
+    }
+
+    #[test]
+    fn events_render_through_the_subscriber() {
+        use std::io::Write;
+        use std::sync::{Arc, Mutex};
+
+        #[derive(Clone, Default)]
+        struct Buffer(Arc<Mutex<Vec<u8>>>);
+        impl Write for Buffer {
+            fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+                self.0.lock().unwrap().write(buf)
+            }
+            fn flush(&mut self) -> std::io::Result<()> {
+                Ok(())
+            }
+        }
+
+        let buffer = Buffer::default();
+        let writer = buffer.clone();
+        let subscriber = tracing_subscriber::fmt()
+            .with_writer(move || writer.clone())
+            .with_max_level(LevelFilter::from(LogLevel::Warn))
+            .event_format(StderrFormat { format: LogFormat::Json })
+            .finish();
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::info!("hidden");
+            tracing::warn!(rule = "no-todo", "skipped {}", "a.ts");
+        });
+        let out = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let v: serde_json::Value = serde_json::from_str(out.trim()).unwrap();
+        assert_eq!(v["level"], "warn");
+        assert_eq!(v["message"], "skipped a.ts rule=\"no-todo\"");
+    }
+
+    #[test]
+    fn log_level_maps_to_filter() {
+        assert_eq!(LevelFilter::from(LogLevel::Off), LevelFilter::OFF);
+        assert_eq!(LevelFilter::from(LogLevel::Debug), LevelFilter::DEBUG);
+    }
+}
@@ -1,131 +1,415 @@
-use clap::Parser;
+use clap::{Parser, ValueEnum};
+use code_baseline::archive;
+use code_baseline::bench;
+use code_baseline::clean;
 use code_baseline::cli::format;
-use code_baseline::cli::{Cli, Commands, OutputFormat};
-use code_baseline::config::Severity;
+use code_baseline::cli::toml_config::HookFailure;
+use code_baseline::cli::{Cli, Commands, OutputFormat, PresetCommands, SimulatedSeverity, StdinFixOutput};
+use code_baseline::components;
+use code_baseline::docs;
+use code_baseline::fix;
 use code_baseline::git_diff;
+use code_baseline::hooks;
 use code_baseline::init;
+use code_baseline::locale::Catalog;
+use code_baseline::logging;
 use code_baseline::mcp;
+use code_baseline::notify;
+use code_baseline::presets;
+use code_baseline::projects;
 use code_baseline::ratchet;
 use code_baseline::scan;
+use code_baseline::schema;
+use code_baseline::self_update::{self, UpdateOutcome};
+use code_baseline::snippet;
+use code_baseline::suppress;
+use code_baseline::upgrades;
+use code_baseline::verify;
 use std::fs;
 use std::io::Read;
 use std::process;
 
 fn main() {
     let cli = Cli::parse();
+    logging::init(cli.log_level, cli.log_format);
 
     match cli.command {
         Commands::Scan {
-            paths,
+            mut paths,
+            archive,
             config,
+            groups,
             format: output_format,
             stdin,
             filename,
             changed_only,
             base,
+            no_renames,
             fix,
+            fix_unsafe,
+            fix_output,
             dry_run,
+            fix_suppress,
+            suppress_rules,
+            suppress_ticket,
+            include_minified,
+            include_generated,
+            list_files,
+            emit_problem_matcher,
+            column_unit,
+            export,
+            fail_fast,
+            max_depth,
+            timing,
+            count_all,
+            by_project,
+            simulate_severity,
+            simulate_rules,
+            max_per_rule,
+            by_directory,
+            verbose,
+            locale,
+            suggest_upgrades,
+            strict,
+            annotation_limit,
+            annotation_groups,
         } => {
-            let result = if stdin {
+            let catalog = match locale {
+                Some(ref locale) => Catalog::load(locale, &config[0]).unwrap_or_else(|e| {
+                    tracing::error!("{}", e);
+                    process::exit(2);
+                }),
+                None => Catalog::default(),
+            };
+            let options = scan::ScanOptions {
+                include_minified,
+                include_generated,
+                catalog: catalog.clone(),
+                max_depth,
+                fail_fast,
+                strict,
+                groups,
+                changed: None,
+                no_renames,
+                list_files,
+            };
+            if stdin && config.len() > 1 {
+                tracing::error!("--stdin takes a single --config");
+                process::exit(2);
+            }
+            if let Some(ref target) = export {
+                if let Err(e) = code_baseline::export::check_available(target) {
+                    tracing::error!("cannot export: {}", e);
+                    process::exit(2);
+                }
+            }
+            if let Some(ref path) = emit_problem_matcher {
+                let matcher = serde_json::to_string_pretty(&format::problem_matcher()).unwrap();
+                if let Err(e) = fs::write(path, matcher) {
+                    tracing::error!("failed to write {}: {}", path.display(), e);
+                    process::exit(2);
+                }
+                // Only compact lines match, and other formats must keep stdout clean
+                let in_actions = std::env::var("GITHUB_ACTIONS").is_ok_and(|v| v == "true");
+                if in_actions && matches!(output_format, OutputFormat::Compact) {
+                    println!("::add-matcher::{}", path.display());
+                }
+            }
+            let missing_config = || {
+                config
+                    .iter()
+                    .find(|c| !c.exists())
+                    .unwrap_or(&config[0])
+                    .display()
+                    .to_string()
+            };
+
+            let extracted = archive.map(|path| {
+                let extracted = archive::ExtractedArchive::extract(&path).unwrap_or_else(|e| {
+                    tracing::error!("{}", e);
+                    process::exit(2);
+                });
+                paths = vec![extracted.root().to_path_buf()];
+                extracted
+            });
+
+            let mut stdin_content = String::new();
+            let mut result = if stdin {
                 // Read from stdin
-                let mut content = String::new();
-                std::io::stdin().read_to_string(&mut content).unwrap_or_else(|e| {
-                    eprintln!("\x1b[31merror\x1b[0m: failed to read stdin: {}", e);
+                let content = &mut stdin_content;
+                std::io::stdin().read_to_string(content).unwrap_or_else(|e| {
+                    tracing::error!("failed to read stdin: {}", e);
                     process::exit(2);
                 });
                 let fname = filename.as_deref().unwrap_or("stdin.tsx");
-                match scan::run_scan_stdin(&config, &content, fname) {
+                match scan::run_scan_stdin_with_options(&config[0], content, fname, &options) {
                     Ok(r) => r,
                     Err(e) => {
-                        eprintln!("\x1b[31merror\x1b[0m: {}", e);
+                        tracing::error!("{}", e);
                         process::exit(2);
                     }
                 }
             } else if changed_only {
                 let base_ref = base.unwrap_or_else(|| git_diff::detect_base_ref());
-                match scan::run_scan_changed(&config, &paths, &base_ref) {
+                match scan::run_scan_changed_configs(&config, &paths, &base_ref, &options) {
                     Ok(r) => r,
                     Err(scan::ScanError::GitDiff(ref msg)) => {
-                        eprintln!("\x1b[31merror\x1b[0m: {}", msg);
-                        eprintln!(
-                            "\x1b[90mhint\x1b[0m: --changed-only requires a git repository with the base branch available"
+                        tracing::error!(
+                            "{} (--changed-only requires a git repository with the base branch available)",
+                            msg
                         );
                         process::exit(2);
                     }
                     Err(scan::ScanError::ConfigRead(ref e))
                         if e.kind() == std::io::ErrorKind::NotFound =>
                     {
-                        eprintln!(
-                            "\x1b[31merror\x1b[0m: config file '{}' not found",
-                            config.display()
-                        );
-                        eprintln!(
-                            "\x1b[90mhint\x1b[0m: run \x1b[1mbaseline init\x1b[0m to generate a starter config"
+                        tracing::error!(
+                            "config file '{}' not found; run `baseline init` to generate a starter config",
+                            missing_config()
                         );
                         process::exit(2);
                     }
                     Err(e) => {
-                        eprintln!("\x1b[31merror\x1b[0m: {}", e);
+                        tracing::error!("{}", e);
                         process::exit(2);
                     }
                 }
             } else {
-                match scan::run_scan(&config, &paths) {
+                // process::exit skips destructors, so the unpacked archive
+                // (only ever scanned here) is dropped before exiting
+                match scan::run_scan_configs(&config, &paths, &options) {
                     Ok(r) => r,
                     Err(scan::ScanError::ConfigRead(ref e))
                         if e.kind() == std::io::ErrorKind::NotFound =>
                     {
-                        eprintln!(
-                            "\x1b[31merror\x1b[0m: config file '{}' not found",
-                            config.display()
-                        );
-                        eprintln!(
-                            "\x1b[90mhint\x1b[0m: run \x1b[1mbaseline init\x1b[0m to generate a starter config"
+                        drop(extracted);
+                        tracing::error!(
+                            "config file '{}' not found; run `baseline init` to generate a starter config",
+                            missing_config()
                         );
                         process::exit(2);
                     }
                     Err(e) => {
-                        eprintln!("\x1b[31merror\x1b[0m: {}", e);
+                        drop(extracted);
+                        tracing::error!("{}", e);
                         process::exit(2);
                     }
                 }
             };
+            // Report paths inside the archive; the unpacked copy isn't needed
+            // past this point, and process::exit would skip its cleanup
+            if let Some(extracted) = extracted {
+                extracted.relabel(&mut result);
+            }
+            if list_files {
+                format::print_file_list(&result, matches!(output_format, OutputFormat::Json));
+                return;
+            }
+            if column_unit.is_some() {
+                result.output.column_unit = column_unit;
+            }
 
-            // Apply fixes if requested
-            if fix && !stdin {
-                let applied = format::apply_fixes(&result, dry_run);
-                if applied > 0 {
+            // Insert suppression comments if requested
+            if fix_suppress {
+                let options = suppress::SuppressOptions {
+                    rules: suppress_rules,
+                    ticket: suppress_ticket,
+                };
+                let inserted = suppress::apply_suppressions(&result, &options, dry_run);
+                if inserted > 0 {
                     if dry_run {
-                        eprintln!(
-                            "\x1b[36m(dry run)\x1b[0m {} fix{} would be applied",
-                            applied,
-                            if applied == 1 { "" } else { "es" }
+                        tracing::info!(
+                            "(dry run) {} suppression comment{} would be inserted",
+                            inserted,
+                            if inserted == 1 { "" } else { "s" }
                         );
                     } else {
-                        eprintln!(
-                            "\x1b[32m✓\x1b[0m Applied {} fix{}",
-                            applied,
-                            if applied == 1 { "" } else { "es" }
+                        tracing::info!(
+                            "✓ Inserted {} suppression comment{}",
+                            inserted,
+                            if inserted == 1 { "" } else { "s" }
                         );
                     }
                 }
             }
 
-            match output_format {
-                OutputFormat::Pretty => format::print_pretty(&result),
-                OutputFormat::Json => format::print_json(&result),
-                OutputFormat::Compact => format::print_compact(&result),
-                OutputFormat::Github => format::print_github(&result),
-                OutputFormat::Sarif => format::print_sarif(&result),
-                OutputFormat::Markdown => format::print_markdown(&result),
+            // Apply fixes if requested; the report is rendered by the formatter
+            if fix {
+                let verifier = match scan::FixVerifier::for_configs(&config) {
+                    Ok(v) => v,
+                    Err(e) => {
+                        tracing::error!("{}", e);
+                        process::exit(2);
+                    }
+                };
+                if stdin {
+                    // Piped content is fixed in memory and written back to
+                    // stdout, so editors can round-trip their buffer
+                    let fixed = fix::fix_stdin(&mut result, &stdin_content, Some(&verifier), fix_unsafe);
+                    let output = match fix_output {
+                        StdinFixOutput::Content => fixed,
+                        StdinFixOutput::Diff if fixed == stdin_content => String::new(),
+                        StdinFixOutput::Diff => fix::FileChange {
+                            path: filename.as_deref().unwrap_or("stdin.tsx").into(),
+                            before: stdin_content.clone(),
+                            after: fixed,
+                        }
+                        .unified_diff(),
+                    };
+                    format::print_stdin_fix(&result, &output);
+                    process::exit(result.exit_code());
+                }
+                result.fixes = Some(fix::apply_fixes(&result, Some(&verifier), dry_run, fix_unsafe));
+            }
+
+            let project_results = by_project
+                .then(|| projects::partition(&result, &projects::detect_projects(&paths)));
+
+            // The exit code, project summaries, severity simulation and rule totals
+            // see every violation; the report shows what `[output.<format>]` allows
+            let exit_code = result.exit_code();
+            let simulation = simulate_severity
+                .map(|SimulatedSeverity::Error| scan::simulate_error_severity(&result, &simulate_rules));
+            let rule_totals = count_all.then(|| scan::rule_totals(&result));
+            if let Some(ref target) = export {
+                if let Err(e) = code_baseline::export::export(&result, target) {
+                    tracing::error!("export failed: {}", e);
+                    process::exit(2);
+                }
+            }
+            // Hooks get the complete result, before any format's gate applies
+            let hook_input = (!stdin && !result.hooks.after_scan.is_empty()).then(|| format::json_string(&result));
+            if let Some(format_name) = output_format.to_possible_value() {
+                result.retain_for_format(format_name.get_name());
+            }
+
+            let display = format::DisplayOptions {
+                max_per_rule,
+                verbose,
+                catalog,
+                by_directory,
+            };
+            match (&output_format, &project_results) {
+                (OutputFormat::Json, Some(p)) => format::print_json_by_project(&result, p),
+                (OutputFormat::Markdown, Some(p)) => {
+                    format::print_markdown_by_project(&result, p, &display)
+                }
+                (OutputFormat::Pretty, _) => format::print_pretty_with_options(&result, &display),
+                (OutputFormat::Json, None) => format::print_json(&result),
+                (OutputFormat::Compact, _) => format::print_compact(&result),
+                (OutputFormat::Github, _) => {
+                    // Under a pull request, annotate violations on changed
+                    // lines first. A --changed-only scan has no others.
+                    let changed = match std::env::var("GITHUB_BASE_REF") {
+                        Ok(base) if !base.is_empty() && !changed_only => git_diff::ChangedLines::load(&base, true).ok(),
+                        _ => None,
+                    };
+                    let options = format::GithubOptions {
+                        annotation_limit,
+                        group_overflow: annotation_groups,
+                        changed,
+                    };
+                    format::print_github_with_options(&result, &options)
+                }
+                (OutputFormat::Sarif, _) => format::print_sarif(&result),
+                (OutputFormat::Markdown, None) => {
+                    format::print_markdown_with_options(&result, &display)
+                }
+                (OutputFormat::Summary, _) => format::print_summary(&result),
+                (OutputFormat::LspEdits, _) => {
+                    let edits = fix::text_edits(&result, |path| {
+                        if stdin {
+                            Some(stdin_content.clone())
+                        } else {
+                            fs::read_to_string(path).ok()
+                        }
+                    });
+                    format::print_lsp_edits(&edits)
+                }
+            }
+
+            if let Some(ref p) = project_results {
+                if !matches!(
+                    output_format,
+                    OutputFormat::Json | OutputFormat::Markdown | OutputFormat::LspEdits
+                ) {
+                    format::print_project_summary(p);
+                }
+            }
+
+            if timing {
+                format::print_timing(&result);
+            }
+
+            if let Some(ref sim) = simulation {
+                format::print_simulation(&result, sim);
+            }
+
+            if let Some(ref totals) = rule_totals {
+                format::print_rule_totals(totals);
+            }
+
+            if verbose {
+                for unmatched in &result.unmatched_globs {
+                    tracing::warn!("{}", unmatched);
+                }
+            }
+
+            let mut exit_code = exit_code;
+            if let Some(ref input) = hook_input {
+                let dir = config[0].parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(std::path::Path::new("."));
+                for (hook, e) in hooks::run_after_scan(&result.hooks.after_scan, input, dir, exit_code) {
+                    let command = hook.command.join(" ");
+                    match hook.on_failure {
+                        HookFailure::Ignore => {}
+                        HookFailure::Warn => tracing::warn!("hook '{}' {}", command, e),
+                        HookFailure::Fail => {
+                            tracing::error!("hook '{}' {}", command, e);
+                            exit_code = 2;
+                        }
+                    }
+                }
+            }
+
+            if suggest_upgrades {
+                for config_path in &config {
+                    // The scan already loaded each config, so this can't fail
+                    let rules = scan::list_rules(config_path).unwrap_or_default();
+                    for upgrade in upgrades::suggest_upgrades(&rules) {
+                        tracing::info!("hint: {}", upgrade);
+                    }
+                }
             }
 
-            let has_errors = result
-                .violations
-                .iter()
-                .any(|v| v.severity == Severity::Error);
+            process::exit(exit_code);
+        }
 
-            process::exit(if has_errors { 1 } else { 0 });
+        Commands::Fix {
+            paths,
+            config,
+            rules,
+            dry_run,
+            allow_unsafe,
+            include_minified,
+            include_generated,
+        } => {
+            let options = scan::ScanOptions {
+                include_minified,
+                include_generated,
+                ..Default::default()
+            };
+            let fixed = scan::run_scan_configs(&config, &paths, &options).and_then(|mut result| {
+                let verifier = scan::FixVerifier::for_configs(&config)?;
+                fix::retain_fixable(&mut result, &rules);
+                Ok(fix::apply_fixes(&result, Some(&verifier), dry_run, allow_unsafe))
+            });
+            match fixed {
+                Ok(report) => format::print_fix(&report),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    process::exit(2);
+                }
+            }
         }
 
         Commands::Baseline {
@@ -136,19 +420,19 @@ fn main() {
             let result = match scan::run_baseline(&config, &paths) {
                 Ok(r) => r,
                 Err(e) => {
-                    eprintln!("\x1b[31merror\x1b[0m: {}", e);
+                    tracing::error!("{}", e);
                     process::exit(2);
                 }
             };
 
             let json = serde_json::to_string_pretty(&result).unwrap();
             if let Err(e) = fs::write(&output, &json) {
-                eprintln!("\x1b[31merror\x1b[0m: failed to write baseline: {}", e);
+                tracing::error!("failed to write baseline: {}", e);
                 process::exit(2);
             }
 
-            eprintln!(
-                "\x1b[32m✓\x1b[0m Baseline written to {} ({} ratchet rule{}, {} files scanned)",
+            tracing::info!(
+                "✓ Baseline written to {} ({} ratchet rule{}, {} files scanned)",
                 output.display(),
                 result.entries.len(),
                 if result.entries.len() == 1 { "" } else { "s" },
@@ -156,7 +440,7 @@ fn main() {
             );
 
             for entry in &result.entries {
-                eprintln!(
+                tracing::info!(
                     "  {:<30} {} occurrence{}",
                     entry.rule_id,
                     entry.count,
@@ -165,49 +449,371 @@ fn main() {
             }
         }
 
-        Commands::Mcp { config } => {
-            mcp::run_mcp_server(&config);
+        Commands::Clean {
+            config,
+            dry_run,
+            keep_history,
+            only,
+        } => {
+            // Stale snapshot entries are judged against the config's ratchets
+            let ratchets = if only.is_empty() || only.iter().any(|n| n == "snapshot") {
+                match scan::list_rules(&config) {
+                    Ok(rules) => rules
+                        .into_iter()
+                        .filter(|r| r.rule_type == "ratchet")
+                        .map(|r| r.id)
+                        .collect(),
+                    Err(e) => {
+                        tracing::error!("{}", e);
+                        process::exit(2);
+                    }
+                }
+            } else {
+                Default::default()
+            };
+            let root = config.parent().filter(|p| !p.as_os_str().is_empty()).unwrap_or(std::path::Path::new("."));
+            let options = clean::CleanOptions {
+                dry_run,
+                keep_history,
+                ratchets: &ratchets,
+                only: &only,
+            };
+            let outcomes = match clean::clean(root, &options) {
+                Ok(outcomes) => outcomes,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    process::exit(2);
+                }
+            };
+            format::print_clean(&outcomes, dry_run);
         }
 
+        Commands::Rules { config } => match scan::list_rules(&config) {
+            Ok(rules) => format::print_rules(&rules),
+            Err(e) => {
+                tracing::error!("{}", e);
+                process::exit(2);
+            }
+        },
+
+        Commands::CheckConfig { paths, config } => match scan::check_config(&config, &paths) {
+            Ok(check) => format::print_config_check(&check),
+            Err(e) => {
+                tracing::error!("{}", e);
+                process::exit(2);
+            }
+        },
+
+        Commands::Docs {
+            config,
+            output,
+            check,
+        } => {
+            let rules = match scan::list_rules(&config) {
+                Ok(rules) => rules,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    process::exit(2);
+                }
+            };
+            let config_name = config.file_name().unwrap_or_default().to_string_lossy();
+            let doc = docs::render(&rules, &config_name);
+            match output {
+                Some(path) if check => {
+                    if fs::read_to_string(&path).ok().as_deref() != Some(doc.as_str()) {
+                        tracing::error!(
+                            "{} is out of date; run `baseline docs -o {}`",
+                            path.display(),
+                            path.display()
+                        );
+                        process::exit(1);
+                    }
+                }
+                Some(path) => {
+                    if let Err(e) = fs::write(&path, &doc) {
+                        tracing::error!("failed to write {}: {}", path.display(), e);
+                        process::exit(2);
+                    }
+                }
+                None => print!("{}", doc),
+            }
+        }
+
+        Commands::Notify {
+            paths,
+            config,
+            webhook,
+            only_on_failure,
+            link,
+            dry_run,
+        } => {
+            let result = match scan::run_scan(&config, &paths) {
+                Ok(r) => r,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    process::exit(2);
+                }
+            };
+            let exit_code = result.exit_code();
+            let (name, section) = notify::config_settings(&config);
+            if exit_code == 0 && (only_on_failure || section.only_on_failure) {
+                tracing::info!("✓ Scan passed; nothing posted");
+                process::exit(0);
+            }
+            let options = notify::NotifyOptions {
+                title: name.unwrap_or_else(|| "baseline".to_string()),
+                top_rules: section.top_rules.unwrap_or(notify::DEFAULT_TOP_RULES),
+                link: link.or_else(notify::ci_run_url),
+            };
+            let payload = notify::payload(&result, &options);
+            if dry_run {
+                println!("{}", serde_json::to_string_pretty(&payload).unwrap());
+                process::exit(exit_code);
+            }
+            let Some(url) = notify::webhook_url(webhook, &section) else {
+                tracing::error!("no webhook; pass --webhook or set [notify] webhook or webhook_env");
+                process::exit(2);
+            };
+            if let Err(e) = notify::post(&url, &payload) {
+                tracing::error!("{}", e);
+                process::exit(2);
+            }
+            tracing::info!("✓ Posted scan summary to the webhook");
+            process::exit(exit_code);
+        }
+
+        Commands::Verify {
+            paths,
+            config,
+            against,
+            include_minified,
+            include_generated,
+        } => {
+            let expected = match verify::read_result(&against) {
+                Ok(v) => v,
+                Err(e) => {
+                    tracing::error!("{}: {}", against.display(), e);
+                    process::exit(2);
+                }
+            };
+            let options = scan::ScanOptions {
+                include_minified,
+                include_generated,
+                ..Default::default()
+            };
+            let result = match scan::run_scan_with_options(&config, &paths, &options) {
+                Ok(r) => r,
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    process::exit(2);
+                }
+            };
+
+            let report = verify::compare(&expected, &result);
+            format::print_verify(&report);
+            process::exit(if report.is_match() { 0 } else { 1 });
+        }
+
+        Commands::Bench {
+            files,
+            iterations,
+            top,
+        } => match bench::run(&bench::BenchOptions { files, iterations }) {
+            Ok(report) => format::print_bench(&report, top),
+            Err(e) => {
+                tracing::error!("{}", e);
+                process::exit(2);
+            }
+        },
+
+        Commands::CheckSnippet {
+            rule,
+            define,
+            pattern,
+            regex,
+            code,
+            filename,
+            config,
+        } => {
+            let source = match (rule, define, pattern) {
+                (Some(id), _, _) => snippet::SnippetRule::Id(id),
+                (_, Some(definition), _) => snippet::SnippetRule::Inline(definition),
+                (_, _, Some(pattern)) => snippet::SnippetRule::Pattern { pattern, regex },
+                (None, None, None) => unreachable!("clap requires one of --rule, --define, --pattern"),
+            };
+            let content = code.unwrap_or_else(|| {
+                let mut buf = String::new();
+                if let Err(e) = std::io::stdin().read_to_string(&mut buf) {
+                    tracing::error!("failed to read stdin: {}", e);
+                    process::exit(2);
+                }
+                buf
+            });
+            let checked = source
+                .resolve(&config)
+                .and_then(|rule| snippet::check_snippet(&rule, &filename, &content));
+            match checked {
+                Ok(result) => {
+                    format::print_snippet(&result, &filename);
+                    if result.violations.is_empty() {
+                        process::exit(1);
+                    }
+                }
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    process::exit(2);
+                }
+            }
+        }
+
+        Commands::Components {
+            paths,
+            config,
+            sort,
+            format: report_format,
+            limit,
+        } => match components::inventory(&config, &paths) {
+            Ok(mut found) => {
+                components::sort_components(&mut found, sort);
+                let total = found.len();
+                found.truncate(limit.unwrap_or(total));
+                format::print_components(&found, total, report_format);
+            }
+            Err(e) => {
+                tracing::error!("{}", e);
+                process::exit(2);
+            }
+        },
+
+        Commands::Schema { output_format } => {
+            let schema = if output_format {
+                schema::output_schema()
+            } else {
+                schema::config_schema()
+            };
+            println!("{}", serde_json::to_string_pretty(&schema).unwrap());
+        }
+
+        Commands::SelfUpdate { version } => match self_update::self_update(version.as_deref()) {
+            Ok(UpdateOutcome::UpToDate(current)) => {
+                tracing::info!("✓ baseline {} is already installed", current);
+            }
+            Ok(UpdateOutcome::Updated { from, to, path }) => {
+                tracing::info!("✓ Updated baseline {} → {} ({})", from, to, path.display());
+            }
+            Err(e) => {
+                tracing::error!("{}", e);
+                process::exit(2);
+            }
+        },
+
+        Commands::Mcp {
+            config,
+            watch,
+            watch_interval,
+        } => {
+            let options = mcp::McpOptions {
+                watch: watch.then(|| std::time::Duration::from_millis(watch_interval)),
+            };
+            mcp::run_mcp_server_with_options(&config, &options);
+        }
+
+        Commands::Preset { command } => match command {
+            PresetCommands::Export { name } => match presets::PresetSnapshot::bundled(&name) {
+                Ok(snapshot) => print!("{}", toml::to_string_pretty(&snapshot).unwrap()),
+                Err(e) => {
+                    tracing::error!("{}", e);
+                    process::exit(2);
+                }
+            },
+            PresetCommands::Diff { old, new } => {
+                let load = |spec: &str| {
+                    presets::PresetSnapshot::from_spec(spec).unwrap_or_else(|e| {
+                        tracing::error!("{}", e);
+                        process::exit(2);
+                    })
+                };
+                let (old_snapshot, new_snapshot) = (load(&old), load(&new));
+                let changes = presets::diff_rules(&old_snapshot.rule, &new_snapshot.rule);
+                format::print_preset_diff((&old, &old_snapshot), (&new, &new_snapshot), &changes);
+                if !changes.is_empty() {
+                    process::exit(1);
+                }
+            }
+        },
+
         Commands::Ratchet { command } => {
             if let Err(e) = ratchet::run(command) {
-                eprintln!("\x1b[31merror\x1b[0m: {}", e);
+                tracing::error!("{}", e);
                 process::exit(2);
             }
         }
 
-        Commands::Init { output, force } => {
-            if output.exists() && !force {
-                eprintln!(
-                    "\x1b[31merror\x1b[0m: '{}' already exists (use --force to overwrite)",
+        Commands::Init { output, force, ci } => {
+            let pipeline = ci.map(|provider| (provider, provider.pipeline_path()));
+            if let Some((_, ref path)) = pipeline {
+                if path.exists() && !force {
+                    tracing::error!(
+                        "'{}' already exists (use --force to overwrite)",
+                        path.display()
+                    );
+                    process::exit(2);
+                }
+            }
+            // With --ci, an existing config is kept so a pipeline can be added later
+            let keep_config = output.exists() && !force && pipeline.is_some();
+            if output.exists() && !force && !keep_config {
+                tracing::error!(
+                    "'{}' already exists (use --force to overwrite)",
                     output.display()
                 );
                 process::exit(2);
             }
 
-            let project_dir = std::env::current_dir().unwrap_or_default();
-            let project_type = init::detect_project(&project_dir);
-            let config = init::generate_config(&project_type);
+            if keep_config {
+                tracing::info!("hint: keeping existing {}", output.display());
+            } else {
+                let project_dir = std::env::current_dir().unwrap_or_default();
+                let project_type = init::detect_project(&project_dir);
+                let config = init::generate_config(&project_type);
 
-            if let Err(e) = fs::write(&output, &config) {
-                eprintln!("\x1b[31merror\x1b[0m: failed to write config: {}", e);
-                process::exit(2);
-            }
+                if let Err(e) = fs::write(&output, &config) {
+                    tracing::error!("failed to write config: {}", e);
+                    process::exit(2);
+                }
 
-            let type_label = match project_type {
-                init::ProjectType::ShadcnTailwind => "shadcn + Tailwind",
-                init::ProjectType::TailwindOnly => "Tailwind CSS",
-                init::ProjectType::Generic => "generic",
-                init::ProjectType::Unknown => "generic",
-            };
+                let type_label = match project_type {
+                    init::ProjectType::ShadcnTailwind => "shadcn + Tailwind",
+                    init::ProjectType::TailwindOnly => "Tailwind CSS",
+                    init::ProjectType::Generic => "generic",
+                    init::ProjectType::Unknown => "generic",
+                };
 
-            eprintln!(
-                "\x1b[32m✓\x1b[0m Created {} (detected: {})",
-                output.display(),
-                type_label
-            );
-            eprintln!(
-                "\x1b[90mhint\x1b[0m: run \x1b[1mbaseline scan .\x1b[0m to find violations"
+                tracing::info!(
+                    "✓ Created {} (detected: {})",
+                    output.display(),
+                    type_label
+                );
+            }
+
+            if let Some((provider, ref path)) = pipeline {
+                let content = init::generate_pipeline(provider, &output);
+                let written = match path.parent() {
+                    Some(dir) if !dir.as_os_str().is_empty() => fs::create_dir_all(dir),
+                    _ => Ok(()),
+                }
+                .and_then(|()| fs::write(path, content));
+                if let Err(e) = written {
+                    tracing::error!("failed to write {}: {}", path.display(), e);
+                    process::exit(2);
+                }
+                tracing::info!("✓ Created {}", path.display());
+                if let Some(step) = provider.next_step() {
+                    tracing::info!("hint: {}", step);
+                }
+            }
+            tracing::info!(
+                "hint: run `baseline scan .` to find violations"
             );
         }
     }
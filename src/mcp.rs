@@ -1,9 +1,20 @@
 use crate::cli::toml_config::TomlConfig;
 use crate::presets;
 use crate::scan;
+use crate::watch::{FileChange, FileWatcher};
 use serde_json::json;
 use std::io::{self, BufRead, Write};
 use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+/// Options for the MCP server.
+#[derive(Debug, Clone, Default)]
+pub struct McpOptions {
+    /// Poll the workspace at this interval and push a notification whenever
+    /// a modified file gains or loses violations.
+    pub watch: Option<Duration>,
+}
 
 /// Run a simple MCP-compatible server over stdio.
 ///
@@ -11,8 +22,18 @@ use std::path::{Path, PathBuf};
 /// JSON-RPC responses to stdout. Supports the MCP protocol for
 /// tool discovery and execution.
 pub fn run_mcp_server(config_path: &Path) {
+    run_mcp_server_with_options(config_path, &McpOptions::default());
+}
+
+/// Run the MCP server with explicit options (e.g. file watching).
+pub fn run_mcp_server_with_options(config_path: &Path, options: &McpOptions) {
     let stdin = io::stdin();
-    let mut stdout = io::stdout();
+    let stdout = Arc::new(Mutex::new(io::stdout()));
+    tracing::info!("mcp server started (config: {})", config_path.display());
+
+    if let Some(interval) = options.watch {
+        spawn_watcher(config_path, interval, Arc::clone(&stdout));
+    }
 
     // Read line-delimited JSON-RPC messages
     for line in stdin.lock().lines() {
@@ -28,13 +49,13 @@ pub fn run_mcp_server(config_path: &Path) {
         let request: serde_json::Value = match serde_json::from_str(&line) {
             Ok(v) => v,
             Err(e) => {
+                tracing::warn!("malformed request: {}", e);
                 let error_response = json!({
                     "jsonrpc": "2.0",
                     "id": null,
                     "error": { "code": -32700, "message": format!("Parse error: {}", e) }
                 });
-                let _ = writeln!(stdout, "{}", error_response);
-                let _ = stdout.flush();
+                send(&stdout, &error_response);
                 continue;
             }
         };
@@ -42,24 +63,84 @@ pub fn run_mcp_server(config_path: &Path) {
         let id = request.get("id").cloned();
         let method = request.get("method").and_then(|m| m.as_str()).unwrap_or("");
         let params = request.get("params").cloned().unwrap_or(json!({}));
+        tracing::debug!("request {}", method);
 
         let response = match method {
             "initialize" => handle_initialize(id.clone()),
             "tools/list" => handle_tools_list(id.clone()),
             "tools/call" => handle_tools_call(id.clone(), &params, config_path),
+            "resources/list" => handle_resources_list(id.clone()),
+            "resources/read" => handle_resources_read(id.clone(), &params, config_path),
             "notifications/initialized" | "notifications/cancelled" => continue,
-            _ => json!({
-                "jsonrpc": "2.0",
-                "id": id,
-                "error": { "code": -32601, "message": format!("Unknown method: {}", method) }
-            }),
+            _ => {
+                tracing::warn!("unknown method: {}", method);
+                json!({
+                    "jsonrpc": "2.0",
+                    "id": id,
+                    "error": { "code": -32601, "message": format!("Unknown method: {}", method) }
+                })
+            }
         };
 
-        let _ = writeln!(stdout, "{}", response);
-        let _ = stdout.flush();
+        send(&stdout, &response);
     }
 }
 
+/// Write one JSON-RPC message to the shared stdout.
+fn send(stdout: &Mutex<io::Stdout>, message: &serde_json::Value) {
+    let mut out = stdout.lock().unwrap();
+    let _ = writeln!(out, "{}", message);
+    let _ = out.flush();
+}
+
+/// Start a background thread that polls the workspace and pushes
+/// `notifications/message` whenever violations change in an edited file.
+fn spawn_watcher(config_path: &Path, interval: Duration, stdout: Arc<Mutex<io::Stdout>>) {
+    let mut watcher = match FileWatcher::new(config_path, &[PathBuf::from(".")]) {
+        Ok(w) => w,
+        Err(e) => {
+            tracing::error!("file watching disabled: {}", e);
+            return;
+        }
+    };
+    tracing::info!("watching workspace every {}ms", interval.as_millis());
+
+    std::thread::spawn(move || loop {
+        std::thread::sleep(interval);
+        match watcher.poll() {
+            Ok(changes) => {
+                for change in &changes {
+                    send(&stdout, &change_notification(change));
+                }
+            }
+            Err(e) => tracing::warn!("watch poll failed: {}", e),
+        }
+    });
+}
+
+/// Build the push notification for a file whose violations changed.
+fn change_notification(change: &FileChange) -> serde_json::Value {
+    let keys = |list: &[crate::watch::ViolationKey]| -> Vec<serde_json::Value> {
+        list.iter()
+            .map(|k| json!({ "rule_id": k.rule_id, "line": k.line, "message": k.message }))
+            .collect()
+    };
+
+    json!({
+        "jsonrpc": "2.0",
+        "method": "notifications/message",
+        "params": {
+            "level": if change.appeared.is_empty() { "info" } else { "warning" },
+            "logger": "baseline",
+            "data": {
+                "file": change.file.display().to_string(),
+                "appeared": keys(&change.appeared),
+                "resolved": keys(&change.resolved),
+            }
+        }
+    })
+}
+
 fn handle_initialize(id: Option<serde_json::Value>) -> serde_json::Value {
     json!({
         "jsonrpc": "2.0",
@@ -67,7 +148,9 @@ fn handle_initialize(id: Option<serde_json::Value>) -> serde_json::Value {
         "result": {
             "protocolVersion": "2024-11-05",
             "capabilities": {
-                "tools": {}
+                "tools": {},
+                "resources": {},
+                "logging": {}
             },
             "serverInfo": {
                 "name": "baseline",
@@ -118,6 +201,118 @@ fn handle_tools_list(id: Option<serde_json::Value>) -> serde_json::Value {
     })
 }
 
+/// URI of the resource exposing the effective (merged) rule set.
+const CONFIG_RESOURCE_URI: &str = "baseline://config";
+/// URI of the resource exposing current ratchet counts vs budgets.
+const RATCHET_RESOURCE_URI: &str = "baseline://ratchet";
+
+fn handle_resources_list(id: Option<serde_json::Value>) -> serde_json::Value {
+    json!({
+        "jsonrpc": "2.0",
+        "id": id,
+        "result": {
+            "resources": [
+                {
+                    "uri": CONFIG_RESOURCE_URI,
+                    "name": "baseline config",
+                    "description": "Effective rules after merging presets, plugins, and user rules.",
+                    "mimeType": "application/json"
+                },
+                {
+                    "uri": RATCHET_RESOURCE_URI,
+                    "name": "baseline ratchet status",
+                    "description": "Current occurrence count of each ratchet rule against its max_count budget.",
+                    "mimeType": "application/json"
+                }
+            ]
+        }
+    })
+}
+
+fn handle_resources_read(
+    id: Option<serde_json::Value>,
+    params: &serde_json::Value,
+    config_path: &Path,
+) -> serde_json::Value {
+    let uri = params.get("uri").and_then(|u| u.as_str()).unwrap_or("");
+
+    let body = match uri {
+        CONFIG_RESOURCE_URI => config_resource(config_path),
+        RATCHET_RESOURCE_URI => ratchet_resource(config_path, &[PathBuf::from(".")]),
+        _ => {
+            return json!({
+                "jsonrpc": "2.0",
+                "id": id,
+                "error": { "code": -32002, "message": format!("Resource not found: {}", uri) }
+            });
+        }
+    };
+
+    match body {
+        Ok(value) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "result": {
+                "contents": [{
+                    "uri": uri,
+                    "mimeType": "application/json",
+                    "text": serde_json::to_string_pretty(&value).unwrap()
+                }]
+            }
+        }),
+        Err(e) => json!({
+            "jsonrpc": "2.0",
+            "id": id,
+            "error": { "code": -32603, "message": format!("Error: {}", e) }
+        }),
+    }
+}
+
+/// Effective rule set: presets + plugins + user rules, fully resolved.
+fn config_resource(config_path: &Path) -> Result<serde_json::Value, scan::ScanError> {
+    let loaded = scan::load_config(config_path)?;
+    Ok(json!({
+        "config": config_path.display().to_string(),
+        "extends": loaded.toml.baseline.extends,
+        "rules": loaded.rules,
+        "disabled": loaded.disabled.iter().map(|r| &r.id).collect::<Vec<_>>(),
+    }))
+}
+
+/// Current ratchet counts compared against each rule's `max_count`.
+fn ratchet_resource(
+    config_path: &Path,
+    paths: &[PathBuf],
+) -> Result<serde_json::Value, scan::ScanError> {
+    let loaded = scan::load_config(config_path)?;
+    let baseline = scan::run_baseline(config_path, paths)?;
+
+    let ratchets: Vec<serde_json::Value> = baseline
+        .entries
+        .iter()
+        .map(|entry| {
+            let max_count = loaded
+                .rules
+                .iter()
+                .find(|r| r.id == entry.rule_id)
+                .and_then(|r| r.max_count)
+                .unwrap_or(0);
+            json!({
+                "rule_id": entry.rule_id,
+                "pattern": entry.pattern,
+                "count": entry.count,
+                "max_count": max_count,
+                "status": if entry.count <= max_count { "pass" } else { "over" },
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "ratchets": ratchets,
+        "files_scanned": baseline.files_scanned,
+    }))
+}
+
 fn handle_tools_call(
     id: Option<serde_json::Value>,
     params: &serde_json::Value,
@@ -275,6 +470,7 @@ fn handle_list_rules(
                 "id": r.id,
                 "type": r.rule_type,
                 "severity": r.severity,
+                "enabled": r.enabled,
                 "glob": r.glob,
                 "message": r.message,
             })
@@ -301,15 +497,13 @@ fn format_violations_json(result: &scan::ScanResult) -> serde_json::Value {
         .map(|v| {
             let mut obj = json!({
                 "rule_id": v.rule_id,
-                "severity": match v.severity {
-                    Severity::Error => "error",
-                    Severity::Warning => "warning",
-                },
+                "severity": v.severity.as_str(),
                 "file": v.file.display().to_string(),
                 "line": v.line,
-                "column": v.column,
+                "column": v.column_in(result.output.column_unit()),
                 "message": v.message,
-                "suggest": v.suggest,
+                "suggest": v.suggest_text(),
+                "suggestions": crate::cli::format::suggestions_json(v),
             });
 
             if let Some(ref fix) = v.fix {
@@ -326,6 +520,8 @@ fn format_violations_json(result: &scan::ScanResult) -> serde_json::Value {
             "total": result.violations.len(),
             "errors": result.violations.iter().filter(|v| v.severity == Severity::Error).count(),
             "warnings": result.violations.iter().filter(|v| v.severity == Severity::Warning).count(),
+            "info": result.violations.iter().filter(|v| v.severity == Severity::Info).count(),
+            "hints": result.violations.iter().filter(|v| v.severity == Severity::Hint).count(),
             "files_scanned": result.files_scanned,
         }
     })
@@ -363,10 +559,22 @@ mod tests {
         let result = scan::ScanResult {
             violations: vec![],
             files_scanned: 3,
+            generated_skipped: 0,
             rules_loaded: 2,
+            unmatched_globs: Vec::new(),
+            groups: Vec::new(),
+            rules: Vec::new(),
             ratchet_counts: HashMap::new(),
+            ratchet_deadlines: HashMap::new(),
             changed_files_count: None,
             base_ref: None,
+            timings: Default::default(),
+            scanned_files: vec![],
+            file_hashes: HashMap::new(),
+            fixes: None,
+            output: Default::default(),
+            hooks: Default::default(),
+            stopped_early: false,
         };
         let json = format_violations_json(&result);
         assert_eq!(json["summary"]["total"], 0);
@@ -384,18 +592,32 @@ mod tests {
                 line: Some(5),
                 column: Some(10),
                 message: "bad class".into(),
-                suggest: Some("use good class".into()),
+                suggest: vec![crate::rules::Suggestion::new("use good class")],
                 source_line: None,
                 fix: Some(crate::rules::Fix {
                     old: "bg-red-500".into(),
                     new: "bg-destructive".into(),
                 }),
+                origin: None,
+                enclosing: None,
             }],
             files_scanned: 1,
+            generated_skipped: 0,
             rules_loaded: 1,
+            unmatched_globs: Vec::new(),
+            groups: Vec::new(),
+            rules: Vec::new(),
             ratchet_counts: HashMap::new(),
+            ratchet_deadlines: HashMap::new(),
             changed_files_count: None,
             base_ref: None,
+            timings: Default::default(),
+            scanned_files: vec![],
+            file_hashes: HashMap::new(),
+            fixes: None,
+            output: Default::default(),
+            hooks: Default::default(),
+            stopped_early: false,
         };
         let json = format_violations_json(&result);
         assert_eq!(json["summary"]["total"], 1);
@@ -417,9 +639,11 @@ mod tests {
                     line: Some(1),
                     column: None,
                     message: "err".into(),
-                    suggest: None,
+                    suggest: Vec::new(),
                     source_line: None,
                     fix: None,
+                    origin: None,
+                    enclosing: None,
                 },
                 Violation {
                     rule_id: "r2".into(),
@@ -428,16 +652,30 @@ mod tests {
                     line: Some(2),
                     column: None,
                     message: "warn".into(),
-                    suggest: None,
+                    suggest: Vec::new(),
                     source_line: None,
                     fix: None,
+                    origin: None,
+                    enclosing: None,
                 },
             ],
             files_scanned: 2,
+            generated_skipped: 0,
             rules_loaded: 2,
+            unmatched_globs: Vec::new(),
+            groups: Vec::new(),
+            rules: Vec::new(),
             ratchet_counts: HashMap::new(),
+            ratchet_deadlines: HashMap::new(),
             changed_files_count: None,
             base_ref: None,
+            timings: Default::default(),
+            scanned_files: vec![],
+            file_hashes: HashMap::new(),
+            fixes: None,
+            output: Default::default(),
+            hooks: Default::default(),
+            stopped_early: false,
         };
         let json = format_violations_json(&result);
         assert_eq!(json["summary"]["errors"], 1);
@@ -455,4 +693,114 @@ mod tests {
         assert!(resp["error"].is_object());
         assert_eq!(resp["error"]["code"], -32602);
     }
+
+    #[test]
+    fn change_notification_levels() {
+        let key = crate::watch::ViolationKey {
+            rule_id: "no-console".into(),
+            line: Some(3),
+            message: "no console.log".into(),
+        };
+        let appeared = FileChange {
+            file: PathBuf::from("src/a.ts"),
+            appeared: vec![key.clone()],
+            resolved: vec![],
+        };
+        let msg = change_notification(&appeared);
+        assert_eq!(msg["method"], "notifications/message");
+        assert!(msg.get("id").is_none());
+        assert_eq!(msg["params"]["level"], "warning");
+        assert_eq!(msg["params"]["data"]["file"], "src/a.ts");
+        assert_eq!(msg["params"]["data"]["appeared"][0]["line"], 3);
+
+        let resolved = FileChange {
+            file: PathBuf::from("src/a.ts"),
+            appeared: vec![],
+            resolved: vec![key],
+        };
+        assert_eq!(change_notification(&resolved)["params"]["level"], "info");
+    }
+
+    #[test]
+    fn resources_list_returns_config_and_ratchet() {
+        let resp = handle_resources_list(Some(json!(4)));
+        let resources = resp["result"]["resources"].as_array().unwrap();
+        assert_eq!(resources.len(), 2);
+        assert_eq!(resources[0]["uri"], "baseline://config");
+        assert_eq!(resources[1]["uri"], "baseline://ratchet");
+    }
+
+    #[test]
+    fn resources_read_config_includes_preset_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        std::fs::write(
+            &config,
+            r#"
+[baseline]
+extends = ["dependency-hygiene"]
+
+[[rule]]
+id = "no-todo"
+type = "banned-pattern"
+pattern = "TODO"
+message = "no TODOs"
+"#,
+        )
+        .unwrap();
+
+        let resp = handle_resources_read(
+            Some(json!(5)),
+            &json!({ "uri": "baseline://config" }),
+            &config,
+        );
+        let contents = &resp["result"]["contents"][0];
+        assert_eq!(contents["uri"], "baseline://config");
+        let body: serde_json::Value =
+            serde_json::from_str(contents["text"].as_str().unwrap()).unwrap();
+        let rules = body["rules"].as_array().unwrap();
+        assert!(rules.iter().any(|r| r["id"] == "no-todo" && r["type"] == "banned-pattern"));
+        assert!(rules.len() > 1);
+    }
+
+    #[test]
+    fn ratchet_resource_reports_budget_status() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        std::fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "legacy-fetch"
+type = "ratchet"
+pattern = "legacyFetch("
+max_count = 1
+glob = "**/*.ts"
+message = "migrate"
+"#,
+        )
+        .unwrap();
+        let src = dir.path().join("src");
+        std::fs::create_dir(&src).unwrap();
+        std::fs::write(src.join("a.ts"), "legacyFetch();\nlegacyFetch();\n").unwrap();
+
+        let body = ratchet_resource(&config, &[src]).unwrap();
+        let entry = &body["ratchets"][0];
+        assert_eq!(entry["rule_id"], "legacy-fetch");
+        assert_eq!(entry["count"], 2);
+        assert_eq!(entry["max_count"], 1);
+        assert_eq!(entry["status"], "over");
+    }
+
+    #[test]
+    fn resources_read_unknown_uri_errors() {
+        let resp = handle_resources_read(
+            Some(json!(6)),
+            &json!({ "uri": "baseline://nope" }),
+            std::path::Path::new("baseline.toml"),
+        );
+        assert_eq!(resp["error"]["code"], -32002);
+    }
 }
@@ -0,0 +1,300 @@
+use crate::cli::toml_config::{NotifySection, TomlConfig};
+use crate::config::Severity;
+use crate::scan::{self, ScanResult};
+use serde_json::{json, Value};
+use std::fmt;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Stdio};
+
+/// Rules listed in a notification when `top_rules` is unset.
+pub const DEFAULT_TOP_RULES: usize = 5;
+
+#[derive(Debug)]
+pub enum NotifyError {
+    CurlNotFound,
+    RequestFailed(String),
+}
+
+impl fmt::Display for NotifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            NotifyError::CurlNotFound => write!(f, "curl is not installed or not in PATH"),
+            NotifyError::RequestFailed(msg) => write!(f, "webhook request failed: {}", msg),
+        }
+    }
+}
+
+impl std::error::Error for NotifyError {}
+
+pub struct NotifyOptions {
+    /// Heading of the message (the config's `[baseline] name`, or "baseline").
+    pub title: String,
+    /// Rules listed, most violations first.
+    pub top_rules: usize,
+    /// Link to the CI run, shown at the bottom.
+    pub link: Option<String>,
+}
+
+/// The `[baseline] name` and `[notify]` section of a config. Read after the
+/// scan has loaded the config, so a config that can't be read gives defaults.
+pub fn config_settings(config_path: &Path) -> (Option<String>, NotifySection) {
+    std::fs::read_to_string(config_path)
+        .ok()
+        .and_then(|text| toml::from_str::<TomlConfig>(&text).ok())
+        .map(|config| (config.baseline.name, config.notify))
+        .unwrap_or_default()
+}
+
+/// The webhook to post to: `--webhook`, then the variable named by
+/// `webhook_env`, then `webhook`.
+pub fn webhook_url(flag: Option<String>, section: &NotifySection) -> Option<String> {
+    flag.or_else(|| {
+        section
+            .webhook_env
+            .as_deref()
+            .and_then(|name| std::env::var(name).ok())
+            .filter(|url| !url.is_empty())
+    })
+    .or_else(|| section.webhook.clone())
+}
+
+/// The current GitHub Actions run, when running under one.
+pub fn ci_run_url() -> Option<String> {
+    let var = |name| std::env::var(name).ok().filter(|v: &String| !v.is_empty());
+    Some(format!(
+        "{}/{}/actions/runs/{}",
+        var("GITHUB_SERVER_URL")?,
+        var("GITHUB_REPOSITORY")?,
+        var("GITHUB_RUN_ID")?
+    ))
+}
+
+/// A Slack-compatible message summarizing `result`: counts, the rules with
+/// the most violations, ratchet status and the CI link. `text` carries the
+/// counts alone, for webhooks that ignore `blocks`.
+pub fn payload(result: &ScanResult, options: &NotifyOptions) -> Value {
+    let count = |severity: Severity| result.violations.iter().filter(|v| v.severity == severity).count();
+    let (errors, warnings) = (count(Severity::Error), count(Severity::Warning));
+    let status = if result.exit_code() == 0 { "passed" } else { "failed" };
+    let summary = format!(
+        "{} {}: {} error{}, {} warning{} ({} file{} scanned)",
+        options.title,
+        status,
+        errors,
+        if errors == 1 { "" } else { "s" },
+        warnings,
+        if warnings == 1 { "" } else { "s" },
+        result.files_scanned,
+        if result.files_scanned == 1 { "" } else { "s" }
+    );
+    let mrkdwn = |text: String| json!({ "type": "section", "text": { "type": "mrkdwn", "text": text } });
+
+    let icon = if result.exit_code() == 0 { ":white_check_mark:" } else { ":x:" };
+    let mut blocks = vec![mrkdwn(format!("{} *{}*", icon, summary))];
+
+    let counts = scan::rule_counts(result);
+    if !counts.is_empty() && options.top_rules > 0 {
+        let mut lines = vec!["*Top rules*".to_string()];
+        for c in counts.iter().take(options.top_rules) {
+            lines.push(format!("• `{}` ({}): {}", c.rule_id, c.severity.as_str(), c.violations));
+        }
+        if counts.len() > options.top_rules {
+            lines.push(format!("…and {} more", counts.len() - options.top_rules));
+        }
+        blocks.push(mrkdwn(lines.join("\n")));
+    }
+
+    if !result.ratchet_counts.is_empty() {
+        let mut ratchets: Vec<_> = result.ratchet_counts.iter().collect();
+        ratchets.sort_by(|a, b| a.0.cmp(b.0));
+        let mut lines = vec!["*Ratchets*".to_string()];
+        for (key, &(found, max)) in ratchets {
+            let state = if found > max { " :warning: over budget" } else { "" };
+            lines.push(format!("• `{}`: {}/{}{}", key, found, max, state));
+        }
+        blocks.push(mrkdwn(lines.join("\n")));
+    }
+
+    if let Some(ref link) = options.link {
+        blocks.push(json!({
+            "type": "context",
+            "elements": [{ "type": "mrkdwn", "text": format!("<{}|View CI run>", link) }],
+        }));
+    }
+
+    json!({ "text": summary, "blocks": blocks })
+}
+
+/// POST `payload` as JSON to `url` with `curl`. The webhook URL is a secret,
+/// so it reaches curl in a config on stdin rather than on the command line,
+/// where other local users could read it, and is redacted from errors.
+pub fn post(url: &str, payload: &Value) -> Result<(), NotifyError> {
+    let mut child = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--max-time", "30"])
+        .args(["--header", "Content-Type: application/json", "--config", "-"])
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .spawn()
+        .map_err(|_| NotifyError::CurlNotFound)?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(curl_config(url, &payload.to_string()).as_bytes())
+            .map_err(|e| NotifyError::RequestFailed(e.to_string()))?;
+    }
+    let output = child
+        .wait_with_output()
+        .map_err(|e| NotifyError::RequestFailed(e.to_string()))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(NotifyError::RequestFailed(stderr.trim().replace(url, "<webhook>")));
+    }
+    Ok(())
+}
+
+/// A curl config (`--config -`) posting `body` to `url`.
+fn curl_config(url: &str, body: &str) -> String {
+    format!("url = {}\ndata-binary = {}\n", curl_quote(url), curl_quote(body))
+}
+
+/// `value` as a double-quoted curl config string.
+fn curl_quote(value: &str) -> String {
+    let mut quoted = String::with_capacity(value.len() + 2);
+    quoted.push('"');
+    for c in value.chars() {
+        match c {
+            '\\' => quoted.push_str("\\\\"),
+            '"' => quoted.push_str("\\\""),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            '\t' => quoted.push_str("\\t"),
+            _ => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Violation;
+    use std::collections::HashMap;
+
+    fn violation(rule_id: &str, severity: Severity) -> Violation {
+        Violation {
+            rule_id: rule_id.into(),
+            severity,
+            file: "src/a.ts".into(),
+            line: Some(1),
+            column: None,
+            message: "m".into(),
+            suggest: Vec::new(),
+            source_line: None,
+            fix: None,
+            origin: None,
+            enclosing: None,
+        }
+    }
+
+    #[test]
+    fn payload_summarizes_counts_rules_and_ratchets() {
+        let mut result = ScanResult {
+            violations: vec![
+                violation("no-console", Severity::Error),
+                violation("no-console", Severity::Error),
+                violation("no-any", Severity::Warning),
+            ],
+            files_scanned: 12,
+            generated_skipped: 0,
+            rules_loaded: 3,
+            unmatched_globs: Vec::new(),
+            groups: Vec::new(),
+            rules: Vec::new(),
+            ratchet_counts: HashMap::new(),
+            ratchet_deadlines: HashMap::new(),
+            changed_files_count: None,
+            base_ref: None,
+            timings: Default::default(),
+            scanned_files: Vec::new(),
+            file_hashes: HashMap::new(),
+            fixes: None,
+            output: Default::default(),
+            hooks: Default::default(),
+            stopped_early: false,
+        };
+        result.ratchet_counts.insert("legacy-fetch".into(), (7, 5));
+        let options = NotifyOptions {
+            title: "acme".into(),
+            top_rules: 1,
+            link: Some("https://ci.example/runs/1".into()),
+        };
+        let payload = payload(&result, &options);
+
+        assert_eq!(payload["text"], "acme failed: 2 errors, 1 warning (12 files scanned)");
+        let blocks = payload["blocks"].as_array().unwrap();
+        assert_eq!(blocks.len(), 4);
+        assert_eq!(blocks[1]["text"]["text"], "*Top rules*\n• `no-console` (error): 2\n…and 1 more");
+        assert_eq!(blocks[2]["text"]["text"], "*Ratchets*\n• `legacy-fetch`: 7/5 :warning: over budget");
+        assert_eq!(blocks[3]["elements"][0]["text"], "<https://ci.example/runs/1|View CI run>");
+    }
+
+    #[test]
+    fn curl_config_quotes_url_and_body() {
+        assert_eq!(
+            curl_config("https://hooks.example.com/T0/B0/secret", "say \"hi\" \\ now\n"),
+            concat!(
+                r#"url = "https://hooks.example.com/T0/B0/secret""#,
+                "\n",
+                r#"data-binary = "say \"hi\" \\ now\n""#,
+                "\n"
+            )
+        );
+    }
+
+    #[test]
+    fn post_sends_payload_without_url_in_args_or_errors() {
+        use std::io::{BufRead, BufReader, Read};
+        use std::net::TcpListener;
+
+        if Command::new("curl").arg("--version").output().is_err() {
+            return;
+        }
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = std::thread::spawn(move || {
+            let mut bodies = Vec::new();
+            for status in ["200 OK", "404 Not Found"] {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.to_ascii_lowercase().strip_prefix("content-length:") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    if line.trim().is_empty() {
+                        break;
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                bodies.push(String::from_utf8(body).unwrap());
+                let response = format!("HTTP/1.1 {}\r\nContent-Length: 0\r\nConnection: close\r\n\r\n", status);
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+            bodies
+        });
+
+        let url = format!("http://127.0.0.1:{}/services/T0/B0/secret-token", port);
+        let payload = serde_json::json!({ "text": "baseline: 2 \"errors\"\nsee run" });
+        post(&url, &payload).unwrap();
+        let err = post(&url, &payload).unwrap_err().to_string();
+        assert!(!err.contains("secret-token"), "{}", err);
+
+        let bodies = server.join().unwrap();
+        assert_eq!(serde_json::from_str::<Value>(&bodies[0]).unwrap(), payload);
+    }
+}
@@ -1,6 +1,8 @@
-use crate::cli::toml_config::{ScopedPreset, TomlRule};
-use std::collections::HashMap;
+use crate::cli::toml_config::{PatternList, RuleOrigin, ScopedPreset, TomlRule};
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug)]
 pub enum PresetError {
@@ -8,6 +10,11 @@ pub enum PresetError {
         name: String,
         available: Vec<&'static str>,
     },
+    /// A preset snapshot file that couldn't be read or parsed.
+    InvalidSnapshot {
+        path: PathBuf,
+        reason: String,
+    },
 }
 
 impl fmt::Display for PresetError {
@@ -21,6 +28,9 @@ impl fmt::Display for PresetError {
                     available.join(", ")
                 )
             }
+            PresetError::InvalidSnapshot { path, reason } => {
+                write!(f, "invalid preset snapshot {}: {}", path.display(), reason)
+            }
         }
     }
 }
@@ -38,9 +48,14 @@ enum Preset {
     NextjsBestPractices,
     Accessibility,
     ReactNative,
+    ExpoRouter,
+    SvelteKit,
+    Remix,
     React19,
     ReactOpinions,
     DependencyHygiene,
+    SupplyChain,
+    LibraryHygiene,
 }
 
 /// Returns the list of all available preset names.
@@ -58,6 +73,11 @@ pub fn available_presets() -> &'static [&'static str] {
         "nextjs-best-practices",
         "accessibility",
         "react-native",
+        "expo-router",
+        "sveltekit",
+        "remix",
+        "supply-chain",
+        "library-hygiene",
     ]
 }
 
@@ -75,6 +95,11 @@ fn resolve_preset(name: &str) -> Option<Preset> {
         "nextjs-best-practices" => Some(Preset::NextjsBestPractices),
         "accessibility" => Some(Preset::Accessibility),
         "react-native" => Some(Preset::ReactNative),
+        "expo-router" => Some(Preset::ExpoRouter),
+        "sveltekit" => Some(Preset::SvelteKit),
+        "remix" => Some(Preset::Remix),
+        "supply-chain" => Some(Preset::SupplyChain),
+        "library-hygiene" => Some(Preset::LibraryHygiene),
         _ => None,
     }
 }
@@ -316,6 +341,16 @@ fn preset_rules(preset: Preset) -> Vec<TomlRule> {
                 suggest: Some("Remove onPaste preventDefault — let users paste freely".into()),
                 ..Default::default()
             },
+            TomlRule {
+                id: "no-sql-interpolation".into(),
+                rule_type: "no-sql-interpolation".into(),
+                severity: "error".into(),
+                glob: Some("**/*.{ts,tsx,js,jsx}".into()),
+                exclude_glob: vec!["**/*.test.*".into(), "**/*.spec.*".into()],
+                message: "SQL built with template interpolation is an injection risk".into(),
+                suggest: Some("Use query parameters or a tagged sql`...` template instead of ${...} in the query text".into()),
+                ..Default::default()
+            },
         ],
         Preset::Nextjs => vec![
             TomlRule {
@@ -746,7 +781,43 @@ fn preset_rules(preset: Preset) -> Vec<TomlRule> {
                     regex: true,
                     file_contains: Some("use client".into()),
                     message: "Client components cannot be async — only server components support async/await".into(),
-                    suggest: Some("Remove 'use client' to make this a server component, or remove async and use useEffect for data fetching".into()),
+                    suggest: Some(PatternList(vec![
+                        "Remove 'use client' to make this a server component".into(),
+                        "Remove async and fetch data in useEffect".into(),
+                    ])),
+                    ..Default::default()
+                },
+                TomlRule {
+                    id: "require-server-only".into(),
+                    rule_type: "server-only-import".into(),
+                    severity: "warning".into(),
+                    packages: vec![
+                        "@prisma/client".into(),
+                        "drizzle-orm".into(),
+                        "pg".into(),
+                        "mysql2".into(),
+                        "postgres".into(),
+                        "@planetscale/database".into(),
+                        "@neondatabase/serverless".into(),
+                        "@vercel/postgres".into(),
+                        "mongoose".into(),
+                        "mongodb".into(),
+                        "firebase-admin".into(),
+                        "stripe".into(),
+                        "@aws-sdk/*".into(),
+                    ],
+                    exclude_glob: vec![
+                        "**/route.{ts,js}".into(),
+                        "**/middleware.{ts,js}".into(),
+                        "**/instrumentation.{ts,js}".into(),
+                        "pages/api/**".into(),
+                        "scripts/**".into(),
+                        "**/*.config.*".into(),
+                        "**/*.test.*".into(),
+                        "**/*.spec.*".into(),
+                    ],
+                    message: "Server-only code is not poisoned against client imports".into(),
+                    suggest: Some("Add import \"server-only\" at the top so importing this file from a client component fails the build".into()),
                     ..Default::default()
                 },
                 // ── SEO ──────────────────────────────────────────────────
@@ -798,6 +869,14 @@ fn preset_rules(preset: Preset) -> Vec<TomlRule> {
                     suggest: Some("Use Zod or similar: const data = schema.parse(formData)".into()),
                     ..Default::default()
                 },
+                TomlRule {
+                    id: "route-handler-requires-validation".into(),
+                    rule_type: "route-handler-validation".into(),
+                    severity: "warning".into(),
+                    message: "Route handlers should validate the request body and params".into(),
+                    suggest: Some("Use Zod or similar: const data = schema.parse(await request.json())".into()),
+                    ..Default::default()
+                },
                 // ── Hydration ────────────────────────────────────────
                 TomlRule {
                     id: "no-suppress-hydration-warning".into(),
@@ -895,6 +974,15 @@ fn preset_rules(preset: Preset) -> Vec<TomlRule> {
                 message: "Empty catch block swallows errors — handle or re-throw the error".into(),
                 ..Default::default()
             },
+            TomlRule {
+                id: "no-silent-promise-catch".into(),
+                rule_type: "banned-pattern".into(),
+                severity: "error".into(),
+                pattern: Some(r"\.catch\(\s*(?:(?:\([^)]*\)|\w+)\s*=>\s*\{\s*\}|console\.\w+)\s*\)".into()),
+                regex: true,
+                message: "Promise .catch() handler swallows the error — handle, report or re-throw it".into(),
+                ..Default::default()
+            },
             TomlRule {
                 id: "no-console-log".into(),
                 rule_type: "banned-pattern".into(),
@@ -1067,7 +1155,44 @@ fn preset_rules(preset: Preset) -> Vec<TomlRule> {
                     severity: "error".into(),
                     glob: Some("**/*.{tsx,jsx}".into()),
                     message: "img element must have an alt attribute for screen readers".into(),
-                    suggest: Some("Add alt=\"description\" or alt=\"\" for decorative images".into()),
+                    suggest: Some(PatternList(vec![
+                        "Add alt=\"...\" describing the image".into(),
+                        "Add alt=\"\" if the image is decorative".into(),
+                    ])),
+                    ..Default::default()
+                });
+                rules.push(TomlRule {
+                    id: "require-icon-button-label".into(),
+                    rule_type: "require-icon-button-label".into(),
+                    severity: "warning".into(),
+                    glob: Some("**/*.{tsx,jsx}".into()),
+                    message: "Icon-only button has no accessible name for screen readers".into(),
+                    suggest: Some(PatternList(vec![
+                        "Add aria-label=\"...\" describing the action".into(),
+                        "Point aria-labelledby at visible text naming the action".into(),
+                    ])),
+                    ..Default::default()
+                });
+                rules.push(TomlRule {
+                    id: "heading-hierarchy".into(),
+                    rule_type: "heading-hierarchy".into(),
+                    severity: "warning".into(),
+                    glob: Some("**/*.{tsx,jsx}".into()),
+                    message: "Heading structure is confusing for screen reader navigation".into(),
+                    suggest: Some("Use one <h1> and <main> per page and step heading levels down one at a time".into()),
+                    ..Default::default()
+                });
+                rules.push(TomlRule {
+                    id: "require-input-label".into(),
+                    rule_type: "require-input-label".into(),
+                    severity: "warning".into(),
+                    glob: Some("**/*.{tsx,jsx}".into()),
+                    message: "Form control has no label for screen readers".into(),
+                    suggest: Some(PatternList(vec![
+                        "Wrap it in a <label>".into(),
+                        "Pair it with <label htmlFor> and a matching id".into(),
+                        "Add aria-label".into(),
+                    ])),
                     ..Default::default()
                 });
             }
@@ -1209,7 +1334,194 @@ fn preset_rules(preset: Preset) -> Vec<TomlRule> {
                 message: "@gorhom/bottom-sheet uses JS animations — use expo-bottom-sheet or react-native-bottom-sheet for native performance".into(),
                 ..Default::default()
             },
+            TomlRule {
+                id: "rn-no-stylesheet-in-render".into(),
+                rule_type: "no-stylesheet-in-render".into(),
+                severity: "warning".into(),
+                glob: Some("**/*.{tsx,jsx}".into()),
+                message: "Styles created inside a component are rebuilt on every render".into(),
+                suggest: Some("Move StyleSheet.create() and static style objects to module scope".into()),
+                ..Default::default()
+            },
         ],
+        Preset::ExpoRouter => vec![TomlRule {
+            id: "expo-router-structure".into(),
+            rule_type: "expo-router-structure".into(),
+            severity: "warning".into(),
+            message: "Expo Router file convention".into(),
+            suggest: Some("Add a _layout.tsx per route directory, name params in camelCase, and branch on Platform inside shared components".into()),
+            ..Default::default()
+        }],
+        Preset::SvelteKit => vec![
+            TomlRule {
+                id: "sveltekit-no-fetch-with-server-load".into(),
+                rule_type: "banned-pattern".into(),
+                severity: "warning".into(),
+                glob: Some("**/+page.svelte".into()),
+                pattern: Some(r"\bfetch\s*\(".into()),
+                regex: true,
+                sibling_files: vec!["+page.server.ts".into(), "+page.server.js".into()],
+                message: "fetch() in +page.svelte duplicates the route's server load — data should come from +page.server".into(),
+                suggest: Some("Return the data from load() in +page.server and read it from the data prop".into()),
+                ..Default::default()
+            },
+            TomlRule {
+                id: "sveltekit-no-private-env-in-client".into(),
+                rule_type: "banned-pattern".into(),
+                severity: "error".into(),
+                glob: Some("src/**/*.{svelte,ts,js}".into()),
+                pattern: Some(r"\$env/(?:static|dynamic)/private".into()),
+                regex: true,
+                exclude_glob: vec![
+                    "**/*.server.*".into(),
+                    "**/+server.*".into(),
+                    "src/lib/server/**".into(),
+                    "src/hooks.server.*".into(),
+                ],
+                message: "Private env vars imported into code that can reach the browser".into(),
+                suggest: Some("Move the import into a .server module or src/lib/server, or use $env/static/public".into()),
+                ..Default::default()
+            },
+            TomlRule {
+                id: "sveltekit-no-process-env".into(),
+                rule_type: "banned-pattern".into(),
+                severity: "warning".into(),
+                glob: Some("src/**/*.{svelte,ts,js}".into()),
+                pattern: Some(r"\bprocess\.env\b".into()),
+                regex: true,
+                message: "process.env bypasses SvelteKit's env modules and is undefined in the browser".into(),
+                suggest: Some("Import from $env/static/private, $env/static/public, or their dynamic variants".into()),
+                ..Default::default()
+            },
+        ],
+        Preset::Remix => vec![
+            TomlRule {
+                id: "remix-no-process-env-client".into(),
+                rule_type: "banned-pattern".into(),
+                severity: "error".into(),
+                glob: Some("app/**/*.{ts,tsx,js,jsx}".into()),
+                pattern: Some(r"\bprocess\.env\b".into()),
+                regex: true,
+                exclude_glob: vec![
+                    "**/*.server.*".into(),
+                    "app/entry.server.*".into(),
+                    "app/routes/**".into(),
+                ],
+                message: "process.env in a module that ships to the client is undefined in the browser and can leak secrets".into(),
+                suggest: Some("Read env in a loader and return what the UI needs, or move the code into a .server module".into()),
+                ..Default::default()
+            },
+            TomlRule {
+                id: "remix-loader-validate-params".into(),
+                rule_type: "window-pattern".into(),
+                severity: "warning".into(),
+                glob: Some("app/routes/**/*.{ts,tsx,js,jsx}".into()),
+                pattern: Some(r"\bparams\.\w+".into()),
+                condition_pattern: Some(r"\binvariant\s*\(|\.(?:safe)?[pP]arse(?:Async)?\s*\(|\bif\s*\(\s*!\s*params\.".into()),
+                max_count: Some(10),
+                regex: true,
+                scope_to_function: true,
+                message: "Route params are used without validation — they are untrusted user input".into(),
+                suggest: Some("Check them first, e.g. invariant(params.id, \"Missing id\") or z.object({ id: z.string() }).parse(params)".into()),
+                ..Default::default()
+            },
+        ],
+        Preset::SupplyChain => vec![
+            TomlRule {
+                id: "docker-pinned-base-image".into(),
+                rule_type: "pinned-references".into(),
+                severity: "error".into(),
+                checks: vec!["docker-base-tag".into()],
+                message: "Unpinned base image — an untagged or latest image changes under you between builds".into(),
+                suggest: Some("Pin a version tag (node:20.11-alpine) or a digest (node@sha256:...)".into()),
+                ..Default::default()
+            },
+            TomlRule {
+                id: "actions-pinned-sha".into(),
+                rule_type: "pinned-references".into(),
+                severity: "error".into(),
+                checks: vec!["action-sha".into()],
+                message: "Action not pinned to a commit SHA — tags and branches can be moved to point at other code".into(),
+                suggest: Some("Pin the full SHA and keep the tag as a comment: uses: actions/checkout@<sha> # v4".into()),
+                ..Default::default()
+            },
+            TomlRule {
+                id: "workflow-permissions".into(),
+                rule_type: "required-pattern".into(),
+                severity: "error".into(),
+                glob: Some(".github/workflows/*.{yml,yaml}".into()),
+                pattern: Some(r"(?m)^permissions:".into()),
+                regex: true,
+                message: "Workflow has no top-level permissions block — GITHUB_TOKEN gets the repository default, often write access".into(),
+                suggest: Some("Add `permissions: { contents: read }` at the top level and widen it per job where needed".into()),
+                ..Default::default()
+            },
+            TomlRule {
+                id: "docker-no-pipe-to-shell".into(),
+                rule_type: "banned-pattern".into(),
+                severity: "warning".into(),
+                glob: Some("**/{Dockerfile,Dockerfile.*,*.dockerfile,Containerfile}".into()),
+                pattern: Some(r"\b(?:curl|wget)\b[^|\n]*\|\s*(?:sudo\s+)?(?:ba|z|da)?sh\b".into()),
+                regex: true,
+                message: "Piping a download into a shell runs whatever the server returns at build time".into(),
+                suggest: Some("Download to a file, verify its checksum, then run it".into()),
+                ..Default::default()
+            },
+            TomlRule {
+                id: "package-script-hygiene".into(),
+                rule_type: "package-scripts".into(),
+                severity: "warning".into(),
+                message: "Risky package.json script".into(),
+                suggest: Some("Guard variables with ${VAR:?} (or use rimraf) and don't pipe downloads into a shell".into()),
+                ..Default::default()
+            },
+        ],
+        Preset::LibraryHygiene => {
+            let glob = "packages/**/*.{ts,tsx,js,jsx,mjs,cjs}";
+            let exclude = || -> Vec<String> {
+                vec![
+                    "**/*.test.*".into(),
+                    "**/*.spec.*".into(),
+                    "**/__tests__/**".into(),
+                    "packages/*/bin/**".into(),
+                ]
+            };
+            vec![
+                TomlRule {
+                    id: "library-no-process-exit".into(),
+                    rule_type: "banned-pattern".into(),
+                    severity: "error".into(),
+                    glob: Some(glob.into()),
+                    pattern: Some(r"\bprocess\.exit\s*\(".into()),
+                    regex: true,
+                    exclude_glob: exclude(),
+                    message: "process.exit() in a library kills the host process — the caller decides when to exit".into(),
+                    suggest: Some("Throw an error or return a result, and exit from the CLI entry point in bin/".into()),
+                    ..Default::default()
+                },
+                TomlRule {
+                    id: "library-no-global-state".into(),
+                    rule_type: "no-global-state".into(),
+                    severity: "warning".into(),
+                    glob: Some("packages/**/*.{ts,tsx,js,jsx}".into()),
+                    exclude_glob: exclude(),
+                    message: "Mutable module-level singleton in a library — every importer shares it, and tests can't reset it".into(),
+                    ..Default::default()
+                },
+                TomlRule {
+                    id: "library-no-console".into(),
+                    rule_type: "banned-pattern".into(),
+                    severity: "warning".into(),
+                    glob: Some(glob.into()),
+                    pattern: Some(r"\bconsole\.(?:log|info|warn|error|debug|trace)\s*\(".into()),
+                    regex: true,
+                    exclude_glob: exclude(),
+                    message: "Library code writes to the console directly — consumers can't silence or redirect it".into(),
+                    suggest: Some("Accept a logger option (e.g. { logger = noopLogger }) and call it instead".into()),
+                    ..Default::default()
+                },
+            ]
+        }
         Preset::React19 => vec![
             TomlRule {
                 id: "no-forwardref".into(),
@@ -1361,8 +1673,9 @@ fn preset_rules(preset: Preset) -> Vec<TomlRule> {
 }
 
 /// Merge preset rules with user-defined rules. User rules with the same `id`
-/// as a preset rule replace the preset version entirely. New user rules are
-/// appended after all preset rules.
+/// as a preset rule replace the preset version entirely, except for a bare
+/// `enabled = false` override (no `type`), which only disables the preset
+/// rule. New user rules are appended after all preset rules.
 fn merge_rules(preset_rules: Vec<TomlRule>, user_rules: &[TomlRule]) -> Vec<TomlRule> {
     let mut merged = preset_rules;
 
@@ -1374,8 +1687,13 @@ fn merge_rules(preset_rules: Vec<TomlRule>, user_rules: &[TomlRule]) -> Vec<Toml
 
     for user_rule in user_rules {
         if let Some(&idx) = id_to_index.get(&user_rule.id) {
-            // User rule overrides preset rule with same id
-            merged[idx] = user_rule.clone();
+            if !user_rule.enabled && user_rule.rule_type.is_empty() {
+                // Two-line override: keep the preset definition, just disable it
+                merged[idx].enabled = false;
+            } else {
+                // User rule overrides preset rule with same id
+                merged[idx] = user_rule.clone();
+            }
         } else {
             // New user rule appended
             merged.push(user_rule.clone());
@@ -1404,8 +1722,10 @@ pub fn resolve_rules(
             name: preset_name.clone(),
             available: available_presets().to_vec(),
         })?;
+        tracing::debug!("extending preset '{}'", preset_name);
 
-        for rule in preset_rules(preset) {
+        for mut rule in preset_rules(preset) {
+            rule.origin = RuleOrigin::Preset(preset_name.clone());
             if let Some(&idx) = seen.get(&rule.id) {
                 // Later preset overrides earlier for same id
                 all_preset_rules[idx] = rule;
@@ -1426,32 +1746,69 @@ fn scope_glob(path: &str, glob: &str) -> String {
     format!("{path}/{stripped}")
 }
 
+/// Globs excluding `exclusion` (a path or glob relative to the scope) from a
+/// scope. A plain path excludes both the file and everything under it.
+fn scope_exclusion(path: &str, exclusion: &str) -> Vec<String> {
+    let exclusion = exclusion.trim_end_matches('/');
+    if exclusion.contains(['*', '?', '[', '{']) {
+        vec![scope_glob(path, exclusion)]
+    } else {
+        vec![format!("{path}/{exclusion}"), format!("{path}/{exclusion}/**")]
+    }
+}
+
+/// Whether `inner` is a directory strictly inside `outer`.
+fn is_nested(inner: &str, outer: &str) -> bool {
+    inner
+        .strip_prefix(outer)
+        .is_some_and(|rest| rest.starts_with('/') && rest.len() > 1)
+}
+
 /// Resolve scoped presets and return rules with globs prefixed to the scoped path.
+///
+/// Each scope's `exclude_paths` are excluded from all of its rules. When
+/// scopes nest, a rule defined by both the outer and the inner scope only
+/// runs from the inner one: the outer scope's copy excludes the inner path.
 pub fn resolve_scoped_rules(
     scoped: &[ScopedPreset],
     user_rules: &[TomlRule],
 ) -> Result<Vec<TomlRule>, PresetError> {
     let mut result: Vec<TomlRule> = Vec::new();
+    // Index into `scoped` of the entry that produced each rule in `result`
+    let mut scope_of: Vec<usize> = Vec::new();
 
-    for entry in scoped {
+    for (scope_idx, entry) in scoped.iter().enumerate() {
+        let scope_path = entry.path.trim_end_matches('/');
         for preset_name in &entry.preset {
         let preset = resolve_preset(preset_name).ok_or_else(|| PresetError::UnknownPreset {
             name: preset_name.clone(),
             available: available_presets().to_vec(),
         })?;
+        tracing::debug!("scoping preset '{}' to {}", preset_name, entry.path);
 
         for mut rule in preset_rules(preset) {
+            rule.origin = RuleOrigin::ScopedPreset {
+                preset: preset_name.clone(),
+                path: entry.path.clone(),
+            };
+
             // Prefix glob
             rule.glob = Some(match rule.glob {
                 Some(g) => scope_glob(&entry.path, &g),
                 None => format!("{}/**", entry.path),
             });
 
-            // Prefix exclude_glob entries
+            // Prefix exclude_glob entries, then add the scope's exclude_paths
             rule.exclude_glob = rule
                 .exclude_glob
                 .iter()
                 .map(|g| scope_glob(&entry.path, g))
+                .chain(
+                    entry
+                        .exclude_paths
+                        .iter()
+                        .flat_map(|p| scope_exclusion(scope_path, p)),
+                )
                 .collect();
 
             // Prefix file-presence paths
@@ -1465,6 +1822,11 @@ pub fn resolve_scoped_rules(
                 .iter()
                 .map(|f| format!("{}/{f}", entry.path))
                 .collect();
+            rule.app_dir = rule.app_dir.as_ref().map(|d| format!("{}/{d}", entry.path));
+            rule.must_contain = std::mem::take(&mut rule.must_contain)
+                .into_iter()
+                .map(|(f, patterns)| (format!("{}/{f}", entry.path), patterns))
+                .collect();
 
             // User rules with the same id override scoped preset rules
             if user_rules.iter().any(|u| u.id == rule.id) {
@@ -1477,13 +1839,272 @@ pub fn resolve_scoped_rules(
             }
 
             result.push(rule);
+            scope_of.push(scope_idx);
         }
         } // for preset_name
     }
 
+    // The most specific scope wins: hand nested paths over to inner scopes
+    let defined: HashSet<(usize, &str)> = scope_of
+        .iter()
+        .zip(&result)
+        .map(|(&i, rule)| (i, rule.id.as_str()))
+        .collect();
+    let mut handed_over: Vec<Vec<String>> = vec![Vec::new(); result.len()];
+    for (idx, rule) in result.iter().enumerate() {
+        let outer = scoped[scope_of[idx]].path.trim_end_matches('/');
+        for (inner_idx, inner) in scoped.iter().enumerate() {
+            let inner_path = inner.path.trim_end_matches('/');
+            if is_nested(inner_path, outer) && defined.contains(&(inner_idx, rule.id.as_str())) {
+                handed_over[idx].push(format!("{inner_path}/**"));
+            }
+        }
+    }
+    for (rule, globs) in result.iter_mut().zip(handed_over) {
+        rule.exclude_glob.extend(globs);
+    }
+
     Ok(result)
 }
 
+/// Two definitions of the same rule id that disagree on what the rule does.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleConflict {
+    pub id: String,
+    pub first: RuleOrigin,
+    pub first_type: String,
+    pub second: RuleOrigin,
+    pub second_type: String,
+    /// Whether `second` replaced `first`, rather than both running.
+    pub replaced: bool,
+}
+
+impl fmt::Display for RuleConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rule '{}' is defined by {} ", self.id, self.first)?;
+        if self.first_type == self.second_type {
+            write!(f, "and {} with different patterns", self.second)?;
+        } else {
+            write!(
+                f,
+                "as {} and by {} as {}",
+                self.first_type, self.second, self.second_type
+            )?;
+        }
+        if self.replaced {
+            write!(f, "; the {} definition wins", self.second)
+        } else {
+            write!(f, "; both definitions run")
+        }
+    }
+}
+
+/// Find rule ids defined more than once with different types or patterns.
+///
+/// A user or plugin rule replacing a preset rule is the intended way to
+/// customize a preset and is not reported. Everything else is: presets in
+/// `extends` overriding each other, scoped presets redefining a global preset
+/// rule, and the config and its plugins defining the same id twice. Identical
+/// definitions (the same preset reached twice) are not conflicts. Unknown
+/// presets are skipped; [`resolve_rules`] reports them.
+pub fn find_conflicts(
+    extends: &[String],
+    scoped: &[ScopedPreset],
+    user_rules: &[TomlRule],
+) -> Vec<RuleConflict> {
+    let mut definitions: Vec<TomlRule> = Vec::new();
+    for preset_name in extends {
+        if let Some(preset) = resolve_preset(preset_name) {
+            definitions.extend(preset_rules(preset).into_iter().map(|rule| TomlRule {
+                origin: RuleOrigin::Preset(preset_name.clone()),
+                ..rule
+            }));
+        }
+    }
+    for entry in scoped {
+        for preset_name in &entry.preset {
+            let Some(preset) = resolve_preset(preset_name) else {
+                continue;
+            };
+            definitions.extend(
+                preset_rules(preset)
+                    .into_iter()
+                    .filter(|rule| {
+                        !entry.exclude_rules.contains(&rule.id)
+                            && !user_rules.iter().any(|u| u.id == rule.id)
+                    })
+                    .map(|rule| TomlRule {
+                        origin: RuleOrigin::ScopedPreset {
+                            preset: preset_name.clone(),
+                            path: entry.path.clone(),
+                        },
+                        ..rule
+                    }),
+            );
+        }
+    }
+    // A bare `enabled = false` only disables; it doesn't define anything
+    definitions.extend(
+        user_rules
+            .iter()
+            .filter(|rule| !rule.rule_type.is_empty())
+            .cloned(),
+    );
+
+    let is_preset = |o: &RuleOrigin| matches!(o, RuleOrigin::Preset(_) | RuleOrigin::ScopedPreset { .. });
+    let mut conflicts: Vec<RuleConflict> = Vec::new();
+    for (i, first) in definitions.iter().enumerate() {
+        for second in &definitions[i + 1..] {
+            if first.id != second.id
+                || is_preset(&first.origin) != is_preset(&second.origin)
+                // Different scopes are disjoint, or nested with the inner one winning
+                || matches!(
+                    (&first.origin, &second.origin),
+                    (RuleOrigin::ScopedPreset { path: a, .. }, RuleOrigin::ScopedPreset { path: b, .. })
+                        if a.trim_end_matches('/') != b.trim_end_matches('/')
+                )
+                // Redefining or disabling the id settles which preset rule applies
+                || (is_preset(&first.origin) && user_rules.iter().any(|u| u.id == first.id))
+                || (first.rule_type == second.rule_type
+                    && first.pattern == second.pattern
+                    && first.regex == second.regex)
+            {
+                continue;
+            }
+            let conflict = RuleConflict {
+                id: first.id.clone(),
+                first: first.origin.clone(),
+                first_type: first.rule_type.clone(),
+                second: second.origin.clone(),
+                second_type: second.rule_type.clone(),
+                // Later `extends` presets replace earlier ones, and so does a
+                // later user rule once a preset rule with the id exists
+                replaced: match (&first.origin, &second.origin) {
+                    (RuleOrigin::Preset(_), RuleOrigin::Preset(_)) => true,
+                    (a, _) if !is_preset(a) => definitions
+                        .iter()
+                        .any(|d| d.id == first.id && matches!(d.origin, RuleOrigin::Preset(_))),
+                    _ => false,
+                },
+            };
+            if !conflicts.contains(&conflict) {
+                conflicts.push(conflict);
+            }
+        }
+    }
+    conflicts
+}
+
+/// A preset's rules as one version of baseline bundles them. `baseline
+/// preset export` writes one; `baseline preset diff` compares them.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PresetSnapshot {
+    #[serde(default)]
+    pub preset: String,
+    /// The baseline version the rules were exported from.
+    #[serde(default)]
+    pub version: String,
+    #[serde(default)]
+    pub rule: Vec<TomlRule>,
+}
+
+impl PresetSnapshot {
+    /// The rules `name` has in this build.
+    pub fn bundled(name: &str) -> Result<Self, PresetError> {
+        let preset = resolve_preset(name).ok_or_else(|| PresetError::UnknownPreset {
+            name: name.to_string(),
+            available: available_presets().to_vec(),
+        })?;
+        Ok(Self {
+            preset: name.to_string(),
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            rule: preset_rules(preset),
+        })
+    }
+
+    /// Read a snapshot written by `baseline preset export`. A config file
+    /// works too; its `[[rule]]` entries are compared.
+    pub fn read(path: &Path) -> Result<Self, PresetError> {
+        let invalid = |reason: String| PresetError::InvalidSnapshot {
+            path: path.to_path_buf(),
+            reason,
+        };
+        let content = std::fs::read_to_string(path).map_err(|e| invalid(e.to_string()))?;
+        toml::from_str(&content).map_err(|e| invalid(e.to_string()))
+    }
+
+    /// Resolve a `baseline preset diff` operand: `NAME` or `NAME@current` is
+    /// the bundled preset, `NAME@PATH` or an existing `PATH` a snapshot file.
+    pub fn from_spec(spec: &str) -> Result<Self, PresetError> {
+        match spec.split_once('@') {
+            Some((name, "current")) => Self::bundled(name),
+            Some((name, path)) => {
+                let mut snapshot = Self::read(Path::new(path))?;
+                if snapshot.preset.is_empty() {
+                    snapshot.preset = name.to_string();
+                }
+                Ok(snapshot)
+            }
+            None if Path::new(spec).is_file() => Self::read(Path::new(spec)),
+            None => Self::bundled(spec),
+        }
+    }
+}
+
+/// How one rule id differs between two sets of preset rules.
+#[derive(Debug, Clone)]
+pub enum RuleChange {
+    Added(TomlRule),
+    Removed(TomlRule),
+    /// Fields whose values differ, as `(field, old, new)`; `None` is unset.
+    Changed {
+        id: String,
+        fields: Vec<(String, Option<serde_json::Value>, Option<serde_json::Value>)>,
+    },
+}
+
+/// Rule-level differences from `old` to `new`: changed and removed rules in
+/// `old`'s order, then added rules in `new`'s.
+pub fn diff_rules(old: &[TomlRule], new: &[TomlRule]) -> Vec<RuleChange> {
+    // Unset options serialize as null; leave them out so they show as unset
+    let fields = |rule: &TomlRule| match serde_json::to_value(rule) {
+        Ok(serde_json::Value::Object(mut map)) => {
+            map.retain(|_, v| !v.is_null());
+            map
+        }
+        _ => serde_json::Map::new(),
+    };
+
+    let mut changes = Vec::new();
+    for old_rule in old {
+        let Some(new_rule) = new.iter().find(|r| r.id == old_rule.id) else {
+            changes.push(RuleChange::Removed(old_rule.clone()));
+            continue;
+        };
+        let (before, after) = (fields(old_rule), fields(new_rule));
+        let mut names: Vec<&String> = before.keys().chain(after.keys()).collect();
+        names.sort();
+        names.dedup();
+        let changed: Vec<_> = names
+            .into_iter()
+            .filter(|name| before.get(*name) != after.get(*name))
+            .map(|name| (name.clone(), before.get(name).cloned(), after.get(name).cloned()))
+            .collect();
+        if !changed.is_empty() {
+            changes.push(RuleChange::Changed {
+                id: old_rule.id.clone(),
+                fields: changed,
+            });
+        }
+    }
+    for new_rule in new {
+        if !old.iter().any(|r| r.id == new_rule.id) {
+            changes.push(RuleChange::Added(new_rule.clone()));
+        }
+    }
+    changes
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1581,6 +2202,139 @@ mod tests {
         assert_eq!(result[5].id, "my-custom");
     }
 
+    #[test]
+    fn conflicts_between_presets_are_reported() {
+        let extends = vec!["dependency-hygiene".to_string(), "react-opinions".to_string()];
+        let conflicts = find_conflicts(&extends, &[], &[]);
+        assert_eq!(conflicts.len(), 1);
+        let conflict = &conflicts[0];
+        assert_eq!(conflict.id, "no-moment");
+        assert_eq!(conflict.first, RuleOrigin::Preset("dependency-hygiene".into()));
+        assert_eq!(conflict.second, RuleOrigin::Preset("react-opinions".into()));
+        assert!(conflict.replaced);
+        assert_eq!(
+            conflict.to_string(),
+            "rule 'no-moment' is defined by preset dependency-hygiene as banned-dependency \
+             and by preset react-opinions as banned-import; the preset react-opinions definition wins"
+        );
+
+        // The same preset reached globally and scoped is identical, not a conflict
+        let scoped = vec![ScopedPreset {
+            preset: vec!["nextjs".into()],
+            path: "apps/web".into(),
+            exclude_rules: vec![],
+            exclude_paths: vec![],
+        }];
+        assert!(find_conflicts(&["nextjs".to_string()], &scoped, &[]).is_empty());
+
+        let disabled = vec![TomlRule {
+            id: "no-moment".into(),
+            enabled: false,
+            ..Default::default()
+        }];
+        assert!(find_conflicts(&extends, &[], &disabled).is_empty());
+    }
+
+    #[test]
+    fn conflicts_ignore_intended_overrides() {
+        let user_rules = vec![
+            TomlRule {
+                id: "use-theme-tokens".into(),
+                rule_type: "banned-pattern".into(),
+                pattern: Some("bg-white".into()),
+                ..Default::default()
+            },
+            TomlRule {
+                id: "enforce-dark-mode".into(),
+                enabled: false,
+                ..Default::default()
+            },
+        ];
+        assert!(find_conflicts(&["shadcn-strict".to_string()], &[], &user_rules).is_empty());
+    }
+
+    #[test]
+    fn conflicts_between_user_and_plugin_rules() {
+        let rule = |pattern: &str, origin: RuleOrigin| TomlRule {
+            id: "no-debug".into(),
+            rule_type: "banned-pattern".into(),
+            pattern: Some(pattern.into()),
+            origin,
+            ..Default::default()
+        };
+        let plugin = RuleOrigin::Plugin("team.toml".into());
+        let user_rules = vec![
+            rule("debugger", RuleOrigin::User),
+            rule("console.debug", plugin.clone()),
+        ];
+        let conflicts = find_conflicts(&[], &[], &user_rules);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].second, plugin);
+        assert!(!conflicts[0].replaced);
+        assert!(conflicts[0].to_string().ends_with(
+            "defined by user config and plugin team.toml with different patterns; both definitions run"
+        ));
+
+        let same = vec![rule("debugger", RuleOrigin::User), rule("debugger", plugin)];
+        assert!(find_conflicts(&[], &[], &same).is_empty());
+    }
+
+    #[test]
+    fn resolved_rules_record_their_origin() {
+        let user_rules = vec![
+            TomlRule {
+                id: "use-theme-tokens".into(),
+                rule_type: "tailwind-theme-tokens".into(),
+                ..Default::default()
+            },
+            TomlRule {
+                id: "enforce-dark-mode".into(),
+                enabled: false,
+                ..Default::default()
+            },
+        ];
+        let result = resolve_rules(&["shadcn-strict".to_string()], &user_rules).unwrap();
+        let origin = |id: &str| &result.iter().find(|r| r.id == id).unwrap().origin;
+        assert_eq!(origin("use-theme-tokens"), &RuleOrigin::User);
+        assert_eq!(
+            origin("enforce-dark-mode"),
+            &RuleOrigin::Preset("shadcn-strict".into()),
+            "a bare disable keeps the preset definition"
+        );
+
+        let scoped = vec![ScopedPreset {
+            preset: vec!["nextjs".into()],
+            path: "apps/web".into(),
+            exclude_rules: vec![],
+            exclude_paths: vec![],
+        }];
+        let rules = resolve_scoped_rules(&scoped, &[]).unwrap();
+        assert!(rules.iter().all(|r| r.origin
+            == RuleOrigin::ScopedPreset {
+                preset: "nextjs".into(),
+                path: "apps/web".into(),
+            }));
+        assert_eq!(
+            rules[0].origin.to_string(),
+            "preset nextjs scoped to apps/web"
+        );
+    }
+
+    #[test]
+    fn disabled_override_keeps_preset_definition() {
+        let user_rules: Vec<TomlRule> = toml::from_str::<crate::cli::toml_config::TomlConfig>(
+            "[baseline]\n[[rule]]\nid = \"use-theme-tokens\"\nenabled = false\n",
+        )
+        .unwrap()
+        .rule;
+        let result = resolve_rules(&["shadcn-strict".to_string()], &user_rules).unwrap();
+        assert_eq!(result.len(), 5);
+        let token_rule = result.iter().find(|r| r.id == "use-theme-tokens").unwrap();
+        assert!(!token_rule.enabled);
+        assert_eq!(token_rule.rule_type, "tailwind-theme-tokens");
+        assert!(result.iter().filter(|r| r.id != "use-theme-tokens").all(|r| r.enabled));
+    }
+
     #[test]
     fn later_preset_overrides_earlier() {
         // shadcn-strict sets use-theme-tokens severity to "error"
@@ -1608,9 +2362,9 @@ mod tests {
     }
 
     #[test]
-    fn security_has_eleven_rules() {
+    fn security_has_twelve_rules() {
         let rules = preset_rules(Preset::Security);
-        assert_eq!(rules.len(), 11);
+        assert_eq!(rules.len(), 12);
         let ids: Vec<&str> = rules.iter().map(|r| r.id.as_str()).collect();
         assert!(ids.contains(&"no-env-files"));
         assert!(ids.contains(&"no-hardcoded-secrets"));
@@ -1623,6 +2377,7 @@ mod tests {
         assert!(ids.contains(&"no-outerhtml"));
         assert!(ids.contains(&"no-http-links"));
         assert!(ids.contains(&"no-paste-prevention"));
+        assert!(ids.contains(&"no-sql-interpolation"));
     }
 
     #[test]
@@ -1641,14 +2396,15 @@ mod tests {
     }
 
     #[test]
-    fn ai_codegen_has_twelve_rules() {
+    fn ai_codegen_has_thirteen_rules() {
         let rules = preset_rules(Preset::AiCodegen);
-        assert_eq!(rules.len(), 12);
+        assert_eq!(rules.len(), 13);
         let ids: Vec<&str> = rules.iter().map(|r| r.id.as_str()).collect();
         assert!(ids.contains(&"no-placeholder-text"));
         assert!(ids.contains(&"no-unresolved-todos"));
         assert!(ids.contains(&"no-type-any"));
         assert!(ids.contains(&"no-empty-catch"));
+        assert!(ids.contains(&"no-silent-promise-catch"));
         assert!(ids.contains(&"no-console-log"));
         assert!(ids.contains(&"no-ts-ignore"));
         assert!(ids.contains(&"no-as-any"));
@@ -1717,7 +2473,7 @@ mod tests {
     #[test]
     fn nextjs_best_practices_has_expected_rule_count() {
         let rules = preset_rules(Preset::NextjsBestPractices);
-        assert_eq!(rules.len(), 21);
+        assert_eq!(rules.len(), 23);
         let ids: Vec<&str> = rules.iter().map(|r| r.id.as_str()).collect();
         assert!(ids.contains(&"use-next-image"));
         assert!(ids.contains(&"next-image-fill-needs-sizes"));
@@ -1731,10 +2487,12 @@ mod tests {
         assert!(ids.contains(&"no-private-env-client"));
         assert!(ids.contains(&"require-use-client-for-hooks"));
         assert!(ids.contains(&"no-async-client-component"));
+        assert!(ids.contains(&"require-server-only"));
         assert!(ids.contains(&"require-metadata-in-pages"));
         assert!(ids.contains(&"no-redirect-in-try-catch"));
         assert!(ids.contains(&"server-action-requires-auth"));
         assert!(ids.contains(&"server-action-requires-validation"));
+        assert!(ids.contains(&"route-handler-requires-validation"));
         assert!(ids.contains(&"no-suppress-hydration-warning"));
         assert!(ids.contains(&"max-component-size"));
         assert!(ids.contains(&"no-nested-components"));
@@ -1745,7 +2503,7 @@ mod tests {
     #[test]
     fn accessibility_has_expected_rule_count() {
         let rules = preset_rules(Preset::Accessibility);
-        assert_eq!(rules.len(), 9);
+        assert_eq!(rules.len(), 12);
         let ids: Vec<&str> = rules.iter().map(|r| r.id.as_str()).collect();
         assert!(ids.contains(&"no-div-click-handler"));
         assert!(ids.contains(&"no-span-click-handler"));
@@ -1756,12 +2514,22 @@ mod tests {
         assert!(ids.contains(&"no-hardcoded-date-format"));
         assert!(ids.contains(&"no-inline-navigation-onclick"));
         assert!(ids.contains(&"require-img-alt"));
+        assert!(ids.contains(&"require-icon-button-label"));
+        assert!(ids.contains(&"heading-hierarchy"));
+        assert!(ids.contains(&"require-input-label"));
+
+        // Rules added to an existing preset start as warnings, so upgrading
+        // doesn't fail CI for projects that extend it
+        for id in ["require-icon-button-label", "heading-hierarchy", "require-input-label"] {
+            let rule = rules.iter().find(|r| r.id == id).unwrap();
+            assert_eq!(rule.severity, "warning", "{}", id);
+        }
     }
 
     #[test]
-    fn react_native_has_thirteen_rules() {
+    fn react_native_has_fourteen_rules() {
         let rules = preset_rules(Preset::ReactNative);
-        assert_eq!(rules.len(), 13);
+        assert_eq!(rules.len(), 14);
         let ids: Vec<&str> = rules.iter().map(|r| r.id.as_str()).collect();
         assert!(ids.contains(&"rn-no-touchable-opacity"));
         assert!(ids.contains(&"rn-no-touchable-highlight"));
@@ -1776,6 +2544,65 @@ mod tests {
         assert!(ids.contains(&"rn-no-js-bottom-tabs"));
         assert!(ids.contains(&"rn-no-linear-gradient-lib"));
         assert!(ids.contains(&"rn-no-js-bottom-sheet"));
+        assert!(ids.contains(&"rn-no-stylesheet-in-render"));
+    }
+
+    #[test]
+    fn expo_router_preset_checks_structure() {
+        let rules = preset_rules(Preset::ExpoRouter);
+        assert_eq!(rules.len(), 1);
+        assert_eq!(rules[0].rule_type, "expo-router-structure");
+        assert!(crate::rules::factory::build_rule("expo-router-structure", &rules[0].to_rule_config()).is_ok());
+    }
+
+    #[test]
+    fn sveltekit_fetch_rule_requires_server_load() {
+        let rules = preset_rules(Preset::SvelteKit);
+        assert_eq!(rules.len(), 3);
+        let fetch = rules.iter().find(|r| r.id == "sveltekit-no-fetch-with-server-load").unwrap();
+        assert_eq!(fetch.sibling_files, ["+page.server.ts", "+page.server.js"]);
+        let re = regex_for(Preset::SvelteKit, "sveltekit-no-private-env-in-client");
+        assert!(re.is_match("import { SECRET } from '$env/static/private';"));
+        assert!(!re.is_match("import { PUBLIC_URL } from '$env/static/public';"));
+    }
+
+    #[test]
+    fn remix_rules_match() {
+        let rules = preset_rules(Preset::Remix);
+        assert_eq!(rules.len(), 2);
+        let validate = rules.iter().find(|r| r.id == "remix-loader-validate-params").unwrap();
+        let condition = regex::Regex::new(validate.condition_pattern.as_deref().unwrap()).unwrap();
+        assert!(condition.is_match(r#"invariant(params.noteId, "noteId not found");"#));
+        assert!(condition.is_match("const { id } = ParamsSchema.parse(params);"));
+        assert!(condition.is_match("if (!params.slug) throw new Response(null, { status: 404 });"));
+        assert!(!condition.is_match("return json(await getNote(params.noteId));"));
+    }
+
+    #[test]
+    fn supply_chain_checks_dockerfiles_and_workflows() {
+        let rules = preset_rules(Preset::SupplyChain);
+        assert_eq!(rules.len(), 5);
+        let permissions = regex_for(Preset::SupplyChain, "workflow-permissions");
+        assert!(permissions.is_match("name: CI\npermissions:\n  contents: read\n"));
+        assert!(!permissions.is_match("jobs:\n  build:\n    permissions:\n      contents: read\n"));
+        let pipe = regex_for(Preset::SupplyChain, "docker-no-pipe-to-shell");
+        assert!(pipe.is_match("RUN curl -fsSL https://deb.nodesource.com/setup_20.x | bash -"));
+        assert!(!pipe.is_match("RUN curl -fsSLo node.tar.gz https://nodejs.org/dist/node.tar.gz"));
+    }
+
+    #[test]
+    fn library_hygiene_targets_packages() {
+        let rules = preset_rules(Preset::LibraryHygiene);
+        assert_eq!(rules.len(), 3);
+        assert!(rules.iter().all(|r| r.glob.as_deref().is_some_and(|g| g.starts_with("packages/**"))));
+        let global = rules.iter().find(|r| r.id == "library-no-global-state").unwrap();
+        assert!(crate::rules::factory::build_rule(&global.rule_type, &global.to_rule_config()).is_ok());
+        let exit = regex_for(Preset::LibraryHygiene, "library-no-process-exit");
+        assert!(exit.is_match("if (failed) process.exit(1);"));
+        assert!(!exit.is_match("process.exitCode = 1;"));
+        let console = regex_for(Preset::LibraryHygiene, "library-no-console");
+        assert!(console.is_match("console.warn('deprecated');"));
+        assert!(!console.is_match("logger.warn('deprecated');"));
     }
 
     #[test]
@@ -1958,6 +2785,19 @@ mod tests {
         assert!(!re.is_match("const isVariable = true"));
     }
 
+    #[test]
+    fn no_silent_promise_catch_pattern() {
+        let re = regex_for(Preset::AiCodegen, "no-silent-promise-catch");
+        assert!(re.is_match("load().catch(() => {})"));
+        assert!(re.is_match("load().catch( (err) => { } )"));
+        assert!(re.is_match("load().catch(e => {})"));
+        assert!(re.is_match("load().catch(console.log)"));
+        // handlers with a body, and try/catch, are left alone
+        assert!(!re.is_match("load().catch((e) => { report(e); })"));
+        assert!(!re.is_match("load().catch(handleError)"));
+        assert!(!re.is_match("try { run(); } catch (e) {}"));
+    }
+
     #[test]
     fn no_require_in_ts_pattern() {
         let re = regex_for(Preset::AiCodegen, "no-require-in-ts");
@@ -2402,6 +3242,7 @@ mod tests {
             preset: vec!["nextjs".into()],
             path: "apps/web".into(),
             exclude_rules: vec![],
+            exclude_paths: vec![],
         }];
         let rules = resolve_scoped_rules(&scoped, &[]).unwrap();
         assert!(!rules.is_empty());
@@ -2421,6 +3262,7 @@ mod tests {
             preset: vec!["ai-safety".into()],
             path: "packages/core".into(),
             exclude_rules: vec![],
+            exclude_paths: vec![],
         }];
         let rules = resolve_scoped_rules(&scoped, &[]).unwrap();
         // banned-dependency rules have no glob by default — should get scoped catch-all
@@ -2439,6 +3281,7 @@ mod tests {
             preset: vec!["nextjs".into()],
             path: "apps/web".into(),
             exclude_rules: vec![],
+            exclude_paths: vec![],
         }];
         let user_rules = vec![TomlRule {
             id: "use-next-image".into(),
@@ -2463,6 +3306,7 @@ mod tests {
                 preset: vec!["nextjs".into()],
                 path: "apps/web".into(),
                 exclude_rules: vec![],
+            exclude_paths: vec![],
             }],
             &[],
         )
@@ -2483,6 +3327,7 @@ mod tests {
             preset: vec!["nonexistent".into()],
             path: "apps/web".into(),
             exclude_rules: vec![],
+            exclude_paths: vec![],
         }];
         let result = resolve_scoped_rules(&scoped, &[]);
         assert!(result.is_err());
@@ -2497,6 +3342,7 @@ mod tests {
             preset: vec!["security".into()],
             path: "apps/api".into(),
             exclude_rules: vec![],
+            exclude_paths: vec![],
         }];
         let rules = resolve_scoped_rules(&scoped, &[]).unwrap();
         let fp_rule = rules.iter().find(|r| r.id == "no-env-files").unwrap();
@@ -2515,6 +3361,7 @@ mod tests {
             preset: vec!["security".into()],
             path: "apps/api".into(),
             exclude_rules: vec![],
+            exclude_paths: vec![],
         }];
         let rules = resolve_scoped_rules(&scoped, &[]).unwrap();
         let console_rule = rules.iter().find(|r| r.id == "no-console-log").unwrap();
@@ -2526,12 +3373,54 @@ mod tests {
         }
     }
 
+    #[test]
+    fn resolve_scoped_exclude_paths_and_nested_scopes() {
+        let scoped = vec![
+            ScopedPreset {
+                preset: vec!["nextjs".into()],
+                path: "apps/web/".into(),
+                exclude_rules: vec![],
+                exclude_paths: vec!["legacy".into(), "**/*.stories.tsx".into()],
+            },
+            ScopedPreset {
+                preset: vec!["nextjs".into()],
+                path: "apps/web/admin".into(),
+                exclude_rules: vec!["no-next-head".into()],
+                exclude_paths: vec![],
+            },
+        ];
+        let rules = resolve_scoped_rules(&scoped, &[]).unwrap();
+        let outer = |id: &str| {
+            rules
+                .iter()
+                .find(|r| r.id == id && r.glob.as_deref().is_some_and(|g| !g.contains("admin")))
+                .unwrap()
+        };
+
+        let image = outer("use-next-image");
+        assert!(image.exclude_glob.contains(&"apps/web/legacy".to_string()));
+        assert!(image.exclude_glob.contains(&"apps/web/legacy/**".to_string()));
+        assert!(image.exclude_glob.contains(&"apps/web/*.stories.tsx".to_string()));
+        assert!(image.exclude_glob.contains(&"apps/web/admin/**".to_string()));
+
+        // The inner scope left this rule out, so the outer scope keeps it
+        assert!(!outer("no-next-head").exclude_glob.contains(&"apps/web/admin/**".to_string()));
+        assert!(rules
+            .iter()
+            .filter(|r| r.glob.as_deref().is_some_and(|g| g.starts_with("apps/web/admin/")))
+            .all(|r| r.exclude_glob.iter().all(|g| !g.contains("legacy"))));
+
+        assert!(find_conflicts(&[], &scoped, &[]).is_empty());
+        assert!(!is_nested("apps/website", "apps/web"));
+    }
+
     #[test]
     fn resolve_scoped_exclude_rules_skips_listed() {
         let scoped = vec![ScopedPreset {
             preset: vec!["nextjs".into()],
             path: "apps/web".into(),
             exclude_rules: vec!["use-next-image".into()],
+            exclude_paths: vec![],
         }];
         let rules = resolve_scoped_rules(&scoped, &[]).unwrap();
         assert!(
@@ -2551,11 +3440,13 @@ mod tests {
             preset: vec!["nextjs".into()],
             path: "apps/web".into(),
             exclude_rules: vec![],
+            exclude_paths: vec![],
         }];
         let scoped_none = vec![ScopedPreset {
             preset: vec!["nextjs".into()],
             path: "apps/web".into(),
             exclude_rules: vec![],
+            exclude_paths: vec![],
         }];
         let rules_empty = resolve_scoped_rules(&scoped_empty, &[]).unwrap();
         let rules_none = resolve_scoped_rules(&scoped_none, &[]).unwrap();
@@ -2570,6 +3461,7 @@ mod tests {
             preset: vec!["nextjs".into(), "security".into()],
             path: "apps/web".into(),
             exclude_rules: vec![],
+            exclude_paths: vec![],
         }];
         let rules = resolve_scoped_rules(&scoped, &[]).unwrap();
         // Should contain rules from both presets
@@ -2584,4 +3476,49 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn preset_snapshot_round_trips_through_toml() {
+        let snapshot = PresetSnapshot::bundled("react").unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("react.toml");
+        std::fs::write(&path, toml::to_string_pretty(&snapshot).unwrap()).unwrap();
+
+        let read = PresetSnapshot::from_spec(&format!("react@{}", path.display())).unwrap();
+        assert_eq!(read.preset, "react");
+        assert!(diff_rules(&snapshot.rule, &read.rule).is_empty());
+        assert!(matches!(
+            PresetSnapshot::from_spec("reakt@current"),
+            Err(PresetError::UnknownPreset { .. })
+        ));
+    }
+
+    #[test]
+    fn diff_rules_reports_added_removed_and_changed() {
+        let old = PresetSnapshot::bundled("react").unwrap().rule;
+        let mut new = old.clone();
+        let removed = new.remove(0);
+        new[0].severity = "hint".into();
+        new[0].glob = None;
+        new.push(TomlRule {
+            id: "no-new-thing".into(),
+            rule_type: "banned-pattern".into(),
+            ..Default::default()
+        });
+
+        let changes = diff_rules(&old, &new);
+        assert_eq!(changes.len(), 3);
+        assert!(matches!(&changes[0], RuleChange::Removed(r) if r.id == removed.id));
+        match &changes[1] {
+            RuleChange::Changed { id, fields } => {
+                assert_eq!(id, &old[1].id);
+                let names: Vec<&str> = fields.iter().map(|(name, _, _)| name.as_str()).collect();
+                assert_eq!(names, vec!["glob", "severity"]);
+                assert_eq!(fields[0].2, None);
+                assert_eq!(fields[1].2, Some(serde_json::json!("hint")));
+            }
+            other => panic!("expected a change, got {:?}", other),
+        }
+        assert!(matches!(&changes[2], RuleChange::Added(r) if r.id == "no-new-thing"));
+    }
 }
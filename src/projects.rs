@@ -0,0 +1,266 @@
+use crate::config::Severity;
+use crate::scan::ScanResult;
+use ignore::WalkBuilder;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Name used for files that don't belong to any detected package.
+pub const ROOT_PROJECT_NAME: &str = "(root)";
+
+/// A workspace package, identified by a directory containing `package.json`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Project {
+    pub name: String,
+    pub root: PathBuf,
+}
+
+/// The slice of a scan result that falls inside one project.
+pub struct ProjectResult {
+    pub project: Project,
+    pub result: ScanResult,
+}
+
+impl ProjectResult {
+    pub fn errors(&self) -> usize {
+        self.result
+            .violations
+            .iter()
+            .filter(|v| v.severity == Severity::Error)
+            .count()
+    }
+
+    pub fn warnings(&self) -> usize {
+        self.result
+            .violations
+            .iter()
+            .filter(|v| v.severity == Severity::Warning)
+            .count()
+    }
+
+    /// Exit code this project would produce if scanned on its own.
+    pub fn exit_code(&self) -> i32 {
+        self.result.exit_code()
+    }
+}
+
+/// Find workspace packages under the target paths. Each directory holding a
+/// `package.json` (outside `node_modules`) is a project; its name is the
+/// manifest's `name` field, falling back to the directory name.
+pub fn detect_projects(target_paths: &[PathBuf]) -> Vec<Project> {
+    let mut projects = Vec::new();
+
+    for target in target_paths {
+        let walker = WalkBuilder::new(target)
+            .hidden(true)
+            .git_ignore(true)
+            .filter_entry(|e| e.file_name() != "node_modules")
+            .build();
+
+        for entry in walker.flatten() {
+            if entry.file_name() != "package.json" || !entry.path().is_file() {
+                continue;
+            }
+            let root = entry.path().parent().unwrap_or(Path::new("")).to_path_buf();
+            projects.push(Project {
+                name: manifest_name(entry.path()).unwrap_or_else(|| dir_name(&root)),
+                root,
+            });
+        }
+    }
+
+    projects.sort_by(|a, b| a.root.cmp(&b.root));
+    projects.dedup_by(|a, b| a.root == b.root);
+    projects
+}
+
+fn manifest_name(manifest: &Path) -> Option<String> {
+    let text = fs::read_to_string(manifest).ok()?;
+    let json: serde_json::Value = serde_json::from_str(&text).ok()?;
+    json.get("name")?.as_str().map(str::to_string)
+}
+
+fn dir_name(root: &Path) -> String {
+    root.file_name()
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_else(|| ROOT_PROJECT_NAME.to_string())
+}
+
+/// Index of the innermost project containing `file`, if any.
+fn owning_project(projects: &[Project], file: &Path) -> Option<usize> {
+    projects
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| file.starts_with(&p.root))
+        .max_by_key(|(_, p)| p.root.components().count())
+        .map(|(i, _)| i)
+}
+
+/// Split a scan result into per-project results. Files outside every project
+/// are grouped under a synthetic `(root)` project, which is only emitted when
+/// it has scanned files or violations. Ratchet budgets are repo-wide, so
+/// `ratchet_counts` stay on the aggregate result only.
+pub fn partition(result: &ScanResult, projects: &[Project]) -> Vec<ProjectResult> {
+    let mut buckets: Vec<ProjectResult> = projects
+        .iter()
+        .chain(std::iter::once(&Project {
+            name: ROOT_PROJECT_NAME.to_string(),
+            root: PathBuf::new(),
+        }))
+        .map(|project| ProjectResult {
+            project: project.clone(),
+            result: ScanResult {
+                violations: Vec::new(),
+                files_scanned: 0,
+                generated_skipped: 0,
+                rules_loaded: result.rules_loaded,
+                unmatched_globs: Vec::new(),
+                groups: result.groups.clone(),
+                rules: result.rules.clone(),
+                ratchet_counts: HashMap::new(),
+                ratchet_deadlines: HashMap::new(),
+                changed_files_count: None,
+                base_ref: result.base_ref.clone(),
+                timings: Default::default(),
+                scanned_files: Vec::new(),
+                file_hashes: HashMap::new(),
+                fixes: None,
+                output: result.output.clone(),
+                hooks: result.hooks.clone(),
+                stopped_early: false,
+            },
+        })
+        .collect();
+    let root_idx = projects.len();
+
+    for file in &result.scanned_files {
+        let idx = owning_project(projects, file).unwrap_or(root_idx);
+        buckets[idx].result.scanned_files.push(file.clone());
+    }
+    for v in &result.violations {
+        let idx = owning_project(projects, &v.file).unwrap_or(root_idx);
+        buckets[idx].result.violations.push(v.clone());
+    }
+    for bucket in &mut buckets {
+        bucket.result.files_scanned = bucket.result.scanned_files.len();
+    }
+
+    let root = buckets.pop().unwrap();
+    if root.result.files_scanned > 0 || !root.result.violations.is_empty() {
+        buckets.push(root);
+    }
+    buckets
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rules::Violation;
+
+    fn violation(file: &str, severity: Severity) -> Violation {
+        Violation {
+            rule_id: "r".into(),
+            severity,
+            file: PathBuf::from(file),
+            line: Some(1),
+            column: None,
+            message: "m".into(),
+            suggest: Vec::new(),
+            source_line: None,
+            fix: None,
+            origin: None,
+            enclosing: None,
+        }
+    }
+
+    fn result(files: &[&str], violations: Vec<Violation>) -> ScanResult {
+        ScanResult {
+            violations,
+            files_scanned: files.len(),
+            generated_skipped: 0,
+            rules_loaded: 1,
+            unmatched_globs: Vec::new(),
+            groups: Vec::new(),
+            rules: Vec::new(),
+            ratchet_counts: HashMap::new(),
+            ratchet_deadlines: HashMap::new(),
+            changed_files_count: None,
+            base_ref: None,
+            timings: Default::default(),
+            scanned_files: files.iter().map(PathBuf::from).collect(),
+            file_hashes: HashMap::new(),
+            fixes: None,
+            output: Default::default(),
+            hooks: Default::default(),
+            stopped_early: false,
+        }
+    }
+
+    #[test]
+    fn detect_projects_reads_names_and_skips_node_modules() {
+        let dir = tempfile::tempdir().unwrap();
+        let web = dir.path().join("packages/web");
+        let api = dir.path().join("packages/api");
+        let dep = dir.path().join("node_modules/left-pad");
+        for d in [&web, &api, &dep] {
+            fs::create_dir_all(d).unwrap();
+        }
+        fs::write(web.join("package.json"), r#"{ "name": "@acme/web" }"#).unwrap();
+        fs::write(api.join("package.json"), "{}").unwrap();
+        fs::write(dep.join("package.json"), r#"{ "name": "left-pad" }"#).unwrap();
+
+        let projects = detect_projects(&[dir.path().to_path_buf()]);
+        assert_eq!(projects.len(), 2);
+        assert_eq!(projects[0].name, "api");
+        assert_eq!(projects[0].root, api);
+        assert_eq!(projects[1].name, "@acme/web");
+    }
+
+    #[test]
+    fn partition_assigns_innermost_project() {
+        let projects = vec![
+            Project { name: "monorepo".into(), root: PathBuf::from("repo") },
+            Project { name: "web".into(), root: PathBuf::from("repo/packages/web") },
+        ];
+        let scan = result(
+            &["repo/packages/web/a.ts", "repo/packages/web/b.ts", "repo/scripts/c.ts"],
+            vec![
+                violation("repo/packages/web/a.ts", Severity::Error),
+                violation("repo/scripts/c.ts", Severity::Warning),
+            ],
+        );
+
+        let parts = partition(&scan, &projects);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].project.name, "monorepo");
+        assert_eq!(parts[0].result.files_scanned, 1);
+        assert_eq!(parts[0].warnings(), 1);
+        assert_eq!(parts[0].exit_code(), 0);
+        assert_eq!(parts[1].project.name, "web");
+        assert_eq!(parts[1].result.files_scanned, 2);
+        assert_eq!(parts[1].errors(), 1);
+        assert_eq!(parts[1].exit_code(), 1);
+    }
+
+    #[test]
+    fn partition_collects_unowned_files_under_root() {
+        let projects = vec![Project { name: "web".into(), root: PathBuf::from("packages/web") }];
+        let scan = result(&["tools/x.ts"], vec![violation("tools/x.ts", Severity::Error)]);
+
+        let parts = partition(&scan, &projects);
+        assert_eq!(parts.len(), 2);
+        assert_eq!(parts[0].result.files_scanned, 0);
+        assert_eq!(parts[1].project.name, ROOT_PROJECT_NAME);
+        assert_eq!(parts[1].errors(), 1);
+    }
+
+    #[test]
+    fn partition_omits_empty_root() {
+        let projects = vec![Project { name: "web".into(), root: PathBuf::from("web") }];
+        let scan = result(&["web/a.ts"], vec![]);
+
+        let parts = partition(&scan, &projects);
+        assert_eq!(parts.len(), 1);
+        assert_eq!(parts[0].project.name, "web");
+    }
+}
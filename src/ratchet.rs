@@ -1,15 +1,19 @@
 use crate::cli::toml_config::{TomlConfig, TomlRule};
+use crate::config_edit::{set_value, ConfigDocument, ConfigEditError};
 use crate::rules::factory;
-use crate::rules::ScanContext;
+use crate::rules::{ScanContext, Violation};
 use crate::scan::{self, BaselineResult};
 use std::fmt;
 use std::fs;
+use std::io::{BufRead, Write};
 use std::path::{Path, PathBuf};
+use toml_edit::{value, Table};
 
 #[derive(Debug)]
 pub enum RatchetError {
     ConfigRead(std::io::Error),
     ConfigParse(toml::de::Error),
+    ConfigEdit(ConfigEditError),
     Scan(scan::ScanError),
     RuleNotFound(String),
     RuleAlreadyExists(String),
@@ -27,6 +31,7 @@ impl fmt::Display for RatchetError {
         match self {
             RatchetError::ConfigRead(e) => write!(f, "failed to read config: {}", e),
             RatchetError::ConfigParse(e) => write!(f, "failed to parse config: {}", e),
+            RatchetError::ConfigEdit(e) => write!(f, "{}", e),
             RatchetError::Scan(e) => write!(f, "scan failed: {}", e),
             RatchetError::RuleNotFound(id) => {
                 write!(f, "no ratchet rule found with id '{}'", id)
@@ -82,12 +87,23 @@ fn count_pattern(
     regex: bool,
     paths: &[PathBuf],
 ) -> Result<usize, RatchetError> {
+    Ok(find_matches(config_path, pattern, glob, regex, paths)?.len())
+}
+
+/// Find every occurrence of a pattern across files, in walk order.
+fn find_matches(
+    config_path: &Path,
+    pattern: &str,
+    glob: &str,
+    regex: bool,
+    paths: &[PathBuf],
+) -> Result<Vec<Violation>, RatchetError> {
     // Read config to get exclude patterns
     let config_text = fs::read_to_string(config_path).map_err(RatchetError::ConfigRead)?;
     let toml_config: TomlConfig =
         toml::from_str(&config_text).map_err(RatchetError::ConfigParse)?;
 
-    let exclude_set = scan::build_glob_set(&toml_config.baseline.exclude)
+    let exclude_set = scan::build_glob_set(&toml_config.baseline.excludes())
         .map_err(RatchetError::Scan)?;
 
     // Build a temporary ratchet rule (max_count is required by RatchetRule)
@@ -112,9 +128,13 @@ fn count_pattern(
         None
     };
 
-    let files = scan::collect_files(paths, &exclude_set);
+    let walk_policy = scan::WalkPolicy {
+        hidden_dirs: rule.file_glob().map(scan::hidden_dirs).unwrap_or_default(),
+        ..scan::WalkPolicy::from_section(&toml_config.baseline, config_path)
+    };
+    let files = scan::collect_files(paths, &exclude_set, &walk_policy);
 
-    let mut count = 0usize;
+    let mut matches = Vec::new();
     for file_path in &files {
         if let Some(ref gs) = rule_glob {
             let file_str = file_path.to_string_lossy();
@@ -127,149 +147,148 @@ fn count_pattern(
             }
         }
         if let Ok(content) = fs::read_to_string(file_path) {
-            let ctx = ScanContext {
-                file_path,
-                content: &content,
-            };
-            count += rule.check_file(&ctx).len();
+            let ctx = ScanContext::new(file_path, &content);
+            matches.extend(rule.check_file(&ctx));
         }
     }
 
-    Ok(count)
+    Ok(matches)
+}
+
+/// Default number of matches shown by `--preview` / `--interactive`.
+pub const DEFAULT_PREVIEW_LIMIT: usize = 10;
+
+/// Options for `ratchet add`.
+pub struct AddOptions<'a> {
+    /// Rule ID (default: slugified pattern).
+    pub id: Option<&'a str>,
+    pub glob: &'a str,
+    pub regex: bool,
+    pub message: Option<&'a str>,
+    /// Show up to this many matches before writing the rule.
+    pub preview: Option<usize>,
+    /// Ask for confirmation before writing the rule (implies a preview).
+    pub interactive: bool,
+}
+
+impl Default for AddOptions<'_> {
+    fn default() -> Self {
+        Self {
+            id: None,
+            glob: "**/*",
+            regex: false,
+            message: None,
+            preview: None,
+            interactive: false,
+        }
+    }
+}
+
+/// Write the first `limit` matches as `file:line  source`, plus a total.
+fn write_preview(matches: &[Violation], limit: usize, out: &mut dyn Write) {
+    for m in matches.iter().take(limit) {
+        let location = match m.line {
+            Some(line) => format!("{}:{}", m.file.display(), line),
+            None => m.file.display().to_string(),
+        };
+        let source = m.source_line.as_deref().unwrap_or("").trim();
+        let _ = writeln!(out, "  \x1b[90m{}\x1b[0m  {}", location, source);
+    }
+    if matches.len() > limit {
+        let _ = writeln!(out, "  \x1b[90m… and {} more\x1b[0m", matches.len() - limit);
+    }
+
+    let files = {
+        let mut f: Vec<&PathBuf> = matches.iter().map(|m| &m.file).collect();
+        f.dedup();
+        f.len()
+    };
+    let _ = writeln!(
+        out,
+        "{} match{} in {} file{}",
+        matches.len(),
+        if matches.len() == 1 { "" } else { "es" },
+        files,
+        if files == 1 { "" } else { "s" }
+    );
+}
+
+/// Prompt on `out` and read a yes/no answer from `input`. Anything other
+/// than `y`/`yes` (including EOF) counts as no.
+fn confirm(prompt: &str, input: &mut dyn BufRead, out: &mut dyn Write) -> bool {
+    let _ = write!(out, "{} [y/N] ", prompt);
+    let _ = out.flush();
+    let mut answer = String::new();
+    if input.read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
 }
 
 /// Specification for a ratchet rule to be appended to config.
 struct RatchetRuleSpec {
     id: String,
     pattern: String,
+    /// Rule to track instead of matching `pattern`.
+    track: Option<String>,
     glob: String,
     regex: bool,
     max_count: usize,
     message: String,
 }
 
-/// Append a `[[rule]]` block for a ratchet rule to the end of config text.
-fn append_ratchet_rule(config_text: &str, spec: &RatchetRuleSpec) -> String {
-    let mut result = config_text.to_string();
-    if !result.ends_with('\n') {
-        result.push('\n');
+/// Build the `[[rule]]` table for a ratchet rule.
+fn ratchet_rule_table(spec: &RatchetRuleSpec) -> Table {
+    let mut rule = Table::new();
+    rule["id"] = value(spec.id.as_str());
+    rule["type"] = value("ratchet");
+    rule["severity"] = value("warning");
+    match &spec.track {
+        Some(track) => rule["track"] = value(track.as_str()),
+        None => rule["pattern"] = value(spec.pattern.as_str()),
     }
-    result.push('\n');
-    result.push_str("[[rule]]\n");
-    result.push_str(&format!("id = \"{}\"\n", spec.id));
-    result.push_str("type = \"ratchet\"\n");
-    result.push_str("severity = \"warning\"\n");
-    result.push_str(&format!("pattern = \"{}\"\n", escape_toml_string(&spec.pattern)));
     if spec.regex {
-        result.push_str("regex = true\n");
+        rule["regex"] = value(true);
     }
     if spec.glob != "**/*" {
-        result.push_str(&format!("glob = \"{}\"\n", escape_toml_string(&spec.glob)));
+        rule["glob"] = value(spec.glob.as_str());
     }
-    result.push_str(&format!("max_count = {}\n", spec.max_count));
-    result.push_str(&format!(
-        "message = \"{}\"\n",
-        escape_toml_string(&spec.message)
-    ));
-    result
+    rule["max_count"] = value(spec.max_count as i64);
+    rule["message"] = value(spec.message.as_str());
+    rule
 }
 
-/// Escape a string for use in a TOML double-quoted value.
-fn escape_toml_string(s: &str) -> String {
-    s.replace('\\', "\\\\").replace('"', "\\\"")
+/// Append a `[[rule]]` block for a ratchet rule after the existing rules.
+fn append_ratchet_rule(doc: &mut ConfigDocument, spec: &RatchetRuleSpec) {
+    doc.push_rule(ratchet_rule_table(spec));
 }
 
-/// Update the `max_count` value for a specific rule ID in config text.
+/// Update the `max_count` value for a specific rule ID.
 /// Also updates the message if it contains an "N remaining" pattern.
-fn update_max_count(config_text: &str, rule_id: &str, new_max: usize) -> Result<String, RatchetError> {
-    let lines: Vec<&str> = config_text.lines().collect();
-    let mut result_lines: Vec<String> = Vec::with_capacity(lines.len());
-
-    let mut in_target_rule = false;
-    let mut found = false;
-    let mut updated_max = false;
-
-    for line in &lines {
-        // Detect `[[rule]]` boundaries
-        let trimmed = line.trim();
-        if trimmed == "[[rule]]" {
-            // If we were in the target rule but never updated max_count, error
-            if in_target_rule && !updated_max {
-                return Err(RatchetError::RuleNotFound(rule_id.to_string()));
-            }
-            in_target_rule = false;
-            result_lines.push(line.to_string());
-            continue;
-        }
-
-        // Check if this is the target rule by its id
-        if !in_target_rule && !found {
-            if let Some(id_val) = extract_toml_string_value(trimmed, "id") {
-                if id_val == rule_id {
-                    in_target_rule = true;
-                    found = true;
-                }
-            }
-        }
-
-        // Update max_count line if in target rule
-        if in_target_rule && trimmed.starts_with("max_count") {
-            result_lines.push(format!("max_count = {}", new_max));
-            updated_max = true;
-            continue;
-        }
+fn update_max_count(
+    doc: &mut ConfigDocument,
+    rule_id: &str,
+    new_max: usize,
+) -> Result<(), RatchetError> {
+    let rule = doc
+        .rule_mut(rule_id)
+        .ok_or_else(|| RatchetError::RuleNotFound(rule_id.to_string()))?;
 
-        // Update message if it contains "N remaining"
-        if in_target_rule && trimmed.starts_with("message") {
-            let updated = update_remaining_in_message(line, new_max);
-            result_lines.push(updated);
-            continue;
+    set_value(rule, "max_count", new_max as i64);
+    if let Some(message) = rule.get("message").and_then(|m| m.as_str()) {
+        let updated = update_remaining_in_message(message, new_max);
+        if updated != message {
+            set_value(rule, "message", updated);
         }
-
-        result_lines.push(line.to_string());
-    }
-
-    if !found {
-        return Err(RatchetError::RuleNotFound(rule_id.to_string()));
-    }
-
-    let mut output = result_lines.join("\n");
-    // Preserve trailing newline if original had one
-    if config_text.ends_with('\n') && !output.ends_with('\n') {
-        output.push('\n');
-    }
-    Ok(output)
-}
-
-/// Extract a TOML string value from a line like `key = "value"`.
-fn extract_toml_string_value<'a>(line: &'a str, key: &str) -> Option<&'a str> {
-    let line = line.trim();
-    if !line.starts_with(key) {
-        return None;
-    }
-    let rest = line[key.len()..].trim();
-    if !rest.starts_with('=') {
-        return None;
-    }
-    let rest = rest[1..].trim();
-    if rest.starts_with('"') && rest.len() >= 2 {
-        let end = rest[1..].find('"')?;
-        Some(&rest[1..1 + end])
-    } else {
-        None
     }
+    Ok(())
 }
 
-/// If a message line contains "N remaining", update N to new_max.
-fn update_remaining_in_message(line: &str, new_max: usize) -> String {
-    // Match pattern like: message = "... 42 remaining ..."
+/// If a message contains "N remaining", update N to new_max.
+fn update_remaining_in_message(message: &str, new_max: usize) -> String {
     let re = regex::Regex::new(r"\d+ remaining").unwrap();
-    if re.is_match(line) {
-        re.replace(line, &format!("{} remaining", new_max))
-            .to_string()
-    } else {
-        line.to_string()
-    }
+    re.replace(message, format!("{} remaining", new_max).as_str())
+        .to_string()
 }
 
 /// Entry point dispatching to subcommands.
@@ -283,7 +302,21 @@ pub fn run(command: crate::cli::RatchetCommands) -> Result<(), RatchetError> {
             message,
             config,
             paths,
-        } => run_add(&config, &pattern, id.as_deref(), &glob, regex, message.as_deref(), &paths),
+            preview,
+            interactive,
+        } => run_add(
+            &config,
+            &pattern,
+            &AddOptions {
+                id: id.as_deref(),
+                glob: &glob,
+                regex,
+                message: message.as_deref(),
+                preview,
+                interactive,
+            },
+            &paths,
+        ),
 
         crate::cli::RatchetCommands::Down {
             rule_id,
@@ -300,12 +333,36 @@ pub fn run(command: crate::cli::RatchetCommands) -> Result<(), RatchetError> {
 fn run_add(
     config_path: &Path,
     pattern: &str,
-    id: Option<&str>,
-    glob: &str,
-    regex: bool,
-    message: Option<&str>,
+    options: &AddOptions,
     paths: &[PathBuf],
 ) -> Result<(), RatchetError> {
+    let stdin = std::io::stdin();
+    run_add_with_io(
+        config_path,
+        pattern,
+        options,
+        paths,
+        &mut stdin.lock(),
+        &mut std::io::stderr(),
+    )
+}
+
+fn run_add_with_io(
+    config_path: &Path,
+    pattern: &str,
+    options: &AddOptions,
+    paths: &[PathBuf],
+    input: &mut dyn BufRead,
+    out: &mut dyn Write,
+) -> Result<(), RatchetError> {
+    let AddOptions {
+        id,
+        glob,
+        regex,
+        message,
+        ..
+    } = *options;
+
     let config_text = fs::read_to_string(config_path).map_err(RatchetError::ConfigRead)?;
     let toml_config: TomlConfig =
         toml::from_str(&config_text).map_err(RatchetError::ConfigParse)?;
@@ -319,7 +376,27 @@ fn run_add(
         return Err(RatchetError::RuleAlreadyExists(rule_id));
     }
 
-    let count = count_pattern(config_path, pattern, glob, regex, paths)?;
+    let matches = find_matches(config_path, pattern, glob, regex, paths)?;
+    let count = matches.len();
+
+    let preview = match options.preview {
+        Some(limit) => Some(limit),
+        None if options.interactive => Some(DEFAULT_PREVIEW_LIMIT),
+        None => None,
+    };
+    if let Some(limit) = preview {
+        write_preview(&matches, limit, out);
+    }
+    if options.interactive
+        && !confirm(
+            &format!("Add ratchet rule '{}' with max_count = {}?", rule_id, count),
+            input,
+            out,
+        )
+    {
+        let _ = writeln!(out, "Aborted; {} unchanged", config_path.display());
+        return Ok(());
+    }
 
     let msg = message
         .map(|s| s.to_string())
@@ -328,17 +405,20 @@ fn run_add(
     let spec = RatchetRuleSpec {
         id: rule_id.clone(),
         pattern: pattern.to_string(),
+        track: None,
         glob: glob.to_string(),
         regex,
         max_count: count,
         message: msg,
     };
 
-    let updated = append_ratchet_rule(&config_text, &spec);
-    fs::write(config_path, &updated).map_err(RatchetError::ConfigRead)?;
+    let mut doc =
+        ConfigDocument::parse(config_path, &config_text).map_err(RatchetError::ConfigEdit)?;
+    append_ratchet_rule(&mut doc, &spec);
+    doc.save().map_err(RatchetError::ConfigEdit)?;
 
-    eprintln!(
-        "\x1b[32m✓\x1b[0m Added ratchet rule '{}' (max_count = {}, {} current occurrence{})",
+    tracing::info!(
+        "✓ Added ratchet rule '{}' (max_count = {}, {} current occurrence{})",
         rule_id,
         count,
         count,
@@ -365,14 +445,19 @@ fn run_down(
         .ok_or_else(|| RatchetError::RuleNotFound(rule_id.to_string()))?;
 
     let old_max = toml_rule.max_count.unwrap_or(0);
-    let pattern = toml_rule
-        .pattern
-        .as_deref()
-        .unwrap_or("");
-    let glob = toml_rule.glob.as_deref().unwrap_or("**/*");
-    let regex = toml_rule.regex;
-
-    let current = count_pattern(config_path, pattern, glob, regex, paths)?;
+    let current = if toml_rule.track.is_some() {
+        // The tracked rule may come from anywhere in the config, so count
+        // with a full scan
+        let result = scan::run_scan(config_path, paths).map_err(RatchetError::Scan)?;
+        result.ratchet_counts.get(rule_id).map_or(0, |&(found, _)| found)
+    } else {
+        let pattern = toml_rule
+            .pattern
+            .as_deref()
+            .unwrap_or("");
+        let glob = toml_rule.glob.as_deref().unwrap_or("**/*");
+        count_pattern(config_path, pattern, glob, toml_rule.regex, paths)?
+    };
 
     if current >= old_max {
         return Err(RatchetError::NoDecrease {
@@ -382,11 +467,13 @@ fn run_down(
         });
     }
 
-    let updated = update_max_count(&config_text, rule_id, current)?;
-    fs::write(config_path, &updated).map_err(RatchetError::ConfigRead)?;
+    let mut doc =
+        ConfigDocument::parse(config_path, &config_text).map_err(RatchetError::ConfigEdit)?;
+    update_max_count(&mut doc, rule_id, current)?;
+    doc.save().map_err(RatchetError::ConfigEdit)?;
 
-    eprintln!(
-        "\x1b[32m✓\x1b[0m Ratcheted down '{}': {} → {}",
+    tracing::info!(
+        "✓ Ratcheted down '{}': {} → {}",
         rule_id, old_max, current
     );
 
@@ -407,40 +494,39 @@ fn run_from(config_path: &Path, baseline_path: &Path) -> Result<(), RatchetError
     let existing_ids: std::collections::HashSet<&str> =
         toml_config.rule.iter().map(|r| r.id.as_str()).collect();
 
-    let mut updated = config_text.clone();
+    let mut doc =
+        ConfigDocument::parse(config_path, &config_text).map_err(RatchetError::ConfigEdit)?;
     let mut added = 0usize;
 
     for entry in &baseline.entries {
         if existing_ids.contains(entry.rule_id.as_str()) {
-            eprintln!(
-                "\x1b[33m⚠\x1b[0m Skipping '{}': rule already exists",
-                entry.rule_id
-            );
+            tracing::warn!("skipping '{}': rule already exists", entry.rule_id);
             continue;
         }
 
         let spec = RatchetRuleSpec {
             id: entry.rule_id.clone(),
             pattern: entry.pattern.clone(),
+            track: entry.track.clone(),
             glob: "**/*".to_string(),
             regex: false,
             max_count: entry.count,
             message: format!("{} remaining", entry.count),
         };
 
-        updated = append_ratchet_rule(&updated, &spec);
+        append_ratchet_rule(&mut doc, &spec);
         added += 1;
 
-        eprintln!(
+        tracing::info!(
             "  {} (max_count = {})",
             entry.rule_id, entry.count
         );
     }
 
-    fs::write(config_path, &updated).map_err(RatchetError::ConfigRead)?;
+    doc.save().map_err(RatchetError::ConfigEdit)?;
 
-    eprintln!(
-        "\x1b[32m✓\x1b[0m Added {} ratchet rule{} from baseline",
+    tracing::info!(
+        "✓ Added {} ratchet rule{} from baseline",
         added,
         if added == 1 { "" } else { "s" }
     );
@@ -489,24 +575,29 @@ mod tests {
         assert_eq!(slugify("v2_api"), "v2-api");
     }
 
+    fn doc(config: &str) -> ConfigDocument {
+        ConfigDocument::parse(Path::new("baseline.toml"), config).unwrap()
+    }
+
     // ── append_ratchet_rule tests ──
 
     #[test]
     fn append_generates_valid_toml() {
-        let config = "[baseline]\n";
+        let mut config = doc("[baseline]\n");
         let spec = RatchetRuleSpec {
             id: "no-console".into(),
             pattern: r"console\.log".into(),
+            track: None,
             glob: "**/*.ts".into(),
             regex: true,
             max_count: 42,
             message: "42 remaining".into(),
         };
 
-        let result = append_ratchet_rule(config, &spec);
+        append_ratchet_rule(&mut config, &spec);
 
         // Verify the result is valid TOML
-        let parsed: TomlConfig = toml::from_str(&result).unwrap();
+        let parsed: TomlConfig = toml::from_str(&config.to_string()).unwrap();
         assert_eq!(parsed.rule.len(), 1);
         assert_eq!(parsed.rule[0].id, "no-console");
         assert_eq!(parsed.rule[0].rule_type, "ratchet");
@@ -518,43 +609,63 @@ mod tests {
 
     #[test]
     fn append_default_glob_omitted() {
-        let config = "[baseline]\n";
+        let mut config = doc("[baseline]\n");
         let spec = RatchetRuleSpec {
             id: "test".into(),
             pattern: "foo".into(),
+            track: None,
             glob: "**/*".into(),
             regex: false,
             max_count: 5,
             message: "5 remaining".into(),
         };
 
-        let result = append_ratchet_rule(config, &spec);
-        assert!(!result.contains("glob = "));
+        append_ratchet_rule(&mut config, &spec);
+        assert!(!config.to_string().contains("glob = "));
     }
 
     #[test]
     fn append_escapes_quotes() {
-        let config = "[baseline]\n";
+        let mut config = doc("[baseline]\n");
         let spec = RatchetRuleSpec {
             id: "test".into(),
             pattern: r#"say "hello""#.into(),
+            track: None,
             glob: "**/*".into(),
             regex: false,
             max_count: 1,
             message: r#"found "hello""#.into(),
         };
 
-        let result = append_ratchet_rule(config, &spec);
+        append_ratchet_rule(&mut config, &spec);
         // Should parse without error
-        let parsed: TomlConfig = toml::from_str(&result).unwrap();
+        let parsed: TomlConfig = toml::from_str(&config.to_string()).unwrap();
         assert_eq!(parsed.rule[0].pattern.as_deref(), Some(r#"say "hello""#));
     }
 
+    #[test]
+    fn append_preserves_comments() {
+        let original = "# shared config\n[baseline]\nexclude = [\"dist/**\"] # build\n";
+        let mut config = doc(original);
+        let spec = RatchetRuleSpec {
+            id: "test".into(),
+            pattern: "foo".into(),
+            track: None,
+            glob: "**/*".into(),
+            regex: false,
+            max_count: 1,
+            message: "1 remaining".into(),
+        };
+
+        append_ratchet_rule(&mut config, &spec);
+        assert!(config.to_string().starts_with(original));
+    }
+
     // ── update_max_count tests ──
 
     #[test]
     fn update_max_count_basic() {
-        let config = r#"[baseline]
+        let mut config = doc(r#"[baseline]
 
 [[rule]]
 id = "legacy-api"
@@ -562,9 +673,10 @@ type = "ratchet"
 pattern = "legacyCall"
 max_count = 42
 message = "42 remaining"
-"#;
+"#);
 
-        let result = update_max_count(config, "legacy-api", 10).unwrap();
+        update_max_count(&mut config, "legacy-api", 10).unwrap();
+        let result = config.to_string();
         assert!(result.contains("max_count = 10"));
         assert!(result.contains("10 remaining"));
         assert!(!result.contains("max_count = 42"));
@@ -572,23 +684,22 @@ message = "42 remaining"
 
     #[test]
     fn update_max_count_nonexistent_id() {
-        let config = r#"[baseline]
+        let mut config = doc(r#"[baseline]
 
 [[rule]]
 id = "legacy-api"
 type = "ratchet"
 max_count = 42
 message = "test"
-"#;
+"#);
 
-        let result = update_max_count(config, "nonexistent", 10);
-        assert!(result.is_err());
+        let result = update_max_count(&mut config, "nonexistent", 10);
         assert!(matches!(result.unwrap_err(), RatchetError::RuleNotFound(_)));
     }
 
     #[test]
     fn update_max_count_multiple_rules() {
-        let config = r#"[baseline]
+        let mut config = doc(r#"[baseline]
 
 [[rule]]
 id = "rule-a"
@@ -603,9 +714,10 @@ type = "ratchet"
 pattern = "b"
 max_count = 200
 message = "200 remaining"
-"#;
+"#);
 
-        let result = update_max_count(config, "rule-b", 50).unwrap();
+        update_max_count(&mut config, "rule-b", 50).unwrap();
+        let result = config.to_string();
         // rule-a should be unchanged
         assert!(result.contains("max_count = 100"));
         assert!(result.contains("100 remaining"));
@@ -616,67 +728,28 @@ message = "200 remaining"
     }
 
     #[test]
-    fn update_max_count_preserves_trailing_newline() {
-        let config = "[baseline]\n\n[[rule]]\nid = \"test\"\ntype = \"ratchet\"\nmax_count = 5\nmessage = \"test\"\n";
-        let result = update_max_count(config, "test", 3).unwrap();
-        assert!(result.ends_with('\n'));
-    }
-
-    // ── extract_toml_string_value tests ──
-
-    #[test]
-    fn extract_value_basic() {
-        assert_eq!(
-            extract_toml_string_value(r#"id = "my-rule""#, "id"),
-            Some("my-rule")
-        );
-    }
-
-    #[test]
-    fn extract_value_with_spaces() {
-        assert_eq!(
-            extract_toml_string_value(r#"id   =   "my-rule""#, "id"),
-            Some("my-rule")
-        );
-    }
-
-    #[test]
-    fn extract_value_wrong_key() {
+    fn update_max_count_preserves_layout() {
+        let original = "[baseline]\n\n# legacy\n[[rule]]\nid = \"test\"\ntype   = \"ratchet\"\nmax_count = 5   # audit\nmessage = \"test\"\n";
+        let mut config = doc(original);
+        update_max_count(&mut config, "test", 3).unwrap();
         assert_eq!(
-            extract_toml_string_value(r#"type = "ratchet""#, "id"),
-            None
+            config.to_string(),
+            original.replace("max_count = 5 ", "max_count = 3 ")
         );
     }
 
-    // ── escape_toml_string tests ──
-
-    #[test]
-    fn escape_backslash() {
-        assert_eq!(escape_toml_string(r"console\.log"), r"console\\.log");
-    }
-
-    #[test]
-    fn escape_quotes() {
-        assert_eq!(escape_toml_string(r#"say "hi""#), r#"say \"hi\""#);
-    }
-
     // ── update_remaining_in_message tests ──
 
     #[test]
     fn update_remaining_replaces_count() {
-        let line = r#"message = "42 remaining""#;
-        assert_eq!(
-            update_remaining_in_message(line, 10),
-            r#"message = "10 remaining""#
-        );
+        assert_eq!(update_remaining_in_message("42 remaining", 10), "10 remaining");
     }
 
     #[test]
     fn update_remaining_no_match_passthrough() {
-        let line = r#"message = "legacy API usage""#;
         assert_eq!(
-            update_remaining_in_message(line, 10),
-            r#"message = "legacy API usage""#
+            update_remaining_in_message("legacy API usage", 10),
+            "legacy API usage"
         );
     }
 
@@ -734,7 +807,7 @@ message = "200 remaining"
         fs::create_dir(&src_dir).unwrap();
         fs::write(src_dir.join("app.ts"), "TODO: fix\nTODO: cleanup\nok\n").unwrap();
 
-        run_add(&config, "TODO", None, "**/*", false, None, &[src_dir]).unwrap();
+        run_add(&config, "TODO", &AddOptions::default(), &[src_dir]).unwrap();
 
         let result = fs::read_to_string(&config).unwrap();
         let parsed: TomlConfig = toml::from_str(&result).unwrap();
@@ -757,10 +830,11 @@ message = "200 remaining"
         run_add(
             &config,
             "legacy",
-            Some("my-legacy"),
-            "**/*",
-            false,
-            Some("stop using legacy"),
+            &AddOptions {
+                id: Some("my-legacy"),
+                message: Some("stop using legacy"),
+                ..Default::default()
+            },
             &[src_dir],
         )
         .unwrap();
@@ -792,10 +866,10 @@ message = "m"
         let result = run_add(
             &config,
             "x",
-            Some("existing"),
-            "**/*",
-            false,
-            None,
+            &AddOptions {
+                id: Some("existing"),
+                ..Default::default()
+            },
             &[dir.path().to_path_buf()],
         );
         assert!(result.is_err());
@@ -819,10 +893,10 @@ message = "m"
         run_add(
             &config,
             r"console\.(log|warn)",
-            None,
-            "**/*",
-            true,
-            None,
+            &AddOptions {
+                regex: true,
+                ..Default::default()
+            },
             &[src_dir],
         )
         .unwrap();
@@ -833,6 +907,87 @@ message = "m"
         assert!(parsed.rule[0].regex);
     }
 
+    #[test]
+    fn run_add_preview_lists_first_matches() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let config = dir.path().join("baseline.toml");
+        fs::write(&config, "[baseline]\n").unwrap();
+
+        let src_dir = dir.path().join("src");
+        fs::create_dir(&src_dir).unwrap();
+        fs::write(src_dir.join("app.ts"), "TODO: one\nok\nTODO: two\nTODO: three\n").unwrap();
+
+        let mut out = Vec::new();
+        run_add_with_io(
+            &config,
+            "TODO",
+            &AddOptions {
+                preview: Some(2),
+                ..Default::default()
+            },
+            &[src_dir],
+            &mut std::io::empty(),
+            &mut out,
+        )
+        .unwrap();
+
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("app.ts:1\x1b[0m  TODO: one"));
+        assert!(output.contains("app.ts:3\x1b[0m  TODO: two"));
+        assert!(!output.contains("TODO: three"));
+        assert!(output.contains("and 1 more"));
+        assert!(output.contains("3 matches in 1 file"));
+
+        let parsed: TomlConfig = toml::from_str(&fs::read_to_string(&config).unwrap()).unwrap();
+        assert_eq!(parsed.rule[0].max_count, Some(3));
+    }
+
+    #[test]
+    fn run_add_interactive_declined_leaves_config() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let config = dir.path().join("baseline.toml");
+        fs::write(&config, "[baseline]\n").unwrap();
+
+        let src_dir = dir.path().join("src");
+        fs::create_dir(&src_dir).unwrap();
+        fs::write(src_dir.join("app.ts"), "TODO: one\n").unwrap();
+
+        let interactive = AddOptions {
+            interactive: true,
+            ..Default::default()
+        };
+
+        let mut out = Vec::new();
+        run_add_with_io(
+            &config,
+            "TODO",
+            &interactive,
+            std::slice::from_ref(&src_dir),
+            &mut "n\n".as_bytes(),
+            &mut out,
+        )
+        .unwrap();
+        let output = String::from_utf8(out).unwrap();
+        assert!(output.contains("TODO: one"));
+        assert!(output.contains("Add ratchet rule 'todo' with max_count = 1? [y/N]"));
+        assert!(output.contains("Aborted"));
+        assert_eq!(fs::read_to_string(&config).unwrap(), "[baseline]\n");
+
+        run_add_with_io(
+            &config,
+            "TODO",
+            &interactive,
+            &[src_dir],
+            &mut "y\n".as_bytes(),
+            &mut Vec::new(),
+        )
+        .unwrap();
+        let parsed: TomlConfig = toml::from_str(&fs::read_to_string(&config).unwrap()).unwrap();
+        assert_eq!(parsed.rule[0].id, "todo");
+    }
+
     #[test]
     fn run_down_lowers_max_count() {
         let dir = tempfile::tempdir().unwrap();
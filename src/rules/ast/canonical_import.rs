@@ -0,0 +1,334 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::ast::parse_file;
+use crate::rules::{Fix, Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+use std::collections::{BTreeMap, HashMap};
+
+/// Requires components to be imported from their canonical module
+/// (`canonical_imports`), e.g. `Button` from `@acme/ui` rather than
+/// `./button` or `@mui/material`.
+///
+/// Named imports match by the imported name (`{ Button as Btn }` is
+/// `Button`), default imports by their local name. Namespace imports,
+/// re-exports and dynamic `import()` are not checked. A statement is reported
+/// once per module its components should come from, at the module specifier.
+/// When every binding in the statement belongs to that one module, the fix
+/// rewrites the import: just the specifier for named imports, or the whole
+/// statement (if it fits on one line) when it has a default import.
+pub struct CanonicalImportRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: Option<String>,
+    /// Canonical module of each component.
+    modules: HashMap<String, String>,
+}
+
+impl CanonicalImportRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        if config.canonical_imports.is_empty() {
+            return Err(RuleBuildError::MissingField(config.id.clone(), "canonical_imports"));
+        }
+        let mut modules: HashMap<String, String> = HashMap::new();
+        for (module, components) in &config.canonical_imports {
+            for component in components {
+                if let Some(other) = modules.insert(component.clone(), module.clone()) {
+                    return Err(RuleBuildError::InvalidValue(
+                        config.id.clone(),
+                        "canonical_imports",
+                        format!("`{}` is listed under both {} and {}", component, other, module),
+                    ));
+                }
+            }
+        }
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob: config.glob.clone(),
+            modules,
+        })
+    }
+}
+
+impl Rule for CanonicalImportRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+    fn file_glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        if !ctx.content.contains("import") {
+            return violations;
+        }
+        let tree = match parse_file(ctx.file_path, ctx.content) {
+            Some(t) => t,
+            None => return violations,
+        };
+        let root = tree.root_node();
+        let source = ctx.content.as_bytes();
+
+        for statement in (0..root.named_child_count()).filter_map(|i| root.named_child(i)) {
+            if statement.kind() != "import_statement" {
+                continue;
+            }
+            let Some(specifier) = statement.child_by_field_name("source") else {
+                continue;
+            };
+            let Some(import) = ImportBindings::read(statement, source) else {
+                continue;
+            };
+            let quoted = specifier.utf8_text(source).unwrap_or_default();
+            let module = quoted.trim_matches(['"', '\'']);
+
+            // Components imported from the wrong module, by the module they belong to
+            let mut misplaced: BTreeMap<&str, Vec<&str>> = BTreeMap::new();
+            for binding in &import.bindings {
+                match self.modules.get(&binding.name) {
+                    Some(canonical) if canonical != module => {
+                        misplaced.entry(canonical.as_str()).or_default().push(&binding.name)
+                    }
+                    _ => {}
+                }
+            }
+            let fixable = misplaced.len() == 1
+                && !import.namespace
+                && misplaced.values().all(|names| names.len() == import.bindings.len());
+
+            for (canonical, names) in &misplaced {
+                let listed = names.iter().map(|n| format!("`{}`", n)).collect::<Vec<_>>().join(", ");
+                let fix = if fixable { import.rewrite(statement, quoted, canonical, source) } else { None };
+                let suggest = match fix {
+                    Some(fix) => vec![Suggestion::with_fix(format!("import from {}", canonical), fix)],
+                    None => vec![Suggestion::new(format!("import {} from {} in its own import", listed, canonical))],
+                };
+                let row = specifier.start_position().row;
+                violations.push(Violation {
+                    rule_id: self.id.clone(),
+                    severity: self.severity,
+                    file: ctx.file_path.to_path_buf(),
+                    line: Some(row + 1),
+                    column: Some(specifier.start_position().column + 1),
+                    message: format!(
+                        "{}: {} {} from {} instead of {}",
+                        self.message,
+                        listed,
+                        if names.len() == 1 { "comes" } else { "come" },
+                        module,
+                        canonical
+                    ),
+                    fix: suggest[0].fix.clone(),
+                    suggest: if self.suggest.is_empty() { suggest } else { self.suggest.clone() },
+                    source_line: ctx.source_line(row),
+                    origin: None,
+                    enclosing: None,
+                });
+            }
+        }
+        violations
+    }
+}
+
+/// A binding an import statement introduces.
+struct Binding {
+    /// The imported name for named imports, the local name for a default one.
+    name: String,
+    /// The specifier as written (`Button`, `Button as Btn`, `type Props`).
+    text: String,
+}
+
+/// The bindings of one `import ... from` statement.
+struct ImportBindings {
+    bindings: Vec<Binding>,
+    /// Has a default import.
+    default: bool,
+    /// Has `* as ns`.
+    namespace: bool,
+}
+
+impl ImportBindings {
+    /// `None` for side-effect imports (`import "./styles.css"`).
+    fn read(statement: tree_sitter::Node, source: &[u8]) -> Option<Self> {
+        let clause = (0..statement.named_child_count())
+            .filter_map(|i| statement.named_child(i))
+            .find(|c| c.kind() == "import_clause")?;
+        let text = |n: tree_sitter::Node| n.utf8_text(source).ok().map(str::to_string);
+        let mut import = Self {
+            bindings: Vec::new(),
+            default: false,
+            namespace: false,
+        };
+        for child in (0..clause.named_child_count()).filter_map(|i| clause.named_child(i)) {
+            match child.kind() {
+                "identifier" => {
+                    if let Some(name) = text(child) {
+                        import.default = true;
+                        import.bindings.push(Binding { text: name.clone(), name });
+                    }
+                }
+                "namespace_import" => import.namespace = true,
+                "named_imports" => {
+                    for specifier in (0..child.named_child_count()).filter_map(|i| child.named_child(i)) {
+                        if specifier.kind() != "import_specifier" {
+                            continue;
+                        }
+                        if let (Some(name), Some(written)) =
+                            (specifier.child_by_field_name("name").and_then(text), text(specifier))
+                        {
+                            import.bindings.push(Binding { name, text: written });
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+        Some(import)
+    }
+
+    /// A fix moving the whole statement to `canonical`. Named imports only
+    /// need the specifier swapped; a default import becomes a named one,
+    /// which needs the statement on one line.
+    fn rewrite(&self, statement: tree_sitter::Node, quoted: &str, canonical: &str, source: &[u8]) -> Option<Fix> {
+        let quote = if quoted.starts_with('\'') { "'" } else { "\"" };
+        let specifier = format!("{}{}{}", quote, canonical, quote);
+        if !self.default {
+            return Some(Fix {
+                old: quoted.to_string(),
+                new: specifier,
+            });
+        }
+        if statement.start_position().row != statement.end_position().row {
+            return None;
+        }
+        let old = statement.utf8_text(source).ok()?;
+        let type_only = (0..statement.child_count())
+            .filter_map(|i| statement.child(i))
+            .any(|c| c.kind() == "type");
+        let names: Vec<&str> = self.bindings.iter().map(|b| b.text.as_str()).collect();
+        Some(Fix {
+            old: old.to_string(),
+            new: format!(
+                "import {}{{ {} }} from {}{}",
+                if type_only { "type " } else { "" },
+                names.join(", "),
+                specifier,
+                if old.ends_with(';') { ";" } else { "" }
+            ),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn rule() -> CanonicalImportRule {
+        let mut canonical_imports = BTreeMap::new();
+        canonical_imports.insert("@acme/ui".to_string(), vec!["Button".to_string(), "Dialog".to_string()]);
+        canonical_imports.insert("@acme/icons".to_string(), vec!["Icon".to_string()]);
+        CanonicalImportRule::new(&RuleConfig {
+            id: "ui-kit-imports".into(),
+            message: "Use the UI kit".into(),
+            canonical_imports,
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn check(content: &str) -> Vec<Violation> {
+        rule().check_file(&ScanContext::new(Path::new("src/app/page.tsx"), content))
+    }
+
+    fn fix(v: &Violation) -> Option<(&str, &str)> {
+        v.fix.as_ref().map(|f| (f.old.as_str(), f.new.as_str()))
+    }
+
+    #[test]
+    fn flags_named_and_default_imports_from_other_modules() {
+        let content = "\
+import { Button, Dialog as Modal } from '@mui/material';
+import Button from \"./button\";
+import {
+  Dialog,
+} from \"../components/dialog\";
+import { Button as KitButton, Icon } from \"@acme/ui\";
+import { Card } from \"./card\";
+import \"./styles.css\";
+";
+        let violations = check(content);
+        let summary: Vec<(usize, &str)> = violations.iter().map(|v| (v.line.unwrap(), v.message.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, "Use the UI kit: `Button`, `Dialog` come from @mui/material instead of @acme/ui"),
+                (2, "Use the UI kit: `Button` comes from ./button instead of @acme/ui"),
+                (5, "Use the UI kit: `Dialog` comes from ../components/dialog instead of @acme/ui"),
+                (6, "Use the UI kit: `Icon` comes from @acme/ui instead of @acme/icons"),
+            ]
+        );
+        assert_eq!(violations[0].column, Some(41));
+    }
+
+    #[test]
+    fn fixes_rewrite_the_import() {
+        let content = "\
+import { Button, Dialog as Modal } from '@mui/material';
+import Button from \"./button\";
+import {
+  Dialog,
+} from \"../components/dialog\";
+import { Button as KitButton, Icon } from \"@acme/ui\";
+";
+        let violations = check(content);
+        assert_eq!(fix(&violations[0]), Some(("'@mui/material'", "'@acme/ui'")));
+        assert_eq!(violations[0].suggest[0].title, "import from @acme/ui");
+        assert_eq!(
+            fix(&violations[1]),
+            Some(("import Button from \"./button\";", "import { Button } from \"@acme/ui\";"))
+        );
+        assert_eq!(fix(&violations[2]), Some(("\"../components/dialog\"", "\"@acme/ui\"")));
+        // Button is already in the right place, so moving the statement would break it
+        assert_eq!(fix(&violations[3]), None);
+        assert_eq!(violations[3].suggest[0].title, "import `Icon` from @acme/icons in its own import");
+    }
+
+    #[test]
+    fn mixed_statements_get_no_fix() {
+        let violations = check("import Button, { TextField } from \"@mui/material\";\nimport * as Mui from \"@mui/material\";\n");
+        assert_eq!(violations.len(), 1);
+        assert_eq!(fix(&violations[0]), None);
+    }
+
+    #[test]
+    fn requires_an_unambiguous_map() {
+        let err = CanonicalImportRule::new(&RuleConfig {
+            id: "ui".into(),
+            ..Default::default()
+        })
+        .err()
+        .unwrap();
+        assert_eq!(err.to_string(), "rule 'ui': missing required field 'canonical_imports'");
+
+        let mut canonical_imports = BTreeMap::new();
+        canonical_imports.insert("@acme/ui".to_string(), vec!["Button".to_string()]);
+        canonical_imports.insert("@acme/legacy".to_string(), vec!["Button".to_string()]);
+        let err = CanonicalImportRule::new(&RuleConfig {
+            id: "ui".into(),
+            canonical_imports,
+            ..Default::default()
+        })
+        .err()
+        .unwrap();
+        assert_eq!(
+            err.to_string(),
+            "rule 'ui': invalid 'canonical_imports': `Button` is listed under both @acme/legacy and @acme/ui"
+        );
+    }
+}
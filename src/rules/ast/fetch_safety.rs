@@ -0,0 +1,396 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::ast::parse_file;
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+
+/// Names accepted in `checks`.
+const CHECKS: &[&str] = &["response-ok", "axios-timeout"];
+
+/// Response body readers; calling one straight on a fetch result skips the
+/// status check.
+const BODY_METHODS: &[&str] = &["json", "text", "blob", "arrayBuffer", "formData"];
+
+/// Node kinds that start a new function scope.
+const FUNCTION_KINDS: &[&str] = &[
+    "function_declaration",
+    "function_expression",
+    "function",
+    "arrow_function",
+    "method_definition",
+    "generator_function_declaration",
+];
+
+/// Flags brittle network calls.
+///
+/// - `response-ok`: a `fetch()` response that is read or dropped without
+///   looking at `ok` or `status`. A response bound to a variable passes when
+///   the enclosing function reads `.ok` / `.status` on it, returns it, or
+///   hands it to another function; the same goes for the parameter of a
+///   `.then()` callback. `(await fetch(url)).json()` and a bare
+///   `await fetch(url);` are always reported. Results returned directly or
+///   passed along (`return fetch(url)`, `Promise.all([fetch(a)])`) are left
+///   to the caller.
+/// - `axios-timeout`: `axios.create()` without a `timeout` option. Options
+///   passed as a variable or spread from elsewhere aren't checked.
+///
+/// `checks` picks which of these run; by default both do.
+pub struct FetchSafetyRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: Option<String>,
+    response_ok: bool,
+    axios_timeout: bool,
+}
+
+impl FetchSafetyRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        if let Some(unknown) = config.checks.iter().find(|c| !CHECKS.contains(&c.as_str())) {
+            return Err(RuleBuildError::InvalidValue(
+                config.id.clone(),
+                "checks",
+                format!("unknown check '{}' (expected one of: {})", unknown, CHECKS.join(", ")),
+            ));
+        }
+        let enabled = |check: &str| config.checks.is_empty() || config.checks.iter().any(|c| c == check);
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob: config.glob.clone(),
+            response_ok: enabled("response-ok"),
+            axios_timeout: enabled("axios-timeout"),
+        })
+    }
+}
+
+impl Rule for FetchSafetyRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+    fn file_glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let tree = match parse_file(ctx.file_path, ctx.content) {
+            Some(t) => t,
+            None => return violations,
+        };
+        self.visit(tree.root_node(), ctx.content.as_bytes(), ctx, &mut violations);
+        violations
+    }
+}
+
+impl FetchSafetyRule {
+    fn visit(
+        &self,
+        node: tree_sitter::Node,
+        source: &[u8],
+        ctx: &ScanContext,
+        violations: &mut Vec<Violation>,
+    ) {
+        if node.kind() == "call_expression" {
+            let callee = node
+                .child_by_field_name("function")
+                .and_then(|f| f.utf8_text(source).ok())
+                .unwrap_or_default();
+            if self.response_ok && callee == "fetch" && !response_checked(node, source) {
+                self.report(
+                    node,
+                    ctx,
+                    "fetch() response is used without checking `ok` or `status`",
+                    "check `response.ok` (or `status`) and handle failures before reading the body",
+                    violations,
+                );
+            }
+            if self.axios_timeout && callee == "axios.create" && !sets_timeout(node, source) {
+                self.report(
+                    node,
+                    ctx,
+                    "axios.create() without a timeout",
+                    "pass a `timeout` (in milliseconds) so stalled requests fail",
+                    violations,
+                );
+            }
+        }
+
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                self.visit(child, source, ctx, violations);
+            }
+        }
+    }
+
+    fn report(
+        &self,
+        node: tree_sitter::Node,
+        ctx: &ScanContext,
+        detail: &str,
+        default_suggest: &str,
+        violations: &mut Vec<Violation>,
+    ) {
+        let row = node.start_position().row;
+        let suggest = if self.suggest.is_empty() {
+            vec![Suggestion::new(default_suggest)]
+        } else {
+            self.suggest.clone()
+        };
+        violations.push(Violation {
+            rule_id: self.id.clone(),
+            severity: self.severity,
+            file: ctx.file_path.to_path_buf(),
+            line: Some(row + 1),
+            column: Some(node.start_position().column + 1),
+            message: format!("{}: {}", self.message, detail),
+            suggest,
+            source_line: ctx.source_line(row),
+            fix: None,
+            origin: None,
+            enclosing: None,
+        });
+    }
+}
+
+/// Whether the response of the `fetch()` call `call` is checked, or left to
+/// code this rule can't see.
+fn response_checked(call: tree_sitter::Node, source: &[u8]) -> bool {
+    // The expression the response comes out of: `fetch()`, `await fetch()`
+    // or either in parentheses
+    let mut value = call;
+    while let Some(parent) = value.parent() {
+        if matches!(parent.kind(), "await_expression" | "parenthesized_expression") {
+            value = parent;
+        } else {
+            break;
+        }
+    }
+    let Some(parent) = value.parent() else {
+        return true;
+    };
+
+    match parent.kind() {
+        "variable_declarator" | "assignment_expression" => {
+            let target = parent
+                .child_by_field_name("name")
+                .or_else(|| parent.child_by_field_name("left"));
+            match target.filter(|t| t.kind() == "identifier") {
+                Some(target) => {
+                    let name = target.utf8_text(source).unwrap_or_default();
+                    name_checked(enclosing_scope(parent), source, name, target)
+                }
+                // Destructuring picks fields explicitly
+                None => true,
+            }
+        }
+        "member_expression" => {
+            let property = parent
+                .child_by_field_name("property")
+                .and_then(|p| p.utf8_text(source).ok())
+                .unwrap_or_default();
+            if property == "then" {
+                then_callback_checked(parent, source)
+            } else {
+                !BODY_METHODS.contains(&property)
+            }
+        }
+        "expression_statement" => false,
+        _ => true,
+    }
+}
+
+/// Whether the callback of `fetch().then(cb)` checks its response parameter.
+fn then_callback_checked(member: tree_sitter::Node, source: &[u8]) -> bool {
+    let callback = member
+        .parent()
+        .filter(|p| p.kind() == "call_expression")
+        .and_then(|call| call.child_by_field_name("arguments"))
+        .and_then(|args| args.named_child(0));
+    let Some(callback) = callback.filter(|c| FUNCTION_KINDS.contains(&c.kind())) else {
+        // A named handler is assumed to deal with the response
+        return true;
+    };
+    let param = callback.child_by_field_name("parameter").or_else(|| {
+        let first = callback.child_by_field_name("parameters")?.named_child(0)?;
+        // TypeScript wraps parameters as `required_parameter { pattern }`
+        Some(first.child_by_field_name("pattern").unwrap_or(first))
+    });
+    match param.filter(|p| p.kind() == "identifier") {
+        Some(param) => {
+            let name = param.utf8_text(source).unwrap_or_default();
+            name_checked(callback, source, name, param)
+        }
+        None => false,
+    }
+}
+
+/// The innermost function around `node`, or the whole file.
+fn enclosing_scope(node: tree_sitter::Node) -> tree_sitter::Node {
+    let mut scope = node;
+    while let Some(parent) = scope.parent() {
+        scope = parent;
+        if FUNCTION_KINDS.contains(&parent.kind()) {
+            break;
+        }
+    }
+    scope
+}
+
+/// Whether `name` (bound at `binding`) has `.ok` / `.status` read, is
+/// returned, or is passed to a call anywhere in `scope`.
+fn name_checked(scope: tree_sitter::Node, source: &[u8], name: &str, binding: tree_sitter::Node) -> bool {
+    if scope.kind() == "identifier" && scope.id() != binding.id() && scope.utf8_text(source).ok() == Some(name) {
+        if let Some(parent) = scope.parent() {
+            let checked = match parent.kind() {
+                "member_expression" => parent
+                    .child_by_field_name("property")
+                    .and_then(|p| p.utf8_text(source).ok())
+                    .is_some_and(|p| p == "ok" || p == "status"),
+                "arguments" | "return_statement" => true,
+                "arrow_function" => parent.child_by_field_name("body").is_some_and(|b| b.id() == scope.id()),
+                _ => false,
+            };
+            if checked {
+                return true;
+            }
+        }
+    }
+    (0..scope.child_count())
+        .filter_map(|i| scope.child(i))
+        .any(|child| name_checked(child, source, name, binding))
+}
+
+/// Whether an `axios.create()` call sets `timeout`, or passes options this
+/// rule can't see into.
+fn sets_timeout(call: tree_sitter::Node, source: &[u8]) -> bool {
+    let Some(options) = call
+        .child_by_field_name("arguments")
+        .and_then(|args| args.named_child(0))
+    else {
+        return false;
+    };
+    if options.kind() != "object" {
+        return true;
+    }
+    (0..options.named_child_count())
+        .filter_map(|i| options.named_child(i))
+        .any(|entry| match entry.kind() {
+            "pair" => entry
+                .child_by_field_name("key")
+                .and_then(|k| k.utf8_text(source).ok())
+                .is_some_and(|k| k.trim_matches(|c| c == '"' || c == '\'') == "timeout"),
+            "shorthand_property_identifier" => entry.utf8_text(source).ok() == Some("timeout"),
+            "spread_element" => true,
+            _ => false,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn make_rule(checks: &[&str]) -> FetchSafetyRule {
+        FetchSafetyRule::new(&RuleConfig {
+            id: "fetch-safety".into(),
+            severity: Severity::Warning,
+            message: "Brittle network call".into(),
+            checks: checks.iter().map(|c| c.to_string()).collect(),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn check(content: &str) -> Vec<Violation> {
+        let ctx = ScanContext::new(Path::new("api.ts"), content);
+        make_rule(&[]).check_file(&ctx)
+    }
+
+    #[test]
+    fn flags_unchecked_fetch_responses() {
+        let content = "\
+async function load(id: string) {
+  const res = await fetch(`/api/users/${id}`);
+  return res.json();
+}
+async function save() {
+  await fetch('/api/save', { method: 'POST' });
+}
+const read = async () => (await fetch('/api/a')).json();
+fetch('/api/b').then((r) => r.json());
+";
+        let violations = check(content);
+        let lines: Vec<_> = violations.iter().map(|v| v.line.unwrap()).collect();
+        assert_eq!(lines, [2, 6, 8, 9]);
+        assert_eq!(
+            violations[0].message,
+            "Brittle network call: fetch() response is used without checking `ok` or `status`"
+        );
+        assert_eq!(violations[0].column, Some(21));
+    }
+
+    #[test]
+    fn allows_checked_or_handed_off_responses() {
+        let content = "\
+async function load() {
+  const res = await fetch('/api/users');
+  if (!res.ok) throw new Error(`HTTP ${res.status}`);
+  return res.json();
+}
+async function raw() {
+  const res = await fetch('/api/raw');
+  return res;
+}
+async function handled() {
+  const res = await fetch('/api/x');
+  return parseResponse(res);
+}
+const passthrough = () => fetch('/api/y');
+const all = Promise.all([fetch('/a'), fetch('/b')]);
+fetch('/api/z').then(function (response) {
+  if (response.status !== 200) return null;
+  return response.json();
+});
+fetch('/api/w').then(handleResponse);
+";
+        assert!(check(content).is_empty());
+    }
+
+    #[test]
+    fn flags_axios_instances_without_timeout() {
+        let content = "\
+const a = axios.create({ baseURL: '/api' });
+const b = axios.create();
+const c = axios.create({ baseURL: '/api', timeout: 10_000 });
+const d = axios.create({ ...defaults });
+const e = axios.create(options);
+";
+        let violations = check(content);
+        let lines: Vec<_> = violations.iter().map(|v| v.line.unwrap()).collect();
+        assert_eq!(lines, [1, 2]);
+        assert!(violations[0].message.ends_with("axios.create() without a timeout"));
+    }
+
+    #[test]
+    fn checks_select_what_runs() {
+        let content = "const a = axios.create();\nfetch('/x').then((r) => r.json());\n";
+        let ctx = ScanContext::new(Path::new("api.ts"), content);
+        let only_axios = make_rule(&["axios-timeout"]).check_file(&ctx);
+        assert_eq!(only_axios.len(), 1);
+        assert_eq!(only_axios[0].line, Some(1));
+
+        let config = RuleConfig {
+            id: "fetch-safety".into(),
+            checks: vec!["retries".into()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            FetchSafetyRule::new(&config),
+            Err(RuleBuildError::InvalidValue(_, "checks", _))
+        ));
+    }
+}
@@ -0,0 +1,278 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::ast::{is_component_node, parse_file};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+
+/// Flags heading and landmark structure that confuses screen-reader
+/// navigation.
+///
+/// Within each component, JSX headings (`<h1>`–`<h6>`) must not skip a level
+/// going down: `<h1>` followed by `<h3>` is flagged. The first heading of a
+/// component may be any level, since components are embedded under headings
+/// defined elsewhere. Across the whole file there may be at most one `<h1>`
+/// and at most one `<main>` landmark; every extra one is flagged.
+pub struct HeadingHierarchyRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: Option<String>,
+}
+
+/// A heading or landmark element found while walking the tree.
+struct Element {
+    /// Heading level (1-6), or 0 for `<main>`.
+    level: usize,
+    /// Id of the innermost enclosing component node, if any.
+    component: Option<usize>,
+    row: usize,
+    column: usize,
+}
+
+impl HeadingHierarchyRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob: config.glob.clone(),
+        })
+    }
+}
+
+impl Rule for HeadingHierarchyRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn file_glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let tree = match parse_file(ctx.file_path, ctx.content) {
+            Some(t) => t,
+            None => return violations,
+        };
+        let source = ctx.content.as_bytes();
+        let mut elements = Vec::new();
+        collect(tree.root_node(), source, None, &mut elements);
+
+        let mut last_level: Vec<(Option<usize>, usize)> = Vec::new();
+        let (mut h1_seen, mut main_seen) = (false, false);
+        for el in &elements {
+            let detail = match el.level {
+                0 if main_seen => Some("more than one <main> landmark in this file".to_string()),
+                0 => {
+                    main_seen = true;
+                    None
+                }
+                level => {
+                    let prev = last_level
+                        .iter()
+                        .rev()
+                        .find(|(c, _)| *c == el.component)
+                        .map(|(_, l)| *l);
+                    last_level.push((el.component, level));
+                    if level == 1 && h1_seen {
+                        Some("more than one <h1> in this file".to_string())
+                    } else {
+                        h1_seen |= level == 1;
+                        prev.filter(|&p| level > p + 1)
+                            .map(|p| format!("<h{}> follows <h{}>, skipping a level", level, p))
+                    }
+                }
+            };
+            if let Some(detail) = detail {
+                violations.push(self.violation(ctx, el, detail));
+            }
+        }
+        violations
+    }
+}
+
+impl HeadingHierarchyRule {
+    fn violation(&self, ctx: &ScanContext, el: &Element, detail: String) -> Violation {
+        let message = if self.message.is_empty() {
+            detail
+        } else {
+            format!("{} ({})", self.message, detail)
+        };
+        Violation {
+            rule_id: self.id.clone(),
+            severity: self.severity,
+            file: ctx.file_path.to_path_buf(),
+            line: Some(el.row + 1),
+            column: Some(el.column + 1),
+            message,
+            suggest: self.suggest.clone(),
+            source_line: ctx.source_line(el.row),
+            fix: None,
+            origin: None,
+            enclosing: None,
+        }
+    }
+}
+
+/// Collect headings and `<main>` elements in source order, each tagged with
+/// its innermost enclosing component.
+fn collect(
+    node: tree_sitter::Node,
+    source: &[u8],
+    component: Option<usize>,
+    out: &mut Vec<Element>,
+) {
+    let component = if is_component_node(&node, source) {
+        Some(node.id())
+    } else {
+        component
+    };
+
+    let kind = node.kind();
+    if kind == "jsx_self_closing_element" || kind == "jsx_opening_element" {
+        if let Some(level) = tag_name(&node, source).and_then(element_level) {
+            out.push(Element {
+                level,
+                component,
+                row: node.start_position().row,
+                column: node.start_position().column,
+            });
+        }
+    }
+
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            collect(child, source, component, out);
+        }
+    }
+}
+
+fn tag_name<'a>(node: &tree_sitter::Node, source: &'a [u8]) -> Option<&'a str> {
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            if child.kind() == "identifier" || child.kind() == "member_expression" {
+                return child.utf8_text(source).ok();
+            }
+        }
+    }
+    None
+}
+
+/// `Some(1..=6)` for `h1`–`h6`, `Some(0)` for `main`.
+fn element_level(tag: &str) -> Option<usize> {
+    if tag == "main" {
+        return Some(0);
+    }
+    let level: usize = tag.strip_prefix('h')?.parse().ok()?;
+    (1..=6).contains(&level).then_some(level)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check(content: &str) -> Vec<Violation> {
+        let rule = HeadingHierarchyRule::new(&RuleConfig {
+            id: "heading-hierarchy".into(),
+            severity: Severity::Warning,
+            glob: Some("**/*.{tsx,jsx}".into()),
+            ..Default::default()
+        })
+        .unwrap();
+        let ctx = ScanContext::new(Path::new("page.tsx"), content);
+        rule.check_file(&ctx)
+    }
+
+    #[test]
+    fn sequential_headings_pass() {
+        let content = r#"export default function Page() {
+  return (
+    <main>
+      <h1>Title</h1>
+      <h2>Section</h2>
+      <h3>Sub</h3>
+      <h2>Next</h2>
+    </main>
+  );
+}"#;
+        assert!(check(content).is_empty());
+    }
+
+    #[test]
+    fn skipped_level_is_flagged() {
+        let content = r#"export default function Page() {
+  return (
+    <div>
+      <h1>Title</h1>
+      <h3 className="x">Sub</h3>
+    </div>
+  );
+}"#;
+        let violations = check(content);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line, Some(5));
+        assert_eq!(violations[0].message, "<h3> follows <h1>, skipping a level");
+    }
+
+    #[test]
+    fn each_component_starts_fresh() {
+        let content = r#"function Card() {
+  return <section><h3>Card</h3><h4>Detail</h4></section>;
+}
+export default function Page() {
+  return <main><h1>Title</h1><Card /></main>;
+}"#;
+        assert!(check(content).is_empty());
+    }
+
+    #[test]
+    fn extra_h1_and_main_are_flagged() {
+        let content = r#"export default function Page() {
+  return (
+    <>
+      <main><h1>One</h1></main>
+      <main><h1>Two</h1></main>
+    </>
+  );
+}"#;
+        let messages: Vec<_> = check(content).into_iter().map(|v| v.message).collect();
+        assert_eq!(
+            messages,
+            [
+                "more than one <main> landmark in this file",
+                "more than one <h1> in this file",
+            ]
+        );
+    }
+
+    #[test]
+    fn configured_message_is_a_prefix() {
+        let rule = HeadingHierarchyRule::new(&RuleConfig {
+            id: "heading-hierarchy".into(),
+            message: "Heading structure".into(),
+            ..Default::default()
+        })
+        .unwrap();
+        let ctx = ScanContext::new(Path::new("a.tsx"), "const A = () => <div><h2>a</h2><h4>b</h4></div>;");
+        let violations = rule.check_file(&ctx);
+        assert_eq!(
+            violations[0].message,
+            "Heading structure (<h4> follows <h2>, skipping a level)"
+        );
+    }
+
+    #[test]
+    fn element_levels() {
+        assert_eq!(element_level("h1"), Some(1));
+        assert_eq!(element_level("h6"), Some(6));
+        assert_eq!(element_level("h7"), None);
+        assert_eq!(element_level("header"), None);
+        assert_eq!(element_level("main"), Some(0));
+    }
+}
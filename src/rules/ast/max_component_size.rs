@@ -1,6 +1,6 @@
 use crate::config::{RuleConfig, Severity};
 use crate::rules::ast::{is_component_node, parse_file};
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
 
 /// Flags React components that exceed a configurable line count.
 ///
@@ -11,7 +11,7 @@ pub struct MaxComponentSizeRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
     max_count: usize,
 }
@@ -22,7 +22,7 @@ impl MaxComponentSizeRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob: config.glob.clone(),
             max_count: config.max_count.unwrap_or(150),
         })
@@ -76,8 +76,10 @@ impl MaxComponentSizeRule {
                     column: Some(1),
                     message: self.message.clone(),
                     suggest: self.suggest.clone(),
-                    source_line: ctx.content.lines().nth(start).map(String::from),
+                    source_line: ctx.source_line(start),
                     fix: None,
+                    origin: None,
+                    enclosing: None,
                 });
             }
         }
@@ -100,7 +102,7 @@ mod tests {
             id: "max-component-size".into(),
             severity: Severity::Warning,
             message: format!("Component exceeds {} lines", max_count),
-            suggest: Some("Split into smaller components".into()),
+            suggest: vec!["Split into smaller components".into()],
             glob: Some("**/*.tsx".into()),
             max_count: Some(max_count),
             ..Default::default()
@@ -108,10 +110,7 @@ mod tests {
     }
 
     fn check(rule: &MaxComponentSizeRule, content: &str) -> Vec<Violation> {
-        let ctx = ScanContext {
-            file_path: Path::new("test.tsx"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.tsx"), content);
         rule.check_file(&ctx)
     }
 
@@ -197,10 +196,7 @@ function helper() {
     #[test]
     fn non_tsx_file_skipped() {
         let rule = MaxComponentSizeRule::new(&make_config(1)).unwrap();
-        let ctx = ScanContext {
-            file_path: Path::new("test.rs"),
-            content: "fn main() { println!(\"hello\"); }",
-        };
+        let ctx = ScanContext::new(Path::new("test.rs"), "fn main() { println!(\"hello\"); }");
         assert!(rule.check_file(&ctx).is_empty());
     }
 
@@ -1,26 +1,50 @@
+pub mod canonical_import;
+pub mod fetch_safety;
+pub mod heading_hierarchy;
 pub mod max_component_size;
+pub mod no_barrel_files;
 pub mod no_cascading_set_state;
 pub mod no_click_handler;
 pub mod no_derived_state_effect;
+pub mod no_global_state;
 pub mod no_nested_components;
 pub mod no_object_dep_array;
 pub mod no_outline_none;
 pub mod no_regexp_in_render;
+pub mod no_silent_promise_catch;
+pub mod no_sql_interpolation;
+pub mod no_stylesheet_in_render;
 pub mod prefer_use_reducer;
+pub mod require_icon_button_label;
 pub mod require_img_alt;
+pub mod require_input_label;
+pub mod route_handler_validation;
 
+pub use canonical_import::CanonicalImportRule;
+pub use fetch_safety::FetchSafetyRule;
+pub use heading_hierarchy::HeadingHierarchyRule;
 pub use max_component_size::MaxComponentSizeRule;
+pub use no_barrel_files::NoBarrelFilesRule;
 pub use no_cascading_set_state::NoCascadingSetStateRule;
 pub use no_click_handler::{NoDivClickHandlerRule, NoSpanClickHandlerRule};
 pub use no_derived_state_effect::NoDerivedStateEffectRule;
+pub use no_global_state::NoGlobalStateRule;
 pub use no_nested_components::NoNestedComponentsRule;
 pub use no_object_dep_array::NoObjectDepArrayRule;
 pub use no_outline_none::NoOutlineNoneRule;
 pub use no_regexp_in_render::NoRegexpInRenderRule;
+pub use no_silent_promise_catch::NoSilentPromiseCatchRule;
+pub use no_sql_interpolation::NoSqlInterpolationRule;
+pub use no_stylesheet_in_render::NoStylesheetInRenderRule;
 pub use prefer_use_reducer::PreferUseReducerRule;
+pub use require_icon_button_label::RequireIconButtonLabelRule;
 pub use require_img_alt::RequireImgAltRule;
+pub use require_input_label::RequireInputLabelRule;
+pub use route_handler_validation::RouteHandlerValidationRule;
 
+use std::cell::RefCell;
 use std::path::Path;
+use std::sync::LazyLock;
 
 /// Supported languages for AST parsing.
 #[derive(Debug, Clone, Copy)]
@@ -42,17 +66,40 @@ pub fn detect_language(path: &Path) -> Option<Lang> {
     }
 }
 
+static TSX: LazyLock<tree_sitter::Language> =
+    LazyLock::new(|| tree_sitter_typescript::LANGUAGE_TSX.into());
+static TYPESCRIPT: LazyLock<tree_sitter::Language> =
+    LazyLock::new(|| tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into());
+static JAVASCRIPT: LazyLock<tree_sitter::Language> =
+    LazyLock::new(|| tree_sitter_javascript::LANGUAGE.into());
+
+/// The tree-sitter grammar for `lang`, loaded once per process.
+pub fn language(lang: Lang) -> &'static tree_sitter::Language {
+    match lang {
+        Lang::Tsx => &TSX,
+        Lang::Typescript => &TYPESCRIPT,
+        Lang::Jsx | Lang::Javascript => &JAVASCRIPT,
+    }
+}
+
+thread_local! {
+    /// One parser per scan thread, plus the grammar it is currently set to.
+    /// The grammar is only switched when consecutive files differ.
+    static PARSER: RefCell<(tree_sitter::Parser, Option<&'static tree_sitter::Language>)> =
+        RefCell::new((tree_sitter::Parser::new(), None));
+}
+
 /// Parse a file into a tree-sitter syntax tree.
 pub fn parse_file(path: &Path, content: &str) -> Option<tree_sitter::Tree> {
-    let lang = detect_language(path)?;
-    let mut parser = tree_sitter::Parser::new();
-    let ts_lang: tree_sitter::Language = match lang {
-        Lang::Tsx => tree_sitter_typescript::LANGUAGE_TSX.into(),
-        Lang::Typescript => tree_sitter_typescript::LANGUAGE_TYPESCRIPT.into(),
-        Lang::Jsx | Lang::Javascript => tree_sitter_javascript::LANGUAGE.into(),
-    };
-    parser.set_language(&ts_lang).ok()?;
-    parser.parse(content, None)
+    let ts_lang = language(detect_language(path)?);
+    PARSER.with(|cell| {
+        let (parser, current) = &mut *cell.borrow_mut();
+        if !current.is_some_and(|c| std::ptr::eq(c, ts_lang)) {
+            parser.set_language(ts_lang).ok()?;
+            *current = Some(ts_lang);
+        }
+        parser.parse(content, None)
+    })
 }
 
 /// Check if a tree-sitter node represents a React component declaration.
@@ -79,6 +126,76 @@ pub fn is_component_node(node: &tree_sitter::Node, source: &[u8]) -> bool {
     }
 }
 
+/// Wrappers whose function argument is named after the variable they are
+/// assigned to: `const Button = memo(() => ...)`.
+const COMPONENT_WRAPPERS: &[&str] = &["memo", "forwardRef", "observer"];
+
+/// Name of the innermost named function, method or class around a 0-based
+/// row and byte column: `Button`, `Cart.render`. Anonymous callbacks are
+/// looked through, so a position inside `items.map(...)` reports the
+/// function that calls it.
+pub fn enclosing_function(tree: &tree_sitter::Tree, source: &[u8], row: usize, column: usize) -> Option<String> {
+    let point = tree_sitter::Point { row, column };
+    let mut node = tree.root_node().descendant_for_point_range(point, point)?;
+    loop {
+        if let Some(name) = function_name(node, source) {
+            return Some(name);
+        }
+        node = node.parent()?;
+    }
+}
+
+fn function_name(node: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    let text = |n: tree_sitter::Node| n.utf8_text(source).ok().map(str::to_string);
+    match node.kind() {
+        "function_declaration" | "generator_function_declaration" | "class_declaration" => {
+            text(node.child_by_field_name("name")?)
+        }
+        "method_definition" => {
+            let method = text(node.child_by_field_name("name")?)?;
+            // method_definition -> class_body -> class
+            let class = node
+                .parent()
+                .and_then(|body| body.parent())
+                .and_then(|class| class.child_by_field_name("name"))
+                .and_then(text);
+            Some(match class {
+                Some(class) => format!("{}.{}", class, method),
+                None => method,
+            })
+        }
+        "arrow_function" | "function_expression" | "function" => assigned_name(node, source),
+        _ => None,
+    }
+}
+
+/// The name a function expression is bound to: `const X = () => ...`,
+/// `const X = memo(forwardRef(() => ...))`, `{ x: () => ... }` or `x = function () {}`.
+fn assigned_name(function: tree_sitter::Node, source: &[u8]) -> Option<String> {
+    let mut parent = function.parent()?;
+    while parent.kind() == "arguments" {
+        let call = parent.parent()?;
+        let callee = call.child_by_field_name("function")?.utf8_text(source).ok()?;
+        if !COMPONENT_WRAPPERS.contains(&callee.rsplit('.').next()?) {
+            return None;
+        }
+        parent = call.parent()?;
+    }
+    let name = match parent.kind() {
+        "variable_declarator" => parent.child_by_field_name("name")?,
+        "pair" => parent.child_by_field_name("key")?,
+        "assignment_expression" => parent.child_by_field_name("left")?,
+        "public_field_definition" | "field_definition" => parent
+            .child_by_field_name("name")
+            .or_else(|| parent.child_by_field_name("property"))?,
+        _ => return None,
+    };
+    if matches!(name.kind(), "object_pattern" | "array_pattern") {
+        return None;
+    }
+    name.utf8_text(source).ok().map(str::to_string)
+}
+
 fn starts_with_uppercase(name: &str) -> bool {
     name.chars()
         .next()
@@ -317,6 +434,17 @@ mod tests {
         assert!(tree.is_some());
     }
 
+    #[test]
+    fn reused_parser_switches_grammar_between_files() {
+        // Type assertions are only valid TypeScript, so each parse must use
+        // its own file's grammar even though the parser is shared.
+        let ts = "let n = <number>value;";
+        assert!(!parse_file(Path::new("a.ts"), ts).unwrap().root_node().has_error());
+        assert!(parse_file(Path::new("a.js"), ts).unwrap().root_node().has_error());
+        assert!(!parse_file(Path::new("b.ts"), ts).unwrap().root_node().has_error());
+        assert!(std::ptr::eq(language(Lang::Jsx), language(Lang::Javascript)));
+    }
+
     #[test]
     fn parse_unknown_ext_returns_none() {
         let tree = parse_file(Path::new("app.rs"), "fn main() {}");
@@ -422,4 +550,27 @@ mod tests {
         let attrs = collect_class_attributes(&tree, content.as_bytes());
         assert!(attrs.is_empty(), "non-utility calls should produce no fragments");
     }
+
+    #[test]
+    fn enclosing_function_names() {
+        let content = r#"const top = 1;
+export function List({ items }) {
+  return items.map((item) => <Row item={item} />);
+}
+const Button = memo(forwardRef((props, ref) => {
+  return <button ref={ref} />;
+}));
+class Cart {
+  render() {
+    return null;
+  }
+}
+"#;
+        let tree = parse_file(Path::new("a.tsx"), content).unwrap();
+        let at = |row, column| enclosing_function(&tree, content.as_bytes(), row, column);
+        assert_eq!(at(0, 6), None);
+        assert_eq!(at(2, 30).as_deref(), Some("List"));
+        assert_eq!(at(5, 10).as_deref(), Some("Button"));
+        assert_eq!(at(9, 4).as_deref(), Some("Cart.render"));
+    }
 }
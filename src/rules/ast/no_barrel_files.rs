@@ -0,0 +1,378 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::ast::parse_file;
+use crate::rules::{Fix, Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Re-export statements an index file may hold when `max_count` is unset.
+const DEFAULT_MAX_REEXPORTS: usize = 10;
+
+/// Extensions tried when resolving a relative `export * from` specifier.
+const MODULE_EXTENSIONS: &[&str] = &["ts", "tsx", "mts", "js", "jsx", "mjs"];
+
+const INDEX_NAMES: &[&str] = &["index.ts", "index.tsx", "index.mts", "index.js", "index.jsx", "index.mjs"];
+
+/// Flags wildcard re-exports and oversized barrel files, which defeat
+/// tree-shaking and make TypeScript load every module behind the barrel.
+///
+/// Every `export * from "..."` is reported (`export * as ns` is left alone,
+/// since it names what it exports). When the target is a relative module
+/// whose exports can be read, the suggestion lists them explicitly, with a
+/// fix; if the target itself has `export *`, the message says the chain
+/// continues there. An `index` file with more than `max_count` re-export
+/// statements (default 10) is reported once, at its first line.
+pub struct NoBarrelFilesRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: Option<String>,
+    max_reexports: usize,
+}
+
+impl NoBarrelFilesRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob: config.glob.clone(),
+            max_reexports: config.max_count.unwrap_or(DEFAULT_MAX_REEXPORTS),
+        })
+    }
+
+    fn violation(&self, ctx: &ScanContext, node: tree_sitter::Node, message: String, suggest: Vec<Suggestion>) -> Violation {
+        let row = node.start_position().row;
+        let fix = suggest.first().and_then(|s| s.fix.clone());
+        Violation {
+            rule_id: self.id.clone(),
+            severity: self.severity,
+            file: ctx.file_path.to_path_buf(),
+            line: Some(row + 1),
+            column: Some(node.start_position().column + 1),
+            message,
+            suggest: if self.suggest.is_empty() { suggest } else { self.suggest.clone() },
+            source_line: ctx.source_line(row),
+            fix,
+            origin: None,
+            enclosing: None,
+        }
+    }
+}
+
+impl Rule for NoBarrelFilesRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+    fn file_glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        if !ctx.content.contains("export") {
+            return violations;
+        }
+        let tree = match parse_file(ctx.file_path, ctx.content) {
+            Some(t) => t,
+            None => return violations,
+        };
+        let root = tree.root_node();
+        let source = ctx.content.as_bytes();
+
+        let reexports: Vec<tree_sitter::Node> = (0..root.named_child_count())
+            .filter_map(|i| root.named_child(i))
+            .filter(|n| n.kind() == "export_statement" && n.child_by_field_name("source").is_some())
+            .collect();
+
+        let is_index = ctx
+            .file_path
+            .file_name()
+            .and_then(|n| n.to_str())
+            .is_some_and(|n| INDEX_NAMES.contains(&n));
+        if is_index && reexports.len() > self.max_reexports {
+            violations.push(self.violation(
+                ctx,
+                root,
+                format!(
+                    "{}: barrel file has {} re-export statements (max {})",
+                    self.message,
+                    reexports.len(),
+                    self.max_reexports
+                ),
+                vec![Suggestion::new("import from the modules directly instead of through this index")],
+            ));
+        }
+
+        for statement in reexports.into_iter().filter(|n| is_wildcard(*n)) {
+            let Some(specifier) = statement
+                .child_by_field_name("source")
+                .and_then(|s| s.utf8_text(source).ok())
+            else {
+                continue;
+            };
+            let target = resolve_relative(ctx.file_path, unquote(specifier));
+            let exports = target.as_deref().and_then(module_exports);
+            let (message, suggest) = match exports {
+                Some(ref exports) if exports.wildcard => (
+                    format!("{}: `export *` chain continues in {}", self.message, specifier),
+                    vec![Suggestion::new("list the re-exported names explicitly in both modules")],
+                ),
+                Some(ref exports) => (
+                    format!("{}: `export *` from {}", self.message, specifier),
+                    vec![explicit_exports(statement, source, specifier, exports)],
+                ),
+                None => (
+                    format!("{}: `export *` from {}", self.message, specifier),
+                    vec![Suggestion::new(format!("export {{ ... }} from {} with the names you need", specifier))],
+                ),
+            };
+            violations.push(self.violation(ctx, statement, message, suggest));
+        }
+        violations
+    }
+}
+
+/// `export * from "..."`, but not `export * as ns from "..."`.
+fn is_wildcard(statement: tree_sitter::Node) -> bool {
+    let mut has_star = false;
+    for i in 0..statement.child_count() {
+        match statement.child(i).map(|c| c.kind()) {
+            Some("*") => has_star = true,
+            Some("namespace_export") => return false,
+            _ => {}
+        }
+    }
+    has_star
+}
+
+fn unquote(specifier: &str) -> &str {
+    specifier.trim_matches(|c| c == '"' || c == '\'' || c == '`')
+}
+
+/// The file a `./` or `../` specifier names, trying module extensions and
+/// `index` files. Package specifiers aren't resolved.
+fn resolve_relative(from: &Path, specifier: &str) -> Option<PathBuf> {
+    if !specifier.starts_with("./") && !specifier.starts_with("../") {
+        return None;
+    }
+    let base = from.parent()?.join(specifier);
+    if base.is_file() {
+        return Some(base);
+    }
+    // TypeScript ESM imports name the compiled `.js` file
+    let stripped = base.with_extension("");
+    let with_extension = MODULE_EXTENSIONS.iter().map(|ext| stripped.with_extension(ext));
+    let index = INDEX_NAMES.iter().map(|name| base.join(name));
+    with_extension.chain(index).find(|p| p.is_file())
+}
+
+/// Names a module exports, split into values and types. `wildcard` is set
+/// when it re-exports another module wholesale, so the names are incomplete.
+#[derive(Debug, Default, PartialEq)]
+struct ModuleExports {
+    values: Vec<String>,
+    types: Vec<String>,
+    wildcard: bool,
+}
+
+fn module_exports(path: &Path) -> Option<ModuleExports> {
+    let content = fs::read_to_string(path).ok()?;
+    let tree = parse_file(path, &content)?;
+    let root = tree.root_node();
+    let source = content.as_bytes();
+    let text = |n: tree_sitter::Node| n.utf8_text(source).ok().map(str::to_string);
+
+    let mut exports = ModuleExports::default();
+    for statement in (0..root.named_child_count()).filter_map(|i| root.named_child(i)) {
+        if statement.kind() != "export_statement" {
+            continue;
+        }
+        let tokens: Vec<&str> = (0..statement.child_count())
+            .filter_map(|i| statement.child(i))
+            .map(|c| c.kind())
+            .collect();
+        if tokens.contains(&"default") {
+            continue;
+        }
+        if is_wildcard(statement) {
+            exports.wildcard = true;
+            continue;
+        }
+        let type_only = tokens.contains(&"type");
+        if let Some(declaration) = statement.child_by_field_name("declaration") {
+            let (names, is_type) = declared_names(declaration, &text);
+            let list = if is_type { &mut exports.types } else { &mut exports.values };
+            list.extend(names);
+            continue;
+        }
+        for child in (0..statement.named_child_count()).filter_map(|i| statement.named_child(i)) {
+            match child.kind() {
+                "export_clause" => {
+                    for specifier in (0..child.named_child_count()).filter_map(|i| child.named_child(i)) {
+                        let name = specifier
+                            .child_by_field_name("alias")
+                            .or_else(|| specifier.child_by_field_name("name"))
+                            .and_then(text);
+                        let Some(name) = name.filter(|n| n != "default") else {
+                            continue;
+                        };
+                        let is_type = type_only || specifier.child(0).is_some_and(|c| c.kind() == "type");
+                        if is_type {
+                            exports.types.push(name);
+                        } else {
+                            exports.values.push(name);
+                        }
+                    }
+                }
+                // export * as ns from "..."
+                "namespace_export" => exports.values.extend(child.named_child(0).and_then(text)),
+                _ => {}
+            }
+        }
+    }
+    exports.values.dedup();
+    exports.types.dedup();
+    Some(exports)
+}
+
+/// Names an exported declaration introduces, and whether they are types.
+fn declared_names(
+    declaration: tree_sitter::Node,
+    text: &impl Fn(tree_sitter::Node) -> Option<String>,
+) -> (Vec<String>, bool) {
+    match declaration.kind() {
+        "interface_declaration" | "type_alias_declaration" => {
+            (declaration.child_by_field_name("name").and_then(text).into_iter().collect(), true)
+        }
+        "lexical_declaration" | "variable_declaration" => {
+            let names = (0..declaration.named_child_count())
+                .filter_map(|i| declaration.named_child(i))
+                .filter(|d| d.kind() == "variable_declarator")
+                .filter_map(|d| d.child_by_field_name("name"))
+                .filter(|n| n.kind() == "identifier")
+                .filter_map(text)
+                .collect();
+            (names, false)
+        }
+        _ => (declaration.child_by_field_name("name").and_then(text).into_iter().collect(), false),
+    }
+}
+
+/// A suggestion replacing `export *` with the target's names; it carries a
+/// fix when the statement is on one line.
+fn explicit_exports(
+    statement: tree_sitter::Node,
+    source: &[u8],
+    specifier: &str,
+    exports: &ModuleExports,
+) -> Suggestion {
+    let semicolon = if statement.utf8_text(source).is_ok_and(|t| t.ends_with(';')) { ";" } else { "" };
+    let mut lines = Vec::new();
+    if !exports.values.is_empty() {
+        lines.push(format!("export {{ {} }} from {}{}", exports.values.join(", "), specifier, semicolon));
+    }
+    if !exports.types.is_empty() {
+        lines.push(format!("export type {{ {} }} from {}{}", exports.types.join(", "), specifier, semicolon));
+    }
+    if lines.is_empty() {
+        return Suggestion::new(format!("remove it; {} has no named exports", specifier));
+    }
+    let new = lines.join("\n");
+    let title = format!("export the names explicitly: {}", lines.join("; "));
+    match statement.utf8_text(source) {
+        Ok(old) if statement.start_position().row == statement.end_position().row => Suggestion::with_fix(
+            title,
+            Fix {
+                old: old.to_string(),
+                new,
+            },
+        ),
+        _ => Suggestion::new(title),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write(root: &Path, file: &str, content: &str) -> PathBuf {
+        let path = root.join(file);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(&path, content).unwrap();
+        path
+    }
+
+    fn check(config: &RuleConfig, path: &Path) -> Vec<Violation> {
+        let content = fs::read_to_string(path).unwrap();
+        NoBarrelFilesRule::new(config).unwrap().check_file(&ScanContext::new(path, &content))
+    }
+
+    fn config() -> RuleConfig {
+        RuleConfig {
+            id: "no-barrels".into(),
+            message: "Avoid barrel re-exports".into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn wildcard_reexports_suggest_explicit_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "src/ui/button.tsx",
+            "export function Button() {}\nexport const SIZES = [], other = 1;\nexport interface ButtonProps {}\nexport type Size = string;\nexport default Button;\nconst hidden = 1;\nexport { hidden as shown };\n",
+        );
+        write(root, "src/ui/card/index.ts", "export * from './card';\n");
+        let barrel = write(
+            root,
+            "src/ui/index.ts",
+            "export * from './button';\nexport * from './card';\nexport * as icons from './icons';\nexport * from 'lodash';\n",
+        );
+
+        let violations = check(&config(), &barrel);
+        let summary: Vec<(usize, &str)> = violations.iter().map(|v| (v.line.unwrap(), v.message.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, "Avoid barrel re-exports: `export *` from './button'"),
+                (2, "Avoid barrel re-exports: `export *` chain continues in './card'"),
+                (4, "Avoid barrel re-exports: `export *` from 'lodash'"),
+            ]
+        );
+        let fix = violations[0].fix.as_ref().unwrap();
+        assert_eq!(violations[0].suggest[0].fix.as_ref(), Some(fix));
+        assert_eq!(fix.old, "export * from './button';");
+        assert_eq!(
+            fix.new,
+            "export { Button, SIZES, other, shown } from './button';\nexport type { ButtonProps, Size } from './button';"
+        );
+        assert!(violations[2].suggest[0].fix.is_none());
+    }
+
+    #[test]
+    fn index_files_over_the_reexport_limit() {
+        let dir = tempfile::tempdir().unwrap();
+        let lines: String = (0..4).map(|i| format!("export {{ a{} }} from './m{}';\n", i, i)).collect();
+        let barrel = write(dir.path(), "lib/index.ts", &lines);
+        let not_index = write(dir.path(), "lib/all.ts", &lines);
+
+        let limited = RuleConfig {
+            max_count: Some(3),
+            ..config()
+        };
+        let violations = check(&limited, &barrel);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(
+            violations[0].message,
+            "Avoid barrel re-exports: barrel file has 4 re-export statements (max 3)"
+        );
+        assert!(check(&limited, &not_index).is_empty());
+        assert!(check(&config(), &barrel).is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::config::{RuleConfig, Severity};
 use crate::rules::ast::parse_file;
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
 
 /// Flags `useEffect` callbacks that call too many setState functions.
 ///
@@ -12,7 +12,7 @@ pub struct NoCascadingSetStateRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
     max_count: usize,
 }
@@ -23,7 +23,7 @@ impl NoCascadingSetStateRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob: config.glob.clone(),
             max_count: config.max_count.unwrap_or(3),
         })
@@ -88,6 +88,8 @@ impl NoCascadingSetStateRule {
                                                 .nth(line)
                                                 .map(String::from),
                                             fix: None,
+                                            origin: None,
+                                            enclosing: None,
                                         });
                                     }
                                 }
@@ -150,7 +152,7 @@ mod tests {
             id: "no-cascading-set-state".into(),
             severity: Severity::Warning,
             message: format!("useEffect has {}+ setState calls", max_count),
-            suggest: Some("Consider useReducer".into()),
+            suggest: vec!["Consider useReducer".into()],
             glob: Some("**/*.tsx".into()),
             max_count: Some(max_count),
             ..Default::default()
@@ -158,10 +160,7 @@ mod tests {
     }
 
     fn check(rule: &NoCascadingSetStateRule, content: &str) -> Vec<Violation> {
-        let ctx = ScanContext {
-            file_path: Path::new("test.tsx"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.tsx"), content);
         rule.check_file(&ctx)
     }
 
@@ -301,10 +300,7 @@ function MyComponent() {
     #[test]
     fn non_tsx_file_skipped() {
         let rule = NoCascadingSetStateRule::new(&make_config(1)).unwrap();
-        let ctx = ScanContext {
-            file_path: Path::new("test.rs"),
-            content: "fn main() {}",
-        };
+        let ctx = ScanContext::new(Path::new("test.rs"), "fn main() {}");
         assert!(rule.check_file(&ctx).is_empty());
     }
 
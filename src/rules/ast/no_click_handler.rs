@@ -1,6 +1,6 @@
 use crate::config::{RuleConfig, Severity};
 use crate::rules::ast::parse_file;
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
 
 /// Shared logic for flagging non-interactive elements with onClick but no role.
 fn check_click_handler(
@@ -9,7 +9,7 @@ fn check_click_handler(
     id: &str,
     severity: Severity,
     message: &str,
-    suggest: &Option<String>,
+    suggest: &[Suggestion],
 ) -> Vec<Violation> {
     let mut violations = Vec::new();
     let tree = match parse_file(ctx.file_path, ctx.content) {
@@ -39,7 +39,7 @@ fn visit(
     id: &str,
     severity: Severity,
     message: &str,
-    suggest: &Option<String>,
+    suggest: &[Suggestion],
     violations: &mut Vec<Violation>,
 ) {
     let kind = node.kind();
@@ -56,9 +56,11 @@ fn visit(
                 line: Some(row + 1),
                 column: Some(node.start_position().column + 1),
                 message: message.to_string(),
-                suggest: suggest.clone(),
-                source_line: ctx.content.lines().nth(row).map(String::from),
+                suggest: suggest.to_vec(),
+                source_line: ctx.source_line(row),
                 fix: None,
+                origin: None,
+                enclosing: None,
             });
         }
     }
@@ -122,7 +124,7 @@ pub struct NoDivClickHandlerRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
 }
 
@@ -132,7 +134,7 @@ impl NoDivClickHandlerRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob: config.glob.clone(),
         })
     }
@@ -158,7 +160,7 @@ pub struct NoSpanClickHandlerRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
 }
 
@@ -168,7 +170,7 @@ impl NoSpanClickHandlerRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob: config.glob.clone(),
         })
     }
@@ -199,7 +201,7 @@ mod tests {
             id: "no-div-click-handler".into(),
             severity: Severity::Error,
             message: "Non-interactive <div> with onClick".into(),
-            suggest: Some("Use <button> instead".into()),
+            suggest: vec!["Use <button> instead".into()],
             glob: Some("**/*.{tsx,jsx}".into()),
             ..Default::default()
         })
@@ -211,7 +213,7 @@ mod tests {
             id: "no-span-click-handler".into(),
             severity: Severity::Error,
             message: "Non-interactive <span> with onClick".into(),
-            suggest: Some("Use <button> instead".into()),
+            suggest: vec!["Use <button> instead".into()],
             glob: Some("**/*.{tsx,jsx}".into()),
             ..Default::default()
         })
@@ -220,19 +222,13 @@ mod tests {
 
     fn check_div(content: &str) -> Vec<Violation> {
         let rule = make_div_rule();
-        let ctx = ScanContext {
-            file_path: Path::new("test.tsx"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.tsx"), content);
         rule.check_file(&ctx)
     }
 
     fn check_span(content: &str) -> Vec<Violation> {
         let rule = make_span_rule();
-        let ctx = ScanContext {
-            file_path: Path::new("test.tsx"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.tsx"), content);
         rule.check_file(&ctx)
     }
 
@@ -285,10 +281,7 @@ mod tests {
     #[test]
     fn non_tsx_skipped() {
         let rule = make_div_rule();
-        let ctx = ScanContext {
-            file_path: Path::new("test.rs"),
-            content: "fn main() {}",
-        };
+        let ctx = ScanContext::new(Path::new("test.rs"), "fn main() {}");
         assert!(rule.check_file(&ctx).is_empty());
     }
 
@@ -1,6 +1,6 @@
 use crate::config::{RuleConfig, Severity};
 use crate::rules::ast::parse_file;
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
 
 /// Flags `useEffect` callbacks where the body contains ONLY `set*()` calls.
 ///
@@ -11,7 +11,7 @@ pub struct NoDerivedStateEffectRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
 }
 
@@ -21,7 +21,7 @@ impl NoDerivedStateEffectRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob: config.glob.clone(),
         })
     }
@@ -80,6 +80,8 @@ impl NoDerivedStateEffectRule {
                                                 .nth(line)
                                                 .map(String::from),
                                             fix: None,
+                                            origin: None,
+                                            enclosing: None,
                                         });
                                     }
                                 }
@@ -169,7 +171,7 @@ mod tests {
             id: "no-derived-state-effect".into(),
             severity: Severity::Warning,
             message: "useEffect that only calls setState is derived state".into(),
-            suggest: Some("Compute during render with useMemo instead".into()),
+            suggest: vec!["Compute during render with useMemo instead".into()],
             glob: Some("**/*.{tsx,jsx}".into()),
             ..Default::default()
         })
@@ -178,10 +180,7 @@ mod tests {
 
     fn check(content: &str) -> Vec<Violation> {
         let rule = make_rule();
-        let ctx = ScanContext {
-            file_path: Path::new("test.tsx"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.tsx"), content);
         rule.check_file(&ctx)
     }
 
@@ -265,10 +264,7 @@ function MyComponent({ data }) {
     #[test]
     fn non_tsx_skipped() {
         let rule = make_rule();
-        let ctx = ScanContext {
-            file_path: Path::new("test.rs"),
-            content: "fn main() {}",
-        };
+        let ctx = ScanContext::new(Path::new("test.rs"), "fn main() {}");
         assert!(rule.check_file(&ctx).is_empty());
     }
 }
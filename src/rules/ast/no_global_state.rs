@@ -0,0 +1,272 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::ast::parse_file;
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+use std::collections::HashSet;
+
+const FUNCTION_KINDS: &[&str] = &[
+    "function_declaration",
+    "function_expression",
+    "function",
+    "arrow_function",
+    "method_definition",
+    "generator_function_declaration",
+    "generator_function",
+];
+
+/// Flags module-scope `let` bindings that a function reassigns: mutable
+/// singletons shared by every importer of the module.
+///
+/// A binding counts when any function in the file assigns to it (`=`, `+=`,
+/// `++`, ...) without declaring its own variable or parameter of the same
+/// name. Top-level reassignments (setup code run once at import) and `const`
+/// objects that are mutated in place are not reported. Each binding is
+/// reported once, at its declaration.
+pub struct NoGlobalStateRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: Option<String>,
+}
+
+impl NoGlobalStateRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob: config.glob.clone(),
+        })
+    }
+}
+
+impl Rule for NoGlobalStateRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+    fn file_glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        if !ctx.content.contains("let ") {
+            return violations;
+        }
+        let tree = match parse_file(ctx.file_path, ctx.content) {
+            Some(t) => t,
+            None => return violations,
+        };
+        let root = tree.root_node();
+        let source = ctx.content.as_bytes();
+
+        let bindings = module_lets(root, source);
+        if bindings.is_empty() {
+            return violations;
+        }
+        let names: HashSet<&str> = bindings.iter().map(|(name, _)| name.as_str()).collect();
+        let mut reassigned = HashSet::new();
+        find_reassignments(root, source, &names, &HashSet::new(), false, &mut reassigned);
+
+        for (name, node) in &bindings {
+            if !reassigned.contains(name.as_str()) {
+                continue;
+            }
+            let row = node.start_position().row;
+            violations.push(Violation {
+                rule_id: self.id.clone(),
+                severity: self.severity,
+                file: ctx.file_path.to_path_buf(),
+                line: Some(row + 1),
+                column: Some(node.start_position().column + 1),
+                message: format!("{}: `{}` is module-level state reassigned inside a function", self.message, name),
+                suggest: if self.suggest.is_empty() {
+                    vec![Suggestion::new(format!(
+                        "pass `{}` in from the caller, or keep it on an instance created by a factory",
+                        name
+                    ))]
+                } else {
+                    self.suggest.clone()
+                },
+                source_line: ctx.source_line(row),
+                fix: None,
+                origin: None,
+                enclosing: None,
+            });
+        }
+        violations
+    }
+}
+
+/// `let` bindings declared at the top level, including `export let`, with
+/// the identifier node each one is declared at.
+fn module_lets<'a>(root: tree_sitter::Node<'a>, source: &[u8]) -> Vec<(String, tree_sitter::Node<'a>)> {
+    let mut bindings = Vec::new();
+    for statement in (0..root.named_child_count()).filter_map(|i| root.named_child(i)) {
+        let declaration = match statement.kind() {
+            "export_statement" => match statement.child_by_field_name("declaration") {
+                Some(d) => d,
+                None => continue,
+            },
+            _ => statement,
+        };
+        if declaration.kind() != "lexical_declaration"
+            || declaration.child_by_field_name("kind").map(|k| k.kind()) != Some("let")
+        {
+            continue;
+        }
+        for declarator in (0..declaration.named_child_count()).filter_map(|i| declaration.named_child(i)) {
+            if declarator.kind() != "variable_declarator" {
+                continue;
+            }
+            if let Some(name) = declarator.child_by_field_name("name").filter(|n| n.kind() == "identifier") {
+                if let Ok(text) = name.utf8_text(source) {
+                    bindings.push((text.to_string(), name));
+                }
+            }
+        }
+    }
+    bindings
+}
+
+/// Collect the module bindings in `names` that are assigned inside a
+/// function, skipping names a function shadows with its own declaration.
+fn find_reassignments<'s>(
+    node: tree_sitter::Node,
+    source: &'s [u8],
+    names: &HashSet<&str>,
+    shadowed: &HashSet<&'s str>,
+    in_function: bool,
+    reassigned: &mut HashSet<String>,
+) {
+    if FUNCTION_KINDS.contains(&node.kind()) {
+        let mut inner = shadowed.clone();
+        collect_declared(node, source, true, &mut inner);
+        for child in (0..node.child_count()).filter_map(|i| node.child(i)) {
+            find_reassignments(child, source, names, &inner, true, reassigned);
+        }
+        return;
+    }
+
+    if in_function {
+        let target = match node.kind() {
+            "assignment_expression" | "augmented_assignment_expression" => node.child_by_field_name("left"),
+            "update_expression" => node.child_by_field_name("argument"),
+            _ => None,
+        };
+        if let Some(name) = target
+            .filter(|t| t.kind() == "identifier")
+            .and_then(|t| t.utf8_text(source).ok())
+        {
+            if names.contains(name) && !shadowed.contains(name) {
+                reassigned.insert(name.to_string());
+            }
+        }
+    }
+
+    for child in (0..node.child_count()).filter_map(|i| node.child(i)) {
+        find_reassignments(child, source, names, shadowed, in_function, reassigned);
+    }
+}
+
+/// Names a function declares: its parameters and the variables in its body.
+/// Nested functions are left to their own pass.
+fn collect_declared<'s>(node: tree_sitter::Node, source: &'s [u8], is_root: bool, names: &mut HashSet<&'s str>) {
+    if !is_root && FUNCTION_KINDS.contains(&node.kind()) {
+        return;
+    }
+    let declared = match node.kind() {
+        "variable_declarator" => node.child_by_field_name("name"),
+        "required_parameter" | "optional_parameter" => node.child_by_field_name("pattern"),
+        _ => None,
+    };
+    if let Some(name) = declared.filter(|n| n.kind() == "identifier").and_then(|n| n.utf8_text(source).ok()) {
+        names.insert(name);
+    }
+    // JavaScript parameters and a bare arrow parameter are plain identifiers
+    if matches!(node.kind(), "formal_parameters") || (is_root && node.kind() == "arrow_function") {
+        let params = if node.kind() == "arrow_function" {
+            node.child_by_field_name("parameter").into_iter().collect::<Vec<_>>()
+        } else {
+            (0..node.named_child_count()).filter_map(|i| node.named_child(i)).collect()
+        };
+        for param in params.into_iter().filter(|p| p.kind() == "identifier") {
+            if let Ok(name) = param.utf8_text(source) {
+                names.insert(name);
+            }
+        }
+    }
+    for child in (0..node.child_count()).filter_map(|i| node.child(i)) {
+        collect_declared(child, source, false, names);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check(file: &str, content: &str) -> Vec<Violation> {
+        let rule = NoGlobalStateRule::new(&RuleConfig {
+            id: "no-global-state".into(),
+            message: "Module-level mutable state".into(),
+            ..Default::default()
+        })
+        .unwrap();
+        rule.check_file(&ScanContext::new(Path::new(file), content))
+    }
+
+    #[test]
+    fn flags_lets_reassigned_in_functions() {
+        let content = "\
+let client;
+export let count = 0;
+let config = load();
+let untouched = 1;
+
+export function getClient() {
+  client ??= create();
+  return client;
+}
+export const bump = () => { count += 1; };
+config = override(config);
+";
+        let violations = check("src/client.ts", content);
+        let summary: Vec<(usize, &str)> = violations.iter().map(|v| (v.line.unwrap(), v.message.as_str())).collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, "Module-level mutable state: `client` is module-level state reassigned inside a function"),
+                (2, "Module-level mutable state: `count` is module-level state reassigned inside a function"),
+            ]
+        );
+        assert!(violations[0].suggest[0].title.contains("pass `client` in"));
+    }
+
+    #[test]
+    fn shadowed_names_and_consts_pass() {
+        let content = "\
+let cache = null;
+const registry = new Map();
+let total = 0;
+
+function reset(cache) {
+  cache = null;
+}
+function sum(items) {
+  let total = 0;
+  for (const i of items) total++;
+  return total;
+}
+class Store {
+  add(key, value) {
+    registry.set(key, value);
+  }
+}
+";
+        assert!(check("src/store.js", content).is_empty());
+    }
+}
@@ -1,6 +1,6 @@
 use crate::config::{RuleConfig, Severity};
 use crate::rules::ast::{is_component_node, parse_file};
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
 
 /// Flags React component definitions that appear inside another component.
 ///
@@ -11,7 +11,7 @@ pub struct NoNestedComponentsRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
 }
 
@@ -21,7 +21,7 @@ impl NoNestedComponentsRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob: config.glob.clone(),
         })
     }
@@ -70,8 +70,10 @@ impl NoNestedComponentsRule {
                 column: Some(node.start_position().column + 1),
                 message: self.message.clone(),
                 suggest: self.suggest.clone(),
-                source_line: ctx.content.lines().nth(line).map(String::from),
+                source_line: ctx.source_line(line),
                 fix: None,
+                origin: None,
+                enclosing: None,
             });
         }
 
@@ -105,17 +107,14 @@ mod tests {
             id: "no-nested-components".into(),
             severity: Severity::Error,
             message: "Nested component definition".into(),
-            suggest: Some("Move the component to the module level".into()),
+            suggest: vec!["Move the component to the module level".into()],
             glob: Some("**/*.tsx".into()),
             ..Default::default()
         }
     }
 
     fn check(rule: &NoNestedComponentsRule, content: &str) -> Vec<Violation> {
-        let ctx = ScanContext {
-            file_path: Path::new("test.tsx"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.tsx"), content);
         rule.check_file(&ctx)
     }
 
@@ -214,10 +213,7 @@ function A() {
     #[test]
     fn non_tsx_file_skipped() {
         let rule = NoNestedComponentsRule::new(&make_config()).unwrap();
-        let ctx = ScanContext {
-            file_path: Path::new("test.rs"),
-            content: "fn main() {}",
-        };
+        let ctx = ScanContext::new(Path::new("test.rs"), "fn main() {}");
         assert!(rule.check_file(&ctx).is_empty());
     }
 }
@@ -1,6 +1,6 @@
 use crate::config::{RuleConfig, Severity};
 use crate::rules::ast::parse_file;
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
 
 /// Flags object or array literals inside `useEffect`/`useMemo`/`useCallback`
 /// dependency arrays.
@@ -11,7 +11,7 @@ pub struct NoObjectDepArrayRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
 }
 
@@ -21,7 +21,7 @@ impl NoObjectDepArrayRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob: config.glob.clone(),
         })
     }
@@ -106,8 +106,10 @@ impl NoObjectDepArrayRule {
                         column: Some(elem.start_position().column + 1),
                         message: self.message.clone(),
                         suggest: self.suggest.clone(),
-                        source_line: ctx.content.lines().nth(line).map(String::from),
+                        source_line: ctx.source_line(line),
                         fix: None,
+                        origin: None,
+                        enclosing: None,
                     });
                 }
             }
@@ -125,7 +127,7 @@ mod tests {
             id: "no-object-dep-array".into(),
             severity: Severity::Warning,
             message: "Object/array literal in dependency array".into(),
-            suggest: Some("Extract to useMemo or a ref".into()),
+            suggest: vec!["Extract to useMemo or a ref".into()],
             glob: Some("**/*.{tsx,jsx}".into()),
             ..Default::default()
         })
@@ -134,10 +136,7 @@ mod tests {
 
     fn check(content: &str) -> Vec<Violation> {
         let rule = make_rule();
-        let ctx = ScanContext {
-            file_path: Path::new("test.tsx"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.tsx"), content);
         rule.check_file(&ctx)
     }
 
@@ -202,10 +201,7 @@ function MyComponent({ config }) {
     #[test]
     fn non_tsx_skipped() {
         let rule = make_rule();
-        let ctx = ScanContext {
-            file_path: Path::new("test.rs"),
-            content: "fn main() {}",
-        };
+        let ctx = ScanContext::new(Path::new("test.rs"), "fn main() {}");
         assert!(rule.check_file(&ctx).is_empty());
     }
 }
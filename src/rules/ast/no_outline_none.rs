@@ -1,6 +1,6 @@
 use crate::config::{RuleConfig, Severity};
 use crate::rules::ast::{collect_class_attributes, parse_file};
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
 
 /// Flags `outline-none` or `outline-0` in className attributes when there is
 /// no companion `focus-visible:` ring class in the same attribute.
@@ -8,7 +8,7 @@ pub struct NoOutlineNoneRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
 }
 
@@ -18,7 +18,7 @@ impl NoOutlineNoneRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob: config.glob.clone(),
         })
     }
@@ -83,8 +83,10 @@ impl Rule for NoOutlineNoneRule {
                             column: Some(frag.col + col_offset + 1),
                             message: self.message.clone(),
                             suggest: self.suggest.clone(),
-                            source_line: ctx.content.lines().nth(line).map(String::from),
+                            source_line: ctx.source_line(line),
                             fix: None,
+                            origin: None,
+                            enclosing: None,
                         });
                         break;
                     }
@@ -106,7 +108,7 @@ mod tests {
             id: "no-outline-none".into(),
             severity: Severity::Warning,
             message: "outline-none removes the focus indicator".into(),
-            suggest: Some("Use focus-visible:outline-none with a custom focus ring instead".into()),
+            suggest: vec!["Use focus-visible:outline-none with a custom focus ring instead".into()],
             glob: Some("**/*.{tsx,jsx}".into()),
             ..Default::default()
         })
@@ -114,10 +116,7 @@ mod tests {
     }
 
     fn check(rule: &NoOutlineNoneRule, content: &str) -> Vec<Violation> {
-        let ctx = ScanContext {
-            file_path: Path::new("test.tsx"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.tsx"), content);
         rule.check_file(&ctx)
     }
 
@@ -179,10 +178,7 @@ mod tests {
     #[test]
     fn non_tsx_skipped() {
         let rule = make_rule();
-        let ctx = ScanContext {
-            file_path: Path::new("test.rs"),
-            content: "fn main() {}",
-        };
+        let ctx = ScanContext::new(Path::new("test.rs"), "fn main() {}");
         assert!(rule.check_file(&ctx).is_empty());
     }
 
@@ -1,6 +1,6 @@
 use crate::config::{RuleConfig, Severity};
 use crate::rules::ast::{is_component_node, parse_file};
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
 
 /// Flags `new RegExp()` calls inside React component function bodies.
 ///
@@ -11,7 +11,7 @@ pub struct NoRegexpInRenderRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
 }
 
@@ -21,7 +21,7 @@ impl NoRegexpInRenderRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob: config.glob.clone(),
         })
     }
@@ -96,8 +96,10 @@ impl NoRegexpInRenderRule {
                             column: Some(node.start_position().column + 1),
                             message: self.message.clone(),
                             suggest: self.suggest.clone(),
-                            source_line: ctx.content.lines().nth(line).map(String::from),
+                            source_line: ctx.source_line(line),
                             fix: None,
+                            origin: None,
+                            enclosing: None,
                         });
                     }
                 }
@@ -139,7 +141,7 @@ mod tests {
             id: "no-regexp-in-render".into(),
             severity: Severity::Warning,
             message: "new RegExp() in component body re-compiles every render".into(),
-            suggest: Some("Move to module scope or useMemo".into()),
+            suggest: vec!["Move to module scope or useMemo".into()],
             glob: Some("**/*.{tsx,jsx}".into()),
             ..Default::default()
         })
@@ -148,10 +150,7 @@ mod tests {
 
     fn check(content: &str) -> Vec<Violation> {
         let rule = make_rule();
-        let ctx = ScanContext {
-            file_path: Path::new("test.tsx"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.tsx"), content);
         rule.check_file(&ctx)
     }
 
@@ -220,10 +219,7 @@ const MyComponent = () => {
     #[test]
     fn non_tsx_skipped() {
         let rule = make_rule();
-        let ctx = ScanContext {
-            file_path: Path::new("test.rs"),
-            content: "fn main() {}",
-        };
+        let ctx = ScanContext::new(Path::new("test.rs"), "fn main() {}");
         assert!(rule.check_file(&ctx).is_empty());
     }
 }
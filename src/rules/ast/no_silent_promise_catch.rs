@@ -0,0 +1,246 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::ast::parse_file;
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+
+/// Flags promise rejection handlers that swallow the error.
+///
+/// The handler is the argument of `.catch(handler)` or the second argument
+/// of `.then(onFulfilled, handler)`. It is silent when it is:
+///
+/// - a function with an empty body: `() => {}`, `function () {}`
+/// - a function that only logs: `e => console.log(e)`, or `console.log`
+///   itself
+/// - an identity handler, `e => e`, which turns the rejection into a value
+/// - a function that returns `undefined` or `null`: `() => null`
+///
+/// A body holding only a comment counts as a deliberate choice and is not
+/// reported, so `.catch(() => { /* best effort */ })` passes.
+pub struct NoSilentPromiseCatchRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: Option<String>,
+}
+
+impl NoSilentPromiseCatchRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob: config.glob.clone(),
+        })
+    }
+}
+
+impl Rule for NoSilentPromiseCatchRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+    fn file_glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        if !ctx.content.contains(".catch") && !ctx.content.contains(".then") {
+            return violations;
+        }
+        let tree = match parse_file(ctx.file_path, ctx.content) {
+            Some(t) => t,
+            None => return violations,
+        };
+        self.visit(tree.root_node(), ctx.content.as_bytes(), ctx, &mut violations);
+        violations
+    }
+}
+
+impl NoSilentPromiseCatchRule {
+    fn visit(
+        &self,
+        node: tree_sitter::Node,
+        source: &[u8],
+        ctx: &ScanContext,
+        violations: &mut Vec<Violation>,
+    ) {
+        if node.kind() == "call_expression" {
+            if let Some((method, handler)) = rejection_handler(node, source) {
+                if let Some(why) = silent_reason(handler, source) {
+                    let row = handler.start_position().row;
+                    let suggest = if self.suggest.is_empty() {
+                        vec![Suggestion::new(
+                            "handle the error (report it, show it, or recover), rethrow it, or drop the handler",
+                        )]
+                    } else {
+                        self.suggest.clone()
+                    };
+                    violations.push(Violation {
+                        rule_id: self.id.clone(),
+                        severity: self.severity,
+                        file: ctx.file_path.to_path_buf(),
+                        line: Some(row + 1),
+                        column: Some(handler.start_position().column + 1),
+                        message: format!("{}: .{}() handler {}", self.message, method, why),
+                        suggest,
+                        source_line: ctx.source_line(row),
+                        fix: None,
+                        origin: None,
+                        enclosing: None,
+                    });
+                }
+            }
+        }
+
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                self.visit(child, source, ctx, violations);
+            }
+        }
+    }
+}
+
+/// The method name and rejection handler of a `.catch(h)` or `.then(_, h)`
+/// call.
+fn rejection_handler<'t>(call: tree_sitter::Node<'t>, source: &[u8]) -> Option<(&'static str, tree_sitter::Node<'t>)> {
+    let function = call.child_by_field_name("function")?;
+    if function.kind() != "member_expression" {
+        return None;
+    }
+    let (method, index) = match function.child_by_field_name("property")?.utf8_text(source).ok()? {
+        "catch" => ("catch", 0),
+        "then" => ("then", 1),
+        _ => return None,
+    };
+    let arguments = call.child_by_field_name("arguments")?;
+    let handler = (0..arguments.named_child_count())
+        .filter_map(|i| arguments.named_child(i))
+        .filter(|a| a.kind() != "comment")
+        .nth(index)?;
+    Some((method, handler))
+}
+
+/// Why `handler` swallows the rejection, or `None` if it handles it.
+fn silent_reason(handler: tree_sitter::Node, source: &[u8]) -> Option<&'static str> {
+    match handler.kind() {
+        "member_expression" => is_console(handler, source).then_some("only logs the error"),
+        "arrow_function" | "function_expression" | "function" => {
+            let body = handler.child_by_field_name("body")?;
+            if body.kind() == "statement_block" {
+                let statements: Vec<_> = (0..body.named_child_count())
+                    .filter_map(|i| body.named_child(i))
+                    .collect();
+                if statements.is_empty() {
+                    return Some("is empty");
+                }
+                let only_logs = statements.iter().all(|s| {
+                    s.kind() == "expression_statement"
+                        && s.named_child(0).is_some_and(|e| is_console_call(e, source))
+                });
+                return only_logs.then_some("only logs the error");
+            }
+            if is_console_call(body, source) {
+                return Some("only logs the error");
+            }
+            let text = body.utf8_text(source).ok()?;
+            if matches!(text, "undefined" | "null" | "void 0") {
+                return Some("discards the error");
+            }
+            let param = first_param_name(handler, source)?;
+            (body.kind() == "identifier" && text == param).then_some("returns the error as a value")
+        }
+        _ => None,
+    }
+}
+
+fn first_param_name<'a>(function: tree_sitter::Node, source: &'a [u8]) -> Option<&'a str> {
+    let param = match function.child_by_field_name("parameter") {
+        Some(param) => param,
+        None => {
+            let mut param = function.child_by_field_name("parameters")?.named_child(0)?;
+            // TypeScript wraps each parameter (`e: unknown`)
+            if matches!(param.kind(), "required_parameter" | "optional_parameter") {
+                param = param.child_by_field_name("pattern")?;
+            }
+            param
+        }
+    };
+    (param.kind() == "identifier").then(|| param.utf8_text(source).ok()).flatten()
+}
+
+/// `console.log`, `console.error`, ...
+fn is_console(node: tree_sitter::Node, source: &[u8]) -> bool {
+    node.kind() == "member_expression"
+        && node
+            .child_by_field_name("object")
+            .and_then(|o| o.utf8_text(source).ok())
+            == Some("console")
+}
+
+fn is_console_call(node: tree_sitter::Node, source: &[u8]) -> bool {
+    node.kind() == "call_expression"
+        && node
+            .child_by_field_name("function")
+            .is_some_and(|f| is_console(f, source))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check(content: &str) -> Vec<Violation> {
+        let config = RuleConfig {
+            id: "no-silent-promise-catch".into(),
+            message: "Rejection swallowed".into(),
+            ..Default::default()
+        };
+        let rule = NoSilentPromiseCatchRule::new(&config).unwrap();
+        rule.check_file(&ScanContext::new(Path::new("api.ts"), content))
+    }
+
+    #[test]
+    fn flags_handlers_that_swallow_errors() {
+        let content = "\
+load().catch(() => {});
+load().catch(console.error);
+load().catch((e: unknown) => console.log(e));
+load().catch(function (err) { console.warn(err); });
+load().catch((e) => e);
+load().then(render, () => null);
+";
+        let found: Vec<_> = check(content)
+            .iter()
+            .map(|v| (v.line.unwrap(), v.column.unwrap(), v.message.clone()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (1, 14, "Rejection swallowed: .catch() handler is empty".to_string()),
+                (2, 14, "Rejection swallowed: .catch() handler only logs the error".to_string()),
+                (3, 14, "Rejection swallowed: .catch() handler only logs the error".to_string()),
+                (4, 14, "Rejection swallowed: .catch() handler only logs the error".to_string()),
+                (5, 14, "Rejection swallowed: .catch() handler returns the error as a value".to_string()),
+                (6, 21, "Rejection swallowed: .then() handler discards the error".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn allows_handlers_that_handle_errors() {
+        let content = "\
+load().catch((e) => { report(e); });
+load().catch((e) => { console.error(e); throw e; });
+load().catch(() => { /* best effort: the cache is optional */ });
+load().catch(handleError);
+load().catch((e) => fallback);
+load().then(render);
+load().then(() => {}, showError);
+try { run(); } catch (e) {}
+";
+        assert!(check(content).is_empty());
+    }
+}
@@ -0,0 +1,214 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::ast::parse_file;
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+use regex::Regex;
+use std::sync::LazyLock;
+
+/// Function and method names that run a raw SQL string.
+const QUERY_FUNCTIONS: &[&str] = &[
+    "query",
+    "execute",
+    "raw",
+    "unsafe",
+    "sql",
+    "$queryRaw",
+    "$executeRaw",
+    "$queryRawUnsafe",
+    "$executeRawUnsafe",
+];
+
+static SQL_KEYWORDS: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)\b(select|insert\s+into|update|delete\s+from|where|values|order\s+by|group\s+by|join)\b")
+        .unwrap()
+});
+
+/// Flags SQL built by template-literal interpolation and passed to a query
+/// function.
+///
+/// `db.query(`SELECT * FROM users WHERE id = ${id}`)` splices `id` into the
+/// SQL text, which is an injection risk. The rule reports calls to
+/// `query`, `execute`, `raw`, `unsafe`, `sql` and Prisma's `$queryRaw*` /
+/// `$executeRaw*` (as functions or methods) whose first argument is a
+/// template literal with both `${...}` and an SQL keyword. Tagged templates
+/// (`sql`...``, `prisma.$queryRaw`...``) are left alone: the tag receives
+/// the values separately and sends them as parameters.
+pub struct NoSqlInterpolationRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: Option<String>,
+}
+
+impl NoSqlInterpolationRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob: config.glob.clone(),
+        })
+    }
+}
+
+impl Rule for NoSqlInterpolationRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+    fn file_glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let tree = match parse_file(ctx.file_path, ctx.content) {
+            Some(t) => t,
+            None => return violations,
+        };
+        self.visit(tree.root_node(), ctx.content.as_bytes(), ctx, &mut violations);
+        violations
+    }
+}
+
+impl NoSqlInterpolationRule {
+    fn visit(
+        &self,
+        node: tree_sitter::Node,
+        source: &[u8],
+        ctx: &ScanContext,
+        violations: &mut Vec<Violation>,
+    ) {
+        if node.kind() == "call_expression" {
+            if let Some((callee, template)) = interpolated_query(&node, source) {
+                let row = template.start_position().row;
+                let suggest = if self.suggest.is_empty() {
+                    vec![Suggestion::new(format!(
+                        "pass values as query parameters instead of interpolating them into the SQL given to {}()",
+                        callee
+                    ))]
+                } else {
+                    self.suggest.clone()
+                };
+                violations.push(Violation {
+                    rule_id: self.id.clone(),
+                    severity: self.severity,
+                    file: ctx.file_path.to_path_buf(),
+                    line: Some(row + 1),
+                    column: Some(template.start_position().column + 1),
+                    message: self.message.clone(),
+                    suggest,
+                    source_line: ctx.source_line(row),
+                    fix: None,
+                    origin: None,
+                    enclosing: None,
+                });
+            }
+        }
+
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                self.visit(child, source, ctx, violations);
+            }
+        }
+    }
+}
+
+/// The callee and template argument of a query call whose first argument
+/// interpolates values into SQL text.
+fn interpolated_query<'t>(
+    call: &tree_sitter::Node<'t>,
+    source: &[u8],
+) -> Option<(String, tree_sitter::Node<'t>)> {
+    let function = call.child_by_field_name("function")?;
+    let name = match function.kind() {
+        "identifier" => function.utf8_text(source).ok()?,
+        "member_expression" => function.child_by_field_name("property")?.utf8_text(source).ok()?,
+        _ => return None,
+    };
+    if !QUERY_FUNCTIONS.contains(&name) {
+        return None;
+    }
+
+    // A tagged template's `arguments` is the template itself
+    let arguments = call.child_by_field_name("arguments")?;
+    if arguments.kind() != "arguments" {
+        return None;
+    }
+    let template = arguments.named_child(0).filter(|a| a.kind() == "template_string")?;
+
+    // The literal parts of the template, with substitutions blanked out
+    let mut text = String::new();
+    let mut interpolated = false;
+    for i in 0..template.named_child_count() {
+        let part = template.named_child(i)?;
+        match part.kind() {
+            "template_substitution" => {
+                interpolated = true;
+                text.push(' ');
+            }
+            _ => text.push_str(part.utf8_text(source).ok()?),
+        }
+    }
+
+    (interpolated && SQL_KEYWORDS.is_match(&text))
+        .then(|| (function.utf8_text(source).unwrap_or(name).to_string(), template))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check(content: &str) -> Vec<Violation> {
+        let rule = NoSqlInterpolationRule::new(&RuleConfig {
+            id: "no-sql-interpolation".into(),
+            severity: Severity::Error,
+            message: "SQL built with template interpolation".into(),
+            ..Default::default()
+        })
+        .unwrap();
+        let ctx = ScanContext::new(Path::new("repo.ts"), content);
+        rule.check_file(&ctx)
+    }
+
+    #[test]
+    fn flags_interpolated_sql_passed_to_query_functions() {
+        let content = "\
+const user = await db.query(`SELECT * FROM users WHERE id = ${id}`);
+await prisma.$queryRawUnsafe(`DELETE FROM posts WHERE author = '${name}'`);
+const rows = sql(`select name from teams where slug = ${slug}`);
+";
+        let violations = check(content);
+        assert_eq!(violations.len(), 3);
+        assert_eq!((violations[0].line, violations[0].column), (Some(1), Some(29)));
+        assert_eq!(
+            violations[0].suggest_text(),
+            Some("pass values as query parameters instead of interpolating them into the SQL given to db.query()")
+        );
+        assert_eq!(violations[1].line, Some(2));
+        assert_eq!(violations[2].line, Some(3));
+    }
+
+    #[test]
+    fn allows_tagged_templates_and_parameters() {
+        let content = "\
+const a = await sql`SELECT * FROM users WHERE id = ${id}`;
+const b = await prisma.$queryRaw`SELECT * FROM users WHERE email = ${email}`;
+const c = await db.query('SELECT * FROM users WHERE id = $1', [id]);
+const d = await db.query(`SELECT * FROM users WHERE active`);
+";
+        assert!(check(content).is_empty());
+    }
+
+    #[test]
+    fn ignores_non_sql_templates_and_other_functions() {
+        let content = "\
+const params = router.query(`${base}/users`);
+logger.info(`SELECT took ${ms}ms`);
+";
+        assert!(check(content).is_empty());
+    }
+}
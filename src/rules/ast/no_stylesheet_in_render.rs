@@ -0,0 +1,275 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::ast::{is_component_node, parse_file};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+
+/// Flags React Native styles that are rebuilt on every render.
+///
+/// Inside a component body (outside `useMemo`/`useCallback`) this reports
+/// `StyleSheet.create()` calls and static object literals passed to a style
+/// prop (`style`, `contentContainerStyle`, ...), either directly or inside a
+/// style array. Objects with any computed value are left alone, since those
+/// genuinely depend on render state.
+pub struct NoStylesheetInRenderRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: Option<String>,
+}
+
+impl NoStylesheetInRenderRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob: config.glob.clone(),
+        })
+    }
+}
+
+impl Rule for NoStylesheetInRenderRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn file_glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let tree = match parse_file(ctx.file_path, ctx.content) {
+            Some(t) => t,
+            None => return violations,
+        };
+        let source = ctx.content.as_bytes();
+        self.find_components(tree.root_node(), source, ctx, &mut violations);
+        violations
+    }
+}
+
+impl NoStylesheetInRenderRule {
+    fn find_components(
+        &self,
+        node: tree_sitter::Node,
+        source: &[u8],
+        ctx: &ScanContext,
+        violations: &mut Vec<Violation>,
+    ) {
+        if is_component_node(&node, source) {
+            self.find_styles(node, source, ctx, violations);
+            return;
+        }
+
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                self.find_components(child, source, ctx, violations);
+            }
+        }
+    }
+
+    fn find_styles(
+        &self,
+        node: tree_sitter::Node,
+        source: &[u8],
+        ctx: &ScanContext,
+        violations: &mut Vec<Violation>,
+    ) {
+        if is_memo_or_callback_call(&node, source) {
+            return;
+        }
+
+        if is_stylesheet_create(&node, source) {
+            self.report(node, ctx, violations);
+        } else if node.kind() == "jsx_attribute" && is_style_prop(&node, source) {
+            if let Some(value) = node
+                .named_child(node.named_child_count().saturating_sub(1))
+                .filter(|v| v.kind() == "jsx_expression")
+                .and_then(|v| v.named_child(0))
+            {
+                for object in static_style_objects(value) {
+                    self.report(object, ctx, violations);
+                }
+            }
+        }
+
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                // Nested components are visited by find_components
+                if is_component_node(&child, source) {
+                    self.find_components(child, source, ctx, violations);
+                    continue;
+                }
+                self.find_styles(child, source, ctx, violations);
+            }
+        }
+    }
+
+    fn report(&self, node: tree_sitter::Node, ctx: &ScanContext, violations: &mut Vec<Violation>) {
+        let row = node.start_position().row;
+        violations.push(Violation {
+            rule_id: self.id.clone(),
+            severity: self.severity,
+            file: ctx.file_path.to_path_buf(),
+            line: Some(row + 1),
+            column: Some(node.start_position().column + 1),
+            message: self.message.clone(),
+            suggest: self.suggest.clone(),
+            source_line: ctx.source_line(row),
+            fix: None,
+            origin: None,
+            enclosing: None,
+        });
+    }
+}
+
+fn is_stylesheet_create(node: &tree_sitter::Node, source: &[u8]) -> bool {
+    node.kind() == "call_expression"
+        && node
+            .child_by_field_name("function")
+            .and_then(|f| f.utf8_text(source).ok())
+            .is_some_and(|name| name == "StyleSheet.create")
+}
+
+fn is_memo_or_callback_call(node: &tree_sitter::Node, source: &[u8]) -> bool {
+    node.kind() == "call_expression"
+        && node
+            .child_by_field_name("function")
+            .filter(|f| f.kind() == "identifier")
+            .and_then(|f| f.utf8_text(source).ok())
+            .is_some_and(|name| name == "useMemo" || name == "useCallback")
+}
+
+/// `style`, or any prop ending in `Style` (`contentContainerStyle`, ...).
+fn is_style_prop(attr: &tree_sitter::Node, source: &[u8]) -> bool {
+    attr.child(0)
+        .and_then(|n| n.utf8_text(source).ok())
+        .is_some_and(|name| name == "style" || name.ends_with("Style"))
+}
+
+/// Static object literals in a style prop value: the value itself, or the
+/// elements of a style array.
+fn static_style_objects(value: tree_sitter::Node) -> Vec<tree_sitter::Node> {
+    match value.kind() {
+        "object" if is_static(&value) => vec![value],
+        "array" => (0..value.named_child_count())
+            .filter_map(|i| value.named_child(i))
+            .filter(|el| el.kind() == "object" && is_static(el))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Whether a value is built only from literals, so it could be hoisted to
+/// module scope unchanged.
+fn is_static(node: &tree_sitter::Node) -> bool {
+    match node.kind() {
+        "number" | "string" | "true" | "false" | "null" => true,
+        "template_string" => (0..node.named_child_count())
+            .filter_map(|i| node.named_child(i))
+            .all(|child| child.kind() != "template_substitution"),
+        "unary_expression" => node
+            .child_by_field_name("argument")
+            .is_some_and(|arg| arg.kind() == "number"),
+        "object" | "array" => (0..node.named_child_count())
+            .filter_map(|i| node.named_child(i))
+            .filter(|child| child.kind() != "comment")
+            .all(|child| is_static(&child)),
+        "pair" => {
+            let key_static = node
+                .child_by_field_name("key")
+                .is_some_and(|k| k.kind() != "computed_property_name");
+            key_static
+                && node
+                    .child_by_field_name("value")
+                    .is_some_and(|v| is_static(&v))
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn make_rule() -> NoStylesheetInRenderRule {
+        NoStylesheetInRenderRule::new(&RuleConfig {
+            id: "rn-no-stylesheet-in-render".into(),
+            severity: Severity::Warning,
+            message: "Styles created in the component body are rebuilt every render".into(),
+            suggest: vec!["Move StyleSheet.create() and static style objects to module scope".into()],
+            glob: Some("**/*.{tsx,jsx}".into()),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn check(content: &str) -> Vec<Violation> {
+        let ctx = ScanContext::new(Path::new("test.tsx"), content);
+        make_rule().check_file(&ctx)
+    }
+
+    #[test]
+    fn stylesheet_create_in_component() {
+        let content = r#"function Card() {
+  const styles = StyleSheet.create({ card: { padding: 16 } });
+  return <View style={styles.card} />;
+}"#;
+        let violations = check(content);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line, Some(2));
+    }
+
+    #[test]
+    fn module_scope_stylesheet_passes() {
+        let content = r#"const styles = StyleSheet.create({ card: { padding: 16 } });
+function Card() {
+  return <View style={styles.card} />;
+}"#;
+        assert!(check(content).is_empty());
+    }
+
+    #[test]
+    fn static_inline_styles_are_flagged() {
+        let content = r#"function Card() {
+  return (
+    <ScrollView contentContainerStyle={{ padding: 16, gap: -4 }}>
+      <View style={[styles.row, { marginTop: 8, borderStyle: "dashed" }]} />
+      <Text style={{ fontWeight: `600`, transform: [{ scale: 1.1 }] }} />
+    </ScrollView>
+  );
+}"#;
+        let lines: Vec<_> = check(content).iter().map(|v| v.line).collect();
+        assert_eq!(lines, [Some(3), Some(4), Some(5)]);
+    }
+
+    #[test]
+    fn dynamic_and_memoized_styles_pass() {
+        let content = r#"function Card({ active, width }) {
+  const themed = useMemo(() => StyleSheet.create({ card: { padding: 16 } }), []);
+  return (
+    <View style={{ opacity: active ? 1 : 0.5 }}>
+      <View style={[styles.row, { width }]} />
+      <View style={{ [edge]: 4 }} />
+      <View style={styles.row} />
+    </View>
+  );
+}"#;
+        assert!(check(content).is_empty());
+    }
+
+    #[test]
+    fn non_components_are_ignored() {
+        let content = r#"function makeStyles() {
+  return StyleSheet.create({ card: { padding: 16 } });
+}"#;
+        assert!(check(content).is_empty());
+    }
+}
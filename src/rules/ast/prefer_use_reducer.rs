@@ -1,6 +1,6 @@
 use crate::config::{RuleConfig, Severity};
 use crate::rules::ast::{count_calls_in_scope, is_component_node, parse_file};
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
 
 /// Flags React components that have too many `useState` calls.
 ///
@@ -11,7 +11,7 @@ pub struct PreferUseReducerRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
     max_count: usize,
 }
@@ -22,7 +22,7 @@ impl PreferUseReducerRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob: config.glob.clone(),
             max_count: config.max_count.unwrap_or(4),
         })
@@ -74,8 +74,10 @@ impl PreferUseReducerRule {
                     column: Some(1),
                     message: self.message.clone(),
                     suggest: self.suggest.clone(),
-                    source_line: ctx.content.lines().nth(line).map(String::from),
+                    source_line: ctx.source_line(line),
                     fix: None,
+                    origin: None,
+                    enclosing: None,
                 });
             }
         }
@@ -98,7 +100,7 @@ mod tests {
             id: "prefer-use-reducer".into(),
             severity: Severity::Warning,
             message: format!("Component has {}+ useState calls", max_count),
-            suggest: Some("Consider useReducer for related state".into()),
+            suggest: vec!["Consider useReducer for related state".into()],
             glob: Some("**/*.tsx".into()),
             max_count: Some(max_count),
             ..Default::default()
@@ -106,10 +108,7 @@ mod tests {
     }
 
     fn check(rule: &PreferUseReducerRule, content: &str) -> Vec<Violation> {
-        let ctx = ScanContext {
-            file_path: Path::new("test.tsx"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.tsx"), content);
         rule.check_file(&ctx)
     }
 
@@ -219,10 +218,7 @@ function useMyHook() {
     #[test]
     fn non_tsx_file_skipped() {
         let rule = PreferUseReducerRule::new(&make_config(2)).unwrap();
-        let ctx = ScanContext {
-            file_path: Path::new("test.rs"),
-            content: "fn main() {}",
-        };
+        let ctx = ScanContext::new(Path::new("test.rs"), "fn main() {}");
         assert!(rule.check_file(&ctx).is_empty());
     }
 }
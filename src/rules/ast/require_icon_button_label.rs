@@ -0,0 +1,312 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::ast::parse_file;
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+
+/// Tags treated as buttons.
+const BUTTON_TAGS: &[&str] = &["button", "Button"];
+
+/// Packages whose named (or default) exports are icon components.
+const ICON_PACKAGES: &[&str] = &[
+    "lucide-react",
+    "@heroicons/react",
+    "react-icons",
+    "@radix-ui/react-icons",
+    "@tabler/icons-react",
+    "@phosphor-icons/react",
+    "@mui/icons-material",
+];
+
+/// Flags icon-only buttons that have no accessible name.
+///
+/// A button is icon-only when, ignoring whitespace, its only child is an
+/// `<svg>` or an icon component: a name imported from a known icon package,
+/// or a PascalCase name starting or ending with `Icon` (`<Icon />`,
+/// `<IconX />`, `<CloseIcon />`, `<Icons.Close />`). Such buttons need
+/// `aria-label` or `aria-labelledby`. Buttons that spread props are skipped,
+/// since the label may come from the spread.
+pub struct RequireIconButtonLabelRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: Option<String>,
+}
+
+impl RequireIconButtonLabelRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob: config.glob.clone(),
+        })
+    }
+}
+
+impl Rule for RequireIconButtonLabelRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn file_glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let tree = match parse_file(ctx.file_path, ctx.content) {
+            Some(t) => t,
+            None => return violations,
+        };
+        let source = ctx.content.as_bytes();
+        let icons = icon_imports(tree.root_node(), source);
+        self.visit(tree.root_node(), source, ctx, &icons, &mut violations);
+        violations
+    }
+}
+
+impl RequireIconButtonLabelRule {
+    fn visit(
+        &self,
+        node: tree_sitter::Node,
+        source: &[u8],
+        ctx: &ScanContext,
+        icons: &[String],
+        violations: &mut Vec<Violation>,
+    ) {
+        if node.kind() == "jsx_element" {
+            if let Some(open) = node.child_by_field_name("open_tag") {
+                let is_button = tag_name(&open, source).is_some_and(|n| BUTTON_TAGS.contains(&n));
+                if is_button && !is_labelled(&open, source) && is_icon_only(&node, source, icons) {
+                    let row = open.start_position().row;
+                    violations.push(Violation {
+                        rule_id: self.id.clone(),
+                        severity: self.severity,
+                        file: ctx.file_path.to_path_buf(),
+                        line: Some(row + 1),
+                        column: Some(open.start_position().column + 1),
+                        message: self.message.clone(),
+                        suggest: self.suggest.clone(),
+                        source_line: ctx.source_line(row),
+                        fix: None,
+                        origin: None,
+                        enclosing: None,
+                    });
+                }
+            }
+        }
+
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                self.visit(child, source, ctx, icons, violations);
+            }
+        }
+    }
+}
+
+/// The tag name of an opening or self-closing element.
+fn tag_name<'a>(node: &tree_sitter::Node, source: &'a [u8]) -> Option<&'a str> {
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            if child.kind() == "identifier" || child.kind() == "member_expression" {
+                return child.utf8_text(source).ok();
+            }
+        }
+    }
+    None
+}
+
+/// Whether the element has an accessible-name attribute, or spreads props
+/// that might supply one.
+fn is_labelled(node: &tree_sitter::Node, source: &[u8]) -> bool {
+    for i in 0..node.child_count() {
+        let Some(child) = node.child(i) else { continue };
+        match child.kind() {
+            "jsx_attribute" => {
+                let name = child.child(0).and_then(|n| n.utf8_text(source).ok());
+                if matches!(name, Some("aria-label" | "aria-labelledby")) {
+                    return true;
+                }
+            }
+            "jsx_expression" => return true,
+            _ => {}
+        }
+    }
+    false
+}
+
+/// Whether the element's only non-whitespace child is an icon.
+fn is_icon_only(element: &tree_sitter::Node, source: &[u8], icons: &[String]) -> bool {
+    let mut children = Vec::new();
+    for i in 0..element.named_child_count() {
+        let Some(child) = element.named_child(i) else { continue };
+        match child.kind() {
+            "jsx_opening_element" | "jsx_closing_element" => {}
+            "jsx_text" if child.utf8_text(source).is_ok_and(|t| t.trim().is_empty()) => {}
+            _ => children.push(child),
+        }
+    }
+    let [child] = children.as_slice() else {
+        return false;
+    };
+    let name = match child.kind() {
+        "jsx_self_closing_element" => tag_name(child, source),
+        "jsx_element" => child
+            .child_by_field_name("open_tag")
+            .and_then(|open| tag_name(&open, source)),
+        _ => None,
+    };
+    name.is_some_and(|n| is_icon_name(n, icons))
+}
+
+fn is_icon_name(name: &str, icons: &[String]) -> bool {
+    if name == "svg" || icons.iter().any(|i| i == name) {
+        return true;
+    }
+    // `Icons.Close` is a member of an icon namespace
+    let base = name.split('.').next().unwrap_or(name);
+    base == "Icons"
+        || base.starts_with("Icon") && (base.len() == 4 || base[4..].starts_with(char::is_uppercase))
+        || (name.ends_with("Icon") && name.starts_with(char::is_uppercase))
+}
+
+/// Local names of components imported from an icon package.
+fn icon_imports(root: tree_sitter::Node, source: &[u8]) -> Vec<String> {
+    let mut names = Vec::new();
+    for i in 0..root.child_count() {
+        let Some(stmt) = root.child(i).filter(|n| n.kind() == "import_statement") else {
+            continue;
+        };
+        let from_icons = stmt
+            .child_by_field_name("source")
+            .and_then(|s| s.utf8_text(source).ok())
+            .map(|s| s.trim_matches(['"', '\'']))
+            .is_some_and(|module| {
+                ICON_PACKAGES
+                    .iter()
+                    .any(|p| module == *p || module.strip_prefix(p).is_some_and(|r| r.starts_with('/')))
+            });
+        if from_icons {
+            collect_bindings(stmt, source, &mut names);
+        }
+    }
+    names
+}
+
+/// Push the local names an import statement binds.
+fn collect_bindings(node: tree_sitter::Node, source: &[u8], names: &mut Vec<String>) {
+    for i in 0..node.named_child_count() {
+        let Some(child) = node.named_child(i) else { continue };
+        match child.kind() {
+            "import_clause" | "named_imports" => collect_bindings(child, source, names),
+            "identifier" => names.extend(child.utf8_text(source).ok().map(String::from)),
+            "import_specifier" => {
+                let local = child
+                    .child_by_field_name("alias")
+                    .or_else(|| child.child_by_field_name("name"));
+                names.extend(local.and_then(|n| n.utf8_text(source).ok()).map(String::from));
+            }
+            _ => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn make_rule() -> RequireIconButtonLabelRule {
+        RequireIconButtonLabelRule::new(&RuleConfig {
+            id: "require-icon-button-label".into(),
+            severity: Severity::Error,
+            message: "Icon-only button needs an accessible name".into(),
+            suggest: vec!["Add aria-label=\"...\" describing the action".into()],
+            glob: Some("**/*.{tsx,jsx}".into()),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn check(content: &str) -> Vec<Violation> {
+        let ctx = ScanContext::new(Path::new("test.tsx"), content);
+        make_rule().check_file(&ctx)
+    }
+
+    #[test]
+    fn svg_only_button_without_label() {
+        let violations = check(
+            r#"function A() {
+  return (
+    <button onClick={close}>
+      <svg viewBox="0 0 10 10"><path d="M0 0" /></svg>
+    </button>
+  );
+}"#,
+        );
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line, Some(3));
+    }
+
+    #[test]
+    fn labelled_icon_buttons_pass() {
+        let content = r#"function A() {
+  return (
+    <div>
+      <button aria-label="Close"><XIcon /></button>
+      <Button aria-labelledby="close-label"><Icon name="x" /></Button>
+      <button {...props}><XIcon /></button>
+    </div>
+  );
+}"#;
+        assert!(check(content).is_empty());
+    }
+
+    #[test]
+    fn buttons_with_text_pass() {
+        let content = r#"function A() {
+  return (
+    <div>
+      <button><XIcon /> Close</button>
+      <button><XIcon /><span className="sr-only">Close</span></button>
+      <button>{label}</button>
+      <button><Avatar /></button>
+    </div>
+  );
+}"#;
+        assert!(check(content).is_empty());
+    }
+
+    #[test]
+    fn icon_package_imports_count_as_icons() {
+        let content = r#"import { X, ChevronLeft as Back } from "lucide-react";
+import { TrashIcon } from "@heroicons/react/24/outline";
+function A() {
+  return (
+    <div>
+      <Button variant="ghost"><X /></Button>
+      <button><Back className="h-4 w-4" /></button>
+      <button><TrashIcon /></button>
+      <button><Icons.Spinner /></button>
+    </div>
+  );
+}"#;
+        let lines: Vec<_> = check(content).iter().map(|v| v.line).collect();
+        assert_eq!(lines, [Some(6), Some(7), Some(8), Some(9)]);
+    }
+
+    #[test]
+    fn icon_name_heuristic() {
+        assert!(is_icon_name("Icon", &[]));
+        assert!(is_icon_name("IconTrash", &[]));
+        assert!(is_icon_name("CloseIcon", &[]));
+        assert!(is_icon_name("Icons.Close", &[]));
+        assert!(!is_icon_name("Iconography", &[]));
+        assert!(!is_icon_name("Avatar", &[]));
+    }
+}
@@ -1,17 +1,19 @@
 use crate::config::{RuleConfig, Severity};
 use crate::rules::ast::parse_file;
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
 
-/// Flags `<img>` elements that are missing an `alt` attribute.
+/// Flags `<img>` elements, and `next/image` components, that are missing an
+/// `alt` attribute.
 ///
 /// Walks the AST for `jsx_self_closing_element` and `jsx_opening_element`
-/// nodes with tag name `img` and checks that at least one child
-/// `jsx_attribute` has the name `alt`.
+/// nodes with tag name `img` (or the local name of the `next/image` default
+/// import) and checks that at least one child `jsx_attribute` has the name
+/// `alt`.
 pub struct RequireImgAltRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
 }
 
@@ -21,7 +23,7 @@ impl RequireImgAltRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob: config.glob.clone(),
         })
     }
@@ -47,22 +49,58 @@ impl Rule for RequireImgAltRule {
             None => return violations,
         };
         let source = ctx.content.as_bytes();
-        self.visit(tree.root_node(), source, ctx, &mut violations);
+        let image_names = next_image_imports(tree.root_node(), source);
+        self.visit(tree.root_node(), source, ctx, &image_names, &mut violations);
         violations
     }
 }
 
+/// Modules whose default export is Next.js's `<Image>` component.
+const NEXT_IMAGE_MODULES: &[&str] = &["next/image", "next/legacy/image"];
+
+/// Local names bound to the default import of a `next/image` module, e.g.
+/// `Image` for `import Image from "next/image"`.
+fn next_image_imports(root: tree_sitter::Node, source: &[u8]) -> Vec<String> {
+    let mut names = Vec::new();
+    for i in 0..root.child_count() {
+        let Some(stmt) = root.child(i).filter(|n| n.kind() == "import_statement") else {
+            continue;
+        };
+        let from_next_image = stmt
+            .child_by_field_name("source")
+            .and_then(|s| s.utf8_text(source).ok())
+            .is_some_and(|s| NEXT_IMAGE_MODULES.contains(&s.trim_matches(['"', '\''])));
+        if !from_next_image {
+            continue;
+        }
+        for j in 0..stmt.child_count() {
+            let Some(clause) = stmt.child(j).filter(|n| n.kind() == "import_clause") else {
+                continue;
+            };
+            for k in 0..clause.child_count() {
+                if let Some(default) = clause.child(k).filter(|n| n.kind() == "identifier") {
+                    if let Ok(name) = default.utf8_text(source) {
+                        names.push(name.to_string());
+                    }
+                }
+            }
+        }
+    }
+    names
+}
+
 impl RequireImgAltRule {
     fn visit(
         &self,
         node: tree_sitter::Node,
         source: &[u8],
         ctx: &ScanContext,
+        image_names: &[String],
         violations: &mut Vec<Violation>,
     ) {
         let kind = node.kind();
         if kind == "jsx_self_closing_element" || kind == "jsx_opening_element" {
-            if self.is_img_tag(&node, source) && !self.has_alt_attribute(&node, source) {
+            if self.is_img_tag(&node, source, image_names) && !self.has_alt_attribute(&node, source) {
                 let row = node.start_position().row;
                 violations.push(Violation {
                     rule_id: self.id.clone(),
@@ -72,25 +110,27 @@ impl RequireImgAltRule {
                     column: Some(node.start_position().column + 1),
                     message: self.message.clone(),
                     suggest: self.suggest.clone(),
-                    source_line: ctx.content.lines().nth(row).map(String::from),
+                    source_line: ctx.source_line(row),
                     fix: None,
+                    origin: None,
+                    enclosing: None,
                 });
             }
         }
 
         for i in 0..node.child_count() {
             if let Some(child) = node.child(i) {
-                self.visit(child, source, ctx, violations);
+                self.visit(child, source, ctx, image_names, violations);
             }
         }
     }
 
-    fn is_img_tag(&self, node: &tree_sitter::Node, source: &[u8]) -> bool {
+    fn is_img_tag(&self, node: &tree_sitter::Node, source: &[u8], image_names: &[String]) -> bool {
         for i in 0..node.child_count() {
             if let Some(child) = node.child(i) {
                 if child.kind() == "identifier" || child.kind() == "member_expression" {
                     if let Ok(name) = child.utf8_text(source) {
-                        return name == "img";
+                        return name == "img" || image_names.iter().any(|n| n == name);
                     }
                 }
             }
@@ -126,7 +166,7 @@ mod tests {
             id: "require-img-alt".into(),
             severity: Severity::Error,
             message: "img element must have an alt attribute".into(),
-            suggest: Some("Add alt=\"description\" or alt=\"\" for decorative images".into()),
+            suggest: vec!["Add alt=\"description\" or alt=\"\" for decorative images".into()],
             glob: Some("**/*.{tsx,jsx}".into()),
             ..Default::default()
         })
@@ -134,10 +174,7 @@ mod tests {
     }
 
     fn check(rule: &RequireImgAltRule, content: &str) -> Vec<Violation> {
-        let ctx = ScanContext {
-            file_path: Path::new("test.tsx"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.tsx"), content);
         rule.check_file(&ctx)
     }
 
@@ -184,6 +221,34 @@ mod tests {
         assert!(violations.is_empty());
     }
 
+    #[test]
+    fn next_image_without_alt_violation() {
+        let rule = make_rule();
+        let content = r#"import Image from "next/image";
+function App() {
+  return (
+    <>
+      <Image src="/a.jpg" width={10} height={10} />
+      <Image src="/b.jpg" alt="" width={10} height={10} />
+    </>
+  );
+}"#;
+        let violations = check(&rule, content);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line, Some(5));
+    }
+
+    #[test]
+    fn next_image_renamed_import() {
+        let rule = make_rule();
+        let content = r#"import NextImg from 'next/legacy/image';
+import Image from "./my-image";
+function App() { return <div><NextImg src="/a.jpg" /><Image src="/b.jpg" /></div>; }"#;
+        let violations = check(&rule, content);
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].source_line.as_deref().unwrap().contains("NextImg"));
+    }
+
     #[test]
     fn multiple_imgs_mixed() {
         let rule = make_rule();
@@ -203,10 +268,7 @@ mod tests {
     #[test]
     fn non_tsx_file_skipped() {
         let rule = make_rule();
-        let ctx = ScanContext {
-            file_path: Path::new("test.rs"),
-            content: "fn main() {}",
-        };
+        let ctx = ScanContext::new(Path::new("test.rs"), "fn main() {}");
         assert!(rule.check_file(&ctx).is_empty());
     }
 }
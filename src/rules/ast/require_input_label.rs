@@ -0,0 +1,331 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::ast::{is_component_node, parse_file};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+
+/// Native form controls that need a label.
+const CONTROL_TAGS: &[&str] = &["input", "select", "textarea"];
+
+/// Tags that label their contents or the control named by `htmlFor`.
+/// `Label` covers shadcn and Radix, which render a native `<label>`.
+const LABEL_TAGS: &[&str] = &["label", "Label"];
+
+/// `<input type>` values that don't take a label.
+const UNLABELLED_INPUT_TYPES: &[&str] = &["hidden", "submit", "reset", "button", "image"];
+
+/// Flags form controls without an accessible label.
+///
+/// `<input>`, `<select>`, `<textarea>` and any names listed in `components`
+/// pass when they have `aria-label` or `aria-labelledby`, sit inside a
+/// `<label>`, or have an `id` matched by a `<label htmlFor>` in the same
+/// component. Controls that spread props are skipped, as are inputs whose
+/// `type` is hidden or a button.
+pub struct RequireInputLabelRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: Option<String>,
+    components: Vec<String>,
+}
+
+/// A string literal attribute value, or an expression we can't evaluate.
+#[derive(PartialEq)]
+enum AttrValue {
+    Literal(String),
+    Dynamic,
+}
+
+struct Control {
+    component: Option<usize>,
+    id: Option<AttrValue>,
+    row: usize,
+    column: usize,
+}
+
+#[derive(Default)]
+struct Walk {
+    controls: Vec<Control>,
+    /// `htmlFor` values of labels, with their enclosing component.
+    label_targets: Vec<(Option<usize>, AttrValue)>,
+}
+
+impl RequireInputLabelRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob: config.glob.clone(),
+            components: config.components.clone(),
+        })
+    }
+
+    fn is_control(&self, tag: &str) -> bool {
+        CONTROL_TAGS.contains(&tag) || self.components.iter().any(|c| c == tag)
+    }
+}
+
+impl Rule for RequireInputLabelRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn file_glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        let tree = match parse_file(ctx.file_path, ctx.content) {
+            Some(t) => t,
+            None => return violations,
+        };
+        let source = ctx.content.as_bytes();
+        let mut walk = Walk::default();
+        self.visit(tree.root_node(), source, None, false, &mut walk);
+
+        for control in &walk.controls {
+            let labels = walk
+                .label_targets
+                .iter()
+                .filter(|(c, _)| *c == control.component)
+                .map(|(_, target)| target);
+            let labelled = match &control.id {
+                Some(AttrValue::Dynamic) => labels.count() > 0,
+                Some(id) => labels.into_iter().any(|t| t == id || *t == AttrValue::Dynamic),
+                None => false,
+            };
+            if labelled {
+                continue;
+            }
+            violations.push(Violation {
+                rule_id: self.id.clone(),
+                severity: self.severity,
+                file: ctx.file_path.to_path_buf(),
+                line: Some(control.row + 1),
+                column: Some(control.column + 1),
+                message: self.message.clone(),
+                suggest: self.suggest.clone(),
+                source_line: ctx.source_line(control.row),
+                fix: None,
+                origin: None,
+                enclosing: None,
+            });
+        }
+        violations
+    }
+}
+
+impl RequireInputLabelRule {
+    fn visit(
+        &self,
+        node: tree_sitter::Node,
+        source: &[u8],
+        component: Option<usize>,
+        in_label: bool,
+        walk: &mut Walk,
+    ) {
+        let component = if is_component_node(&node, source) {
+            Some(node.id())
+        } else {
+            component
+        };
+        let mut in_label = in_label;
+
+        let element = match node.kind() {
+            "jsx_self_closing_element" => Some(node),
+            "jsx_element" => node.child_by_field_name("open_tag"),
+            _ => None,
+        };
+        if let Some(tag) = element.and_then(|el| tag_name(&el, source).map(|name| (el, name))) {
+            let (el, name) = tag;
+            if LABEL_TAGS.contains(&name) {
+                in_label |= node.kind() == "jsx_element";
+                if let Some(target) = attr_value(&el, source, "htmlFor") {
+                    walk.label_targets.push((component, target));
+                }
+            } else if self.is_control(name) && !in_label && needs_label(&el, source, name) {
+                walk.controls.push(Control {
+                    component,
+                    id: attr_value(&el, source, "id"),
+                    row: el.start_position().row,
+                    column: el.start_position().column,
+                });
+            }
+        }
+
+        for i in 0..node.child_count() {
+            if let Some(child) = node.child(i) {
+                self.visit(child, source, component, in_label, walk);
+            }
+        }
+    }
+}
+
+fn tag_name<'a>(node: &tree_sitter::Node, source: &'a [u8]) -> Option<&'a str> {
+    for i in 0..node.child_count() {
+        if let Some(child) = node.child(i) {
+            if child.kind() == "identifier" || child.kind() == "member_expression" {
+                return child.utf8_text(source).ok();
+            }
+        }
+    }
+    None
+}
+
+/// Whether a control still needs a label after looking at its own
+/// attributes: ARIA naming, spread props and label-less input types all
+/// settle it.
+fn needs_label(node: &tree_sitter::Node, source: &[u8], tag: &str) -> bool {
+    for i in 0..node.child_count() {
+        let Some(child) = node.child(i) else { continue };
+        match child.kind() {
+            "jsx_attribute" => {
+                let name = child.child(0).and_then(|n| n.utf8_text(source).ok());
+                if matches!(name, Some("aria-label" | "aria-labelledby")) {
+                    return false;
+                }
+            }
+            "jsx_expression" => return false,
+            _ => {}
+        }
+    }
+    match attr_value(node, source, "type") {
+        Some(AttrValue::Literal(ty)) if tag == "input" => !UNLABELLED_INPUT_TYPES.contains(&ty.as_str()),
+        _ => true,
+    }
+}
+
+/// The value of the named attribute on an opening or self-closing element.
+fn attr_value(node: &tree_sitter::Node, source: &[u8], attr: &str) -> Option<AttrValue> {
+    for i in 0..node.child_count() {
+        let Some(child) = node.child(i).filter(|c| c.kind() == "jsx_attribute") else {
+            continue;
+        };
+        if child.child(0).and_then(|n| n.utf8_text(source).ok()) != Some(attr) {
+            continue;
+        }
+        let value = child.named_child(child.named_child_count().saturating_sub(1))?;
+        return Some(match value.kind() {
+            "string" => AttrValue::Literal(
+                value
+                    .utf8_text(source)
+                    .unwrap_or("")
+                    .trim_matches(['"', '\''])
+                    .to_string(),
+            ),
+            _ => AttrValue::Dynamic,
+        });
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check_with(content: &str, components: &[&str]) -> Vec<Violation> {
+        let rule = RequireInputLabelRule::new(&RuleConfig {
+            id: "require-input-label".into(),
+            severity: Severity::Error,
+            message: "Form control has no label".into(),
+            glob: Some("**/*.{tsx,jsx}".into()),
+            components: components.iter().map(|c| c.to_string()).collect(),
+            ..Default::default()
+        })
+        .unwrap();
+        let ctx = ScanContext::new(Path::new("test.tsx"), content);
+        rule.check_file(&ctx)
+    }
+
+    fn check(content: &str) -> Vec<Violation> {
+        check_with(content, &[])
+    }
+
+    #[test]
+    fn unlabelled_controls_are_flagged() {
+        let content = r#"function Form() {
+  return (
+    <form>
+      <input name="email" placeholder="Email" />
+      <select name="plan"><option>Free</option></select>
+      <textarea name="bio"></textarea>
+    </form>
+  );
+}"#;
+        let lines: Vec<_> = check(content).iter().map(|v| v.line).collect();
+        assert_eq!(lines, [Some(4), Some(5), Some(6)]);
+    }
+
+    #[test]
+    fn labelled_controls_pass() {
+        let content = r#"function Form() {
+  return (
+    <form>
+      <label htmlFor="email">Email</label>
+      <input id="email" />
+      <label>Plan <select name="plan" /></label>
+      <textarea aria-label="Bio" />
+      <input aria-labelledby="search-heading" />
+      <input {...register("name")} />
+      <Label htmlFor={fieldId}>Name</Label>
+      <input id={fieldId} />
+      <input type="hidden" name="csrf" />
+      <input type="submit" value="Save" />
+    </form>
+  );
+}"#;
+        assert!(check(content).is_empty());
+    }
+
+    #[test]
+    fn html_for_must_match_the_id() {
+        let content = r#"function Form() {
+  return (
+    <form>
+      <label htmlFor="email">Email</label>
+      <input id="e-mail" />
+    </form>
+  );
+}"#;
+        assert_eq!(check(content).len(), 1);
+    }
+
+    #[test]
+    fn labels_in_another_component_do_not_count() {
+        let content = r#"function EmailLabel() {
+  return <label htmlFor="email">Email</label>;
+}
+function EmailInput() {
+  return <input id="email" />;
+}"#;
+        let violations = check(content);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line, Some(5));
+    }
+
+    #[test]
+    fn configured_components_are_checked() {
+        let content = r#"function Form() {
+  return (
+    <div>
+      <Input placeholder="Search" />
+      <Input aria-label="Search" />
+      <Textarea />
+    </div>
+  );
+}"#;
+        assert!(check(content).is_empty());
+        let lines: Vec<_> = check_with(content, &["Input", "Textarea"])
+            .iter()
+            .map(|v| v.line)
+            .collect();
+        assert_eq!(lines, [Some(4), Some(6)]);
+    }
+}
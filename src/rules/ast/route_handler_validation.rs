@@ -0,0 +1,342 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::ast::parse_file;
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+use regex::Regex;
+
+/// HTTP methods a route handler can export.
+const HTTP_METHODS: &[&str] = &["GET", "HEAD", "POST", "PUT", "PATCH", "DELETE", "OPTIONS"];
+
+/// Methods checked when `checks` is empty: the ones that carry a body.
+const DEFAULT_METHODS: &[&str] = &["POST", "PUT", "PATCH"];
+
+const DEFAULT_GLOB: &str = "**/app/api/**/route.{ts,js}";
+
+/// Schema methods that count as validation (`schema.parse(body)`, ...).
+const PARSE_METHODS: &[&str] = &["parse", "safeParse", "parseAsync", "safeParseAsync"];
+
+/// Requires every exported route handler for a body-carrying HTTP method to
+/// run its input through a schema.
+///
+/// Handlers are found in the file's exports: `export async function POST`,
+/// `export const PUT = ...` (including wrapped handlers such as
+/// `withAuth(async (req) => ...)`), and `export { handler as PATCH }` for a
+/// function defined in the same file. A handler passes when it calls
+/// `.parse()`, `.safeParse()`, `.parseAsync()` or `.safeParseAsync()` on
+/// anything but `JSON`; with `pattern` set, it passes when its source matches
+/// that regex instead. Handlers declared without parameters can't read the
+/// request, so they are skipped.
+///
+/// `checks` lists the methods to check (default: POST, PUT, PATCH).
+pub struct RouteHandlerValidationRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: Option<String>,
+    methods: Vec<String>,
+    pattern: Option<Regex>,
+}
+
+impl RouteHandlerValidationRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        if let Some(unknown) = config.checks.iter().find(|c| !HTTP_METHODS.contains(&c.as_str())) {
+            return Err(RuleBuildError::InvalidValue(
+                config.id.clone(),
+                "checks",
+                format!("unknown HTTP method '{}' (expected one of: {})", unknown, HTTP_METHODS.join(", ")),
+            ));
+        }
+        let methods = if config.checks.is_empty() {
+            DEFAULT_METHODS.iter().map(|m| m.to_string()).collect()
+        } else {
+            config.checks.clone()
+        };
+        let pattern = config
+            .pattern
+            .as_deref()
+            .map(Regex::new)
+            .transpose()
+            .map_err(|e| RuleBuildError::InvalidRegex(config.id.clone(), e))?;
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob: config.glob.clone().or_else(|| Some(DEFAULT_GLOB.to_string())),
+            methods,
+            pattern,
+        })
+    }
+
+    fn validates(&self, handler: tree_sitter::Node, source: &[u8]) -> bool {
+        match self.pattern {
+            Some(ref pattern) => handler.utf8_text(source).is_ok_and(|text| pattern.is_match(text)),
+            None => calls_schema_parse(handler, source),
+        }
+    }
+}
+
+impl Rule for RouteHandlerValidationRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+    fn file_glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        if !self.methods.iter().any(|m| ctx.content.contains(m.as_str())) {
+            return violations;
+        }
+        let tree = match parse_file(ctx.file_path, ctx.content) {
+            Some(t) => t,
+            None => return violations,
+        };
+        let source = ctx.content.as_bytes();
+        for (method, at, handler) in exported_handlers(tree.root_node(), source) {
+            if !self.methods.iter().any(|m| m == method) {
+                continue;
+            }
+            if takes_no_parameters(handler) || self.validates(handler, source) {
+                continue;
+            }
+            let row = at.start_position().row;
+            let suggest = if self.suggest.is_empty() {
+                vec![Suggestion::new(
+                    "parse the body or params with a schema before using them: const data = schema.parse(await request.json())",
+                )]
+            } else {
+                self.suggest.clone()
+            };
+            violations.push(Violation {
+                rule_id: self.id.clone(),
+                severity: self.severity,
+                file: ctx.file_path.to_path_buf(),
+                line: Some(row + 1),
+                column: Some(at.start_position().column + 1),
+                message: format!("{}: {} handler doesn't validate its input", self.message, method),
+                suggest,
+                source_line: ctx.source_line(row),
+                fix: None,
+                origin: None,
+                enclosing: None,
+            });
+        }
+        violations
+    }
+}
+
+/// Exported HTTP handlers as (method, node to report at, handler node).
+fn exported_handlers<'t>(
+    root: tree_sitter::Node<'t>,
+    source: &'t [u8],
+) -> Vec<(&'t str, tree_sitter::Node<'t>, tree_sitter::Node<'t>)> {
+    let mut handlers = Vec::new();
+    for i in 0..root.named_child_count() {
+        let Some(export) = root.named_child(i).filter(|n| n.kind() == "export_statement") else {
+            continue;
+        };
+        if let Some(declaration) = export.child_by_field_name("declaration") {
+            for (name, handler) in declared_functions(declaration) {
+                if let Some(method) = http_method(name, source) {
+                    handlers.push((method, name, handler));
+                }
+            }
+            continue;
+        }
+        // `export { handler as POST }`
+        let Some(clause) = (0..export.named_child_count())
+            .filter_map(|j| export.named_child(j))
+            .find(|n| n.kind() == "export_clause")
+        else {
+            continue;
+        };
+        for j in 0..clause.named_child_count() {
+            let Some(specifier) = clause.named_child(j).filter(|n| n.kind() == "export_specifier") else {
+                continue;
+            };
+            let Some(local) = specifier.child_by_field_name("name") else {
+                continue;
+            };
+            let exported = specifier.child_by_field_name("alias").unwrap_or(local);
+            let Some(method) = http_method(exported, source) else {
+                continue;
+            };
+            let local_name = local.utf8_text(source).ok();
+            if let Some(handler) = top_level_function(root, source, local_name) {
+                handlers.push((method, specifier, handler));
+            }
+        }
+    }
+    handlers
+}
+
+/// Functions a declaration introduces, as (name node, function node). For
+/// `const X = value`, the function node is the whole value.
+fn declared_functions(declaration: tree_sitter::Node) -> Vec<(tree_sitter::Node, tree_sitter::Node)> {
+    match declaration.kind() {
+        "function_declaration" | "generator_function_declaration" => declaration
+            .child_by_field_name("name")
+            .map(|name| vec![(name, declaration)])
+            .unwrap_or_default(),
+        "lexical_declaration" | "variable_declaration" => (0..declaration.named_child_count())
+            .filter_map(|i| declaration.named_child(i))
+            .filter(|d| d.kind() == "variable_declarator")
+            .filter_map(|d| Some((d.child_by_field_name("name")?, d.child_by_field_name("value")?)))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The function declared at the top level of the file under `name`.
+fn top_level_function<'t>(
+    root: tree_sitter::Node<'t>,
+    source: &[u8],
+    name: Option<&str>,
+) -> Option<tree_sitter::Node<'t>> {
+    let name = name?;
+    (0..root.named_child_count())
+        .filter_map(|i| root.named_child(i))
+        .map(|n| {
+            if n.kind() == "export_statement" {
+                n.child_by_field_name("declaration").unwrap_or(n)
+            } else {
+                n
+            }
+        })
+        .flat_map(declared_functions)
+        .find(|(declared, _)| declared.utf8_text(source).ok() == Some(name))
+        .map(|(_, handler)| handler)
+}
+
+fn http_method<'t>(name: tree_sitter::Node, source: &'t [u8]) -> Option<&'t str> {
+    let text = name.utf8_text(source).ok()?;
+    HTTP_METHODS.contains(&text).then_some(text)
+}
+
+/// A function written with an empty parameter list. Wrapped handlers and
+/// other values don't count.
+fn takes_no_parameters(handler: tree_sitter::Node) -> bool {
+    matches!(
+        handler.kind(),
+        "function_declaration" | "generator_function_declaration" | "arrow_function" | "function_expression" | "function"
+    ) && handler.child_by_field_name("parameter").is_none()
+        && handler
+            .child_by_field_name("parameters")
+            .is_some_and(|p| p.named_child_count() == 0)
+}
+
+/// Whether `node` contains a `.parse()`-style call on something other than
+/// `JSON`.
+fn calls_schema_parse(node: tree_sitter::Node, source: &[u8]) -> bool {
+    if node.kind() == "call_expression" {
+        let is_parse = node
+            .child_by_field_name("function")
+            .filter(|f| f.kind() == "member_expression")
+            .is_some_and(|f| {
+                let method = f.child_by_field_name("property").and_then(|p| p.utf8_text(source).ok());
+                let object = f.child_by_field_name("object").and_then(|o| o.utf8_text(source).ok());
+                method.is_some_and(|m| PARSE_METHODS.contains(&m)) && object != Some("JSON")
+            });
+        if is_parse {
+            return true;
+        }
+    }
+    (0..node.child_count())
+        .filter_map(|i| node.child(i))
+        .any(|child| calls_schema_parse(child, source))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn check_with(config: RuleConfig, content: &str) -> Vec<(usize, String)> {
+        let rule = RouteHandlerValidationRule::new(&config).unwrap();
+        rule.check_file(&ScanContext::new(Path::new("app/api/users/route.ts"), content))
+            .iter()
+            .map(|v| (v.line.unwrap(), v.message.clone()))
+            .collect()
+    }
+
+    fn config() -> RuleConfig {
+        RuleConfig {
+            id: "route-validation".into(),
+            message: "Unvalidated route input".into(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn flags_body_handlers_without_a_schema_parse() {
+        let content = r#"
+import { z } from "zod";
+const Body = z.object({ name: z.string() });
+
+export async function GET(request: Request) {
+  return Response.json(await db.users.list());
+}
+
+export async function POST(request: Request) {
+  const data = Body.parse(await request.json());
+  return Response.json(await db.users.create(data));
+}
+
+export const PUT = async (request: Request) => {
+  const body = JSON.parse(await request.text());
+  return Response.json(await db.users.update(body));
+};
+
+export const PATCH = withAuth(async (request) => {
+  const result = await Body.safeParseAsync(await request.json());
+  return Response.json(result);
+});
+
+async function remove(request: Request, { params }) {
+  await db.users.delete(params.id);
+}
+export { remove as DELETE };
+
+export async function OPTIONS() {
+  return new Response(null, { status: 204 });
+}
+"#;
+        assert_eq!(
+            check_with(config(), content),
+            vec![(14, "Unvalidated route input: PUT handler doesn't validate its input".to_string())]
+        );
+
+        let config = RuleConfig {
+            checks: vec!["DELETE".into(), "OPTIONS".into()],
+            ..config()
+        };
+        assert_eq!(
+            check_with(config, content),
+            vec![(27, "Unvalidated route input: DELETE handler doesn't validate its input".to_string())]
+        );
+    }
+
+    #[test]
+    fn pattern_replaces_the_builtin_detection() {
+        let content = "export async function POST(req) {\n  const body = await validateBody(req);\n}\n";
+        assert_eq!(check_with(config(), content).len(), 1);
+        let config = RuleConfig {
+            pattern: Some(r"validateBody\(".into()),
+            ..config()
+        };
+        assert!(check_with(config, content).is_empty());
+
+        let config = RuleConfig {
+            checks: vec!["post".into()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            RouteHandlerValidationRule::new(&config),
+            Err(RuleBuildError::InvalidValue(_, "checks", _))
+        ));
+    }
+}
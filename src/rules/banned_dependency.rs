@@ -1,21 +1,99 @@
 use crate::config::{RuleConfig, Severity};
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
-use std::collections::HashSet;
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+use crate::version::{range_floor, Version, VersionReq};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
 
 /// Checks `package.json` (or other manifest) files for banned packages
 /// in dependency sections.
 ///
 /// Scans `dependencies`, `devDependencies`, `peerDependencies`, and
-/// `optionalDependencies` for packages that should not be used.
+/// `optionalDependencies` for packages that should not be used. Every
+/// manifest matched by the glob is checked, so nested workspace packages are
+/// covered as well as the root.
+///
+/// A `packages` entry may carry a version constraint, e.g. `moment@<3` or
+/// `react@>=16 <18`. The version checked is the one `package-lock.json`
+/// resolved (the nearest lockfile at or above the manifest), falling back to
+/// the lowest version the declared range allows. Only npm's lockfile is
+/// read: where the nearest lockfile is `yarn.lock` or `pnpm-lock.yaml`, the
+/// declared range is used and a warning is logged. Dependencies whose version
+/// can't be determined (`latest`, `workspace:*`, git URLs) are not reported
+/// for constrained entries.
 #[derive(Debug)]
 pub struct BannedDependencyRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
-    packages: HashSet<String>,
+    packages: Vec<PackageBan>,
     manifest: String,
+    /// The lockfile in each directory looked at this scan.
+    lockfiles: Mutex<HashMap<PathBuf, Lockfile>>,
+}
+
+#[derive(Debug)]
+enum Lockfile {
+    Npm(Arc<serde_json::Value>),
+    /// `yarn.lock` or `pnpm-lock.yaml`, which aren't read.
+    Unsupported,
+    Missing,
+}
+
+/// One `packages` entry: a name and an optional version constraint.
+#[derive(Debug)]
+struct PackageBan {
+    name: String,
+    range: Option<VersionReq>,
+}
+
+impl PackageBan {
+    fn parse(entry: &str) -> Option<Self> {
+        // Split at the last `@` that isn't the scope prefix
+        let Some(at) = entry.rfind('@').filter(|&i| i > 0) else {
+            return Some(Self {
+                name: entry.to_string(),
+                range: None,
+            });
+        };
+        Some(Self {
+            name: entry[..at].to_string(),
+            range: Some(VersionReq::parse_npm(&entry[at + 1..]).ok()?),
+        })
+    }
+
+    /// Whether the ban applies to a dependency at `version`. Unconstrained
+    /// bans always apply; constrained ones need a known version.
+    fn matches(&self, version: Option<Version>) -> bool {
+        match (&self.range, version) {
+            (None, _) => true,
+            (Some(range), Some(version)) => range.matches(&version),
+            (Some(_), None) => false,
+        }
+    }
+}
+
+/// Lockfiles from other package managers, which aren't read.
+const UNSUPPORTED_LOCKFILES: &[&str] = &["yarn.lock", "pnpm-lock.yaml"];
+
+/// Version of `name` in a parsed `package-lock.json` at `lock_dir`, as
+/// resolved for a manifest in `dir`. Handles lockfile v2/v3 `packages`
+/// entries (both nested and hoisted) and v1 `dependencies`.
+fn locked_version(lock: &serde_json::Value, lock_dir: &Path, dir: &Path, name: &str) -> Option<Version> {
+    let rel = dir.strip_prefix(lock_dir).ok()?;
+    let mut keys = Vec::new();
+    if !rel.as_os_str().is_empty() {
+        keys.push(format!("{}/node_modules/{}", rel.to_string_lossy().replace('\\', "/"), name));
+    }
+    keys.push(format!("node_modules/{}", name));
+
+    let version = keys
+        .iter()
+        .find_map(|key| lock.get("packages")?.get(key)?.get("version")?.as_str())
+        .or_else(|| lock.get("dependencies")?.get(name)?.get("version")?.as_str());
+    version.and_then(|v| Version::parse(v).ok())
 }
 
 /// JSON dependency sections to check.
@@ -35,7 +113,22 @@ impl BannedDependencyRule {
             ));
         }
 
-        let packages: HashSet<String> = config.packages.iter().cloned().collect();
+        let packages = config
+            .packages
+            .iter()
+            .map(|entry| {
+                PackageBan::parse(entry).ok_or_else(|| {
+                    RuleBuildError::InvalidValue(
+                        config.id.clone(),
+                        "packages",
+                        format!(
+                            "can't parse version range in '{}' (expected e.g. `<3` or `>=16 <18`, checked against package-lock.json)",
+                            entry
+                        ),
+                    )
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
         let manifest = config
             .manifest
             .as_deref()
@@ -52,12 +145,49 @@ impl BannedDependencyRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob,
             packages,
             manifest,
+            lockfiles: Mutex::new(HashMap::new()),
         })
     }
+
+    /// The nearest `package-lock.json` at or above `dir` and the directory
+    /// it is in. Each directory is looked at once per scan.
+    fn lockfile(&self, dir: &Path) -> Option<(PathBuf, Arc<serde_json::Value>)> {
+        let mut cache = self.lockfiles.lock().unwrap_or_else(|e| e.into_inner());
+        for lock_dir in dir.ancestors() {
+            let found = cache
+                .entry(lock_dir.to_path_buf())
+                .or_insert_with(|| self.read_lockfile(lock_dir));
+            match found {
+                Lockfile::Npm(lock) => return Some((lock_dir.to_path_buf(), Arc::clone(lock))),
+                Lockfile::Unsupported => return None,
+                Lockfile::Missing => {}
+            }
+        }
+        None
+    }
+
+    fn read_lockfile(&self, dir: &Path) -> Lockfile {
+        if let Ok(text) = std::fs::read_to_string(dir.join("package-lock.json")) {
+            // An unparseable lockfile still ends the search, and resolves nothing
+            return Lockfile::Npm(Arc::new(serde_json::from_str(&text).unwrap_or_default()));
+        }
+        match UNSUPPORTED_LOCKFILES.iter().find(|name| dir.join(name).is_file()) {
+            Some(name) => {
+                tracing::warn!(
+                    "{}: {} is not read (only package-lock.json is); version constraints use declared ranges under {}",
+                    self.id,
+                    name,
+                    dir.display()
+                );
+                Lockfile::Unsupported
+            }
+            None => Lockfile::Missing,
+        }
+    }
 }
 
 impl Rule for BannedDependencyRule {
@@ -73,6 +203,10 @@ impl Rule for BannedDependencyRule {
         self.glob.as_deref()
     }
 
+    fn shareable(&self) -> bool {
+        false
+    }
+
     fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
         // Only process files that match the manifest name
         let file_name = ctx
@@ -91,31 +225,55 @@ impl Rule for BannedDependencyRule {
         };
 
         let mut violations = Vec::new();
+        let dir = ctx.file_path.parent();
 
         for section in DEP_SECTIONS {
             if let Some(deps) = json.get(section).and_then(|v| v.as_object()) {
-                for pkg_name in deps.keys() {
-                    if self.packages.contains(pkg_name) {
-                        // Find the line number by searching for the package name in the raw text
-                        let line_num = find_line_number(ctx.content, pkg_name, section);
-
-                        violations.push(Violation {
-                            rule_id: self.id.clone(),
-                            severity: self.severity,
-                            file: ctx.file_path.to_path_buf(),
-                            line: line_num,
-                            column: None,
-                            message: format!(
-                                "{}: '{}' in {}",
-                                self.message, pkg_name, section
-                            ),
-                            suggest: self.suggest.clone(),
-                            source_line: line_num.and_then(|n| {
-                                ctx.content.lines().nth(n - 1).map(|l| l.to_string())
-                            }),
-                            fix: None,
-                        });
+                for (pkg_name, declared) in deps {
+                    let Some(ban) = self.packages.iter().find(|b| &b.name == pkg_name) else {
+                        continue;
+                    };
+                    let version = match ban.range {
+                        None => None,
+                        Some(_) => dir
+                            .and_then(|dir| {
+                                let (lock_dir, lock) = self.lockfile(dir)?;
+                                locked_version(&lock, &lock_dir, dir, pkg_name)
+                            })
+                            .or_else(|| declared.as_str().and_then(range_floor)),
+                    };
+                    if !ban.matches(version) {
+                        continue;
                     }
+
+                    // Find the line number by searching for the package name in the raw text
+                    let line_num = find_line_number(ctx.lines(), pkg_name, section);
+                    let source_line = line_num.and_then(|n| ctx.source_line(n - 1));
+                    let column = source_line
+                        .as_deref()
+                        .and_then(|l| l.find(&format!(r#""{}""#, pkg_name)))
+                        .map(|c| c + 1);
+                    let message = match (&ban.range, version) {
+                        (Some(range), Some(version)) => format!(
+                            "{}: '{}@{}' in {} (banned: {})",
+                            self.message, pkg_name, version, section, range
+                        ),
+                        _ => format!("{}: '{}' in {}", self.message, pkg_name, section),
+                    };
+
+                    violations.push(Violation {
+                        rule_id: self.id.clone(),
+                        severity: self.severity,
+                        file: ctx.file_path.to_path_buf(),
+                        line: line_num,
+                        column,
+                        message,
+                        suggest: self.suggest.clone(),
+                        source_line,
+                        fix: None,
+                        origin: None,
+                        enclosing: None,
+                    });
                 }
             }
         }
@@ -125,14 +283,14 @@ impl Rule for BannedDependencyRule {
 }
 
 /// Find the line number of a package name within a specific dependency section.
-fn find_line_number(content: &str, pkg_name: &str, section: &str) -> Option<usize> {
+fn find_line_number(lines: &[&str], pkg_name: &str, section: &str) -> Option<usize> {
     let needle = format!(r#""{}""#, pkg_name);
     let section_needle = format!(r#""{}""#, section);
 
     let mut in_section = false;
     let mut brace_depth = 0;
 
-    for (idx, line) in content.lines().enumerate() {
+    for (idx, line) in lines.iter().enumerate() {
         if line.contains(&section_needle) {
             in_section = true;
             brace_depth = 0;
@@ -155,7 +313,7 @@ fn find_line_number(content: &str, pkg_name: &str, section: &str) -> Option<usiz
     }
 
     // Fallback: search anywhere in the file
-    for (idx, line) in content.lines().enumerate() {
+    for (idx, line) in lines.iter().enumerate() {
         if line.contains(&needle) {
             return Some(idx + 1);
         }
@@ -174,7 +332,7 @@ mod tests {
             id: "test-banned-dep".into(),
             severity: Severity::Error,
             message: "banned dependency".into(),
-            suggest: Some("remove this package".into()),
+            suggest: vec!["remove this package".into()],
             packages: packages.into_iter().map(|s| s.to_string()).collect(),
             ..Default::default()
         };
@@ -182,10 +340,7 @@ mod tests {
     }
 
     fn check(rule: &BannedDependencyRule, content: &str) -> Vec<Violation> {
-        let ctx = ScanContext {
-            file_path: Path::new("package.json"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("package.json"), content);
         rule.check_file(&ctx)
     }
 
@@ -247,10 +402,7 @@ mod tests {
     #[test]
     fn skips_non_manifest_files() {
         let rule = make_rule(vec!["bootstrap"]);
-        let ctx = ScanContext {
-            file_path: Path::new("src/component.tsx"),
-            content: r#"{"dependencies": {"bootstrap": "^5.0.0"}}"#,
-        };
+        let ctx = ScanContext::new(Path::new("src/component.tsx"), r#"{"dependencies": {"bootstrap": "^5.0.0"}}"#);
         let violations = rule.check_file(&ctx);
         assert!(violations.is_empty());
     }
@@ -276,6 +428,120 @@ mod tests {
         assert_eq!(violations[0].line, Some(4));
     }
 
+    #[test]
+    fn reports_key_column() {
+        let rule = make_rule(vec!["bootstrap"]);
+        let violations = check(&rule, "{\n  \"dependencies\": {\n    \"bootstrap\": \"^5.0.0\"\n  }\n}");
+        assert_eq!(violations[0].column, Some(5));
+    }
+
+    #[test]
+    fn version_range_uses_declared_floor() {
+        let rule = make_rule(vec!["moment@<3", "@scope/pkg@>=1 <2"]);
+        let content = r#"{
+  "dependencies": {
+    "moment": "^2.29.4",
+    "@scope/pkg": "~2.0.0"
+  },
+  "devDependencies": {
+    "@scope/pkg": "1.5.0-beta.1",
+    "moment": "latest"
+  }
+}"#;
+        let violations = check(&rule, content);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].line, Some(3));
+        assert!(violations[0].message.contains("'moment@2.29.4' in dependencies (banned: <3)"));
+        assert_eq!(violations[1].line, Some(7));
+        assert!(violations[1].message.contains("'@scope/pkg@1.5.0' in devDependencies"));
+    }
+
+    #[test]
+    fn version_range_prefers_lockfile() {
+        let dir = tempfile::tempdir().unwrap();
+        let pkg = dir.path().join("packages/app");
+        std::fs::create_dir_all(&pkg).unwrap();
+        let manifest = r#"{ "dependencies": { "moment": "^2.0.0", "lodash": "^4.0.0" } }"#;
+        std::fs::write(pkg.join("package.json"), manifest).unwrap();
+        std::fs::write(
+            dir.path().join("package-lock.json"),
+            r#"{
+  "lockfileVersion": 3,
+  "packages": {
+    "node_modules/moment": { "version": "2.30.1" },
+    "packages/app/node_modules/lodash": { "version": "4.17.21" },
+    "node_modules/lodash": { "version": "3.10.1" }
+  }
+}"#,
+        )
+        .unwrap();
+
+        let rule = make_rule(vec!["moment@<=2.29", "lodash@<4"]);
+        let file = pkg.join("package.json");
+        let violations = rule.check_file(&ScanContext::new(&file, manifest));
+        // moment resolves to 2.30.1 and lodash (nested) to 4.17.21: neither banned
+        assert!(violations.is_empty());
+
+        let rule = make_rule(vec!["moment@<3"]);
+        let violations = rule.check_file(&ScanContext::new(&file, manifest));
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("'moment@2.30.1'"));
+    }
+
+    #[test]
+    fn lockfile_is_read_once_per_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        let manifest = r#"{ "dependencies": { "moment": "^2.0.0" }, "devDependencies": { "left-pad": "^1.0.0" } }"#;
+        let file = dir.path().join("package.json");
+        std::fs::write(&file, manifest).unwrap();
+        let lock = dir.path().join("package-lock.json");
+        std::fs::write(&lock, r#"{ "packages": { "node_modules/moment": { "version": "2.30.1" } } }"#).unwrap();
+
+        let rule = make_rule(vec!["moment@<3", "left-pad@<2"]);
+        assert_eq!(rule.check_file(&ScanContext::new(&file, manifest)).len(), 2);
+        assert_eq!(rule.lockfiles.lock().unwrap().len(), 1);
+
+        // Later edits are seen only by the next scan
+        std::fs::write(&lock, r#"{ "packages": { "node_modules/moment": { "version": "3.0.0" } } }"#).unwrap();
+        assert_eq!(rule.check_file(&ScanContext::new(&file, manifest)).len(), 2);
+        assert!(!rule.shareable());
+        let rule = make_rule(vec!["moment@<3", "left-pad@<2"]);
+        let violations = rule.check_file(&ScanContext::new(&file, manifest));
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("'left-pad@1.0.0'"));
+    }
+
+    #[test]
+    fn other_lockfiles_fall_back_to_declared_ranges() {
+        let dir = tempfile::tempdir().unwrap();
+        let pkg = dir.path().join("app");
+        std::fs::create_dir_all(&pkg).unwrap();
+        std::fs::write(pkg.join("yarn.lock"), "moment@^2.0.0:\n  version \"2.30.1\"\n").unwrap();
+        // An npm lockfile further up is not used past the yarn one
+        std::fs::write(
+            dir.path().join("package-lock.json"),
+            r#"{ "packages": { "node_modules/moment": { "version": "3.0.0" } } }"#,
+        )
+        .unwrap();
+        let manifest = r#"{ "dependencies": { "moment": "^2.0.0" } }"#;
+        let rule = make_rule(vec!["moment@<3"]);
+        let violations = rule.check_file(&ScanContext::new(&pkg.join("package.json"), manifest));
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("'moment@2.0.0'"));
+    }
+
+    #[test]
+    fn invalid_version_range_errors() {
+        let config = RuleConfig {
+            id: "test".into(),
+            packages: vec!["moment@<three".into()],
+            ..Default::default()
+        };
+        let err = BannedDependencyRule::new(&config).unwrap_err();
+        assert!(matches!(err, RuleBuildError::InvalidValue(_, "packages", _)));
+        assert!(err.to_string().contains("'moment@<three'"));
+    }
+
     #[test]
     fn missing_packages_error() {
         let config = RuleConfig {
@@ -301,17 +567,11 @@ mod tests {
         let rule = BannedDependencyRule::new(&config).unwrap();
 
         // Should not match package.json
-        let ctx = ScanContext {
-            file_path: Path::new("package.json"),
-            content: r#"{"dependencies": {"bootstrap": "^5.0.0"}}"#,
-        };
+        let ctx = ScanContext::new(Path::new("package.json"), r#"{"dependencies": {"bootstrap": "^5.0.0"}}"#);
         assert!(rule.check_file(&ctx).is_empty());
 
         // Should match bower.json
-        let ctx = ScanContext {
-            file_path: Path::new("bower.json"),
-            content: r#"{"dependencies": {"bootstrap": "^5.0.0"}}"#,
-        };
+        let ctx = ScanContext::new(Path::new("bower.json"), r#"{"dependencies": {"bootstrap": "^5.0.0"}}"#);
         assert_eq!(rule.check_file(&ctx).len(), 1);
     }
 }
@@ -1,6 +1,8 @@
 use crate::config::{RuleConfig, Severity};
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::resolve::{ImportResolver, Resolution};
+use crate::rules::{PackageInfo, Rule, RuleBuildError, ScanContext, Suggestion, Violation};
 use regex::Regex;
+use std::path::{Component, Path, PathBuf};
 
 /// Scans source files for import/require statements referencing banned packages.
 ///
@@ -11,22 +13,41 @@ use regex::Regex;
 /// - Subpath imports like `import ... from 'lodash/debounce'`
 ///
 /// Uses word-boundary matching to avoid false positives (e.g., `moment` won't
-/// match `momentum`).
+/// match `momentum`). A `*` in a package entry matches within one path
+/// segment, so `lodash/*` bans every lodash subpath (but not `lodash` itself)
+/// and `@internal/legacy-*` bans a family of scoped packages. With
+/// `allow_type_imports`, type-only imports and re-exports are not reported.
+///
+/// Relative imports can be restricted too: `max_relative_depth` caps how many
+/// `../` segments an import may climb, and `ban_cross_package_imports` flags
+/// relative imports that resolve into a different workspace package (the
+/// nearest directory with a `package.json`), suggesting the package name.
+/// The latter also resolves aliases from the nearest `tsconfig.json`
+/// (`paths` and `baseUrl`), so `@/../../ui/src/button`-style shortcuts into
+/// another package are caught; an alias named after the package it points
+/// to is fine. Either option can be used without `packages`.
 #[derive(Debug)]
 pub struct BannedImportRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
     #[allow(dead_code)]
     packages: Vec<String>,
-    import_re: Regex,
+    allow_type_imports: bool,
+    import_re: Option<Regex>,
+    max_relative_depth: Option<usize>,
+    ban_cross_package_imports: bool,
+    specifier_re: Regex,
+    resolver: ImportResolver,
 }
 
 impl BannedImportRule {
     pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
-        if config.packages.is_empty() {
+        let restricts_relative =
+            config.max_relative_depth.is_some() || config.ban_cross_package_imports;
+        if config.packages.is_empty() && !restricts_relative {
             return Err(RuleBuildError::MissingField(
                 config.id.clone(),
                 "packages",
@@ -35,25 +56,33 @@ impl BannedImportRule {
 
         // Build a regex that matches import/require of any banned package.
         // Escaped package names joined with | to form alternatives.
-        let escaped: Vec<String> = config
-            .packages
-            .iter()
-            .map(|p| regex::escape(p))
-            .collect();
-        let pkg_group = escaped.join("|");
-
-        // Match:
-        //   import ... from ['"]pkg['"]      (named/default import)
-        //   import ['"]pkg['"]               (side-effect import)
-        //   require\(['"]pkg['"]\)           (CommonJS require)
-        //   export ... from ['"]pkg['"]      (re-exports)
-        // Also match subpath imports: pkg/subpath
-        let pattern = format!(
-            r#"(?:import\s+.*?\s+from\s+|import\s+|export\s+.*?\s+from\s+|require\s*\(\s*)['"]({})(?:/[^'"]*)?['"]"#,
-            pkg_group
-        );
+        let import_re = if config.packages.is_empty() {
+            None
+        } else {
+            let escaped: Vec<String> = config
+                .packages
+                .iter()
+                .map(|p| package_pattern(p))
+                .collect();
+            let pkg_group = escaped.join("|");
+
+            // Match:
+            //   import ... from ['"]pkg['"]      (named/default import)
+            //   import ['"]pkg['"]               (side-effect import)
+            //   require\(['"]pkg['"]\)           (CommonJS require)
+            //   export ... from ['"]pkg['"]      (re-exports)
+            // Also match subpath imports: pkg/subpath
+            let pattern = format!(
+                r#"{}['"]({})(?:/[^'"]*)?['"]"#,
+                IMPORT_PREFIX, pkg_group
+            );
+            Some(
+                Regex::new(&pattern)
+                    .map_err(|e| RuleBuildError::InvalidRegex(config.id.clone(), e))?,
+            )
+        };
 
-        let import_re = Regex::new(&pattern)
+        let specifier_re = Regex::new(&format!(r#"{}['"]([^'"]+)['"]"#, IMPORT_PREFIX))
             .map_err(|e| RuleBuildError::InvalidRegex(config.id.clone(), e))?;
 
         let default_glob = "**/*.{ts,tsx,js,jsx,mjs,cjs}".to_string();
@@ -62,12 +91,162 @@ impl BannedImportRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob: config.glob.clone().or(Some(default_glob)),
             packages: config.packages.clone(),
+            allow_type_imports: config.allow_type_imports,
             import_re,
+            max_relative_depth: config.max_relative_depth,
+            ban_cross_package_imports: config.ban_cross_package_imports,
+            specifier_re,
+            resolver: ImportResolver::new(),
         })
     }
+
+    /// Violation message for a relative import that breaks a configured
+    /// limit, with a suggested replacement when one can be derived.
+    fn check_relative(&self, ctx: &ScanContext, specifier: &str) -> Option<(String, Option<String>)> {
+        let depth = Path::new(specifier)
+            .components()
+            .take_while(|c| matches!(c, Component::ParentDir | Component::CurDir))
+            .filter(|c| matches!(c, Component::ParentDir))
+            .count();
+        if depth == 0 {
+            return None;
+        }
+
+        if self.ban_cross_package_imports {
+            let dir = ctx.file_path.parent().unwrap_or(Path::new(""));
+            if let Some(found) = self.check_cross_package(ctx, specifier, &normalize(&dir.join(specifier))) {
+                return Some(found);
+            }
+        }
+
+        match self.max_relative_depth {
+            Some(max) if depth > max => Some((
+                format!(
+                    "{}: '{}' climbs {} directories (max {})",
+                    self.message, specifier, depth, max
+                ),
+                Some("use the workspace path alias instead of a deep relative import".to_string()),
+            )),
+            _ => None,
+        }
+    }
+
+    /// Like [`Self::check_relative`], for a specifier that a tsconfig alias
+    /// resolves into another package.
+    fn check_alias(&self, ctx: &ScanContext, specifier: &str) -> Option<(String, Option<String>)> {
+        if !self.ban_cross_package_imports {
+            return None;
+        }
+        let dir = ctx.file_path.parent().unwrap_or(Path::new(""));
+        match self.resolver.resolve(dir, specifier)? {
+            Resolution::Alias(target) => self.check_cross_package(ctx, specifier, &target),
+            _ => None,
+        }
+    }
+
+    /// Violation message when `target` lies in a different workspace package
+    /// than the importing file.
+    fn check_cross_package(
+        &self,
+        ctx: &ScanContext,
+        specifier: &str,
+        target: &Path,
+    ) -> Option<(String, Option<String>)> {
+        let from = ctx.package()?;
+        let to = package_root(target)?;
+        if from.root == to {
+            return None;
+        }
+        let name = PackageInfo::read(&to).name;
+        if let Some(name) = &name {
+            // An alias spelled like the package it points to is fine
+            if specifier == name || specifier.strip_prefix(name.as_str()).is_some_and(|r| r.starts_with('/')) {
+                return None;
+            }
+        }
+        let suggest = name.as_ref().map(|name| {
+            let rest = target.strip_prefix(&to).unwrap_or(Path::new(""));
+            format!("import from '{}' instead (the package's workspace name)", join_specifier(name, rest))
+        });
+        let package = name.unwrap_or_else(|| to.display().to_string());
+        Some((
+            format!("{}: '{}' reaches into package '{}'", self.message, specifier, package),
+            suggest,
+        ))
+    }
+}
+
+/// Statement prefixes that precede a module specifier.
+pub(crate) const IMPORT_PREFIX: &str =
+    r#"(?:import\s+.*?\s+from\s+|import\s+|export\s+.*?\s+from\s+|require\s*\(\s*)"#;
+
+/// Resolve `.` and `..` components lexically.
+pub(crate) fn normalize(path: &Path) -> PathBuf {
+    let mut out = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !out.pop() {
+                    out.push("..");
+                }
+            }
+            other => out.push(other),
+        }
+    }
+    out
+}
+
+/// Nearest directory at or above `path` that holds a `package.json`.
+fn package_root(path: &Path) -> Option<PathBuf> {
+    path.ancestors()
+        .find(|dir| dir.join("package.json").is_file())
+        .map(Path::to_path_buf)
+}
+
+/// `name` plus the import's path inside the package, using `/` separators.
+fn join_specifier(name: &str, rest: &Path) -> String {
+    let mut specifier = name.to_string();
+    for part in rest.components() {
+        specifier.push('/');
+        specifier.push_str(&part.as_os_str().to_string_lossy());
+    }
+    specifier
+}
+
+/// Regex for one `packages` entry: literal text, with `*` matching any run
+/// of characters inside a single path segment.
+pub(crate) fn package_pattern(package: &str) -> String {
+    package
+        .split('*')
+        .map(regex::escape)
+        .collect::<Vec<_>>()
+        .join(r#"[^'"/]*"#)
+}
+
+/// Whether an import/export statement only brings in types: `import type`,
+/// `export type`, or a braced list where every specifier is `type X`.
+pub(crate) fn is_type_only(statement: &str) -> bool {
+    for keyword in ["import", "export"] {
+        let Some(rest) = statement.strip_prefix(keyword) else {
+            continue;
+        };
+        let rest = rest.trim_start();
+        // `import type from 'x'` is a default import named `type`
+        if let Some(after) = rest.strip_prefix("type") {
+            if after.starts_with(char::is_whitespace) && !after.trim_start().starts_with("from") {
+                return true;
+            }
+        }
+        if let Some(inner) = rest.strip_prefix('{').and_then(|r| r.split_once('}')) {
+            let mut specifiers = inner.0.split(',').map(str::trim).filter(|s| !s.is_empty()).peekable();
+            return specifiers.peek().is_some() && specifiers.all(|s| s.starts_with("type "));
+        }
+    }
+    false
 }
 
 impl Rule for BannedImportRule {
@@ -83,13 +262,54 @@ impl Rule for BannedImportRule {
         self.glob.as_deref()
     }
 
+    fn shareable(&self) -> bool {
+        false
+    }
+
     fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
         let mut violations = Vec::new();
 
-        for (line_idx, line) in ctx.content.lines().enumerate() {
-            for cap in self.import_re.captures_iter(line) {
+        for (line_idx, &line) in ctx.lines().iter().enumerate() {
+            if self.max_relative_depth.is_some() || self.ban_cross_package_imports {
+                for cap in self.specifier_re.captures_iter(line) {
+                    let specifier = cap.get(1).unwrap().as_str();
+                    let full_match = cap.get(0).unwrap();
+                    let found = if specifier.starts_with('.') {
+                        self.check_relative(ctx, specifier)
+                    } else {
+                        self.check_alias(ctx, specifier)
+                    };
+                    if let Some((message, suggest)) = found {
+                        violations.push(Violation {
+                            rule_id: self.id.clone(),
+                            severity: self.severity,
+                            file: ctx.file_path.to_path_buf(),
+                            line: Some(line_idx + 1),
+                            column: Some(full_match.start() + 1),
+                            message,
+                            suggest: if self.suggest.is_empty() {
+                                suggest.map(Suggestion::new).into_iter().collect()
+                            } else {
+                                self.suggest.clone()
+                            },
+                            source_line: Some(line.to_string()),
+                            fix: None,
+                            origin: None,
+                            enclosing: None,
+                        });
+                    }
+                }
+            }
+
+            let Some(import_re) = &self.import_re else {
+                continue;
+            };
+            for cap in import_re.captures_iter(line) {
                 let matched_pkg = cap.get(1).unwrap().as_str();
                 let full_match = cap.get(0).unwrap();
+                if self.allow_type_imports && is_type_only(full_match.as_str()) {
+                    continue;
+                }
 
                 violations.push(Violation {
                     rule_id: self.id.clone(),
@@ -101,6 +321,8 @@ impl Rule for BannedImportRule {
                     suggest: self.suggest.clone(),
                     source_line: Some(line.to_string()),
                     fix: None,
+                    origin: None,
+                    enclosing: None,
                 });
             }
         }
@@ -119,7 +341,7 @@ mod tests {
             id: "test-banned-import".into(),
             severity: Severity::Error,
             message: "banned import".into(),
-            suggest: Some("use an alternative".into()),
+            suggest: vec!["use an alternative".into()],
             packages: packages.into_iter().map(|s| s.to_string()).collect(),
             ..Default::default()
         };
@@ -127,10 +349,7 @@ mod tests {
     }
 
     fn check(rule: &BannedImportRule, content: &str) -> Vec<Violation> {
-        let ctx = ScanContext {
-            file_path: Path::new("test.ts"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.ts"), content);
         rule.check_file(&ctx)
     }
 
@@ -221,6 +440,152 @@ import { jsx } from '@emotion/react';"#;
         assert!(matches!(err, RuleBuildError::MissingField(_, "packages")));
     }
 
+    #[test]
+    fn wildcard_matches_subpaths_only() {
+        let rule = make_rule(vec!["lodash/*"]);
+        let content = r#"import debounce from 'lodash/debounce';
+import map from 'lodash/fp/map';
+import _ from 'lodash';
+import x from 'lodash-es/debounce';"#;
+        let violations = check(&rule, content);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].line, Some(1));
+        assert_eq!(violations[1].line, Some(2));
+    }
+
+    #[test]
+    fn wildcard_matches_scoped_package_family() {
+        let rule = make_rule(vec!["@internal/legacy-*"]);
+        let content = r#"import a from '@internal/legacy-auth';
+import b from '@internal/legacy-ui/button';
+import c from '@internal/modern-ui';
+import d from '@other/legacy-auth';"#;
+        let violations = check(&rule, content);
+        assert_eq!(violations.len(), 2);
+        assert!(violations[0].message.contains("'@internal/legacy-auth'"));
+    }
+
+    #[test]
+    fn type_only_imports_allowed_when_configured() {
+        let config = RuleConfig {
+            id: "no-legacy".into(),
+            message: "banned".into(),
+            packages: vec!["legacy".into()],
+            allow_type_imports: true,
+            ..Default::default()
+        };
+        let rule = BannedImportRule::new(&config).unwrap();
+        let content = r#"import type { User } from 'legacy';
+export type { User } from 'legacy';
+import { type User, type Role } from 'legacy';
+import { type User, fetchUser } from 'legacy';
+import type from 'legacy';
+import legacy from 'legacy';"#;
+        let lines: Vec<usize> = check(&rule, content).iter().filter_map(|v| v.line).collect();
+        assert_eq!(lines, vec![4, 5, 6]);
+
+        // Without the option, type imports are banned like any other
+        let rule = make_rule(vec!["legacy"]);
+        assert_eq!(check(&rule, content).len(), 6);
+    }
+
+    fn relative_rule(max_relative_depth: Option<usize>, ban_cross_package_imports: bool) -> BannedImportRule {
+        let config = RuleConfig {
+            id: "no-deep-relative".into(),
+            message: "avoid deep relative imports".into(),
+            max_relative_depth,
+            ban_cross_package_imports,
+            ..Default::default()
+        };
+        BannedImportRule::new(&config).unwrap()
+    }
+
+    #[test]
+    fn relative_depth_limit() {
+        let rule = relative_rule(Some(1), false);
+        let content = r#"import a from './a';
+import b from '../b';
+import c from '../../c';
+const d = require('../../../d');
+import e from 'lodash';"#;
+        let violations = check(&rule, content);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].line, Some(3));
+        assert!(violations[0].message.contains("'../../c' climbs 2 directories (max 1)"));
+        assert!(violations[0].suggest_text().unwrap().contains("alias"));
+        assert_eq!(violations[1].line, Some(4));
+    }
+
+    #[test]
+    fn cross_package_relative_import_suggests_package_name() {
+        let dir = tempfile::tempdir().unwrap();
+        let app = dir.path().join("packages/app");
+        let ui = dir.path().join("packages/ui");
+        std::fs::create_dir_all(app.join("src/lib")).unwrap();
+        std::fs::create_dir_all(ui.join("src")).unwrap();
+        std::fs::write(app.join("package.json"), r#"{ "name": "@acme/app" }"#).unwrap();
+        std::fs::write(ui.join("package.json"), r#"{ "name": "@acme/ui" }"#).unwrap();
+
+        let rule = relative_rule(None, true);
+        let file = app.join("src/page.ts");
+        let content = r#"import { Button } from '../../ui/src/button';
+import { helper } from './lib/helper';
+import { config } from '../config';"#;
+        let violations = rule.check_file(&ScanContext::new(&file, content));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line, Some(1));
+        assert!(violations[0].message.contains("reaches into package '@acme/ui'"));
+        assert_eq!(
+            violations[0].suggest_text(),
+            Some("import from '@acme/ui/src/button' instead (the package's workspace name)")
+        );
+    }
+
+    #[test]
+    fn cross_package_alias_import_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let write = |path: &str, content: &str| {
+            let path = root.join(path);
+            std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+            std::fs::write(path, content).unwrap();
+        };
+        write(
+            "tsconfig.json",
+            r#"{ "compilerOptions": { "paths": { "~ui/*": ["packages/ui/src/*"], "@acme/ui": ["packages/ui/src/index.ts"] } } }"#,
+        );
+        write("packages/ui/package.json", r#"{ "name": "@acme/ui" }"#);
+        write("packages/app/package.json", r#"{ "name": "@acme/app" }"#);
+
+        let rule = relative_rule(None, true);
+        let file = root.join("packages/app/src/page.ts");
+        let content = r#"import { Button } from '~ui/button';
+import { Card } from '@acme/ui';
+import React from 'react';"#;
+        let violations = rule.check_file(&ScanContext::new(&file, content));
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("'~ui/button' reaches into package '@acme/ui'"));
+        assert_eq!(
+            violations[0].suggest_text(),
+            Some("import from '@acme/ui/src/button' instead (the package's workspace name)")
+        );
+    }
+
+    #[test]
+    fn relative_options_combine_with_packages() {
+        let config = RuleConfig {
+            id: "imports".into(),
+            message: "bad import".into(),
+            packages: vec!["moment".into()],
+            max_relative_depth: Some(0),
+            ..Default::default()
+        };
+        let rule = BannedImportRule::new(&config).unwrap();
+        let violations = check(&rule, "import m from 'moment';
+import x from '../x';");
+        assert_eq!(violations.len(), 2);
+    }
+
     #[test]
     fn default_glob_set() {
         let rule = make_rule(vec!["moment"]);
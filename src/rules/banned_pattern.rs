@@ -1,5 +1,5 @@
 use crate::config::{RuleConfig, Severity};
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
 use regex::Regex;
 use std::ops::Range;
 
@@ -12,7 +12,7 @@ pub struct BannedPatternRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
     pattern: String,
     compiled_regex: Option<Regex>,
@@ -40,7 +40,7 @@ impl BannedPatternRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob: config.glob.clone(),
             pattern,
             compiled_regex,
@@ -65,15 +65,7 @@ impl Rule for BannedPatternRule {
     fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
         let mut violations = Vec::new();
 
-let line_offsets: Vec<usize> = if self.skip_strings {
-            std::iter::once(0)
-                .chain(ctx.content.match_indices('\n').map(|(i, _)| i + 1))
-                .collect()
-        } else {
-            Vec::new()
-        };
-
-        for (line_idx, line) in ctx.content.lines().enumerate() {
+        for (line_idx, &line) in ctx.lines().iter().enumerate() {
             if let Some(ref re) = self.compiled_regex {
                 // Regex mode: report each match
                 for m in re.find_iter(line) {
@@ -87,6 +79,8 @@ let line_offsets: Vec<usize> = if self.skip_strings {
                         suggest: self.suggest.clone(),
                         source_line: Some(line.to_string()),
                         fix: None,
+                        origin: None,
+                        enclosing: None,
                     });
                 }
             } else {
@@ -106,6 +100,8 @@ let line_offsets: Vec<usize> = if self.skip_strings {
                         suggest: self.suggest.clone(),
                         source_line: Some(line.to_string()),
                         fix: None,
+                        origin: None,
+                        enclosing: None,
                     });
                     search_start = col + pat_len;
                 }
@@ -116,9 +112,9 @@ if self.skip_strings {
             if let Some(tree) = crate::rules::ast::parse_file(ctx.file_path, ctx.content) {
                 let string_ranges = collect_string_ranges(&tree, ctx.content);
                 violations.retain(|v| {
-                    let byte_offset = match (v.line, v.column) {
-                        (Some(line), Some(col)) => line_offsets[line - 1] + (col - 1),
-                        _ => return true,
+                    let index = ctx.line_index();
+                    let Some(byte_offset) = v.line.zip(v.column).and_then(|(l, c)| index.offset(l, c)) else {
+                        return true;
                     };
                     !string_ranges
                         .iter()
@@ -168,7 +164,7 @@ mod tests {
             id: "test-banned-pattern".into(),
             severity: Severity::Warning,
             message: "banned pattern found".into(),
-            suggest: Some("remove this pattern".into()),
+            suggest: vec!["remove this pattern".into()],
             pattern: Some(pattern.to_string()),
             regex,
             ..Default::default()
@@ -176,10 +172,7 @@ mod tests {
     }
 
     fn check(rule: &BannedPatternRule, content: &str) -> Vec<Violation> {
-        let ctx = ScanContext {
-            file_path: Path::new("test.tsx"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.tsx"), content);
         rule.check_file(&ctx)
     }
 
@@ -275,7 +268,7 @@ mod tests {
         assert_eq!(violations[0].rule_id, "test-banned-pattern");
         assert_eq!(violations[0].severity, Severity::Warning);
         assert_eq!(violations[0].message, "banned pattern found");
-        assert_eq!(violations[0].suggest.as_deref(), Some("remove this pattern"));
+        assert_eq!(violations[0].suggest_text(), Some("remove this pattern"));
         assert!(violations[0].source_line.is_some());
     }
 
@@ -299,10 +292,7 @@ mod tests {
             let config = make_skip_config("process.env", false, true);
             let rule = BannedPatternRule::new(&config).unwrap();
             let content = "const docs = `Use process.env.SECRET for config`;";
-            let ctx = ScanContext {
-                file_path: Path::new("test.tsx"),
-                content,
-            };
+            let ctx = ScanContext::new(Path::new("test.tsx"), content);
             let violations = rule.check_file(&ctx);
             assert!(violations.is_empty());
         }
@@ -312,10 +302,7 @@ mod tests {
             let config = make_skip_config("process.env", false, true);
             let rule = BannedPatternRule::new(&config).unwrap();
             let content = "const val = process.env.SECRET;";
-            let ctx = ScanContext {
-                file_path: Path::new("test.tsx"),
-                content,
-            };
+            let ctx = ScanContext::new(Path::new("test.tsx"), content);
             let violations = rule.check_file(&ctx);
             assert_eq!(violations.len(), 1);
         }
@@ -325,10 +312,7 @@ mod tests {
             let config = make_skip_config("process.env", false, true);
             let rule = BannedPatternRule::new(&config).unwrap();
             let content = r#"const msg = "Use process.env.SECRET";"#;
-            let ctx = ScanContext {
-                file_path: Path::new("test.tsx"),
-                content,
-            };
+            let ctx = ScanContext::new(Path::new("test.tsx"), content);
             let violations = rule.check_file(&ctx);
             assert!(violations.is_empty());
         }
@@ -338,10 +322,7 @@ mod tests {
             let config = make_skip_config("process.env", false, false);
             let rule = BannedPatternRule::new(&config).unwrap();
             let content = "const docs = `Use process.env.SECRET for config`;";
-            let ctx = ScanContext {
-                file_path: Path::new("test.tsx"),
-                content,
-            };
+            let ctx = ScanContext::new(Path::new("test.tsx"), content);
             let violations = rule.check_file(&ctx);
             assert_eq!(violations.len(), 1);
         }
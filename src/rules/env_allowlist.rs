@@ -0,0 +1,317 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+/// Variables set by Node and Vite themselves, allowed without being listed.
+const BUILTIN_VARS: &[&str] = &["NODE_ENV", "MODE", "DEV", "PROD", "SSR", "BASE_URL"];
+
+/// Keeps code to a known set of environment variables.
+///
+/// Reads of `process.env.NAME`, `process.env["NAME"]` and
+/// `import.meta.env.NAME` are reported unless `NAME` is listed in `env_vars`
+/// or declared in the `env_file` template (e.g. `.env.example`), so a new
+/// variable can't appear without being documented. The template is looked
+/// up from each scanned file's directory upward, so packages in a monorepo
+/// can keep their own.
+///
+/// After a scan, template entries that no scanned file reads are reported
+/// too (see [`EnvAllowlistRule::unread_entries`]). That check only sees the
+/// files scanned, so run it over the whole project. Dynamic reads such as
+/// `process.env[name]` aren't checked.
+#[derive(Debug)]
+pub struct EnvAllowlistRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: Option<String>,
+    allowed: BTreeSet<String>,
+    env_file: Option<String>,
+    access_re: Regex,
+    templates: Mutex<Templates>,
+}
+
+/// Env templates found while scanning.
+#[derive(Debug, Default)]
+struct Templates {
+    /// The template governing each directory seen so far.
+    by_dir: HashMap<PathBuf, Option<PathBuf>>,
+    files: BTreeMap<PathBuf, Template>,
+}
+
+#[derive(Debug, Default)]
+struct Template {
+    entries: Vec<TemplateEntry>,
+    /// Names read by scanned files that use this template.
+    read: BTreeSet<String>,
+}
+
+#[derive(Debug)]
+struct TemplateEntry {
+    name: String,
+    line: usize,
+    text: String,
+}
+
+impl EnvAllowlistRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        if config.env_vars.is_empty() && config.env_file.is_none() {
+            return Err(RuleBuildError::MissingField(config.id.clone(), "env_vars or env_file"));
+        }
+        let access_re = Regex::new(
+            r#"\b(?:process\.env|import\.meta\.env)(?:\??\.([A-Za-z_$][\w$]*)|\[\s*['"`]([^'"`]+)['"`]\s*\])"#,
+        )
+        .map_err(|e| RuleBuildError::InvalidRegex(config.id.clone(), e))?;
+
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob: config
+                .glob
+                .clone()
+                .or(Some("**/*.{ts,tsx,js,jsx,mjs,cjs}".to_string())),
+            allowed: config.env_vars.iter().cloned().collect(),
+            env_file: config.env_file.clone(),
+            access_re,
+            templates: Mutex::new(Templates::default()),
+        })
+    }
+
+    /// The template for files in `dir`: the nearest `env_file` at or above it.
+    fn template_for(&self, templates: &mut Templates, dir: &Path) -> Option<PathBuf> {
+        let name = self.env_file.as_deref()?;
+        if let Some(found) = templates.by_dir.get(dir) {
+            return found.clone();
+        }
+        let candidate = dir.join(name);
+        let found = if candidate.is_file() {
+            if !templates.files.contains_key(&candidate) {
+                let content = std::fs::read_to_string(&candidate).unwrap_or_default();
+                let template = Template {
+                    entries: parse_template(&content),
+                    read: BTreeSet::new(),
+                };
+                templates.files.insert(candidate.clone(), template);
+            }
+            Some(candidate)
+        } else {
+            dir.parent().and_then(|parent| self.template_for(templates, parent))
+        };
+        templates.by_dir.insert(dir.to_path_buf(), found.clone());
+        found
+    }
+
+    /// Template entries that no file scanned so far reads, one violation each.
+    pub fn unread_entries(&self) -> Vec<Violation> {
+        let templates = self.templates.lock().unwrap_or_else(|e| e.into_inner());
+        let mut violations = Vec::new();
+        for (path, template) in &templates.files {
+            let file_name = path.file_name().unwrap_or_default().to_string_lossy();
+            for entry in template.entries.iter().filter(|e| !template.read.contains(&e.name)) {
+                violations.push(Violation {
+                    rule_id: self.id.clone(),
+                    severity: self.severity,
+                    file: path.clone(),
+                    line: Some(entry.line),
+                    column: Some(1),
+                    message: format!(
+                        "{}: '{}' is declared in {} but never read",
+                        self.message, entry.name, file_name
+                    ),
+                    suggest: vec![Suggestion::new(format!(
+                        "remove {} from {} if nothing needs it",
+                        entry.name, file_name
+                    ))],
+                    source_line: Some(entry.text.clone()),
+                    fix: None,
+                    origin: None,
+                    enclosing: None,
+                });
+            }
+        }
+        violations
+    }
+}
+
+/// `NAME=value` entries of an env template, skipping comments and blanks.
+fn parse_template(content: &str) -> Vec<TemplateEntry> {
+    content
+        .lines()
+        .enumerate()
+        .filter_map(|(idx, line)| {
+            let decl = line.trim();
+            let decl = decl.strip_prefix("export ").unwrap_or(decl);
+            let (name, _) = decl.split_once('=')?;
+            let name = name.trim();
+            let valid = !name.is_empty()
+                && !name.starts_with('#')
+                && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+            valid.then(|| TemplateEntry {
+                name: name.to_string(),
+                line: idx + 1,
+                text: line.to_string(),
+            })
+        })
+        .collect()
+}
+
+impl Rule for EnvAllowlistRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn file_glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        // (line index, column, name)
+        let mut reads = Vec::new();
+        for (line_idx, &line) in ctx.lines().iter().enumerate() {
+            for cap in self.access_re.captures_iter(line) {
+                let name = cap.get(1).or_else(|| cap.get(2)).unwrap().as_str();
+                reads.push((line_idx, cap.get(0).unwrap().start() + 1, name));
+            }
+        }
+
+        let dir = ctx.file_path.parent().unwrap_or(Path::new(""));
+        let mut templates = self.templates.lock().unwrap_or_else(|e| e.into_inner());
+        let template_path = self.template_for(&mut templates, dir);
+        let template = template_path.as_ref().and_then(|p| templates.files.get_mut(p));
+        let mut declared = BTreeSet::new();
+        if let Some(template) = template {
+            declared.extend(template.entries.iter().map(|e| e.name.clone()));
+            template.read.extend(reads.iter().map(|(_, _, name)| name.to_string()));
+        }
+        drop(templates);
+
+        let mut violations = Vec::new();
+        for (line_idx, column, name) in reads {
+            if self.allowed.contains(name) || declared.contains(name) || BUILTIN_VARS.contains(&name) {
+                continue;
+            }
+            let suggest = if self.suggest.is_empty() {
+                let target = match (&template_path, &self.env_file) {
+                    (Some(path), _) => path.file_name().unwrap_or_default().to_string_lossy().into_owned(),
+                    (None, Some(file)) => file.clone(),
+                    (None, None) => "env_vars".to_string(),
+                };
+                vec![Suggestion::new(format!(
+                    "use an existing variable, or declare {} in {}",
+                    name, target
+                ))]
+            } else {
+                self.suggest.clone()
+            };
+            violations.push(Violation {
+                rule_id: self.id.clone(),
+                severity: self.severity,
+                file: ctx.file_path.to_path_buf(),
+                line: Some(line_idx + 1),
+                column: Some(column),
+                message: format!("{}: '{}' is not an allowed environment variable", self.message, name),
+                suggest,
+                source_line: ctx.source_line(line_idx),
+                fix: None,
+                origin: None,
+                enclosing: None,
+            });
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_rule(env_vars: &[&str], env_file: Option<&str>) -> EnvAllowlistRule {
+        let config = RuleConfig {
+            id: "env-allowlist".into(),
+            severity: Severity::Error,
+            message: "undeclared env var".into(),
+            env_vars: env_vars.iter().map(|s| s.to_string()).collect(),
+            env_file: env_file.map(String::from),
+            ..Default::default()
+        };
+        EnvAllowlistRule::new(&config).unwrap()
+    }
+
+    fn check(rule: &EnvAllowlistRule, file: &Path, content: &str) -> Vec<Violation> {
+        rule.check_file(&ScanContext::new(file, content))
+    }
+
+    #[test]
+    fn reports_reads_outside_the_allowlist() {
+        let rule = make_rule(&["DATABASE_URL"], None);
+        let content = "const db = process.env.DATABASE_URL;\n\
+                       const key = process.env['STRIPE_KEY'];\n\
+                       const url = import.meta.env.VITE_API_URL;\n\
+                       if (process.env.NODE_ENV === 'test') {}\n";
+        let violations = check(&rule, Path::new("no-such-root/src/config.ts"), content);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(
+            violations[0].message,
+            "undeclared env var: 'STRIPE_KEY' is not an allowed environment variable"
+        );
+        assert_eq!((violations[0].line, violations[0].column), (Some(2), Some(13)));
+        assert_eq!(
+            violations[0].suggest_text(),
+            Some("use an existing variable, or declare STRIPE_KEY in env_vars")
+        );
+        assert!(violations[1].message.contains("'VITE_API_URL'"));
+    }
+
+    #[test]
+    fn template_entries_are_allowed_and_unread_ones_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("src/lib")).unwrap();
+        std::fs::write(
+            dir.path().join(".env.example"),
+            "# Database\nDATABASE_URL=postgres://localhost\n\nexport SENTRY_DSN=\nLEGACY_TOKEN=\n",
+        )
+        .unwrap();
+        let rule = make_rule(&[], Some(".env.example"));
+
+        let file = dir.path().join("src/lib/db.ts");
+        let violations = check(&rule, &file, "connect(process.env.DATABASE_URL, process.env.REDIS_URL);\n");
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("'REDIS_URL'"));
+        assert_eq!(
+            violations[0].suggest_text(),
+            Some("use an existing variable, or declare REDIS_URL in .env.example")
+        );
+        let file = dir.path().join("src/sentry.ts");
+        assert!(check(&rule, &file, "init({ dsn: process.env?.SENTRY_DSN });\n").is_empty());
+
+        let unread = rule.unread_entries();
+        assert_eq!(unread.len(), 1);
+        assert_eq!(
+            unread[0].message,
+            "undeclared env var: 'LEGACY_TOKEN' is declared in .env.example but never read"
+        );
+        assert_eq!(unread[0].file, dir.path().join(".env.example"));
+        assert_eq!(unread[0].line, Some(5));
+    }
+
+    #[test]
+    fn requires_an_allowlist() {
+        let config = RuleConfig {
+            id: "env".into(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            EnvAllowlistRule::new(&config),
+            Err(RuleBuildError::MissingField(_, "env_vars or env_file"))
+        ));
+    }
+}
@@ -0,0 +1,287 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// App directories tried, in order, when `app_dir` isn't set.
+const DEFAULT_APP_DIRS: &[&str] = &["app", "src/app"];
+
+/// Default naming for dynamic segment params: camelCase.
+const DEFAULT_SEGMENT_PATTERN: &str = "^[a-z][a-zA-Z0-9]*$";
+
+const ROUTE_EXTENSIONS: &[&str] = &["tsx", "ts", "jsx", "js"];
+
+const PLATFORM_SUFFIXES: &[&str] = &["web", "ios", "android", "native"];
+
+/// Enforces Expo Router file conventions under the app directory.
+///
+/// Like `file-presence`, this checks the file tree rather than file content:
+/// - every directory that holds route files needs a `_layout` file
+/// - dynamic segments (`[id]`, `[...rest]`) must have param names matching
+///   `pattern` (default: camelCase)
+/// - platform-specific route files (`index.web.tsx`, `profile.ios.tsx`) are
+///   flagged, since they duplicate the route they shadow
+///
+/// The app directory is `app_dir` when set, otherwise `app` or `src/app`.
+/// Nothing is reported when no app directory exists.
+#[derive(Debug)]
+pub struct ExpoRouterRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    app_dir: Option<String>,
+    segment_pattern: Regex,
+}
+
+impl ExpoRouterRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        let pattern = config.pattern.as_deref().unwrap_or(DEFAULT_SEGMENT_PATTERN);
+        let segment_pattern =
+            Regex::new(pattern).map_err(|e| RuleBuildError::InvalidRegex(config.id.clone(), e))?;
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            app_dir: config.app_dir.clone(),
+            segment_pattern,
+        })
+    }
+
+    /// Check the app directory under each root.
+    pub fn check_paths(&self, root_paths: &[PathBuf]) -> Vec<Violation> {
+        let mut violations = Vec::new();
+        for root in root_paths.iter().filter(|r| r.is_dir()) {
+            let candidates: Vec<&str> = match &self.app_dir {
+                Some(dir) => vec![dir.as_str()],
+                None => DEFAULT_APP_DIRS.to_vec(),
+            };
+            if let Some(rel) = candidates.into_iter().find(|d| root.join(d).is_dir()) {
+                self.check_dir(&root.join(rel), Path::new(rel), &mut violations);
+            }
+        }
+        violations
+    }
+
+    fn check_dir(&self, dir: &Path, rel: &Path, violations: &mut Vec<Violation>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        let mut names: Vec<(String, bool)> = entries
+            .filter_map(|e| e.ok())
+            .filter_map(|e| {
+                let is_dir = e.file_type().ok()?.is_dir();
+                Some((e.file_name().to_str()?.to_string(), is_dir))
+            })
+            .collect();
+        names.sort();
+
+        let mut has_layout = false;
+        let mut has_routes = false;
+        for (name, is_dir) in &names {
+            let path = rel.join(name);
+            if *is_dir {
+                self.check_segment(name, &path, violations);
+                self.check_dir(&dir.join(name), &path, violations);
+                continue;
+            }
+            let Some((stem, ext)) = name.rsplit_once('.') else { continue };
+            if !ROUTE_EXTENSIONS.contains(&ext) {
+                continue;
+            }
+            if stem == "_layout" {
+                has_layout = true;
+                continue;
+            }
+            if stem.starts_with('_') || stem.starts_with('+') {
+                continue;
+            }
+            has_routes = true;
+            self.check_segment(stem, &path, violations);
+            if let Some((route, platform)) = stem.rsplit_once('.') {
+                if PLATFORM_SUFFIXES.contains(&platform) {
+                    violations.push(self.violation(
+                        &path,
+                        format!(
+                            "platform-specific route '{}' duplicates route '{}'",
+                            name, route
+                        ),
+                    ));
+                }
+            }
+        }
+
+        if has_routes && !has_layout {
+            violations.push(self.violation(
+                &rel.join("_layout.tsx"),
+                format!("route directory '{}' has no _layout file", rel.display()),
+            ));
+        }
+    }
+
+    /// Check the param name of a dynamic segment (`[id]`, `[...rest]`).
+    fn check_segment(&self, segment: &str, path: &Path, violations: &mut Vec<Violation>) {
+        let segment = segment.split('.').next().unwrap_or(segment);
+        let Some(param) = segment
+            .strip_prefix('[')
+            .and_then(|s| s.strip_suffix(']'))
+            .map(|s| s.trim_start_matches("..."))
+        else {
+            return;
+        };
+        if !self.segment_pattern.is_match(param) {
+            violations.push(self.violation(
+                path,
+                format!(
+                    "dynamic segment '{}' does not match {}",
+                    segment,
+                    self.segment_pattern.as_str()
+                ),
+            ));
+        }
+    }
+
+    fn violation(&self, path: &Path, detail: String) -> Violation {
+        let message = if self.message.is_empty() {
+            detail
+        } else {
+            format!("{}: {}", self.message, detail)
+        };
+        Violation {
+            rule_id: self.id.clone(),
+            severity: self.severity,
+            file: path.to_path_buf(),
+            line: None,
+            column: None,
+            message,
+            suggest: self.suggest.clone(),
+            source_line: None,
+            fix: None,
+            origin: None,
+            enclosing: None,
+        }
+    }
+}
+
+impl Rule for ExpoRouterRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn file_glob(&self) -> Option<&str> {
+        // Like file-presence, this checks the tree, not file content
+        None
+    }
+
+    fn check_file(&self, _ctx: &ScanContext) -> Vec<Violation> {
+        // Checked via check_paths
+        Vec::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    fn make_rule(config: RuleConfig) -> ExpoRouterRule {
+        ExpoRouterRule::new(&RuleConfig {
+            id: "expo-router-structure".into(),
+            severity: Severity::Warning,
+            ..config
+        })
+        .unwrap()
+    }
+
+    fn touch(root: &Path, files: &[&str]) {
+        for file in files {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+    }
+
+    fn messages(rule: &ExpoRouterRule, root: &Path) -> Vec<String> {
+        rule.check_paths(&[root.to_path_buf()])
+            .into_iter()
+            .map(|v| format!("{}: {}", v.file.display(), v.message))
+            .collect()
+    }
+
+    #[test]
+    fn well_formed_app_passes() {
+        let dir = TempDir::new().unwrap();
+        touch(
+            dir.path(),
+            &[
+                "app/_layout.tsx",
+                "app/+not-found.tsx",
+                "app/index.tsx",
+                "app/(tabs)/_layout.tsx",
+                "app/(tabs)/home.tsx",
+                "app/user/_layout.tsx",
+                "app/user/[userId].tsx",
+                "app/docs/_layout.tsx",
+                "app/docs/[...slug].tsx",
+                "app/api/_layout.tsx",
+                "app/api/hello+api.ts",
+            ],
+        );
+        assert!(messages(&make_rule(RuleConfig::default()), dir.path()).is_empty());
+    }
+
+    #[test]
+    fn reports_each_convention() {
+        let dir = TempDir::new().unwrap();
+        touch(
+            dir.path(),
+            &[
+                "app/_layout.tsx",
+                "app/index.tsx",
+                "app/index.web.tsx",
+                "app/settings/profile.tsx",
+                "app/[user_id]/_layout.tsx",
+                "app/[user_id]/index.tsx",
+            ],
+        );
+        assert_eq!(
+            messages(&make_rule(RuleConfig::default()), dir.path()),
+            [
+                "app/[user_id]: dynamic segment '[user_id]' does not match ^[a-z][a-zA-Z0-9]*$",
+                "app/index.web.tsx: platform-specific route 'index.web.tsx' duplicates route 'index'",
+                "app/settings/_layout.tsx: route directory 'app/settings' has no _layout file",
+            ]
+        );
+    }
+
+    #[test]
+    fn custom_pattern_and_app_dir() {
+        let dir = TempDir::new().unwrap();
+        touch(dir.path(), &["mobile/routes/_layout.tsx", "mobile/routes/[user_id].tsx"]);
+        let rule = make_rule(RuleConfig {
+            pattern: Some("^[a-z_]+$".into()),
+            app_dir: Some("mobile/routes".into()),
+            ..Default::default()
+        });
+        assert!(messages(&rule, dir.path()).is_empty());
+    }
+
+    #[test]
+    fn src_app_is_found_and_missing_app_is_ignored() {
+        let dir = TempDir::new().unwrap();
+        let rule = make_rule(RuleConfig::default());
+        assert!(messages(&rule, dir.path()).is_empty());
+
+        touch(dir.path(), &["src/app/index.tsx"]);
+        assert_eq!(
+            messages(&rule, dir.path()),
+            ["src/app/_layout.tsx: route directory 'src/app' has no _layout file"]
+        );
+    }
+}
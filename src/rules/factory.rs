@@ -3,9 +3,17 @@ use crate::config::RuleConfig;
 use crate::rules::banned_dependency::BannedDependencyRule;
 use crate::rules::banned_import::BannedImportRule;
 use crate::rules::banned_pattern::BannedPatternRule;
+use crate::rules::env_allowlist::EnvAllowlistRule;
+use crate::rules::expo_router::ExpoRouterRule;
+use crate::rules::feature_boundaries::FeatureBoundariesRule;
 use crate::rules::file_presence::FilePresenceRule;
+use crate::rules::no_new_files::NoNewFilesRule;
+use crate::rules::test_colocation::TestColocationRule;
+use crate::rules::package_scripts::PackageScriptsRule;
+use crate::rules::pinned_references::PinnedReferencesRule;
 use crate::rules::ratchet::RatchetRule;
 use crate::rules::required_pattern::RequiredPatternRule;
+use crate::rules::server_only_import::ServerOnlyImportRule;
 use crate::rules::tailwind_dark_mode::TailwindDarkModeRule;
 use crate::rules::tailwind_theme_tokens::TailwindThemeTokensRule;
 use crate::rules::window_pattern::WindowPatternRule;
@@ -21,7 +29,13 @@ pub enum FactoryError {
 impl fmt::Display for FactoryError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            FactoryError::UnknownRuleType(t) => write!(f, "unknown rule type: '{}'", t),
+            FactoryError::UnknownRuleType(t) => {
+                write!(f, "unknown rule type: '{}'", t)?;
+                if let Some(suggestion) = suggest_rule_type(t) {
+                    write!(f, " (did you mean '{}'?)", suggestion)?;
+                }
+                write!(f, "; registered types: {}", RULE_TYPES.join(", "))
+            }
             FactoryError::BuildError(e) => write!(f, "{}", e),
         }
     }
@@ -35,29 +49,203 @@ impl From<RuleBuildError> for FactoryError {
     }
 }
 
-/// Build a rule instance from a type string and config.
+/// Every rule type `build_rule` accepts.
+pub const RULE_TYPES: &[&str] = &[
+    "tailwind-dark-mode",
+    "tailwind-theme-tokens",
+    "ratchet",
+    "banned-pattern",
+    "banned-import",
+    "banned-dependency",
+    "package-scripts",
+    "pinned-references",
+    "required-pattern",
+    "file-presence",
+    "test-colocation",
+    "expo-router-structure",
+    "server-only-import",
+    "window-pattern",
+    "feature-boundaries",
+    "env-allowlist",
+    "no-new-files",
+    "max-component-size",
+    "no-nested-components",
+    "prefer-use-reducer",
+    "no-cascading-set-state",
+    "require-img-alt",
+    "require-icon-button-label",
+    "heading-hierarchy",
+    "require-input-label",
+    "no-stylesheet-in-render",
+    "no-outline-none",
+    "no-div-click-handler",
+    "no-span-click-handler",
+    "no-derived-state-effect",
+    "no-regexp-in-render",
+    "no-object-dep-array",
+    "no-sql-interpolation",
+    "fetch-safety",
+    "no-silent-promise-catch",
+    "route-handler-validation",
+    "no-barrel-files",
+    "no-global-state",
+    "canonical-import",
+];
+
+/// Alternative names accepted for rule types, as `(alias, canonical)`.
+pub const RULE_TYPE_ALIASES: &[(&str, &str)] = &[
+    ("regex", "banned-pattern"),
+    ("forbidden-pattern", "banned-pattern"),
+    ("forbidden-import", "banned-import"),
+    ("forbidden-dependency", "banned-dependency"),
+];
+
+/// Resolve an alias to its canonical rule type. Unknown names are returned
+/// unchanged so the caller can report them.
+pub fn canonical_rule_type(rule_type: &str) -> &str {
+    RULE_TYPE_ALIASES
+        .iter()
+        .find(|(alias, _)| *alias == rule_type)
+        .map(|(_, canonical)| *canonical)
+        .unwrap_or(rule_type)
+}
+
+/// The registered type (or alias, resolved) closest to `rule_type` by edit
+/// distance, if any is close enough to be a plausible typo.
+fn suggest_rule_type(rule_type: &str) -> Option<&'static str> {
+    let max_distance = (rule_type.len() / 3).max(2);
+    RULE_TYPES
+        .iter()
+        .map(|t| (*t, *t))
+        .chain(RULE_TYPE_ALIASES.iter().copied())
+        .map(|(name, canonical)| (edit_distance(rule_type, name), canonical))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, canonical)| canonical)
+}
+
+/// Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut curr = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = prev[j] + usize::from(ca != *cb);
+            curr[j + 1] = substitution.min(prev[j + 1] + 1).min(curr[j] + 1);
+        }
+        prev = curr;
+    }
+    prev[b.len()]
+}
+
+/// Build a rule instance from a type string (or alias) and config.
 pub fn build_rule(rule_type: &str, config: &RuleConfig) -> Result<Box<dyn Rule>, FactoryError> {
-    match rule_type {
+    match canonical_rule_type(rule_type) {
         "tailwind-dark-mode" => Ok(Box::new(TailwindDarkModeRule::new(config)?)),
         "tailwind-theme-tokens" => Ok(Box::new(TailwindThemeTokensRule::new(config)?)),
         "ratchet" => Ok(Box::new(RatchetRule::new(config)?)),
         "banned-pattern" => Ok(Box::new(BannedPatternRule::new(config)?)),
         "banned-import" => Ok(Box::new(BannedImportRule::new(config)?)),
         "banned-dependency" => Ok(Box::new(BannedDependencyRule::new(config)?)),
+        "package-scripts" => Ok(Box::new(PackageScriptsRule::new(config)?)),
+        "pinned-references" => Ok(Box::new(PinnedReferencesRule::new(config)?)),
         "required-pattern" => Ok(Box::new(RequiredPatternRule::new(config)?)),
         "file-presence" => Ok(Box::new(FilePresenceRule::new(config)?)),
+        "test-colocation" => Ok(Box::new(TestColocationRule::new(config)?)),
+        "expo-router-structure" => Ok(Box::new(ExpoRouterRule::new(config)?)),
+        "server-only-import" => Ok(Box::new(ServerOnlyImportRule::new(config)?)),
         "window-pattern" => Ok(Box::new(WindowPatternRule::new(config)?)),
+        "feature-boundaries" => Ok(Box::new(FeatureBoundariesRule::new(config)?)),
+        "env-allowlist" => Ok(Box::new(EnvAllowlistRule::new(config)?)),
+        "no-new-files" => Ok(Box::new(NoNewFilesRule::new(config)?)),
 "max-component-size" => Ok(Box::new(ast::MaxComponentSizeRule::new(config)?)),
 "no-nested-components" => Ok(Box::new(ast::NoNestedComponentsRule::new(config)?)),
 "prefer-use-reducer" => Ok(Box::new(ast::PreferUseReducerRule::new(config)?)),
 "no-cascading-set-state" => Ok(Box::new(ast::NoCascadingSetStateRule::new(config)?)),
 "require-img-alt" => Ok(Box::new(ast::RequireImgAltRule::new(config)?)),
+"require-icon-button-label" => Ok(Box::new(ast::RequireIconButtonLabelRule::new(config)?)),
+"heading-hierarchy" => Ok(Box::new(ast::HeadingHierarchyRule::new(config)?)),
+"require-input-label" => Ok(Box::new(ast::RequireInputLabelRule::new(config)?)),
+"no-stylesheet-in-render" => Ok(Box::new(ast::NoStylesheetInRenderRule::new(config)?)),
 "no-outline-none" => Ok(Box::new(ast::NoOutlineNoneRule::new(config)?)),
 "no-div-click-handler" => Ok(Box::new(ast::NoDivClickHandlerRule::new(config)?)),
 "no-span-click-handler" => Ok(Box::new(ast::NoSpanClickHandlerRule::new(config)?)),
 "no-derived-state-effect" => Ok(Box::new(ast::NoDerivedStateEffectRule::new(config)?)),
 "no-regexp-in-render" => Ok(Box::new(ast::NoRegexpInRenderRule::new(config)?)),
 "no-object-dep-array" => Ok(Box::new(ast::NoObjectDepArrayRule::new(config)?)),
+"no-sql-interpolation" => Ok(Box::new(ast::NoSqlInterpolationRule::new(config)?)),
+"fetch-safety" => Ok(Box::new(ast::FetchSafetyRule::new(config)?)),
+"no-silent-promise-catch" => Ok(Box::new(ast::NoSilentPromiseCatchRule::new(config)?)),
+"route-handler-validation" => Ok(Box::new(ast::RouteHandlerValidationRule::new(config)?)),
+"no-barrel-files" => Ok(Box::new(ast::NoBarrelFilesRule::new(config)?)),
+"no-global-state" => Ok(Box::new(ast::NoGlobalStateRule::new(config)?)),
+"canonical-import" => Ok(Box::new(ast::CanonicalImportRule::new(config)?)),
         _ => Err(FactoryError::UnknownRuleType(rule_type.to_string())),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_listed_rule_type_is_registered() {
+        let config = RuleConfig {
+            id: "t".into(),
+            ..Default::default()
+        };
+        for rule_type in RULE_TYPES {
+            let result = build_rule(rule_type, &config);
+            assert!(
+                !matches!(result, Err(FactoryError::UnknownRuleType(_))),
+                "{} is listed but not registered",
+                rule_type
+            );
+        }
+        assert!(matches!(
+            build_rule("nope", &config),
+            Err(FactoryError::UnknownRuleType(_))
+        ));
+    }
+
+    #[test]
+    fn aliases_resolve_to_registered_types() {
+        assert_eq!(canonical_rule_type("regex"), "banned-pattern");
+        assert_eq!(canonical_rule_type("forbidden-import"), "banned-import");
+        assert_eq!(canonical_rule_type("ratchet"), "ratchet");
+        assert_eq!(canonical_rule_type("nope"), "nope");
+        for (_, canonical) in RULE_TYPE_ALIASES {
+            assert!(RULE_TYPES.contains(canonical));
+        }
+
+        let config = RuleConfig {
+            id: "t".into(),
+            pattern: Some("foo".into()),
+            ..Default::default()
+        };
+        assert!(build_rule("regex", &config).is_ok());
+    }
+
+    #[test]
+    fn unknown_type_error_suggests_closest_type() {
+        let message = FactoryError::UnknownRuleType("banned-patern".into()).to_string();
+        assert!(message.starts_with("unknown rule type: 'banned-patern' (did you mean 'banned-pattern'?)"));
+        assert!(message.contains("registered types: tailwind-dark-mode, "));
+
+        let message = FactoryError::UnknownRuleType("forbiden-import".into()).to_string();
+        assert!(message.contains("did you mean 'banned-import'?"));
+
+        let message = FactoryError::UnknownRuleType("xyz".into()).to_string();
+        assert!(!message.contains("did you mean"));
+        assert!(message.contains("no-object-dep-array"));
+    }
+
+    #[test]
+    fn edit_distance_counts_single_edits() {
+        assert_eq!(edit_distance("ratchet", "ratchet"), 0);
+        assert_eq!(edit_distance("ratchett", "ratchet"), 1);
+        assert_eq!(edit_distance("rachet", "ratchet"), 1);
+        assert_eq!(edit_distance("", "abc"), 3);
+    }
+}
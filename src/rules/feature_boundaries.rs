@@ -0,0 +1,299 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::banned_import::IMPORT_PREFIX;
+use crate::rules::resolve::ImportResolver;
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+use globset::{GlobBuilder, GlobMatcher};
+use regex::Regex;
+use std::path::Path;
+
+/// Module names that make up a feature's public entry point.
+const INDEX_FILES: &[&str] = &["index", "index.ts", "index.tsx", "index.js", "index.jsx"];
+
+/// Keeps features from reaching into each other's internals.
+///
+/// `feature_dirs` is a glob for feature root directories, e.g. `features/*`
+/// (`*` stays within one path segment). A file inside one feature may import
+/// another feature only through its root or `index` module:
+///
+/// - `import { Login } from '../auth'` is allowed
+/// - `import { Login } from '../auth/index'` is allowed
+/// - `import { Login } from '../auth/components/Login'` is reported
+///
+/// Relative specifiers are resolved against the importing file, and aliases
+/// (tsconfig `paths` and `baseUrl`, workspace package names) to the
+/// directories they name; see [`ImportResolver`]. Specifiers that don't
+/// resolve (`@/features/auth/...` without a tsconfig) are matched as
+/// written, so the glob should start at a directory the alias keeps, like
+/// `features/*` rather than `src/features/*`. Files outside every feature
+/// are not checked.
+#[derive(Debug)]
+pub struct FeatureBoundariesRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: Option<String>,
+    feature_dirs: GlobMatcher,
+    import_re: Regex,
+    resolver: ImportResolver,
+}
+
+/// A feature root and the path segments below it that an import names.
+struct FeatureTarget {
+    name: String,
+    inner: Vec<String>,
+}
+
+impl FeatureBoundariesRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        let pattern = config
+            .feature_dirs
+            .as_deref()
+            .ok_or_else(|| RuleBuildError::MissingField(config.id.clone(), "feature_dirs"))?;
+        let pattern = pattern.trim_end_matches('/');
+        let anchored = if pattern.starts_with("**") || pattern.starts_with('/') {
+            pattern.to_string()
+        } else {
+            format!("**/{}", pattern)
+        };
+        let feature_dirs = GlobBuilder::new(&anchored)
+            .literal_separator(true)
+            .build()
+            .map_err(|e| RuleBuildError::InvalidValue(config.id.clone(), "feature_dirs", e.to_string()))?
+            .compile_matcher();
+
+        let import_re = Regex::new(&format!(r#"{}['"]([^'"]+)['"]"#, IMPORT_PREFIX))
+            .map_err(|e| RuleBuildError::InvalidRegex(config.id.clone(), e))?;
+
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob: config
+                .glob
+                .clone()
+                .or(Some("**/*.{ts,tsx,js,jsx,mjs,cjs}".to_string())),
+            feature_dirs,
+            import_re,
+            resolver: ImportResolver::new(),
+        })
+    }
+
+    /// The innermost feature root among `segments`' prefixes, with the
+    /// segments after it.
+    fn feature_of(&self, segments: &[String]) -> Option<FeatureTarget> {
+        (1..=segments.len()).rev().find_map(|len| {
+            let prefix = segments[..len].join("/");
+            self.feature_dirs.is_match(&prefix).then(|| FeatureTarget {
+                name: segments[len - 1].clone(),
+                inner: segments[len..].to_vec(),
+            })
+        })
+    }
+
+    /// The feature an import specifier points into.
+    fn import_target(&self, dir: &Path, specifier: &str) -> Option<FeatureTarget> {
+        match self.resolver.resolve(dir, specifier) {
+            Some(resolved) => self.feature_of(&segments(resolved.path())),
+            None => {
+                let parts: Vec<String> = specifier.split('/').map(str::to_string).collect();
+                self.feature_of(&parts)
+            }
+        }
+    }
+}
+
+fn segments(path: &Path) -> Vec<String> {
+    path.components()
+        .map(|c| c.as_os_str().to_string_lossy().into_owned())
+        .collect()
+}
+
+/// `specifier` with its last `inner` segments dropped, i.e. the import of the
+/// feature root, when the specifier ends with them.
+fn root_specifier(specifier: &str, inner: &[String]) -> Option<String> {
+    let parts: Vec<&str> = specifier.split('/').collect();
+    let keep = parts.len().checked_sub(inner.len())?;
+    (parts[keep..].iter().zip(inner).all(|(a, b)| *a == b) && keep > 0)
+        .then(|| parts[..keep].join("/"))
+}
+
+impl Rule for FeatureBoundariesRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn file_glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+
+    fn shareable(&self) -> bool {
+        false
+    }
+
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        let dir = ctx.file_path.parent().unwrap_or(Path::new(""));
+        let Some(own) = self.feature_of(&segments(dir)) else {
+            return Vec::new();
+        };
+
+        let mut violations = Vec::new();
+        for (line_idx, &line) in ctx.lines().iter().enumerate() {
+            for cap in self.import_re.captures_iter(line) {
+                let specifier = cap.get(1).unwrap().as_str();
+                let Some(target) = self.import_target(dir, specifier) else {
+                    continue;
+                };
+                let public = match target.inner.as_slice() {
+                    [] => true,
+                    [file] => INDEX_FILES.contains(&file.as_str()),
+                    _ => false,
+                };
+                if target.name == own.name || public {
+                    continue;
+                }
+
+                let suggest = if self.suggest.is_empty() {
+                    let root = root_specifier(specifier, &target.inner);
+                    vec![Suggestion::new(match root {
+                        Some(root) => format!(
+                            "import from '{}' and export what you need from its index",
+                            root
+                        ),
+                        None => format!("import from the '{}' feature's index", target.name),
+                    })]
+                } else {
+                    self.suggest.clone()
+                };
+                violations.push(Violation {
+                    rule_id: self.id.clone(),
+                    severity: self.severity,
+                    file: ctx.file_path.to_path_buf(),
+                    line: Some(line_idx + 1),
+                    column: Some(cap.get(0).unwrap().start() + 1),
+                    message: format!(
+                        "{}: '{}' reaches into feature '{}'",
+                        self.message, specifier, target.name
+                    ),
+                    suggest,
+                    source_line: Some(line.to_string()),
+                    fix: None,
+                    origin: None,
+                    enclosing: None,
+                });
+            }
+        }
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn make_rule(feature_dirs: &str) -> FeatureBoundariesRule {
+        let config = RuleConfig {
+            id: "feature-boundaries".into(),
+            severity: Severity::Error,
+            message: "cross-feature import".into(),
+            feature_dirs: Some(feature_dirs.into()),
+            ..Default::default()
+        };
+        FeatureBoundariesRule::new(&config).unwrap()
+    }
+
+    fn check(rule: &FeatureBoundariesRule, file: &str, content: &str) -> Vec<Violation> {
+        let ctx = ScanContext::new(Path::new(file), content);
+        rule.check_file(&ctx)
+    }
+
+    #[test]
+    fn reports_imports_into_another_features_internals() {
+        let rule = make_rule("features/*");
+        let file = "/repo/src/features/billing/components/Plan.tsx";
+        let violations = check(
+            &rule,
+            file,
+            "import { Login } from '../../auth/components/Login';\n\
+             import { useUser } from '@/features/auth/hooks/useUser';\n",
+        );
+        assert_eq!(violations.len(), 2);
+        assert_eq!(
+            violations[0].message,
+            "cross-feature import: '../../auth/components/Login' reaches into feature 'auth'"
+        );
+        assert_eq!(
+            violations[0].suggest_text(),
+            Some("import from '../../auth' and export what you need from its index")
+        );
+        assert_eq!(violations[1].line, Some(2));
+        assert!(violations[1].suggest[0].title.contains("'@/features/auth'"));
+    }
+
+    #[test]
+    fn allows_index_own_feature_and_shared_imports() {
+        let rule = make_rule("features/*");
+        let file = "/repo/src/features/billing/components/Plan.tsx";
+        let content = "import { Login } from '../../auth';\n\
+                       import { Login } from '../../auth/index';\n\
+                       import { useUser } from '@/features/auth/index.ts';\n\
+                       import { Price } from '../utils/price';\n\
+                       import { Button } from '@/components/ui/button';\n\
+                       import React from 'react';\n";
+        assert!(check(&rule, file, content).is_empty());
+    }
+
+    #[test]
+    fn files_outside_features_are_not_checked() {
+        let rule = make_rule("features/*");
+        let content = "import { Login } from '../features/auth/components/Login';\n";
+        assert!(check(&rule, "/repo/src/app/page.tsx", content).is_empty());
+    }
+
+    #[test]
+    fn feature_dirs_can_be_anchored_deeper() {
+        let rule = make_rule("src/modules/*");
+        let file = "/repo/src/modules/cart/api.ts";
+        assert_eq!(check(&rule, file, "import x from '../orders/internal/db';\n").len(), 1);
+        assert!(check(&rule, file, "import x from '../../features/orders/internal/db';\n").is_empty());
+    }
+
+    #[test]
+    fn resolves_tsconfig_aliases() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::write(
+            root.join("tsconfig.json"),
+            r#"{ "compilerOptions": { "paths": { "~/*": ["./src/*"] } } }"#,
+        )
+        .unwrap();
+        let rule = make_rule("src/features/*");
+        let file = root.join("src/features/billing/Plan.tsx");
+        let violations = check(
+            &rule,
+            file.to_str().unwrap(),
+            "import { useUser } from '~/features/auth/hooks/useUser';
+             import { Login } from '~/features/auth';
+",
+        );
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].suggest[0].title.contains("'~/features/auth'"));
+    }
+
+    #[test]
+    fn requires_feature_dirs() {
+        let config = RuleConfig {
+            id: "fb".into(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            FeatureBoundariesRule::new(&config),
+            Err(RuleBuildError::MissingField(_, "feature_dirs"))
+        ));
+    }
+}
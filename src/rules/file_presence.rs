@@ -1,5 +1,6 @@
 use crate::config::{RuleConfig, Severity};
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+use regex::Regex;
 use std::path::PathBuf;
 
 /// Ensures that specific files exist (or do not exist) in the project.
@@ -12,54 +13,130 @@ use std::path::PathBuf;
 /// The `required_files` config field lists relative paths that must exist.
 /// The `forbidden_files` config field lists relative paths that must NOT exist.
 /// The rule emits one violation per missing required file or present forbidden file.
+///
+/// The `must_contain` table adds content assertions: each listed file is
+/// required and must contain every pattern given for it (literal, or regex
+/// with `regex = true`). A file that exists but lacks a pattern gets its own
+/// violation per missing pattern.
 #[derive(Debug)]
 pub struct FilePresenceRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     required_files: Vec<String>,
     forbidden_files: Vec<String>,
+    /// (file, [(pattern text, matcher)])
+    must_contain: Vec<(String, Vec<(String, Regex)>)>,
 }
 
 impl FilePresenceRule {
     pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
-        if config.required_files.is_empty() && config.forbidden_files.is_empty() {
+        if config.required_files.is_empty()
+            && config.forbidden_files.is_empty()
+            && config.must_contain.is_empty()
+        {
             return Err(RuleBuildError::MissingField(
                 config.id.clone(),
                 "required_files or forbidden_files",
             ));
         }
 
+        let mut must_contain = Vec::new();
+        for (file, patterns) in &config.must_contain {
+            let matchers = patterns
+                .iter()
+                .map(|p| {
+                    let source = if config.regex { p.clone() } else { regex::escape(p) };
+                    Regex::new(&source)
+                        .map(|re| (p.clone(), re))
+                        .map_err(|e| RuleBuildError::InvalidRegex(config.id.clone(), e))
+                })
+                .collect::<Result<Vec<_>, _>>()?;
+            must_contain.push((file.clone(), matchers));
+        }
+
+        // Files with content assertions are implicitly required
+        let mut required_files = config.required_files.clone();
+        for file in config.must_contain.keys() {
+            if !required_files.contains(file) {
+                required_files.push(file.clone());
+            }
+        }
+
         Ok(Self {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
-            required_files: config.required_files.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            required_files,
             forbidden_files: config.forbidden_files.clone(),
+            must_contain,
         })
     }
 
+    /// The first existing path for `rel` under any of the roots.
+    fn locate(root_paths: &[PathBuf], rel: &str) -> Option<PathBuf> {
+        root_paths
+            .iter()
+            .map(|root| {
+                if root.is_dir() {
+                    root.join(rel)
+                } else {
+                    // If root is a file, check relative to its parent
+                    root.parent()
+                        .map(|p| p.join(rel))
+                        .unwrap_or_else(|| PathBuf::from(rel))
+                }
+            })
+            .find(|path| path.exists())
+    }
+
+    /// Violations for `must_contain` patterns missing from an existing file.
+    fn check_content(&self, rel: &str, path: &PathBuf) -> Vec<Violation> {
+        let Some((_, patterns)) = self.must_contain.iter().find(|(file, _)| file == rel) else {
+            return Vec::new();
+        };
+        let content = std::fs::read_to_string(path).unwrap_or_default();
+
+        patterns
+            .iter()
+            .filter(|(_, re)| !re.is_match(&content))
+            .map(|(pattern, _)| {
+                let msg = if self.message.is_empty() {
+                    format!("Required file '{}' exists but does not contain '{}'", rel, pattern)
+                } else {
+                    format!("{}: '{}' does not contain '{}'", self.message, rel, pattern)
+                };
+                Violation {
+                    rule_id: self.id.clone(),
+                    severity: self.severity,
+                    file: PathBuf::from(rel),
+                    line: None,
+                    column: None,
+                    message: msg,
+                    suggest: self.suggest.clone(),
+                    source_line: None,
+                    fix: None,
+                    origin: None,
+                    enclosing: None,
+                }
+            })
+            .collect()
+    }
+
     /// Check which required files are missing and which forbidden files exist.
     /// Returns violations for each missing required file or present forbidden file.
     pub fn check_paths(&self, root_paths: &[PathBuf]) -> Vec<Violation> {
         let mut violations = Vec::new();
 
         for required in &self.required_files {
-            let exists = root_paths.iter().any(|root| {
-                let check_path = if root.is_dir() {
-                    root.join(required)
-                } else {
-                    // If root is a file, check relative to its parent
-                    root.parent()
-                        .map(|p| p.join(required))
-                        .unwrap_or_else(|| PathBuf::from(required))
-                };
-                check_path.exists()
-            });
+            let found = Self::locate(root_paths, required);
+            if let Some(path) = &found {
+                violations.extend(self.check_content(required, path));
+            }
 
-            if !exists {
+            if found.is_none() {
                 let msg = if self.message.is_empty() {
                     format!("Required file '{}' is missing", required)
                 } else {
@@ -76,23 +153,14 @@ impl FilePresenceRule {
                     suggest: self.suggest.clone(),
                     source_line: None,
                     fix: None,
+                    origin: None,
+                    enclosing: None,
                 });
             }
         }
 
         for forbidden in &self.forbidden_files {
-            let exists = root_paths.iter().any(|root| {
-                let check_path = if root.is_dir() {
-                    root.join(forbidden)
-                } else {
-                    root.parent()
-                        .map(|p| p.join(forbidden))
-                        .unwrap_or_else(|| PathBuf::from(forbidden))
-                };
-                check_path.exists()
-            });
-
-            if exists {
+            if Self::locate(root_paths, forbidden).is_some() {
                 let msg = if self.message.is_empty() {
                     format!("Forbidden file '{}' must not exist", forbidden)
                 } else {
@@ -109,6 +177,8 @@ impl FilePresenceRule {
                     suggest: self.suggest.clone(),
                     source_line: None,
                     fix: None,
+                    origin: None,
+                    enclosing: None,
                 });
             }
         }
@@ -148,7 +218,7 @@ mod tests {
             id: "test-file-presence".into(),
             severity: Severity::Error,
             message: "required file missing".into(),
-            suggest: Some("create the required file".into()),
+            suggest: vec!["create the required file".into()],
             required_files: files.into_iter().map(|s| s.to_string()).collect(),
             ..Default::default()
         };
@@ -204,6 +274,75 @@ mod tests {
         assert!(matches!(err, RuleBuildError::MissingField(_, _)));
     }
 
+    fn make_content_rule(entries: &[(&str, &[&str])], regex: bool) -> FilePresenceRule {
+        let config = RuleConfig {
+            id: "test-content".into(),
+            severity: Severity::Error,
+            message: "".into(),
+            regex,
+            must_contain: entries
+                .iter()
+                .map(|(file, patterns)| {
+                    (file.to_string(), patterns.iter().map(|p| p.to_string()).collect())
+                })
+                .collect(),
+            ..Default::default()
+        };
+        FilePresenceRule::new(&config).unwrap()
+    }
+
+    #[test]
+    fn must_contain_reports_missing_content() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join(".gitignore"), "node_modules\n.env\n").unwrap();
+        fs::write(dir.path().join("tsconfig.json"), r#"{ "compilerOptions": {} }"#).unwrap();
+        let rule = make_content_rule(
+            &[
+                (".gitignore", &[".env", "dist"]),
+                ("tsconfig.json", &[r#""strict": true"#]),
+            ],
+            false,
+        );
+
+        let violations = rule.check_paths(&[dir.path().to_path_buf()]);
+        let messages: Vec<&str> = violations.iter().map(|v| v.message.as_str()).collect();
+        assert_eq!(
+            messages,
+            [
+                "Required file '.gitignore' exists but does not contain 'dist'",
+                r#"Required file 'tsconfig.json' exists but does not contain '"strict": true'"#,
+            ]
+        );
+    }
+
+    #[test]
+    fn must_contain_implies_required() {
+        let dir = TempDir::new().unwrap();
+        let rule = make_content_rule(&[(".gitignore", &[".env"])], false);
+        let violations = rule.check_paths(&[dir.path().to_path_buf()]);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].message, "Required file '.gitignore' is missing");
+    }
+
+    #[test]
+    fn must_contain_regex_patterns() {
+        let dir = TempDir::new().unwrap();
+        fs::write(dir.path().join("tsconfig.json"), r#"{ "strict":true }"#).unwrap();
+        let rule = make_content_rule(&[("tsconfig.json", &[r#""strict"\s*:\s*true"#])], true);
+        assert!(rule.check_paths(&[dir.path().to_path_buf()]).is_empty());
+
+        let config = RuleConfig {
+            id: "bad".into(),
+            regex: true,
+            must_contain: [("a".to_string(), vec!["(".to_string()])].into_iter().collect(),
+            ..Default::default()
+        };
+        assert!(matches!(
+            FilePresenceRule::new(&config).unwrap_err(),
+            RuleBuildError::InvalidRegex(_, _)
+        ));
+    }
+
     fn make_forbidden_rule(files: Vec<&str>) -> FilePresenceRule {
         let config = RuleConfig {
             id: "test-forbidden".into(),
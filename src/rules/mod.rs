@@ -2,15 +2,27 @@ pub mod ast;
 pub mod banned_dependency;
 pub mod banned_import;
 pub mod banned_pattern;
+pub mod env_allowlist;
+pub mod expo_router;
 pub mod factory;
+pub mod feature_boundaries;
 pub mod file_presence;
+pub mod no_new_files;
+pub mod package_scripts;
+pub mod pinned_references;
 pub mod ratchet;
 pub mod required_pattern;
+pub mod resolve;
+pub mod server_only_import;
 pub mod tailwind_dark_mode;
 pub mod tailwind_theme_tokens;
+pub mod test_colocation;
 pub mod window_pattern;
 
-use crate::config::Severity;
+use crate::config::{ColumnUnit, Severity};
+use crate::git_diff::FileStatus;
+use std::cell::OnceCell;
+use std::collections::BTreeSet;
 use std::path::{Path, PathBuf};
 
 /// A lint rule that checks source files for violations.
@@ -26,21 +38,263 @@ pub trait Rule: Send + Sync {
 
     /// Scan a single file and return any violations found.
     fn check_file(&self, ctx: &ScanContext) -> Vec<Violation>;
+
+    /// Whether one instance may serve several scans, including concurrent
+    /// ones. Rules that cache what they read from project files (tsconfig,
+    /// lockfiles) return `false`, so every scan builds them fresh and sees
+    /// edits to those files.
+    fn shareable(&self) -> bool {
+        true
+    }
 }
 
 /// The file currently being scanned.
+///
+/// Facts that several rules need about the file (its lines, workspace
+/// package, Next.js router, and module directive) are resolved on first use
+/// and then shared by every rule that checks the file.
 pub struct ScanContext<'a> {
     pub file_path: &'a Path,
     pub content: &'a str,
+    lines: OnceCell<LineIndex<'a>>,
+    package: OnceCell<Option<PackageInfo>>,
+    directive: OnceCell<Option<Directive>>,
+    diff_status: Option<FileStatus>,
+}
+
+impl<'a> ScanContext<'a> {
+    pub fn new(file_path: &'a Path, content: &'a str) -> Self {
+        Self {
+            file_path,
+            content,
+            lines: OnceCell::new(),
+            package: OnceCell::new(),
+            directive: OnceCell::new(),
+            diff_status: None,
+        }
+    }
+
+    /// Record how the file changed in a `--changed-only` diff.
+    pub fn with_diff_status(mut self, status: Option<FileStatus>) -> Self {
+        self.diff_status = status;
+        self
+    }
+
+    /// Whether the file is added or modified relative to the `--changed-only`
+    /// base ref. `None` outside `--changed-only`, and for unchanged files.
+    pub fn diff_status(&self) -> Option<FileStatus> {
+        self.diff_status
+    }
+
+    /// The file's line index. Prefer this over `content.lines()` so the
+    /// split happens once per file rather than once per rule.
+    pub fn line_index(&self) -> &LineIndex<'a> {
+        self.lines.get_or_init(|| LineIndex::new(self.content))
+    }
+
+    /// The file's lines, as `content.lines()` would yield them.
+    pub fn lines(&self) -> &[&'a str] {
+        &self.line_index().lines
+    }
+
+    /// The text of line `idx` (0-based), for a violation's `source_line`.
+    pub fn source_line(&self, idx: usize) -> Option<String> {
+        self.lines().get(idx).map(|l| l.to_string())
+    }
+
+    /// The nearest `package.json` at or above the file.
+    pub fn package(&self) -> Option<&PackageInfo> {
+        self.package
+            .get_or_init(|| {
+                let dir = self.file_path.parent()?;
+                let root = dir.ancestors().find(|d| d.join("package.json").is_file())?;
+                Some(PackageInfo::read(root))
+            })
+            .as_ref()
+    }
+
+    /// Which Next.js router the file belongs to: under `app/` or `pages/`
+    /// (optionally inside `src/`) at the package root. Without a package,
+    /// the outermost `app` or `pages` directory in the path decides.
+    pub fn router(&self) -> Option<Router> {
+        let dir = self.file_path.parent()?;
+        let router = |name: &str| match name {
+            "app" => Some(Router::App),
+            "pages" => Some(Router::Pages),
+            _ => None,
+        };
+        let names: Vec<String> = match self.package() {
+            Some(package) => dir
+                .strip_prefix(&package.root)
+                .ok()?
+                .components()
+                .take(2)
+                .map(|c| c.as_os_str().to_string_lossy().into_owned())
+                .collect(),
+            None => {
+                return dir
+                    .components()
+                    .find_map(|c| router(&c.as_os_str().to_string_lossy()))
+            }
+        };
+        match names.as_slice() {
+            [first, ..] if router(first).is_some() => router(first),
+            [src, second] if src == "src" => router(second),
+            _ => None,
+        }
+    }
+
+    /// The file's `"use client"` / `"use server"` directive, if any.
+    pub fn directive(&self) -> Option<Directive> {
+        *self.directive.get_or_init(|| Directive::parse(self.content))
+    }
+}
+
+/// A file's lines and the byte offset each one starts at.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineIndex<'a> {
+    lines: Vec<&'a str>,
+    starts: Vec<usize>,
+}
+
+impl<'a> LineIndex<'a> {
+    pub fn new(content: &'a str) -> Self {
+        let starts = std::iter::once(0)
+            .chain(content.match_indices('\n').map(|(i, _)| i + 1))
+            .collect();
+        Self {
+            lines: content.lines().collect(),
+            starts,
+        }
+    }
+
+    pub fn lines(&self) -> &[&'a str] {
+        &self.lines
+    }
+
+    /// Byte offset where `line` (1-based) starts.
+    pub fn line_start(&self, line: usize) -> Option<usize> {
+        self.starts.get(line.checked_sub(1)?).copied()
+    }
+
+    /// The 1-based `(line, column)` of a byte offset, with the column
+    /// counted in bytes, as rules report it.
+    pub fn position(&self, offset: usize) -> (usize, usize) {
+        let idx = self.starts.partition_point(|&start| start <= offset) - 1;
+        (idx + 1, offset - self.starts[idx] + 1)
+    }
+
+    /// Byte offset of a 1-based `(line, column)`; the inverse of `position`.
+    pub fn offset(&self, line: usize, column: usize) -> Option<usize> {
+        Some(self.line_start(line)? + column.checked_sub(1)?)
+    }
+}
+
+/// Identity and dependencies of a workspace package, from its `package.json`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PackageInfo {
+    /// The directory holding `package.json`.
+    pub root: PathBuf,
+    pub name: Option<String>,
+    /// Names from `dependencies`, `devDependencies`, `peerDependencies` and
+    /// `optionalDependencies`.
+    pub dependencies: BTreeSet<String>,
+}
+
+impl PackageInfo {
+    /// Read `root/package.json`. A manifest that can't be parsed yields a
+    /// package with no name or dependencies.
+    pub fn read(root: &Path) -> Self {
+        let json: serde_json::Value = std::fs::read_to_string(root.join("package.json"))
+            .ok()
+            .and_then(|text| serde_json::from_str(&text).ok())
+            .unwrap_or_default();
+        let dependencies = ["dependencies", "devDependencies", "peerDependencies", "optionalDependencies"]
+            .iter()
+            .filter_map(|key| json.get(key)?.as_object())
+            .flat_map(|deps| deps.keys().cloned())
+            .collect();
+        Self {
+            root: root.to_path_buf(),
+            name: json.get("name").and_then(|n| n.as_str()).map(str::to_string),
+            dependencies,
+        }
+    }
+
+    pub fn depends_on(&self, name: &str) -> bool {
+        self.dependencies.contains(name)
+    }
+}
+
+/// A Next.js router, as decided by where a file lives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Router {
+    App,
+    Pages,
+}
+
+/// A React module directive.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Directive {
+    UseClient,
+    UseServer,
+}
+
+impl Directive {
+    /// The directive in `content`'s prologue. Directives must come before any
+    /// other statement, so only the leading comment and blank lines are skipped.
+    pub fn parse(content: &str) -> Option<Self> {
+        for line in content.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with("//") || line.starts_with("/*") || line.starts_with('*') {
+                continue;
+            }
+            let stmt = line.trim_end_matches(';').trim_matches(['"', '\'']);
+            return match stmt {
+                "use client" => Some(Directive::UseClient),
+                "use server" => Some(Directive::UseServer),
+                _ => None,
+            };
+        }
+        None
+    }
 }
 
 /// Machine-actionable fix data for a violation.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Fix {
     pub old: String,
     pub new: String,
 }
 
+/// One way to resolve a violation: a short description, plus an edit when
+/// the remediation is mechanical. A violation may offer several.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Suggestion {
+    pub title: String,
+    pub fix: Option<Fix>,
+}
+
+impl Suggestion {
+    pub fn new(title: impl Into<String>) -> Self {
+        Self {
+            title: title.into(),
+            fix: None,
+        }
+    }
+
+    pub fn with_fix(title: impl Into<String>, fix: Fix) -> Self {
+        Self {
+            title: title.into(),
+            fix: Some(fix),
+        }
+    }
+
+    /// Suggestions from a rule's configured `suggest` text, one per entry.
+    pub fn from_config(suggest: &[String]) -> Vec<Suggestion> {
+        suggest.iter().map(Suggestion::new).collect()
+    }
+}
+
 /// A single violation emitted by a rule.
 #[derive(Debug, Clone)]
 pub struct Violation {
@@ -48,11 +302,83 @@ pub struct Violation {
     pub severity: Severity,
     pub file: PathBuf,
     pub line: Option<usize>,
+    /// 1-based byte column; output converts it with [`Violation::column_in`].
     pub column: Option<usize>,
     pub message: String,
-    pub suggest: Option<String>,
+    /// Alternative remediations, most preferred first.
+    pub suggest: Vec<Suggestion>,
     pub source_line: Option<String>,
     pub fix: Option<Fix>,
+    /// Config file whose rule produced this violation; set only when a scan
+    /// evaluates several configs (`--config a.toml --config b.toml`).
+    pub origin: Option<PathBuf>,
+    /// Function, method or component the violation is in (`Button`,
+    /// `Cart.render`), filled in by the scan for JS/TS files.
+    pub enclosing: Option<String>,
+}
+
+impl Violation {
+    /// Title of the preferred suggestion, if any.
+    pub fn suggest_text(&self) -> Option<&str> {
+        self.suggest.first().map(|s| s.title.as_str())
+    }
+
+    /// `column` counted in `unit`, measured against `source_line`. A column
+    /// without a source line, or one that falls inside a character, stays
+    /// in bytes.
+    pub fn column_in(&self, unit: ColumnUnit) -> Option<usize> {
+        let column = self.column?;
+        let prefix = self
+            .source_line
+            .as_deref()
+            .and_then(|line| line.get(..column.checked_sub(1)?));
+        Some(prefix.map_or(column, |p| unit.measure(p) + 1))
+    }
+
+    /// Every edit offered for this violation with its title: the primary
+    /// `fix` first, then fixes carried by other suggestions.
+    pub fn fixes(&self) -> Vec<(Option<&str>, &Fix)> {
+        let mut fixes = Vec::new();
+        if let Some(fix) = &self.fix {
+            let title = self
+                .suggest
+                .iter()
+                .find(|s| s.fix.as_ref() == Some(fix))
+                .map(|s| s.title.as_str())
+                .or_else(|| self.suggest_text());
+            fixes.push((title, fix));
+        }
+        for s in &self.suggest {
+            if let Some(fix) = s.fix.as_ref().filter(|f| self.fix.as_ref() != Some(*f)) {
+                fixes.push((Some(s.title.as_str()), fix));
+            }
+        }
+        fixes
+    }
+
+    /// Stable identity of this violation: a hex FNV-1a hash of the rule,
+    /// severity, file, position, and message. The same tree scanned with the
+    /// same config yields the same fingerprints on every platform and build.
+    pub fn fingerprint(&self) -> String {
+        let file = self.file.to_string_lossy().replace('\\', "/");
+        let line = self.line.map(|l| l.to_string()).unwrap_or_default();
+        let column = self.column.map(|c| c.to_string()).unwrap_or_default();
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        for part in [
+            self.rule_id.as_str(),
+            self.severity.as_str(),
+            &file,
+            &line,
+            &column,
+            &self.message,
+        ] {
+            for byte in part.bytes().chain(std::iter::once(0)) {
+                hash ^= u64::from(byte);
+                hash = hash.wrapping_mul(0x0100_0000_01b3);
+            }
+        }
+        format!("{:016x}", hash)
+    }
 }
 
 /// Errors that can occur when constructing a rule from config.
@@ -60,6 +386,8 @@ pub struct Violation {
 pub enum RuleBuildError {
     InvalidRegex(String, regex::Error),
     MissingField(String, &'static str),
+    /// A field is present but its value can't be used: (rule id, field, reason).
+    InvalidValue(String, &'static str, String),
 }
 
 impl std::fmt::Display for RuleBuildError {
@@ -71,8 +399,105 @@ impl std::fmt::Display for RuleBuildError {
             RuleBuildError::MissingField(id, field) => {
                 write!(f, "rule '{}': missing required field '{}'", id, field)
             }
+            RuleBuildError::InvalidValue(id, field, reason) => {
+                write!(f, "rule '{}': invalid '{}': {}", id, field, reason)
+            }
         }
     }
 }
 
 impl std::error::Error for RuleBuildError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn line_index_maps_offsets_to_positions() {
+        let content = "one\r\ntwo\n\nfour";
+        let ctx = ScanContext::new(Path::new("a.ts"), content);
+        assert_eq!(ctx.lines(), content.lines().collect::<Vec<_>>().as_slice());
+        assert_eq!(ctx.source_line(1).as_deref(), Some("two"));
+        assert_eq!(ctx.source_line(4), None);
+
+        let index = ctx.line_index();
+        let four = content.find("four").unwrap();
+        assert_eq!(index.position(0), (1, 1));
+        assert_eq!(index.position(content.find("two").unwrap() + 2), (2, 3));
+        assert_eq!(index.position(four), (4, 1));
+        assert_eq!(index.position(content.len()), (4, 5));
+        assert_eq!(index.offset(4, 1), Some(four));
+        assert_eq!(index.line_start(5), None);
+    }
+
+    #[test]
+    fn column_in_converts_byte_columns() {
+        let violation = |column, source_line: Option<&str>| Violation {
+            rule_id: "r".into(),
+            severity: Severity::Error,
+            file: PathBuf::from("a.ts"),
+            line: Some(1),
+            column,
+            message: String::new(),
+            suggest: Vec::new(),
+            source_line: source_line.map(String::from),
+            fix: None,
+            origin: None,
+            enclosing: None,
+        };
+        // "ü" is 2 bytes / 1 char / 1 unit, "𝒳" is 4 bytes / 1 char / 2 units
+        let v = violation(Some(8), Some("ü𝒳 = x"));
+        assert_eq!(v.column_in(ColumnUnit::Bytes), Some(8));
+        assert_eq!(v.column_in(ColumnUnit::Chars), Some(4));
+        assert_eq!(v.column_in(ColumnUnit::Utf16), Some(5));
+
+        // Nothing to measure against, or a column inside a character
+        assert_eq!(violation(Some(8), None).column_in(ColumnUnit::Utf16), Some(8));
+        assert_eq!(violation(Some(2), Some("ü")).column_in(ColumnUnit::Chars), Some(2));
+        assert_eq!(violation(None, Some("ü")).column_in(ColumnUnit::Chars), None);
+    }
+
+    #[test]
+    fn directive_is_read_from_the_prologue() {
+        let directive = |content| ScanContext::new(Path::new("a.tsx"), content).directive();
+        assert_eq!(directive("// header\n\n'use client';\nimport x from 'y';"), Some(Directive::UseClient));
+        assert_eq!(directive("\"use server\"\n"), Some(Directive::UseServer));
+        assert_eq!(directive("import x from 'y';\n'use client';"), None);
+    }
+
+    #[test]
+    fn package_and_router_come_from_the_nearest_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let web = dir.path().join("apps/web");
+        std::fs::create_dir_all(web.join("src/app/dashboard")).unwrap();
+        std::fs::create_dir_all(web.join("components/pages")).unwrap();
+        std::fs::write(
+            web.join("package.json"),
+            r#"{ "name": "@acme/web", "dependencies": { "next": "15" }, "devDependencies": { "vitest": "2" } }"#,
+        )
+        .unwrap();
+
+        let page = web.join("src/app/dashboard/page.tsx");
+        let ctx = ScanContext::new(&page, "");
+        let package = ctx.package().unwrap();
+        assert_eq!(package.root, web);
+        assert_eq!(package.name.as_deref(), Some("@acme/web"));
+        assert!(package.depends_on("next") && package.depends_on("vitest"));
+        assert!(!package.depends_on("react"));
+        assert_eq!(ctx.router(), Some(Router::App));
+
+        // `pages` below the package root is just a folder name
+        let card = web.join("components/pages/Card.tsx");
+        assert_eq!(ScanContext::new(&card, "").router(), None);
+        let outside = dir.path().join("scripts/build.ts");
+        assert!(ScanContext::new(&outside, "").package().is_none());
+    }
+
+    #[test]
+    fn router_without_a_package_uses_the_outermost_directory() {
+        let router = |path| ScanContext::new(Path::new(path), "").router();
+        assert_eq!(router("no-such-root/pages/api/user.ts"), Some(Router::Pages));
+        assert_eq!(router("no-such-root/app/pages/x.tsx"), Some(Router::App));
+        assert_eq!(router("no-such-root/lib/x.ts"), None);
+    }
+}
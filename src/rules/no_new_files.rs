@@ -0,0 +1,110 @@
+use crate::config::{RuleConfig, Severity};
+use crate::git_diff::FileStatus;
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+
+/// Flags files the diff adds under legacy paths (`glob`), while files that
+/// already existed there can keep changing.
+///
+/// Only `--changed-only` scans know which files are new, so a full scan never
+/// reports anything. Files renamed or copied into the glob count as modified.
+/// The violation has no line, since it's about the file as a whole.
+pub struct NoNewFilesRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: String,
+}
+
+impl NoNewFilesRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        let glob = config
+            .glob
+            .clone()
+            .ok_or_else(|| RuleBuildError::MissingField(config.id.clone(), "glob"))?;
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob,
+        })
+    }
+}
+
+impl Rule for NoNewFilesRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+    fn file_glob(&self) -> Option<&str> {
+        Some(&self.glob)
+    }
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        if ctx.diff_status() != Some(FileStatus::Added) {
+            return Vec::new();
+        }
+        let suggest = if self.suggest.is_empty() {
+            vec![Suggestion::new(format!("add the file outside {}", self.glob))]
+        } else {
+            self.suggest.clone()
+        };
+        vec![Violation {
+            rule_id: self.id.clone(),
+            severity: self.severity,
+            file: ctx.file_path.to_path_buf(),
+            line: None,
+            column: None,
+            message: self.message.clone(),
+            suggest,
+            source_line: None,
+            fix: None,
+            origin: None,
+            enclosing: None,
+        }]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn rule() -> NoNewFilesRule {
+        NoNewFilesRule::new(&RuleConfig {
+            id: "no-new-pages".into(),
+            glob: Some("pages/**".into()),
+            message: "New routes go in app/".into(),
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn flags_only_added_files() {
+        let path = Path::new("pages/settings.tsx");
+        let check = |status| rule().check_file(&ScanContext::new(path, "").with_diff_status(status));
+
+        let violations = check(Some(FileStatus::Added));
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line, None);
+        assert_eq!(violations[0].message, "New routes go in app/");
+        assert_eq!(violations[0].suggest[0].title, "add the file outside pages/**");
+
+        assert!(check(Some(FileStatus::Modified)).is_empty());
+        assert!(check(None).is_empty());
+    }
+
+    #[test]
+    fn requires_glob() {
+        let err = NoNewFilesRule::new(&RuleConfig {
+            id: "legacy".into(),
+            ..Default::default()
+        })
+        .err()
+        .unwrap();
+        assert_eq!(err.to_string(), "rule 'legacy': missing required field 'glob'");
+    }
+}
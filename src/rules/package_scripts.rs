@@ -0,0 +1,539 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+use regex::Regex;
+
+/// Names accepted in `checks`.
+const CHECKS: &[&str] = &["unguarded-rm", "pipe-to-shell"];
+
+/// `rm -rf` targets that wipe far more than a build directory.
+const DANGEROUS_TARGETS: &[&str] = &["/", "/*", "~", "~/", "~/*", "*", ".", "./", "..", "../"];
+
+/// Checks the `scripts` of `package.json` (or another manifest) for risky
+/// shell.
+///
+/// - `unguarded-rm`: `rm -rf` (any spelling of recursive + force) aimed at
+///   `/`, `~`, `*`, `.` or a variable expansion. `${DIR:?}` guards a
+///   variable against being empty and is allowed.
+/// - `pipe-to-shell`: a `curl` / `wget` download piped into `sh`, `bash`
+///   and the like.
+///
+/// `checks` picks which of these run; by default both do. `pattern` (with
+/// `regex`) bans further constructs in script bodies, and `scripts` lists
+/// scripts the manifest must define, e.g. `lint` and `typecheck`.
+/// Violations point at the offending script through a JSON parser that
+/// keeps source offsets.
+#[derive(Debug)]
+pub struct PackageScriptsRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: Option<String>,
+    manifest: String,
+    unguarded_rm: bool,
+    pipe_to_shell: Option<Regex>,
+    pattern: Option<BannedConstruct>,
+    required: Vec<String>,
+}
+
+#[derive(Debug)]
+enum BannedConstruct {
+    Literal(String),
+    Regex(Regex),
+}
+
+impl BannedConstruct {
+    fn find(&self, text: &str) -> Option<usize> {
+        match self {
+            BannedConstruct::Literal(s) => text.find(s.as_str()),
+            BannedConstruct::Regex(re) => re.find(text).map(|m| m.start()),
+        }
+    }
+}
+
+impl PackageScriptsRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        if let Some(unknown) = config.checks.iter().find(|c| !CHECKS.contains(&c.as_str())) {
+            return Err(RuleBuildError::InvalidValue(
+                config.id.clone(),
+                "checks",
+                format!("unknown check '{}' (expected one of: {})", unknown, CHECKS.join(", ")),
+            ));
+        }
+        let enabled = |check: &str| config.checks.is_empty() || config.checks.iter().any(|c| c == check);
+
+        let pipe_to_shell = if enabled("pipe-to-shell") {
+            let re = Regex::new(r"\b(?:curl|wget)\b[^|;&]*\|\s*(?:sudo\s+)?(?:ba|z|da|k)?sh\b")
+                .map_err(|e| RuleBuildError::InvalidRegex(config.id.clone(), e))?;
+            Some(re)
+        } else {
+            None
+        };
+        let pattern = match &config.pattern {
+            Some(p) if config.regex => Some(BannedConstruct::Regex(
+                Regex::new(p).map_err(|e| RuleBuildError::InvalidRegex(config.id.clone(), e))?,
+            )),
+            Some(p) => Some(BannedConstruct::Literal(p.clone())),
+            None => None,
+        };
+        let manifest = config.manifest.as_deref().unwrap_or("package.json").to_string();
+
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob: config.glob.clone().or_else(|| Some(format!("**/{}", manifest))),
+            manifest,
+            unguarded_rm: enabled("unguarded-rm"),
+            pipe_to_shell,
+            pattern,
+            required: config.scripts.clone(),
+        })
+    }
+
+    /// Problems in one script body, as (offset in `body`, detail).
+    fn check_script(&self, name: &str, body: &str) -> Vec<(usize, String)> {
+        let mut found = Vec::new();
+        if self.unguarded_rm {
+            for (offset, target) in unguarded_rm(body) {
+                found.push((
+                    offset,
+                    format!("script '{}' runs `rm -rf` on an unguarded path '{}'", name, target),
+                ));
+            }
+        }
+        if let Some(m) = self.pipe_to_shell.as_ref().and_then(|re| re.find(body)) {
+            found.push((m.start(), format!("script '{}' pipes a download into a shell", name)));
+        }
+        if let Some(offset) = self.pattern.as_ref().and_then(|p| p.find(body)) {
+            found.push((offset, format!("script '{}' uses a banned construct", name)));
+        }
+        found
+    }
+
+    fn violation(&self, ctx: &ScanContext, offset: usize, detail: String) -> Violation {
+        let (line, column) = ctx.line_index().position(offset);
+        Violation {
+            rule_id: self.id.clone(),
+            severity: self.severity,
+            file: ctx.file_path.to_path_buf(),
+            line: Some(line),
+            column: Some(column),
+            message: format!("{}: {}", self.message, detail),
+            suggest: self.suggest.clone(),
+            source_line: ctx.source_line(line - 1),
+            fix: None,
+            origin: None,
+            enclosing: None,
+        }
+    }
+}
+
+impl Rule for PackageScriptsRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn file_glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        let file_name = ctx.file_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        if file_name != self.manifest {
+            return Vec::new();
+        }
+        // Malformed manifests are left to other tooling
+        let Some(root) = json::parse(ctx.content) else {
+            return Vec::new();
+        };
+        let json::Value::Object(members) = &root.value else {
+            return Vec::new();
+        };
+        let scripts = members.iter().find(|m| m.key == "scripts");
+
+        let mut violations = Vec::new();
+        let mut defined = Vec::new();
+        if let Some(json::Value::Object(entries)) = scripts.map(|m| &m.value.value) {
+            for entry in entries {
+                defined.push(entry.key.as_str());
+                let json::Value::String(body) = &entry.value.value else {
+                    continue;
+                };
+                for (offset, detail) in self.check_script(&entry.key, body) {
+                    let at = entry.value.source_offset(ctx.content, offset);
+                    violations.push(self.violation(ctx, at, detail));
+                }
+            }
+        }
+
+        // Missing scripts are reported at `"scripts"`, or the top of the file
+        let anchor = scripts.map_or(root.start, |m| m.key_start);
+        for name in self.required.iter().filter(|n| !defined.contains(&n.as_str())) {
+            violations.push(self.violation(ctx, anchor, format!("missing required script '{}'", name)));
+        }
+        violations
+    }
+}
+
+/// `rm -rf` commands in `script` with a dangerous target, as (offset of
+/// `rm`, target).
+fn unguarded_rm(script: &str) -> Vec<(usize, String)> {
+    let mut found = Vec::new();
+    for (start, command) in commands(script) {
+        let mut words = command.split_whitespace().skip_while(|w| *w == "sudo" || is_assignment(w));
+        if words.next() != Some("rm") {
+            continue;
+        }
+        let (mut recursive, mut force) = (false, false);
+        let mut targets = Vec::new();
+        for word in words {
+            match word {
+                "--recursive" => recursive = true,
+                "--force" => force = true,
+                w if w.starts_with("--") => {}
+                w if w.starts_with('-') && w.len() > 1 => {
+                    recursive |= w.contains(['r', 'R']);
+                    force |= w.contains('f');
+                }
+                w => targets.push(w),
+            }
+        }
+        if !(recursive && force) {
+            continue;
+        }
+        if let Some(target) = targets.into_iter().find(|t| is_unguarded(t)) {
+            let rm = command.find("rm").unwrap_or(0);
+            found.push((start + rm, target.to_string()));
+        }
+    }
+    found
+}
+
+/// The simple commands of a script, split at `&&`, `||`, `;`, `|` and
+/// newlines, with their offsets.
+fn commands(script: &str) -> Vec<(usize, &str)> {
+    let mut commands = Vec::new();
+    let mut start = 0;
+    for (i, c) in script.char_indices() {
+        if matches!(c, ';' | '&' | '|' | '\n') {
+            commands.push((start, &script[start..i]));
+            start = i + 1;
+        }
+    }
+    commands.push((start, &script[start..]));
+    commands
+}
+
+/// `NAME=value`, as in `CI=1 rm -rf dist`.
+fn is_assignment(word: &str) -> bool {
+    word.split_once('=')
+        .is_some_and(|(name, _)| !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_'))
+}
+
+fn is_unguarded(target: &str) -> bool {
+    let target = target.trim_matches(|c| c == '"' || c == '\'');
+    if DANGEROUS_TARGETS.contains(&target) {
+        return true;
+    }
+    // Every expansion must be `${NAME:?...}`, which fails when NAME is empty
+    target.match_indices('$').any(|(i, _)| {
+        let rest = &target[i + 1..];
+        let Some(inner) = rest.strip_prefix('{') else {
+            return true;
+        };
+        let name_len = inner.find(|c: char| !(c.is_ascii_alphanumeric() || c == '_')).unwrap_or(inner.len());
+        !inner[name_len..].starts_with(":?")
+    })
+}
+
+/// A minimal JSON parser that remembers where each value starts, so
+/// violations can point into the manifest.
+mod json {
+    pub struct Node {
+        /// Byte offset of the value in the source.
+        pub start: usize,
+        pub value: Value,
+    }
+
+    pub enum Value {
+        Object(Vec<Member>),
+        String(String),
+        Other,
+    }
+
+    pub struct Member {
+        pub key: String,
+        /// Byte offset of the key's opening quote.
+        pub key_start: usize,
+        pub value: Node,
+    }
+
+    impl Node {
+        /// Source offset of byte `offset` of this string's decoded text.
+        /// Strings with escapes map to their opening quote.
+        pub fn source_offset(&self, source: &str, offset: usize) -> usize {
+            let raw = &source[self.start + 1..];
+            match &self.value {
+                Value::String(text) if raw.starts_with(text.as_str()) => self.start + 1 + offset,
+                _ => self.start,
+            }
+        }
+    }
+
+    /// Parse `source`, or `None` if it isn't valid JSON.
+    pub fn parse(source: &str) -> Option<Node> {
+        let mut parser = Parser { src: source.as_bytes(), source, pos: 0 };
+        let node = parser.value()?;
+        parser.skip_ws();
+        (parser.pos == source.len()).then_some(node)
+    }
+
+    struct Parser<'a> {
+        src: &'a [u8],
+        source: &'a str,
+        pos: usize,
+    }
+
+    impl Parser<'_> {
+        fn skip_ws(&mut self) {
+            while self.src.get(self.pos).is_some_and(|b| b.is_ascii_whitespace()) {
+                self.pos += 1;
+            }
+        }
+
+        fn eat(&mut self, byte: u8) -> bool {
+            self.skip_ws();
+            if self.src.get(self.pos) == Some(&byte) {
+                self.pos += 1;
+                true
+            } else {
+                false
+            }
+        }
+
+        fn value(&mut self) -> Option<Node> {
+            self.skip_ws();
+            let start = self.pos;
+            let value = match *self.src.get(self.pos)? {
+                b'{' => Value::Object(self.object()?),
+                b'[' => {
+                    self.array()?;
+                    Value::Other
+                }
+                b'"' => Value::String(self.string()?),
+                _ => {
+                    self.scalar()?;
+                    Value::Other
+                }
+            };
+            Some(Node { start, value })
+        }
+
+        fn object(&mut self) -> Option<Vec<Member>> {
+            self.pos += 1;
+            let mut members = Vec::new();
+            if self.eat(b'}') {
+                return Some(members);
+            }
+            loop {
+                self.skip_ws();
+                let key_start = self.pos;
+                if self.src.get(self.pos) != Some(&b'"') {
+                    return None;
+                }
+                let key = self.string()?;
+                if !self.eat(b':') {
+                    return None;
+                }
+                let value = self.value()?;
+                members.push(Member { key, key_start, value });
+                if self.eat(b'}') {
+                    return Some(members);
+                }
+                if !self.eat(b',') {
+                    return None;
+                }
+            }
+        }
+
+        fn array(&mut self) -> Option<()> {
+            self.pos += 1;
+            if self.eat(b']') {
+                return Some(());
+            }
+            loop {
+                self.value()?;
+                if self.eat(b']') {
+                    return Some(());
+                }
+                if !self.eat(b',') {
+                    return None;
+                }
+            }
+        }
+
+        fn string(&mut self) -> Option<String> {
+            self.pos += 1;
+            let mut text = String::new();
+            loop {
+                let rest = &self.source[self.pos..];
+                let end = rest.find(['"', '\\'])?;
+                text.push_str(&rest[..end]);
+                self.pos += end + 1;
+                if rest.as_bytes()[end] == b'"' {
+                    return Some(text);
+                }
+                let escape = *self.src.get(self.pos)?;
+                self.pos += 1;
+                match escape {
+                    b'"' => text.push('"'),
+                    b'\\' => text.push('\\'),
+                    b'/' => text.push('/'),
+                    b'b' => text.push('\u{8}'),
+                    b'f' => text.push('\u{c}'),
+                    b'n' => text.push('\n'),
+                    b'r' => text.push('\r'),
+                    b't' => text.push('\t'),
+                    b'u' => {
+                        let unit = self.hex4()?;
+                        let c = if (0xD800..0xDC00).contains(&unit) && self.source[self.pos..].starts_with("\\u") {
+                            self.pos += 2;
+                            let low = self.hex4()?;
+                            char::from_u32(0x10000 + ((unit - 0xD800) << 10) + (low.checked_sub(0xDC00)?))
+                        } else {
+                            char::from_u32(unit)
+                        };
+                        text.push(c.unwrap_or('\u{fffd}'));
+                    }
+                    _ => return None,
+                }
+            }
+        }
+
+        fn hex4(&mut self) -> Option<u32> {
+            let digits = self.source.get(self.pos..self.pos + 4)?;
+            self.pos += 4;
+            u32::from_str_radix(digits, 16).ok()
+        }
+
+        /// Numbers, `true`, `false` and `null`; validated loosely.
+        fn scalar(&mut self) -> Option<()> {
+            let start = self.pos;
+            while self
+                .src
+                .get(self.pos)
+                .is_some_and(|b| b.is_ascii_alphanumeric() || matches!(b, b'-' | b'+' | b'.'))
+            {
+                self.pos += 1;
+            }
+            let word = &self.source[start..self.pos];
+            let valid = matches!(word, "true" | "false" | "null") || word.parse::<f64>().is_ok();
+            valid.then_some(())
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn make_rule(checks: &[&str], scripts: &[&str]) -> PackageScriptsRule {
+        let config = RuleConfig {
+            id: "script-hygiene".into(),
+            severity: Severity::Error,
+            message: "Risky package script".into(),
+            checks: checks.iter().map(|c| c.to_string()).collect(),
+            scripts: scripts.iter().map(|s| s.to_string()).collect(),
+            ..Default::default()
+        };
+        PackageScriptsRule::new(&config).unwrap()
+    }
+
+    fn check(rule: &PackageScriptsRule, content: &str) -> Vec<Violation> {
+        rule.check_file(&ScanContext::new(Path::new("package.json"), content))
+    }
+
+    #[test]
+    fn flags_unguarded_rm_and_pipe_to_shell() {
+        let rule = make_rule(&[], &[]);
+        let content = r#"{
+  "name": "app",
+  "scripts": {
+    "clean": "rm -rf dist && rm -rf $OUT_DIR/",
+    "safe": "rm -rf \"${OUT_DIR:?}/build\" && rm -r /tmp/x",
+    "nuke": "sudo rm -fr /",
+    "setup": "curl -fsSL https://example.com/install | bash"
+  }
+}"#;
+        let violations = check(&rule, content);
+        let found: Vec<_> = violations.iter().map(|v| (v.line, v.column)).collect();
+        assert_eq!(
+            found,
+            vec![(Some(4), Some(30)), (Some(6), Some(19)), (Some(7), Some(15))]
+        );
+        assert!(violations[0].message.ends_with("script 'clean' runs `rm -rf` on an unguarded path '$OUT_DIR/'"));
+        assert!(violations[2].message.ends_with("script 'setup' pipes a download into a shell"));
+    }
+
+    #[test]
+    fn reports_missing_required_scripts() {
+        let rule = make_rule(&["pipe-to-shell"], &["lint", "typecheck"]);
+        let content = "{\n  \"scripts\": {\n    \"lint\": \"eslint .\",\n    \"clean\": \"rm -rf ~\"\n  }\n}";
+        let violations = check(&rule, content);
+        assert_eq!(violations.len(), 1);
+        assert_eq!((violations[0].line, violations[0].column), (Some(2), Some(3)));
+        assert!(violations[0].message.ends_with("missing required script 'typecheck'"));
+
+        // No scripts at all: reported at the top of the file
+        let violations = check(&rule, "{ \"name\": \"app\" }");
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].line, Some(1));
+    }
+
+    #[test]
+    fn custom_pattern_and_escaped_scripts() {
+        let config = RuleConfig {
+            id: "no-preinstall".into(),
+            message: "banned".into(),
+            pattern: Some(r"npm\s+install\s+-g".into()),
+            regex: true,
+            checks: vec!["unguarded-rm".into()],
+            ..Default::default()
+        };
+        let rule = PackageScriptsRule::new(&config).unwrap();
+        let content = "{\"scripts\": {\"a\": \"echo \\\"hi\\\" && npm install -g x\", \"b\": \"npm install -g y\"}}";
+        let violations = check(&rule, content);
+        assert_eq!(violations.len(), 2);
+        // Escapes before the match: the column falls back to the string's start
+        assert_eq!(violations[0].column, Some(19));
+        assert_eq!(violations[1].column, Some(60));
+    }
+
+    #[test]
+    fn skips_other_files_and_malformed_json() {
+        let rule = make_rule(&[], &["lint"]);
+        assert!(rule
+            .check_file(&ScanContext::new(Path::new("src/a.json"), "{}"))
+            .is_empty());
+        assert!(check(&rule, "{ \"scripts\": { \"a\": \"rm -rf /\" }").is_empty());
+        assert!(check(&rule, "{ \"scripts\": {} } trailing").is_empty());
+    }
+
+    #[test]
+    fn unknown_check_errors() {
+        let config = RuleConfig {
+            id: "t".into(),
+            checks: vec!["rm".into()],
+            ..Default::default()
+        };
+        let err = PackageScriptsRule::new(&config).unwrap_err();
+        assert!(matches!(err, RuleBuildError::InvalidValue(_, "checks", _)));
+    }
+}
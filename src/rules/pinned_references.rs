@@ -0,0 +1,251 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+
+/// Names accepted in `checks`.
+const CHECKS: &[&str] = &["docker-base-tag", "action-sha"];
+
+const DEFAULT_GLOB: &str = "**/{Dockerfile,Dockerfile.*,*.dockerfile,Containerfile,\
+                            .github/workflows/*.yml,.github/workflows/*.yaml}";
+
+/// Flags build inputs that can change without a commit to this repo.
+///
+/// - `docker-base-tag`: a `FROM` in a Dockerfile whose image has no tag or
+///   the `latest` tag. Digests (`@sha256:...`), `scratch`, earlier build
+///   stages and `$ARG`-built image names are allowed.
+/// - `action-sha`: a `uses:` step in a workflow that names an action by tag
+///   or branch instead of a full 40-character commit SHA. Local actions
+///   (`./...`) and `docker://` images are skipped.
+///
+/// Which check applies is decided by the file: Dockerfiles (`Dockerfile`,
+/// `Dockerfile.*`, `*.dockerfile`, `Containerfile`) get the first, YAML
+/// files the second. `checks` picks which of these run; by default both do.
+#[derive(Debug)]
+pub struct PinnedReferencesRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: Option<String>,
+    docker_base_tag: bool,
+    action_sha: bool,
+}
+
+impl PinnedReferencesRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        if let Some(unknown) = config.checks.iter().find(|c| !CHECKS.contains(&c.as_str())) {
+            return Err(RuleBuildError::InvalidValue(
+                config.id.clone(),
+                "checks",
+                format!("unknown check '{}' (expected one of: {})", unknown, CHECKS.join(", ")),
+            ));
+        }
+        let enabled = |check: &str| config.checks.is_empty() || config.checks.iter().any(|c| c == check);
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob: config.glob.clone().or_else(|| Some(DEFAULT_GLOB.to_string())),
+            docker_base_tag: enabled("docker-base-tag"),
+            action_sha: enabled("action-sha"),
+        })
+    }
+
+    fn violation(&self, ctx: &ScanContext, line: usize, column: usize, detail: String) -> Violation {
+        Violation {
+            rule_id: self.id.clone(),
+            severity: self.severity,
+            file: ctx.file_path.to_path_buf(),
+            line: Some(line),
+            column: Some(column),
+            message: format!("{}: {}", self.message, detail),
+            suggest: self.suggest.clone(),
+            source_line: ctx.source_line(line - 1),
+            fix: None,
+            origin: None,
+            enclosing: None,
+        }
+    }
+}
+
+impl Rule for PinnedReferencesRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn file_glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        let file_name = ctx.file_path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+        let found = if is_dockerfile(file_name) {
+            if !self.docker_base_tag {
+                return Vec::new();
+            }
+            unpinned_base_images(ctx.lines())
+        } else if file_name.ends_with(".yml") || file_name.ends_with(".yaml") {
+            if !self.action_sha {
+                return Vec::new();
+            }
+            unpinned_actions(ctx.lines())
+        } else {
+            return Vec::new();
+        };
+        found
+            .into_iter()
+            .map(|(line, column, detail)| self.violation(ctx, line, column, detail))
+            .collect()
+    }
+}
+
+fn is_dockerfile(file_name: &str) -> bool {
+    file_name == "Dockerfile"
+        || file_name == "Containerfile"
+        || file_name.starts_with("Dockerfile.")
+        || file_name.ends_with(".dockerfile")
+}
+
+/// `FROM` images without a version, as (line, column, detail).
+fn unpinned_base_images(lines: &[&str]) -> Vec<(usize, usize, String)> {
+    let mut stages: Vec<String> = Vec::new();
+    let mut found = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        let mut words = line.split_whitespace();
+        if !words.next().is_some_and(|w| w.eq_ignore_ascii_case("FROM")) {
+            continue;
+        }
+        let Some(image) = words.find(|w| !w.starts_with("--")) else {
+            continue;
+        };
+        let rest: Vec<&str> = words.collect();
+        if let [as_kw, stage, ..] = rest.as_slice() {
+            if as_kw.eq_ignore_ascii_case("AS") {
+                stages.push(stage.to_ascii_lowercase());
+            }
+        }
+
+        let lower = image.to_ascii_lowercase();
+        if lower == "scratch" || image.contains('$') || image.contains('@') || stages[..].contains(&lower) {
+            continue;
+        }
+        // A `:` before the last `/` is a registry port, not a tag
+        let name = image.rsplit('/').next().unwrap_or(image);
+        let detail = match name.split_once(':') {
+            None => format!("base image '{}' has no tag", image),
+            Some((_, "latest")) => format!("base image '{}' uses the latest tag", image),
+            Some(_) => continue,
+        };
+        let column = line.find(image).map_or(1, |c| c + 1);
+        found.push((idx + 1, column, detail));
+    }
+    found
+}
+
+/// `uses:` references not pinned to a commit SHA, as (line, column, detail).
+fn unpinned_actions(lines: &[&str]) -> Vec<(usize, usize, String)> {
+    let mut found = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        let trimmed = line.trim_start().trim_start_matches("- ");
+        let Some(value) = trimmed.strip_prefix("uses:") else {
+            continue;
+        };
+        let value = value.split(" #").next().unwrap_or(value).trim();
+        let action = value.trim_matches(|c| c == '"' || c == '\'');
+        if action.is_empty() || action.starts_with("./") || action.starts_with("docker://") {
+            continue;
+        }
+        let detail = match action.rsplit_once('@') {
+            Some((_, git_ref)) if git_ref.len() == 40 && git_ref.bytes().all(|b| b.is_ascii_hexdigit()) => continue,
+            Some(_) => format!("action '{}' is not pinned to a commit SHA", action),
+            None => format!("action '{}' has no version", action),
+        };
+        let column = line.find(action).map_or(1, |c| c + 1);
+        found.push((idx + 1, column, detail));
+    }
+    found
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn make_rule(checks: &[&str]) -> PinnedReferencesRule {
+        let config = RuleConfig {
+            id: "pinned".into(),
+            message: "Unpinned reference".into(),
+            checks: checks.iter().map(|c| c.to_string()).collect(),
+            ..Default::default()
+        };
+        PinnedReferencesRule::new(&config).unwrap()
+    }
+
+    fn check(rule: &PinnedReferencesRule, path: &str, content: &str) -> Vec<(usize, usize)> {
+        rule.check_file(&ScanContext::new(Path::new(path), content))
+            .iter()
+            .map(|v| (v.line.unwrap(), v.column.unwrap()))
+            .collect()
+    }
+
+    #[test]
+    fn flags_untagged_and_latest_base_images() {
+        let rule = make_rule(&[]);
+        let dockerfile = "\
+ARG NODE=node:20
+FROM --platform=linux/amd64 node AS deps
+FROM node:20-alpine AS build
+FROM deps
+from localhost:5000/team/base:latest
+FROM ${NODE}
+FROM python@sha256:0123abcd
+FROM scratch
+";
+        assert_eq!(check(&rule, "Dockerfile", dockerfile), vec![(2, 29), (5, 6)]);
+        assert_eq!(check(&rule, "api.dockerfile", "FROM ubuntu\n"), vec![(1, 6)]);
+        let violations = rule.check_file(&ScanContext::new(Path::new("Dockerfile.prod"), "FROM redis:latest\n"));
+        assert!(violations[0].message.ends_with("base image 'redis:latest' uses the latest tag"));
+    }
+
+    #[test]
+    fn flags_actions_not_pinned_to_a_sha() {
+        let rule = make_rule(&[]);
+        let workflow = "\
+jobs:
+  build:
+    steps:
+      - uses: actions/checkout@v4
+      - uses: actions/setup-node@1d0ff469b7ec7b3cb9d8673fde0c81c44821de2a # v4.2.0
+      - uses: ./.github/actions/setup
+      - uses: \"octo/deploy\"
+      - name: Lint
+        uses: docker://alpine:3.20
+    ";
+        assert_eq!(check(&rule, ".github/workflows/ci.yml", workflow), vec![(4, 15), (7, 16)]);
+        let violations = rule.check_file(&ScanContext::new(Path::new("ci.yml"), workflow));
+        assert!(violations[0].message.ends_with("action 'actions/checkout@v4' is not pinned to a commit SHA"));
+        assert!(violations[1].message.ends_with("action 'octo/deploy' has no version"));
+    }
+
+    #[test]
+    fn checks_select_what_runs() {
+        let rule = make_rule(&["action-sha"]);
+        assert!(check(&rule, "Dockerfile", "FROM node\n").is_empty());
+        assert_eq!(check(&rule, "ci.yaml", "- uses: a/b@main\n").len(), 1);
+        assert!(check(&rule, "notes.txt", "uses: a/b@main\n").is_empty());
+
+        let config = RuleConfig {
+            id: "t".into(),
+            checks: vec!["docker".into()],
+            ..Default::default()
+        };
+        assert!(matches!(
+            PinnedReferencesRule::new(&config),
+            Err(RuleBuildError::InvalidValue(_, "checks", _))
+        ));
+    }
+}
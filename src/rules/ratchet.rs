@@ -1,5 +1,5 @@
 use crate::config::{RuleConfig, Severity};
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
 use regex::Regex;
 
 /// A ratchet rule that counts literal pattern occurrences across all files.
@@ -12,7 +12,7 @@ pub struct RatchetRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
     pattern: String,
     max_count: usize,
@@ -44,7 +44,7 @@ impl RatchetRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob: config.glob.clone(),
             pattern,
             max_count,
@@ -77,7 +77,7 @@ impl Rule for RatchetRule {
     fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
         let mut violations = Vec::new();
 
-        for (line_idx, line) in ctx.content.lines().enumerate() {
+        for (line_idx, &line) in ctx.lines().iter().enumerate() {
             if let Some(ref re) = self.compiled_regex {
                 // Regex mode
                 for m in re.find_iter(line) {
@@ -91,6 +91,8 @@ impl Rule for RatchetRule {
                         suggest: self.suggest.clone(),
                         source_line: Some(line.to_string()),
                         fix: None,
+                        origin: None,
+                        enclosing: None,
                     });
                 }
             } else {
@@ -110,6 +112,8 @@ impl Rule for RatchetRule {
                         suggest: self.suggest.clone(),
                         source_line: Some(line.to_string()),
                         fix: None,
+                        origin: None,
+                        enclosing: None,
                     });
                     search_start = col + pattern_len;
                 }
@@ -130,7 +134,7 @@ mod tests {
             id: "test-ratchet".into(),
             severity: Severity::Error,
             message: "legacy pattern found".into(),
-            suggest: Some("use newApi() instead".into()),
+            suggest: vec!["use newApi() instead".into()],
             pattern: pattern.map(|s| s.to_string()),
             max_count,
             ..Default::default()
@@ -142,10 +146,7 @@ mod tests {
         let config = make_config(Some("legacyFetch("), Some(10));
         let rule = RatchetRule::new(&config).unwrap();
         let content = "let x = legacyFetch(url);\nlet y = newFetch(url);";
-        let ctx = ScanContext {
-            file_path: Path::new("test.ts"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.ts"), content);
         let violations = rule.check_file(&ctx);
         assert_eq!(violations.len(), 1);
         assert_eq!(violations[0].line, Some(1));
@@ -157,10 +158,7 @@ mod tests {
         let config = make_config(Some("TODO"), Some(5));
         let rule = RatchetRule::new(&config).unwrap();
         let content = "// TODO fix this TODO and that TODO";
-        let ctx = ScanContext {
-            file_path: Path::new("test.ts"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.ts"), content);
         let violations = rule.check_file(&ctx);
         assert_eq!(violations.len(), 3);
         assert_eq!(violations[0].column, Some(4));
@@ -173,10 +171,7 @@ mod tests {
         let config = make_config(Some("legacyFetch("), Some(0));
         let rule = RatchetRule::new(&config).unwrap();
         let content = "let x = apiFetch(url);";
-        let ctx = ScanContext {
-            file_path: Path::new("test.ts"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.ts"), content);
         let violations = rule.check_file(&ctx);
         assert!(violations.is_empty());
     }
@@ -186,10 +181,7 @@ mod tests {
         let config = make_config(Some("bad("), Some(10));
         let rule = RatchetRule::new(&config).unwrap();
         let content = "    bad(x)";
-        let ctx = ScanContext {
-            file_path: Path::new("test.ts"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.ts"), content);
         let violations = rule.check_file(&ctx);
         assert_eq!(violations.len(), 1);
         assert_eq!(violations[0].column, Some(5)); // 1-indexed
@@ -1,5 +1,5 @@
 use crate::config::{RuleConfig, Severity};
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
 use regex::Regex;
 
 /// Ensures that files matching a glob contain a required pattern.
@@ -15,7 +15,7 @@ pub struct RequiredPatternRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
     pattern: String,
     compiled_regex: Option<Regex>,
@@ -57,7 +57,7 @@ impl RequiredPatternRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob: config.glob.clone(),
             pattern,
             compiled_regex,
@@ -116,8 +116,10 @@ impl Rule for RequiredPatternRule {
             column: Some(1),
             message: self.message.clone(),
             suggest: self.suggest.clone(),
-            source_line: ctx.content.lines().next().map(|l| l.to_string()),
+            source_line: ctx.source_line(0),
             fix: None,
+            origin: None,
+            enclosing: None,
         }]
     }
 }
@@ -132,7 +134,7 @@ mod tests {
             id: "test-required-pattern".into(),
             severity: Severity::Error,
             message: "required pattern missing".into(),
-            suggest: Some("add the required pattern".into()),
+            suggest: vec!["add the required pattern".into()],
             pattern: Some(pattern.to_string()),
             glob: glob.map(|s| s.to_string()),
             ..Default::default()
@@ -140,10 +142,7 @@ mod tests {
     }
 
     fn check(rule: &RequiredPatternRule, content: &str) -> Vec<Violation> {
-        let ctx = ScanContext {
-            file_path: Path::new("src/pages/Home.tsx"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("src/pages/Home.tsx"), content);
         rule.check_file(&ctx)
     }
 
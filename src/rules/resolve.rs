@@ -0,0 +1,530 @@
+use crate::rules::banned_import::normalize;
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+/// Extensions tried when checking whether a `baseUrl` or `paths` target exists.
+const EXTENSIONS: &[&str] = &["ts", "tsx", "d.ts", "js", "jsx", "mjs", "cjs"];
+
+/// Nested `extends` followed before giving up.
+const MAX_EXTENDS_DEPTH: usize = 8;
+
+/// Maps import specifiers to the directories they name, the way a
+/// TypeScript monorepo's tooling would:
+///
+/// - `./` and `../` specifiers resolve against the importing file
+/// - `compilerOptions.paths` and `baseUrl` from the nearest `tsconfig.json`
+///   (or `jsconfig.json`), following relative `extends`
+/// - workspace package names, from the nearest `package.json` with
+///   `workspaces` or a `pnpm-workspace.yaml`, to the package's directory
+///   (this covers dependencies declared as `workspace:*`)
+///
+/// Resolution is lexical: a specifier maps to a path whether or not a file
+/// exists there, except that `baseUrl` only applies when one does. Configs
+/// are read once per directory and shared across files and threads.
+#[derive(Debug, Default)]
+pub struct ImportResolver {
+    cache: Mutex<Cache>,
+}
+
+#[derive(Debug, Default)]
+struct Cache {
+    /// The tsconfig options governing each directory seen so far.
+    aliases: HashMap<PathBuf, Option<Arc<PathAliases>>>,
+    /// The workspace each directory seen so far belongs to.
+    workspaces: HashMap<PathBuf, Option<Arc<Workspace>>>,
+}
+
+/// Where a specifier points, and how that was decided.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Resolution {
+    /// A `./` or `../` specifier.
+    Relative(PathBuf),
+    /// A tsconfig `paths` entry or a path under `baseUrl`.
+    Alias(PathBuf),
+    /// A workspace package, by name.
+    Workspace(PathBuf),
+}
+
+impl Resolution {
+    pub fn path(&self) -> &Path {
+        match self {
+            Resolution::Relative(path) | Resolution::Alias(path) | Resolution::Workspace(path) => path,
+        }
+    }
+}
+
+impl ImportResolver {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Resolve `specifier` as imported from a file in `dir`. Bare package
+    /// names that aren't aliases or workspace packages yield `None`.
+    pub fn resolve(&self, dir: &Path, specifier: &str) -> Option<Resolution> {
+        if specifier.starts_with("./") || specifier.starts_with("../") || specifier == "." || specifier == ".." {
+            return Some(Resolution::Relative(normalize(&dir.join(specifier))));
+        }
+        let (aliases, workspace) = {
+            let mut cache = self.cache.lock().unwrap_or_else(|e| e.into_inner());
+            (aliases_for(&mut cache, dir), workspace_for(&mut cache, dir))
+        };
+        if let Some(path) = aliases.as_ref().and_then(|a| a.resolve(specifier)) {
+            return Some(Resolution::Alias(path));
+        }
+        if let Some(path) = workspace.as_ref().and_then(|w| w.resolve(specifier)) {
+            return Some(Resolution::Workspace(path));
+        }
+        aliases
+            .as_ref()
+            .and_then(|a| a.resolve_base_url(specifier))
+            .map(Resolution::Alias)
+    }
+}
+
+/// The aliases for files in `dir`: those of the nearest tsconfig at or above it.
+fn aliases_for(cache: &mut Cache, dir: &Path) -> Option<Arc<PathAliases>> {
+    if let Some(found) = cache.aliases.get(dir) {
+        return found.clone();
+    }
+    let config = ["tsconfig.json", "jsconfig.json"]
+        .iter()
+        .map(|name| dir.join(name))
+        .find(|path| path.is_file());
+    let found = match config {
+        Some(path) => Some(Arc::new(PathAliases::from_options(read_options(&path, 0)))),
+        None => dir.parent().and_then(|parent| aliases_for(cache, parent)),
+    };
+    cache.aliases.insert(dir.to_path_buf(), found.clone());
+    found
+}
+
+/// The workspace for files in `dir`: the nearest one whose root is at or
+/// above it.
+fn workspace_for(cache: &mut Cache, dir: &Path) -> Option<Arc<Workspace>> {
+    if let Some(found) = cache.workspaces.get(dir) {
+        return found.clone();
+    }
+    let found = match workspace_patterns(dir) {
+        Some(patterns) => Some(Arc::new(Workspace::discover(dir, &patterns))),
+        None => dir.parent().and_then(|parent| workspace_for(cache, parent)),
+    };
+    cache.workspaces.insert(dir.to_path_buf(), found.clone());
+    found
+}
+
+/// `compilerOptions` after following `extends`.
+#[derive(Debug, Default)]
+struct CompilerOptions {
+    base_url: Option<PathBuf>,
+    /// The `paths` map and the directory of the config that set it.
+    paths: Option<(PathBuf, serde_json::Map<String, serde_json::Value>)>,
+}
+
+fn read_options(path: &Path, depth: usize) -> CompilerOptions {
+    let mut options = CompilerOptions::default();
+    let Some(json) = read_jsonc(path) else {
+        return options;
+    };
+    let dir = path.parent().unwrap_or(Path::new(""));
+
+    let extends: Vec<&str> = match json.get("extends") {
+        Some(serde_json::Value::String(s)) => vec![s.as_str()],
+        Some(serde_json::Value::Array(items)) => items.iter().filter_map(|i| i.as_str()).collect(),
+        _ => Vec::new(),
+    };
+    // Only relative configs can be found without node_modules
+    for parent in extends.into_iter().filter(|e| e.starts_with('.')) {
+        if depth >= MAX_EXTENDS_DEPTH {
+            break;
+        }
+        let mut parent_path = normalize(&dir.join(parent));
+        if parent_path.extension().is_none_or(|e| e != "json") {
+            parent_path.as_mut_os_string().push(".json");
+        }
+        let inherited = read_options(&parent_path, depth + 1);
+        options.base_url = inherited.base_url.or(options.base_url);
+        options.paths = inherited.paths.or(options.paths);
+    }
+
+    if let Some(compiler) = json.get("compilerOptions") {
+        if let Some(base_url) = compiler.get("baseUrl").and_then(|b| b.as_str()) {
+            options.base_url = Some(normalize(&dir.join(base_url)));
+        }
+        if let Some(paths) = compiler.get("paths").and_then(|p| p.as_object()) {
+            options.paths = Some((dir.to_path_buf(), paths.clone()));
+        }
+    }
+    options
+}
+
+/// A tsconfig's `paths`, with targets made relative to the scan.
+#[derive(Debug, Default)]
+struct PathAliases {
+    base_url: Option<PathBuf>,
+    /// Patterns (with at most one `*`) and their targets, most specific first.
+    entries: Vec<(String, Vec<String>)>,
+}
+
+impl PathAliases {
+    fn from_options(options: CompilerOptions) -> Self {
+        let mut entries = Vec::new();
+        if let Some((dir, paths)) = &options.paths {
+            // Targets are relative to baseUrl when set, else to the config
+            let base = options.base_url.as_deref().unwrap_or(dir);
+            for (pattern, targets) in paths {
+                let targets = targets
+                    .as_array()
+                    .into_iter()
+                    .flatten()
+                    .filter_map(|t| t.as_str())
+                    .map(|t| base.join(t).to_string_lossy().into_owned())
+                    .collect();
+                entries.push((pattern.clone(), targets));
+            }
+        }
+        // Exact patterns win, then the longest prefix before the `*`
+        entries.sort_by_key(|(pattern, _)| match pattern.find('*') {
+            None => (0, 0),
+            Some(star) => (1, usize::MAX - star),
+        });
+        Self {
+            base_url: options.base_url,
+            entries,
+        }
+    }
+
+    /// The first existing target of the most specific matching pattern, or
+    /// its first target when none exists.
+    fn resolve(&self, specifier: &str) -> Option<PathBuf> {
+        for (pattern, targets) in &self.entries {
+            let matched = match pattern.split_once('*') {
+                None => (pattern == specifier).then_some(""),
+                Some((prefix, suffix)) => specifier
+                    .strip_prefix(prefix)
+                    .and_then(|rest| rest.strip_suffix(suffix)),
+            };
+            let Some(matched) = matched else {
+                continue;
+            };
+            let candidates: Vec<PathBuf> = targets
+                .iter()
+                .map(|t| normalize(Path::new(&t.replace('*', matched))))
+                .collect();
+            let found = candidates.iter().find(|c| exists(c)).or(candidates.first());
+            return found.cloned();
+        }
+        None
+    }
+
+    /// `specifier` under `baseUrl`, when something is there.
+    fn resolve_base_url(&self, specifier: &str) -> Option<PathBuf> {
+        let path = normalize(&self.base_url.as_ref()?.join(specifier));
+        exists(&path).then_some(path)
+    }
+}
+
+/// Whether `path` is a directory or a module, with or without an extension.
+fn exists(path: &Path) -> bool {
+    path.exists()
+        || EXTENSIONS.iter().any(|ext| {
+            let mut file = path.as_os_str().to_owned();
+            file.push(".");
+            file.push(ext);
+            Path::new(&file).is_file()
+        })
+}
+
+/// Workspace packages by name.
+#[derive(Debug, Default)]
+struct Workspace {
+    packages: HashMap<String, PathBuf>,
+}
+
+impl Workspace {
+    fn discover(root: &Path, patterns: &[String]) -> Self {
+        let mut packages = HashMap::new();
+        for pattern in patterns.iter().filter(|p| !p.starts_with('!')) {
+            for dir in expand_dirs(root, pattern.trim_end_matches('/')) {
+                let name = read_jsonc(&dir.join("package.json"))
+                    .and_then(|json| json.get("name")?.as_str().map(str::to_string));
+                if let Some(name) = name {
+                    packages.insert(name, dir);
+                }
+            }
+        }
+        Self { packages }
+    }
+
+    /// The package directory plus the subpath, for `name` or `name/...`.
+    fn resolve(&self, specifier: &str) -> Option<PathBuf> {
+        self.packages
+            .iter()
+            .filter_map(|(name, dir)| {
+                let rest = specifier.strip_prefix(name.as_str())?;
+                match rest.strip_prefix('/') {
+                    Some(sub) => Some((name.len(), dir.join(sub))),
+                    None if rest.is_empty() => Some((name.len(), dir.clone())),
+                    None => None,
+                }
+            })
+            .max_by_key(|(len, _)| *len)
+            .map(|(_, path)| path)
+    }
+}
+
+/// Workspace globs declared in `dir`, by `package.json` `workspaces` (an
+/// array, or `{ packages: [...] }`) or `pnpm-workspace.yaml`.
+fn workspace_patterns(dir: &Path) -> Option<Vec<String>> {
+    let strings = |items: &Vec<serde_json::Value>| -> Vec<String> {
+        items.iter().filter_map(|i| i.as_str()).map(str::to_string).collect()
+    };
+    let manifest = dir.join("package.json");
+    if manifest.is_file() {
+        if let Some(workspaces) = read_jsonc(&manifest).and_then(|json| json.get("workspaces").cloned()) {
+            match &workspaces {
+                serde_json::Value::Array(items) => return Some(strings(items)),
+                serde_json::Value::Object(map) => {
+                    if let Some(items) = map.get("packages").and_then(|p| p.as_array()) {
+                        return Some(strings(items));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+    let pnpm = std::fs::read_to_string(dir.join("pnpm-workspace.yaml")).ok()?;
+    Some(pnpm_packages(&pnpm))
+}
+
+/// The `packages:` list of a `pnpm-workspace.yaml`.
+fn pnpm_packages(yaml: &str) -> Vec<String> {
+    let mut patterns = Vec::new();
+    let mut in_packages = false;
+    for line in yaml.lines() {
+        let line = line.split(" #").next().unwrap_or(line);
+        if !line.starts_with([' ', '\t', '-']) {
+            in_packages = line.trim_end() == "packages:";
+            continue;
+        }
+        if let Some(item) = line.trim().strip_prefix('-').filter(|_| in_packages) {
+            let item = item.trim().trim_matches(|c| c == '"' || c == '\'');
+            if !item.is_empty() {
+                patterns.push(item.to_string());
+            }
+        }
+    }
+    patterns
+}
+
+/// Directories under `root` matching a workspace glob. `*` matches one
+/// directory name; `**` matches any number of them.
+fn expand_dirs(root: &Path, pattern: &str) -> Vec<PathBuf> {
+    let mut current = vec![root.to_path_buf()];
+    for segment in pattern.split('/').filter(|s| !s.is_empty() && *s != ".") {
+        let mut next = Vec::new();
+        for dir in &current {
+            if segment == "**" {
+                descendants(dir, &mut next);
+            } else if segment.contains('*') {
+                let Ok(glob) = globset::Glob::new(segment) else {
+                    continue;
+                };
+                let matcher = glob.compile_matcher();
+                next.extend(subdirs(dir).into_iter().filter(|d| {
+                    d.file_name().is_some_and(|n| matcher.is_match(n))
+                }));
+            } else {
+                let path = dir.join(segment);
+                if path.is_dir() {
+                    next.push(path);
+                }
+            }
+        }
+        current = next;
+    }
+    current
+}
+
+fn subdirs(dir: &Path) -> Vec<PathBuf> {
+    let Ok(entries) = std::fs::read_dir(if dir.as_os_str().is_empty() { Path::new(".") } else { dir }) else {
+        return Vec::new();
+    };
+    let mut dirs: Vec<PathBuf> = entries
+        .filter_map(|e| e.ok())
+        .filter(|e| e.file_type().is_ok_and(|t| t.is_dir()))
+        .filter(|e| {
+            let name = e.file_name();
+            let name = name.to_string_lossy();
+            !name.starts_with('.') && name != "node_modules"
+        })
+        .map(|e| dir.join(e.file_name()))
+        .collect();
+    dirs.sort();
+    dirs
+}
+
+/// `dir` and every directory below it, skipping `node_modules` and hidden ones.
+fn descendants(dir: &Path, out: &mut Vec<PathBuf>) {
+    out.push(dir.to_path_buf());
+    for sub in subdirs(dir) {
+        descendants(&sub, out);
+    }
+}
+
+/// Parse a JSON file that may contain comments and trailing commas, as
+/// `tsconfig.json` files do.
+fn read_jsonc(path: &Path) -> Option<serde_json::Value> {
+    let text = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&strip_jsonc(&text)).ok()
+}
+
+fn strip_jsonc(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    let mut in_string = false;
+    while let Some(c) = chars.next() {
+        if in_string {
+            out.push(c);
+            match c {
+                '\\' => out.extend(chars.next()),
+                '"' => in_string = false,
+                _ => {}
+            }
+            continue;
+        }
+        match c {
+            '"' => {
+                in_string = true;
+                out.push(c);
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.next_if(|&n| n != '\n').is_some() {}
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut prev = ' ';
+                for n in chars.by_ref() {
+                    if prev == '*' && n == '/' {
+                        break;
+                    }
+                    prev = n;
+                }
+                out.push(' ');
+            }
+            '}' | ']' => {
+                let kept = out.trim_end().len();
+                if out[..kept].ends_with(',') {
+                    out.truncate(kept - 1);
+                }
+                out.push(c);
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn write(root: &Path, path: &str, content: &str) {
+        let path = root.join(path);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    #[test]
+    fn resolves_tsconfig_paths_and_base_url() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(
+            root,
+            "tsconfig.base.json",
+            r#"{
+              // shared by every app
+              "compilerOptions": {
+                "baseUrl": ".",
+                "paths": { "@shared/*": ["libs/shared/*"], "@shared/config": ["libs/config/index.ts"], },
+              },
+            }"#,
+        );
+        write(
+            root,
+            "apps/web/tsconfig.json",
+            r#"{ "extends": "../../tsconfig.base", "compilerOptions": { "baseUrl": ".", "paths": { "@/*": ["./src/*"] } } }"#,
+        );
+        write(root, "apps/web/src/lib/db.ts", "");
+        write(root, "apps/api/tsconfig.json", r#"{ "extends": ["../../tsconfig.base.json"] }"#);
+        write(root, "libs/shared/date.ts", "");
+
+        let resolver = ImportResolver::new();
+        let web = root.join("apps/web/src/pages");
+        assert_eq!(
+            resolver.resolve(&web, "@/lib/db"),
+            Some(Resolution::Alias(root.join("apps/web/src/lib/db")))
+        );
+        // apps/web's own `paths` replaces the base config's
+        assert_eq!(resolver.resolve(&web, "@shared/date"), None);
+        assert_eq!(
+            resolver.resolve(&web, "../components/Nav"),
+            Some(Resolution::Relative(root.join("apps/web/src/components/Nav")))
+        );
+
+        let api = root.join("apps/api/src");
+        assert_eq!(
+            resolver.resolve(&api, "@shared/config"),
+            Some(Resolution::Alias(root.join("libs/config/index.ts")))
+        );
+        assert_eq!(
+            resolver.resolve(&api, "@shared/date"),
+            Some(Resolution::Alias(root.join("libs/shared/date")))
+        );
+        // baseUrl applies only to paths that exist
+        assert_eq!(
+            resolver.resolve(&api, "libs/shared/date"),
+            Some(Resolution::Alias(root.join("libs/shared/date")))
+        );
+        assert_eq!(resolver.resolve(&api, "react"), None);
+    }
+
+    #[test]
+    fn resolves_workspace_packages() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        write(root, "package.json", r#"{ "name": "repo", "workspaces": ["packages/*", "apps/web"] }"#);
+        write(root, "packages/ui/package.json", r#"{ "name": "@acme/ui" }"#);
+        write(root, "packages/ui-icons/package.json", r#"{ "name": "@acme/ui-icons" }"#);
+        write(root, "apps/web/package.json", r#"{ "name": "web", "dependencies": { "@acme/ui": "workspace:*" } }"#);
+
+        let resolver = ImportResolver::new();
+        let web = root.join("apps/web/src");
+        assert_eq!(
+            resolver.resolve(&web, "@acme/ui"),
+            Some(Resolution::Workspace(root.join("packages/ui")))
+        );
+        assert_eq!(
+            resolver.resolve(&web, "@acme/ui-icons/arrow"),
+            Some(Resolution::Workspace(root.join("packages/ui-icons/arrow")))
+        );
+        assert_eq!(resolver.resolve(&web, "@acme/uix"), None);
+
+        let pnpm = tempfile::tempdir().unwrap();
+        write(pnpm.path(), "pnpm-workspace.yaml", "packages:\n  - 'libs/**'\n  - \"!**/test\"\n");
+        write(pnpm.path(), "libs/core/auth/package.json", r#"{ "name": "auth" }"#);
+        assert_eq!(
+            resolver.resolve(pnpm.path(), "auth/session"),
+            Some(Resolution::Workspace(pnpm.path().join("libs/core/auth/session")))
+        );
+    }
+
+    #[test]
+    fn strips_comments_and_trailing_commas() {
+        let text = "{\n  // a \"comment\"\n  \"a\": \"//not a comment\", /* b */\n  \"c\": [1, 2,],\n}";
+        let json: serde_json::Value = serde_json::from_str(&strip_jsonc(text)).unwrap();
+        assert_eq!(json["a"], "//not a comment");
+        assert_eq!(json["c"], serde_json::json!([1, 2]));
+    }
+}
@@ -0,0 +1,235 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::banned_import::{is_type_only, package_pattern, IMPORT_PREFIX};
+use crate::rules::{Directive, Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+use regex::Regex;
+
+/// Enforces Next.js `server-only` / `client-only` import poisoning.
+///
+/// A file that imports one of the configured `packages` (database clients,
+/// secret-bearing SDKs) must also `import "server-only"`, so that pulling it
+/// into a client bundle fails the build. Files with a `"use server"`
+/// directive are already server-only and are skipped, as are type-only
+/// imports.
+///
+/// The marker imports are checked for contradictions too: a `client-only`
+/// module that also imports `server-only`, a listed package, or is marked
+/// `"use server"`, and a `"use client"` file that imports `server-only`.
+#[derive(Debug)]
+pub struct ServerOnlyImportRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: Option<String>,
+    package_re: Regex,
+    specifier_re: Regex,
+}
+
+/// An import of interest, with where it appears.
+struct Import<'a> {
+    specifier: &'a str,
+    line: usize,
+    column: usize,
+    source_line: &'a str,
+}
+
+impl ServerOnlyImportRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        if config.packages.is_empty() {
+            return Err(RuleBuildError::MissingField(config.id.clone(), "packages"));
+        }
+        let pkg_group = config
+            .packages
+            .iter()
+            .map(|p| package_pattern(p))
+            .collect::<Vec<_>>()
+            .join("|");
+        let package_re = Regex::new(&format!("^(?:{})(?:/|$)", pkg_group))
+            .map_err(|e| RuleBuildError::InvalidRegex(config.id.clone(), e))?;
+        let specifier_re = Regex::new(&format!(r#"{}['"]([^'"]+)['"]"#, IMPORT_PREFIX))
+            .map_err(|e| RuleBuildError::InvalidRegex(config.id.clone(), e))?;
+
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob: config
+                .glob
+                .clone()
+                .or(Some("**/*.{ts,tsx,js,jsx,mjs,cjs}".to_string())),
+            package_re,
+            specifier_re,
+        })
+    }
+
+    fn violation(&self, ctx: &ScanContext, import: &Import, detail: String) -> Violation {
+        let message = if self.message.is_empty() {
+            detail
+        } else {
+            format!("{}: {}", self.message, detail)
+        };
+        Violation {
+            rule_id: self.id.clone(),
+            severity: self.severity,
+            file: ctx.file_path.to_path_buf(),
+            line: Some(import.line),
+            column: Some(import.column),
+            message,
+            suggest: self.suggest.clone(),
+            source_line: Some(import.source_line.to_string()),
+            fix: None,
+            origin: None,
+            enclosing: None,
+        }
+    }
+}
+
+impl Rule for ServerOnlyImportRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+
+    fn file_glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        let mut imports = Vec::new();
+        for (idx, &line) in ctx.lines().iter().enumerate() {
+            for cap in self.specifier_re.captures_iter(line) {
+                let full = cap.get(0).unwrap();
+                if is_type_only(full.as_str()) {
+                    continue;
+                }
+                imports.push(Import {
+                    specifier: cap.get(1).unwrap().as_str(),
+                    line: idx + 1,
+                    column: full.start() + 1,
+                    source_line: line,
+                });
+            }
+        }
+
+        let server_only = imports.iter().find(|i| i.specifier == "server-only");
+        let client_only = imports.iter().find(|i| i.specifier == "client-only");
+        let packages: Vec<&Import> = imports
+            .iter()
+            .filter(|i| self.package_re.is_match(i.specifier))
+            .collect();
+        let directive = ctx.directive();
+
+        let mut violations = Vec::new();
+        if server_only.is_none() && directive != Some(Directive::UseServer) {
+            for import in &packages {
+                let detail = format!("'{}' is imported without 'server-only'", import.specifier);
+                violations.push(self.violation(ctx, import, detail));
+            }
+        }
+
+        if let Some(client) = client_only {
+            let conflict = server_only
+                .map(|i| format!("'{}'", i.specifier))
+                .or_else(|| packages.first().map(|i| format!("'{}'", i.specifier)))
+                .or_else(|| (directive == Some(Directive::UseServer)).then(|| "\"use server\"".to_string()));
+            if let Some(conflict) = conflict {
+                let detail = format!("'client-only' module also uses {}", conflict);
+                violations.push(self.violation(ctx, client, detail));
+            }
+        }
+
+        if let Some(server) = server_only.filter(|_| directive == Some(Directive::UseClient)) {
+            let detail = "'server-only' is imported in a \"use client\" file".to_string();
+            violations.push(self.violation(ctx, server, detail));
+        }
+
+        violations.sort_by_key(|v| (v.line, v.column));
+        violations
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::Path;
+
+    fn make_rule() -> ServerOnlyImportRule {
+        ServerOnlyImportRule::new(&RuleConfig {
+            id: "require-server-only".into(),
+            severity: Severity::Error,
+            packages: vec!["@prisma/client".into(), "drizzle-orm".into(), "@aws-sdk/*".into()],
+            ..Default::default()
+        })
+        .unwrap()
+    }
+
+    fn messages(content: &str) -> Vec<String> {
+        let ctx = ScanContext::new(Path::new("lib/db.ts"), content);
+        make_rule()
+            .check_file(&ctx)
+            .into_iter()
+            .map(|v| format!("{}: {}", v.line.unwrap(), v.message))
+            .collect()
+    }
+
+    #[test]
+    fn package_without_server_only_is_flagged() {
+        let content = r#"import { PrismaClient } from "@prisma/client";
+import { eq } from "drizzle-orm/sql";
+import { S3Client } from "@aws-sdk/client-s3";
+import { clsx } from "clsx";"#;
+        assert_eq!(
+            messages(content),
+            [
+                "1: '@prisma/client' is imported without 'server-only'",
+                "2: 'drizzle-orm/sql' is imported without 'server-only'",
+                "3: '@aws-sdk/client-s3' is imported without 'server-only'",
+            ]
+        );
+    }
+
+    #[test]
+    fn server_only_and_use_server_pass() {
+        assert!(messages("import 'server-only';\nimport { PrismaClient } from '@prisma/client';").is_empty());
+        assert!(messages("\"use server\";\n\nimport { db } from \"drizzle-orm\";").is_empty());
+        assert!(messages("import type { User } from '@prisma/client';").is_empty());
+        assert!(messages("import { PrismaClient } from '@prisma/client-extensions';").is_empty());
+    }
+
+    #[test]
+    fn client_only_conflicts_are_flagged() {
+        assert_eq!(
+            messages("import 'client-only';\nimport { PrismaClient } from '@prisma/client';"),
+            [
+                "1: 'client-only' module also uses '@prisma/client'",
+                "2: '@prisma/client' is imported without 'server-only'",
+            ]
+        );
+        assert_eq!(
+            messages("'use server'\nimport 'client-only';"),
+            ["2: 'client-only' module also uses \"use server\""]
+        );
+        assert!(messages("'use client'\nimport 'client-only';").is_empty());
+    }
+
+    #[test]
+    fn server_only_in_client_file_is_flagged() {
+        assert_eq!(
+            messages("// Shared helpers\n'use client';\nimport 'server-only';"),
+            ["3: 'server-only' is imported in a \"use client\" file"]
+        );
+    }
+
+    #[test]
+    fn packages_are_required() {
+        let result = ServerOnlyImportRule::new(&RuleConfig {
+            id: "require-server-only".into(),
+            ..Default::default()
+        });
+        assert!(result.is_err());
+    }
+}
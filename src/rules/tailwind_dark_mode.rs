@@ -1,6 +1,6 @@
 use crate::config::{RuleConfig, Severity};
 use crate::rules::ast::{collect_class_attributes, parse_file};
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
 use regex::Regex;
 use std::collections::HashSet;
 
@@ -17,7 +17,7 @@ pub struct TailwindDarkModeRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
     /// Classes that are exempt (don't need a dark: variant).
     allowed: HashSet<String>,
@@ -128,7 +128,7 @@ impl TailwindDarkModeRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob: config.glob.clone().or(Some(default_glob)),
             allowed,
             class_attr_re,
@@ -193,6 +193,18 @@ impl TailwindDarkModeRule {
 
         violations
     }
+
+    /// Remediations for a class missing its dark variant: the matching
+    /// semantic token when there is one, then adding a `dark:` class.
+    /// Configured `suggest` text replaces the generic advice.
+    fn suggestions(&self, class: &str, token_suggestion: Option<String>) -> Vec<Suggestion> {
+        let add_dark = Suggestion::new(format!("Add 'dark:{}'", suggest_dark_counterpart(class)));
+        match token_suggestion {
+            Some(token) => vec![Suggestion::new(token), add_dark],
+            None if !self.suggest.is_empty() => self.suggest.clone(),
+            None => vec![add_dark, Suggestion::new("Replace with a semantic token class")],
+        }
+    }
 }
 
 /// Suggest a semantic token replacement for a raw color class.
@@ -286,16 +298,9 @@ impl TailwindDarkModeRule {
                     format!("{}: '{}'", self.message, class)
                 };
 
-                let suggest = token_suggestion
-                    .or_else(|| self.suggest.clone())
-                    .or_else(|| {
-                        Some(format!(
-                            "Add 'dark:{}' or replace with a semantic token class",
-                            suggest_dark_counterpart(&class)
-                        ))
-                    });
+                let suggest = self.suggestions(&class, token_suggestion);
 
-                let source_line = ctx.content.lines().nth(line - 1).map(|l| l.to_string());
+                let source_line = ctx.source_line(line - 1);
 
                 violations.push(Violation {
                     rule_id: self.id.clone(),
@@ -307,6 +312,8 @@ impl TailwindDarkModeRule {
                     suggest,
                     source_line,
                     fix: None,
+                    origin: None,
+                    enclosing: None,
                 });
             }
         }
@@ -317,7 +324,7 @@ impl TailwindDarkModeRule {
     fn check_with_regex(&self, ctx: &ScanContext) -> Vec<Violation> {
         let mut violations = Vec::new();
 
-        for (line_num, line) in ctx.content.lines().enumerate() {
+        for (line_num, &line) in ctx.lines().iter().enumerate() {
             let class_strings = self.extract_class_strings(line);
             let extra_strings = self.extract_cn_strings(line);
 
@@ -335,14 +342,7 @@ impl TailwindDarkModeRule {
                         format!("{}: '{}'", self.message, class)
                     };
 
-                    let suggest = token_suggestion
-                        .or_else(|| self.suggest.clone())
-                        .or_else(|| {
-                            Some(format!(
-                                "Add 'dark:{}' or replace with a semantic token class",
-                                suggest_dark_counterpart(&class)
-                            ))
-                        });
+                    let suggest = self.suggestions(&class, token_suggestion);
 
                     violations.push(Violation {
                         rule_id: self.id.clone(),
@@ -354,6 +354,8 @@ impl TailwindDarkModeRule {
                         suggest,
                         source_line: Some(line.to_string()),
                         fix: None,
+                        origin: None,
+                        enclosing: None,
                     });
                 }
             }
@@ -433,10 +435,7 @@ mod tests {
     }
 
     fn check(rule: &TailwindDarkModeRule, content: &str) -> Vec<Violation> {
-        let ctx = ScanContext {
-            file_path: Path::new("test.tsx"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.tsx"), content);
         rule.check_file(&ctx)
     }
 
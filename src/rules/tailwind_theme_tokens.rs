@@ -1,6 +1,6 @@
 use crate::config::{RuleConfig, Severity};
 use crate::rules::ast::{collect_class_attributes, parse_file};
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
 use regex::Regex;
 use std::collections::HashMap;
 
@@ -229,7 +229,7 @@ impl TailwindThemeTokensRule {
                             };
 
                             let source_line =
-                                ctx.content.lines().nth(line - 1).map(|l| l.to_string());
+                                ctx.source_line(line - 1);
 
                             violations.push(Violation {
                                 rule_id: self.id.clone(),
@@ -238,15 +238,17 @@ impl TailwindThemeTokensRule {
                                 line: Some(line),
                                 column: Some(col),
                                 message: msg,
-                                suggest: Some(format!(
+                                suggest: vec![Suggestion::new(format!(
                                     "Replace '{}' with '{}'",
                                     base_class, replacement
-                                )),
+                                ))],
                                 source_line,
                                 fix: Some(crate::rules::Fix {
                                     old: base_class.to_string(),
                                     new: replacement.clone(),
                                 }),
+                                origin: None,
+                                enclosing: None,
                             });
                         }
                     }
@@ -260,7 +262,7 @@ impl TailwindThemeTokensRule {
     fn check_with_regex(&self, ctx: &ScanContext) -> Vec<Violation> {
         let mut violations = Vec::new();
 
-        for (line_num, line) in ctx.content.lines().enumerate() {
+        for (line_num, &line) in ctx.lines().iter().enumerate() {
             if !self.line_has_class_context(line) {
                 continue;
             }
@@ -293,12 +295,14 @@ impl TailwindThemeTokensRule {
                         line: Some(line_num + 1),
                         column: Some(cap.get(0).unwrap().start() + 1),
                         message: msg,
-                        suggest: Some(format!("Replace '{}' with '{}'", full_match, replacement)),
+                        suggest: vec![Suggestion::new(format!("Replace '{}' with '{}'", full_match, replacement))],
                         source_line: Some(line.to_string()),
                         fix: Some(crate::rules::Fix {
                             old: full_match.to_string(),
                             new: replacement.clone(),
                         }),
+                        origin: None,
+                        enclosing: None,
                     });
                 }
             }
@@ -326,10 +330,7 @@ mod tests {
     }
 
     fn check(rule: &TailwindThemeTokensRule, content: &str) -> Vec<Violation> {
-        let ctx = ScanContext {
-            file_path: Path::new("test.tsx"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.tsx"), content);
         rule.check_file(&ctx)
     }
 
@@ -359,7 +360,7 @@ mod tests {
         let v = violations.iter().find(|v| v.message.contains("text-gray-500"));
         assert!(v.is_some(), "text-gray-500 should be flagged");
         assert!(
-            v.unwrap().suggest.as_ref().unwrap().contains("text-muted-foreground"),
+            v.unwrap().suggest_text().unwrap().contains("text-muted-foreground"),
             "should suggest text-muted-foreground"
         );
     }
@@ -371,7 +372,7 @@ mod tests {
         let violations = check(&rule, line);
         let v = violations.iter().find(|v| v.message.contains("border-gray-200"));
         assert!(v.is_some());
-        assert!(v.unwrap().suggest.as_ref().unwrap().contains("border-border"));
+        assert!(v.unwrap().suggest_text().unwrap().contains("border-border"));
     }
 
     #[test]
@@ -381,7 +382,7 @@ mod tests {
         let violations = check(&rule, line);
         let v = violations.iter().find(|v| v.message.contains("bg-red-500"));
         assert!(v.is_some());
-        assert!(v.unwrap().suggest.as_ref().unwrap().contains("bg-destructive"));
+        assert!(v.unwrap().suggest_text().unwrap().contains("bg-destructive"));
     }
 
     #[test]
@@ -484,7 +485,7 @@ mod tests {
         let violations = check(&rule, line);
         let v = violations.iter().find(|v| v.message.contains("bg-blue-500"));
         assert!(v.is_some());
-        assert!(v.unwrap().suggest.as_ref().unwrap().contains("bg-brand"));
+        assert!(v.unwrap().suggest_text().unwrap().contains("bg-brand"));
     }
 
     // ── allowed_classes removes from ban map ──
@@ -0,0 +1,204 @@
+use crate::config::{RuleConfig, Severity};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+use std::ffi::OsStr;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+const DEFAULT_GLOB: &str = "src/**/{components,hooks,lib}/**/*.{ts,tsx}";
+
+/// Mirror directories checked when `test_dirs` is empty.
+const DEFAULT_TEST_DIRS: &[&str] = &["tests", "test", "__tests__"];
+
+/// Infixes that mark a file as a test.
+const TEST_INFIXES: &[&str] = &["test", "spec"];
+
+/// Requires every matched source file to have a test.
+///
+/// For `src/components/Button.tsx`, any of these counts:
+/// - a sibling `Button.test.*` or `Button.spec.*`
+/// - `__tests__/Button.*` next to it
+/// - a mirrored `Button.test.*` or `Button.spec.*` under one of `test_dirs`,
+///   e.g. `tests/components/Button.test.tsx`. Mirror directories are
+///   relative to the directory holding `src`.
+///
+/// Test files, stories and `.d.ts` files are never flagged themselves. The
+/// violation has no line, since it's about the file as a whole; pair the rule
+/// with a tracking ratchet to budget the files that still lack tests.
+pub struct TestColocationRule {
+    id: String,
+    severity: Severity,
+    message: String,
+    suggest: Vec<Suggestion>,
+    glob: Option<String>,
+    test_dirs: Vec<String>,
+}
+
+impl TestColocationRule {
+    pub fn new(config: &RuleConfig) -> Result<Self, RuleBuildError> {
+        let test_dirs = if config.test_dirs.is_empty() {
+            DEFAULT_TEST_DIRS.iter().map(|d| d.to_string()).collect()
+        } else {
+            config.test_dirs.clone()
+        };
+        Ok(Self {
+            id: config.id.clone(),
+            severity: config.severity,
+            message: config.message.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
+            glob: config.glob.clone().or_else(|| Some(DEFAULT_GLOB.to_string())),
+            test_dirs,
+        })
+    }
+
+    /// Directories that may hold a test for `file`: its own, its
+    /// `__tests__`, and the mirror of its directory under each test dir.
+    fn candidate_dirs(&self, file: &Path) -> Vec<(PathBuf, bool)> {
+        let dir = file.parent().unwrap_or(Path::new(""));
+        let mut dirs = vec![(dir.to_path_buf(), true), (dir.join("__tests__"), false)];
+        if let Some((root, rest)) = split_at_src(dir) {
+            for test_dir in &self.test_dirs {
+                dirs.push((root.join(test_dir).join(&rest), true));
+            }
+        }
+        dirs
+    }
+}
+
+impl Rule for TestColocationRule {
+    fn id(&self) -> &str {
+        &self.id
+    }
+    fn severity(&self) -> Severity {
+        self.severity
+    }
+    fn file_glob(&self) -> Option<&str> {
+        self.glob.as_deref()
+    }
+    fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
+        let Some(name) = ctx.file_path.file_name().and_then(OsStr::to_str) else {
+            return Vec::new();
+        };
+        if is_exempt(ctx.file_path, name) {
+            return Vec::new();
+        }
+        let (stem, extension) = name.rsplit_once('.').unwrap_or((name, "ts"));
+        let tested = self
+            .candidate_dirs(ctx.file_path)
+            .iter()
+            .any(|(dir, needs_infix)| has_test(dir, stem, *needs_infix));
+        if tested {
+            return Vec::new();
+        }
+        let suggest = if self.suggest.is_empty() {
+            vec![Suggestion::new(format!("add {}.test.{} next to it", stem, extension))]
+        } else {
+            self.suggest.clone()
+        };
+        vec![Violation {
+            rule_id: self.id.clone(),
+            severity: self.severity,
+            file: ctx.file_path.to_path_buf(),
+            line: None,
+            column: None,
+            message: self.message.clone(),
+            suggest,
+            source_line: None,
+            fix: None,
+            origin: None,
+            enclosing: None,
+        }]
+    }
+}
+
+/// Tests, stories and declaration files, which don't need tests of their own.
+fn is_exempt(path: &Path, name: &str) -> bool {
+    let mut parts = name.split('.').skip(1);
+    let marked = parts.any(|part| TEST_INFIXES.contains(&part) || part == "stories");
+    marked || name.ends_with(".d.ts") || path.components().any(|c| c.as_os_str() == "__tests__")
+}
+
+/// Whether `dir` has a test for `stem`: `stem.test.*` or `stem.spec.*`, or
+/// when `needs_infix` is false, any `stem.*`.
+fn has_test(dir: &Path, stem: &str, needs_infix: bool) -> bool {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return false;
+    };
+    entries.filter_map(Result::ok).any(|entry| {
+        let name = entry.file_name();
+        let Some(rest) = name.to_str().and_then(|n| n.strip_prefix(stem)).and_then(|r| r.strip_prefix('.')) else {
+            return false;
+        };
+        !needs_infix || TEST_INFIXES.iter().any(|infix| rest.strip_prefix(infix).is_some_and(|r| r.starts_with('.')))
+    })
+}
+
+/// `dir` split around its last `src` component: the directory holding
+/// `src`, and the path below it.
+fn split_at_src(dir: &Path) -> Option<(PathBuf, PathBuf)> {
+    let components: Vec<Component> = dir.components().collect();
+    let at = components.iter().rposition(|c| c.as_os_str() == "src")?;
+    let root: PathBuf = components[..at].iter().collect();
+    let rest: PathBuf = components[at + 1..].iter().collect();
+    Some((root, rest))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn missing(root: &Path, config: &RuleConfig, files: &[&str]) -> Vec<String> {
+        let rule = TestColocationRule::new(config).unwrap();
+        files
+            .iter()
+            .filter(|f| {
+                let path = root.join(f);
+                !rule.check_file(&ScanContext::new(&path, "")).is_empty()
+            })
+            .map(|f| f.to_string())
+            .collect()
+    }
+
+    #[test]
+    fn accepts_sibling_nested_and_mirrored_tests() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for file in [
+            "src/components/Button.tsx",
+            "src/components/Button.test.tsx",
+            "src/components/Card.tsx",
+            "src/components/__tests__/Card.tsx",
+            "src/hooks/useAuth.ts",
+            "tests/hooks/useAuth.spec.ts",
+            "src/lib/format.ts",
+            "src/lib/format.testing.ts",
+            "src/lib/types.d.ts",
+            "src/components/Button.stories.tsx",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "").unwrap();
+        }
+        let files = [
+            "src/components/Button.tsx",
+            "src/components/Card.tsx",
+            "src/hooks/useAuth.ts",
+            "src/lib/format.ts",
+            "src/lib/format.testing.ts",
+            "src/lib/types.d.ts",
+            "src/components/Button.stories.tsx",
+            "src/components/Button.test.tsx",
+        ];
+        let config = RuleConfig {
+            id: "needs-tests".into(),
+            message: "Missing tests".into(),
+            ..Default::default()
+        };
+        assert_eq!(missing(root, &config, &files), vec!["src/lib/format.ts", "src/lib/format.testing.ts"]);
+
+        let config = RuleConfig {
+            test_dirs: vec!["spec".into()],
+            ..config
+        };
+        assert!(missing(root, &config, &files).contains(&"src/hooks/useAuth.ts".to_string()));
+    }
+}
@@ -1,5 +1,5 @@
 use crate::config::{RuleConfig, Severity};
-use crate::rules::{Rule, RuleBuildError, ScanContext, Violation};
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
 use regex::Regex;
 
 /// Enforces that when a trigger pattern appears, a required pattern
@@ -14,18 +14,21 @@ use regex::Regex;
 /// - `condition_pattern` — required pattern that must appear nearby
 /// - `max_count` — window size (number of lines to search after trigger)
 /// - `regex` — whether patterns are regex
+/// - `scope_to_function` — search the enclosing function instead of
+///   `max_count` lines
 #[derive(Debug)]
 pub struct WindowPatternRule {
     id: String,
     severity: Severity,
     message: String,
-    suggest: Option<String>,
+    suggest: Vec<Suggestion>,
     glob: Option<String>,
     trigger: String,
     trigger_re: Option<Regex>,
     required: String,
     required_re: Option<Regex>,
     window_size: usize,
+    scope_to_function: bool,
 }
 
 impl WindowPatternRule {
@@ -70,13 +73,14 @@ impl WindowPatternRule {
             id: config.id.clone(),
             severity: config.severity,
             message: config.message.clone(),
-            suggest: config.suggest.clone(),
+            suggest: Suggestion::from_config(&config.suggest),
             glob: config.glob.clone(),
             trigger,
             trigger_re,
             required,
             required_re,
             window_size,
+            scope_to_function: config.scope_to_function,
         })
     }
 
@@ -110,17 +114,30 @@ impl Rule for WindowPatternRule {
 
     fn check_file(&self, ctx: &ScanContext) -> Vec<Violation> {
         let mut violations = Vec::new();
-        let lines: Vec<&str> = ctx.content.lines().collect();
+        let lines = ctx.lines();
         let total = lines.len();
 
+        let functions = if self.scope_to_function {
+            crate::rules::ast::parse_file(ctx.file_path, ctx.content)
+                .map(|tree| function_spans(tree.root_node()))
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
         for (idx, line) in lines.iter().enumerate() {
             if !self.line_matches_trigger(line) {
                 continue;
             }
 
-            // Search within window for the required pattern
-            let window_end = (idx + self.window_size + 1).min(total);
-            let window_start = idx.saturating_sub(self.window_size);
+            // Search the enclosing function, or the line window around the trigger
+            let (window_start, window_end) = match enclosing_span(&functions, idx) {
+                Some((start, end)) => (start, (end + 1).min(total)),
+                None => (
+                    idx.saturating_sub(self.window_size),
+                    (idx + self.window_size + 1).min(total),
+                ),
+            };
 
             let found = (window_start..window_end)
                 .any(|i| i != idx && self.line_matches_required(lines[i]));
@@ -136,6 +153,8 @@ impl Rule for WindowPatternRule {
                     suggest: self.suggest.clone(),
                     source_line: Some(line.to_string()),
                     fix: None,
+                    origin: None,
+                    enclosing: None,
                 });
             }
         }
@@ -144,6 +163,42 @@ impl Rule for WindowPatternRule {
     }
 }
 
+/// Node kinds that bound a `scope_to_function` window.
+const FUNCTION_KINDS: &[&str] = &[
+    "function_declaration",
+    "generator_function_declaration",
+    "function_expression",
+    "function",
+    "arrow_function",
+    "method_definition",
+];
+
+/// Line spans (start row, end row) of every multi-line function-like node.
+/// Single-line callbacks are skipped so a trigger inside `xs.map(x => ...)`
+/// is scoped to the function around it rather than to its own line.
+fn function_spans(node: tree_sitter::Node) -> Vec<(usize, usize)> {
+    let mut spans = Vec::new();
+    let mut cursor = node.walk();
+    let mut stack = vec![node];
+    while let Some(node) = stack.pop() {
+        let (start, end) = (node.start_position().row, node.end_position().row);
+        if FUNCTION_KINDS.contains(&node.kind()) && end > start {
+            spans.push((start, end));
+        }
+        stack.extend(node.children(&mut cursor));
+    }
+    spans
+}
+
+/// The smallest span containing `line`, i.e. the innermost enclosing function.
+fn enclosing_span(spans: &[(usize, usize)], line: usize) -> Option<(usize, usize)> {
+    spans
+        .iter()
+        .filter(|(start, end)| (*start..=*end).contains(&line))
+        .min_by_key(|(start, end)| end - start)
+        .copied()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -159,7 +214,7 @@ mod tests {
             id: "test-window".into(),
             severity: Severity::Error,
             message: "required pattern not found within window".into(),
-            suggest: Some("add the required pattern nearby".into()),
+            suggest: vec!["add the required pattern nearby".into()],
             pattern: Some(trigger.to_string()),
             condition_pattern: Some(required.to_string()),
             max_count: Some(window),
@@ -169,10 +224,7 @@ mod tests {
     }
 
     fn check(rule: &WindowPatternRule, content: &str) -> Vec<Violation> {
-        let ctx = ScanContext {
-            file_path: Path::new("test.ts"),
-            content,
-        };
+        let ctx = ScanContext::new(Path::new("test.ts"), content);
         rule.check_file(&ctx)
     }
 
@@ -231,6 +283,77 @@ mod tests {
         assert!(violations.is_empty(), "organizationId appears before the trigger within window");
     }
 
+    fn scoped(window: usize) -> WindowPatternRule {
+        let mut config = make_config("db.delete(", "organizationId", window, false);
+        config.scope_to_function = true;
+        WindowPatternRule::new(&config).unwrap()
+    }
+
+    const HANDLERS: &str = "\
+function removeUser(id) {
+  return db.delete(users).where({ id });
+}
+
+function removePost(id, organizationId) {
+  return db.delete(posts).where({ id, organizationId });
+}
+";
+
+    #[test]
+    fn line_window_crosses_function_boundaries() {
+        let config = make_config("db.delete(", "organizationId", 5, false);
+        let rule = WindowPatternRule::new(&config).unwrap();
+        assert!(check(&rule, HANDLERS).is_empty());
+    }
+
+    #[test]
+    fn scope_to_function_stops_at_function_boundary() {
+        let violations = check(&scoped(5), HANDLERS);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line, Some(2));
+    }
+
+    #[test]
+    fn scope_to_function_searches_whole_function() {
+        let content = "\
+function removeUser(id, organizationId) {
+  assertMember(organizationId);
+
+
+
+  return db.delete(users).where({ id });
+}
+";
+        assert!(check(&scoped(1), content).is_empty());
+    }
+
+    #[test]
+    fn scope_to_function_uses_innermost_multi_line_function() {
+        let content = "\
+const Page = () => {
+  const organizationId = useOrg();
+  const onDelete = async (id) => {
+    await db.delete(items).where({ id });
+  };
+  return null;
+};
+";
+        let violations = check(&scoped(5), content);
+        assert_eq!(violations.len(), 1, "guard is outside the inner handler");
+
+        let one_liner = "function purge(ids, organizationId) {\n\n\n  ids.map((id) => db.delete(id));\n}\n";
+        assert!(check(&scoped(1), one_liner).is_empty());
+    }
+
+    #[test]
+    fn scope_to_function_falls_back_outside_functions() {
+        let rule = scoped(1);
+        let content = "const organizationId = 1;\nawait db.delete(users);\n\n\nawait db.delete(posts);\n";
+        let violations = check(&rule, content);
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].line, Some(5));
+    }
+
     #[test]
     fn missing_trigger_pattern_error() {
         let config = RuleConfig {
@@ -1,27 +1,250 @@
-use crate::cli::toml_config::{TomlConfig, TomlRule};
+use crate::fix::FixReport;
+use crate::cli::toml_config::{
+    BaselineSection, HooksSection, OutputSection, RatchetMode, RuleOrigin, ScopedPreset, SubmodulePolicy, TomlConfig, TomlRule, VendoredPolicy,
+};
+use crate::deadline::{self, RatchetDeadline};
 use crate::git_diff;
+use crate::version::{self, Version, VersionError, VersionReq};
+use crate::locale::Catalog;
 use crate::presets::{self, PresetError};
+use crate::config::{RuleConfig, Severity};
 use crate::rules::factory::{self, FactoryError};
+use crate::rules::env_allowlist::EnvAllowlistRule;
+use crate::rules::expo_router::ExpoRouterRule;
 use crate::rules::file_presence::FilePresenceRule;
-use crate::rules::{Rule, ScanContext, Violation};
-use globset::{Glob, GlobSet, GlobSetBuilder};
+use crate::rules::ast;
+use crate::rules::{Rule, RuleBuildError, ScanContext, Suggestion, Violation};
+use globset::{Glob, GlobBuilder, GlobMatcher, GlobSet, GlobSetBuilder};
+use ignore::overrides::{Override, OverrideBuilder};
 use ignore::WalkBuilder;
 use rayon::prelude::*;
 use serde::Serialize;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::hash::{Hash, Hasher};
 use std::fs;
+use std::panic::{self, AssertUnwindSafe};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicUsize, Ordering};
-use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, OnceLock};
+use std::time::{Duration, Instant};
 
 /// Detect minified/bundled files by checking for extremely long lines.
 /// Normal source code rarely exceeds 500 chars per line; minified code
-/// routinely has lines of 10K+ chars.
+/// routinely has lines of 10K+ chars. Overridable via `[baseline] max_line_length`.
 const MINIFIED_LINE_LENGTH_THRESHOLD: usize = 500;
 
-fn is_likely_minified(content: &str) -> bool {
-    content.lines().any(|line| line.len() > MINIFIED_LINE_LENGTH_THRESHOLD)
+/// How many lines above a violation an `allow-next-line` directive may sit
+/// (directive lines themselves don't count). Overridable via
+/// `[baseline] suppression_lookback`.
+const DEFAULT_SUPPRESSION_LOOKBACK: usize = 1;
+
+/// Upper bound on how many lines a multi-line JSX opening tag may span before
+/// suppression stops treating it as one element.
+const JSX_TAG_MAX_LINES: usize = 64;
+
+/// How many leading lines are searched for a generated-file marker.
+/// Overridable via `[baseline] generated_header_lines`.
+const GENERATED_HEADER_LINES: usize = 5;
+
+/// Rule ids of the violations `--strict` adds.
+pub const UNUSED_SUPPRESSION: &str = "unused-suppression";
+pub const UNREADABLE_FILE: &str = "unreadable-file";
+pub const UNMATCHED_GLOB: &str = "unmatched-glob";
+
+/// Rule id of the notice a `--changed-only` scan reports when a change
+/// suppresses a `requires_review` rule or edits its config.
+pub const REVIEW_REQUIRED: &str = "review-required";
+
+/// Rule id of the violation reported when a rule panics on a file.
+pub const INTERNAL_ERROR: &str = "internal-error";
+
+/// Exit code of a scan in which some rule panicked: the report is missing
+/// that rule's results for the files it panicked on.
+pub const PARTIAL_SCAN_EXIT_CODE: i32 = 3;
+
+/// Header markers code generators use to flag their output.
+const GENERATED_MARKERS: &[&str] = &["@generated", "DO NOT EDIT", "Code generated by"];
+
+fn is_likely_minified(content: &str, max_line_length: usize) -> bool {
+    content.lines().any(|line| line.len() > max_line_length)
+}
+
+/// Whether one of the first `header_lines` lines carries a generated-file
+/// marker. An `@generated` that is closed by `@end-generated` marks an ignore
+/// region rather than the whole file.
+fn is_likely_generated(content: &str, header_lines: usize) -> bool {
+    content.lines().take(header_lines).any(|line| {
+        GENERATED_MARKERS.iter().any(|marker| {
+            line.contains(marker) && !(*marker == "@generated" && content.contains("@end-generated"))
+        })
+    })
+}
+
+/// Runtime scan options that come from the CLI rather than the config file.
+#[derive(Debug, Clone, Default)]
+pub struct ScanOptions {
+    /// Scan files even when they look minified (`--include-minified`).
+    pub include_minified: bool,
+    /// Scan files even when their header marks them as generated (`--include-generated`).
+    pub include_generated: bool,
+    /// Translated rule messages and suggestions (`--locale`).
+    pub catalog: Catalog,
+    /// Descend at most this many directories below each target (`--max-depth`).
+    pub max_depth: Option<usize>,
+    /// Stop at the first error-severity violation and report only it (`--fail-fast`).
+    pub fail_fast: bool,
+    /// Raise warnings to errors, and report unreadable files, rules whose
+    /// glob matches no files, and unused suppressions as errors (`--strict`).
+    pub strict: bool,
+    /// Run only the rules in these `[groups.*]` (`--group`).
+    pub groups: Vec<String>,
+    /// The `--changed-only` diff, so rules can tell added files from modified ones.
+    pub changed: Option<Arc<git_diff::ChangedLines>>,
+    /// Under `--changed-only`, treat a renamed file as new (`--no-renames`).
+    pub no_renames: bool,
+    /// Walk and filter files as usual but run no rules, so `scanned_files`
+    /// lists what a scan would read (`--list-files`).
+    pub list_files: bool,
+}
+
+/// Decides whether a file should be skipped as minified/bundled output.
+struct MinifiedFilter {
+    /// Lines longer than this mark a file as minified. `0` disables detection.
+    max_line_length: usize,
+    /// Files matching these globs are always scanned (`minified_globs`).
+    force_scan: Option<GlobSet>,
+    include_minified: bool,
+}
+
+impl MinifiedFilter {
+    fn new(section: &BaselineSection, options: &ScanOptions) -> Result<Self, ScanError> {
+        let force_scan = if section.minified_globs.is_empty() {
+            None
+        } else {
+            Some(build_glob_set(&section.minified_globs)?)
+        };
+
+        Ok(Self {
+            max_line_length: section
+                .max_line_length
+                .unwrap_or(MINIFIED_LINE_LENGTH_THRESHOLD),
+            force_scan,
+            include_minified: options.include_minified,
+        })
+    }
+
+    fn should_skip(&self, file_str: &str, file_name: &str, content: &str) -> bool {
+        if self.include_minified || self.max_line_length == 0 {
+            return false;
+        }
+        if let Some(ref gs) = self.force_scan {
+            if gs.is_match(file_str) || gs.is_match(file_name) {
+                return false;
+            }
+        }
+        is_likely_minified(content, self.max_line_length)
+    }
+}
+
+/// Decides whether a file should be skipped as generated code.
+struct GeneratedFilter {
+    /// Leading lines searched for a marker. `0` disables detection.
+    header_lines: usize,
+    include_generated: bool,
+}
+
+impl GeneratedFilter {
+    fn new(section: &BaselineSection, options: &ScanOptions) -> Self {
+        Self {
+            header_lines: section.generated_header_lines.unwrap_or(GENERATED_HEADER_LINES),
+            include_generated: options.include_generated,
+        }
+    }
+
+    fn should_skip(&self, content: &str) -> bool {
+        !self.include_generated && is_likely_generated(content, self.header_lines)
+    }
+}
+
+/// Directory-walking policy derived from `[baseline]` settings.
+///
+/// The default walks everything except gitignored and hidden paths, which is
+/// what the walker did before these settings existed.
+#[derive(Debug, Clone, Default)]
+pub struct WalkPolicy {
+    pub follow_symlinks: bool,
+    pub submodules: SubmodulePolicy,
+    /// Directory names pruned as vendored code.
+    pub vendored_dirs: Vec<String>,
+    /// Config filename looked up inside submodules for the `nested` policy.
+    pub nested_config_name: Option<String>,
+    /// Gitignore-style directory globs pruned during traversal, derived from
+    /// excludes of the form `<dir>/**`.
+    pub pruned_dirs: Vec<String>,
+    /// Maximum directory depth below each walk root.
+    pub max_depth: Option<usize>,
+    /// Hidden directories walked anyway because a rule's glob names them,
+    /// e.g. `.github` (see [`hidden_dirs`]).
+    pub hidden_dirs: Vec<String>,
+}
+
+impl WalkPolicy {
+    pub fn from_section(section: &BaselineSection, config_path: &Path) -> Self {
+        let vendored_dirs = match section.vendored {
+            VendoredPolicy::Skip => section.vendored_dirs.clone(),
+            VendoredPolicy::Scan => Vec::new(),
+        };
+        Self {
+            follow_symlinks: section.follow_symlinks,
+            submodules: section.submodules,
+            vendored_dirs,
+            nested_config_name: config_path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned()),
+            pruned_dirs: pruned_dirs(&section.excludes()),
+            max_depth: None,
+            hidden_dirs: Vec::new(),
+        }
+    }
+
+    /// Overrides that stop the walker from descending into `pruned_dirs`
+    /// below `root`. Files in those directories would all be excluded anyway.
+    fn overrides(&self, root: &Path) -> Option<Override> {
+        if self.pruned_dirs.is_empty() {
+            return None;
+        }
+        let mut builder = OverrideBuilder::new(root);
+        for dir in &self.pruned_dirs {
+            if let Err(e) = builder.add(&format!("!{}", dir)) {
+                tracing::debug!("not pruning '{}' during the walk: {}", dir, e);
+            }
+        }
+        builder.build().ok()
+    }
+
+    /// Whether a directory below the walk root should be pruned.
+    fn prunes_dir(&self, dir: &Path) -> bool {
+        let name = dir.file_name().unwrap_or_default().to_string_lossy();
+        if self.vendored_dirs.iter().any(|v| *v == name) {
+            return true;
+        }
+        self.submodules == SubmodulePolicy::Skip && is_submodule(dir)
+    }
+
+    /// The submodule's own config file, if the `nested` policy applies to `dir`.
+    fn nested_config(&self, dir: &Path) -> Option<PathBuf> {
+        if self.submodules != SubmodulePolicy::Nested || !is_submodule(dir) {
+            return None;
+        }
+        let candidate = dir.join(self.nested_config_name.as_ref()?);
+        candidate.is_file().then_some(candidate)
+    }
+}
+
+/// A git submodule checkout has a `.git` file (not directory) at its root.
+fn is_submodule(dir: &Path) -> bool {
+    dir.join(".git").is_file()
 }
 
 /// A plugin config file containing additional rules.
@@ -31,14 +254,47 @@ struct PluginConfig {
     rule: Vec<crate::cli::toml_config::TomlRule>,
 }
 
+/// A file pulled in by the root config's `include`. Unlike a plugin it may
+/// also extend list settings of `[baseline]`; scalar settings stay in the
+/// root config, so setting one here is a parse error.
+#[derive(Debug, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IncludedConfig {
+    #[serde(default)]
+    baseline: IncludedSection,
+    #[serde(default)]
+    rule: Vec<TomlRule>,
+}
+
+#[derive(Debug, Default, serde::Deserialize)]
+#[serde(deny_unknown_fields)]
+struct IncludedSection {
+    #[serde(default)]
+    exclude: Vec<String>,
+    #[serde(default)]
+    exclude_extend: Vec<String>,
+    #[serde(default)]
+    extends: Vec<String>,
+    #[serde(default)]
+    plugins: Vec<String>,
+    #[serde(default)]
+    scoped: Vec<ScopedPreset>,
+}
+
 #[derive(Debug)]
 pub enum ScanError {
     ConfigRead(std::io::Error),
     ConfigParse(toml::de::Error),
+    IncludeRead(PathBuf, std::io::Error),
+    IncludeParse(PathBuf, toml::de::Error),
     GlobParse(globset::Error),
     RuleFactory(FactoryError),
     Preset(PresetError),
     GitDiff(String),
+    UnknownGroup(String),
+    UnknownGroupRule { group: String, rule: String },
+    RequiredVersion(VersionError),
+    UnsupportedVersion { config: PathBuf, required: VersionReq },
 }
 
 impl fmt::Display for ScanError {
@@ -46,10 +302,30 @@ impl fmt::Display for ScanError {
         match self {
             ScanError::ConfigRead(e) => write!(f, "failed to read config: {}", e),
             ScanError::ConfigParse(e) => write!(f, "failed to parse config: {}", e),
+            ScanError::IncludeRead(path, e) => {
+                write!(f, "failed to read included config {}: {}", path.display(), e)
+            }
+            ScanError::IncludeParse(path, e) => {
+                write!(f, "failed to parse included config {}: {}", path.display(), e)
+            }
             ScanError::GlobParse(e) => write!(f, "invalid glob pattern: {}", e),
             ScanError::RuleFactory(e) => write!(f, "failed to build rule: {}", e),
             ScanError::Preset(e) => write!(f, "preset error: {}", e),
             ScanError::GitDiff(e) => write!(f, "git diff failed: {}", e),
+            ScanError::UnknownGroup(name) => {
+                write!(f, "unknown group '{}' (define it under [groups.{}])", name, name)
+            }
+            ScanError::UnknownGroupRule { group, rule } => {
+                write!(f, "group '{}' lists unknown rule '{}'", group, rule)
+            }
+            ScanError::RequiredVersion(e) => write!(f, "[baseline] required_version: {}", e),
+            ScanError::UnsupportedVersion { config, required } => write!(
+                f,
+                "{} requires baseline {}, but this is {}; run `baseline self-update` to upgrade, or `baseline self-update --version <VERSION>` to install a matching release",
+                config.display(),
+                required,
+                version::CURRENT
+            ),
         }
     }
 }
@@ -59,19 +335,334 @@ impl std::error::Error for ScanError {}
 pub struct ScanResult {
     pub violations: Vec<Violation>,
     pub files_scanned: usize,
+    /// Files skipped because their header marks them as generated.
+    pub generated_skipped: usize,
     pub rules_loaded: usize,
+    /// Every rule the config loaded, in resolution order (for rule metrics).
+    pub rules: Vec<RuleMeta>,
     /// For each ratchet rule: (found_count, max_count).
     pub ratchet_counts: HashMap<String, (usize, usize)>,
+    /// Deadlines of ratchet rules that set one, keyed by rule id.
+    pub ratchet_deadlines: HashMap<String, RatchetDeadline>,
     /// Number of changed files when using --changed-only.
     pub changed_files_count: Option<usize>,
     /// Base ref used for diff when using --changed-only.
     pub base_ref: Option<String>,
+    /// Phase timings, reported with `--timing`.
+    pub timings: ScanTimings,
+    /// Files whose content was actually scanned (length == `files_scanned`).
+    pub scanned_files: Vec<PathBuf>,
+    /// Content hash of each scanned file, used by `--fix` to skip files that
+    /// changed after the scan.
+    pub file_hashes: HashMap<PathBuf, u64>,
+    /// Outcome of `--fix`, attached after fixes are applied.
+    pub fixes: Option<FixReport>,
+    /// The `[output]` section of the (first) config.
+    pub output: OutputSection,
+    /// The `[hooks]` section of the (first) config.
+    pub hooks: HooksSection,
+    /// Rules whose `glob` matched none of the walked files.
+    pub unmatched_globs: Vec<UnmatchedGlob>,
+    /// `[groups.*]` the scan was limited to (`--group`); empty runs every rule.
+    pub groups: Vec<String>,
+    /// `--fail-fast` stopped the scan before every file was checked, so
+    /// ratchets were not evaluated.
+    pub stopped_early: bool,
+}
+
+impl ScanResult {
+    /// Ids of the ratchet rules a scan that [`stopped_early`](Self::stopped_early)
+    /// didn't evaluate, sorted.
+    pub fn unevaluated_ratchets(&self) -> Vec<&str> {
+        if !self.stopped_early {
+            return Vec::new();
+        }
+        let mut ids: Vec<&str> = self
+            .rules
+            .iter()
+            .filter(|meta| meta.rule_type == "ratchet")
+            .map(|meta| meta.rule_id.as_str())
+            .collect();
+        ids.sort_unstable();
+        ids.dedup();
+        ids
+    }
+
+    /// [`PARTIAL_SCAN_EXIT_CODE`] when a rule panicked, 1 when any violation
+    /// is at or above `[output.exit]`'s severity (default: error), else 0.
+    pub fn exit_code(&self) -> i32 {
+        let fail_on = self.output.fail_on();
+        if self.violations.iter().any(|v| v.rule_id == INTERNAL_ERROR) {
+            PARTIAL_SCAN_EXIT_CODE
+        } else if self.violations.iter().any(|v| v.severity.at_least(fail_on)) {
+            1
+        } else {
+            0
+        }
+    }
+
+    /// Drop violations below the severity `[output.<format>]` reports.
+    pub fn retain_for_format(&mut self, format: &str) {
+        let min = self.output.min_severity(format);
+        self.violations.retain(|v| v.severity.at_least(min));
+    }
+}
+
+/// A rule whose `glob` matched none of the files a scan collected, which
+/// usually means the glob names the wrong directory (`src/**` vs `app/**`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct UnmatchedGlob {
+    pub rule_id: String,
+    pub glob: String,
+}
+
+impl fmt::Display for UnmatchedGlob {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "rule '{}' matched no files (glob '{}')", self.rule_id, self.glob)
+    }
+}
+
+/// Wall-clock timings for the phases of a scan.
+#[derive(Debug, Clone, Default)]
+pub struct ScanTimings {
+    pub config_load: Duration,
+    pub rule_build: Duration,
+    pub scan: Duration,
+    /// Rules served from the compiled-rule cache instead of being rebuilt.
+    pub cached_rules: usize,
+    /// How rules were grouped, and how many files each group ran on.
+    pub rule_groups: Vec<RuleGroupSummary>,
+}
+
+/// One group of rules sharing a glob and `file_contains` precondition, as
+/// reported by `--timing`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleGroupSummary {
+    pub glob: Option<String>,
+    pub exclude: Vec<String>,
+    pub file_contains: Option<String>,
+    pub rule_ids: Vec<String>,
+    /// Files the group's globs matched.
+    pub files_matched: usize,
+    /// Of those, files that also passed `file_contains`, so the rules ran.
+    pub files_checked: usize,
+}
+
+/// What a result would look like if warnings were enforced as errors.
+/// Produced by `--simulate-severity error`; never affects the real exit code.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeveritySimulation {
+    pub errors: usize,
+    pub warnings: usize,
+    /// Number of warnings that would become errors.
+    pub promoted: usize,
+    /// Rules whose warnings would be promoted, sorted.
+    pub promoted_rules: Vec<String>,
+    pub exit_code: i32,
+}
+
+/// Re-grade warnings as errors. When `only_rules` is non-empty, only warnings
+/// from those rule ids are promoted.
+pub fn simulate_error_severity(result: &ScanResult, only_rules: &[String]) -> SeveritySimulation {
+    let mut errors = 0;
+    let mut warnings = 0;
+    let mut promoted = 0;
+    let mut promoted_rules: Vec<String> = Vec::new();
+
+    for v in &result.violations {
+        match v.severity {
+            Severity::Error => errors += 1,
+            Severity::Warning if only_rules.is_empty() || only_rules.contains(&v.rule_id) => {
+                errors += 1;
+                promoted += 1;
+                promoted_rules.push(v.rule_id.clone());
+            }
+            Severity::Warning => warnings += 1,
+            Severity::Info | Severity::Hint => {}
+        }
+    }
+    promoted_rules.sort();
+    promoted_rules.dedup();
+
+    SeveritySimulation {
+        errors,
+        warnings,
+        promoted,
+        promoted_rules,
+        exit_code: if errors > 0 { 1 } else { 0 },
+    }
+}
+
+/// A loaded rule and whether it only runs on some files.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleMeta {
+    pub rule_id: String,
+    pub rule_type: String,
+    pub severity: Severity,
+    /// Narrowed by `glob` or `exclude_glob`.
+    pub glob_restricted: bool,
+    /// Narrowed by `file_contains`, `file_not_contains` or `sibling_files`.
+    pub conditioned: bool,
+    pub origin: RuleOrigin,
+    /// The config that loaded the rule, when several were scanned together.
+    pub config: Option<PathBuf>,
+    /// Its fixes need `--fix-unsafe`: the config sets `safe_fixes_only` and
+    /// the rule's fixes aren't safe.
+    pub fix_gated: bool,
+    /// Team or person responsible for the rule (`owner`).
+    pub owner: Option<String>,
+    /// Suppressing the rule or changing its config needs the owner's review.
+    pub requires_review: bool,
+}
+
+impl RuleMeta {
+    fn from_toml(rule: &TomlRule) -> Self {
+        let config = rule.to_rule_config();
+        Self {
+            rule_id: config.id,
+            rule_type: rule.rule_type.clone(),
+            severity: config.severity,
+            glob_restricted: config.glob.is_some() || !config.exclude_glob.is_empty(),
+            conditioned: config.file_contains.is_some()
+                || config.file_not_contains.is_some()
+                || !config.sibling_files.is_empty(),
+            origin: rule.origin.clone(),
+            config: None,
+            fix_gated: false,
+            owner: rule.owner.clone(),
+            requires_review: rule.requires_review,
+        }
+    }
+
+    /// Meta for a rule loaded under `section`.
+    fn from_config(rule: &TomlRule, section: &BaselineSection) -> Self {
+        Self {
+            fix_gated: section.safe_fixes_only && !crate::fix::is_safe_fix(rule),
+            ..Self::from_toml(rule)
+        }
+    }
+}
+
+/// The loaded rule that produced `v`, matched by id and originating config.
+/// When scoped presets define the id more than once, the most specific scope
+/// containing the file wins, as it does during the scan.
+pub fn rule_for<'a>(result: &'a ScanResult, v: &Violation) -> Option<&'a RuleMeta> {
+    let file = v.file.to_string_lossy().replace('\\', "/");
+    let scope_depth = |meta: &RuleMeta| match &meta.origin {
+        RuleOrigin::ScopedPreset { path, .. } => {
+            let dir = format!("{}/", path.trim_end_matches('/'));
+            if file.starts_with(&dir) || file.contains(&format!("/{}", dir)) {
+                dir.len()
+            } else {
+                0
+            }
+        }
+        _ => 0,
+    };
+    result
+        .rules
+        .iter()
+        .filter(|meta| meta.rule_id == v.rule_id && meta.config == v.origin)
+        .rev()
+        .max_by_key(|meta| scope_depth(meta))
+}
+
+/// How many times each loaded rule fired, in config order, including rules
+/// that never fired. Ratchet rules count every match, even those within
+/// budget that were not reported as violations.
+pub fn rule_metrics(result: &ScanResult) -> Vec<(&RuleMeta, usize)> {
+    let mut fired: HashMap<&str, usize> = HashMap::new();
+    for v in &result.violations {
+        *fired.entry(v.rule_id.as_str()).or_insert(0) += 1;
+    }
+    let mut ratchet_matches: HashMap<&str, usize> = HashMap::new();
+    for (key, &(found, _)) in &result.ratchet_counts {
+        *ratchet_matches.entry(split_ratchet_key(key).0).or_insert(0) += found;
+    }
+
+    result
+        .rules
+        .iter()
+        .map(|meta| {
+            let id = meta.rule_id.as_str();
+            let count = match ratchet_matches.get(id) {
+                Some(&found) => found,
+                None => fired.get(id).copied().unwrap_or(0),
+            };
+            (meta, count)
+        })
+        .collect()
+}
+
+/// Violation totals for one rule.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuleCount {
+    pub rule_id: String,
+    pub severity: Severity,
+    pub violations: usize,
+    /// Number of distinct files with at least one violation of the rule.
+    pub files: usize,
+}
+
+/// Aggregate violations per rule, most violations first (ties by rule id).
+pub fn rule_counts(result: &ScanResult) -> Vec<RuleCount> {
+    let mut by_rule: HashMap<&str, (Severity, usize, Vec<&Path>)> = HashMap::new();
+    for v in &result.violations {
+        let entry = by_rule
+            .entry(v.rule_id.as_str())
+            .or_insert((v.severity, 0, Vec::new()));
+        entry.1 += 1;
+        if !entry.2.contains(&v.file.as_path()) {
+            entry.2.push(&v.file);
+        }
+    }
+
+    let mut counts: Vec<RuleCount> = by_rule
+        .into_iter()
+        .map(|(rule_id, (severity, violations, files))| RuleCount {
+            rule_id: rule_id.to_string(),
+            severity,
+            violations,
+            files: files.len(),
+        })
+        .collect();
+    counts.sort_by(|a, b| b.violations.cmp(&a.violations).then_with(|| a.rule_id.cmp(&b.rule_id)));
+    counts
+}
+
+/// [`rule_counts`] for every loaded rule that isn't a ratchet, including
+/// rules that never fired, in the same order (for `--count-all`).
+pub fn rule_totals(result: &ScanResult) -> Vec<RuleCount> {
+    let ratchets: HashSet<&str> = result.ratchet_counts.keys().map(|key| split_ratchet_key(key).0).collect();
+    let mut totals: Vec<RuleCount> = rule_counts(result)
+        .into_iter()
+        .filter(|c| !ratchets.contains(c.rule_id.as_str()))
+        .collect();
+    for meta in &result.rules {
+        if meta.rule_type == "ratchet"
+            || ratchets.contains(meta.rule_id.as_str())
+            || totals.iter().any(|c| c.rule_id == meta.rule_id)
+        {
+            continue;
+        }
+        totals.push(RuleCount {
+            rule_id: meta.rule_id.clone(),
+            severity: meta.severity,
+            violations: 0,
+            files: 0,
+        });
+    }
+    totals.sort_by(|a, b| b.violations.cmp(&a.violations).then_with(|| a.rule_id.cmp(&b.rule_id)));
+    totals
 }
 
 #[derive(Debug, Serialize, serde::Deserialize)]
 pub struct BaselineEntry {
     pub rule_id: String,
     pub pattern: String,
+    /// The rule a tracking ratchet counts, in place of `pattern`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub track: Option<String>,
     pub count: usize,
 }
 
@@ -81,20 +672,45 @@ pub struct BaselineResult {
     pub files_scanned: usize,
 }
 
-/// A group of rules that share the same glob patterns.
-/// Glob matching is done once per group, amortizing the cost when
-/// multiple rules use the same inclusion/exclusion patterns.
+/// A group of rules that share the same glob patterns and `file_contains`
+/// precondition. Glob matching and the containment check are done once per
+/// group, so a file that fails either skips every rule in it.
 struct RuleGroup {
+    /// Source pattern of `inclusion_glob`, for reporting.
+    inclusion_pattern: Option<String>,
+    /// Source patterns of `exclusion_glob`, for reporting.
+    exclusion_patterns: Vec<String>,
     inclusion_glob: Option<GlobSet>,
     exclusion_glob: Option<GlobSet>,
+    file_contains: Option<String>,
     rules: Vec<RuleWithConditioning>,
+    /// Files the globs matched, counted for `--timing`.
+    files_matched: AtomicUsize,
+    /// Files that also passed `file_contains`.
+    files_checked: AtomicUsize,
+}
+
+impl RuleGroup {
+    fn summary(&self) -> RuleGroupSummary {
+        RuleGroupSummary {
+            glob: self.inclusion_pattern.clone(),
+            exclude: self.exclusion_patterns.clone(),
+            file_contains: self.file_contains.clone(),
+            rule_ids: self.rules.iter().map(|r| r.rule.id().to_string()).collect(),
+            files_matched: self.files_matched.load(Ordering::Relaxed),
+            files_checked: self.files_checked.load(Ordering::Relaxed),
+        }
+    }
 }
 
 /// A single rule with its conditioning data and pre-computed suppression strings.
 struct RuleWithConditioning {
-    rule: Box<dyn Rule>,
-    file_contains: Option<String>,
+    rule: Arc<dyn Rule>,
     file_not_contains: Option<String>,
+    sibling_files: Vec<String>,
+    /// Rule id and severity of the ratchet tracking this rule, which its
+    /// violations are reported under once suppressions are applied.
+    report_as: Option<(String, Severity)>,
     /// Pre-computed `"baseline:allow-{rule_id}"` string.
     allow_marker: String,
     /// Pre-computed `"baseline:allow-next-line {rule_id}"` string.
@@ -105,112 +721,651 @@ struct RuleWithConditioning {
 struct BuiltRules {
     rule_groups: Vec<RuleGroup>,
     ratchet_thresholds: HashMap<String, usize>,
+    /// Ratchet rules with per-directory budgets; these are not in `ratchet_thresholds`.
+    ratchet_budgets: HashMap<String, ScopedBudget>,
+    /// Deadlines of ratchet rules that set one, already applied to the budgets above.
+    ratchet_deadlines: HashMap<String, RatchetDeadline>,
     file_presence_rules: Vec<FilePresenceRule>,
+    /// Expo Router structure rules; like file-presence, these check the tree.
+    expo_router_rules: Vec<ExpoRouterRule>,
+    /// Env allowlist rules, which also report unread template entries once
+    /// every file has been checked.
+    env_allowlist_rules: Vec<Arc<EnvAllowlistRule>>,
+    /// Number of rules served from the compiled-rule cache.
+    cache_hits: usize,
+    /// Hidden directories the rules' globs name, which the walk must enter.
+    hidden_dirs: Vec<String>,
+    /// Ratchets with `ratchet_mode = "no-new"`. They stay in the budgets above
+    /// until [`BuiltRules::hold_to_diff`] takes them out.
+    ratchet_no_new: HashSet<String>,
 }
 
-/// Build rules from resolved TOML rules. Shared by run_scan and run_scan_stdin.
-fn build_rules(resolved_rules: &[TomlRule]) -> Result<BuiltRules, ScanError> {
-    let mut ratchet_thresholds: HashMap<String, usize> = HashMap::new();
-    let mut file_presence_rules: Vec<FilePresenceRule> = Vec::new();
-
-    // Intermediate representation before grouping
-    struct IntermediateRule {
-        rule: Box<dyn Rule>,
-        inclusion_pattern: Option<String>,
-        exclusion_patterns: Vec<String>,
-        file_contains: Option<String>,
-        file_not_contains: Option<String>,
+impl BuiltRules {
+    /// Whether `rule_id` is a ratchet, counted against a budget at the end
+    /// of the scan.
+    fn is_ratchet(&self, rule_id: &str) -> bool {
+        self.ratchet_thresholds.contains_key(rule_id)
+            || self.ratchet_budgets.contains_key(rule_id)
+            || self.ratchet_no_new.contains(rule_id)
     }
 
-    let mut intermediates: Vec<IntermediateRule> = Vec::new();
-
-    for toml_rule in resolved_rules {
-        let rule_config = toml_rule.to_rule_config();
-
-        // File-presence rules are handled separately (they check existence, not content)
-        if toml_rule.rule_type == "file-presence" {
-            if let Ok(fp_rule) = FilePresenceRule::new(&rule_config) {
-                file_presence_rules.push(fp_rule);
-            }
-            continue;
+    /// For a `--changed-only` scan, take `no-new` ratchets out of budget
+    /// accounting; [`apply_ratchet_no_new`] holds them to zero matches on
+    /// changed lines instead.
+    fn hold_to_diff(&mut self) {
+        for id in &self.ratchet_no_new {
+            self.ratchet_thresholds.remove(id);
+            self.ratchet_budgets.remove(id);
         }
+    }
+}
 
-        let rule = factory::build_rule(&toml_rule.rule_type, &rule_config)
-            .map_err(ScanError::RuleFactory)?;
-
-        if toml_rule.rule_type == "ratchet" {
-            if let Some(max) = toml_rule.max_count {
-                ratchet_thresholds.insert(rule.id().to_string(), max);
-            }
-        }
+/// Most entries [`RULE_CACHE`] and [`GLOB_CACHE`] hold; a full cache is
+/// emptied before the next insert, so repeated config edits in long-running
+/// modes can't grow it without bound.
+const COMPILE_CACHE_CAPACITY: usize = 1024;
 
-        let inclusion_pattern = rule.file_glob().map(|s| s.to_string());
-        let exclusion_patterns = toml_rule.exclude_glob.clone();
+/// A compiled preset rule and the config it was built from.
+struct CachedRule {
+    rule_type: String,
+    config: RuleConfig,
+    rule: Arc<dyn Rule>,
+}
 
-        intermediates.push(IntermediateRule {
-            rule,
-            inclusion_pattern,
-            exclusion_patterns,
-            file_contains: toml_rule.file_contains.clone(),
-            file_not_contains: toml_rule.file_not_contains.clone(),
-        });
+/// Compiled preset rules keyed by origin (preset, and scope for scoped
+/// presets) and rule id. Long-running modes (MCP, repeated stdin scans)
+/// resolve the same presets on every call, so their regexes are compiled
+/// once per process instead of once per scan. An entry is reused only while
+/// the rule's type and config are unchanged, e.g. not overridden by the user.
+static RULE_CACHE: OnceLock<Mutex<HashMap<(RuleOrigin, String), CachedRule>>> = OnceLock::new();
+
+/// Compiled glob sets keyed by their (NUL-joined) source patterns.
+static GLOB_CACHE: OnceLock<Mutex<HashMap<String, GlobSet>>> = OnceLock::new();
+
+/// Build a rule through the factory, reusing the instance compiled for the
+/// same preset rule earlier in this process. User and plugin rules, and rules
+/// that keep per-scan state (see [`Rule::shareable`]), are built fresh every
+/// time. Returns the rule and whether it came from the cache.
+fn build_rule_cached(toml_rule: &TomlRule, config: &RuleConfig) -> Result<(Arc<dyn Rule>, bool), ScanError> {
+    let build = || -> Result<Arc<dyn Rule>, ScanError> {
+        Ok(Arc::from(
+            factory::build_rule(&toml_rule.rule_type, config).map_err(ScanError::RuleFactory)?,
+        ))
+    };
+    if !matches!(toml_rule.origin, RuleOrigin::Preset(_) | RuleOrigin::ScopedPreset { .. }) {
+        return Ok((build()?, false));
     }
 
-    // Group rules by (inclusion_pattern, exclusion_patterns) to avoid redundant glob matching.
-    let mut groups: Vec<((Option<String>, Vec<String>), Vec<IntermediateRule>)> = Vec::new();
+    let key = (toml_rule.origin.clone(), toml_rule.id.clone());
+    let cache = RULE_CACHE.get_or_init(Default::default);
+    if let Some(entry) = cache.lock().unwrap().get(&key) {
+        if entry.rule_type == toml_rule.rule_type && entry.config == *config {
+            return Ok((Arc::clone(&entry.rule), true));
+        }
+    }
 
-    for ir in intermediates {
-        let key = (ir.inclusion_pattern.clone(), ir.exclusion_patterns.clone());
-        if let Some(group) = groups.iter_mut().find(|(k, _)| *k == key) {
-            group.1.push(ir);
-        } else {
-            groups.push((key, vec![ir]));
+    let rule = build()?;
+    if rule.shareable() {
+        let mut cache = cache.lock().unwrap();
+        if cache.len() >= COMPILE_CACHE_CAPACITY && !cache.contains_key(&key) {
+            cache.clear();
         }
+        cache.insert(
+            key,
+            CachedRule {
+                rule_type: toml_rule.rule_type.clone(),
+                config: config.clone(),
+                rule: Arc::clone(&rule),
+            },
+        );
     }
+    Ok((rule, false))
+}
 
-    // Build RuleGroups with compiled GlobSets (once per unique pattern)
-    let mut rule_groups: Vec<RuleGroup> = Vec::new();
-    for ((inc_pattern, exc_patterns), intermediates) in groups {
-        let inclusion_glob = if let Some(ref pattern) = inc_pattern {
-            Some(build_glob_set_from_pattern(pattern)?)
-        } else {
-            None
-        };
+/// A parsed config with plugin rules loaded and presets resolved.
+pub(crate) struct LoadedConfig {
+    pub toml: TomlConfig,
+    /// Final rule list: presets merged with user + plugin rules, then scoped presets.
+    pub rules: Vec<TomlRule>,
+    /// Rules turned off with `enabled = false`, in resolution order.
+    pub disabled: Vec<TomlRule>,
+}
 
-        let exclusion_glob = if !exc_patterns.is_empty() {
-            Some(build_glob_set(&exc_patterns)?)
-        } else {
-            None
-        };
+/// Read a config file, load its plugins (in parallel), and resolve presets.
+pub(crate) fn load_config(config_path: &Path) -> Result<LoadedConfig, ScanError> {
+    // 1. Read and parse TOML config
+    let config_text = fs::read_to_string(config_path).map_err(ScanError::ConfigRead)?;
+    check_required_version(config_path, &config_text)?;
+    let mut toml_config: TomlConfig =
+        toml::from_str(&config_text).map_err(ScanError::ConfigParse)?;
+    tracing::debug!("loaded config {}", config_path.display());
+    merge_includes(config_path, &mut toml_config)?;
+
+    // 2. Load plugin rules from external TOML files (order is preserved)
+    let plugin_rules: Vec<Vec<TomlRule>> = toml_config
+        .baseline
+        .plugins
+        .par_iter()
+        .map(|plugin_path| {
+            let plugin_text = fs::read_to_string(plugin_path).map_err(ScanError::ConfigRead)?;
+            let plugin_config: PluginConfig =
+                toml::from_str(&plugin_text).map_err(ScanError::ConfigParse)?;
+            tracing::debug!(
+                "loaded {} rule(s) from plugin {}",
+                plugin_config.rule.len(),
+                plugin_path
+            );
+            let origin = RuleOrigin::Plugin(PathBuf::from(plugin_path));
+            Ok(plugin_config
+                .rule
+                .into_iter()
+                .map(|rule| TomlRule { origin: origin.clone(), ..rule })
+                .collect())
+        })
+        .collect::<Result<_, ScanError>>()?;
 
-        let rules = intermediates
-            .into_iter()
-            .map(|ir| {
-                let id = ir.rule.id().to_string();
-                RuleWithConditioning {
-                    rule: ir.rule,
-                    file_contains: ir.file_contains,
-                    file_not_contains: ir.file_not_contains,
-                    allow_marker: format!("baseline:allow-{}", id),
-                    allow_next_line: format!("baseline:allow-next-line {}", id),
-                }
-            })
-            .collect();
+    // 3. Resolve presets and merge with user-defined rules + plugin rules
+    let mut all_user_rules = toml_config.rule.clone();
+    all_user_rules.extend(plugin_rules.into_iter().flatten());
 
-        rule_groups.push(RuleGroup {
-            inclusion_glob,
-            exclusion_glob,
-            rules,
-        });
+    let mut resolved_rules = presets::resolve_rules(
+        &toml_config.baseline.extends,
+        &all_user_rules,
+    )
+    .map_err(ScanError::Preset)?;
+
+    for conflict in presets::find_conflicts(
+        &toml_config.baseline.extends,
+        &toml_config.baseline.scoped,
+        &all_user_rules,
+    ) {
+        tracing::warn!("{}: {}", config_path.display(), conflict);
     }
 
-    Ok(BuiltRules {
+    // 3b. Resolve scoped presets and append
+    let scoped_rules = presets::resolve_scoped_rules(
+        &toml_config.baseline.scoped,
+        &all_user_rules,
+    )
+    .map_err(ScanError::Preset)?;
+    resolved_rules.extend(scoped_rules);
+    for rule in &mut resolved_rules {
+        rule.rule_type = factory::canonical_rule_type(&rule.rule_type).to_string();
+    }
+
+    // 3c. Drop disabled rules
+    let (rules, disabled): (Vec<TomlRule>, Vec<TomlRule>) =
+        resolved_rules.into_iter().partition(|r| r.enabled);
+    tracing::debug!("resolved {} rule(s), {} disabled", rules.len(), disabled.len());
+
+    Ok(LoadedConfig {
+        toml: toml_config,
+        rules,
+        disabled,
+    })
+}
+
+/// Keep only the rules listed in the named `[groups.*]`. A rule in several
+/// selected groups runs once, and a disabled rule stays disabled.
+fn select_groups(loaded: &mut LoadedConfig, groups: &[String]) -> Result<(), ScanError> {
+    if groups.is_empty() {
+        return Ok(());
+    }
+    let mut selected: HashSet<&str> = HashSet::new();
+    for name in groups {
+        let group = loaded
+            .toml
+            .groups
+            .get(name)
+            .ok_or_else(|| ScanError::UnknownGroup(name.clone()))?;
+        for id in &group.rules {
+            if !loaded.rules.iter().chain(&loaded.disabled).any(|r| &r.id == id) {
+                return Err(ScanError::UnknownGroupRule {
+                    group: name.clone(),
+                    rule: id.clone(),
+                });
+            }
+            selected.insert(id);
+        }
+    }
+    loaded.rules.retain(|r| selected.contains(r.id.as_str()));
+    tracing::debug!("group(s) {} select {} rule(s)", groups.join(", "), loaded.rules.len());
+    Ok(())
+}
+
+/// Every resolved rule for a config, enabled ones first, with disabled rules
+/// kept (and marked `enabled = false`) so they can be listed.
+pub fn list_rules(config_path: &Path) -> Result<Vec<TomlRule>, ScanError> {
+    let loaded = load_config(config_path)?;
+    let mut rules = loaded.rules;
+    rules.extend(loaded.disabled);
+    Ok(rules)
+}
+
+/// What `check-config` found in a config.
+#[derive(Debug)]
+pub struct ConfigCheck {
+    pub rules_loaded: usize,
+    /// Files the walk collected, before any rule's glob is applied.
+    pub files: usize,
+    pub unmatched_globs: Vec<UnmatchedGlob>,
+}
+
+/// Build every rule in a config and walk `target_paths` as a scan would,
+/// without reading any file, to find rules whose glob matches nothing.
+pub fn check_config(config_path: &Path, target_paths: &[PathBuf]) -> Result<ConfigCheck, ScanError> {
+    let loaded = load_config(config_path)?;
+    let built = build_rules(&loaded.rules)?;
+    let excludes = loaded.toml.baseline.excludes();
+    let walk_policy = WalkPolicy {
+        pruned_dirs: pruned_dirs(&excludes),
+        hidden_dirs: built.hidden_dirs.clone(),
+        ..WalkPolicy::from_section(&loaded.toml.baseline, config_path)
+    };
+    let files = walk_files(target_paths, &build_glob_set(&excludes)?, &walk_policy).files;
+    Ok(ConfigCheck {
+        rules_loaded: built.rule_groups.iter().map(|g| g.rules.len()).sum(),
+        files: files.len(),
+        unmatched_globs: unmatched_globs(&built.rule_groups, &files),
+    })
+}
+
+/// Fail when `[baseline] required_version` excludes this binary. Runs before
+/// the config is parsed in full, so a config written for a newer release
+/// reports the version it needs rather than a field this one doesn't know.
+fn check_required_version(config_path: &Path, config_text: &str) -> Result<(), ScanError> {
+    // Syntax errors are left to the full parse, which reports them properly
+    let Ok(table) = config_text.parse::<toml::Table>() else {
+        return Ok(());
+    };
+    let Some(text) = table
+        .get("baseline")
+        .and_then(|b| b.get("required_version"))
+        .and_then(|v| v.as_str())
+    else {
+        return Ok(());
+    };
+    let required = VersionReq::parse(text).map_err(ScanError::RequiredVersion)?;
+    if !required.matches(&Version::current()) {
+        return Err(ScanError::UnsupportedVersion {
+            config: config_path.to_path_buf(),
+            required,
+        });
+    }
+    Ok(())
+}
+
+/// Merge the files named by the config's `include` globs into it. Patterns
+/// are resolved relative to the config file and processed in order; files
+/// matched by one pattern are merged in path order, and a file matched twice
+/// is merged once. Rules and list settings are appended after the root's.
+fn merge_includes(config_path: &Path, config: &mut TomlConfig) -> Result<(), ScanError> {
+    let config_dir = config_path.parent().unwrap_or(Path::new(""));
+    let mut seen: Vec<PathBuf> = Vec::new();
+
+    for pattern in &config.include {
+        for path in expand_include(config_dir, pattern)? {
+            if seen.contains(&path) {
+                continue;
+            }
+            let text = fs::read_to_string(&path)
+                .map_err(|e| ScanError::IncludeRead(path.clone(), e))?;
+            let included: IncludedConfig =
+                toml::from_str(&text).map_err(|e| ScanError::IncludeParse(path.clone(), e))?;
+            tracing::debug!(
+                "merged {} rule(s) from included config {}",
+                included.rule.len(),
+                path.display()
+            );
+
+            let section = &mut config.baseline;
+            section.exclude_extend.extend(included.baseline.exclude);
+            section.exclude_extend.extend(included.baseline.exclude_extend);
+            section.extends.extend(included.baseline.extends);
+            section.plugins.extend(included.baseline.plugins);
+            section.scoped.extend(included.baseline.scoped);
+            config.rule.extend(included.rule);
+            seen.push(path);
+        }
+    }
+    Ok(())
+}
+
+/// Files matching one `include` pattern, sorted. A pattern without glob
+/// characters names a single file, which must exist.
+fn expand_include(config_dir: &Path, pattern: &str) -> Result<Vec<PathBuf>, ScanError> {
+    let is_glob = |s: &str| s.contains(['*', '?', '[', '{']);
+    if !is_glob(pattern) {
+        return Ok(vec![config_dir.join(pattern)]);
+    }
+
+    // Walk from the longest literal prefix and match the rest
+    let parts: Vec<&str> = pattern.split('/').collect();
+    let literal = parts.iter().take_while(|p| !is_glob(p)).count();
+    let base = config_dir.join(parts[..literal].join("/"));
+    let matcher = globset::GlobBuilder::new(&parts[literal..].join("/"))
+        .literal_separator(true)
+        .build()
+        .and_then(|g| {
+            let mut builder = GlobSetBuilder::new();
+            builder.add(g);
+            builder.build()
+        })
+        .map_err(ScanError::GlobParse)?;
+
+    let walk_root = if base.as_os_str().is_empty() { Path::new(".") } else { &base };
+    let mut matches: Vec<PathBuf> = WalkBuilder::new(walk_root)
+        .standard_filters(false)
+        .build()
+        .flatten()
+        .filter(|e| e.file_type().is_some_and(|t| t.is_file()))
+        .filter(|e| {
+            e.path()
+                .strip_prefix(walk_root)
+                .is_ok_and(|rel| matcher.is_match(rel))
+        })
+        .map(|e| e.into_path())
+        .collect();
+    matches.sort();
+    Ok(matches)
+}
+
+/// Per-directory budgets for one ratchet rule.
+struct ScopedBudget {
+    /// `(scope path, budget)`, longest scope first so nested scopes win.
+    scopes: Vec<(String, usize)>,
+    /// Budget for matches outside every scope (the rule's `max_count`).
+    rest: usize,
+}
+
+/// Key under which a scoped ratchet budget is reported in `ratchet_counts`.
+pub fn ratchet_scope_key(rule_id: &str, scope: &str) -> String {
+    format!("{}:{}", rule_id, scope)
+}
+
+/// Split a `ratchet_counts` key into rule id and optional scope.
+pub fn split_ratchet_key(key: &str) -> (&str, Option<&str>) {
+    match key.split_once(':') {
+        Some((id, scope)) => (id, Some(scope)),
+        None => (key, None),
+    }
+}
+
+/// Whether `file` lies under `scope`, a directory relative to the scan root.
+fn file_in_scope(file: &Path, scope: &Path) -> bool {
+    let relative = if file.is_absolute() {
+        std::env::current_dir()
+            .ok()
+            .and_then(|cwd| file.strip_prefix(cwd).ok().map(Path::to_path_buf))
+            .unwrap_or_else(|| file.to_path_buf())
+    } else {
+        file.to_path_buf()
+    };
+    let relative = relative.strip_prefix(".").unwrap_or(&relative);
+    relative.starts_with(scope)
+}
+
+/// The deadline of a ratchet with `deadline`, as seen on `today`.
+fn ratchet_deadline(toml_rule: &TomlRule, today: i64) -> Result<Option<RatchetDeadline>, ScanError> {
+    let invalid = |field: &'static str, reason: &str| {
+        ScanError::RuleFactory(RuleBuildError::InvalidValue(toml_rule.id.clone(), field, reason.to_string()).into())
+    };
+    let Some(date) = toml_rule.deadline.as_deref() else {
+        return match toml_rule.target {
+            Some(_) => Err(invalid("target", "only applies with a deadline")),
+            None => Ok(None),
+        };
+    };
+    RatchetDeadline::new(date, toml_rule.target.unwrap_or(0), today)
+        .map(Some)
+        .ok_or_else(|| invalid("deadline", "expected a YYYY-MM-DD date"))
+}
+
+/// Ratchets with `track`, keyed by the id of the rule they budget, as the
+/// ratchet's id and severity.
+fn ratchet_trackers(resolved_rules: &[TomlRule]) -> Result<HashMap<&str, (String, Severity)>, ScanError> {
+    let mut trackers = HashMap::new();
+    for ratchet in resolved_rules.iter().filter(|r| r.rule_type == "ratchet") {
+        let Some(track) = ratchet.track.as_deref() else {
+            continue;
+        };
+        let invalid = |reason: String| {
+            ScanError::RuleFactory(RuleBuildError::InvalidValue(ratchet.id.clone(), "track", reason).into())
+        };
+        if ratchet.max_count.is_none() {
+            return Err(ScanError::RuleFactory(
+                RuleBuildError::MissingField(ratchet.id.clone(), "max_count").into(),
+            ));
+        }
+        let tracked = resolved_rules
+            .iter()
+            .find(|r| r.id == track)
+            .ok_or_else(|| invalid(format!("no enabled rule has id '{}'", track)))?;
+        if matches!(
+            tracked.rule_type.as_str(),
+            "ratchet" | "file-presence" | "expo-router-structure" | "env-allowlist"
+        ) {
+            return Err(invalid(format!(
+                "'{}' is a {} rule, which a ratchet can't track",
+                track, tracked.rule_type
+            )));
+        }
+        let severity = ratchet.to_rule_config().severity;
+        if trackers.insert(track, (ratchet.id.clone(), severity)).is_some() {
+            return Err(invalid(format!("'{}' is already tracked by another ratchet", track)));
+        }
+    }
+    Ok(trackers)
+}
+
+/// Build rules from resolved TOML rules. Shared by run_scan and run_scan_stdin.
+fn build_rules(resolved_rules: &[TomlRule]) -> Result<BuiltRules, ScanError> {
+    let mut ratchet_thresholds: HashMap<String, usize> = HashMap::new();
+    let mut ratchet_budgets: HashMap<String, ScopedBudget> = HashMap::new();
+    let mut ratchet_deadlines: HashMap<String, RatchetDeadline> = HashMap::new();
+    let today = deadline::today();
+    let mut file_presence_rules: Vec<FilePresenceRule> = Vec::new();
+    let mut expo_router_rules: Vec<ExpoRouterRule> = Vec::new();
+    let mut env_allowlist_rules: Vec<Arc<EnvAllowlistRule>> = Vec::new();
+    let mut ratchet_no_new: HashSet<String> = HashSet::new();
+    let mut cache_hits = 0;
+
+    // Intermediate representation before grouping
+    struct IntermediateRule {
+        rule: Arc<dyn Rule>,
+        inclusion_pattern: Option<String>,
+        exclusion_patterns: Vec<String>,
+        file_contains: Option<String>,
+        file_not_contains: Option<String>,
+        sibling_files: Vec<String>,
+        report_as: Option<(String, Severity)>,
+    }
+
+    let trackers = ratchet_trackers(resolved_rules)?;
+    let mut intermediates: Vec<IntermediateRule> = Vec::new();
+
+    for toml_rule in resolved_rules {
+        let rule_config = toml_rule.to_rule_config();
+
+        // File-presence rules are handled separately (they check existence, not content)
+        if toml_rule.rule_type == "file-presence" {
+            if let Ok(fp_rule) = FilePresenceRule::new(&rule_config) {
+                file_presence_rules.push(fp_rule);
+            }
+            continue;
+        }
+        if toml_rule.rule_type == "expo-router-structure" {
+            let rule = ExpoRouterRule::new(&rule_config)
+                .map_err(|e| ScanError::RuleFactory(e.into()))?;
+            expo_router_rules.push(rule);
+            continue;
+        }
+
+        if toml_rule.rule_type == "ratchet" {
+            let deadline = ratchet_deadline(toml_rule, today)?;
+            if let Some(max) = toml_rule.max_count {
+                let max = deadline.as_ref().map_or(max, |d| d.max_count(max));
+                if toml_rule.budgets.is_empty() {
+                    ratchet_thresholds.insert(toml_rule.id.clone(), max);
+                } else {
+                    let mut scopes: Vec<(String, usize)> = toml_rule
+                        .budgets
+                        .iter()
+                        .map(|(scope, &budget)| {
+                            let scope = scope.trim_start_matches("./").trim_end_matches('/');
+                            let budget = deadline.as_ref().map_or(budget, |d| d.max_count(budget));
+                            (scope.to_string(), budget)
+                        })
+                        .collect();
+                    scopes.sort_by_key(|(scope, _)| {
+                        std::cmp::Reverse(Path::new(scope).components().count())
+                    });
+                    ratchet_budgets.insert(
+                        toml_rule.id.clone(),
+                        ScopedBudget { scopes, rest: max },
+                    );
+                }
+            }
+            if let Some(deadline) = deadline {
+                ratchet_deadlines.insert(toml_rule.id.clone(), deadline);
+            }
+            if toml_rule.ratchet_mode == Some(RatchetMode::NoNew) {
+                ratchet_no_new.insert(toml_rule.id.clone());
+            }
+            // A tracking ratchet has no matcher of its own; the tracked
+            // rule reports under its id instead
+            if toml_rule.track.is_some() {
+                continue;
+            }
+        }
+
+        // Env allowlist rules record what each scan reads, so they aren't shared
+        let rule: Arc<dyn Rule> = if toml_rule.rule_type == "env-allowlist" {
+            let rule = Arc::new(
+                EnvAllowlistRule::new(&rule_config).map_err(|e| ScanError::RuleFactory(e.into()))?,
+            );
+            env_allowlist_rules.push(Arc::clone(&rule));
+            rule
+        } else {
+            let (rule, cached) = build_rule_cached(toml_rule, &rule_config)?;
+            if cached {
+                cache_hits += 1;
+            }
+            rule
+        };
+
+        let inclusion_pattern = rule.file_glob().map(|s| s.to_string());
+        let exclusion_patterns = toml_rule.exclude_glob.clone();
+
+        intermediates.push(IntermediateRule {
+            rule,
+            inclusion_pattern,
+            exclusion_patterns,
+            file_contains: toml_rule.file_contains.clone(),
+            file_not_contains: toml_rule.file_not_contains.clone(),
+            sibling_files: toml_rule.sibling_files.clone(),
+            report_as: trackers.get(toml_rule.id.as_str()).cloned(),
+        });
+    }
+
+    // Group rules by (inclusion_pattern, exclusion_patterns, file_contains) to
+    // avoid redundant glob matching and containment checks.
+    type GroupKey = (Option<String>, Vec<String>, Option<String>);
+    let mut groups: Vec<(GroupKey, Vec<IntermediateRule>)> = Vec::new();
+
+    for ir in intermediates {
+        let key = (ir.inclusion_pattern.clone(), ir.exclusion_patterns.clone(), ir.file_contains.clone());
+        if let Some(group) = groups.iter_mut().find(|(k, _)| *k == key) {
+            group.1.push(ir);
+        } else {
+            groups.push((key, vec![ir]));
+        }
+    }
+
+    // Build RuleGroups with compiled GlobSets (once per unique pattern)
+    let mut rule_groups: Vec<RuleGroup> = Vec::new();
+    let mut walked_hidden_dirs: Vec<String> = Vec::new();
+    for ((inc_pattern, exc_patterns, file_contains), intermediates) in groups {
+        let inclusion_glob = if let Some(ref pattern) = inc_pattern {
+            for dir in hidden_dirs(pattern) {
+                if !walked_hidden_dirs.contains(&dir) {
+                    walked_hidden_dirs.push(dir);
+                }
+            }
+            Some(build_glob_set_from_pattern(pattern)?)
+        } else {
+            None
+        };
+
+        let exclusion_glob = if !exc_patterns.is_empty() {
+            Some(build_glob_set(&exc_patterns)?)
+        } else {
+            None
+        };
+
+        let rules = intermediates
+            .into_iter()
+            .map(|ir| {
+                let id = ir.rule.id().to_string();
+                RuleWithConditioning {
+                    rule: ir.rule,
+                    file_not_contains: ir.file_not_contains,
+                    sibling_files: ir.sibling_files,
+                    report_as: ir.report_as,
+                    allow_marker: format!("baseline:allow-{}", id),
+                    allow_next_line: format!("baseline:allow-next-line {}", id),
+                }
+            })
+            .collect();
+
+        rule_groups.push(RuleGroup {
+            inclusion_pattern: inc_pattern,
+            exclusion_patterns: exc_patterns,
+            inclusion_glob,
+            exclusion_glob,
+            file_contains,
+            rules,
+            files_matched: AtomicUsize::new(0),
+            files_checked: AtomicUsize::new(0),
+        });
+    }
+
+    Ok(BuiltRules {
         rule_groups,
         ratchet_thresholds,
+        ratchet_budgets,
+        ratchet_deadlines,
         file_presence_rules,
+        expo_router_rules,
+        env_allowlist_rules,
+        cache_hits,
+        hidden_dirs: walked_hidden_dirs,
+        ratchet_no_new,
     })
 }
 
+/// Rules in `rule_groups` whose inclusion glob matches none of `files`.
+/// Exclusions are ignored: a glob that only ever matches excluded files is
+/// doing its job.
+fn unmatched_globs(rule_groups: &[RuleGroup], files: &[PathBuf]) -> Vec<UnmatchedGlob> {
+    let mut unmatched = Vec::new();
+    for group in rule_groups {
+        let (Some(pattern), Some(glob)) = (&group.inclusion_pattern, &group.inclusion_glob) else {
+            continue;
+        };
+        let matched = files.iter().any(|file| {
+            glob.is_match(file) || file.file_name().is_some_and(|name| glob.is_match(name))
+        });
+        if !matched {
+            unmatched.extend(group.rules.iter().map(|r| UnmatchedGlob {
+                rule_id: r.rule.id().to_string(),
+                glob: pattern.clone(),
+            }));
+        }
+    }
+    unmatched
+}
+
 /// Check if a rule group matches a file path (inclusion + exclusion globs).
 fn group_matches_file(group: &RuleGroup, file_str: &str, file_name: &str) -> bool {
     let included = match &group.inclusion_glob {
@@ -228,20 +1383,26 @@ fn group_matches_file(group: &RuleGroup, file_str: &str, file_name: &str) -> boo
     true
 }
 
-/// Check file-context conditioning (file_contains / file_not_contains) with caching.
-fn passes_file_conditioning_cached<'a>(
-    rule: &'a RuleWithConditioning,
+/// Check a group's `file_contains` precondition, with caching.
+fn passes_group_conditioning_cached<'a>(
+    group: &'a RuleGroup,
     content: &str,
     cache: &mut HashMap<&'a str, bool>,
 ) -> bool {
-    if let Some(ref needle) = rule.file_contains {
-        let &mut result = cache
+    match group.file_contains {
+        Some(ref needle) => *cache
             .entry(needle.as_str())
-            .or_insert_with(|| content.contains(needle.as_str()));
-        if !result {
-            return false;
-        }
+            .or_insert_with(|| content.contains(needle.as_str())),
+        None => true,
     }
+}
+
+/// Check a rule's `file_not_contains` conditioning, with caching.
+fn passes_file_conditioning_cached<'a>(
+    rule: &'a RuleWithConditioning,
+    content: &str,
+    cache: &mut HashMap<&'a str, bool>,
+) -> bool {
     if let Some(ref needle) = rule.file_not_contains {
         let &mut result = cache
             .entry(needle.as_str())
@@ -253,6 +1414,16 @@ fn passes_file_conditioning_cached<'a>(
     true
 }
 
+/// Check `sibling_files` conditioning: at least one listed file must exist in
+/// the scanned file's directory.
+fn passes_sibling_conditioning(rule: &RuleWithConditioning, file_path: &Path) -> bool {
+    if rule.sibling_files.is_empty() {
+        return true;
+    }
+    let dir = file_path.parent().unwrap_or(Path::new(""));
+    rule.sibling_files.iter().any(|name| dir.join(name).is_file())
+}
+
 /// Run rules against content and collect violations, filtering escape-hatch comments.
 fn run_rules_on_content(
     rule_groups: &[RuleGroup],
@@ -260,37 +1431,78 @@ fn run_rules_on_content(
     content: &str,
     file_str: &str,
     file_name: &str,
+    lookback: usize,
+) -> Vec<Violation> {
+    let mut used = HashSet::new();
+    let ctx = ScanContext::new(file_path, content);
+    run_rules_tracking_suppressions(rule_groups, &ctx, file_str, file_name, lookback, &mut used)
+}
+
+/// [`run_rules_on_content`], also recording in `used` the 1-based lines of
+/// the escape-hatch comments that suppressed something.
+fn run_rules_tracking_suppressions(
+    rule_groups: &[RuleGroup],
+    ctx: &ScanContext,
+    file_str: &str,
+    file_name: &str,
+    lookback: usize,
+    used: &mut HashSet<usize>,
 ) -> Vec<Violation> {
     let mut violations = Vec::new();
-    let content_lines: Vec<&str> = content.lines().collect();
-    let ctx = ScanContext {
-        file_path,
-        content,
-    };
+    let (file_path, content) = (ctx.file_path, ctx.content);
+    let content_lines = ctx.lines();
     let mut conditioning_cache: HashMap<&str, bool> = HashMap::new();
+    let regions = if content.contains("baseline:ignore-start") || content.contains("@generated") {
+        ignore_regions(content_lines)
+    } else {
+        Vec::new()
+    };
 
     for group in rule_groups {
         if !group_matches_file(group, file_str, file_name) {
             continue;
         }
+        group.files_matched.fetch_add(1, Ordering::Relaxed);
+        if !passes_group_conditioning_cached(group, content, &mut conditioning_cache) {
+            continue;
+        }
+        group.files_checked.fetch_add(1, Ordering::Relaxed);
 
         for rule_cond in &group.rules {
-            if !passes_file_conditioning_cached(rule_cond, content, &mut conditioning_cache) {
+            if !passes_file_conditioning_cached(rule_cond, content, &mut conditioning_cache)
+                || !passes_sibling_conditioning(rule_cond, file_path)
+            {
                 continue;
             }
 
-            let file_violations = rule_cond.rule.check_file(&ctx);
-            for v in file_violations {
+            let file_violations = match check_file_guarded(rule_cond.rule.as_ref(), ctx) {
+                Ok(file_violations) => file_violations,
+                Err(reason) => {
+                    violations.push(internal_error(rule_cond.rule.id(), file_path, &reason));
+                    continue;
+                }
+            };
+            for mut v in file_violations {
                 if let Some(line_num) = v.line {
-                    if is_suppressed(
-                        &content_lines,
+                    if let Some(region) = regions.iter().find(|r| r.covers(line_num, rule_cond.rule.id())) {
+                        used.insert(region.start);
+                        continue;
+                    }
+                    if let Some(directive) = suppressed_by(
+                        content_lines,
                         line_num,
                         &rule_cond.allow_marker,
                         &rule_cond.allow_next_line,
+                        lookback,
                     ) {
+                        used.insert(directive);
                         continue;
                     }
                 }
+                if let Some((ratchet_id, severity)) = &rule_cond.report_as {
+                    v.rule_id = ratchet_id.clone();
+                    v.severity = *severity;
+                }
                 violations.push(v);
             }
         }
@@ -299,108 +1511,456 @@ fn run_rules_on_content(
     violations
 }
 
-/// Run a full scan: parse config, build rules, walk files, collect violations.
-pub fn run_scan(config_path: &Path, target_paths: &[PathBuf]) -> Result<ScanResult, ScanError> {
-    // 1. Read and parse TOML config
-    let config_text = fs::read_to_string(config_path).map_err(ScanError::ConfigRead)?;
-    let toml_config: TomlConfig = toml::from_str(&config_text).map_err(ScanError::ConfigParse)?;
+/// Run one rule on a file, catching a panic in the rule (a plugin bug, a
+/// pathological input) so the other rules and files are still scanned. The
+/// error is the panic message.
+fn check_file_guarded(rule: &dyn Rule, ctx: &ScanContext) -> Result<Vec<Violation>, String> {
+    panic::catch_unwind(AssertUnwindSafe(|| rule.check_file(ctx))).map_err(|payload| {
+        let reason = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "unknown panic".to_string());
+        tracing::error!("rule '{}' panicked on {}: {}", rule.id(), ctx.file_path.display(), reason);
+        reason
+    })
+}
 
-    // 2. Load plugin rules from external TOML files
-    let mut plugin_rules: Vec<crate::cli::toml_config::TomlRule> = Vec::new();
-    for plugin_path in &toml_config.baseline.plugins {
-        let plugin_text = fs::read_to_string(plugin_path).map_err(ScanError::ConfigRead)?;
-        let plugin_config: PluginConfig =
-            toml::from_str(&plugin_text).map_err(ScanError::ConfigParse)?;
-        plugin_rules.extend(plugin_config.rule);
+/// The [`INTERNAL_ERROR`] violation reported when `rule_id` panicked on `file`.
+fn internal_error(rule_id: &str, file: &Path, reason: &str) -> Violation {
+    Violation {
+        rule_id: INTERNAL_ERROR.to_string(),
+        severity: Severity::Error,
+        file: file.to_path_buf(),
+        line: None,
+        column: None,
+        message: format!("rule '{}' panicked and was skipped for this file: {}", rule_id, reason),
+        suggest: vec![crate::rules::Suggestion::new(
+            "report this as a bug; the rest of the scan completed, and the exit code is 3",
+        )],
+        source_line: None,
+        fix: None,
+        origin: None,
+        enclosing: None,
     }
+}
 
-    // 3. Resolve presets and merge with user-defined rules + plugin rules
-    let mut all_user_rules = toml_config.rule.clone();
-    all_user_rules.extend(plugin_rules);
+/// Fill in each violation's enclosing function or component. The file is
+/// parsed only when it is JS/TS and some violation has a line.
+fn annotate_enclosing<'a>(violations: impl Iterator<Item = &'a mut Violation>, file_path: &Path, content: &str) {
+    let mut violations = violations.filter(|v| v.line.is_some()).peekable();
+    if violations.peek().is_none() || ast::detect_language(file_path).is_none() {
+        return;
+    }
+    let Some(tree) = ast::parse_file(file_path, content) else {
+        return;
+    };
+    for v in violations {
+        let row = v.line.unwrap_or(1).saturating_sub(1);
+        let column = v.column.unwrap_or(1).saturating_sub(1);
+        v.enclosing = ast::enclosing_function(&tree, content.as_bytes(), row, column);
+    }
+}
 
-    let mut resolved_rules = presets::resolve_rules(
-        &toml_config.baseline.extends,
-        &all_user_rules,
-    )
-    .map_err(ScanError::Preset)?;
+/// Run a full scan: parse config, build rules, walk files, collect violations.
+pub fn run_scan(config_path: &Path, target_paths: &[PathBuf]) -> Result<ScanResult, ScanError> {
+    run_scan_with_options(config_path, target_paths, &ScanOptions::default())
+}
 
-    // 3b. Resolve scoped presets and append
-    let scoped_rules = presets::resolve_scoped_rules(
-        &toml_config.baseline.scoped,
-        &all_user_rules,
-    )
-    .map_err(ScanError::Preset)?;
-    resolved_rules.extend(scoped_rules);
+/// Run a full scan with explicit CLI options.
+pub fn run_scan_with_options(
+    config_path: &Path,
+    target_paths: &[PathBuf],
+    options: &ScanOptions,
+) -> Result<ScanResult, ScanError> {
+    run_scan_configs(std::slice::from_ref(&config_path.to_path_buf()), target_paths, options)
+}
 
-    // 4. Build exclude glob set
-    let exclude_set = build_glob_set(&toml_config.baseline.exclude)?;
+/// A scanned file: its path, content hash, and violations tagged with the
+/// index of the config that produced them.
+type FileScan = (PathBuf, u64, Vec<(usize, Violation)>);
+
+/// One config's compiled rules and file filters within a scan.
+struct ConfigRun<'a> {
+    path: &'a Path,
+    built: BuiltRules,
+    exclude_set: GlobSet,
+    minified: MinifiedFilter,
+    generated: GeneratedFilter,
+    lookback: usize,
+}
 
-    // 5. Build rules via factory
-    let built = build_rules(&resolved_rules)?;
-    let rules_loaded: usize = built.rule_groups.iter().map(|g| g.rules.len()).sum();
+/// Run a full scan that evaluates several configs in a single file walk
+/// (`--config a.toml --config b.toml`). Walk settings (symlinks, submodules,
+/// vendored directories) come from the first config; each config still
+/// applies its own excludes, minified-file handling, and ratchet budgets.
+/// With more than one config, every violation records its `origin`.
+pub fn run_scan_configs(
+    config_paths: &[PathBuf],
+    target_paths: &[PathBuf],
+    options: &ScanOptions,
+) -> Result<ScanResult, ScanError> {
+    let primary = config_paths.first().expect("at least one config path");
+    let multi = config_paths.len() > 1;
 
-    // 6. Walk target paths and collect files
-    let files = collect_files(target_paths, &exclude_set);
+    // 1-3. Read configs, load plugins, resolve presets
+    let started = Instant::now();
+    let mut loaded: Vec<LoadedConfig> = config_paths
+        .iter()
+        .map(|path| load_config(path))
+        .collect::<Result<_, _>>()?;
+    for config in &mut loaded {
+        select_groups(config, &options.groups)?;
+        options.catalog.localize_rules(&mut config.rules);
+    }
+    let config_load = started.elapsed();
 
-    // 7. Run rules on each file (parallel)
-    let files_scanned = AtomicUsize::new(0);
+    // 4. Build the walk: only excludes every config shares prune it
+    let excludes: Vec<Vec<String>> = loaded.iter().map(|l| l.toml.baseline.excludes()).collect();
+    let shared_excludes: Vec<String> = excludes[0]
+        .iter()
+        .filter(|p| excludes[1..].iter().all(|other| other.contains(p)))
+        .cloned()
+        .collect();
+    let walk_excludes = build_glob_set(&shared_excludes)?;
+    let mut walk_policy = WalkPolicy {
+        pruned_dirs: pruned_dirs(&shared_excludes),
+        max_depth: options.max_depth,
+        ..WalkPolicy::from_section(&loaded[0].toml.baseline, primary)
+    };
+
+    // 5. Build rules via factory, once per config
+    let started = Instant::now();
+    let mut runs: Vec<ConfigRun> = Vec::with_capacity(loaded.len());
+    for ((path, config), excludes) in config_paths.iter().zip(&loaded).zip(&excludes) {
+        let mut built = build_rules(&config.rules)?;
+        if options.changed.is_some() {
+            built.hold_to_diff();
+        }
+        runs.push(ConfigRun {
+            path,
+            built,
+            exclude_set: build_glob_set(excludes)?,
+            minified: MinifiedFilter::new(&config.toml.baseline, options)?,
+            generated: GeneratedFilter::new(&config.toml.baseline, options),
+            lookback: suppression_lookback(&config.toml.baseline),
+        });
+    }
+    let multiple = config_paths.len() > 1;
+    let mut rules: Vec<RuleMeta> = loaded
+        .iter()
+        .zip(config_paths)
+        .flat_map(|(l, path)| {
+            l.rules.iter().map(move |rule| RuleMeta {
+                config: multiple.then(|| path.clone()),
+                ..RuleMeta::from_config(rule, &l.toml.baseline)
+            })
+        })
+        .collect();
+    let rules_loaded: usize = runs
+        .iter()
+        .flat_map(|r| &r.built.rule_groups)
+        .map(|g| g.rules.len())
+        .sum();
+    let cache_hits: usize = runs.iter().map(|r| r.built.cache_hits).sum();
+    let rule_build = started.elapsed();
+    tracing::debug!(
+        "built {} rule(s) from {} config(s), {} from cache",
+        rules_loaded,
+        runs.len(),
+        cache_hits
+    );
 
-    let mut violations: Vec<Violation> = files
+    // 6. Walk target paths and collect files
+    let started = Instant::now();
+    for run in &runs {
+        for dir in &run.built.hidden_dirs {
+            if !walk_policy.hidden_dirs.contains(dir) {
+                walk_policy.hidden_dirs.push(dir.clone());
+            }
+        }
+    }
+    let walked = walk_files(target_paths, &walk_excludes, &walk_policy);
+    let files = walked.files;
+    tracing::debug!("collected {} file(s) to scan", files.len());
+    let unmatched_by_run: Vec<Vec<UnmatchedGlob>> = runs
+        .iter()
+        .map(|run| unmatched_globs(&run.built.rule_groups, &files))
+        .collect();
+    for u in unmatched_by_run.iter().flatten() {
+        tracing::debug!("{}", u);
+    }
+
+    // 7. Run every config's rules on each file (parallel). With --fail-fast,
+    // the first error stops workers from picking up further files.
+    let generated_skipped = AtomicUsize::new(0);
+    let unreadable: Mutex<Vec<Violation>> = Mutex::new(Vec::new());
+    let stop = AtomicBool::new(false);
+    let per_file: Vec<FileScan> = files
         .par_iter()
         .filter_map(|file_path| {
+            if stop.load(Ordering::Relaxed) {
+                return None;
+            }
             let file_str = file_path.to_string_lossy();
             let file_name = file_path.file_name().unwrap_or_default().to_string_lossy();
+            let relative = multi.then(|| relative_to_target(file_path, target_paths)).flatten();
 
             // Pre-check: does ANY rule group match this file? If not, skip the read entirely.
-            let any_match = built
-                .rule_groups
+            let applicable: Vec<(usize, &ConfigRun)> = runs
                 .iter()
-                .any(|g| group_matches_file(g, &file_str, &file_name));
-            if !any_match {
+                .enumerate()
+                .filter(|(_, run)| {
+                    relative.is_none_or(|rel| !run.exclude_set.is_match(rel))
+                        && run
+                            .built
+                            .rule_groups
+                            .iter()
+                            .any(|g| group_matches_file(g, &file_str, &file_name))
+                })
+                .collect();
+            if applicable.is_empty() {
                 return None;
             }
 
-            let content = fs::read_to_string(file_path).ok()?;
-            if is_likely_minified(&content) {
-                return None;
-            }
+            let content = match fs::read_to_string(file_path) {
+                Ok(c) => c,
+                Err(e) => {
+                    tracing::debug!("skipping unreadable file {}: {}", file_str, e);
+                    if options.strict {
+                        unreadable.lock().unwrap().push(Violation {
+                            rule_id: UNREADABLE_FILE.to_string(),
+                            severity: Severity::Error,
+                            file: file_path.clone(),
+                            line: None,
+                            column: None,
+                            message: format!("file could not be read: {}", e),
+                            suggest: Vec::new(),
+                            source_line: None,
+                            fix: None,
+                            origin: None,
+                            enclosing: None,
+                        });
+                    }
+                    return None;
+                }
+            };
 
-            files_scanned.fetch_add(1, Ordering::Relaxed);
-            let file_violations = run_rules_on_content(
-                &built.rule_groups,
-                file_path,
-                &content,
-                &file_str,
-                &file_name,
-            );
-            if file_violations.is_empty() {
-                None
-            } else {
-                Some(file_violations)
+            let ctx = ScanContext::new(file_path, &content)
+                .with_diff_status(options.changed.as_ref().and_then(|c| c.status(file_path)));
+            let mut scanned = false;
+            let mut used_suppressions = HashSet::new();
+            let mut generated = false;
+            let mut file_violations = Vec::new();
+            for (idx, run) in applicable {
+                if run.minified.should_skip(&file_str, &file_name, &content) {
+                    tracing::debug!("skipping minified file {}", file_str);
+                    continue;
+                }
+                if run.generated.should_skip(&content) {
+                    tracing::debug!("skipping generated file {}", file_str);
+                    generated = true;
+                    continue;
+                }
+                scanned = true;
+                if options.list_files {
+                    continue;
+                }
+                let violations = run_rules_tracking_suppressions(
+                    &run.built.rule_groups,
+                    &ctx,
+                    &file_str,
+                    &file_name,
+                    run.lookback,
+                    &mut used_suppressions,
+                );
+                if options.fail_fast && violations.iter().any(|v| fails_fast(v, &run.built)) {
+                    stop.store(true, Ordering::Relaxed);
+                }
+                file_violations.extend(violations.into_iter().map(|v| (idx, v)));
+            }
+            annotate_enclosing(file_violations.iter_mut().map(|(_, v)| v), file_path, &content);
+            if !scanned && generated {
+                generated_skipped.fetch_add(1, Ordering::Relaxed);
             }
+            if scanned && options.strict && content.contains("baseline:") {
+                let unused = unused_suppressions(file_path, ctx.lines(), &used_suppressions);
+                file_violations.extend(unused.into_iter().map(|v| (0, v)));
+            }
+            scanned.then(|| (file_path.clone(), content_hash(&content), file_violations))
         })
-        .flatten()
         .collect();
+    let stopped_early = stop.into_inner();
+    let mut generated_skipped = generated_skipped.into_inner();
+    let mut scanned_files = Vec::with_capacity(per_file.len());
+    let mut file_hashes = HashMap::with_capacity(per_file.len());
+    let mut by_config: Vec<Vec<Violation>> = vec![Vec::new(); runs.len()];
+    for (path, hash, file_violations) in per_file {
+        file_hashes.insert(path.clone(), hash);
+        scanned_files.push(path);
+        for (idx, v) in file_violations {
+            by_config[idx].push(v);
+        }
+    }
 
-    // 8. Run file-presence checks
-    for fp_rule in &built.file_presence_rules {
-        let mut fp_violations = fp_rule.check_paths(target_paths);
-        violations.append(&mut fp_violations);
+    // 8-9. Run file-presence checks and apply ratchet thresholds per config
+    let mut violations: Vec<Violation> = Vec::new();
+    let mut ratchet_counts = HashMap::new();
+    let mut ratchet_deadlines = HashMap::new();
+    for ((run, mut config_violations), unmatched) in runs.iter().zip(by_config).zip(&unmatched_by_run) {
+        if options.strict {
+            config_violations.extend(unmatched.iter().map(|u| Violation {
+                rule_id: UNMATCHED_GLOB.to_string(),
+                severity: Severity::Error,
+                file: run.path.to_path_buf(),
+                line: None,
+                column: None,
+                message: u.to_string(),
+                suggest: vec![crate::rules::Suggestion::new(
+                    "check the glob against the project layout, or remove the rule",
+                )],
+                source_line: None,
+                fix: None,
+                origin: None,
+                enclosing: None,
+            }));
+        }
+        if !options.list_files {
+            for fp_rule in &run.built.file_presence_rules {
+                config_violations.append(&mut fp_rule.check_paths(target_paths));
+            }
+            for er_rule in &run.built.expo_router_rules {
+                config_violations.append(&mut er_rule.check_paths(target_paths));
+            }
+            for env_rule in &run.built.env_allowlist_rules {
+                config_violations.append(&mut env_rule.unread_entries());
+            }
+        }
+        if stopped_early {
+            // Counts from part of the tree would pass ratchets that are over
+            config_violations.retain(|v| !run.built.is_ratchet(&v.rule_id));
+        } else {
+            if let Some(changed) = &options.changed {
+                ratchet_counts.extend(apply_ratchet_no_new(
+                    &mut config_violations,
+                    &run.built.ratchet_no_new,
+                    changed,
+                ));
+            }
+            ratchet_counts.extend(apply_ratchet_thresholds(
+                &mut config_violations,
+                &run.built.ratchet_thresholds,
+            ));
+            ratchet_counts.extend(apply_ratchet_budgets(
+                &mut config_violations,
+                &run.built.ratchet_budgets,
+            ));
+            ratchet_deadlines.extend(run.built.ratchet_deadlines.clone());
+        }
+        if multi {
+            for v in &mut config_violations {
+                v.origin = Some(run.path.to_path_buf());
+            }
+        }
+        violations.append(&mut config_violations);
     }
+    violations.append(&mut unreadable.into_inner().unwrap());
+    let mut unmatched: Vec<UnmatchedGlob> = unmatched_by_run.into_iter().flatten().collect();
+    let scan = started.elapsed();
+    let rule_groups: Vec<RuleGroupSummary> = runs
+        .iter()
+        .flat_map(|r| &r.built.rule_groups)
+        .map(RuleGroup::summary)
+        .collect();
 
-    // 9. Apply ratchet thresholds
-    let ratchet_counts = apply_ratchet_thresholds(&mut violations, &built.ratchet_thresholds);
+    // 10. Scan submodules that carry their own config (`submodules = "nested"`)
+    let mut stopped = options.fail_fast && keep_first_error(&mut violations);
+    for (root, nested_config) in &walked.nested {
+        if stopped {
+            break;
+        }
+        tracing::info!(
+            "scanning submodule {} with {}",
+            root.display(),
+            nested_config.display()
+        );
+        let nested = run_scan_with_options(nested_config, std::slice::from_ref(root), options)?;
+        violations.extend(nested.violations);
+        scanned_files.extend(nested.scanned_files);
+        generated_skipped += nested.generated_skipped;
+        file_hashes.extend(nested.file_hashes);
+        rules.extend(nested.rules);
+        unmatched.extend(nested.unmatched_globs);
+        for (id, counts) in nested.ratchet_counts {
+            ratchet_counts.entry(id).or_insert(counts);
+        }
+        for (id, deadline) in nested.ratchet_deadlines {
+            ratchet_deadlines.entry(id).or_insert(deadline);
+        }
+        stopped = options.fail_fast && keep_first_error(&mut violations);
+    }
+    if options.strict {
+        escalate_warnings(&mut violations);
+    }
 
     Ok(ScanResult {
         violations,
-        files_scanned: files_scanned.load(Ordering::Relaxed),
+        files_scanned: scanned_files.len(),
+        generated_skipped,
         rules_loaded,
+        rules,
         ratchet_counts,
+        ratchet_deadlines,
         changed_files_count: None,
         base_ref: None,
+        timings: ScanTimings {
+            config_load,
+            rule_build,
+            scan,
+            cached_rules: cache_hits,
+            rule_groups,
+        },
+        scanned_files,
+        file_hashes,
+        fixes: None,
+        output: loaded[0].toml.output.clone(),
+        hooks: loaded[0].toml.hooks.clone(),
+        unmatched_globs: unmatched,
+        groups: options.groups.clone(),
+        stopped_early,
     })
 }
 
+/// Whether `v` ends a `--fail-fast` scan. Ratchet violations don't: whether
+/// they fail depends on the total count, which is only known at the end.
+fn fails_fast(v: &Violation, built: &BuiltRules) -> bool {
+    v.severity == Severity::Error
+        && !built.ratchet_thresholds.contains_key(&v.rule_id)
+        && !built.ratchet_budgets.contains_key(&v.rule_id)
+}
+
+/// Reduce `violations` to the first error, for `--fail-fast`. Returns
+/// whether there was one.
+fn keep_first_error(violations: &mut Vec<Violation>) -> bool {
+    match violations.iter().position(|v| v.severity == Severity::Error) {
+        Some(first) => {
+            let error = violations.swap_remove(first);
+            *violations = vec![error];
+            true
+        }
+        None => false,
+    }
+}
+
+/// `file` relative to the directory target it was walked from, as matched by
+/// exclude globs. Files given directly as targets have no relative path.
+fn relative_to_target<'a>(file: &'a Path, target_paths: &[PathBuf]) -> Option<&'a Path> {
+    target_paths
+        .iter()
+        .filter(|t| t.as_path() != file)
+        .find_map(|t| file.strip_prefix(t).ok())
+}
+
 /// Suppress ratchet violations that are within budget. Returns counts for display.
 fn apply_ratchet_thresholds(
     violations: &mut Vec<Violation>,
@@ -438,59 +1998,199 @@ fn apply_ratchet_thresholds(
     result
 }
 
+/// Hold `no-new` ratchets to zero matches on changed lines: matches elsewhere
+/// are dropped and every one in the diff is kept. Returns counts for display,
+/// against a budget of 0.
+fn apply_ratchet_no_new(
+    violations: &mut Vec<Violation>,
+    rules: &HashSet<String>,
+    changed: &git_diff::ChangedLines,
+) -> HashMap<String, (usize, usize)> {
+    let mut counts: HashMap<String, (usize, usize)> =
+        rules.iter().map(|id| (id.clone(), (0, 0))).collect();
+    violations.retain(|v| {
+        let Some((found, _)) = counts.get_mut(&v.rule_id) else {
+            return true;
+        };
+        let in_diff = changed.contains(&v.file, v.line);
+        if in_diff {
+            *found += 1;
+        }
+        in_diff
+    });
+    counts
+}
+
+/// Apply per-directory ratchet budgets. Each match is attributed to the
+/// innermost scope containing its file (or to the rule's remaining budget),
+/// and every bucket is suppressed or kept independently. Scoped buckets are
+/// reported as `rule:scope`; the remainder under the bare rule id.
+fn apply_ratchet_budgets(
+    violations: &mut Vec<Violation>,
+    budgets: &HashMap<String, ScopedBudget>,
+) -> HashMap<String, (usize, usize)> {
+    if budgets.is_empty() {
+        return HashMap::new();
+    }
+
+    let bucket_of = |v: &Violation| -> Option<String> {
+        let budget = budgets.get(&v.rule_id)?;
+        Some(
+            budget
+                .scopes
+                .iter()
+                .find(|(scope, _)| file_in_scope(&v.file, Path::new(scope)))
+                .map(|(scope, _)| ratchet_scope_key(&v.rule_id, scope))
+                .unwrap_or_else(|| v.rule_id.clone()),
+        )
+    };
+
+    let mut counts: HashMap<String, usize> = HashMap::new();
+    for v in violations.iter() {
+        if let Some(key) = bucket_of(v) {
+            *counts.entry(key).or_insert(0) += 1;
+        }
+    }
+
+    let mut result: HashMap<String, (usize, usize)> = HashMap::new();
+    for (rule_id, budget) in budgets {
+        for (scope, max) in &budget.scopes {
+            let key = ratchet_scope_key(rule_id, scope);
+            let found = counts.get(&key).copied().unwrap_or(0);
+            result.insert(key, (found, *max));
+        }
+        let found = counts.get(rule_id).copied().unwrap_or(0);
+        result.insert(rule_id.clone(), (found, budget.rest));
+    }
+
+    violations.retain(|v| match bucket_of(v) {
+        Some(key) => {
+            let (found, max) = result[&key];
+            found > max
+        }
+        None => true,
+    });
+
+    result
+}
+
 /// Run a scan on stdin content with a virtual filename.
 pub fn run_scan_stdin(
     config_path: &Path,
     content: &str,
     filename: &str,
 ) -> Result<ScanResult, ScanError> {
-    let config_text = fs::read_to_string(config_path).map_err(ScanError::ConfigRead)?;
-    let toml_config: TomlConfig = toml::from_str(&config_text).map_err(ScanError::ConfigParse)?;
-
-    let mut resolved_rules = presets::resolve_rules(
-        &toml_config.baseline.extends,
-        &toml_config.rule,
-    )
-    .map_err(ScanError::Preset)?;
-
-    let scoped_rules = presets::resolve_scoped_rules(
-        &toml_config.baseline.scoped,
-        &toml_config.rule,
-    )
-    .map_err(ScanError::Preset)?;
-    resolved_rules.extend(scoped_rules);
+    run_scan_stdin_with_options(config_path, content, filename, &ScanOptions::default())
+}
 
-    let built = build_rules(&resolved_rules)?;
+/// Run a scan on stdin content with explicit CLI options.
+pub fn run_scan_stdin_with_options(
+    config_path: &Path,
+    content: &str,
+    filename: &str,
+    options: &ScanOptions,
+) -> Result<ScanResult, ScanError> {
+    let started = Instant::now();
+    let mut loaded = load_config(config_path)?;
+    select_groups(&mut loaded, &options.groups)?;
+    options.catalog.localize_rules(&mut loaded.rules);
+    let toml_config = &loaded.toml;
+    let config_load = started.elapsed();
+
+    let started = Instant::now();
+    let built = build_rules(&loaded.rules)?;
+    let rule_build = started.elapsed();
+    let mut timings = ScanTimings {
+        config_load,
+        rule_build,
+        scan: Duration::ZERO,
+        cached_rules: built.cache_hits,
+        rule_groups: Vec::new(),
+    };
     let rules_loaded: usize = built.rule_groups.iter().map(|g| g.rules.len()).sum();
+    let rules: Vec<RuleMeta> = loaded
+        .rules
+        .iter()
+        .map(|rule| RuleMeta::from_config(rule, &loaded.toml.baseline))
+        .collect();
+    let minified = MinifiedFilter::new(&toml_config.baseline, options)?;
+    let generated = GeneratedFilter::new(&toml_config.baseline, options);
+    let lookback = suppression_lookback(&toml_config.baseline);
 
-    if is_likely_minified(content) {
+    let file_path = PathBuf::from(filename);
+    let file_str = file_path.to_string_lossy();
+    let file_name = file_path.file_name().unwrap_or_default().to_string_lossy();
+
+    let skip_generated = generated.should_skip(content);
+    if skip_generated || minified.should_skip(&file_str, &file_name, content) {
         return Ok(ScanResult {
             violations: vec![],
             files_scanned: 0,
+            generated_skipped: usize::from(skip_generated),
             rules_loaded,
+            rules,
             ratchet_counts: HashMap::new(),
+            ratchet_deadlines: HashMap::new(),
             changed_files_count: None,
             base_ref: None,
+            timings,
+            scanned_files: vec![],
+            file_hashes: HashMap::new(),
+            fixes: None,
+            output: toml_config.output.clone(),
+            hooks: toml_config.hooks.clone(),
+            unmatched_globs: Vec::new(),
+            groups: options.groups.clone(),
+            stopped_early: false,
         });
     }
 
-    let file_path = PathBuf::from(filename);
-    let file_str = file_path.to_string_lossy();
-    let file_name = file_path.file_name().unwrap_or_default().to_string_lossy();
-
-    let violations =
-        run_rules_on_content(&built.rule_groups, &file_path, content, &file_str, &file_name);
+    let started = Instant::now();
+    let mut used_suppressions = HashSet::new();
+    let ctx = ScanContext::new(&file_path, content);
+    let mut violations = run_rules_tracking_suppressions(
+        &built.rule_groups,
+        &ctx,
+        &file_str,
+        &file_name,
+        lookback,
+        &mut used_suppressions,
+    );
+    annotate_enclosing(violations.iter_mut(), &file_path, content);
+    if options.strict {
+        violations.extend(unused_suppressions(&file_path, ctx.lines(), &used_suppressions));
+    }
 
-    let mut violations = violations;
-    let ratchet_counts = apply_ratchet_thresholds(&mut violations, &built.ratchet_thresholds);
+    let mut ratchet_counts = apply_ratchet_thresholds(&mut violations, &built.ratchet_thresholds);
+    ratchet_counts.extend(apply_ratchet_budgets(&mut violations, &built.ratchet_budgets));
+    if options.strict {
+        escalate_warnings(&mut violations);
+    }
+    if options.fail_fast {
+        keep_first_error(&mut violations);
+    }
+    timings.scan = started.elapsed();
+    timings.rule_groups = built.rule_groups.iter().map(RuleGroup::summary).collect();
 
     Ok(ScanResult {
         violations,
         files_scanned: 1,
+        generated_skipped: 0,
         rules_loaded,
+        rules,
         ratchet_counts,
+        ratchet_deadlines: built.ratchet_deadlines,
         changed_files_count: None,
         base_ref: None,
+        timings,
+        scanned_files: vec![PathBuf::from(filename)],
+        file_hashes: HashMap::new(),
+        fixes: None,
+        output: toml_config.output.clone(),
+        hooks: toml_config.hooks.clone(),
+        unmatched_globs: Vec::new(),
+        groups: options.groups.clone(),
+        stopped_early: false,
     })
 }
 
@@ -499,35 +2199,51 @@ pub fn run_scan_changed(
     config_path: &Path,
     target_paths: &[PathBuf],
     base_ref: &str,
+    options: &ScanOptions,
+) -> Result<ScanResult, ScanError> {
+    let configs = [config_path.to_path_buf()];
+    run_scan_changed_configs(&configs, target_paths, base_ref, options)
+}
+
+/// [`run_scan_changed`] evaluating several configs in one walk.
+pub fn run_scan_changed_configs(
+    config_paths: &[PathBuf],
+    target_paths: &[PathBuf],
+    base_ref: &str,
+    options: &ScanOptions,
 ) -> Result<ScanResult, ScanError> {
     // Get diff info from git
-    let diff = git_diff::diff_info(base_ref).map_err(|e| ScanError::GitDiff(e.to_string()))?;
+    let diff = git_diff::diff_info(base_ref, !options.no_renames)
+        .map_err(|e| ScanError::GitDiff(e.to_string()))?;
     let repo_root = git_diff::repo_root().map_err(|e| ScanError::GitDiff(e.to_string()))?;
 
     let changed_files_count = diff.changed_lines.len();
-
-    // Run normal scan
-    let mut result = run_scan(config_path, target_paths)?;
+    tracing::debug!("{} file(s) changed, {} renamed", changed_files_count, diff.renamed.len());
+    let changed = Arc::new(git_diff::ChangedLines::new(repo_root, diff));
+
+    // Run normal scan. Fail-fast applies after filtering: an error on an
+    // unchanged line must not end the scan.
+    let full = ScanOptions {
+        fail_fast: false,
+        changed: Some(Arc::clone(&changed)),
+        ..options.clone()
+    };
+    let mut result = run_scan_configs(config_paths, target_paths, &full)?;
 
     // Post-filter violations to only those in changed files/lines
-    result.violations.retain(|v| {
-        // Compute relative path from repo root for matching against diff
-        let rel_path = if v.file.is_absolute() {
-            v.file.strip_prefix(&repo_root).unwrap_or(&v.file).to_path_buf()
-        } else {
-            v.file.clone()
-        };
-
-        if !diff.has_file(&rel_path) {
-            return false;
-        }
-
-        // File-level violations (no line number) pass if file is changed
-        match v.line {
-            Some(line) => diff.has_line(&rel_path, line),
-            None => true,
-        }
+    result.violations.retain(|v| changed.contains(&v.file, v.line));
+    if options.list_files {
+        result.scanned_files.retain(|f| changed.contains(f, None));
+        result.files_scanned = result.scanned_files.len();
+    }
+    let notices = review_notices(&result, config_paths, &changed, |config| {
+        git_diff::base_contents(base_ref, config)
     });
+    result.violations.extend(notices);
+
+    if options.fail_fast {
+        keep_first_error(&mut result.violations);
+    }
 
     result.changed_files_count = Some(changed_files_count);
     result.base_ref = Some(base_ref.to_string());
@@ -535,52 +2251,237 @@ pub fn run_scan_changed(
     Ok(result)
 }
 
+/// `review-required` notices for the `requires_review` rules a diff touches:
+/// a changed line with a suppression comment covering the rule (including
+/// `allow-all` and unscoped forms), and, in a scanned config, a changed line
+/// in the rule's `[[rule]]` block. `base` gives a config's text at the base
+/// revision; comparing it with the current text also reports rules that
+/// were removed or stopped requiring review, and edits to `include` or
+/// `plugins`, which can redefine any rule.
+fn review_notices(
+    result: &ScanResult,
+    config_paths: &[PathBuf],
+    changed: &git_diff::ChangedLines,
+    base: impl Fn(&Path) -> Option<String>,
+) -> Vec<Violation> {
+    let notice = |file: &Path, line: usize, action: &str, rule_id: &str, owner: Option<&str>| {
+        let approver = owner.unwrap_or("the rule's owners");
+        Violation {
+            rule_id: REVIEW_REQUIRED.to_string(),
+            severity: Severity::Info,
+            file: file.to_path_buf(),
+            line: Some(line),
+            column: None,
+            message: format!(
+                "{} `{}`; policy changes need approval from {}",
+                action, rule_id, approver
+            ),
+            suggest: vec![Suggestion::new(format!("request a review from {}", approver))],
+            source_line: None,
+            fix: None,
+            origin: None,
+            enclosing: None,
+        }
+    };
+    let reviewed: Vec<(&str, Option<&str>)> = result
+        .rules
+        .iter()
+        .filter(|r| r.requires_review)
+        .map(|r| (r.rule_id.as_str(), r.owner.as_deref()))
+        .collect();
+
+    let mut notices = Vec::new();
+    if !reviewed.is_empty() {
+        for file in &result.scanned_files {
+            if !changed.contains(file, None) {
+                continue;
+            }
+            let Ok(content) = fs::read_to_string(file) else { continue };
+            for (idx, line) in content.lines().enumerate() {
+                if !line.contains("baseline:") || !changed.contains(file, Some(idx + 1)) {
+                    continue;
+                }
+                for &(id, owner) in reviewed.iter().filter(|(id, _)| names_suppression(line, id)) {
+                    notices.push(notice(file, idx + 1, "suppresses", id, owner));
+                }
+            }
+        }
+    }
+
+    for config in config_paths {
+        if !changed.contains(config, None) {
+            continue;
+        }
+        let Ok(text) = fs::read_to_string(config) else { continue };
+        let current = toml::from_str::<toml::Table>(&text).ok();
+        let previous = base(config).and_then(|old| toml::from_str::<toml::Table>(&old).ok());
+        // Rules that require review now or did at the base revision
+        let mut policy = reviewed.clone();
+        for rule in previous.iter().flat_map(config_rules) {
+            if let Some(id) = reviewed_rule_id(rule).filter(|id| !policy.iter().any(|(p, _)| p == id)) {
+                policy.push((id, rule.get("owner").and_then(toml::Value::as_str)));
+            }
+        }
+        if policy.is_empty() {
+            continue;
+        }
+
+        let lines: Vec<&str> = text.lines().collect();
+        for (id, block) in rule_blocks(&lines) {
+            let Some(&(id, owner)) = policy.iter().find(|(p, _)| *p == id) else { continue };
+            if let Some(line) = block.into_iter().find(|&line| changed.contains(config, Some(line))) {
+                notices.push(notice(config, line, "changes the config of", id, owner));
+            }
+        }
+
+        let (Some(previous), Some(current)) = (previous.as_ref(), current.as_ref()) else { continue };
+        let first_changed = (1..=lines.len()).find(|&line| changed.contains(config, Some(line))).unwrap_or(1);
+        let current_ids: Vec<&str> = config_rules(current)
+            .filter_map(|rule| rule.get("id").and_then(toml::Value::as_str))
+            .collect();
+        for rule in config_rules(previous) {
+            let Some(id) = reviewed_rule_id(rule).filter(|id| !current_ids.contains(id)) else { continue };
+            let owner = rule.get("owner").and_then(toml::Value::as_str);
+            notices.push(notice(config, first_changed, "removes", id, owner));
+        }
+        let sources = [
+            ("include", previous.get("include"), current.get("include")),
+            ("plugins", baseline_key(previous, "plugins"), baseline_key(current, "plugins")),
+        ];
+        for (key, old, new) in sources {
+            if old == new {
+                continue;
+            }
+            let line = key_line(&lines, key).unwrap_or(first_changed);
+            let action = format!("changes `{}`, which can redefine", key);
+            for &(id, owner) in &policy {
+                notices.push(notice(config, line, &action, id, owner));
+            }
+        }
+    }
+    notices
+}
+
+/// The `[[rule]]` tables of a parsed config.
+fn config_rules(config: &toml::Table) -> impl Iterator<Item = &toml::Table> {
+    config
+        .get("rule")
+        .and_then(toml::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(toml::Value::as_table)
+}
+
+/// The id of a `[[rule]]` table with `requires_review = true`.
+fn reviewed_rule_id(rule: &toml::Table) -> Option<&str> {
+    let reviewed = rule.get("requires_review").and_then(toml::Value::as_bool) == Some(true);
+    rule.get("id").and_then(toml::Value::as_str).filter(|_| reviewed)
+}
+
+fn baseline_key<'a>(config: &'a toml::Table, key: &str) -> Option<&'a toml::Value> {
+    config.get("baseline")?.get(key)
+}
+
+/// The 1-based line that assigns `key`.
+fn key_line(lines: &[&str], key: &str) -> Option<usize> {
+    lines
+        .iter()
+        .position(|line| {
+            line.trim()
+                .strip_prefix(key)
+                .is_some_and(|rest| rest.trim_start().starts_with('='))
+        })
+        .map(|idx| idx + 1)
+}
+
+/// Whether `line` has an `allow`, `allow-next-line` or `ignore-start`
+/// comment that covers `rule_id`: one naming it, or `allow-all`,
+/// `allow-next-line all` or an `ignore-start` without rule ids.
+fn names_suppression(line: &str, rule_id: &str) -> bool {
+    const START: &str = "baseline:ignore-start";
+    let markers = [
+        "baseline:allow-all".to_string(),
+        "baseline:allow-next-line all".to_string(),
+        format!("baseline:allow-{}", rule_id),
+        format!("baseline:allow-next-line {}", rule_id),
+    ];
+    markers.iter().any(|marker| contains_marker(line, marker))
+        || line.find(START).filter(|_| contains_marker(line, START)).is_some_and(|pos| {
+            let rules = region_rules(&line[pos + START.len()..]);
+            rules.is_empty() || rules.iter().any(|id| id == rule_id)
+        })
+}
+
+/// The `id` of each `[[rule]]` in a config and the 1-based lines its block
+/// spans, up to the next table that isn't one of its `[rule.*]` subtables.
+fn rule_blocks(lines: &[&str]) -> Vec<(String, std::ops::RangeInclusive<usize>)> {
+    let mut blocks = Vec::new();
+    // (id once seen, 1-based line of the `[[rule]]` header)
+    let mut current: Option<(Option<String>, usize)> = None;
+    for (idx, line) in lines.iter().enumerate() {
+        let trimmed = line.trim();
+        if trimmed.starts_with('[') && !trimmed.starts_with("[rule.") {
+            if let Some((Some(id), start)) = current.take() {
+                blocks.push((id, start..=idx));
+            }
+            if trimmed == "[[rule]]" {
+                current = Some((None, idx + 1));
+            }
+            continue;
+        }
+        if let Some((id @ None, _)) = current.as_mut() {
+            let value = trimmed.strip_prefix("id").map(str::trim_start).and_then(|v| v.strip_prefix('='));
+            if let Some(value) = value.map(str::trim) {
+                let quoted = value.strip_prefix(['"', '\'']).and_then(|v| v.split(['"', '\'']).next());
+                *id = Some(quoted.unwrap_or(value).to_string());
+            }
+        }
+    }
+    if let Some((Some(id), start)) = current {
+        blocks.push((id, start..=lines.len()));
+    }
+    blocks
+}
+
 /// Run baseline counting: parse config, build only ratchet rules, count matches.
 pub fn run_baseline(
     config_path: &Path,
     target_paths: &[PathBuf],
 ) -> Result<BaselineResult, ScanError> {
-    let config_text = fs::read_to_string(config_path).map_err(ScanError::ConfigRead)?;
-    let toml_config: TomlConfig = toml::from_str(&config_text).map_err(ScanError::ConfigParse)?;
-
-    // Resolve presets and merge with user-defined rules
-    let mut resolved_rules = presets::resolve_rules(
-        &toml_config.baseline.extends,
-        &toml_config.rule,
-    )
-    .map_err(ScanError::Preset)?;
-
-    let scoped_rules = presets::resolve_scoped_rules(
-        &toml_config.baseline.scoped,
-        &toml_config.rule,
-    )
-    .map_err(ScanError::Preset)?;
-    resolved_rules.extend(scoped_rules);
-
-    let exclude_set = build_glob_set(&toml_config.baseline.exclude)?;
-
-    // Build only ratchet rules
-    let mut rules: Vec<(Box<dyn Rule>, Option<GlobSet>, String)> = Vec::new();
-    for toml_rule in &resolved_rules {
+    let loaded = load_config(config_path)?;
+    let toml_config = &loaded.toml;
+    let resolved_rules = &loaded.rules;
+
+    let exclude_set = build_glob_set(&toml_config.baseline.excludes())?;
+    let mut walk_policy = WalkPolicy::from_section(&toml_config.baseline, config_path);
+    let minified = MinifiedFilter::new(&toml_config.baseline, &ScanOptions::default())?;
+    let generated = GeneratedFilter::new(&toml_config.baseline, &ScanOptions::default());
+
+    // Build only ratchet rules; a tracking ratchet counts the rule it tracks
+    ratchet_trackers(resolved_rules)?;
+    let mut rules: Vec<(&TomlRule, Box<dyn Rule>, Option<GlobSet>)> = Vec::new();
+    for toml_rule in resolved_rules {
         if toml_rule.rule_type != "ratchet" {
             continue;
         }
-        let rule_config = toml_rule.to_rule_config();
-        let rule = factory::build_rule(&toml_rule.rule_type, &rule_config)
+        let counted = match toml_rule.track.as_deref() {
+            Some(track) => resolved_rules.iter().find(|r| r.id == track).unwrap_or(toml_rule),
+            None => toml_rule,
+        };
+        let rule = factory::build_rule(&counted.rule_type, &counted.to_rule_config())
             .map_err(ScanError::RuleFactory)?;
 
-        let pattern = toml_rule.pattern.clone().unwrap_or_default();
-
         let rule_glob = if let Some(ref pat) = rule.file_glob() {
+            walk_policy.hidden_dirs.extend(hidden_dirs(pat));
             Some(build_glob_set_from_pattern(pat)?)
         } else {
             None
         };
 
-        rules.push((rule, rule_glob, pattern));
+        rules.push((toml_rule, rule, rule_glob));
     }
 
-    let files = collect_files(target_paths, &exclude_set);
+    let files = collect_files(target_paths, &exclude_set, &walk_policy);
 
     let files_scanned = AtomicUsize::new(0);
 
@@ -588,29 +2489,27 @@ pub fn run_baseline(
         .par_iter()
         .filter_map(|file_path| {
             let content = fs::read_to_string(file_path).ok()?;
-            if is_likely_minified(&content) {
+            let file_str = file_path.to_string_lossy();
+            let file_name = file_path.file_name().unwrap_or_default().to_string_lossy();
+            if minified.should_skip(&file_str, &file_name, &content) || generated.should_skip(&content) {
                 return None;
             }
 
             files_scanned.fetch_add(1, Ordering::Relaxed);
-            let ctx = ScanContext {
-                file_path,
-                content: &content,
-            };
+            let ctx = ScanContext::new(file_path, &content);
 
             let mut local_counts: HashMap<String, usize> = HashMap::new();
-            for (rule, rule_glob, _) in &rules {
+            for (toml_rule, rule, rule_glob) in &rules {
                 if let Some(ref gs) = rule_glob {
-                    let file_str = file_path.to_string_lossy();
-                    let file_name = file_path.file_name().unwrap_or_default().to_string_lossy();
                     if !gs.is_match(&*file_str) && !gs.is_match(&*file_name) {
                         continue;
                     }
                 }
 
-                let violations = rule.check_file(&ctx);
+                // A panic is logged by the guard; the rule counts nothing for this file
+                let violations = check_file_guarded(rule.as_ref(), &ctx).unwrap_or_default();
                 if !violations.is_empty() {
-                    *local_counts.entry(rule.id().to_string()).or_insert(0) += violations.len();
+                    *local_counts.entry(toml_rule.id.clone()).or_insert(0) += violations.len();
                 }
             }
 
@@ -632,10 +2531,11 @@ pub fn run_baseline(
 
     let entries: Vec<BaselineEntry> = rules
         .iter()
-        .map(|(rule, _, pattern)| BaselineEntry {
-            rule_id: rule.id().to_string(),
-            pattern: pattern.clone(),
-            count: counts.get(rule.id()).copied().unwrap_or(0),
+        .map(|(toml_rule, _, _)| BaselineEntry {
+            rule_id: toml_rule.id.clone(),
+            pattern: toml_rule.pattern.clone().unwrap_or_default(),
+            track: toml_rule.track.clone(),
+            count: counts.get(&toml_rule.id).copied().unwrap_or(0),
         })
         .collect();
 
@@ -645,44 +2545,372 @@ pub fn run_baseline(
     })
 }
 
-/// Check if a violation is suppressed by an escape-hatch comment.
-/// Uses pre-computed marker strings to avoid per-call allocations.
-fn is_suppressed(lines: &[&str], line_num: usize, allow_marker: &str, allow_next_line: &str) -> bool {
+fn suppression_lookback(section: &BaselineSection) -> usize {
+    section
+        .suppression_lookback
+        .unwrap_or(DEFAULT_SUPPRESSION_LOOKBACK)
+        .max(1)
+}
+
+/// The 1-based line of the escape-hatch comment that suppresses a
+/// violation, if any. Uses pre-computed marker strings to avoid per-call
+/// allocations.
+///
+/// Markers are matched whole, so they work inside any comment syntax —
+/// `// ...`, `/* ... */` and JSX's `{/* ... */}`. When the violation sits in
+/// a multi-line JSX opening tag, the whole tag counts as the violation's
+/// line: a same-line marker may be on any attribute line, and
+/// `allow-next-line` directives are looked up above the tag's first line as
+/// well as directly above the violating attribute (where `--fix-suppress`
+/// puts them).
+fn suppressed_by(
+    lines: &[&str],
+    line_num: usize,
+    allow_marker: &str,
+    allow_next_line: &str,
+    lookback: usize,
+) -> Option<usize> {
     let allow_all = "baseline:allow-all";
 
-    // Check current line (1-indexed)
-    if line_num > 0 && line_num <= lines.len() {
-        let line = lines[line_num - 1];
-        if line.contains(allow_marker) || line.contains(allow_all) {
-            return true;
+    if line_num == 0 || line_num > lines.len() {
+        return None;
+    }
+    let (first, last) = jsx_tag_span(lines, line_num - 1);
+
+    // Check current line (or every line of the enclosing JSX tag)
+    if let Some(idx) = (first..=last)
+        .find(|&idx| contains_marker(lines[idx], allow_marker) || contains_marker(lines[idx], allow_all))
+    {
+        return Some(idx + 1);
+    }
+
+    // Directives stacked directly above an attribute line inside the tag
+    for idx in (first..line_num - 1).rev() {
+        if !lines[idx].contains("baseline:allow-next-line") {
+            break;
+        }
+        if contains_marker(lines[idx], allow_next_line)
+            || contains_marker(lines[idx], "baseline:allow-next-line all")
+        {
+            return Some(idx + 1);
         }
     }
 
-    // Check previous line (next-line style: `// baseline:allow-next-line`)
-    if line_num >= 2 && line_num <= lines.len() {
-        let prev = lines[line_num - 2];
-        if prev.contains(allow_next_line)
-            || prev.contains("baseline:allow-next-line all")
+    // Check preceding lines (next-line style: `// baseline:allow-next-line`).
+    // Consecutive directive lines stack, so one line can be allowed for
+    // several rules; up to `lookback - 1` other lines may sit in between.
+    let mut gap = 0;
+    for (idx, prev) in lines[..first].iter().enumerate().rev() {
+        if !prev.contains("baseline:allow-next-line") {
+            gap += 1;
+            if gap >= lookback {
+                break;
+            }
+            continue;
+        }
+        if contains_marker(prev, allow_next_line)
+            || contains_marker(prev, "baseline:allow-next-line all")
         {
-            return true;
+            return Some(idx + 1);
         }
     }
 
-    false
+    None
 }
 
-pub(crate) fn collect_files(target_paths: &[PathBuf], exclude_set: &GlobSet) -> Vec<PathBuf> {
-    let mut files: Vec<PathBuf> = Vec::new();
-    for target in target_paths {
+/// Escape-hatch comments in `lines` whose line isn't in `used`, one error
+/// each. `@generated` regions mark generated code rather than suppress a
+/// finding, so they are never reported.
+fn unused_suppressions(file_path: &Path, lines: &[&str], used: &HashSet<usize>) -> Vec<Violation> {
+    let mut violations = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        let Some(column) = ["baseline:allow-", "baseline:ignore-start"]
+            .iter()
+            .filter_map(|marker| line.find(marker))
+            .min()
+        else {
+            continue;
+        };
+        if used.contains(&(idx + 1)) {
+            continue;
+        }
+        violations.push(Violation {
+            rule_id: UNUSED_SUPPRESSION.to_string(),
+            severity: Severity::Error,
+            file: file_path.to_path_buf(),
+            line: Some(idx + 1),
+            column: Some(column + 1),
+            message: "suppression comment doesn't suppress any violation".to_string(),
+            suggest: vec![crate::rules::Suggestion::new(
+                "remove the comment, or correct the rule id it names",
+            )],
+            source_line: Some(line.to_string()),
+            fix: None,
+            origin: None,
+            enclosing: None,
+        });
+    }
+    violations
+}
+
+/// Raise every warning in `violations` to an error (`--strict`).
+fn escalate_warnings(violations: &mut [Violation]) {
+    for v in violations {
+        if v.severity == Severity::Warning {
+            v.severity = Severity::Error;
+        }
+    }
+}
+
+/// A range of lines where violations are suppressed, opened by
+/// `baseline:ignore-start` or `@generated` and closed by the matching
+/// `baseline:ignore-end` or `@end-generated`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct IgnoreRegion {
+    /// 1-based line numbers, inclusive. The marker lines are part of the region.
+    start: usize,
+    end: usize,
+    /// Rule ids to suppress; empty means every rule.
+    rules: Vec<String>,
+}
+
+impl IgnoreRegion {
+    fn covers(&self, line: usize, rule_id: &str) -> bool {
+        (self.start..=self.end).contains(&line)
+            && (self.rules.is_empty() || self.rules.iter().any(|r| r == rule_id))
+    }
+}
+
+/// Find the ignore regions in a file. Regions nest: an end marker closes the
+/// most recent open region of its kind. A `baseline:ignore-start` left open
+/// runs to the end of the file and may list rule ids to limit it to those
+/// rules. `@generated` regions cover every rule and need their
+/// `@end-generated`; a lone `@generated` header is handled by generated-file
+/// detection instead.
+fn ignore_regions(lines: &[&str]) -> Vec<IgnoreRegion> {
+    const START: &str = "baseline:ignore-start";
+    const END: &str = "baseline:ignore-end";
+    const GENERATED: &str = "@generated";
+    const END_GENERATED: &str = "@end-generated";
+
+    let mut regions = Vec::new();
+    let mut open: Vec<(usize, Vec<String>)> = Vec::new();
+    let mut open_generated: Vec<usize> = Vec::new();
+    for (idx, line) in lines.iter().enumerate() {
+        let line_num = idx + 1;
+        if let Some(pos) = line.find(START).filter(|_| contains_marker(line, START)) {
+            open.push((line_num, region_rules(&line[pos + START.len()..])));
+        } else if contains_marker(line, END) {
+            if let Some((start, rules)) = open.pop() {
+                regions.push(IgnoreRegion { start, end: line_num, rules });
+            }
+        }
+        if contains_marker(line, GENERATED) {
+            open_generated.push(line_num);
+        } else if contains_marker(line, END_GENERATED) {
+            if let Some(start) = open_generated.pop() {
+                regions.push(IgnoreRegion { start, end: line_num, rules: Vec::new() });
+            }
+        }
+    }
+    let end = lines.len();
+    regions.extend(open.into_iter().map(|(start, rules)| IgnoreRegion { start, end, rules }));
+    regions
+}
+
+/// Rule ids listed after an ignore-start marker, up to the end of the
+/// comment or a ` -- reason`.
+fn region_rules(rest: &str) -> Vec<String> {
+    let end = ["*/", "-->", " -- "]
+        .iter()
+        .filter_map(|close| rest.find(close))
+        .min()
+        .unwrap_or(rest.len());
+    rest[..end]
+        .split(|c: char| c.is_whitespace() || c == ',')
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .collect()
+}
+
+/// Whether `line` contains `marker` followed by a non-identifier character,
+/// so `allow-foo` doesn't match `allow-foo-bar` but does match `allow-foo*/}`.
+fn contains_marker(line: &str, marker: &str) -> bool {
+    line.match_indices(marker).any(|(i, _)| {
+        !line[i + marker.len()..]
+            .starts_with(|c: char| c.is_alphanumeric() || c == '-' || c == '_')
+    })
+}
+
+/// Whether a line closes a JSX/HTML tag (`>` or `/>`), ignoring arrow
+/// functions in attribute expressions.
+fn closes_tag(line: &str) -> bool {
+    line.replace("=>", "").contains('>')
+}
+
+/// Whether a line starts a JSX/HTML element (`<div`, `<Foo.Bar`).
+fn opens_tag(line: &str) -> bool {
+    let mut chars = line.trim_start().chars();
+    chars.next() == Some('<') && chars.next().is_some_and(|c| c.is_alphabetic())
+}
+
+/// The `(first, last)` line indices of the multi-line opening tag that
+/// contains line `idx`, or `(idx, idx)` when it isn't inside one.
+fn jsx_tag_span(lines: &[&str], idx: usize) -> (usize, usize) {
+    let floor = idx.saturating_sub(JSX_TAG_MAX_LINES);
+    let Some(first) = (floor..=idx).rev().find(|&i| opens_tag(lines[i])) else {
+        return (idx, idx);
+    };
+    // A tag closed before `idx` means `idx` is a child, not an attribute.
+    if lines[first..idx].iter().any(|l| closes_tag(l)) {
+        return (idx, idx);
+    }
+    let ceiling = (first + JSX_TAG_MAX_LINES).min(lines.len() - 1);
+    match (idx..=ceiling).find(|&i| closes_tag(lines[i])) {
+        Some(last) if last > first => (first, last),
+        _ => (idx, idx),
+    }
+}
+
+/// Hash of a file's content, recorded at scan time so `--fix` can tell
+/// whether the file changed before fixes were written.
+pub fn content_hash(content: &str) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    content.hash(&mut hasher);
+    hasher.finish()
+}
+
+/// Re-runs a config's rules on in-memory content, so `--fix` can confirm
+/// that a fix actually removed the violation it targets.
+pub struct FixVerifier {
+    /// Compiled rules and suppression lookback, per config.
+    configs: Vec<(BuiltRules, usize)>,
+}
+
+impl FixVerifier {
+    pub fn new(config_path: &Path) -> Result<Self, ScanError> {
+        Self::for_configs(std::slice::from_ref(&config_path.to_path_buf()))
+    }
+
+    /// A verifier running the rules of every config, for multi-config scans.
+    pub fn for_configs(config_paths: &[PathBuf]) -> Result<Self, ScanError> {
+        let mut configs = Vec::with_capacity(config_paths.len());
+        for path in config_paths {
+            let loaded = load_config(path)?;
+            configs.push((build_rules(&loaded.rules)?, suppression_lookback(&loaded.toml.baseline)));
+        }
+        Ok(Self { configs })
+    }
+
+    /// Violations the configs' rules report for `content` as if it were `file`.
+    pub fn check(&self, file: &Path, content: &str) -> Vec<Violation> {
+        let file_str = file.to_string_lossy();
+        let file_name = file.file_name().unwrap_or_default().to_string_lossy();
+        self.configs
+            .iter()
+            .flat_map(|(built, lookback)| {
+                run_rules_on_content(
+                    &built.rule_groups,
+                    file,
+                    content,
+                    &file_str,
+                    &file_name,
+                    *lookback,
+                )
+            })
+            .collect()
+    }
+}
+
+/// Files found by a walk, plus submodules deferred to their own nested config.
+struct WalkedFiles {
+    files: Vec<PathBuf>,
+    /// `(submodule root, nested config path)` pairs.
+    nested: Vec<(PathBuf, PathBuf)>,
+}
+
+/// Split a scan path that is a glob (`'src/**/*.tsx'`) into the directory to
+/// walk (its leading literal components) and a matcher for the rest. Paths
+/// that exist, or have no glob characters, are not globs. Globs are expanded
+/// here rather than by the shell, which fails on huge file lists and doesn't
+/// expand at all on Windows.
+fn glob_target(target: &Path) -> Option<(PathBuf, GlobMatcher)> {
+    let is_glob = |s: &str| s.contains(['*', '?', '[', '{']);
+    let text = target.to_string_lossy().replace('\\', "/");
+    if !is_glob(&text) || target.exists() {
+        return None;
+    }
+    let components: Vec<&str> = text.split('/').collect();
+    let first_glob = components.iter().position(|c| is_glob(c))?;
+    let root = match components[..first_glob].join("/") {
+        root if root.is_empty() && first_glob > 0 => "/".to_string(),
+        root if root.is_empty() => ".".to_string(),
+        root => root,
+    };
+    let pattern = components[first_glob..].join("/");
+    match GlobBuilder::new(&pattern).literal_separator(true).build() {
+        Ok(glob) => Some((PathBuf::from(root), glob.compile_matcher())),
+        Err(e) => {
+            tracing::warn!("invalid scan path glob '{}': {}", text, e);
+            None
+        }
+    }
+}
+
+pub(crate) fn collect_files(
+    target_paths: &[PathBuf],
+    exclude_set: &GlobSet,
+    policy: &WalkPolicy,
+) -> Vec<PathBuf> {
+    walk_files(target_paths, exclude_set, policy).files
+}
+
+fn walk_files(target_paths: &[PathBuf], exclude_set: &GlobSet, policy: &WalkPolicy) -> WalkedFiles {
+    let mut files: Vec<PathBuf> = Vec::new();
+    let nested: Arc<Mutex<Vec<(PathBuf, PathBuf)>>> = Arc::new(Mutex::new(Vec::new()));
+    for target in target_paths {
+        let (target, matcher) = match glob_target(target) {
+            Some((root, matcher)) => (root, Some(matcher)),
+            None => (target.clone(), None),
+        };
+        let target = &target;
         if target.is_file() {
             files.push(target.clone());
         } else {
             // Use the `ignore` crate's parallel walker for multi-threaded directory traversal.
-            let walker = WalkBuilder::new(target)
-                .hidden(true) // skip hidden files/dirs like .git
+            let entry_policy = policy.clone();
+            let nested_found = Arc::clone(&nested);
+            let mut builder = WalkBuilder::new(target);
+            if let Some(overrides) = policy.overrides(target) {
+                builder.overrides(overrides);
+            }
+            let walker = builder
+                .hidden(policy.hidden_dirs.is_empty()) // skip hidden files/dirs like .git
                 .git_ignore(true) // respect .gitignore
                 .git_global(true) // respect global gitignore
                 .git_exclude(true) // respect .git/info/exclude
+                .follow_links(policy.follow_symlinks)
+                .max_depth(policy.max_depth)
+                .filter_entry(move |entry| {
+                    let is_dir = entry.file_type().is_some_and(|ft| ft.is_dir());
+                    if entry.depth() > 0 && !entry_policy.hidden_dirs.is_empty() {
+                        let name = entry.file_name().to_string_lossy();
+                        if name.starts_with('.') {
+                            return is_dir && entry_policy.hidden_dirs.iter().any(|d| *d == name);
+                        }
+                    }
+                    if !is_dir || entry.depth() == 0 {
+                        return true;
+                    }
+                    if let Some(config) = entry_policy.nested_config(entry.path()) {
+                        nested_found
+                            .lock()
+                            .unwrap()
+                            .push((entry.path().to_path_buf(), config));
+                        return false;
+                    }
+                    !entry_policy.prunes_dir(entry.path())
+                })
                 .build_parallel();
 
             let collected: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
@@ -693,7 +2921,8 @@ pub(crate) fn collect_files(target_paths: &[PathBuf], exclude_set: &GlobSet) ->
                         if entry.file_type().map_or(false, |ft| ft.is_file()) {
                             let path = entry.into_path();
                             let rel = path.strip_prefix(target).unwrap_or(&path);
-                            if !exclude_set.is_match(rel.to_string_lossy().as_ref()) {
+                            let selected = matcher.as_ref().is_none_or(|m| m.is_match(rel));
+                            if selected && !exclude_set.is_match(rel.to_string_lossy().as_ref()) {
                                 collected.lock().unwrap().push(path);
                             }
                         }
@@ -705,7 +2934,34 @@ pub(crate) fn collect_files(target_paths: &[PathBuf], exclude_set: &GlobSet) ->
             files.extend(collected.into_inner().unwrap());
         }
     }
-    files
+    let mut nested = std::mem::take(&mut *nested.lock().unwrap());
+    nested.sort();
+    WalkedFiles { files, nested }
+}
+
+/// Directory globs for the excludes that match everything below a directory
+/// (`**/.next/**` → `**/.next/`), so the walker can skip the directory itself.
+fn pruned_dirs(excludes: &[String]) -> Vec<String> {
+    excludes
+        .iter()
+        .flat_map(|pattern| expand_glob(pattern))
+        .filter_map(|pattern| {
+            let dir = pattern.strip_suffix("/**")?;
+            (!dir.is_empty() && !dir.ends_with("**")).then(|| format!("{}/", dir))
+        })
+        .collect()
+}
+
+/// Hidden directory names a rule glob spells out, such as `.github` in
+/// `.github/workflows/*.yml`. The walk skips hidden paths, so these are
+/// entered explicitly; wildcards like `**/.*` don't count.
+pub(crate) fn hidden_dirs(glob: &str) -> Vec<String> {
+    glob.split(['/', '{', '}', ','])
+        .filter(|part| part.len() > 1 && part.starts_with('.') && *part != "..")
+        .filter(|part| !part.contains(['*', '?', '[']))
+        .filter(|part| glob.contains(&format!("{}/", part)))
+        .map(str::to_string)
+        .collect()
 }
 
 /// Normalize a glob pattern:
@@ -751,22 +3007,31 @@ fn expand_glob(pattern: &str) -> Vec<String> {
 
 /// Build a GlobSet from a single pattern string, expanding brace syntax.
 pub(crate) fn build_glob_set_from_pattern(pattern: &str) -> Result<GlobSet, ScanError> {
-    let expanded = expand_glob(pattern);
-    let mut builder = GlobSetBuilder::new();
-    for pat in &expanded {
-        builder.add(Glob::new(pat).map_err(ScanError::GlobParse)?);
-    }
-    builder.build().map_err(ScanError::GlobParse)
+    build_glob_set(std::slice::from_ref(&pattern.to_string()))
 }
 
+/// Build a GlobSet from patterns, expanding brace syntax. Compiled sets are
+/// cached per process, keyed by the source patterns.
 pub(crate) fn build_glob_set(patterns: &[String]) -> Result<GlobSet, ScanError> {
+    let key = patterns.join("\u{0}");
+    let cache = GLOB_CACHE.get_or_init(Default::default);
+    if let Some(gs) = cache.lock().unwrap().get(&key) {
+        return Ok(gs.clone());
+    }
+
     let mut builder = GlobSetBuilder::new();
     for pattern in patterns {
         for pat in &expand_glob(pattern) {
             builder.add(Glob::new(pat).map_err(ScanError::GlobParse)?);
         }
     }
-    builder.build().map_err(ScanError::GlobParse)
+    let gs = builder.build().map_err(ScanError::GlobParse)?;
+    let mut cache = cache.lock().unwrap();
+    if cache.len() >= COMPILE_CACHE_CAPACITY {
+        cache.clear();
+    }
+    cache.insert(key, gs.clone());
+    Ok(gs)
 }
 
 #[cfg(test)]
@@ -782,9 +3047,11 @@ mod tests {
             line: Some(1),
             column: Some(1),
             message: "test".to_string(),
-            suggest: None,
+            suggest: Vec::new(),
             source_line: None,
             fix: None,
+            origin: None,
+            enclosing: None,
         }
     }
 
@@ -884,12 +3151,13 @@ mod tests {
         let lines = vec![
             "let x = style={{ color: 'red' }}; // baseline:allow-no-inline-styles",
         ];
-        assert!(is_suppressed(
+        assert!(suppressed_by(
             &lines,
             1,
             "baseline:allow-no-inline-styles",
             "baseline:allow-next-line no-inline-styles",
-        ));
+            1,
+        ).is_some());
     }
 
     #[test]
@@ -897,18 +3165,20 @@ mod tests {
         let lines = vec![
             "let x = style={{ color: 'red' }}; // baseline:allow-all",
         ];
-        assert!(is_suppressed(
+        assert!(suppressed_by(
             &lines,
             1,
             "baseline:allow-no-inline-styles",
             "baseline:allow-next-line no-inline-styles",
-        ));
-        assert!(is_suppressed(
+            1,
+        ).is_some());
+        assert!(suppressed_by(
             &lines,
             1,
             "baseline:allow-any-other-rule",
             "baseline:allow-next-line any-other-rule",
-        ));
+            1,
+        ).is_some());
     }
 
     #[test]
@@ -917,12 +3187,13 @@ mod tests {
             "// baseline:allow-next-line no-inline-styles",
             "let x = style={{ color: 'red' }};",
         ];
-        assert!(is_suppressed(
+        assert!(suppressed_by(
             &lines,
             2,
             "baseline:allow-no-inline-styles",
             "baseline:allow-next-line no-inline-styles",
-        ));
+            1,
+        ).is_some());
     }
 
     #[test]
@@ -931,12 +3202,13 @@ mod tests {
             "// baseline:allow-next-line all",
             "let x = style={{ color: 'red' }};",
         ];
-        assert!(is_suppressed(
+        assert!(suppressed_by(
             &lines,
             2,
             "baseline:allow-no-inline-styles",
             "baseline:allow-next-line no-inline-styles",
-        ));
+            1,
+        ).is_some());
     }
 
     #[test]
@@ -944,12 +3216,13 @@ mod tests {
         let lines = vec![
             "let x = style={{ color: 'red' }}; // baseline:allow-other-rule",
         ];
-        assert!(!is_suppressed(
+        assert!(suppressed_by(
             &lines,
             1,
             "baseline:allow-no-inline-styles",
             "baseline:allow-next-line no-inline-styles",
-        ));
+            1,
+        ).is_none());
     }
 
     #[test]
@@ -957,12 +3230,13 @@ mod tests {
         let lines = vec![
             "let x = style={{ color: 'red' }};",
         ];
-        assert!(!is_suppressed(
+        assert!(suppressed_by(
             &lines,
             1,
             "baseline:allow-no-inline-styles",
             "baseline:allow-next-line no-inline-styles",
-        ));
+            1,
+        ).is_none());
     }
 
     #[test]
@@ -971,36 +3245,39 @@ mod tests {
             "// baseline:allow-next-line other-rule",
             "let x = style={{ color: 'red' }};",
         ];
-        assert!(!is_suppressed(
+        assert!(suppressed_by(
             &lines,
             2,
             "baseline:allow-no-inline-styles",
             "baseline:allow-next-line no-inline-styles",
-        ));
+            1,
+        ).is_none());
     }
 
     #[test]
     fn suppressed_line_zero_is_safe() {
         let lines = vec!["some content"];
         // line_num 0 should not panic
-        assert!(!is_suppressed(
+        assert!(suppressed_by(
             &lines,
             0,
             "baseline:allow-any-rule",
             "baseline:allow-next-line any-rule",
-        ));
+            1,
+        ).is_none());
     }
 
     #[test]
     fn suppressed_past_end_is_safe() {
         let lines = vec!["some content"];
         // line_num past end should not panic
-        assert!(!is_suppressed(
+        assert!(suppressed_by(
             &lines,
             5,
             "baseline:allow-any-rule",
             "baseline:allow-next-line any-rule",
-        ));
+            1,
+        ).is_none());
     }
 
     // ── ScanError Display tests ──
@@ -1109,6 +3386,276 @@ mod tests {
         assert_eq!(built.file_presence_rules.len(), 1);
     }
 
+    #[test]
+    fn file_presence_must_contain_from_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "project-hygiene"
+type = "file-presence"
+message = "project hygiene"
+
+[rule.must_contain]
+".gitignore" = [".env", "node_modules"]
+"tsconfig.json" = '"strict": true'
+"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join(".gitignore"), "node_modules\n").unwrap();
+        fs::write(dir.path().join("tsconfig.json"), r#"{ "strict": true }"#).unwrap();
+
+        let result = run_scan(&config, &[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(
+            result.violations[0].message,
+            "project hygiene: '.gitignore' does not contain '.env'"
+        );
+    }
+
+    #[test]
+    fn output_section_gates_formats_and_exit_code() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        let write_config = |output: &str| {
+            fs::write(
+                &config,
+                format!(
+                    r#"
+[baseline]
+
+{}
+
+[[rule]]
+id = "no-foo"
+type = "banned-pattern"
+severity = "warning"
+glob = "**/*.ts"
+pattern = "foo"
+message = "no foo"
+"#,
+                    output
+                ),
+            )
+            .unwrap();
+        };
+        fs::write(dir.path().join("a.ts"), "foo();\n").unwrap();
+        let scan = || run_scan(&config, &[dir.path().to_path_buf()]);
+
+        write_config("");
+        assert_eq!(scan().unwrap().exit_code(), 0, "only errors fail by default");
+
+        write_config("[output.exit]\nmin_severity = \"warning\"\n\n[output.github]\nmin_severity = \"error\"");
+        let mut result = scan().unwrap();
+        assert_eq!(result.exit_code(), 1);
+        result.retain_for_format("sarif");
+        assert_eq!(result.violations.len(), 1, "formats without a table report everything");
+        result.retain_for_format("github");
+        assert!(result.violations.is_empty());
+
+        write_config("[output.github]\nmin_severity = \"loud\"");
+        assert!(matches!(scan(), Err(ScanError::ConfigParse(_))));
+    }
+
+    #[test]
+    fn suggest_accepts_a_list_of_alternatives() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "no-moment"
+type = "banned-pattern"
+pattern = "moment("
+message = "moment is deprecated"
+suggest = ["Use date-fns", "Use Temporal"]
+"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join("a.ts"), "moment();\n").unwrap();
+
+        let result = run_scan(&config, &[dir.path().to_path_buf()]).unwrap();
+        let titles: Vec<_> = result.violations[0].suggest.iter().map(|s| s.title.as_str()).collect();
+        assert_eq!(titles, ["Use date-fns", "Use Temporal"]);
+    }
+
+    #[test]
+    fn supply_chain_preset_walks_workflow_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(&config, "[baseline]\nextends = [\"supply-chain\"]\n").unwrap();
+        let workflows = dir.path().join(".github/workflows");
+        fs::create_dir_all(&workflows).unwrap();
+        fs::write(workflows.join("ci.yml"), "on: push\njobs:\n  t:\n    steps:\n      - uses: actions/checkout@v4\n").unwrap();
+        fs::create_dir_all(dir.path().join(".cache")).unwrap();
+        fs::write(dir.path().join(".cache/Dockerfile"), "FROM node\n").unwrap();
+        fs::write(dir.path().join("Dockerfile"), "FROM node:20\nRUN curl -sL https://x.sh | sh\n").unwrap();
+
+        let result = run_scan(&config, &[dir.path().to_path_buf()]).unwrap();
+        let mut found: Vec<_> = result.violations.iter().map(|v| (v.rule_id.as_str(), v.line)).collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                ("actions-pinned-sha", Some(5)),
+                ("docker-no-pipe-to-shell", Some(2)),
+                ("workflow-permissions", Some(1)),
+            ]
+        );
+        assert_eq!(hidden_dirs("**/{Dockerfile,.github/workflows/*.yml}"), vec![".github"]);
+        assert!(hidden_dirs("**/.env*").is_empty());
+    }
+
+    #[test]
+    fn reports_rules_whose_glob_matches_no_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            "[baseline]\n\n\
+             [[rule]]\nid = \"app-todo\"\ntype = \"banned-pattern\"\npattern = \"TODO\"\nglob = \"app/**/*.ts\"\n\n\
+             [[rule]]\nid = \"src-todo\"\ntype = \"banned-pattern\"\npattern = \"TODO\"\nglob = \"**/src/**/*.ts\"\n\n\
+             [[rule]]\nid = \"any-todo\"\ntype = \"banned-pattern\"\npattern = \"TODO\"\n",
+        )
+        .unwrap();
+        fs::create_dir_all(dir.path().join("src")).unwrap();
+        fs::write(dir.path().join("src/a.ts"), "// TODO\n").unwrap();
+
+        let expected = vec![UnmatchedGlob {
+            rule_id: "app-todo".into(),
+            glob: "app/**/*.ts".into(),
+        }];
+        let result = run_scan(&config, &[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(result.unmatched_globs, expected);
+
+        let check = check_config(&config, &[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(check.rules_loaded, 3);
+        assert_eq!(check.files, 2);
+        assert_eq!(check.unmatched_globs, expected);
+        assert_eq!(
+            check.unmatched_globs[0].to_string(),
+            "rule 'app-todo' matched no files (glob 'app/**/*.ts')"
+        );
+    }
+
+    #[test]
+    fn strict_mode_fails_on_advisory_conditions() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            "[baseline]\n\n\
+             [[rule]]\nid = \"no-todo\"\ntype = \"banned-pattern\"\nseverity = \"warning\"\npattern = \"TODO\"\nglob = \"**/*.ts\"\n\n\
+             [[rule]]\nid = \"app-only\"\ntype = \"banned-pattern\"\npattern = \"x\"\nglob = \"app/**/*.ts\"\n",
+        )
+        .unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(&src).unwrap();
+        fs::write(src.join("a.ts"), "// TODO\n").unwrap();
+        fs::write(src.join("b.ts"), "ok(); // baseline:allow-no-todo\n").unwrap();
+        fs::write(src.join("c.ts"), "// baseline:allow-next-line no-todo\n// TODO\n").unwrap();
+        fs::write(src.join("d.ts"), b"\xff\xfe binary").unwrap();
+
+        let targets = [dir.path().to_path_buf()];
+        let lenient = run_scan(&config, &targets).unwrap();
+        assert_eq!(lenient.violations.len(), 1);
+        assert_eq!(lenient.exit_code(), 0);
+
+        let options = ScanOptions {
+            strict: true,
+            ..Default::default()
+        };
+        let strict = run_scan_with_options(&config, &targets, &options).unwrap();
+        let mut found: Vec<_> = strict
+            .violations
+            .iter()
+            .map(|v| {
+                let file = v.file.file_name().unwrap().to_string_lossy().into_owned();
+                (v.rule_id.as_str(), v.severity.as_str(), file, v.line)
+            })
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![
+                ("no-todo", "error", "a.ts".to_string(), Some(1)),
+                (UNMATCHED_GLOB, "error", "baseline.toml".to_string(), None),
+                (UNREADABLE_FILE, "error", "d.ts".to_string(), None),
+                (UNUSED_SUPPRESSION, "error", "b.ts".to_string(), Some(1)),
+            ]
+        );
+        assert_eq!(strict.exit_code(), 1);
+    }
+
+    #[test]
+    fn banned_dependency_checks_every_workspace_manifest() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            "[baseline]\n\n[[rule]]\nid = \"no-moment\"\ntype = \"banned-dependency\"\npackages = [\"moment\"]\nmessage = \"no moment\"\n",
+        )
+        .unwrap();
+        for manifest in ["package.json", "packages/a/package.json", "apps/web/package.json", "node_modules/x/package.json"] {
+            let path = dir.path().join(manifest);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "{\n  \"dependencies\": {\n    \"moment\": \"^2.0.0\"\n  }\n}\n").unwrap();
+        }
+
+        let result = run_scan(&config, &[dir.path().to_path_buf()]).unwrap();
+        let mut files: Vec<String> = result
+            .violations
+            .iter()
+            .map(|v| v.file.strip_prefix(dir.path()).unwrap().display().to_string())
+            .collect();
+        files.sort();
+        assert_eq!(files, ["apps/web/package.json", "package.json", "packages/a/package.json"]);
+        assert!(result.violations.iter().all(|v| v.line == Some(3)));
+    }
+
+    #[test]
+    fn load_config_resolves_rule_type_aliases() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            "[baseline]\n\n[[rule]]\nid = \"no-foo\"\ntype = \"regex\"\nglob = \"**/*.ts\"\npattern = \"foo\"\nmessage = \"no foo\"\n",
+        )
+        .unwrap();
+        fs::write(dir.path().join("a.ts"), "foo();\n").unwrap();
+
+        assert_eq!(load_config(&config).unwrap().rules[0].rule_type, "banned-pattern");
+        let result = run_scan(&config, &[dir.path().to_path_buf()]).unwrap();
+        assert_eq!(result.violations.len(), 1);
+    }
+
+    #[test]
+    fn load_config_checks_required_version() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+
+        fs::write(&config, "[baseline]\nrequired_version = \">=0.6\"\n").unwrap();
+        assert!(load_config(&config).is_ok());
+
+        // A newer config fails on the version, not on keys this binary lacks
+        fs::write(&config, "[baseline]\nrequired_version = \">=99\"\nfuture_setting = true\n").unwrap();
+        let err = load_config(&config).err().unwrap();
+        assert!(matches!(err, ScanError::UnsupportedVersion { .. }));
+        assert!(err
+            .to_string()
+            .starts_with(&format!("{} requires baseline >=99, but this is {};", config.display(), version::CURRENT)));
+
+        fs::write(&config, "[baseline]\nrequired_version = \"latest\"\n").unwrap();
+        assert!(matches!(load_config(&config), Err(ScanError::RequiredVersion(_))));
+    }
+
     #[test]
     fn build_rules_unknown_type_errors() {
         let rules = vec![TomlRule {
@@ -1154,10 +3701,40 @@ mod tests {
 
         let built = build_rules(&rules).unwrap();
         assert_eq!(built.rule_groups.len(), 1);
-        assert!(built.rule_groups[0].rules[0].file_contains.is_some());
+        assert!(built.rule_groups[0].file_contains.is_some());
         assert!(built.rule_groups[0].rules[0].file_not_contains.is_some());
     }
 
+    #[test]
+    fn build_rules_groups_by_file_contains() {
+        let rule = |id: &str, file_contains: Option<&str>| TomlRule {
+            id: id.into(),
+            rule_type: "banned-pattern".into(),
+            pattern: Some("x".into()),
+            message: "m".into(),
+            glob: Some("**/*.tsx".into()),
+            file_contains: file_contains.map(String::from),
+            ..Default::default()
+        };
+        let rules = vec![
+            rule("a", Some("'use client'")),
+            rule("b", None),
+            rule("c", Some("'use client'")),
+        ];
+        let built = build_rules(&rules).unwrap();
+        let summaries: Vec<_> = built.rule_groups.iter().map(RuleGroup::summary).collect();
+        assert_eq!(summaries.len(), 2);
+        assert_eq!(summaries[0].rule_ids, vec!["a", "c"]);
+        assert_eq!(summaries[0].file_contains.as_deref(), Some("'use client'"));
+        assert_eq!(summaries[1].rule_ids, vec!["b"]);
+
+        let path = PathBuf::from("Button.tsx");
+        run_rules_on_content(&built.rule_groups, &path, "x", "Button.tsx", "Button.tsx", 1);
+        run_rules_on_content(&built.rule_groups, &path, "'use client'; x", "Button.tsx", "Button.tsx", 1);
+        let summary = built.rule_groups[0].summary();
+        assert_eq!((summary.files_matched, summary.files_checked), (2, 1));
+    }
+
     // ── group_matches_file tests ──
 
     #[test]
@@ -1231,9 +3808,9 @@ mod tests {
         }];
         let built = build_rules(&rules).unwrap();
         let mut cache = HashMap::new();
-        assert!(passes_file_conditioning_cached(&built.rule_groups[0].rules[0], "import React from 'react';", &mut cache));
+        assert!(passes_group_conditioning_cached(&built.rule_groups[0], "import React from 'react';", &mut cache));
         let mut cache = HashMap::new();
-        assert!(!passes_file_conditioning_cached(&built.rule_groups[0].rules[0], "import Vue from 'vue';", &mut cache));
+        assert!(!passes_group_conditioning_cached(&built.rule_groups[0], "import Vue from 'vue';", &mut cache));
     }
 
     #[test]
@@ -1265,25 +3842,46 @@ mod tests {
             ..Default::default()
         }];
         let built = build_rules(&rules).unwrap();
+        let group = &built.rule_groups[0];
+        let passes = |content: &str| {
+            let mut cache = HashMap::new();
+            passes_group_conditioning_cached(group, content, &mut cache)
+                && passes_file_conditioning_cached(&group.rules[0], content, &mut cache)
+        };
         // Has required, missing excluded -> pass
-        let mut cache = HashMap::new();
-        assert!(passes_file_conditioning_cached(&built.rule_groups[0].rules[0], "import React", &mut cache));
+        assert!(passes("import React"));
         // Missing required -> fail
-        let mut cache = HashMap::new();
-        assert!(!passes_file_conditioning_cached(&built.rule_groups[0].rules[0], "import Vue", &mut cache));
+        assert!(!passes("import Vue"));
         // Has both -> fail (file_not_contains blocks it)
-        let mut cache = HashMap::new();
-        assert!(!passes_file_conditioning_cached(&built.rule_groups[0].rules[0], "import React // @generated", &mut cache));
+        assert!(!passes("import React // @generated"));
     }
 
-    // ── run_rules_on_content tests ──
-
     #[test]
-    fn run_rules_on_content_finds_violations() {
+    fn passes_conditioning_sibling_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+        fs::write(dir.path().join("+page.server.ts"), "").unwrap();
         let rules = vec![TomlRule {
-            id: "no-console".into(),
+            id: "r".into(),
             rule_type: "banned-pattern".into(),
-            pattern: Some("console\\.log".into()),
+            pattern: Some("x".into()),
+            message: "m".into(),
+            sibling_files: vec!["+page.server.js".into(), "+page.server.ts".into()],
+            ..Default::default()
+        }];
+        let built = build_rules(&rules).unwrap();
+        let rule = &built.rule_groups[0].rules[0];
+        assert!(passes_sibling_conditioning(rule, &dir.path().join("+page.svelte")));
+        assert!(!passes_sibling_conditioning(rule, &dir.path().join("nested/+page.svelte")));
+    }
+
+    // ── run_rules_on_content tests ──
+
+    #[test]
+    fn run_rules_on_content_finds_violations() {
+        let rules = vec![TomlRule {
+            id: "no-console".into(),
+            rule_type: "banned-pattern".into(),
+            pattern: Some("console\\.log".into()),
             message: "no console.log".into(),
             regex: true,
             ..Default::default()
@@ -1292,11 +3890,65 @@ mod tests {
         let path = PathBuf::from("test.ts");
         let content = "console.log('hello');\nfoo();\n";
 
-        let violations = run_rules_on_content(&built.rule_groups, &path, content, "test.ts", "test.ts");
+        let violations = run_rules_on_content(&built.rule_groups, &path, content, "test.ts", "test.ts", 1);
         assert_eq!(violations.len(), 1);
         assert_eq!(violations[0].rule_id, "no-console");
     }
 
+    struct PanickingRule;
+
+    impl Rule for PanickingRule {
+        fn id(&self) -> &str {
+            "broken"
+        }
+        fn severity(&self) -> Severity {
+            Severity::Warning
+        }
+        fn file_glob(&self) -> Option<&str> {
+            None
+        }
+        fn check_file(&self, _ctx: &ScanContext) -> Vec<Violation> {
+            panic!("index out of bounds")
+        }
+    }
+
+    #[test]
+    fn run_rules_on_content_reports_panicking_rule() {
+        let rules = vec![
+            TomlRule {
+                id: "broken".into(),
+                rule_type: "banned-pattern".into(),
+                pattern: Some("x".into()),
+                message: "m".into(),
+                ..Default::default()
+            },
+            TomlRule {
+                id: "no-console".into(),
+                rule_type: "banned-pattern".into(),
+                pattern: Some("console.log".into()),
+                message: "no console.log".into(),
+                ..Default::default()
+            },
+        ];
+        let mut built = build_rules(&rules).unwrap();
+        built.rule_groups[0].rules[0].rule = Arc::new(PanickingRule);
+        let path = PathBuf::from("test.ts");
+
+        let violations = run_rules_on_content(&built.rule_groups, &path, "console.log(x);\n", "test.ts", "test.ts", 1);
+        let ids: Vec<&str> = violations.iter().map(|v| v.rule_id.as_str()).collect();
+        assert_eq!(ids, [INTERNAL_ERROR, "no-console"]);
+        assert_eq!(violations[0].severity, Severity::Error);
+        assert_eq!(
+            violations[0].message,
+            "rule 'broken' panicked and was skipped for this file: index out of bounds"
+        );
+
+        let mut result = result_with(violations);
+        assert_eq!(result.exit_code(), PARTIAL_SCAN_EXIT_CODE);
+        result.violations.remove(0);
+        assert_eq!(result.exit_code(), 0);
+    }
+
     #[test]
     fn run_rules_on_content_respects_suppression() {
         let rules = vec![TomlRule {
@@ -1311,7 +3963,7 @@ mod tests {
         let path = PathBuf::from("test.ts");
         let content = "console.log('hello'); // baseline:allow-no-console\n";
 
-        let violations = run_rules_on_content(&built.rule_groups, &path, content, "test.ts", "test.ts");
+        let violations = run_rules_on_content(&built.rule_groups, &path, content, "test.ts", "test.ts", 1);
         assert_eq!(violations.len(), 0);
     }
 
@@ -1330,7 +3982,7 @@ mod tests {
         let path = PathBuf::from("test.rs");
         let content = "console.log('hello');\n";
 
-        let violations = run_rules_on_content(&built.rule_groups, &path, content, "test.rs", "test.rs");
+        let violations = run_rules_on_content(&built.rule_groups, &path, content, "test.rs", "test.rs", 1);
         assert_eq!(violations.len(), 0);
     }
 
@@ -1349,7 +4001,7 @@ mod tests {
         let path = PathBuf::from("test.ts");
         let content = "console.log('hello');\n"; // no "import React"
 
-        let violations = run_rules_on_content(&built.rule_groups, &path, content, "test.ts", "test.ts");
+        let violations = run_rules_on_content(&built.rule_groups, &path, content, "test.ts", "test.ts", 1);
         assert_eq!(violations.len(), 0);
     }
 
@@ -1528,69 +4180,1654 @@ mod tests {
     fn run_scan_with_banned_pattern() {
         let dir = tempfile::tempdir().unwrap();
 
-        // Write config
+        // Write config
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "no-console"
+type = "banned-pattern"
+severity = "error"
+pattern = "console\\.log"
+regex = true
+message = "Do not use console.log"
+"#,
+        )
+        .unwrap();
+
+        // Write a source file
+        let src_dir = dir.path().join("src");
+        fs::create_dir(&src_dir).unwrap();
+        fs::write(src_dir.join("app.ts"), "console.log('hi');\nfoo();\n").unwrap();
+
+        let result = run_scan(&config, &[src_dir]).unwrap();
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.violations[0].rule_id, "no-console");
+        assert_eq!(result.files_scanned, 1);
+        assert_eq!(result.rules_loaded, 1);
+    }
+
+    #[test]
+    fn run_scan_group_runs_only_its_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[groups.security]
+rules = ["no-eval"]
+
+[groups.typo]
+rules = ["no-evil"]
+
+[[rule]]
+id = "no-console"
+type = "banned-pattern"
+pattern = "console.log"
+message = "No console.log"
+
+[[rule]]
+id = "no-eval"
+type = "banned-pattern"
+pattern = "eval("
+message = "No eval"
+"#,
+        )
+        .unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        fs::write(src.join("app.js"), "console.log(1);\neval(code);\n").unwrap();
+        let targets = [src];
+
+        let group = |names: &[&str]| ScanOptions {
+            groups: names.iter().map(|n| n.to_string()).collect(),
+            ..Default::default()
+        };
+        let result = run_scan_with_options(&config, &targets, &group(&["security"])).unwrap();
+        let ids: Vec<&str> = result.violations.iter().map(|v| v.rule_id.as_str()).collect();
+        assert_eq!(ids, vec!["no-eval"]);
+        assert_eq!(result.rules_loaded, 1);
+        assert_eq!(result.groups, vec!["security"]);
+
+        let stdin = run_scan_stdin_with_options(&config, "console.log(1);\n", "a.js", &group(&["security"])).unwrap();
+        assert!(stdin.violations.is_empty());
+
+        let err = run_scan_with_options(&config, &targets, &group(&["perf"])).err().unwrap();
+        assert_eq!(err.to_string(), "unknown group 'perf' (define it under [groups.perf])");
+        let err = run_scan_with_options(&config, &targets, &group(&["typo"])).err().unwrap();
+        assert_eq!(err.to_string(), "group 'typo' lists unknown rule 'no-evil'");
+    }
+
+    #[test]
+    fn run_scan_list_files_collects_files_without_running_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "no-console"
+type = "banned-pattern"
+glob = "**/*.js"
+pattern = "console.log"
+message = "No console.log"
+
+[[rule]]
+id = "readme"
+type = "file-presence"
+required_files = ["README.md"]
+message = "Add a README"
+"#,
+        )
+        .unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        fs::write(src.join("app.js"), "console.log(1);\n").unwrap();
+        fs::write(src.join("bundle.js"), format!("var a={};\n", "1".repeat(600))).unwrap();
+        fs::write(src.join("schema.js"), "// @generated\nconsole.log(1);\n").unwrap();
+        fs::write(src.join("style.css"), "a {}\n").unwrap();
+
+        let options = ScanOptions {
+            list_files: true,
+            ..Default::default()
+        };
+        let result = run_scan_with_options(&config, std::slice::from_ref(&src), &options).unwrap();
+        assert_eq!(result.scanned_files, vec![src.join("app.js")]);
+        assert_eq!(result.generated_skipped, 1);
+        assert!(result.violations.is_empty());
+    }
+
+    #[test]
+    fn run_scan_passes_diff_status_to_rules() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "no-new-pages"
+type = "no-new-files"
+glob = "**/pages/**"
+message = "New routes go in app/"
+"#,
+        )
+        .unwrap();
+        let pages = dir.path().join("pages");
+        fs::create_dir(&pages).unwrap();
+        fs::write(pages.join("index.tsx"), "export default 1;\n").unwrap();
+        fs::write(pages.join("settings.tsx"), "export default 2;\n").unwrap();
+
+        let mut diff = git_diff::DiffInfo::default();
+        for name in ["pages/index.tsx", "pages/settings.tsx"] {
+            diff.changed_lines.insert(PathBuf::from(name), vec![1..=1]);
+        }
+        diff.added.insert(PathBuf::from("pages/settings.tsx"));
+        let options = ScanOptions {
+            changed: Some(Arc::new(git_diff::ChangedLines::new(dir.path().to_path_buf(), diff))),
+            ..Default::default()
+        };
+        let targets = [pages];
+        let result = run_scan_with_options(&config, &targets, &options).unwrap();
+        assert_eq!(result.violations.len(), 1);
+        assert!(result.violations[0].file.ends_with("pages/settings.tsx"));
+
+        // Without a diff nothing counts as new
+        assert!(run_scan(&config, &targets).unwrap().violations.is_empty());
+    }
+
+    #[test]
+    fn run_scan_no_new_ratchet_counts_only_the_diff() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "legacy-fetch"
+type = "ratchet"
+severity = "error"
+pattern = "legacyFetch("
+max_count = 10
+ratchet_mode = "no-new"
+message = "Use apiClient"
+"#,
+        )
+        .unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        fs::write(src.join("old.ts"), "legacyFetch(a);\nlegacyFetch(b);\n").unwrap();
+        fs::write(src.join("new.ts"), "ok();\nlegacyFetch(c);\n").unwrap();
+        let targets = [src];
+
+        // A full scan is within the repo-wide budget
+        let full = run_scan(&config, &targets).unwrap();
+        assert!(full.violations.is_empty());
+        assert_eq!(full.ratchet_counts["legacy-fetch"], (3, 10));
+
+        let mut diff = git_diff::DiffInfo::default();
+        diff.changed_lines.insert(PathBuf::from("src/new.ts"), vec![2..=2]);
+        let options = ScanOptions {
+            changed: Some(Arc::new(git_diff::ChangedLines::new(dir.path().to_path_buf(), diff))),
+            ..Default::default()
+        };
+        let result = run_scan_with_options(&config, &targets, &options).unwrap();
+        assert_eq!(result.violations.len(), 1);
+        assert!(result.violations[0].file.ends_with("src/new.ts"));
+        assert_eq!(result.ratchet_counts["legacy-fetch"], (1, 0));
+        assert_eq!(result.exit_code(), 1);
+    }
+
+    #[test]
+    fn run_scan_changed_keeps_ratchet_budget_by_default() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "legacy-fetch"
+type = "ratchet"
+severity = "error"
+pattern = "legacyFetch("
+max_count = 5
+message = "Use apiClient"
+"#,
+        )
+        .unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        fs::write(src.join("old.ts"), "legacyFetch(a);\nlegacyFetch(b);\nlegacyFetch(c);\n").unwrap();
+        fs::write(src.join("new.ts"), "legacyFetch(d);\n").unwrap();
+
+        let mut diff = git_diff::DiffInfo::default();
+        diff.changed_lines.insert(PathBuf::from("src/new.ts"), vec![1..=1]);
+        let options = ScanOptions {
+            changed: Some(Arc::new(git_diff::ChangedLines::new(dir.path().to_path_buf(), diff))),
+            ..Default::default()
+        };
+        let result = run_scan_with_options(&config, &[src], &options).unwrap();
+        assert!(result.violations.is_empty());
+        assert_eq!(result.ratchet_counts["legacy-fetch"], (4, 5));
+        assert_eq!(result.exit_code(), 0);
+    }
+
+    #[test]
+    fn review_notices_flag_suppressions_and_config_edits() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"[baseline]
+
+[[rule]]
+id = "no-eval"
+type = "banned-pattern"
+pattern = "eval("
+message = "No eval"
+owner = "@security"
+requires_review = true
+
+[[rule]]
+id = "no-console"
+type = "banned-pattern"
+pattern = "console.log"
+message = "No console.log"
+"#,
+        )
+        .unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        fs::write(
+            src.join("app.js"),
+            "eval(a); // baseline:allow-no-eval\n// baseline:allow-next-line no-console\nconsole.log(1);\neval(b); // baseline:allow-no-eval\n",
+        )
+        .unwrap();
+        let result = run_scan(&config, std::slice::from_ref(&src)).unwrap();
+        assert!(result.rules.iter().any(|r| r.owner.as_deref() == Some("@security") && r.requires_review));
+
+        let mut diff = git_diff::DiffInfo::default();
+        diff.changed_lines.insert(PathBuf::from("src/app.js"), vec![1..=3]);
+        diff.changed_lines.insert(PathBuf::from("baseline.toml"), vec![8..=8, 15..=15]);
+        let changed = git_diff::ChangedLines::new(dir.path().to_path_buf(), diff);
+        let configs = [config];
+        let notices: Vec<(String, usize, String)> = review_notices(&result, &configs, &changed, |_| None)
+            .into_iter()
+            .map(|v| {
+                assert_eq!(v.rule_id, REVIEW_REQUIRED);
+                assert_eq!(v.severity, Severity::Info);
+                let file = v.file.file_name().unwrap().to_string_lossy().into_owned();
+                (file, v.line.unwrap(), v.message)
+            })
+            .collect();
+        assert_eq!(
+            notices,
+            vec![
+                (
+                    "app.js".to_string(),
+                    1,
+                    "suppresses `no-eval`; policy changes need approval from @security".to_string()
+                ),
+                (
+                    "baseline.toml".to_string(),
+                    8,
+                    "changes the config of `no-eval`; policy changes need approval from @security".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn review_notices_cover_catch_all_suppressions() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"[baseline]
+
+[[rule]]
+id = "no-eval"
+type = "banned-pattern"
+pattern = "eval("
+message = "No eval"
+owner = "@security"
+requires_review = true
+"#,
+        )
+        .unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        fs::write(
+            src.join("app.js"),
+            "eval(a); // baseline:allow-all\n// baseline:allow-next-line all\neval(b);\n/* baseline:ignore-start -- legacy */\neval(c);\n/* baseline:ignore-start other-rule */\n",
+        )
+        .unwrap();
+        let result = run_scan(&config, std::slice::from_ref(&src)).unwrap();
+
+        let mut diff = git_diff::DiffInfo::default();
+        diff.changed_lines.insert(PathBuf::from("src/app.js"), vec![1..=6]);
+        let changed = git_diff::ChangedLines::new(dir.path().to_path_buf(), diff);
+        let lines: Vec<(usize, String)> = review_notices(&result, &[config], &changed, |_| None)
+            .into_iter()
+            .map(|v| (v.line.unwrap(), v.message))
+            .collect();
+        let suppresses = "suppresses `no-eval`; policy changes need approval from @security".to_string();
+        assert_eq!(lines, vec![(1, suppresses.clone()), (2, suppresses.clone()), (4, suppresses)]);
+    }
+
+    #[test]
+    fn review_notices_diff_the_config_against_the_base() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        let previous = r#"include = ["rules/*.toml"]
+
+[baseline]
+plugins = ["team.toml"]
+
+[[rule]]
+id = "no-eval"
+type = "banned-pattern"
+pattern = "eval("
+message = "No eval"
+owner = "@security"
+requires_review = true
+
+[[rule]]
+id = "no-secrets"
+type = "banned-pattern"
+pattern = "SECRET"
+message = "No secrets"
+owner = "@platform"
+requires_review = true
+"#;
+        let current = r#"include = []
+
+[baseline]
+
+[[rule]]
+id = "no-eval"
+type = "banned-pattern"
+pattern = "eval("
+message = "No eval"
+owner = "@security"
+requires_review = true
+"#;
+        fs::write(&config, current).unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        fs::write(src.join("app.js"), "run();\n").unwrap();
+        let result = run_scan(&config, std::slice::from_ref(&src)).unwrap();
+
+        // Only the `include` line and the blank line left by `plugins` changed
+        let mut diff = git_diff::DiffInfo::default();
+        diff.changed_lines.insert(PathBuf::from("baseline.toml"), vec![1..=1, 4..=4]);
+        let changed = git_diff::ChangedLines::new(dir.path().to_path_buf(), diff);
+        let notices: Vec<(usize, String)> =
+            review_notices(&result, std::slice::from_ref(&config), &changed, |_| Some(previous.to_string()))
+                .into_iter()
+                .map(|v| (v.line.unwrap(), v.message))
+                .collect();
+        let approval = |who: &str| format!("; policy changes need approval from {}", who);
+        assert_eq!(
+            notices,
+            vec![
+                (1, format!("removes `no-secrets`{}", approval("@platform"))),
+                (1, format!("changes `include`, which can redefine `no-eval`{}", approval("@security"))),
+                (1, format!("changes `include`, which can redefine `no-secrets`{}", approval("@platform"))),
+                (1, format!("changes `plugins`, which can redefine `no-eval`{}", approval("@security"))),
+                (1, format!("changes `plugins`, which can redefine `no-secrets`{}", approval("@platform"))),
+            ]
+        );
+
+        // Without a base revision only edited blocks are reported
+        assert!(review_notices(&result, &[config], &changed, |_| None).is_empty());
+    }
+
+    #[test]
+    fn rule_blocks_span_subtables() {
+        let lines = vec![
+            "[baseline]",
+            "[[rule]]",
+            "id = 'budgeted' # legacy",
+            "[rule.budgets]",
+            "\"src\" = 3",
+            "[[rule]]",
+            "type = \"banned-pattern\"",
+            "id=\"last\"",
+        ];
+        assert_eq!(
+            rule_blocks(&lines),
+            vec![("budgeted".to_string(), 2..=5), ("last".to_string(), 6..=8)]
+        );
+        assert!(names_suppression("/* baseline:ignore-start a, budgeted */", "budgeted"));
+        assert!(!names_suppression("// baseline:allow-budgeted-2", "budgeted"));
+    }
+
+    #[test]
+    fn run_scan_no_violations() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "no-console"
+type = "banned-pattern"
+severity = "error"
+pattern = "console\\.log"
+regex = true
+message = "Do not use console.log"
+glob = "**/*.ts"
+"#,
+        )
+        .unwrap();
+
+        let src_dir = dir.path().join("src");
+        fs::create_dir(&src_dir).unwrap();
+        fs::write(src_dir.join("app.ts"), "doStuff();\n").unwrap();
+
+        let result = run_scan(&config, &[src_dir]).unwrap();
+        assert!(result.violations.is_empty());
+        assert_eq!(result.files_scanned, 1);
+    }
+
+    #[test]
+    fn run_scan_excludes_files() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+exclude = ["**/dist/**"]
+
+[[rule]]
+id = "no-console"
+type = "banned-pattern"
+severity = "error"
+pattern = "console\\.log"
+regex = true
+message = "no console"
+"#,
+        )
+        .unwrap();
+
+        // File in dist should be excluded
+        let dist_dir = dir.path().join("dist");
+        fs::create_dir(&dist_dir).unwrap();
+        fs::write(dist_dir.join("app.ts"), "console.log('hi');\n").unwrap();
+
+        let result = run_scan(&config, &[dir.path().to_path_buf()]).unwrap();
+        // The dist file should be excluded
+        for v in &result.violations {
+            assert!(!v.file.to_string_lossy().contains("dist"));
+        }
+    }
+
+    #[test]
+    fn run_scan_file_presence_rule() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "has-readme"
+type = "file-presence"
+severity = "error"
+required_files = ["README.md"]
+message = "README.md is required"
+"#,
+        )
+        .unwrap();
+
+        // No README.md in dir
+        let result = run_scan(&config, &[dir.path().to_path_buf()]).unwrap();
+        assert!(result.violations.iter().any(|v| v.rule_id == "has-readme"));
+    }
+
+    #[test]
+    fn run_scan_reports_unread_env_template_entries() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "env"
+type = "env-allowlist"
+severity = "error"
+env_file = ".env.example"
+message = "undeclared env var"
+"#,
+        )
+        .unwrap();
+        fs::write(dir.path().join(".env.example"), "API_URL=\nOLD_FLAG=\n").unwrap();
+        fs::write(dir.path().join("app.ts"), "fetch(process.env.API_URL + process.env.NEW_KEY);\n").unwrap();
+
+        // Twice, to check that the rule's record of reads doesn't outlive a scan
+        for _ in 0..2 {
+            let result = run_scan(&config, &[dir.path().to_path_buf()]).unwrap();
+            let mut messages: Vec<&str> = result.violations.iter().map(|v| v.message.as_str()).collect();
+            messages.sort();
+            assert_eq!(
+                messages,
+                [
+                    "undeclared env var: 'NEW_KEY' is not an allowed environment variable",
+                    "undeclared env var: 'OLD_FLAG' is declared in .env.example but never read",
+                ]
+            );
+        }
+    }
+
+    #[test]
+    fn run_scan_missing_config_errors() {
+        let result = run_scan(
+            Path::new("/nonexistent/baseline.toml"),
+            &[PathBuf::from(".")],
+        );
+        assert!(result.is_err());
+        assert!(matches!(result.err().unwrap(), ScanError::ConfigRead(_)));
+    }
+
+    #[test]
+    fn run_scan_invalid_config_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(&config, "this is not valid toml [[[").unwrap();
+
+        let result = run_scan(&config, &[dir.path().to_path_buf()]);
+        assert!(result.is_err());
+        assert!(matches!(result.err().unwrap(), ScanError::ConfigParse(_)));
+    }
+
+    #[test]
+    fn run_scan_with_ratchet_rule() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "legacy-api"
+type = "ratchet"
+severity = "warning"
+pattern = "legacyCall"
+max_count = 5
+message = "legacy api usage"
+"#,
+        )
+        .unwrap();
+
+        let src_dir = dir.path().join("src");
+        fs::create_dir(&src_dir).unwrap();
+        fs::write(src_dir.join("app.ts"), "legacyCall();\nlegacyCall();\n").unwrap();
+
+        let result = run_scan(&config, &[src_dir]).unwrap();
+        // 2 matches, max 5 -> suppressed
+        assert!(result.violations.is_empty());
+        assert_eq!(result.ratchet_counts["legacy-api"], (2, 5));
+    }
+
+    #[test]
+    fn run_scan_ratchet_tracks_rule() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let config = dir.path().join("baseline.toml");
+        let write_config = |max_count: usize| {
+            fs::write(
+                &config,
+                format!(
+                    r#"
+[baseline]
+
+[[rule]]
+id = "no-any"
+type = "banned-pattern"
+severity = "error"
+pattern = ": any"
+message = "no any"
+
+[[rule]]
+id = "ratchet-any"
+type = "ratchet"
+severity = "warning"
+track = "no-any"
+max_count = {}
+message = "any budget"
+"#,
+                    max_count
+                ),
+            )
+            .unwrap();
+        };
+
+        let src_dir = dir.path().join("src");
+        fs::create_dir(&src_dir).unwrap();
+        fs::write(
+            src_dir.join("app.ts"),
+            "let a: any;\nlet b: any;\nlet c: any;\n",
+        )
+        .unwrap();
+
+        write_config(3);
+        let result = run_scan(&config, std::slice::from_ref(&src_dir)).unwrap();
+        assert!(result.violations.is_empty());
+        assert_eq!(result.ratchet_counts["ratchet-any"], (3, 3));
+
+        write_config(2);
+        let result = run_scan(&config, std::slice::from_ref(&src_dir)).unwrap();
+        assert_eq!(result.violations.len(), 3);
+        assert!(result.violations.iter().all(|v| v.rule_id == "ratchet-any" && v.severity == Severity::Warning));
+
+        let baseline = run_baseline(&config, &[src_dir]).unwrap();
+        assert_eq!(baseline.entries.len(), 1);
+        assert_eq!(baseline.entries[0].count, 3);
+        assert_eq!(baseline.entries[0].track.as_deref(), Some("no-any"));
+    }
+
+    #[test]
+    fn ratchet_track_must_name_a_rule() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "ratchet-any"
+type = "ratchet"
+track = "no-such-rule"
+max_count = 3
+"#,
+        )
+        .unwrap();
+        let err = match run_scan(&config, &[dir.path().to_path_buf()]) {
+            Err(e) => e,
+            Ok(_) => panic!("expected an unknown track id to fail"),
+        };
+        assert!(err.to_string().contains("no-such-rule"), "{}", err);
+    }
+
+    #[test]
+    fn run_scan_ratchet_deadline_sets_budget() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        let write_config = |deadline: &str| {
+            fs::write(
+                &config,
+                format!(
+                    r#"
+[baseline]
+
+[[rule]]
+id = "legacy-api"
+type = "ratchet"
+pattern = "legacyCall"
+max_count = 5
+deadline = "{}"
+target = 1
+message = "legacy api usage"
+"#,
+                    deadline
+                ),
+            )
+            .unwrap();
+        };
+
+        let src_dir = dir.path().join("src");
+        fs::create_dir(&src_dir).unwrap();
+        fs::write(src_dir.join("app.ts"), "legacyCall();\nlegacyCall();\n").unwrap();
+
+        write_config("2999-01-01");
+        let result = run_scan(&config, std::slice::from_ref(&src_dir)).unwrap();
+        assert!(result.violations.is_empty());
+        assert_eq!(result.ratchet_counts["legacy-api"], (2, 5));
+        assert!(result.ratchet_deadlines["legacy-api"].days_remaining > 0);
+
+        write_config("2000-01-01");
+        let result = run_scan(&config, std::slice::from_ref(&src_dir)).unwrap();
+        assert_eq!(result.violations.len(), 2);
+        assert_eq!(result.ratchet_counts["legacy-api"], (2, 1));
+
+        write_config("01/09/2025");
+        match run_scan(&config, std::slice::from_ref(&src_dir)) {
+            Err(e) => assert!(e.to_string().contains("YYYY-MM-DD"), "{}", e),
+            Ok(_) => panic!("expected an invalid deadline to fail"),
+        }
+    }
+
+    #[test]
+    fn is_suppressed_stacked_next_line_directives() {
+        let lines = vec![
+            "// baseline:allow-next-line rule-a",
+            "// baseline:allow-next-line rule-b",
+            "bad();",
+        ];
+        let a = ("baseline:allow-rule-a", "baseline:allow-next-line rule-a");
+        assert!(suppressed_by(&lines, 3, a.0, a.1, 1).is_some());
+        assert!(suppressed_by(&lines, 3, "baseline:allow-rule-b", "baseline:allow-next-line rule-b", 1).is_some());
+
+        // A non-directive line breaks the stack
+        let lines = vec!["// baseline:allow-next-line rule-a", "ok();", "bad();"];
+        assert!(suppressed_by(&lines, 3, a.0, a.1, 1).is_none());
+    }
+
+    #[test]
+    fn is_suppressed_jsx_comment_syntax() {
+        let m = ("baseline:allow-dark-mode", "baseline:allow-next-line dark-mode");
+        let lines = vec!["  {/* baseline:allow-next-line dark-mode*/}", "  <div className=\"bg-white\" />"];
+        assert!(suppressed_by(&lines, 2, m.0, m.1, 1).is_some());
+
+        // Markers match whole rule ids only
+        let lines = vec!["// baseline:allow-next-line dark-mode-strict", "bad();"];
+        assert!(suppressed_by(&lines, 2, m.0, m.1, 1).is_none());
+    }
+
+    #[test]
+    fn is_suppressed_multiline_jsx_tag() {
+        let m = ("baseline:allow-dark-mode", "baseline:allow-next-line dark-mode");
+        let lines = vec![
+            "{/* baseline:allow-next-line dark-mode */}",
+            "<div",
+            "  onClick={() => go()}",
+            "  className=\"bg-white\"",
+            ">",
+            "  <span className=\"bg-white\" />",
+        ];
+        // Attribute line resolves to the directive above the tag
+        assert!(suppressed_by(&lines, 4, m.0, m.1, 1).is_some());
+        // A child after the tag closed is not covered
+        assert!(suppressed_by(&lines, 6, m.0, m.1, 1).is_none());
+
+        // Same-line marker on an attribute line covers the tag's reported line
+        let lines = vec![
+            "<div",
+            "  className=\"bg-white\" // baseline:allow-dark-mode",
+            "/>",
+        ];
+        assert!(suppressed_by(&lines, 1, m.0, m.1, 1).is_some());
+
+        // A directive directly above an attribute line covers that line only
+        let lines = vec![
+            "<div",
+            "  onClick={() => go()}",
+            "  // baseline:allow-next-line dark-mode",
+            "  className=\"bg-white\"",
+            ">",
+        ];
+        assert_eq!(suppressed_by(&lines, 4, m.0, m.1, 1), Some(3));
+        assert!(suppressed_by(&lines, 2, m.0, m.1, 1).is_none());
+    }
+
+    #[test]
+    fn ignore_regions_nest_and_filter_rules() {
+        let lines = vec![
+            "a();",
+            "// baseline:ignore-start",
+            "b();",
+            "/* baseline:ignore-start no-console, no-debug -- vendored snippet */",
+            "c();",
+            "// baseline:ignore-end",
+            "d();",
+            "// baseline:ignore-end",
+            "e();",
+        ];
+        let regions = ignore_regions(&lines);
+        assert_eq!(
+            regions,
+            [
+                IgnoreRegion {
+                    start: 4,
+                    end: 6,
+                    rules: vec!["no-console".into(), "no-debug".into()],
+                },
+                IgnoreRegion {
+                    start: 2,
+                    end: 8,
+                    rules: vec![],
+                },
+            ]
+        );
+        let covered = |line, rule| regions.iter().any(|r: &IgnoreRegion| r.covers(line, rule));
+        assert!(!covered(1, "no-console"));
+        assert!(covered(3, "any-rule"));
+        assert!(covered(5, "no-debug"));
+        assert!(!covered(9, "no-console"));
+
+        let only = ignore_regions(&["{/* baseline:ignore-start dark-mode */}", "x", "{/* baseline:ignore-end */}", "y"]);
+        assert!(only[0].covers(2, "dark-mode"));
+        assert!(!only[0].covers(2, "dark-mode-strict"));
+        assert!(!only[0].covers(4, "dark-mode"));
+    }
+
+    #[test]
+    fn ignore_regions_generated_blocks() {
+        let lines = vec![
+            "hand();",
+            "// @generated by protoc",
+            "gen();",
+            "// @end-generated",
+            "hand();",
+            "/* @generated */",
+            "gen();",
+        ];
+        let regions = ignore_regions(&lines);
+        assert_eq!(regions.len(), 1);
+        assert!(regions[0].covers(3, "anything"));
+        assert!(!regions.iter().any(|r| r.covers(5, "anything")));
+        // An unclosed `@generated` is a file header, not a region
+        assert!(!regions.iter().any(|r| r.covers(7, "anything")));
+    }
+
+    #[test]
+    fn run_rules_on_content_skips_ignore_regions() {
+        let rules = vec![TomlRule {
+            id: "no-console".into(),
+            rule_type: "banned-pattern".into(),
+            pattern: Some("console.log".into()),
+            message: "no console.log".into(),
+            ..Default::default()
+        }];
+        let built = build_rules(&rules).unwrap();
+        let path = PathBuf::from("test.ts");
+        let content = "console.log(1);\n// baseline:ignore-start other-rule\nconsole.log(2);\n// baseline:ignore-end\n// baseline:ignore-start no-console\nconsole.log(3);\n// baseline:ignore-end\n";
+        let violations = run_rules_on_content(&built.rule_groups, &path, content, "test.ts", "test.ts", 1);
+        let lines: Vec<_> = violations.iter().map(|v| v.line).collect();
+        assert_eq!(lines, [Some(1), Some(3)]);
+    }
+
+    #[test]
+    fn is_suppressed_lookback_window() {
+        let m = ("baseline:allow-rule-a", "baseline:allow-next-line rule-a");
+        let lines = vec!["// baseline:allow-next-line rule-a", "ok();", "bad();"];
+        assert!(suppressed_by(&lines, 3, m.0, m.1, 1).is_none());
+        assert!(suppressed_by(&lines, 3, m.0, m.1, 2).is_some());
+    }
+
+    #[test]
+    fn run_scan_respects_suppression_lookback_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+suppression_lookback = 3
+
+[[rule]]
+id = "no-console"
+type = "banned-pattern"
+severity = "error"
+glob = "**/*.ts"
+pattern = "console.log"
+message = "no console.log"
+"#,
+        )
+        .unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        fs::write(
+            src.join("a.ts"),
+            "// baseline:allow-next-line no-console\nfoo();\nbar();\nconsole.log(1);\nconsole.log(2);\n",
+        )
+        .unwrap();
+
+        let result = run_scan(&config, &[src]).unwrap();
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.violations[0].line, Some(5));
+    }
+
+    #[test]
+    fn load_config_merges_includes_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"include = ["guardrails.d/*.toml", "extra.toml", "guardrails.d/a.toml"]
+
+[baseline]
+exclude = ["dist/**"]
+
+[[rule]]
+id = "root-rule"
+type = "banned-pattern"
+pattern = "x"
+message = "m"
+"#,
+        )
+        .unwrap();
+        let inc = dir.path().join("guardrails.d");
+        fs::create_dir_all(inc.join("nested")).unwrap();
+        let rule = |id: &str| {
+            format!("[[rule]]\nid = \"{}\"\ntype = \"banned-pattern\"\npattern = \"x\"\nmessage = \"m\"\n", id)
+        };
+        fs::write(inc.join("b.toml"), rule("b-rule")).unwrap();
+        fs::write(
+            inc.join("a.toml"),
+            format!("[baseline]\nexclude = [\"gen/**\"]\n\n{}", rule("a-rule")),
+        )
+        .unwrap();
+        fs::write(inc.join("nested/c.toml"), rule("nested-rule")).unwrap();
+        fs::write(dir.path().join("extra.toml"), rule("extra-rule")).unwrap();
+
+        let loaded = load_config(&config).unwrap();
+        let ids: Vec<&str> = loaded.toml.rule.iter().map(|r| r.id.as_str()).collect();
+        // `*` doesn't descend into subdirectories; a.toml is merged once
+        assert_eq!(ids, ["root-rule", "a-rule", "b-rule", "extra-rule"]);
+        assert_eq!(loaded.toml.baseline.excludes(), ["dist/**", "gen/**"]);
+    }
+
+    #[test]
+    fn run_scan_default_excludes() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        let rule = r#"
+[[rule]]
+id = "no-console"
+type = "banned-pattern"
+severity = "error"
+glob = "**/*.js"
+pattern = "console.log"
+message = "no console.log"
+"#;
+        for f in ["src/a.js", "node_modules/dep/index.js", "coverage/x.js", "src/vendor.min.js", "gen/b.js"] {
+            let path = dir.path().join(f);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "console.log(1);\n").unwrap();
+        }
+        let scan = |baseline: &str| {
+            fs::write(&config, format!("[baseline]\n{}\n{}", baseline, rule)).unwrap();
+            let mut files: Vec<String> = run_scan(&config, &[dir.path().to_path_buf()])
+                .unwrap()
+                .violations
+                .iter()
+                .map(|v| v.file.strip_prefix(dir.path()).unwrap().display().to_string())
+                .collect();
+            files.sort();
+            files
+        };
+
+        assert_eq!(scan(""), ["gen/b.js", "src/a.js"]);
+        assert_eq!(scan("exclude_extend = [\"gen/**\"]"), ["src/a.js"]);
+        assert_eq!(scan("exclude = [\"gen/**\"]").len(), 4);
+        assert_eq!(scan("no_default_excludes = true").len(), 5);
+    }
+
+    #[test]
+    fn disabled_rules_are_dropped_and_listed() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+extends = ["shadcn-strict"]
+
+[[rule]]
+id = "use-theme-tokens"
+enabled = false
+
+[[rule]]
+id = "no-console"
+type = "banned-pattern"
+pattern = "console.log"
+enabled = false
+"#,
+        )
+        .unwrap();
+
+        let loaded = load_config(&config).unwrap();
+        assert!(loaded.rules.iter().all(|r| r.enabled));
+        assert!(!loaded.rules.iter().any(|r| r.id == "use-theme-tokens"));
+        let disabled: Vec<&str> = loaded.disabled.iter().map(|r| r.id.as_str()).collect();
+        assert_eq!(disabled, ["use-theme-tokens", "no-console"]);
+
+        let listed = list_rules(&config).unwrap();
+        assert_eq!(listed.len(), loaded.rules.len() + 2);
+        assert_eq!(listed.last().unwrap().id, "no-console");
+
+        fs::write(dir.path().join("a.tsx"), "<div className=\"bg-white\" />\n").unwrap();
+        let result = run_scan(&config, &[dir.path().to_path_buf()]).unwrap();
+        assert!(!result.violations.iter().any(|v| v.rule_id == "use-theme-tokens"));
+    }
+
+    #[test]
+    fn load_config_include_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+
+        fs::write(&config, "include = [\"missing.toml\"]\n[baseline]\n").unwrap();
+        let err = load_config(&config).err().unwrap();
+        assert!(matches!(err, ScanError::IncludeRead(ref p, _) if p.ends_with("missing.toml")));
+
+        // Scalar settings belong to the root config
+        fs::write(dir.path().join("part.toml"), "[baseline]\nmax_line_length = 10\n").unwrap();
+        fs::write(&config, "include = [\"part.toml\"]\n[baseline]\n").unwrap();
+        let err = load_config(&config).err().unwrap();
+        assert!(matches!(err, ScanError::IncludeParse(..)));
+        assert!(err.to_string().contains("part.toml"));
+    }
+
+    #[test]
+    fn apply_ratchet_budgets_per_scope() {
+        let make = |file: &str| Violation {
+            rule_id: "legacy".into(),
+            severity: Severity::Warning,
+            file: PathBuf::from(file),
+            line: Some(1),
+            column: None,
+            message: "m".into(),
+            suggest: Vec::new(),
+            source_line: None,
+            fix: None,
+            origin: None,
+            enclosing: None,
+        };
+        let mut violations = vec![
+            make("./apps/web/a.ts"),
+            make("apps/web/b.ts"),
+            make("packages/ui/c.ts"),
+            make("packages/ui/nested/d.ts"),
+            make("scripts/e.ts"),
+        ];
+        let mut budgets = HashMap::new();
+        budgets.insert(
+            "legacy".to_string(),
+            ScopedBudget {
+                scopes: vec![
+                    ("packages/ui/nested".to_string(), 1),
+                    ("apps/web".to_string(), 10),
+                    ("packages/ui".to_string(), 0),
+                ],
+                rest: 5,
+            },
+        );
+
+        let counts = apply_ratchet_budgets(&mut violations, &budgets);
+        assert_eq!(counts["legacy:apps/web"], (2, 10));
+        assert_eq!(counts["legacy:packages/ui"], (1, 0));
+        assert_eq!(counts["legacy:packages/ui/nested"], (1, 1));
+        assert_eq!(counts["legacy"], (1, 5));
+
+        // Only the over-budget scope keeps its violations
+        assert_eq!(violations.len(), 1);
+        assert_eq!(violations[0].file, PathBuf::from("packages/ui/c.ts"));
+    }
+
+    #[test]
+    fn run_scan_stdin_ratchet_budgets_from_config() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "legacy-api"
+type = "ratchet"
+pattern = "legacyCall"
+max_count = 0
+message = "legacy api usage"
+
+[rule.budgets]
+"apps/web" = 1
+"packages/ui" = 0
+"#,
+        )
+        .unwrap();
+
+        let content = "legacyCall();\nlegacyCall();\n";
+        let result = run_scan_stdin(&config, content, "apps/web/page.ts").unwrap();
+        assert_eq!(result.ratchet_counts["legacy-api:apps/web"], (2, 1));
+        assert_eq!(result.ratchet_counts["legacy-api:packages/ui"], (0, 0));
+        assert_eq!(result.ratchet_counts["legacy-api"], (0, 0));
+        assert_eq!(result.violations.len(), 2);
+    }
+
+    #[test]
+    fn split_ratchet_key_parses_scope() {
+        assert_eq!(split_ratchet_key("legacy"), ("legacy", None));
+        assert_eq!(split_ratchet_key("legacy:apps/web"), ("legacy", Some("apps/web")));
+    }
+
+    // ── run_scan_stdin tests ──
+
+    #[test]
+    fn run_scan_stdin_finds_violations() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "no-console"
+type = "banned-pattern"
+severity = "error"
+pattern = "console\\.log"
+regex = true
+message = "no console.log"
+"#,
+        )
+        .unwrap();
+
+        let result =
+            run_scan_stdin(&config, "console.log('hello');\nfoo();\n", "test.ts").unwrap();
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.files_scanned, 1);
+    }
+
+    #[test]
+    fn run_scan_stdin_no_violations() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "no-console"
+type = "banned-pattern"
+severity = "error"
+pattern = "console\\.log"
+regex = true
+message = "no console.log"
+glob = "**/*.ts"
+"#,
+        )
+        .unwrap();
+
+        let result = run_scan_stdin(&config, "doStuff();\n", "app.ts").unwrap();
+        assert!(result.violations.is_empty());
+    }
+
+    #[test]
+    fn run_scan_stdin_glob_filters_filename() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "no-console"
+type = "banned-pattern"
+severity = "error"
+pattern = "console\\.log"
+regex = true
+message = "no console.log"
+glob = "**/*.tsx"
+"#,
+        )
+        .unwrap();
+
+        // File doesn't match glob
+        let result =
+            run_scan_stdin(&config, "console.log('hello');\n", "app.rs").unwrap();
+        assert!(result.violations.is_empty());
+    }
+
+    // ── run_baseline tests ──
+
+    #[test]
+    fn run_baseline_counts_ratchet_matches() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "legacy-api"
+type = "ratchet"
+severity = "warning"
+pattern = "legacyCall"
+max_count = 100
+message = "legacy usage"
+"#,
+        )
+        .unwrap();
+
+        let src_dir = dir.path().join("src");
+        fs::create_dir(&src_dir).unwrap();
+        fs::write(
+            src_dir.join("app.ts"),
+            "legacyCall();\nlegacyCall();\nlegacyCall();\n",
+        )
+        .unwrap();
+
+        let result = run_baseline(&config, &[src_dir]).unwrap();
+        assert_eq!(result.entries.len(), 1);
+        assert_eq!(result.entries[0].rule_id, "legacy-api");
+        assert_eq!(result.entries[0].count, 3);
+        assert_eq!(result.files_scanned, 1);
+    }
+
+    #[test]
+    fn run_baseline_skips_non_ratchet_rules() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "no-console"
+type = "banned-pattern"
+severity = "error"
+pattern = "console\\.log"
+regex = true
+message = "no console"
+
+[[rule]]
+id = "legacy-api"
+type = "ratchet"
+severity = "warning"
+pattern = "legacyCall"
+max_count = 100
+message = "legacy usage"
+"#,
+        )
+        .unwrap();
+
+        let src_dir = dir.path().join("src");
+        fs::create_dir(&src_dir).unwrap();
+        fs::write(src_dir.join("app.ts"), "console.log('hi');\nlegacyCall();\n").unwrap();
+
+        let result = run_baseline(&config, &[src_dir]).unwrap();
+        // Only ratchet rules appear in baseline
+        assert_eq!(result.entries.len(), 1);
+        assert_eq!(result.entries[0].rule_id, "legacy-api");
+    }
+
+    // ── collect_files tests ──
+
+    #[test]
+    fn collect_files_single_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("test.ts");
+        fs::write(&file, "content").unwrap();
+
+        let empty_glob = build_glob_set(&[]).unwrap();
+        let files = collect_files(std::slice::from_ref(&file), &empty_glob, &WalkPolicy::default());
+        assert_eq!(files.len(), 1);
+        assert_eq!(files[0], file);
+    }
+
+    #[test]
+    fn collect_files_directory_walk() {
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join("sub");
+        fs::create_dir(&sub).unwrap();
+        fs::write(sub.join("a.ts"), "a").unwrap();
+        fs::write(sub.join("b.ts"), "b").unwrap();
+
+        let empty_glob = build_glob_set(&[]).unwrap();
+        let files = collect_files(&[dir.path().to_path_buf()], &empty_glob, &WalkPolicy::default());
+        assert_eq!(files.len(), 2);
+    }
+
+    #[test]
+    fn collect_files_expands_glob_targets() {
+        let dir = tempfile::tempdir().unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("ui")).unwrap();
+        fs::write(src.join("a.tsx"), "a").unwrap();
+        fs::write(src.join("ui/b.tsx"), "b").unwrap();
+        fs::write(src.join("ui/c.ts"), "c").unwrap();
+        fs::write(dir.path().join("d.tsx"), "d").unwrap();
+
+        let empty_glob = build_glob_set(&[]).unwrap();
+        let collect = |pattern: &str| {
+            let mut files: Vec<_> = collect_files(&[dir.path().join(pattern)], &empty_glob, &WalkPolicy::default())
+                .into_iter()
+                .map(|f| f.strip_prefix(dir.path()).unwrap().to_string_lossy().replace('\\', "/"))
+                .collect();
+            files.sort();
+            files
+        };
+        assert_eq!(collect("src/**/*.tsx"), ["src/a.tsx", "src/ui/b.tsx"]);
+        assert_eq!(collect("src/*.tsx"), ["src/a.tsx"]);
+        assert_eq!(collect("src/ui/*.{ts,tsx}"), ["src/ui/b.tsx", "src/ui/c.ts"]);
+        assert_eq!(collect("*.tsx"), ["d.tsx"]);
+        assert!(collect("lib/**/*.tsx").is_empty());
+    }
+
+    #[test]
+    fn pruned_dirs_come_from_whole_directory_excludes() {
+        let excludes: Vec<String> = ["**/.next/**", "apps/legacy/**", "**/*.min.js", "**", "src/**/*.gen.ts"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        assert_eq!(pruned_dirs(&excludes), ["**/.next/", "**/apps/legacy/"]);
+    }
+
+    #[test]
+    fn collect_files_prunes_excluded_dirs_and_honors_max_depth() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("web/out/cache")).unwrap();
+        fs::create_dir_all(dir.path().join("web/src/ui")).unwrap();
+        fs::write(dir.path().join("web/out/cache/chunk.js"), "x").unwrap();
+        fs::write(dir.path().join("web/src/ui/button.tsx"), "x").unwrap();
+        fs::write(dir.path().join("web/page.tsx"), "x").unwrap();
+        fs::write(dir.path().join("root.ts"), "x").unwrap();
+
+        // The walker itself skips pruned directories; the exclude set is empty.
+        let empty_glob = build_glob_set(&[]).unwrap();
+        let collect = |policy: &WalkPolicy| {
+            let mut files: Vec<_> = collect_files(&[dir.path().to_path_buf()], &empty_glob, policy)
+                .into_iter()
+                .map(|f| f.strip_prefix(dir.path()).unwrap().to_string_lossy().replace('\\', "/"))
+                .collect();
+            files.sort();
+            files
+        };
+        let pruned = WalkPolicy {
+            pruned_dirs: pruned_dirs(&["**/out/**".into()]),
+            ..Default::default()
+        };
+        assert_eq!(collect(&pruned), ["root.ts", "web/page.tsx", "web/src/ui/button.tsx"]);
+
+        let shallow = WalkPolicy {
+            max_depth: Some(2),
+            ..Default::default()
+        };
+        assert_eq!(collect(&shallow), ["root.ts", "web/page.tsx"]);
+    }
+
+    #[test]
+    fn glob_targets_split_at_the_first_glob_component() {
+        let (root, matcher) = glob_target(Path::new("no/such/dir/**/*.tsx")).unwrap();
+        assert_eq!(root, Path::new("no/such/dir"));
+        assert!(matcher.is_match("a/b.tsx"));
+        assert_eq!(glob_target(Path::new("*.ts")).unwrap().0, Path::new("."));
+        assert!(glob_target(Path::new("src/app")).is_none());
+    }
+
+    #[test]
+    fn collect_files_excludes_patterns() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("keep.ts"), "keep").unwrap();
+        fs::write(dir.path().join("skip.log"), "skip").unwrap();
+
+        let exclude = build_glob_set(&["*.log".into()]).unwrap();
+        let files = collect_files(&[dir.path().to_path_buf()], &exclude, &WalkPolicy::default());
+        assert!(files.iter().all(|f| !f.to_string_lossy().ends_with(".log")));
+        assert!(files.iter().any(|f| f.to_string_lossy().ends_with(".ts")));
+    }
+
+    #[test]
+    fn collect_files_prunes_vendored_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("vendor")).unwrap();
+        fs::write(dir.path().join("vendor").join("lib.js"), "x").unwrap();
+        fs::write(dir.path().join("app.ts"), "x").unwrap();
+
+        let policy = WalkPolicy {
+            vendored_dirs: vec!["vendor".into()],
+            ..Default::default()
+        };
+        let empty_glob = build_glob_set(&[]).unwrap();
+        let files = collect_files(&[dir.path().to_path_buf()], &empty_glob, &policy);
+        assert_eq!(files.len(), 1);
+        assert!(files[0].ends_with("app.ts"));
+    }
+
+    #[test]
+    fn collect_files_submodule_policies() {
+        let dir = tempfile::tempdir().unwrap();
+        let sub = dir.path().join("libs").join("shared");
+        fs::create_dir_all(&sub).unwrap();
+        fs::write(sub.join(".git"), "gitdir: ../../.git/modules/shared\n").unwrap();
+        fs::write(sub.join("index.ts"), "x").unwrap();
+        fs::write(dir.path().join("app.ts"), "x").unwrap();
+        let empty_glob = build_glob_set(&[]).unwrap();
+        let targets = [dir.path().to_path_buf()];
+
+        let scan_all = collect_files(&targets, &empty_glob, &WalkPolicy::default());
+        assert_eq!(scan_all.len(), 2);
+
+        let skip = WalkPolicy {
+            submodules: SubmodulePolicy::Skip,
+            ..Default::default()
+        };
+        assert_eq!(collect_files(&targets, &empty_glob, &skip).len(), 1);
+
+        // Nested without a config in the submodule behaves like `scan`
+        let nested = WalkPolicy {
+            submodules: SubmodulePolicy::Nested,
+            nested_config_name: Some("baseline.toml".into()),
+            ..Default::default()
+        };
+        assert_eq!(collect_files(&targets, &empty_glob, &nested).len(), 2);
+
+        fs::write(sub.join("baseline.toml"), "[baseline]\n").unwrap();
+        let walked = walk_files(&targets, &empty_glob, &nested);
+        assert_eq!(walked.files.len(), 1);
+        assert_eq!(walked.nested, vec![(sub.clone(), sub.join("baseline.toml"))]);
+    }
+
+    #[test]
+    fn run_scan_nested_submodule_uses_own_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+submodules = "nested"
+
+[[rule]]
+id = "no-todo"
+type = "banned-pattern"
+pattern = "TODO"
+glob = "**/*.ts"
+message = "no TODO"
+"#,
+        )
+        .unwrap();
+
+        let sub = dir.path().join("shared");
+        fs::create_dir(&sub).unwrap();
+        fs::write(sub.join(".git"), "gitdir: ../.git/modules/shared\n").unwrap();
+        fs::write(
+            sub.join("baseline.toml"),
+            r#"
+[baseline]
+
+[[rule]]
+id = "no-fixme"
+type = "banned-pattern"
+pattern = "FIXME"
+glob = "**/*.ts"
+message = "no FIXME"
+"#,
+        )
+        .unwrap();
+        fs::write(sub.join("lib.ts"), "// TODO\n// FIXME\n").unwrap();
+        fs::write(dir.path().join("app.ts"), "// TODO\n// FIXME\n").unwrap();
+
+        let result = run_scan(&config, &[dir.path().to_path_buf()]).unwrap();
+        let mut found: Vec<(String, bool)> = result
+            .violations
+            .iter()
+            .map(|v| (v.rule_id.clone(), v.file.starts_with(&sub)))
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            vec![("no-fixme".to_string(), true), ("no-todo".to_string(), false)]
+        );
+    }
+
+    // ── run_scan with presets ──
+
+    #[test]
+    fn run_scan_with_preset() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+extends = ["shadcn-strict"]
+"#,
+        )
+        .unwrap();
+
+        let src_dir = dir.path().join("src");
+        fs::create_dir(&src_dir).unwrap();
+        fs::write(src_dir.join("app.tsx"), "export default function App() { return <div>hi</div>; }\n").unwrap();
+
+        let result = run_scan(&config, &[src_dir]).unwrap();
+        // Just verify it doesn't error
+        assert!(result.rules_loaded > 0);
+    }
+
+    // ── run_scan with plugins ──
+
+    #[test]
+    fn run_scan_with_plugin() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let plugin_path = dir.path().join("custom-rules.toml");
+        fs::write(
+            &plugin_path,
+            r#"
+[[rule]]
+id = "no-todo"
+type = "banned-pattern"
+severity = "warning"
+pattern = "TODO"
+message = "No TODOs allowed"
+"#,
+        )
+        .unwrap();
+
         let config = dir.path().join("baseline.toml");
         fs::write(
             &config,
-            r#"
+            format!(
+                r#"
 [baseline]
-
-[[rule]]
-id = "no-console"
-type = "banned-pattern"
-severity = "error"
-pattern = "console\\.log"
-regex = true
-message = "Do not use console.log"
+plugins = ["{}"]
 "#,
+                plugin_path.display()
+            ),
         )
         .unwrap();
 
-        // Write a source file
         let src_dir = dir.path().join("src");
         fs::create_dir(&src_dir).unwrap();
-        fs::write(src_dir.join("app.ts"), "console.log('hi');\nfoo();\n").unwrap();
+        fs::write(src_dir.join("app.ts"), "// TODO: fix this\n").unwrap();
 
         let result = run_scan(&config, &[src_dir]).unwrap();
-        assert_eq!(result.violations.len(), 1);
-        assert_eq!(result.violations[0].rule_id, "no-console");
-        assert_eq!(result.files_scanned, 1);
-        assert_eq!(result.rules_loaded, 1);
+        assert!(result.violations.iter().any(|v| v.rule_id == "no-todo"));
+        let meta = rule_for(&result, &result.violations[0]).unwrap();
+        assert_eq!(meta.origin, RuleOrigin::Plugin(plugin_path));
     }
 
     #[test]
-    fn run_scan_no_violations() {
+    fn scoped_presets_honor_exclude_paths_and_nesting() {
         let dir = tempfile::tempdir().unwrap();
-
         let config = dir.path().join("baseline.toml");
         fs::write(
             &config,
             r#"
 [baseline]
 
-[[rule]]
-id = "no-console"
-type = "banned-pattern"
-severity = "error"
-pattern = "console\\.log"
-regex = true
-message = "Do not use console.log"
-glob = "**/*.ts"
+[[baseline.scoped]]
+preset = "nextjs"
+path = "apps/web"
+exclude_paths = ["legacy"]
+
+[[baseline.scoped]]
+preset = "nextjs"
+path = "apps/web/admin"
 "#,
         )
         .unwrap();
+        for sub in ["apps/web/legacy", "apps/web/admin", "apps/api"] {
+            fs::create_dir_all(dir.path().join(sub)).unwrap();
+        }
+        let img = "export const A = () => <img src=\"/a.png\" />;\n";
+        for file in [
+            "apps/web/page.tsx",
+            "apps/web/legacy/old.tsx",
+            "apps/web/admin/panel.tsx",
+            "apps/api/view.tsx",
+        ] {
+            fs::write(dir.path().join(file), img).unwrap();
+        }
 
-        let src_dir = dir.path().join("src");
-        fs::create_dir(&src_dir).unwrap();
-        fs::write(src_dir.join("app.ts"), "doStuff();\n").unwrap();
-
-        let result = run_scan(&config, &[src_dir]).unwrap();
-        assert!(result.violations.is_empty());
-        assert_eq!(result.files_scanned, 1);
+        let result = run_scan(&config, &[dir.path().to_path_buf()]).unwrap();
+        let mut hits: Vec<(String, String)> = result
+            .violations
+            .iter()
+            .filter(|v| v.rule_id == "use-next-image")
+            .map(|v| {
+                let file = v.file.strip_prefix(dir.path()).unwrap_or(&v.file);
+                let meta = rule_for(&result, v).unwrap();
+                (file.display().to_string(), meta.origin.to_string())
+            })
+            .collect();
+        hits.sort();
+        assert_eq!(
+            hits,
+            [
+                (
+                    "apps/web/admin/panel.tsx".to_string(),
+                    "preset nextjs scoped to apps/web/admin".to_string()
+                ),
+                (
+                    "apps/web/page.tsx".to_string(),
+                    "preset nextjs scoped to apps/web".to_string()
+                ),
+            ]
+        );
     }
 
     #[test]
-    fn run_scan_excludes_files() {
+    fn run_scan_stdin_reuses_cached_rules() {
         let dir = tempfile::tempdir().unwrap();
 
         let config = dir.path().join("baseline.toml");
@@ -1598,139 +5835,453 @@ glob = "**/*.ts"
             &config,
             r#"
 [baseline]
-exclude = ["**/dist/**"]
+
+[[baseline.scoped]]
+preset = "security"
+path = "cache-probe-4150"
 
 [[rule]]
-id = "no-console"
+id = "no-cache-probe"
 type = "banned-pattern"
 severity = "error"
-pattern = "console\\.log"
+pattern = "cacheProbe4150\\("
 regex = true
-message = "no console"
+message = "no cacheProbe4150"
 "#,
         )
         .unwrap();
+        // File-presence rules check the tree and aren't compiled rules
+        let preset_rules = load_config(&config)
+            .unwrap()
+            .rules
+            .iter()
+            .filter(|r| r.origin != RuleOrigin::User && r.rule_type != "file-presence")
+            .count();
+        assert!(preset_rules > 0);
+
+        let first = run_scan_stdin(&config, "cacheProbe4150();\n", "a.ts").unwrap();
+        assert_eq!(first.violations.len(), 1);
+        assert_eq!(first.timings.cached_rules, 0);
+
+        // Preset rules are reused; the user's own rule is rebuilt
+        let second = run_scan_stdin(&config, "cacheProbe4150();\n", "b.ts").unwrap();
+        assert_eq!(second.violations.len(), 1);
+        assert_eq!(second.timings.cached_rules, preset_rules);
+    }
 
-        // File in dist should be excluded
-        let dist_dir = dir.path().join("dist");
-        fs::create_dir(&dist_dir).unwrap();
-        fs::write(dist_dir.join("app.ts"), "console.log('hi');\n").unwrap();
-
-        let result = run_scan(&config, &[dir.path().to_path_buf()]).unwrap();
-        // The dist file should be excluded
-        for v in &result.violations {
-            assert!(!v.file.to_string_lossy().contains("dist"));
-        }
+    #[test]
+    fn rule_cache_reuses_only_unchanged_preset_rules() {
+        let toml_rule = TomlRule {
+            id: "cache-key-probe".into(),
+            rule_type: "banned-pattern".into(),
+            pattern: Some("probe(".into()),
+            origin: RuleOrigin::ScopedPreset {
+                preset: "security".into(),
+                path: "rule-cache-key-probe".into(),
+            },
+            ..Default::default()
+        };
+        let config = toml_rule.to_rule_config();
+        assert!(!build_rule_cached(&toml_rule, &config).unwrap().1);
+        assert!(build_rule_cached(&toml_rule, &config).unwrap().1);
+
+        // An override changes the config, so the rule is rebuilt
+        let overridden = TomlRule {
+            severity: "error".into(),
+            ..toml_rule.clone()
+        };
+        let (rule, cached) = build_rule_cached(&overridden, &overridden.to_rule_config()).unwrap();
+        assert!(!cached);
+        assert_eq!(rule.severity(), Severity::Error);
+
+        // User rules are never cached
+        let user = TomlRule {
+            origin: RuleOrigin::User,
+            ..toml_rule
+        };
+        let config = user.to_rule_config();
+        build_rule_cached(&user, &config).unwrap();
+        assert!(!build_rule_cached(&user, &config).unwrap().1);
     }
 
     #[test]
-    fn run_scan_file_presence_rule() {
+    fn import_rules_see_tsconfig_edits_between_scans() {
         let dir = tempfile::tempdir().unwrap();
-
-        let config = dir.path().join("baseline.toml");
+        let root = dir.path();
+        let config = root.join("baseline.toml");
         fs::write(
             &config,
             r#"
 [baseline]
 
 [[rule]]
-id = "has-readme"
-type = "file-presence"
-severity = "error"
-required_files = ["README.md"]
-message = "README.md is required"
+id = "feature-boundaries-tsconfig-probe"
+type = "feature-boundaries"
+feature_dirs = "src/features/*"
+message = "import features through their index"
 "#,
         )
         .unwrap();
-
-        // No README.md in dir
-        let result = run_scan(&config, &[dir.path().to_path_buf()]).unwrap();
-        assert!(result.violations.iter().any(|v| v.rule_id == "has-readme"));
+        let file = root.join("src/features/billing/Plan.ts");
+        fs::create_dir_all(file.parent().unwrap()).unwrap();
+        fs::write(&file, "import { useUser } from '@app/features/auth/hooks/useUser';\n").unwrap();
+        let tsconfig = root.join("tsconfig.json");
+        fs::write(&tsconfig, r#"{ "compilerOptions": { "paths": { "~/*": ["./src/*"] } } }"#).unwrap();
+
+        let first = run_scan(&config, &[root.to_path_buf()]).unwrap();
+        assert!(first.violations.is_empty());
+
+        fs::write(&tsconfig, r#"{ "compilerOptions": { "paths": { "@app/*": ["./src/*"] } } }"#).unwrap();
+        let second = run_scan(&config, &[root.to_path_buf()]).unwrap();
+        assert_eq!(second.violations.len(), 1);
+        assert_eq!(second.violations[0].file, file);
     }
 
     #[test]
-    fn run_scan_missing_config_errors() {
-        let result = run_scan(
-            Path::new("/nonexistent/baseline.toml"),
-            &[PathBuf::from(".")],
-        );
-        assert!(result.is_err());
-        assert!(matches!(result.err().unwrap(), ScanError::ConfigRead(_)));
+    fn run_scan_stdin_loads_plugins() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let plugin_path = dir.path().join("custom-rules.toml");
+        fs::write(
+            &plugin_path,
+            r#"
+[[rule]]
+id = "no-todo"
+type = "banned-pattern"
+severity = "warning"
+pattern = "TODO"
+message = "No TODOs allowed"
+"#,
+        )
+        .unwrap();
+
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            format!(
+                "[baseline]\nplugins = [\"{}\"]\n",
+                plugin_path.display()
+            ),
+        )
+        .unwrap();
+
+        let result = run_scan_stdin(&config, "// TODO: fix this\n", "app.ts").unwrap();
+        assert!(result.violations.iter().any(|v| v.rule_id == "no-todo"));
     }
 
     #[test]
-    fn run_scan_invalid_config_errors() {
+    fn load_config_missing_plugin_errors() {
         let dir = tempfile::tempdir().unwrap();
         let config = dir.path().join("baseline.toml");
-        fs::write(&config, "this is not valid toml [[[").unwrap();
+        fs::write(&config, "[baseline]\nplugins = [\"does-not-exist.toml\"]\n").unwrap();
 
-        let result = run_scan(&config, &[dir.path().to_path_buf()]);
-        assert!(result.is_err());
-        assert!(matches!(result.err().unwrap(), ScanError::ConfigParse(_)));
+        assert!(matches!(load_config(&config), Err(ScanError::ConfigRead(_))));
+    }
+
+    fn warning(rule_id: &str) -> Violation {
+        Violation {
+            rule_id: rule_id.into(),
+            severity: Severity::Warning,
+            file: PathBuf::from("a.ts"),
+            line: Some(1),
+            column: None,
+            message: "m".into(),
+            suggest: Vec::new(),
+            source_line: None,
+            fix: None,
+            origin: None,
+            enclosing: None,
+        }
+    }
+
+    fn result_with(violations: Vec<Violation>) -> ScanResult {
+        ScanResult {
+            violations,
+            files_scanned: 1,
+            generated_skipped: 0,
+            rules_loaded: 2,
+            unmatched_globs: Vec::new(),
+            groups: Vec::new(),
+            rules: Vec::new(),
+            ratchet_counts: HashMap::new(),
+            ratchet_deadlines: HashMap::new(),
+            changed_files_count: None,
+            base_ref: None,
+            timings: Default::default(),
+            scanned_files: vec![],
+            file_hashes: HashMap::new(),
+            fixes: None,
+            output: Default::default(),
+            hooks: Default::default(),
+            stopped_early: false,
+        }
     }
 
     #[test]
-    fn run_scan_with_ratchet_rule() {
+    fn simulate_error_severity_promotes_all_warnings() {
+        let result = result_with(vec![warning("b"), warning("a"), warning("a")]);
+        let sim = simulate_error_severity(&result, &[]);
+        assert_eq!(sim.errors, 3);
+        assert_eq!(sim.warnings, 0);
+        assert_eq!(sim.promoted, 3);
+        assert_eq!(sim.promoted_rules, vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(sim.exit_code, 1);
+    }
+
+    #[test]
+    fn simulate_error_severity_only_listed_rules() {
+        let result = result_with(vec![warning("a"), warning("b")]);
+        let sim = simulate_error_severity(&result, &["b".to_string()]);
+        assert_eq!(sim.errors, 1);
+        assert_eq!(sim.warnings, 1);
+        assert_eq!(sim.promoted_rules, vec!["b".to_string()]);
+
+        let sim = simulate_error_severity(&result, &["c".to_string()]);
+        assert_eq!(sim.promoted, 0);
+        assert_eq!(sim.exit_code, 0);
+    }
+
+    #[test]
+    fn simulate_error_severity_leaves_info_and_hints() {
+        let mut info = warning("a");
+        info.severity = Severity::Info;
+        let mut hint = warning("b");
+        hint.severity = Severity::Hint;
+        let sim = simulate_error_severity(&result_with(vec![info, hint]), &[]);
+        assert_eq!(sim.errors, 0);
+        assert_eq!(sim.promoted, 0);
+        assert_eq!(sim.exit_code, 0);
+    }
+
+    #[test]
+    fn rule_counts_sorted_by_count() {
+        let mut other_file = warning("a");
+        other_file.file = PathBuf::from("b.ts");
+        let result = result_with(vec![warning("b"), warning("a"), warning("a"), other_file, warning("c")]);
+
+        let counts = rule_counts(&result);
+        let summary: Vec<(&str, usize, usize)> = counts
+            .iter()
+            .map(|c| (c.rule_id.as_str(), c.violations, c.files))
+            .collect();
+        assert_eq!(summary, [("a", 3, 2), ("b", 1, 1), ("c", 1, 1)]);
+        assert_eq!(counts[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn rule_totals_include_quiet_rules_and_skip_ratchets() {
+        let meta = |id: &str, rule_type: &str| RuleMeta {
+            rule_id: id.into(),
+            rule_type: rule_type.into(),
+            severity: Severity::Warning,
+            glob_restricted: false,
+            conditioned: false,
+            origin: RuleOrigin::User,
+            config: None,
+            fix_gated: false,
+            owner: None,
+            requires_review: false,
+        };
+        let mut result = result_with(vec![warning("a"), warning("a"), warning("legacy")]);
+        result.rules = vec![
+            meta("a", "banned-pattern"),
+            meta("quiet", "banned-import"),
+            meta("legacy", "ratchet"),
+        ];
+        result.ratchet_counts.insert("legacy".into(), (1, 5));
+
+        let totals = rule_totals(&result);
+        let summary: Vec<(&str, usize, usize)> = totals
+            .iter()
+            .map(|c| (c.rule_id.as_str(), c.violations, c.files))
+            .collect();
+        assert_eq!(summary, [("a", 2, 1), ("quiet", 0, 0)]);
+    }
+
+    #[test]
+    fn multiple_configs_scan_in_one_pass_with_origins() {
         let dir = tempfile::tempdir().unwrap();
+        let platform = dir.path().join("platform.toml");
+        let repo = dir.path().join("baseline.toml");
+        fs::write(
+            &platform,
+            r#"[baseline]
+exclude_extend = ["legacy/**"]
 
-        let config = dir.path().join("baseline.toml");
+[[rule]]
+id = "no-todo"
+type = "banned-pattern"
+pattern = "TODO"
+glob = "**/*.ts"
+"#,
+        )
+        .unwrap();
         fs::write(
-            &config,
-            r#"
-[baseline]
+            &repo,
+            r#"[baseline]
 
 [[rule]]
-id = "legacy-api"
+id = "no-todo"
+type = "banned-pattern"
+pattern = "TODO"
+glob = "**/*.ts"
+
+[[rule]]
+id = "fixme-budget"
 type = "ratchet"
-severity = "warning"
-pattern = "legacyCall"
+pattern = "FIXME"
 max_count = 5
-message = "legacy api usage"
+glob = "**/*.ts"
 "#,
         )
         .unwrap();
-
-        let src_dir = dir.path().join("src");
-        fs::create_dir(&src_dir).unwrap();
-        fs::write(src_dir.join("app.ts"), "legacyCall();\nlegacyCall();\n").unwrap();
-
-        let result = run_scan(&config, &[src_dir]).unwrap();
-        // 2 matches, max 5 -> suppressed
-        assert!(result.violations.is_empty());
-        assert_eq!(result.ratchet_counts["legacy-api"], (2, 5));
+        let src = dir.path().join("src");
+        fs::create_dir_all(src.join("legacy")).unwrap();
+        fs::write(src.join("a.ts"), "// TODO\n// FIXME\n").unwrap();
+        fs::write(src.join("legacy/b.ts"), "// TODO\n").unwrap();
+
+        let configs = [platform.clone(), repo.clone()];
+        let result = run_scan_configs(&configs, std::slice::from_ref(&src), &ScanOptions::default())
+            .unwrap();
+
+        assert_eq!(result.files_scanned, 2);
+        assert_eq!(result.rules_loaded, 3);
+        assert_eq!(result.rules.len(), 3);
+        let mut found: Vec<(String, &str, Option<&Path>)> = result
+            .violations
+            .iter()
+            .map(|v| {
+                (
+                    v.file.strip_prefix(&src).unwrap().display().to_string(),
+                    v.rule_id.as_str(),
+                    v.origin.as_deref(),
+                )
+            })
+            .collect();
+        found.sort();
+        assert_eq!(
+            found,
+            [
+                ("a.ts".to_string(), "no-todo", Some(repo.as_path())),
+                ("a.ts".to_string(), "no-todo", Some(platform.as_path())),
+                ("legacy/b.ts".to_string(), "no-todo", Some(repo.as_path())),
+            ]
+        );
+        assert_eq!(result.ratchet_counts["fixme-budget"], (1, 5));
+        assert!(result
+            .violations
+            .iter()
+            .all(|v| rule_for(&result, v).is_some_and(|m| m.config == v.origin)));
+
+        // A single config leaves violations unattributed
+        let single = run_scan(&repo, std::slice::from_ref(&src)).unwrap();
+        assert!(single.violations.iter().all(|v| v.origin.is_none()));
     }
 
-    // ── run_scan_stdin tests ──
-
     #[test]
-    fn run_scan_stdin_finds_violations() {
+    fn fix_verifier_runs_every_config() {
         let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a.toml");
+        let b = dir.path().join("b.toml");
+        let rule = |id: &str, pattern: &str| {
+            format!("[baseline]\n\n[[rule]]\nid = \"{}\"\ntype = \"banned-pattern\"\npattern = \"{}\"\n", id, pattern)
+        };
+        fs::write(&a, rule("no-todo", "TODO")).unwrap();
+        fs::write(&b, rule("no-fixme", "FIXME")).unwrap();
+
+        let verifier = FixVerifier::for_configs(&[a, b]).unwrap();
+        let found = verifier.check(Path::new("x.ts"), "// TODO FIXME\n");
+        let mut ids: Vec<&str> = found.iter().map(|v| v.rule_id.as_str()).collect();
+        ids.sort();
+        assert_eq!(ids, ["no-fixme", "no-todo"]);
+    }
 
+    #[test]
+    fn scan_result_lists_rules_with_restrictions() {
+        let dir = tempfile::tempdir().unwrap();
         let config = dir.path().join("baseline.toml");
         fs::write(
             &config,
-            r#"
-[baseline]
+            r#"[baseline]
 
 [[rule]]
-id = "no-console"
+id = "no-todo"
 type = "banned-pattern"
-severity = "error"
-pattern = "console\\.log"
-regex = true
-message = "no console.log"
+pattern = "TODO"
+
+[[rule]]
+id = "no-fixme"
+type = "banned-pattern"
+pattern = "FIXME"
+glob = "**/*.rs"
+file_contains = "unsafe"
 "#,
         )
         .unwrap();
+        fs::write(dir.path().join("a.ts"), "// TODO\n").unwrap();
+
+        let result = run_scan(&config, &[dir.path().join("a.ts")]).unwrap();
+        let metrics: Vec<(&str, usize, bool, bool)> = rule_metrics(&result)
+            .into_iter()
+            .map(|(m, fired)| (m.rule_id.as_str(), fired, m.glob_restricted, m.conditioned))
+            .collect();
+        assert_eq!(
+            metrics,
+            [("no-todo", 1, false, false), ("no-fixme", 0, true, true)]
+        );
+    }
+
+    #[test]
+    fn info_and_hint_severities_parse_from_config() {
+        let rule = |severity: &str| TomlRule {
+            id: "r".into(),
+            rule_type: "banned-pattern".into(),
+            severity: severity.into(),
+            ..Default::default()
+        };
+        assert_eq!(rule("info").to_rule_config().severity, Severity::Info);
+        assert_eq!(rule("Hint").to_rule_config().severity, Severity::Hint);
+        assert_eq!(rule("error").to_rule_config().severity, Severity::Error);
+        assert_eq!(rule("other").to_rule_config().severity, Severity::Warning);
+    }
+
+    // ── is_likely_minified tests ──
+
+    #[test]
+    fn minified_empty_content() {
+        assert!(!is_likely_minified("", MINIFIED_LINE_LENGTH_THRESHOLD));
+    }
+
+    #[test]
+    fn minified_normal_source() {
+        let content = "const x = 1;\nconst y = 2;\nfunction foo() { return x + y; }\n";
+        assert!(!is_likely_minified(content, MINIFIED_LINE_LENGTH_THRESHOLD));
+    }
+
+    #[test]
+    fn minified_single_long_line() {
+        let long_line = "a".repeat(MINIFIED_LINE_LENGTH_THRESHOLD + 1);
+        assert!(is_likely_minified(&long_line, MINIFIED_LINE_LENGTH_THRESHOLD));
+    }
+
+    #[test]
+    fn minified_mixed_with_one_long_line() {
+        let mut content = "const x = 1;\n".to_string();
+        content.push_str(&"a".repeat(MINIFIED_LINE_LENGTH_THRESHOLD + 1));
+        content.push_str("\nconst y = 2;\n");
+        assert!(is_likely_minified(&content, MINIFIED_LINE_LENGTH_THRESHOLD));
+    }
 
-        let result =
-            run_scan_stdin(&config, "console.log('hello');\nfoo();\n", "test.ts").unwrap();
-        assert_eq!(result.violations.len(), 1);
-        assert_eq!(result.files_scanned, 1);
+    #[test]
+    fn minified_exactly_at_threshold() {
+        let line = "a".repeat(MINIFIED_LINE_LENGTH_THRESHOLD);
+        assert!(!is_likely_minified(&line, MINIFIED_LINE_LENGTH_THRESHOLD));
     }
 
+    // ── run_scan skips minified files ──
+
     #[test]
-    fn run_scan_stdin_no_violations() {
+    fn run_scan_skips_minified_file() {
         let dir = tempfile::tempdir().unwrap();
 
         let config = dir.path().join("baseline.toml");
@@ -1746,17 +6297,25 @@ severity = "error"
 pattern = "console\\.log"
 regex = true
 message = "no console.log"
-glob = "**/*.ts"
 "#,
         )
         .unwrap();
 
-        let result = run_scan_stdin(&config, "doStuff();\n", "app.ts").unwrap();
+        let src_dir = dir.path().join("src");
+        fs::create_dir(&src_dir).unwrap();
+        // Write a minified file containing the banned pattern
+        let mut minified = "console.log('hi');".to_string();
+        minified.push_str(&"x".repeat(MINIFIED_LINE_LENGTH_THRESHOLD + 1));
+        minified.push('\n');
+        fs::write(src_dir.join("bundle.js"), &minified).unwrap();
+
+        let result = run_scan(&config, &[src_dir]).unwrap();
         assert!(result.violations.is_empty());
+        assert_eq!(result.files_scanned, 0);
     }
 
     #[test]
-    fn run_scan_stdin_glob_filters_filename() {
+    fn run_scan_stdin_skips_minified() {
         let dir = tempfile::tempdir().unwrap();
 
         let config = dir.path().join("baseline.toml");
@@ -1772,21 +6331,20 @@ severity = "error"
 pattern = "console\\.log"
 regex = true
 message = "no console.log"
-glob = "**/*.tsx"
 "#,
         )
         .unwrap();
 
-        // File doesn't match glob
-        let result =
-            run_scan_stdin(&config, "console.log('hello');\n", "app.rs").unwrap();
+        let mut minified = "console.log('hi');".to_string();
+        minified.push_str(&"x".repeat(MINIFIED_LINE_LENGTH_THRESHOLD + 1));
+
+        let result = run_scan_stdin(&config, &minified, "bundle.js").unwrap();
         assert!(result.violations.is_empty());
+        assert_eq!(result.files_scanned, 0);
     }
 
-    // ── run_baseline tests ──
-
     #[test]
-    fn run_baseline_counts_ratchet_matches() {
+    fn run_scan_custom_max_line_length() {
         let dir = tempfile::tempdir().unwrap();
 
         let config = dir.path().join("baseline.toml");
@@ -1794,35 +6352,31 @@ glob = "**/*.tsx"
             &config,
             r#"
 [baseline]
+max_line_length = 2000
 
 [[rule]]
-id = "legacy-api"
-type = "ratchet"
-severity = "warning"
-pattern = "legacyCall"
-max_count = 100
-message = "legacy usage"
+id = "no-console"
+type = "banned-pattern"
+severity = "error"
+pattern = "console.log"
+message = "no console.log"
 "#,
         )
         .unwrap();
 
         let src_dir = dir.path().join("src");
         fs::create_dir(&src_dir).unwrap();
-        fs::write(
-            src_dir.join("app.ts"),
-            "legacyCall();\nlegacyCall();\nlegacyCall();\n",
-        )
-        .unwrap();
+        let mut long_line = "console.log('hi');".to_string();
+        long_line.push_str(&"x".repeat(MINIFIED_LINE_LENGTH_THRESHOLD + 1));
+        fs::write(src_dir.join("fixture.ts"), &long_line).unwrap();
 
-        let result = run_baseline(&config, &[src_dir]).unwrap();
-        assert_eq!(result.entries.len(), 1);
-        assert_eq!(result.entries[0].rule_id, "legacy-api");
-        assert_eq!(result.entries[0].count, 3);
+        let result = run_scan(&config, &[src_dir]).unwrap();
+        assert_eq!(result.violations.len(), 1);
         assert_eq!(result.files_scanned, 1);
     }
 
     #[test]
-    fn run_baseline_skips_non_ratchet_rules() {
+    fn run_scan_minified_globs_force_scan() {
         let dir = tempfile::tempdir().unwrap();
 
         let config = dir.path().join("baseline.toml");
@@ -1830,79 +6384,34 @@ message = "legacy usage"
             &config,
             r#"
 [baseline]
+minified_globs = ["**/queries/**"]
 
 [[rule]]
 id = "no-console"
 type = "banned-pattern"
 severity = "error"
-pattern = "console\\.log"
-regex = true
-message = "no console"
-
-[[rule]]
-id = "legacy-api"
-type = "ratchet"
-severity = "warning"
-pattern = "legacyCall"
-max_count = 100
-message = "legacy usage"
+pattern = "console.log"
+message = "no console.log"
 "#,
         )
         .unwrap();
 
-        let src_dir = dir.path().join("src");
-        fs::create_dir(&src_dir).unwrap();
-        fs::write(src_dir.join("app.ts"), "console.log('hi');\nlegacyCall();\n").unwrap();
-
-        let result = run_baseline(&config, &[src_dir]).unwrap();
-        // Only ratchet rules appear in baseline
-        assert_eq!(result.entries.len(), 1);
-        assert_eq!(result.entries[0].rule_id, "legacy-api");
-    }
-
-    // ── collect_files tests ──
-
-    #[test]
-    fn collect_files_single_file() {
-        let dir = tempfile::tempdir().unwrap();
-        let file = dir.path().join("test.ts");
-        fs::write(&file, "content").unwrap();
-
-        let empty_glob = build_glob_set(&[]).unwrap();
-        let files = collect_files(&[file.clone()], &empty_glob);
-        assert_eq!(files.len(), 1);
-        assert_eq!(files[0], file);
-    }
-
-    #[test]
-    fn collect_files_directory_walk() {
-        let dir = tempfile::tempdir().unwrap();
-        let sub = dir.path().join("sub");
-        fs::create_dir(&sub).unwrap();
-        fs::write(sub.join("a.ts"), "a").unwrap();
-        fs::write(sub.join("b.ts"), "b").unwrap();
-
-        let empty_glob = build_glob_set(&[]).unwrap();
-        let files = collect_files(&[dir.path().to_path_buf()], &empty_glob);
-        assert_eq!(files.len(), 2);
-    }
-
-    #[test]
-    fn collect_files_excludes_patterns() {
-        let dir = tempfile::tempdir().unwrap();
-        fs::write(dir.path().join("keep.ts"), "keep").unwrap();
-        fs::write(dir.path().join("skip.log"), "skip").unwrap();
+        let queries = dir.path().join("src").join("queries");
+        fs::create_dir_all(&queries).unwrap();
+        let mut long_line = "console.log('hi');".to_string();
+        long_line.push_str(&"x".repeat(MINIFIED_LINE_LENGTH_THRESHOLD + 1));
+        fs::write(queries.join("report.ts"), &long_line).unwrap();
+        fs::write(dir.path().join("src").join("bundle.js"), &long_line).unwrap();
 
-        let exclude = build_glob_set(&["*.log".into()]).unwrap();
-        let files = collect_files(&[dir.path().to_path_buf()], &exclude);
-        assert!(files.iter().all(|f| !f.to_string_lossy().ends_with(".log")));
-        assert!(files.iter().any(|f| f.to_string_lossy().ends_with(".ts")));
+        let result = run_scan(&config, &[dir.path().join("src")]).unwrap();
+        // Only the allowlisted file is scanned; bundle.js is still skipped
+        assert_eq!(result.files_scanned, 1);
+        assert_eq!(result.violations.len(), 1);
+        assert!(result.violations[0].file.ends_with("report.ts"));
     }
 
-    // ── run_scan with presets ──
-
     #[test]
-    fn run_scan_with_preset() {
+    fn run_scan_include_minified_option() {
         let dir = tempfile::tempdir().unwrap();
 
         let config = dir.path().join("baseline.toml");
@@ -1910,100 +6419,88 @@ message = "legacy usage"
             &config,
             r#"
 [baseline]
-extends = ["shadcn-strict"]
+
+[[rule]]
+id = "no-console"
+type = "banned-pattern"
+severity = "error"
+pattern = "console.log"
+message = "no console.log"
 "#,
         )
         .unwrap();
 
         let src_dir = dir.path().join("src");
         fs::create_dir(&src_dir).unwrap();
-        fs::write(src_dir.join("app.tsx"), "export default function App() { return <div>hi</div>; }\n").unwrap();
+        let mut minified = "console.log('hi');".to_string();
+        minified.push_str(&"x".repeat(MINIFIED_LINE_LENGTH_THRESHOLD + 1));
+        fs::write(src_dir.join("bundle.js"), &minified).unwrap();
 
-        let result = run_scan(&config, &[src_dir]).unwrap();
-        // Just verify it doesn't error
-        assert!(result.rules_loaded > 0);
+        let options = ScanOptions {
+            include_minified: true,
+            ..Default::default()
+        };
+        let result = run_scan_with_options(&config, &[src_dir], &options).unwrap();
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.files_scanned, 1);
     }
 
-    // ── run_scan with plugins ──
-
     #[test]
-    fn run_scan_with_plugin() {
+    fn fail_fast_reports_only_the_first_error() {
         let dir = tempfile::tempdir().unwrap();
-
-        let plugin_path = dir.path().join("custom-rules.toml");
+        let config = dir.path().join("baseline.toml");
         fs::write(
-            &plugin_path,
+            &config,
             r#"
+[baseline]
+
 [[rule]]
-id = "no-todo"
+id = "no-console"
 type = "banned-pattern"
-severity = "warning"
-pattern = "TODO"
-message = "No TODOs allowed"
-"#,
-        )
-        .unwrap();
+severity = "error"
+pattern = "console.log"
+message = "no console.log"
+glob = "**/*.ts"
 
-        let config = dir.path().join("baseline.toml");
-        fs::write(
-            &config,
-            format!(
-                r#"
-[baseline]
-plugins = ["{}"]
+[[rule]]
+id = "no-debugger"
+type = "banned-pattern"
+severity = "warning"
+pattern = "debugger"
+message = "no debugger"
+glob = "**/*.ts"
 "#,
-                plugin_path.display()
-            ),
         )
         .unwrap();
 
         let src_dir = dir.path().join("src");
         fs::create_dir(&src_dir).unwrap();
-        fs::write(src_dir.join("app.ts"), "// TODO: fix this\n").unwrap();
-
-        let result = run_scan(&config, &[src_dir]).unwrap();
-        assert!(result.violations.iter().any(|v| v.rule_id == "no-todo"));
-    }
-
-    // ── is_likely_minified tests ──
-
-    #[test]
-    fn minified_empty_content() {
-        assert!(!is_likely_minified(""));
-    }
-
-    #[test]
-    fn minified_normal_source() {
-        let content = "const x = 1;\nconst y = 2;\nfunction foo() { return x + y; }\n";
-        assert!(!is_likely_minified(content));
-    }
+        for i in 0..20 {
+            fs::write(src_dir.join(format!("f{}.ts", i)), "debugger;\nconsole.log(1);\n").unwrap();
+        }
+        let targets = std::slice::from_ref(&src_dir);
 
-    #[test]
-    fn minified_single_long_line() {
-        let long_line = "a".repeat(MINIFIED_LINE_LENGTH_THRESHOLD + 1);
-        assert!(is_likely_minified(&long_line));
-    }
+        let full = run_scan_with_options(&config, targets, &ScanOptions::default()).unwrap();
+        assert_eq!(full.violations.len(), 40);
 
-    #[test]
-    fn minified_mixed_with_one_long_line() {
-        let mut content = "const x = 1;\n".to_string();
-        content.push_str(&"a".repeat(MINIFIED_LINE_LENGTH_THRESHOLD + 1));
-        content.push_str("\nconst y = 2;\n");
-        assert!(is_likely_minified(&content));
-    }
+        let options = ScanOptions {
+            fail_fast: true,
+            ..Default::default()
+        };
+        let result = run_scan_with_options(&config, targets, &options).unwrap();
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.violations[0].rule_id, "no-console");
+        assert_eq!(result.exit_code(), 1);
 
-    #[test]
-    fn minified_exactly_at_threshold() {
-        let line = "a".repeat(MINIFIED_LINE_LENGTH_THRESHOLD);
-        assert!(!is_likely_minified(&line));
+        // Warnings alone never stop the scan.
+        let stdin = run_scan_stdin_with_options(&config, "debugger;\n", "a.ts", &options).unwrap();
+        assert_eq!(stdin.violations.len(), 1);
+        assert_eq!(stdin.violations[0].rule_id, "no-debugger");
     }
 
-    // ── run_scan skips minified files ──
-
     #[test]
-    fn run_scan_skips_minified_file() {
+    fn fail_fast_leaves_ratchets_unevaluated() {
         let dir = tempfile::tempdir().unwrap();
-
         let config = dir.path().join("baseline.toml");
         fs::write(
             &config,
@@ -2014,30 +6511,78 @@ plugins = ["{}"]
 id = "no-console"
 type = "banned-pattern"
 severity = "error"
-pattern = "console\\.log"
-regex = true
+pattern = "console.log"
 message = "no console.log"
+glob = "**/*.ts"
+
+[[rule]]
+id = "legacy-fetch"
+type = "ratchet"
+severity = "error"
+pattern = "legacyFetch("
+max_count = 5
+message = "Use apiClient"
 "#,
         )
         .unwrap();
 
         let src_dir = dir.path().join("src");
         fs::create_dir(&src_dir).unwrap();
-        // Write a minified file containing the banned pattern
-        let mut minified = "console.log('hi');".to_string();
-        minified.push_str(&"x".repeat(MINIFIED_LINE_LENGTH_THRESHOLD + 1));
-        minified.push('\n');
-        fs::write(src_dir.join("bundle.js"), &minified).unwrap();
+        fs::write(src_dir.join("a.ts"), "console.log(1);\n").unwrap();
+        for i in 0..20 {
+            fs::write(src_dir.join(format!("f{}.ts", i)), "legacyFetch(a);\n").unwrap();
+        }
+        let targets = std::slice::from_ref(&src_dir);
 
-        let result = run_scan(&config, &[src_dir]).unwrap();
-        assert!(result.violations.is_empty());
-        assert_eq!(result.files_scanned, 0);
+        let full = run_scan_with_options(&config, targets, &ScanOptions::default()).unwrap();
+        assert!(!full.stopped_early);
+        assert_eq!(full.ratchet_counts["legacy-fetch"], (20, 5));
+
+        let options = ScanOptions {
+            fail_fast: true,
+            ..Default::default()
+        };
+        let result = run_scan_with_options(&config, targets, &options).unwrap();
+        assert!(result.stopped_early);
+        assert!(result.ratchet_counts.is_empty());
+        assert_eq!(result.unevaluated_ratchets(), vec!["legacy-fetch"]);
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.violations[0].rule_id, "no-console");
     }
 
     #[test]
-    fn run_scan_stdin_skips_minified() {
+    fn catalog_localizes_preset_rule_messages() {
         let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(&config, "[baseline]\nextends = [\"accessibility\"]\n").unwrap();
+        fs::write(dir.path().join("a.tsx"), "const A = () => <img src=\"a.png\" />;\n").unwrap();
+        let options = ScanOptions {
+            catalog: toml::from_str(
+                "[rules.require-img-alt]\nmessage = \"img 要素には alt 属性が必要です\"\n",
+            )
+            .unwrap(),
+            ..Default::default()
+        };
+
+        let result = run_scan_with_options(&config, &[dir.path().to_path_buf()], &options).unwrap();
+        let v = result.violations.iter().find(|v| v.rule_id == "require-img-alt").unwrap();
+        assert_eq!(v.message, "img 要素には alt 属性が必要です");
+        assert_eq!(v.suggest.len(), 2, "suggestions the catalog leaves out are kept");
+    }
+
+    #[test]
+    fn generated_markers_in_header() {
+        assert!(is_likely_generated("// Code generated by protoc-gen-go. DO NOT EDIT.\npackage x\n", 5));
+        assert!(is_likely_generated("/**\n * @generated\n */\nexport {}\n", 5));
+        assert!(!is_likely_generated("a\nb\nc\nd\ne\n// DO NOT EDIT\n", 5));
+        assert!(!is_likely_generated("// DO NOT EDIT\n", 0));
+        // A closed `@generated` block is an ignore region, not a generated file
+        assert!(!is_likely_generated("// @generated\nx();\n// @end-generated\nhand();\n", 5));
+    }
 
+    #[test]
+    fn run_scan_skips_generated_files() {
+        let dir = tempfile::tempdir().unwrap();
         let config = dir.path().join("baseline.toml");
         fs::write(
             &config,
@@ -2048,19 +6593,32 @@ message = "no console.log"
 id = "no-console"
 type = "banned-pattern"
 severity = "error"
-pattern = "console\\.log"
-regex = true
+pattern = "console.log"
 message = "no console.log"
 "#,
         )
         .unwrap();
+        let src_dir = dir.path().join("src");
+        fs::create_dir(&src_dir).unwrap();
+        fs::write(src_dir.join("api.gen.ts"), "// @generated by openapi\nconsole.log(1);\n").unwrap();
+        fs::write(src_dir.join("app.ts"), "console.log(2);\n").unwrap();
 
-        let mut minified = "console.log('hi');".to_string();
-        minified.push_str(&"x".repeat(MINIFIED_LINE_LENGTH_THRESHOLD + 1));
+        let result = run_scan(&config, std::slice::from_ref(&src_dir)).unwrap();
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.files_scanned, 1);
+        assert_eq!(result.generated_skipped, 1);
 
-        let result = run_scan_stdin(&config, &minified, "bundle.js").unwrap();
-        assert!(result.violations.is_empty());
-        assert_eq!(result.files_scanned, 0);
+        let options = ScanOptions {
+            include_generated: true,
+            ..Default::default()
+        };
+        let result = run_scan_with_options(&config, std::slice::from_ref(&src_dir), &options).unwrap();
+        assert_eq!(result.violations.len(), 2);
+        assert_eq!(result.generated_skipped, 0);
+
+        let stdin = run_scan_stdin(&config, "// DO NOT EDIT\nconsole.log(3);\n", "x.ts").unwrap();
+        assert!(stdin.violations.is_empty());
+        assert_eq!(stdin.generated_skipped, 1);
     }
 
     #[test]
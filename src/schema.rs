@@ -0,0 +1,699 @@
+use crate::cli::format::JSON_OUTPUT_VERSION;
+use crate::rules::factory::{RULE_TYPES, RULE_TYPE_ALIASES};
+use serde_json::{json, Map, Value};
+
+/// Fields each rule type refuses to build without. Kept next to the schema
+/// so `baseline schema` reports the same requirements the factory enforces.
+const REQUIRED_FIELDS: &[(&str, &[&str])] = &[
+    ("banned-pattern", &["pattern"]),
+    ("required-pattern", &["pattern"]),
+    ("ratchet", &["pattern", "max_count"]),
+    ("window-pattern", &["pattern", "condition_pattern"]),
+    ("banned-dependency", &["packages"]),
+    ("server-only-import", &["packages"]),
+    ("no-new-files", &["glob"]),
+    ("canonical-import", &["canonical_imports"]),
+];
+
+/// Build a property map from `(name, schema, description)` entries.
+fn properties(fields: Vec<(&str, Value, &str)>) -> Map<String, Value> {
+    fields
+        .into_iter()
+        .map(|(name, mut schema, description)| {
+            schema["description"] = Value::String(description.to_string());
+            (name.to_string(), schema)
+        })
+        .collect()
+}
+
+fn string() -> Value {
+    json!({ "type": "string" })
+}
+
+fn strings() -> Value {
+    json!({ "type": "array", "items": { "type": "string" } })
+}
+
+fn boolean() -> Value {
+    json!({ "type": "boolean" })
+}
+
+fn count() -> Value {
+    json!({ "type": "integer", "minimum": 0 })
+}
+
+/// Properties of the `[baseline]` section, one per `BaselineSection` field.
+fn baseline_properties() -> Map<String, Value> {
+    properties(vec![
+        ("name", string(), "Project name (informational)"),
+        (
+            "required_version",
+            string(),
+            "Versions of baseline this config works with, e.g. \">=0.6\"; older binaries refuse to scan",
+        ),
+        ("include", strings(), "Unused; use the top-level `include` to merge config files"),
+        ("exclude", strings(), "Globs to skip; replaces the default excludes when set"),
+        ("exclude_extend", strings(), "Globs to skip in addition to `exclude` (or the defaults)"),
+        (
+            "no_default_excludes",
+            boolean(),
+            "Don't apply the default excludes when `exclude` is unset (default: false)",
+        ),
+        ("extends", strings(), "Presets to load, in order; later presets override earlier ones"),
+        ("plugins", strings(), "Paths to plugin TOML files containing additional rules"),
+        (
+            "scoped",
+            json!({
+                "type": "array",
+                "items": {
+                    "type": "object",
+                    "properties": properties(vec![
+                        (
+                            "preset",
+                            json!({ "anyOf": [string(), strings()] }),
+                            "Preset name, or list of preset names",
+                        ),
+                        ("path", string(), "Directory the presets apply to"),
+                        ("exclude_rules", strings(), "Preset rule ids to leave out of this scope"),
+                        (
+                            "exclude_paths",
+                            strings(),
+                            "Paths or globs, relative to path, the presets should not apply to",
+                        ),
+                    ]),
+                    "required": ["preset", "path"],
+                },
+            }),
+            "Scoped presets: apply a preset only to files under a specific path",
+        ),
+        (
+            "max_line_length",
+            count(),
+            "Lines longer than this mark a file as minified and skip it (default: 500, 0 disables)",
+        ),
+        (
+            "minified_globs",
+            strings(),
+            "Globs for files that are always scanned even if they look minified",
+        ),
+        (
+            "generated_header_lines",
+            count(),
+            "Leading lines searched for generated-file markers (default: 5, 0 disables)",
+        ),
+        (
+            "follow_symlinks",
+            boolean(),
+            "Follow symlinked files and directories while walking (default: false)",
+        ),
+        (
+            "submodules",
+            json!({ "enum": ["scan", "skip", "nested"] }),
+            "How git submodules are treated while walking (default: scan)",
+        ),
+        (
+            "vendored",
+            json!({ "enum": ["skip", "scan"] }),
+            "Whether conventional vendored directories are scanned (default: skip)",
+        ),
+        (
+            "vendored_dirs",
+            strings(),
+            "Directory names treated as vendored code (default: vendor, third_party)",
+        ),
+        (
+            "suppression_lookback",
+            count(),
+            "Lines an `allow-next-line` directive may sit above a violation (default: 1)",
+        ),
+        (
+            "safe_fixes_only",
+            boolean(),
+            "Apply only fixes from rules marked `safe_fix` unless --fix-unsafe is passed (default: false)",
+        ),
+    ])
+}
+
+/// Properties of the `[output]` section, one per `OutputSection` field.
+fn output_properties() -> Map<String, Value> {
+    let gate = || {
+        json!({
+            "type": "object",
+            "properties": properties(vec![(
+                "min_severity",
+                json!({ "enum": ["error", "warning", "info", "hint"] }),
+                "Lowest severity included",
+            )]),
+            "required": ["min_severity"],
+            "additionalProperties": false,
+        })
+    };
+    let mut fields = vec![("exit", gate(), "Lowest severity that fails the scan (default: error)")];
+    for format in ["pretty", "compact", "json", "github", "sarif", "markdown", "summary"] {
+        fields.push((format, gate(), "Lowest severity this output format reports (default: hint)"));
+    }
+    fields.push((
+        "column_unit",
+        json!({ "enum": ["bytes", "chars", "utf-16"] }),
+        "How reported columns are counted: UTF-8 bytes, Unicode scalar values, or UTF-16 code units (default: bytes)",
+    ));
+    properties(fields)
+}
+
+fn hook_properties() -> Map<String, Value> {
+    properties(vec![
+        (
+            "command",
+            json!({ "type": "array", "items": { "type": "string" }, "minItems": 1 }),
+            "Program and its arguments, run from the config's directory",
+        ),
+        ("timeout_secs", count(), "Seconds before the hook is killed (default: 30)"),
+        (
+            "on_failure",
+            json!({ "enum": ["ignore", "warn", "fail"] }),
+            "What a failing or timed-out hook does: nothing, print a warning, or exit 2 (default: warn)",
+        ),
+    ])
+}
+
+fn notify_properties() -> Map<String, Value> {
+    properties(vec![
+        ("webhook", string(), "Webhook URL to post to"),
+        (
+            "webhook_env",
+            string(),
+            "Environment variable holding the webhook URL; takes precedence over `webhook`",
+        ),
+        ("only_on_failure", json!({ "type": "boolean" }), "Only post when the scan fails (default: false)"),
+        ("top_rules", count(), "Rules listed in the message, most violations first (default: 5)"),
+    ])
+}
+
+/// Properties of a `[[rule]]` entry, one per `TomlRule` field.
+fn rule_properties() -> Map<String, Value> {
+    properties(vec![
+        ("id", string(), "Unique rule identifier"),
+        (
+            "type",
+            json!({
+                "enum": RULE_TYPES
+                    .iter()
+                    .chain(RULE_TYPE_ALIASES.iter().map(|(alias, _)| alias))
+                    .collect::<Vec<_>>()
+            }),
+            "Rule type (or alias); may be omitted when only disabling a preset rule",
+        ),
+        ("enabled", boolean(), "Set to false to turn the rule off (default: true)"),
+        (
+            "severity",
+            json!({ "enum": ["error", "warning", "info", "hint"] }),
+            "Severity level (default: warning); only errors fail a scan unless [output.exit] says otherwise",
+        ),
+        ("glob", string(), "Narrow which files this rule applies to"),
+        ("message", string(), "Human-readable explanation"),
+        (
+            "suggest",
+            json!({ "anyOf": [string(), strings()] }),
+            "Fix suggestion shown in output; a list offers alternatives, most preferred first",
+        ),
+        (
+            "allowed_classes",
+            strings(),
+            "Classes exempt from checks (tailwind-dark-mode, tailwind-theme-tokens)",
+        ),
+        ("token_map", strings(), "Custom \"raw=semantic\" mappings (tailwind-theme-tokens)"),
+        ("pattern", string(), "String or regex to match"),
+        (
+            "max_count",
+            count(),
+            "Maximum allowed occurrences (ratchet), window size (window-pattern), or AST rule threshold",
+        ),
+        (
+            "packages",
+            strings(),
+            "Package names to ban (banned-import, banned-dependency); banned-import accepts `*` wildcards, banned-dependency a version constraint (`moment@<3`, checked against package-lock.json only)",
+        ),
+        (
+            "allow_type_imports",
+            boolean(),
+            "Allow `import type` / `export type` of banned packages (banned-import, default: false)",
+        ),
+        (
+            "max_relative_depth",
+            count(),
+            "Most `../` segments a relative import may climb (banned-import)",
+        ),
+        (
+            "ban_cross_package_imports",
+            boolean(),
+            "Ban relative imports into another workspace package (banned-import, default: false)",
+        ),
+        ("regex", boolean(), "Treat `pattern` as a regex (default: false)"),
+        (
+            "manifest",
+            string(),
+            "Manifest file to check (banned-dependency, package-scripts; default: package.json)",
+        ),
+        ("exclude_glob", strings(), "Skip files matching these globs, even if they match `glob`"),
+        ("file_contains", string(), "Only run this rule if the file contains this string"),
+        ("file_not_contains", string(), "Skip this rule if the file contains this string"),
+        (
+            "sibling_files",
+            strings(),
+            "Only run this rule if one of these files exists in the same directory",
+        ),
+        ("required_files", strings(), "Files that must exist (file-presence)"),
+        ("forbidden_files", strings(), "Files that must not exist (file-presence)"),
+        (
+            "must_contain",
+            json!({ "type": "object", "additionalProperties": { "anyOf": [string(), strings()] } }),
+            "Patterns a file must contain, keyed by path; the file is also required (file-presence)",
+        ),
+        (
+            "condition_pattern",
+            string(),
+            "Only enforce if this pattern is present (required-pattern, window-pattern)",
+        ),
+        (
+            "skip_strings",
+            boolean(),
+            "Skip matches inside string literals (banned-pattern, default: false)",
+        ),
+        (
+            "scope_to_function",
+            boolean(),
+            "Search the enclosing function instead of `max_count` lines (window-pattern, default: false)",
+        ),
+        (
+            "components",
+            strings(),
+            "Component names checked like native form controls (require-input-label)",
+        ),
+        (
+            "app_dir",
+            string(),
+            "Expo Router app directory (expo-router-structure, default: app or src/app)",
+        ),
+        (
+            "feature_dirs",
+            string(),
+            "Glob for feature root directories, e.g. `features/*` (feature-boundaries)",
+        ),
+        (
+            "env_vars",
+            strings(),
+            "Environment variables code may read (env-allowlist)",
+        ),
+        (
+            "env_file",
+            string(),
+            "Env template such as `.env.example` whose entries are also allowed (env-allowlist)",
+        ),
+        (
+            "checks",
+            strings(),
+            "Which checks run (fetch-safety: response-ok, axios-timeout; \
+             package-scripts: unguarded-rm, pipe-to-shell; \
+             pinned-references: docker-base-tag, action-sha; default: all), \
+             or the HTTP methods route-handler-validation checks (default: POST, PUT, PATCH)",
+        ),
+        ("scripts", strings(), "Scripts the manifest must define (package-scripts)"),
+        (
+            "test_dirs",
+            strings(),
+            "Directories mirroring the source tree with tests, relative to the directory holding `src` \
+             (test-colocation; default: tests, test, __tests__)",
+        ),
+        (
+            "canonical_imports",
+            json!({ "type": "object", "additionalProperties": { "anyOf": [string(), strings()] } }),
+            "Component names keyed by the module they must be imported from (canonical-import)",
+        ),
+        (
+            "track",
+            string(),
+            "Id of another rule whose violations this ratchet budgets instead of matching `pattern` (ratchet)",
+        ),
+        (
+            "safe_fix",
+            boolean(),
+            "Whether --fix may apply this rule's fixes under `safe_fixes_only` (default: built-in fixes only)",
+        ),
+        (
+            "deadline",
+            json!({ "type": "string", "format": "date" }),
+            "Date (YYYY-MM-DD) from which the budget becomes `target` (ratchet)",
+        ),
+        (
+            "target",
+            count(),
+            "Budget once `deadline` is reached, default 0 (ratchet)",
+        ),
+        (
+            "budgets",
+            json!({ "type": "object", "additionalProperties": count() }),
+            "Per-directory ratchet budgets (\"path\" = count); `max_count` covers the rest",
+        ),
+        (
+            "ratchet_mode",
+            json!({ "enum": ["budget", "no-new"] }),
+            "Under --changed-only, `no-new` fails on any match on a changed line instead of \
+             comparing the repo-wide count with `max_count` (ratchet; default: budget)",
+        ),
+        ("owner", string(), "Team or person responsible for the rule, e.g. `@platform-team`"),
+        (
+            "requires_review",
+            boolean(),
+            "Under --changed-only, report a `review-required` notice when a change suppresses the rule or edits its config",
+        ),
+    ])
+}
+
+/// Per-type requirements as `if type == X then required [...]` clauses.
+fn rule_requirements() -> Vec<Value> {
+    let mut clauses: Vec<Value> = REQUIRED_FIELDS
+        .iter()
+        .map(|(rule_type, fields)| {
+            json!({
+                "if": { "properties": { "type": { "const": rule_type } }, "required": ["type"] },
+                "then": { "required": fields },
+            })
+        })
+        .collect();
+    clauses.push(json!({
+        "if": { "properties": { "type": { "const": "banned-import" } }, "required": ["type"] },
+        "then": {
+            "anyOf": [
+                { "required": ["packages"] },
+                { "required": ["max_relative_depth"] },
+                { "required": ["ban_cross_package_imports"] },
+            ]
+        },
+    }));
+    clauses.push(json!({
+        "if": { "properties": { "type": { "const": "file-presence" } }, "required": ["type"] },
+        "then": {
+            "anyOf": [
+                { "required": ["required_files"] },
+                { "required": ["forbidden_files"] },
+                { "required": ["must_contain"] },
+            ]
+        },
+    }));
+    clauses
+}
+
+/// JSON Schema (draft 2020-12) for `baseline.toml`, for editor validation and
+/// completion. Include files and plugins share the `rule` definition.
+pub fn config_schema() -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "baseline.toml",
+        "type": "object",
+        "properties": {
+            "include": {
+                "type": "array",
+                "items": { "type": "string" },
+                "description": "Extra config files (globs relative to this file) merged into this one",
+            },
+            "baseline": { "$ref": "#/$defs/baseline" },
+            "output": {
+                "type": "object",
+                "properties": output_properties(),
+                "additionalProperties": false,
+                "description": "Severity gates per output format and for the exit code, and the column unit",
+            },
+            "hooks": {
+                "type": "object",
+                "properties": {
+                    "after_scan": {
+                        "type": "array",
+                        "items": { "type": "object", "properties": hook_properties(), "required": ["command"], "additionalProperties": false },
+                        "description": "Commands run after `baseline scan`, with its JSON result on stdin",
+                    },
+                },
+                "additionalProperties": false,
+                "description": "External commands run with the scan result",
+            },
+            "notify": {
+                "type": "object",
+                "properties": notify_properties(),
+                "additionalProperties": false,
+                "description": "Defaults for `baseline notify`",
+            },
+            "groups": {
+                "type": "object",
+                "additionalProperties": {
+                    "type": "object",
+                    "properties": {
+                        "rules": { "type": "array", "items": { "type": "string" }, "description": "Ids of the rules in the group" },
+                    },
+                    "required": ["rules"],
+                    "additionalProperties": false,
+                },
+                "description": "Named subsets of rules, run with `scan --group <name>`",
+            },
+            "rule": { "type": "array", "items": { "$ref": "#/$defs/rule" } },
+        },
+        "required": ["baseline"],
+        "$defs": {
+            "baseline": {
+                "type": "object",
+                "properties": baseline_properties(),
+            },
+            "rule": {
+                "type": "object",
+                "properties": rule_properties(),
+                "required": ["id"],
+                "allOf": rule_requirements(),
+            },
+        },
+    })
+}
+
+fn nullable(schema: Value) -> Value {
+    json!({ "anyOf": [schema, { "type": "null" }] })
+}
+
+fn object(fields: Vec<(&str, Value, &str)>) -> Value {
+    let required: Vec<&str> = fields.iter().map(|(name, _, _)| *name).collect();
+    json!({
+        "type": "object",
+        "properties": properties(fields),
+        "required": required,
+    })
+}
+
+fn origin() -> Value {
+    json!({
+        "type": "object",
+        "properties": {
+            "kind": { "enum": ["user", "preset", "scoped-preset", "plugin"] },
+            "preset": { "type": "string" },
+            "path": { "type": "string" },
+        },
+        "required": ["kind"],
+        "description": "Where the rule was defined",
+    })
+}
+
+fn fix() -> Value {
+    object(vec![
+        ("old", string(), "Text to replace"),
+        ("new", string(), "Replacement text"),
+    ])
+}
+
+/// JSON Schema (draft 2020-12) for `scan --format json` output at
+/// [`JSON_OUTPUT_VERSION`]. Fields are only added within a version; a change
+/// to an existing field bumps it.
+pub fn output_schema() -> Value {
+    let severity = json!({ "enum": ["error", "warning", "info", "hint"] });
+    let violation = object(vec![
+        ("fingerprint", string(), "Stable hash of rule, file and matched line, for baselining"),
+        ("rule_id", string(), "Id of the rule that fired"),
+        ("config", nullable(string()), "Config the rule came from, when several configs were scanned"),
+        ("origin", nullable(origin()), "Where the rule was defined"),
+        ("severity", severity.clone(), "Violation severity"),
+        ("file", string(), "File path as scanned"),
+        ("line", nullable(count()), "1-based line"),
+        ("column", nullable(count()), "1-based column, in `summary.column_unit` units"),
+        ("message", string(), "Rule message"),
+        ("suggest", nullable(string()), "First suggestion's text"),
+        (
+            "suggestions",
+            json!({
+                "type": "array",
+                "items": object(vec![
+                    ("title", string(), "What to do instead"),
+                    ("fix", nullable(fix()), "Edit that applies the suggestion"),
+                ]),
+            }),
+            "Every suggestion",
+        ),
+        ("source_line", nullable(string()), "The offending source line"),
+        (
+            "enclosing",
+            nullable(string()),
+            "Function, method or component the violation is in (JS/TS files)",
+        ),
+        ("fix", nullable(fix()), "Automatic fix applied by --fix"),
+    ]);
+    let deadline = object(vec![
+        ("date", string(), "Deadline (YYYY-MM-DD)"),
+        ("target", count(), "Budget from the deadline on"),
+        ("days_remaining", json!({ "type": "integer" }), "Days until the deadline; zero or less once reached"),
+        ("weekly_rate", count(), "Matches to remove per week to meet the target"),
+    ]);
+    let mut ratchet = object(vec![
+        ("rule_id", string(), "Ratchet rule id"),
+        ("scope", nullable(string()), "Budget directory, for `[rule.budgets]` scopes"),
+        ("found", count(), "Matches found"),
+        ("max", count(), "Budget in force"),
+        ("pass", boolean(), "Whether found <= max"),
+    ]);
+    ratchet["properties"]["deadline"] = deadline;
+    let rule = object(vec![
+        ("rule_id", string(), "Rule id"),
+        ("type", string(), "Rule type"),
+        ("severity", severity, "Configured severity"),
+        ("fired", count(), "Violations reported (every match for ratchets)"),
+        ("glob_restricted", boolean(), "Whether `glob` or `exclude_glob` narrows the rule"),
+        ("conditioned", boolean(), "Whether file conditions narrow the rule"),
+        ("origin", origin(), "Where the rule was defined"),
+        ("owner", nullable(string()), "Team or person responsible for the rule"),
+        ("requires_review", boolean(), "Whether suppressing the rule or changing its config needs the owner's review"),
+    ]);
+    let summary = object(vec![
+        ("total", count(), "Violations reported"),
+        ("errors", count(), "Error-severity violations"),
+        ("warnings", count(), "Warning-severity violations"),
+        ("info", count(), "Info-severity violations"),
+        ("hints", count(), "Hint-severity violations"),
+        ("files_scanned", count(), "Files scanned"),
+        ("generated_skipped", count(), "Files skipped as generated"),
+        ("rules_loaded", count(), "Rules loaded"),
+        ("groups", strings(), "Config groups the scan was limited to (`--group`)"),
+        ("column_unit", json!({ "enum": ["bytes", "chars", "utf-16"] }), "Unit of `column`"),
+        ("stopped_early", boolean(), "`--fail-fast` stopped the scan early; `ratchet` is empty because ratchets were not evaluated"),
+    ]);
+    let fixes = object(vec![
+        ("dry_run", boolean(), "Whether fixes were only previewed"),
+        ("applied", count(), "Fixes applied"),
+        ("skipped", count(), "Fixes skipped because the file changed"),
+        ("conflicted", count(), "Fixes that overlapped another fix"),
+        (
+            "outcomes",
+            json!({
+                "type": "array",
+                "items": object(vec![
+                    ("file", string(), "File path"),
+                    ("line", nullable(count()), "1-based line"),
+                    ("rule_id", string(), "Rule id"),
+                    ("status", string(), "Outcome of the fix"),
+                ]),
+            }),
+            "Per-fix outcome",
+        ),
+    ]);
+    let project = object(vec![
+        ("name", string(), "Project name"),
+        ("root", string(), "Project directory"),
+        ("errors", count(), "Error-severity violations in the project"),
+        ("warnings", count(), "Warning-severity violations in the project"),
+        ("files_scanned", count(), "Files scanned in the project"),
+        ("files_with_violations", strings(), "Files with at least one violation"),
+        ("exit_code", json!({ "type": "integer" }), "Exit code the project would produce on its own"),
+    ]);
+
+    let mut schema = object(vec![
+        ("version", json!({ "const": JSON_OUTPUT_VERSION }), "Output format version"),
+        ("violations", json!({ "type": "array", "items": violation }), "Reported violations"),
+        ("summary", summary, "Counts for the scan"),
+        (
+            "ratchet",
+            json!({ "type": "object", "additionalProperties": ratchet }),
+            "Ratchet counts, keyed by rule id or `rule:scope`",
+        ),
+        ("rules", json!({ "type": "array", "items": rule }), "Every loaded rule with its hit count"),
+    ]);
+    // Present only with the flags that produce them
+    let optional = properties(vec![
+        ("fixes", fixes, "Fix results, with --fix"),
+        ("projects", json!({ "type": "array", "items": project }), "Per-project results, with --by-project"),
+    ]);
+    schema["properties"].as_object_mut().unwrap().extend(optional);
+    schema["$schema"] = json!("https://json-schema.org/draft/2020-12/schema");
+    schema["title"] = json!("baseline scan --format json");
+    schema
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::toml_config::{BaselineSection, NotifySection, OutputSection, TomlRule};
+
+    fn keys(value: Value) -> Vec<String> {
+        let mut keys: Vec<String> = value.as_object().unwrap().keys().cloned().collect();
+        keys.sort();
+        keys
+    }
+
+    #[test]
+    fn rule_properties_match_toml_rule_fields() {
+        let mut rule = TomlRule::default();
+        rule.budgets.insert("src".into(), 1);
+        rule.must_contain.insert(".gitignore".into(), Default::default());
+        rule.canonical_imports.insert("@acme/ui".into(), Default::default());
+        let fields = keys(serde_json::to_value(&rule).unwrap());
+        assert_eq!(keys(Value::Object(rule_properties())), fields);
+    }
+
+    #[test]
+    fn baseline_properties_match_section_fields() {
+        let section: BaselineSection = toml::from_str("").unwrap();
+        let fields = keys(serde_json::to_value(&section).unwrap());
+        assert_eq!(keys(Value::Object(baseline_properties())), fields);
+    }
+
+    #[test]
+    fn output_properties_match_section_fields() {
+        let fields = keys(serde_json::to_value(OutputSection::default()).unwrap());
+        assert_eq!(keys(Value::Object(output_properties())), fields);
+    }
+
+    #[test]
+    fn hook_properties_match_hook_fields() {
+        let hook: crate::cli::toml_config::Hook = toml::from_str("command = [\"true\"]").unwrap();
+        let fields = keys(serde_json::to_value(hook).unwrap());
+        assert_eq!(keys(Value::Object(hook_properties())), fields);
+    }
+
+    #[test]
+    fn notify_properties_match_section_fields() {
+        let fields = keys(serde_json::to_value(NotifySection::default()).unwrap());
+        assert_eq!(keys(Value::Object(notify_properties())), fields);
+    }
+
+    #[test]
+    fn schema_lists_every_rule_type_and_requirement() {
+        let schema = config_schema();
+        let rule = &schema["$defs"]["rule"];
+        let types = rule["properties"]["type"]["enum"].as_array().unwrap();
+        assert_eq!(types.len(), RULE_TYPES.len() + RULE_TYPE_ALIASES.len());
+        assert!(types.contains(&json!("regex")));
+        assert!(types.contains(&json!("banned-pattern")));
+        assert!(rule["properties"]["pattern"]["description"].is_string());
+
+        let ratchet = rule["allOf"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|c| c["if"]["properties"]["type"]["const"] == "ratchet")
+            .unwrap();
+        assert_eq!(ratchet["then"]["required"], json!(["pattern", "max_count"]));
+        for (rule_type, _) in REQUIRED_FIELDS {
+            assert!(RULE_TYPES.contains(rule_type));
+        }
+    }
+}
@@ -0,0 +1,364 @@
+use crate::version::{Version, VersionError};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Where release binaries are published, one asset per target triple.
+pub const RELEASES_URL: &str = "https://github.com/stewartjarod/baseline/releases";
+
+#[derive(Debug)]
+pub enum UpdateError {
+    CurlNotFound,
+    UnsupportedPlatform { os: &'static str, arch: &'static str },
+    InvalidVersion(VersionError),
+    LatestUnknown(String),
+    DownloadFailed(String),
+    ChecksumUnavailable(String),
+    ChecksumMismatch { expected: String, actual: String },
+    BrokenDownload(String),
+    Install(PathBuf, std::io::Error),
+}
+
+impl fmt::Display for UpdateError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            UpdateError::CurlNotFound => write!(f, "curl is not installed or not in PATH"),
+            UpdateError::UnsupportedPlatform { os, arch } => {
+                write!(f, "no prebuilt binary for {}-{}; build from source with `cargo install code-baseline`", arch, os)
+            }
+            UpdateError::InvalidVersion(e) => write!(f, "{}", e),
+            UpdateError::LatestUnknown(msg) => write!(f, "could not find the latest release: {}", msg),
+            UpdateError::DownloadFailed(msg) => write!(f, "download failed: {}", msg),
+            UpdateError::ChecksumUnavailable(msg) => write!(f, "cannot verify the download: {}", msg),
+            UpdateError::ChecksumMismatch { expected, actual } => write!(
+                f,
+                "downloaded binary does not match its published SHA-256 (expected {}, got {}); it was deleted",
+                expected, actual
+            ),
+            UpdateError::BrokenDownload(msg) => write!(f, "downloaded binary does not run: {}", msg),
+            UpdateError::Install(path, e) => write!(f, "cannot replace {}: {}", path.display(), e),
+        }
+    }
+}
+
+impl std::error::Error for UpdateError {}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum UpdateOutcome {
+    UpToDate(Version),
+    Updated { from: Version, to: Version, path: PathBuf },
+}
+
+/// The release target triple for an OS and architecture, as named by
+/// `std::env::consts`.
+pub fn target_for(os: &str, arch: &str) -> Option<&'static str> {
+    match (os, arch) {
+        ("macos", "aarch64") => Some("aarch64-apple-darwin"),
+        ("macos", "x86_64") => Some("x86_64-apple-darwin"),
+        ("linux", "x86_64") => Some("x86_64-unknown-linux-gnu"),
+        ("linux", "aarch64") => Some("aarch64-unknown-linux-gnu"),
+        ("windows", "x86_64") => Some("x86_64-pc-windows-msvc"),
+        _ => None,
+    }
+}
+
+/// The release asset for `target` at `version`.
+pub fn download_url(target: &str, version: &Version) -> String {
+    let ext = if target.contains("windows") { ".exe" } else { "" };
+    format!("{}/download/v{}/baseline-{}{}", RELEASES_URL, version, target, ext)
+}
+
+/// The published SHA-256 of a release asset, in `sha256sum` format.
+pub fn checksum_url(asset_url: &str) -> String {
+    format!("{}.sha256", asset_url)
+}
+
+/// The version in a release page URL (`.../releases/tag/v1.6.0`), which is
+/// where `/releases/latest` redirects to.
+pub fn version_from_release_url(url: &str) -> Option<Version> {
+    let tag = url.trim().rsplit_once("/tag/")?.1;
+    Version::parse(tag.trim_end_matches('/')).ok()
+}
+
+/// Replace the running binary with the release matching the host triple:
+/// `version` if given, otherwise the latest release. Nothing is downloaded
+/// when the binary already is that version. The download must match the
+/// release's published SHA-256 before it is run or installed.
+pub fn self_update(version: Option<&str>) -> Result<UpdateOutcome, UpdateError> {
+    let (os, arch) = (std::env::consts::OS, std::env::consts::ARCH);
+    let target = target_for(os, arch).ok_or(UpdateError::UnsupportedPlatform { os, arch })?;
+    let current = Version::current();
+    let wanted = match version {
+        Some(v) => Version::parse(v).map_err(UpdateError::InvalidVersion)?,
+        None => latest_version()?,
+    };
+    if wanted == current {
+        return Ok(UpdateOutcome::UpToDate(current));
+    }
+
+    let exe = std::env::current_exe()
+        .and_then(|p| p.canonicalize())
+        .map_err(|e| UpdateError::Install(PathBuf::from("baseline"), e))?;
+    let url = download_url(target, &wanted);
+    tracing::info!("downloading {}", url);
+    install(&url, &exe)?;
+    Ok(UpdateOutcome::Updated {
+        from: current,
+        to: wanted,
+        path: exe,
+    })
+}
+
+/// Download `url` next to `exe`, verify and try it, then move it over
+/// `exe`. The download is deleted if any step fails.
+fn install(url: &str, exe: &Path) -> Result<(), UpdateError> {
+    let download = sibling(exe, "download");
+    let installed = fetch(url, &download)
+        .and_then(|()| published_checksum(url))
+        .and_then(|expected| install_download(&download, &expected, exe));
+    if installed.is_err() {
+        let _ = fs::remove_file(&download);
+    }
+    installed
+}
+
+/// Check `download` against the `expected` SHA-256, run it once, then move
+/// it over `exe`. `exe` is untouched unless every check passes.
+fn install_download(download: &Path, expected: &str, exe: &Path) -> Result<(), UpdateError> {
+    let bytes = fs::read(download).map_err(|e| UpdateError::Install(download.to_path_buf(), e))?;
+    let actual = sha256_hex(&bytes);
+    if actual != expected {
+        return Err(UpdateError::ChecksumMismatch {
+            expected: expected.to_string(),
+            actual,
+        });
+    }
+    check_runs(download)?;
+    replace(exe, download)
+}
+
+/// Resolve `/releases/latest` by following its redirect to the tag page.
+fn latest_version() -> Result<Version, UpdateError> {
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location", "--max-time", "30"])
+        .args(["--output", if cfg!(windows) { "NUL" } else { "/dev/null" }])
+        .args(["--write-out", "%{url_effective}"])
+        .arg(format!("{}/latest", RELEASES_URL))
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|_| UpdateError::CurlNotFound)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(UpdateError::LatestUnknown(stderr.trim().to_string()));
+    }
+    let url = String::from_utf8_lossy(&output.stdout);
+    version_from_release_url(&url)
+        .ok_or_else(|| UpdateError::LatestUnknown(format!("unexpected release URL '{}'", url.trim())))
+}
+
+fn fetch(url: &str, dest: &Path) -> Result<(), UpdateError> {
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location", "--max-time", "300"])
+        .arg("--output")
+        .arg(dest)
+        .arg(url)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|_| UpdateError::CurlNotFound)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(UpdateError::DownloadFailed(format!("{} ({})", stderr.trim(), url)));
+    }
+    Ok(())
+}
+
+/// The SHA-256 published next to the release asset at `url`.
+fn published_checksum(url: &str) -> Result<String, UpdateError> {
+    let checksum_url = checksum_url(url);
+    let output = Command::new("curl")
+        .args(["--silent", "--show-error", "--fail", "--location", "--max-time", "30"])
+        .arg(&checksum_url)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|_| UpdateError::CurlNotFound)?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(UpdateError::ChecksumUnavailable(format!("{} ({})", stderr.trim(), checksum_url)));
+    }
+    parse_checksum(&String::from_utf8_lossy(&output.stdout))
+        .ok_or_else(|| UpdateError::ChecksumUnavailable(format!("malformed checksum file ({})", checksum_url)))
+}
+
+/// The digest in a `sha256sum` line (`<hex>  <file>`), lowercased.
+fn parse_checksum(text: &str) -> Option<String> {
+    let digest = text.split_whitespace().next()?.to_ascii_lowercase();
+    (digest.len() == 64 && digest.bytes().all(|b| b.is_ascii_hexdigit())).then_some(digest)
+}
+
+/// Lowercase hex SHA-256 of `data`, as `sha256sum` prints it.
+fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+/// Make the download executable and run it once, so a truncated or
+/// wrong-architecture file never replaces a working binary.
+fn check_runs(path: &Path) -> Result<(), UpdateError> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755))
+            .map_err(|e| UpdateError::Install(path.to_path_buf(), e))?;
+    }
+    let output = Command::new(path)
+        .arg("--help")
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|e| UpdateError::BrokenDownload(e.to_string()))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(UpdateError::BrokenDownload(stderr.trim().to_string()));
+    }
+    Ok(())
+}
+
+/// Move `download` over `exe`. Windows can't overwrite a running
+/// executable, but can rename it, so the old binary is moved aside first.
+fn replace(exe: &Path, download: &Path) -> Result<(), UpdateError> {
+    let install = |e| UpdateError::Install(exe.to_path_buf(), e);
+    if cfg!(windows) {
+        let old = sibling(exe, "old");
+        let _ = fs::remove_file(&old);
+        fs::rename(exe, &old).map_err(install)?;
+    }
+    fs::rename(download, exe).map_err(install)
+}
+
+fn sibling(exe: &Path, suffix: &str) -> PathBuf {
+    let name = exe.file_name().map(|n| n.to_string_lossy().into_owned()).unwrap_or_default();
+    exe.with_file_name(format!("{}.{}", name, suffix))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_hosts_to_release_assets() {
+        let v = Version::parse("1.6.0").unwrap();
+        let url = |os, arch| target_for(os, arch).map(|t| download_url(t, &v));
+        assert_eq!(
+            url("linux", "x86_64").unwrap(),
+            "https://github.com/stewartjarod/baseline/releases/download/v1.6.0/baseline-x86_64-unknown-linux-gnu"
+        );
+        assert_eq!(
+            url("windows", "x86_64").unwrap(),
+            "https://github.com/stewartjarod/baseline/releases/download/v1.6.0/baseline-x86_64-pc-windows-msvc.exe"
+        );
+        assert_eq!(target_for("macos", "aarch64"), Some("aarch64-apple-darwin"));
+        assert_eq!(target_for("freebsd", "x86_64"), None);
+    }
+
+    #[test]
+    fn reads_version_from_latest_redirect() {
+        assert_eq!(
+            version_from_release_url("https://github.com/stewartjarod/baseline/releases/tag/v1.7.2\n"),
+            Some(Version::parse("1.7.2").unwrap())
+        );
+        assert_eq!(version_from_release_url("https://github.com/stewartjarod/baseline/releases"), None);
+    }
+
+    #[test]
+    fn hashes_match_known_sha256_digests() {
+        assert_eq!(sha256_hex(b""), "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855");
+        assert_eq!(sha256_hex(b"abc"), "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad");
+        assert_eq!(
+            sha256_hex(&[b'a'; 1000]),
+            "41edece42d63e8d9bf515a9ba6932e1c20cbc9f5a5d134645adb5db1b9737ea3"
+        );
+        assert_eq!(
+            checksum_url("https://example.com/baseline-x86_64-unknown-linux-gnu"),
+            "https://example.com/baseline-x86_64-unknown-linux-gnu.sha256"
+        );
+    }
+
+    #[test]
+    fn reads_sha256sum_lines() {
+        let digest = "BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD";
+        assert_eq!(
+            parse_checksum(&format!("{}  baseline-x86_64-unknown-linux-gnu\n", digest)),
+            Some(digest.to_ascii_lowercase())
+        );
+        assert_eq!(parse_checksum("not-a-digest  baseline"), None);
+        assert_eq!(parse_checksum(""), None);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn mismatched_download_is_deleted_before_it_runs() {
+        if Command::new("curl").arg("--version").output().is_err() {
+            return;
+        }
+        let dir = tempfile::tempdir().unwrap();
+        let release = dir.path().join("baseline-x86_64-unknown-linux-gnu");
+        let ran = dir.path().join("ran");
+        fs::write(&release, format!("#!/bin/sh\ntouch {}\n", ran.display())).unwrap();
+        fs::write(checksum_url(&release.to_string_lossy()), format!("{}  baseline\n", sha256_hex(b"other"))).unwrap();
+        let exe = dir.path().join("baseline");
+        fs::write(&exe, "old").unwrap();
+
+        let url = format!("file://{}", release.display());
+        let err = install(&url, &exe).unwrap_err();
+        assert!(matches!(err, UpdateError::ChecksumMismatch { .. }), "{}", err);
+        assert!(!sibling(&exe, "download").exists());
+        assert!(!ran.exists());
+        assert_eq!(fs::read_to_string(&exe).unwrap(), "old");
+
+        // No published checksum is an error too
+        fs::remove_file(checksum_url(&release.to_string_lossy())).unwrap();
+        let err = install(&url, &exe).unwrap_err();
+        assert!(matches!(err, UpdateError::ChecksumUnavailable(_)), "{}", err);
+        assert!(!sibling(&exe, "download").exists());
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn checksum_mismatch_leaves_existing_binary_untouched() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("baseline");
+        let download = sibling(&exe, "download");
+        let ran = dir.path().join("ran");
+        fs::write(&exe, "old").unwrap();
+        fs::write(&download, format!("#!/bin/sh\ntouch {}\n", ran.display())).unwrap();
+
+        let err = install_download(&download, &sha256_hex(b"other"), &exe).unwrap_err();
+        assert!(
+            matches!(&err, UpdateError::ChecksumMismatch { expected, .. } if *expected == sha256_hex(b"other")),
+            "{}",
+            err
+        );
+        assert_eq!(fs::read_to_string(&exe).unwrap(), "old");
+        assert!(!ran.exists());
+
+        // The matching digest installs it
+        let digest = sha256_hex(&fs::read(&download).unwrap());
+        install_download(&download, &digest, &exe).unwrap();
+        assert!(ran.exists());
+        assert!(fs::read_to_string(&exe).unwrap().starts_with("#!/bin/sh"));
+        assert!(!download.exists());
+    }
+
+    #[test]
+    fn replace_moves_download_over_binary() {
+        let dir = tempfile::tempdir().unwrap();
+        let exe = dir.path().join("baseline");
+        let download = sibling(&exe, "download");
+        fs::write(&exe, "old").unwrap();
+        fs::write(&download, "new").unwrap();
+
+        replace(&exe, &download).unwrap();
+        assert_eq!(fs::read_to_string(&exe).unwrap(), "new");
+        assert!(!download.exists());
+    }
+}
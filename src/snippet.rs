@@ -0,0 +1,156 @@
+use crate::cli::toml_config::TomlRule;
+use crate::rules::factory::{self, FactoryError};
+use crate::rules::{ScanContext, Violation};
+use crate::scan::{self, ScanError};
+use std::fmt;
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum SnippetError {
+    Config(ScanError),
+    UnknownRule(String),
+    Definition(String),
+    Build(FactoryError),
+}
+
+impl fmt::Display for SnippetError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SnippetError::Config(e) => write!(f, "{}", e),
+            SnippetError::UnknownRule(id) => write!(f, "no rule with id '{}' in the config", id),
+            SnippetError::Definition(e) => write!(f, "invalid rule definition: {}", e),
+            SnippetError::Build(e) => write!(f, "failed to build rule: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for SnippetError {}
+
+/// The rule `check-snippet` runs.
+#[derive(Debug, Clone)]
+pub enum SnippetRule {
+    /// A rule from the config, by id (presets and plugins included).
+    Id(String),
+    /// A `[[rule]]` body written inline as TOML; `id` may be left out.
+    Inline(String),
+    /// A `banned-pattern` rule for this pattern.
+    Pattern { pattern: String, regex: bool },
+}
+
+impl SnippetRule {
+    /// The rule definition to test. Only [`SnippetRule::Id`] reads the config.
+    pub fn resolve(&self, config_path: &Path) -> Result<TomlRule, SnippetError> {
+        match self {
+            SnippetRule::Id(id) => {
+                let rules = scan::list_rules(config_path).map_err(SnippetError::Config)?;
+                let rule = rules
+                    .iter()
+                    .find(|r| &r.id == id)
+                    .ok_or_else(|| SnippetError::UnknownRule(id.clone()))?;
+                // A tracking ratchet matches whatever the tracked rule does
+                match rule.track.as_deref() {
+                    Some(track) => rules
+                        .iter()
+                        .find(|r| r.id == track)
+                        .cloned()
+                        .ok_or_else(|| SnippetError::UnknownRule(track.to_string())),
+                    None => Ok(rule.clone()),
+                }
+            }
+            SnippetRule::Inline(definition) => {
+                let mut table: toml::Table =
+                    toml::from_str(definition).map_err(|e| SnippetError::Definition(e.to_string()))?;
+                table
+                    .entry("id")
+                    .or_insert_with(|| toml::Value::String("snippet".into()));
+                table
+                    .try_into()
+                    .map_err(|e: toml::de::Error| SnippetError::Definition(e.to_string()))
+            }
+            SnippetRule::Pattern { pattern, regex } => Ok(TomlRule {
+                id: "snippet".into(),
+                rule_type: "banned-pattern".into(),
+                pattern: Some(pattern.clone()),
+                regex: *regex,
+                ..Default::default()
+            }),
+        }
+    }
+}
+
+/// What a rule reports for a snippet.
+#[derive(Debug)]
+pub struct SnippetResult {
+    pub violations: Vec<Violation>,
+    /// The rule's `glob`, when a scan would skip a file named like the snippet.
+    pub skipped_by_glob: Option<String>,
+}
+
+/// Run `rule` over `content` as if it were the file `filename`. The rule's
+/// glob is reported rather than applied, so a pattern can be tried on any
+/// snippet.
+pub fn check_snippet(rule: &TomlRule, filename: &str, content: &str) -> Result<SnippetResult, SnippetError> {
+    let built = factory::build_rule(&rule.rule_type, &rule.to_rule_config()).map_err(SnippetError::Build)?;
+    let path = Path::new(filename);
+    let violations = built.check_file(&ScanContext::new(path, content));
+
+    let skipped_by_glob = built.file_glob().and_then(|glob| {
+        let matcher = globset::Glob::new(glob).ok()?.compile_matcher();
+        let file_name = path.file_name().unwrap_or_default();
+        (!matcher.is_match(path) && !matcher.is_match(file_name)).then(|| glob.to_string())
+    });
+    Ok(SnippetResult {
+        violations,
+        skipped_by_glob,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checks_a_pattern_against_a_snippet() {
+        let rule = SnippetRule::Pattern {
+            pattern: r"legacy(Fetch|Call)\(".into(),
+            regex: true,
+        }
+        .resolve(Path::new("unused.toml"))
+        .unwrap();
+        let result = check_snippet(&rule, "snippet.ts", "ok();\nlegacyFetch('/a');\nlegacyCall();\n").unwrap();
+        let found: Vec<_> = result.violations.iter().map(|v| (v.line, v.column)).collect();
+        assert_eq!(found, vec![(Some(2), Some(1)), (Some(3), Some(1))]);
+        assert_eq!(result.skipped_by_glob, None);
+    }
+
+    #[test]
+    fn inline_definitions_and_config_rules() {
+        let rule = SnippetRule::Inline(
+            "type = \"banned-pattern\"\npattern = \"TODO\"\nglob = \"**/*.py\"".into(),
+        )
+        .resolve(Path::new("unused.toml"))
+        .unwrap();
+        assert_eq!(rule.id, "snippet");
+        let result = check_snippet(&rule, "snippet.tsx", "// TODO: remove\n").unwrap();
+        assert_eq!(result.violations.len(), 1);
+        assert_eq!(result.skipped_by_glob.as_deref(), Some("**/*.py"));
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        std::fs::write(
+            &config,
+            "[baseline]\n\n[[rule]]\nid = \"no-console\"\ntype = \"banned-pattern\"\npattern = \"console.log\"\n",
+        )
+        .unwrap();
+        let rule = SnippetRule::Id("no-console".into()).resolve(&config).unwrap();
+        assert_eq!(rule.pattern.as_deref(), Some("console.log"));
+        assert!(matches!(
+            SnippetRule::Id("missing".into()).resolve(&config),
+            Err(SnippetError::UnknownRule(_))
+        ));
+        assert!(matches!(
+            SnippetRule::Inline("pattern = ".into()).resolve(&config),
+            Err(SnippetError::Definition(_))
+        ));
+    }
+}
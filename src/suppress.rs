@@ -0,0 +1,329 @@
+use crate::scan::ScanResult;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+/// Options for `scan --fix-suppress`.
+#[derive(Debug, Clone, Default)]
+pub struct SuppressOptions {
+    /// Only suppress violations of these rule ids (empty = all rules).
+    pub rules: Vec<String>,
+    /// Ticket reference written into each comment as `TODO(<ticket>)`.
+    pub ticket: Option<String>,
+}
+
+/// Comment syntax used for an inserted suppression line.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CommentStyle {
+    /// `// ...`
+    Line,
+    /// `{/* ... */}` — JSX children, where `//` would render as text.
+    Jsx,
+    /// `/* ... */`
+    Block,
+    /// `# ...`
+    Hash,
+    /// `<!-- ... -->`
+    Html,
+}
+
+impl CommentStyle {
+    fn wrap(self, body: &str) -> String {
+        match self {
+            CommentStyle::Line => format!("// {}", body),
+            CommentStyle::Jsx => format!("{{/* {} */}}", body),
+            CommentStyle::Block => format!("/* {} */", body),
+            CommentStyle::Hash => format!("# {}", body),
+            CommentStyle::Html => format!("<!-- {} -->", body),
+        }
+    }
+}
+
+/// Insert `baseline:allow-next-line` comments above every violation of the
+/// selected rules, so existing debt is suppressed but stays greppable.
+/// Returns the number of comments inserted (or that would be, with `dry_run`).
+pub fn apply_suppressions(result: &ScanResult, options: &SuppressOptions, dry_run: bool) -> usize {
+    // file -> line -> rule ids violated on that line
+    let mut targets: BTreeMap<String, BTreeMap<usize, Vec<String>>> = BTreeMap::new();
+    for v in &result.violations {
+        if !options.rules.is_empty() && !options.rules.contains(&v.rule_id) {
+            continue;
+        }
+        let Some(line) = v.line else { continue };
+        let ids = targets
+            .entry(v.file.display().to_string())
+            .or_default()
+            .entry(line)
+            .or_default();
+        if !ids.contains(&v.rule_id) {
+            ids.push(v.rule_id.clone());
+        }
+    }
+
+    let mut total = 0;
+    for (file_path, lines) in &targets {
+        let path = Path::new(file_path);
+        let content = match fs::read_to_string(path) {
+            Ok(c) => c,
+            Err(_) => continue,
+        };
+
+        let (modified, inserted) = suppress_content(path, &content, lines, options.ticket.as_deref());
+        if inserted > 0 && !dry_run {
+            if let Err(e) = fs::write(path, &modified) {
+                tracing::error!("failed to write {}: {}", file_path, e);
+                continue;
+            }
+        }
+        total += inserted;
+    }
+    total
+}
+
+/// Insert suppression comments into `content` above each target line
+/// (1-indexed), matching the target line's indentation.
+fn suppress_content(
+    path: &Path,
+    content: &str,
+    targets: &BTreeMap<usize, Vec<String>>,
+    ticket: Option<&str>,
+) -> (String, usize) {
+    let newline = if content.contains("\r\n") { "\r\n" } else { "\n" };
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let mut inserted = 0;
+    let todo = match ticket {
+        Some(t) => format!("TODO({})", t),
+        None => "TODO".to_string(),
+    };
+
+    // Bottom-up so earlier insertions don't shift later targets
+    for (&line_num, rule_ids) in targets.iter().rev() {
+        if line_num == 0 || line_num > lines.len() {
+            continue;
+        }
+        let idx = line_num - 1;
+        let target = &lines[idx];
+        let indent: String = target.chars().take_while(|c| c.is_whitespace()).collect();
+        let style = comment_style(path, &lines, idx);
+
+        let comments: Vec<String> = rule_ids
+            .iter()
+            .map(|id| {
+                let body = format!("baseline:allow-next-line {} -- {}", id, todo);
+                format!("{}{}", indent, style.wrap(&body))
+            })
+            .collect();
+        inserted += comments.len();
+        lines.splice(idx..idx, comments);
+    }
+
+    let mut output = lines.join(newline);
+    if content.ends_with('\n') {
+        output.push_str(newline);
+    }
+    (output, inserted)
+}
+
+/// Pick comment syntax from the file extension and, for JSX files, whether
+/// the target line sits among JSX children.
+fn comment_style(path: &Path, lines: &[String], idx: usize) -> CommentStyle {
+    let ext = path
+        .extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default();
+
+    match ext.as_str() {
+        "tsx" | "jsx" if is_jsx_child(lines, idx) => CommentStyle::Jsx,
+        "css" | "scss" | "less" => CommentStyle::Block,
+        "py" | "rb" | "sh" | "bash" | "yml" | "yaml" | "toml" => CommentStyle::Hash,
+        "html" | "htm" | "vue" | "svelte" | "md" | "mdx" => CommentStyle::Html,
+        _ => CommentStyle::Line,
+    }
+}
+
+/// A line is a JSX child when it starts an element (or text/expression) and
+/// the previous non-blank line closed a tag — i.e. we're between a parent's
+/// `>` and its children, where only `{/* */}` comments are valid. The first
+/// element after `return (` is an expression position, where `//` works.
+fn is_jsx_child(lines: &[String], idx: usize) -> bool {
+    let target = lines[idx].trim_start();
+    if !target.starts_with('<') && !target.starts_with('{') {
+        return false;
+    }
+    let prev = lines[..idx]
+        .iter()
+        .rev()
+        .map(|l| l.trim_end())
+        .find(|l| !l.trim().is_empty());
+    match prev {
+        Some(p) => p.ends_with('>') && !p.ends_with("=>") && !p.trim_start().starts_with("//"),
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn targets(entries: &[(usize, &[&str])]) -> BTreeMap<usize, Vec<String>> {
+        entries
+            .iter()
+            .map(|(line, ids)| (*line, ids.iter().map(|s| s.to_string()).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn inserts_line_comment_with_indentation() {
+        let content = "function f() {\n    console.log(1);\n}\n";
+        let (out, n) = suppress_content(
+            Path::new("a.ts"),
+            content,
+            &targets(&[(2, &["no-console"])]),
+            Some("JIRA-12"),
+        );
+        assert_eq!(n, 1);
+        assert_eq!(
+            out,
+            "function f() {\n    // baseline:allow-next-line no-console -- TODO(JIRA-12)\n    console.log(1);\n}\n"
+        );
+    }
+
+    #[test]
+    fn uses_jsx_comment_among_children() {
+        let content = "return (\n  <div>\n    <span className=\"bg-white\" />\n  </div>\n);\n";
+        let (out, _) = suppress_content(
+            Path::new("a.tsx"),
+            content,
+            &targets(&[(3, &["dark-mode"]), (2, &["no-div"])]),
+            None,
+        );
+        let lines: Vec<&str> = out.lines().collect();
+        // First element after `return (` is an expression position
+        assert_eq!(lines[1], "  // baseline:allow-next-line no-div -- TODO");
+        assert_eq!(lines[2], "  <div>");
+        assert_eq!(lines[3], "    {/* baseline:allow-next-line dark-mode -- TODO */}");
+        assert_eq!(lines[4], "    <span className=\"bg-white\" />");
+    }
+
+    #[test]
+    fn stacks_one_comment_per_rule() {
+        let content = "a();\n";
+        let (out, n) = suppress_content(
+            Path::new("a.ts"),
+            content,
+            &targets(&[(1, &["r1", "r2"])]),
+            None,
+        );
+        assert_eq!(n, 2);
+        assert_eq!(
+            out,
+            "// baseline:allow-next-line r1 -- TODO\n// baseline:allow-next-line r2 -- TODO\na();\n"
+        );
+    }
+
+    #[test]
+    fn picks_style_by_extension_and_keeps_crlf() {
+        let (out, _) = suppress_content(
+            Path::new("a.css"),
+            "a {\r\n  color: red;\r\n}\r\n",
+            &targets(&[(2, &["no-red"])]),
+            None,
+        );
+        assert_eq!(
+            out,
+            "a {\r\n  /* baseline:allow-next-line no-red -- TODO */\r\n  color: red;\r\n}\r\n"
+        );
+
+        let (out, _) = suppress_content(Path::new("x.py"), "x = 1", &targets(&[(1, &["r"])]), None);
+        assert_eq!(out, "# baseline:allow-next-line r -- TODO\nx = 1");
+    }
+
+    #[test]
+    fn apply_suppressions_filters_rules_and_silences_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "no-console"
+type = "banned-pattern"
+severity = "error"
+glob = "**/*.ts"
+pattern = "console.log"
+message = "no console.log"
+
+[[rule]]
+id = "no-debugger"
+type = "banned-pattern"
+severity = "error"
+glob = "**/*.ts"
+pattern = "debugger"
+message = "no debugger"
+"#,
+        )
+        .unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        fs::write(src.join("a.ts"), "console.log(1);\ndebugger;\n").unwrap();
+
+        let result = crate::scan::run_scan(&config, std::slice::from_ref(&src)).unwrap();
+        assert_eq!(result.violations.len(), 2);
+
+        let options = SuppressOptions {
+            rules: vec!["no-console".into()],
+            ticket: None,
+        };
+        assert_eq!(apply_suppressions(&result, &options, true), 1);
+        assert_eq!(
+            fs::read_to_string(src.join("a.ts")).unwrap(),
+            "console.log(1);\ndebugger;\n"
+        );
+
+        assert_eq!(apply_suppressions(&result, &options, false), 1);
+        let rescan = crate::scan::run_scan(&config, &[src]).unwrap();
+        assert_eq!(rescan.violations.len(), 1);
+        assert_eq!(rescan.violations[0].rule_id, "no-debugger");
+    }
+
+    #[test]
+    fn suppressing_multiline_jsx_tag_silences_rescan() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = dir.path().join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "no-red"
+type = "banned-pattern"
+severity = "error"
+glob = "**/*.tsx"
+pattern = "bg-red-500"
+message = "no red"
+"#,
+        )
+        .unwrap();
+        let src = dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        fs::write(
+            src.join("a.tsx"),
+            "export function A() {\n  return (\n    <div>\n      <span\n        id=\"a\"\n        className=\"bg-red-500\"\n      >\n        hi\n      </span>\n    </div>\n  );\n}\n",
+        )
+        .unwrap();
+
+        let result = crate::scan::run_scan(&config, std::slice::from_ref(&src)).unwrap();
+        assert_eq!(result.violations.len(), 1);
+
+        let options = SuppressOptions {
+            rules: Vec::new(),
+            ticket: Some("T-1".into()),
+        };
+        assert_eq!(apply_suppressions(&result, &options, false), 1);
+        let rescan = crate::scan::run_scan(&config, &[src]).unwrap();
+        assert!(rescan.violations.is_empty(), "{:?}", rescan.violations);
+    }
+}
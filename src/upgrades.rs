@@ -0,0 +1,146 @@
+use crate::cli::toml_config::TomlRule;
+use regex::Regex;
+use std::fmt;
+use std::sync::LazyLock;
+
+/// Regex heuristics that have an AST rule type checking the same thing, as
+/// `(AST rule type, what it checks, regexes that must all match)`. The
+/// regexes run against the old rule's id, message and patterns together.
+const UPGRADES: &[(&str, &str, &[&str])] = &[
+    ("no-nested-components", "components defined inside components", &[r"(?i)nested[-_ ]?comp|inline[-_ ]?comp"]),
+    ("require-img-alt", "images without alt text", &[r"<img", r"alt"]),
+    ("no-outline-none", "focus outlines removed without a focus-visible ring", &[r"outline(-none|-0|:\s*none)"]),
+    ("no-div-click-handler", "clickable <div>s without a role", &[r"<div", r"onClick"]),
+    ("no-span-click-handler", "clickable <span>s without a role", &[r"<span", r"onClick"]),
+    ("no-regexp-in-render", "RegExps rebuilt on every render", &[r"RegExp"]),
+    ("no-stylesheet-in-render", "styles rebuilt on every render", &[r"StyleSheet"]),
+    ("no-derived-state-effect", "effects that only set state", &[r"useEffect", r"\bset"]),
+    ("prefer-use-reducer", "components with many useState calls", &[r"useState"]),
+    ("no-object-dep-array", "object literals in dependency arrays", &[r"(?i)dep(endency)?[-_ ]?array"]),
+    ("no-sql-interpolation", "SQL built with template interpolation", &[r"(?i)\b(select|insert|update|delete)\b", r"\\?\$\\?\{"]),
+    ("fetch-safety", "fetch responses used without checking ok", &[r"\bfetch\b|axios"]),
+    ("no-silent-promise-catch", "promise handlers that swallow errors", &[r"\.catch|(?i)silent[-_ ]?promise"]),
+    ("heading-hierarchy", "skipped heading levels", &[r"<h(\[|[1-6])"]),
+    ("require-input-label", "form controls without a label", &[r"<(input|select|textarea)"]),
+    ("require-icon-button-label", "icon buttons without an accessible name", &[r"aria-label", r"(?i)button|icon"]),
+];
+
+/// Rule types that match text with regexes, and so can be upgraded.
+const REGEX_RULE_TYPES: &[&str] = &["banned-pattern", "ratchet", "window-pattern"];
+
+static COMPILED: LazyLock<Vec<(&str, &str, Vec<Regex>)>> = LazyLock::new(|| {
+    UPGRADES
+        .iter()
+        .map(|(ast_type, what, patterns)| {
+            let regexes = patterns.iter().map(|p| Regex::new(p).unwrap()).collect();
+            (*ast_type, *what, regexes)
+        })
+        .collect()
+});
+
+/// A regex rule that an AST rule type could replace.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Upgrade {
+    pub rule_id: String,
+    pub rule_type: String,
+    pub ast_type: &'static str,
+    pub what: &'static str,
+}
+
+impl fmt::Display for Upgrade {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "rule '{}' ({}) looks for {} with a regex; type = \"{}\" checks the syntax tree instead",
+            self.rule_id, self.rule_type, self.what, self.ast_type
+        )
+    }
+}
+
+/// Regex rules in `rules` (as resolved, presets included) that have an AST
+/// equivalent the config doesn't already use. Disabled rules and ratchets
+/// that `track` another rule are skipped.
+pub fn suggest_upgrades(rules: &[TomlRule]) -> Vec<Upgrade> {
+    let mut upgrades = Vec::new();
+    for rule in rules {
+        if !rule.enabled || rule.track.is_some() || !REGEX_RULE_TYPES.contains(&rule.rule_type.as_str()) {
+            continue;
+        }
+        let haystack = [
+            Some(rule.id.as_str()),
+            Some(rule.message.as_str()),
+            rule.pattern.as_deref(),
+            rule.condition_pattern.as_deref(),
+        ]
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>()
+        .join("\n");
+
+        let found = COMPILED.iter().find(|(ast_type, _, regexes)| {
+            !rules.iter().any(|r| r.enabled && r.rule_type == *ast_type)
+                && regexes.iter().all(|re| re.is_match(&haystack))
+        });
+        if let Some(&(ast_type, what, _)) = found {
+            upgrades.push(Upgrade {
+                rule_id: rule.id.clone(),
+                rule_type: rule.rule_type.clone(),
+                ast_type,
+                what,
+            });
+        }
+    }
+    upgrades
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(id: &str, rule_type: &str, pattern: &str) -> TomlRule {
+        TomlRule {
+            id: id.into(),
+            rule_type: rule_type.into(),
+            pattern: Some(pattern.into()),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn suggests_ast_rules_for_regex_heuristics() {
+        let rules = vec![
+            rule("img-needs-alt", "banned-pattern", r"<img(?![^>]*alt=)"),
+            rule("no-outline", "banned-pattern", "outline-none"),
+            rule("no-console", "banned-pattern", r"console\.log"),
+            rule("sql", "ratchet", r"`SELECT .*\$\{"),
+        ];
+        let upgrades = suggest_upgrades(&rules);
+        let found: Vec<_> = upgrades.iter().map(|u| (u.rule_id.as_str(), u.ast_type)).collect();
+        assert_eq!(
+            found,
+            vec![
+                ("img-needs-alt", "require-img-alt"),
+                ("no-outline", "no-outline-none"),
+                ("sql", "no-sql-interpolation"),
+            ]
+        );
+        assert_eq!(
+            upgrades[1].to_string(),
+            "rule 'no-outline' (banned-pattern) looks for focus outlines removed without a focus-visible ring \
+             with a regex; type = \"no-outline-none\" checks the syntax tree instead"
+        );
+    }
+
+    #[test]
+    fn skips_configs_already_using_the_ast_rule() {
+        let mut rules = vec![
+            rule("nested", "banned-pattern", r"function [A-Z]\w*\(.*\{[^}]*function [A-Z]"),
+            rule("no-nested-components", "no-nested-components", ""),
+        ];
+        rules[0].message = "Don't define nested components".into();
+        assert!(suggest_upgrades(&rules).is_empty());
+
+        rules[1].enabled = false;
+        assert_eq!(suggest_upgrades(&rules)[0].ast_type, "no-nested-components");
+    }
+}
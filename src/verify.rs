@@ -0,0 +1,215 @@
+use crate::config::Severity;
+use crate::rules::{Suggestion, Violation};
+use crate::scan::ScanResult;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug)]
+pub enum VerifyError {
+    Read(std::io::Error),
+    Parse(String),
+}
+
+impl fmt::Display for VerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VerifyError::Read(e) => write!(f, "failed to read result: {}", e),
+            VerifyError::Parse(e) => write!(f, "failed to parse result JSON: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for VerifyError {}
+
+/// Differences between a recorded scan result and a fresh scan.
+#[derive(Debug, Default)]
+pub struct VerifyReport {
+    /// Recorded violations the fresh scan did not produce.
+    pub missing: Vec<Violation>,
+    /// Violations the fresh scan produced that were not recorded.
+    pub unexpected: Vec<Violation>,
+    /// Number of violations in both.
+    pub matched: usize,
+}
+
+impl VerifyReport {
+    pub fn is_match(&self) -> bool {
+        self.missing.is_empty() && self.unexpected.is_empty()
+    }
+}
+
+/// Read the violations of a `--format json` result back into `Violation`s.
+/// Fingerprints are recomputed from the fields rather than trusted, so a
+/// result that was edited by hand can't claim to match.
+pub fn read_result(path: &Path) -> Result<Vec<Violation>, VerifyError> {
+    let text = fs::read_to_string(path).map_err(VerifyError::Read)?;
+    parse_result(&text)
+}
+
+/// Parse the text of a `--format json` result. See [`read_result`].
+pub fn parse_result(text: &str) -> Result<Vec<Violation>, VerifyError> {
+    let json: serde_json::Value =
+        serde_json::from_str(text).map_err(|e| VerifyError::Parse(e.to_string()))?;
+    let entries = json
+        .get("violations")
+        .and_then(|v| v.as_array())
+        .ok_or_else(|| VerifyError::Parse("missing 'violations' array".into()))?;
+
+    entries
+        .iter()
+        .enumerate()
+        .map(|(i, entry)| {
+            violation_from_json(entry)
+                .ok_or_else(|| VerifyError::Parse(format!("violation {} is malformed", i)))
+        })
+        .collect()
+}
+
+fn violation_from_json(entry: &serde_json::Value) -> Option<Violation> {
+    let text = |key: &str| entry.get(key).and_then(|v| v.as_str()).map(str::to_string);
+    let number = |key: &str| entry.get(key).and_then(|v| v.as_u64()).map(|n| n as usize);
+    let severity = match entry.get("severity")?.as_str()? {
+        "error" => Severity::Error,
+        "warning" => Severity::Warning,
+        "info" => Severity::Info,
+        "hint" => Severity::Hint,
+        _ => return None,
+    };
+
+    Some(Violation {
+        rule_id: text("rule_id")?,
+        severity,
+        file: PathBuf::from(text("file")?),
+        line: number("line"),
+        column: number("column"),
+        message: text("message")?,
+        suggest: text("suggest").map(Suggestion::new).into_iter().collect(),
+        source_line: text("source_line"),
+        fix: None,
+        origin: None,
+        enclosing: text("enclosing"),
+    })
+}
+
+/// Compare recorded violations against a fresh scan by fingerprint. Repeated
+/// fingerprints are compared as counts, so a duplicated violation that
+/// disappears is still reported.
+pub fn compare(expected: &[Violation], actual: &ScanResult) -> VerifyReport {
+    let mut remaining: BTreeMap<String, Vec<&Violation>> = BTreeMap::new();
+    for v in expected {
+        remaining.entry(v.fingerprint()).or_default().push(v);
+    }
+
+    let mut report = VerifyReport::default();
+    for v in &actual.violations {
+        match remaining.get_mut(&v.fingerprint()).and_then(|vs| vs.pop()) {
+            Some(_) => report.matched += 1,
+            None => report.unexpected.push(v.clone()),
+        }
+    }
+    report.missing = remaining.into_values().flatten().cloned().collect();
+    report.missing.sort_by(|a, b| (&a.file, a.line).cmp(&(&b.file, b.line)));
+    report
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashMap;
+
+    fn violation(rule_id: &str, file: &str, line: usize) -> Violation {
+        Violation {
+            rule_id: rule_id.into(),
+            severity: Severity::Warning,
+            file: PathBuf::from(file),
+            line: Some(line),
+            column: Some(1),
+            message: "m".into(),
+            suggest: Vec::new(),
+            source_line: None,
+            fix: None,
+            origin: None,
+            enclosing: None,
+        }
+    }
+
+    fn result(violations: Vec<Violation>) -> ScanResult {
+        ScanResult {
+            violations,
+            files_scanned: 1,
+            generated_skipped: 0,
+            rules_loaded: 1,
+            unmatched_globs: Vec::new(),
+            groups: Vec::new(),
+            rules: Vec::new(),
+            ratchet_counts: HashMap::new(),
+            ratchet_deadlines: HashMap::new(),
+            changed_files_count: None,
+            base_ref: None,
+            timings: Default::default(),
+            scanned_files: vec![],
+            file_hashes: HashMap::new(),
+            fixes: None,
+            output: Default::default(),
+            hooks: Default::default(),
+            stopped_early: false,
+        }
+    }
+
+    #[test]
+    fn fingerprint_depends_on_identity_fields() {
+        let a = violation("r", "src/a.ts", 3);
+        assert_eq!(a.fingerprint(), violation("r", "src/a.ts", 3).fingerprint());
+        assert_eq!(a.fingerprint().len(), 16);
+        assert_ne!(a.fingerprint(), violation("r", "src/a.ts", 4).fingerprint());
+        assert_ne!(a.fingerprint(), violation("s", "src/a.ts", 3).fingerprint());
+
+        let mut error = a.clone();
+        error.severity = Severity::Error;
+        assert_ne!(a.fingerprint(), error.fingerprint());
+
+        let mut suggested = a.clone();
+        suggested.suggest = vec![crate::rules::Suggestion::new("do x")];
+        assert_eq!(a.fingerprint(), suggested.fingerprint());
+    }
+
+    #[test]
+    fn json_result_round_trips() {
+        let original = result(vec![violation("r", "src/a.ts", 3), violation("s", "b.ts", 1)]);
+        let mut out = Vec::new();
+        crate::cli::format::write_json(&original, &mut out);
+
+        let parsed = parse_result(std::str::from_utf8(&out).unwrap()).unwrap();
+        assert_eq!(parsed.len(), 2);
+        assert!(compare(&parsed, &original).is_match());
+    }
+
+    #[test]
+    fn compare_reports_missing_and_unexpected() {
+        let expected = vec![
+            violation("r", "a.ts", 1),
+            violation("r", "a.ts", 1),
+            violation("r", "a.ts", 2),
+        ];
+        let actual = result(vec![violation("r", "a.ts", 1), violation("r", "a.ts", 9)]);
+
+        let report = compare(&expected, &actual);
+        assert!(!report.is_match());
+        assert_eq!(report.matched, 1);
+        let missing: Vec<_> = report.missing.iter().map(|v| v.line).collect();
+        assert_eq!(missing, [Some(1), Some(2)]);
+        assert_eq!(report.unexpected.len(), 1);
+        assert_eq!(report.unexpected[0].line, Some(9));
+    }
+
+    #[test]
+    fn parse_rejects_non_results() {
+        assert!(matches!(parse_result("{}"), Err(VerifyError::Parse(_))));
+        assert!(matches!(parse_result("not json"), Err(VerifyError::Parse(_))));
+        let bad = r#"{ "violations": [{ "rule_id": "r", "severity": "fatal" }] }"#;
+        let err = parse_result(bad).unwrap_err();
+        assert!(err.to_string().contains("violation 0 is malformed"));
+    }
+}
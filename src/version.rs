@@ -0,0 +1,282 @@
+use std::cmp::Ordering;
+use std::fmt;
+
+/// The version of this binary.
+pub const CURRENT: &str = env!("CARGO_PKG_VERSION");
+
+/// A `major.minor.patch` release version. Missing components are 0, and a
+/// leading `v` and any pre-release or build suffix are ignored.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+}
+
+impl Version {
+    pub fn parse(text: &str) -> Result<Self, VersionError> {
+        Self::parse_parts(text).map(|(version, _)| version)
+    }
+
+    /// The version of this binary.
+    pub fn current() -> Self {
+        Self::parse(CURRENT).expect("CARGO_PKG_VERSION is a valid version")
+    }
+
+    /// The version and how many of its components were written, which
+    /// decides how far `^` and `~` requirements reach.
+    fn parse_parts(text: &str) -> Result<(Self, usize), VersionError> {
+        let invalid = || VersionError::InvalidVersion(text.to_string());
+        let trimmed = text.trim();
+        let trimmed = trimmed.strip_prefix('v').unwrap_or(trimmed);
+        let core = trimmed.split(['-', '+']).next().unwrap_or_default();
+        let parts = core
+            .split('.')
+            .map(|part| part.parse::<u64>().map_err(|_| invalid()))
+            .collect::<Result<Vec<_>, _>>()?;
+        if parts.is_empty() || parts.len() > 3 {
+            return Err(invalid());
+        }
+        let part = |i: usize| parts.get(i).copied().unwrap_or(0);
+        Ok((
+            Self {
+                major: part(0),
+                minor: part(1),
+                patch: part(2),
+            },
+            parts.len(),
+        ))
+    }
+}
+
+/// Lowest version an npm range allows, e.g. `^2.29.1` is 2.29.1 and
+/// `>=1.2 <2` is 1.2.0. `None` for tags, URLs and protocols (`latest`,
+/// `workspace:*`).
+pub fn range_floor(range: &str) -> Option<Version> {
+    let first = range.split("||").next()?.split_whitespace().next()?;
+    let first = first.trim_start_matches(['^', '~', '>', '=']);
+    Version::parse(&first.replace(['x', 'X', '*'], "0")).ok()
+}
+
+impl fmt::Display for Version {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Tilde,
+    Caret,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Comparator {
+    op: Op,
+    version: Version,
+    /// Components written in the requirement (`0.6` has 2).
+    parts: usize,
+}
+
+impl Comparator {
+    fn matches(&self, version: &Version) -> bool {
+        let ordering = version.cmp(&self.version);
+        match self.op {
+            Op::Exact if self.parts == 3 => ordering == Ordering::Equal,
+            Op::Greater => ordering == Ordering::Greater,
+            Op::GreaterEq => ordering != Ordering::Less,
+            Op::Less => ordering == Ordering::Less,
+            Op::LessEq => ordering != Ordering::Greater,
+            Op::Exact | Op::Tilde | Op::Caret => ordering != Ordering::Less && *version < self.upper_bound(),
+        }
+    }
+
+    /// First version a `~`, `^` or partial `=` requirement excludes: `=1.2`
+    /// and `~1.2` allow `1.2.x`, `^1.2` allows `1.x`, and `^0.6` allows
+    /// `0.6.x`.
+    fn upper_bound(&self) -> Version {
+        let Version { major, minor, patch } = self.version;
+        let bump = |major, minor, patch| Version { major, minor, patch };
+        match self.op {
+            Op::Exact | Op::Tilde if self.parts == 1 => bump(major + 1, 0, 0),
+            Op::Exact | Op::Tilde => bump(major, minor + 1, 0),
+            _ if major > 0 || self.parts == 1 => bump(major + 1, 0, 0),
+            _ if minor > 0 || self.parts == 2 => bump(0, minor + 1, 0),
+            _ => bump(0, 0, patch + 1),
+        }
+    }
+}
+
+/// A version requirement such as `>=0.6`, `>=1.2, <2` or `^1.4`: every
+/// comparator must hold. Comparators are separated by commas or, as in npm
+/// ranges, whitespace (`>=1.2 <2`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq {
+    text: String,
+    comparators: Vec<Comparator>,
+}
+
+impl VersionReq {
+    /// A requirement where a bare version means `^`, as in Cargo.
+    pub fn parse(text: &str) -> Result<Self, VersionError> {
+        Self::parse_with(text, Op::Caret)
+    }
+
+    /// A requirement where a bare version means `=`, as in npm package
+    /// ranges (`moment@2.29.4`).
+    pub fn parse_npm(text: &str) -> Result<Self, VersionError> {
+        Self::parse_with(text, Op::Exact)
+    }
+
+    fn parse_with(text: &str, bare: Op) -> Result<Self, VersionError> {
+        let invalid = || VersionError::InvalidRequirement(text.to_string());
+        // Split into comparators, keeping `>= 1.2` together
+        let mut parts: Vec<String> = Vec::new();
+        for token in text.split(',').flat_map(str::split_whitespace) {
+            match parts.last_mut() {
+                Some(last) if last.chars().all(|c| "<>=~^".contains(c)) => last.push_str(token),
+                _ => parts.push(token.to_string()),
+            }
+        }
+        if parts.is_empty() {
+            return Err(invalid());
+        }
+        let comparators = parts
+            .iter()
+            .map(|part| {
+                let (op, rest) = [
+                    (">=", Op::GreaterEq),
+                    ("<=", Op::LessEq),
+                    (">", Op::Greater),
+                    ("<", Op::Less),
+                    ("=", Op::Exact),
+                    ("~", Op::Tilde),
+                    ("^", Op::Caret),
+                ]
+                .iter()
+                .find_map(|(prefix, op)| part.strip_prefix(prefix).map(|rest| (*op, rest)))
+                .unwrap_or((bare, part));
+                let (version, parts) = Version::parse_parts(rest).map_err(|_| invalid())?;
+                Ok(Comparator { op, version, parts })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self {
+            text: text.trim().to_string(),
+            comparators,
+        })
+    }
+
+    pub fn matches(&self, version: &Version) -> bool {
+        self.comparators.iter().all(|c| c.matches(version))
+    }
+}
+
+impl fmt::Display for VersionReq {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.text)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionError {
+    InvalidVersion(String),
+    InvalidRequirement(String),
+}
+
+impl fmt::Display for VersionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VersionError::InvalidVersion(text) => write!(f, "invalid version '{}'", text),
+            VersionError::InvalidRequirement(text) => write!(
+                f,
+                "invalid version requirement '{}' (expected e.g. \">=0.6\" or \">=1.2, <2\")",
+                text
+            ),
+        }
+    }
+}
+
+impl std::error::Error for VersionError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn allows(req: &str, version: &str) -> bool {
+        VersionReq::parse(req).unwrap().matches(&Version::parse(version).unwrap())
+    }
+
+    #[test]
+    fn parses_versions() {
+        let v = |major, minor, patch| Version { major, minor, patch };
+        assert_eq!(Version::parse("1.6.0").unwrap(), v(1, 6, 0));
+        assert_eq!(Version::parse("v0.6").unwrap(), v(0, 6, 0));
+        assert_eq!(Version::parse("2.0.0-beta.1").unwrap(), v(2, 0, 0));
+        assert!(Version::parse("1.x").is_err());
+        assert!(Version::parse("1.2.3.4").is_err());
+        assert!(Version::current() >= v(1, 6, 0));
+    }
+
+    #[test]
+    fn matches_comparators() {
+        assert!(allows(">=0.6", "0.6.0"));
+        assert!(allows(">=0.6", "1.6.0"));
+        assert!(!allows(">=0.6", "0.5.9"));
+        assert!(allows(">=1.2, <2", "1.9.3"));
+        assert!(!allows(">=1.2, <2", "2.0.0"));
+        assert!(allows("=1.6", "1.6.0"));
+        assert!(allows("=1.6", "1.6.4"));
+        assert!(!allows("=1.6.0", "1.6.4"));
+        assert!(allows(">=1.2 <2", "1.9.3"));
+        assert!(allows(">= 1.2, < 2", "1.2.0"));
+        assert!(!allows(">1.6.0", "1.6.0"));
+        assert!(allows("<=1.6", "1.6.0"));
+    }
+
+    #[test]
+    fn caret_and_tilde_stay_within_compatible_releases() {
+        assert!(allows("1.4", "1.9.0"));
+        assert!(!allows("^1.4", "2.0.0"));
+        assert!(allows("^0.6", "0.6.3"));
+        assert!(!allows("^0.6", "0.7.0"));
+        assert!(!allows("^0.0.3", "0.0.4"));
+        assert!(allows("~1.2", "1.2.9"));
+        assert!(!allows("~1.2", "1.3.0"));
+        assert!(allows("~1", "1.9.0"));
+    }
+
+    #[test]
+    fn rejects_malformed_requirements() {
+        let err = VersionReq::parse(">= one").unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "invalid version requirement '>= one' (expected e.g. \">=0.6\" or \">=1.2, <2\")"
+        );
+        assert!(VersionReq::parse("").is_err());
+    }
+
+    #[test]
+    fn npm_ranges_treat_bare_versions_as_exact() {
+        let npm = |req: &str, version: &str| VersionReq::parse_npm(req).unwrap().matches(&Version::parse(version).unwrap());
+        assert!(npm("2.29.4", "2.29.4"));
+        assert!(!npm("2.29.4", "2.30.0"));
+        assert!(npm("2", "2.30.0"));
+        assert!(npm("^2.29", "2.30.0"));
+        assert!(!npm(">=1 <2", "2.0.0"));
+    }
+
+    #[test]
+    fn finds_the_floor_of_npm_ranges() {
+        let floor = |range| range_floor(range).map(|v| v.to_string());
+        assert_eq!(floor("^2.29.1").as_deref(), Some("2.29.1"));
+        assert_eq!(floor(">=1.2 <2").as_deref(), Some("1.2.0"));
+        assert_eq!(floor("1.x || 2").as_deref(), Some("1.0.0"));
+        assert_eq!(floor("latest"), None);
+        assert_eq!(floor("workspace:*"), None);
+    }
+}
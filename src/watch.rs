@@ -0,0 +1,207 @@
+use crate::rules::Violation;
+use crate::scan::{self, ScanError, ScanOptions, WalkPolicy};
+use std::collections::{BTreeSet, HashMap};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// A violation reduced to the fields that identify it across rescans.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub struct ViolationKey {
+    pub rule_id: String,
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl From<&Violation> for ViolationKey {
+    fn from(v: &Violation) -> Self {
+        Self {
+            rule_id: v.rule_id.clone(),
+            line: v.line,
+            message: v.message.clone(),
+        }
+    }
+}
+
+/// Violations that appeared or disappeared in one file since it was last seen.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileChange {
+    pub file: PathBuf,
+    pub appeared: Vec<ViolationKey>,
+    pub resolved: Vec<ViolationKey>,
+}
+
+/// Polling file watcher. No platform notification API is used: each `poll`
+/// walks the workspace (respecting the config's excludes and walk policy),
+/// compares modification times, and rescans only the files that changed.
+pub struct FileWatcher {
+    config_path: PathBuf,
+    roots: Vec<PathBuf>,
+    stamps: HashMap<PathBuf, (SystemTime, u64)>,
+    known: HashMap<PathBuf, BTreeSet<ViolationKey>>,
+}
+
+impl FileWatcher {
+    /// Create a watcher and record the current state of every file under
+    /// `roots`. Files that exist now are not reported until they change.
+    pub fn new(config_path: &Path, roots: &[PathBuf]) -> Result<Self, ScanError> {
+        let mut watcher = Self {
+            config_path: config_path.to_path_buf(),
+            roots: roots.to_vec(),
+            stamps: HashMap::new(),
+            known: HashMap::new(),
+        };
+        watcher.stamps = watcher.snapshot()?;
+        Ok(watcher)
+    }
+
+    fn snapshot(&self) -> Result<HashMap<PathBuf, (SystemTime, u64)>, ScanError> {
+        let loaded = scan::load_config(&self.config_path)?;
+        let exclude_set = scan::build_glob_set(&loaded.toml.baseline.excludes())?;
+        let policy = WalkPolicy::from_section(&loaded.toml.baseline, &self.config_path);
+
+        Ok(scan::collect_files(&self.roots, &exclude_set, &policy)
+            .into_iter()
+            .filter_map(|path| {
+                let meta = fs::metadata(&path).ok()?;
+                Some((path, (meta.modified().ok()?, meta.len())))
+            })
+            .collect())
+    }
+
+    /// Rescan files modified (or deleted) since the last poll and return the
+    /// ones whose violations changed.
+    pub fn poll(&mut self) -> Result<Vec<FileChange>, ScanError> {
+        let current = self.snapshot()?;
+
+        let mut touched: Vec<PathBuf> = current
+            .iter()
+            .filter(|(path, stamp)| self.stamps.get(*path) != Some(stamp))
+            .map(|(path, _)| path.clone())
+            .collect();
+        touched.extend(
+            self.stamps
+                .keys()
+                .filter(|path| !current.contains_key(*path))
+                .cloned(),
+        );
+        touched.sort();
+        self.stamps = current;
+
+        let mut changes = Vec::new();
+        for file in touched {
+            let now: BTreeSet<ViolationKey> = if file.exists() {
+                let result = scan::run_scan_with_options(
+                    &self.config_path,
+                    std::slice::from_ref(&file),
+                    &ScanOptions::default(),
+                )?;
+                result.violations.iter().map(ViolationKey::from).collect()
+            } else {
+                BTreeSet::new()
+            };
+
+            let before = self.known.remove(&file).unwrap_or_default();
+            let appeared: Vec<ViolationKey> = now.difference(&before).cloned().collect();
+            let resolved: Vec<ViolationKey> = before.difference(&now).cloned().collect();
+
+            if !now.is_empty() {
+                self.known.insert(file.clone(), now);
+            }
+            if !appeared.is_empty() || !resolved.is_empty() {
+                changes.push(FileChange {
+                    file,
+                    appeared,
+                    resolved,
+                });
+            }
+        }
+
+        Ok(changes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn write_config(dir: &Path) -> PathBuf {
+        let config = dir.join("baseline.toml");
+        fs::write(
+            &config,
+            r#"
+[baseline]
+
+[[rule]]
+id = "no-console"
+type = "banned-pattern"
+severity = "error"
+glob = "**/*.ts"
+pattern = "console.log"
+message = "no console.log"
+"#,
+        )
+        .unwrap();
+        config
+    }
+
+    #[test]
+    fn poll_reports_appeared_and_resolved() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = write_config(dir.path());
+        let src = dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        let file = src.join("a.ts");
+        fs::write(&file, "ok();\n").unwrap();
+
+        let mut watcher = FileWatcher::new(&config, std::slice::from_ref(&src)).unwrap();
+        assert!(watcher.poll().unwrap().is_empty());
+
+        fs::write(&file, "ok();\nconsole.log(1);\n").unwrap();
+        let changes = watcher.poll().unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].file, file);
+        assert_eq!(changes[0].appeared.len(), 1);
+        assert_eq!(changes[0].appeared[0].rule_id, "no-console");
+        assert_eq!(changes[0].appeared[0].line, Some(2));
+        assert!(changes[0].resolved.is_empty());
+
+        fs::write(&file, "ok();\n").unwrap();
+        let changes = watcher.poll().unwrap();
+        assert_eq!(changes.len(), 1);
+        assert!(changes[0].appeared.is_empty());
+        assert_eq!(changes[0].resolved.len(), 1);
+    }
+
+    #[test]
+    fn poll_ignores_edits_that_keep_violations_unchanged() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = write_config(dir.path());
+        let src = dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        let file = src.join("a.ts");
+        fs::write(&file, "ok();\n").unwrap();
+
+        let mut watcher = FileWatcher::new(&config, std::slice::from_ref(&src)).unwrap();
+        fs::write(&file, "ok();\nstill_ok();\n").unwrap();
+        assert!(watcher.poll().unwrap().is_empty());
+    }
+
+    #[test]
+    fn poll_reports_deleted_file_as_resolved() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = write_config(dir.path());
+        let src = dir.path().join("src");
+        fs::create_dir(&src).unwrap();
+        let file = src.join("a.ts");
+
+        let mut watcher = FileWatcher::new(&config, std::slice::from_ref(&src)).unwrap();
+        fs::write(&file, "console.log(1);\n").unwrap();
+        assert_eq!(watcher.poll().unwrap()[0].appeared.len(), 1);
+
+        fs::remove_file(&file).unwrap();
+        let changes = watcher.poll().unwrap();
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].resolved.len(), 1);
+    }
+}